@@ -0,0 +1,259 @@
+//! Deletion units
+//!
+//! A dead class with dead members produces a finding for the class and one
+//! for each member, and a dead helper only called from another dead
+//! declaration is reported on its own too. Clustering groups such findings
+//! into deletion units: a root declaration plus every finding that becomes
+//! dead code anyway once the root is removed, so the report can show them
+//! under their root and the safe deleter can remove them together.
+//!
+//! ## Algorithm
+//!
+//! 1. A finding nested in another finding (a member of a dead class) joins
+//!    the unit of its outermost dead ancestor
+//! 2. A finding whose references all come from inside a single other unit
+//!    joins that unit. This repeats until no finding moves, so chains of
+//!    helpers end up under the declaration that calls the first of them
+//! 3. Findings referenced from several units, or from code that is not
+//!    reported, stay roots of their own units
+
+use super::size::is_deletable;
+use super::DeadCode;
+use crate::graph::{DeclarationId, Graph};
+use std::collections::{HashMap, HashSet};
+
+/// A root finding and the findings deleted along with it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletionUnit {
+    pub root: DeclarationId,
+    /// Findings that only the root keeps in the code, in report order
+    pub dependents: Vec<DeclarationId>,
+}
+
+impl DeletionUnit {
+    /// The root and its dependents
+    pub fn ids(&self) -> impl Iterator<Item = &DeclarationId> {
+        std::iter::once(&self.root).chain(&self.dependents)
+    }
+}
+
+/// Group the deletable findings into deletion units, in the report order of
+/// their roots
+pub fn cluster_findings(graph: &Graph, dead_code: &[DeadCode]) -> Vec<DeletionUnit> {
+    // A declaration with several findings is one member of its unit
+    let mut dead: HashSet<&DeclarationId> = HashSet::new();
+    let findings: Vec<&DeclarationId> = dead_code
+        .iter()
+        .filter(|dc| is_deletable(dc.issue))
+        .map(|dc| &dc.declaration.id)
+        .filter(|id| dead.insert(*id))
+        .collect();
+
+    // Which unit each finding belongs to, as a link to another finding
+    let mut owner: HashMap<&DeclarationId, &DeclarationId> = HashMap::new();
+    for &id in &findings {
+        if let Some(ancestor) = outermost_dead_ancestor(graph, id, &dead) {
+            owner.insert(id, ancestor);
+        }
+    }
+
+    loop {
+        let mut moved = false;
+        for &id in &findings {
+            if owner.contains_key(id) {
+                continue;
+            }
+            let mut units = HashSet::new();
+            let mut live = false;
+            for (from, _) in graph.get_references_to(id) {
+                match containing_finding(graph, &from.id, &dead) {
+                    Some(finding) => {
+                        let unit = root_of(&owner, finding);
+                        if unit != id {
+                            units.insert(unit);
+                        }
+                    }
+                    None => live = true,
+                }
+            }
+            if !live && units.len() == 1 {
+                let unit = units.into_iter().next().expect("one unit");
+                owner.insert(id, unit);
+                moved = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    // Findings come in report order, so roots and dependents do too
+    let mut units: Vec<DeletionUnit> = Vec::new();
+    let mut index: HashMap<&DeclarationId, usize> = HashMap::new();
+    for &id in &findings {
+        if !owner.contains_key(id) {
+            index.insert(id, units.len());
+            units.push(DeletionUnit {
+                root: id.clone(),
+                dependents: Vec::new(),
+            });
+        }
+    }
+    for &id in &findings {
+        if owner.contains_key(id) {
+            units[index[root_of(&owner, id)]]
+                .dependents
+                .push(id.clone());
+        }
+    }
+    units
+}
+
+/// Follow the owner links of a finding to the root of its unit
+fn root_of<'a>(
+    owner: &HashMap<&'a DeclarationId, &'a DeclarationId>,
+    mut id: &'a DeclarationId,
+) -> &'a DeclarationId {
+    while let Some(next) = owner.get(id) {
+        id = next;
+    }
+    id
+}
+
+/// The outermost enclosing declaration that is itself a finding
+fn outermost_dead_ancestor<'a>(
+    graph: &'a Graph,
+    id: &DeclarationId,
+    dead: &HashSet<&DeclarationId>,
+) -> Option<&'a DeclarationId> {
+    let mut outermost = None;
+    let mut current = graph.get_declaration(id)?.parent.as_ref();
+    while let Some(parent) = current {
+        let decl = graph.get_declaration(parent)?;
+        if dead.contains(&decl.id) {
+            outermost = Some(&decl.id);
+        }
+        current = decl.parent.as_ref();
+    }
+    outermost
+}
+
+/// The finding a declaration is, or is nested in
+fn containing_finding<'a>(
+    graph: &'a Graph,
+    id: &'a DeclarationId,
+    dead: &HashSet<&DeclarationId>,
+) -> Option<&'a DeclarationId> {
+    if dead.contains(id) {
+        return Some(id);
+    }
+    let mut current = graph.get_declaration(id)?.parent.as_ref();
+    while let Some(parent) = current {
+        if dead.contains(parent) {
+            return Some(parent);
+        }
+        current = graph.get_declaration(parent)?.parent.as_ref();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{
+        Declaration, DeclarationKind, Language, Location, Reference, ReferenceKind,
+    };
+    use std::path::PathBuf;
+
+    fn declare(
+        graph: &mut Graph,
+        name: &str,
+        kind: DeclarationKind,
+        line: usize,
+        parent: Option<&DeclarationId>,
+    ) -> DeclarationId {
+        let path = PathBuf::from("Feature.kt");
+        let start = line * 100;
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), start, start + 50),
+            name.to_string(),
+            kind,
+            Location::new(path, line, 1, start, start + 50),
+            Language::Kotlin,
+        );
+        decl.parent = parent.cloned();
+        graph.add_declaration(decl)
+    }
+
+    fn call(graph: &mut Graph, from: &DeclarationId, to: &DeclarationId) {
+        let location = Location::new(from.file.clone(), 1, 1, from.start, from.end);
+        graph.add_reference(
+            from,
+            to,
+            Reference::new(ReferenceKind::Call, location, String::new()),
+        );
+    }
+
+    #[test]
+    fn test_cluster_findings() {
+        let mut graph = Graph::new();
+        let class = declare(&mut graph, "LegacyScreen", DeclarationKind::Class, 1, None);
+        let render = declare(
+            &mut graph,
+            "render",
+            DeclarationKind::Method,
+            2,
+            Some(&class),
+        );
+        let helper = declare(
+            &mut graph,
+            "formatTitle",
+            DeclarationKind::Function,
+            10,
+            None,
+        );
+        let nested = declare(&mut graph, "trimTitle", DeclarationKind::Function, 11, None);
+        let shared = declare(
+            &mut graph,
+            "sharedUtil",
+            DeclarationKind::Function,
+            20,
+            None,
+        );
+        let other = declare(&mut graph, "OldDialog", DeclarationKind::Class, 30, None);
+        call(&mut graph, &render, &helper);
+        call(&mut graph, &helper, &nested);
+        call(&mut graph, &render, &shared);
+        call(&mut graph, &other, &shared);
+
+        let dead_code: Vec<DeadCode> = [&class, &render, &helper, &nested, &shared, &other]
+            .into_iter()
+            .map(|id| {
+                DeadCode::new(
+                    graph.get_declaration(id).unwrap().clone(),
+                    DeadCodeIssue::Unreferenced,
+                )
+            })
+            .collect();
+
+        let units = cluster_findings(&graph, &dead_code);
+        assert_eq!(
+            units,
+            vec![
+                DeletionUnit {
+                    root: class,
+                    dependents: vec![render, helper, nested],
+                },
+                DeletionUnit {
+                    root: shared,
+                    dependents: vec![],
+                },
+                DeletionUnit {
+                    root: other,
+                    dependents: vec![],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! Effectively dead objects
+//!
+//! An object, companion object or class can be reachable while none of its
+//! members is: it is only imported or used to qualify member names, or it is
+//! kept as a member of a reachable class. Member retention then hides its
+//! members from the report, and without retention they show up one by one.
+//! Either way the container can go as a whole, so it is reported as a single
+//! [`DeadCodeIssue::EffectivelyDeadObject`] finding that replaces the
+//! findings of its members.
+
+use super::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{
+    Declaration, DeclarationId, DeclarationKind, Graph, Reference, ReferenceKind, Visibility,
+};
+use std::collections::HashSet;
+
+/// Report reachable containers whose members are all unused, folding the
+/// findings of their members into theirs
+pub fn aggregate_dead_containers(
+    graph: &Graph,
+    entry_points: &HashSet<DeclarationId>,
+    reachable: &HashSet<DeclarationId>,
+    dead_code: Vec<DeadCode>,
+) -> Vec<DeadCode> {
+    let reported: HashSet<&DeclarationId> = dead_code.iter().map(|dc| &dc.declaration.id).collect();
+    let mut containers: Vec<(&Declaration, HashSet<DeclarationId>)> = graph
+        .declarations()
+        .filter(|decl| matches!(decl.kind, DeclarationKind::Class | DeclarationKind::Object))
+        .filter(|decl| reachable.contains(&decl.id) && !entry_points.contains(&decl.id))
+        .filter(|decl| !reported.contains(&decl.id))
+        .filter_map(|decl| {
+            let inside = descendants(graph, &decl.id);
+            is_effectively_dead(graph, entry_points, reachable, decl, &inside)
+                .then_some((decl, inside))
+        })
+        .collect();
+
+    // A dead container nested in another is part of the outer one's finding
+    let nested: HashSet<DeclarationId> = containers
+        .iter()
+        .flat_map(|(_, inside)| inside.iter().cloned())
+        .collect();
+    containers.retain(|(decl, _)| !nested.contains(&decl.id));
+    if containers.is_empty() {
+        return dead_code;
+    }
+    containers.sort_by(|a, b| {
+        let a = &a.0.location;
+        let b = &b.0.location;
+        a.file.cmp(&b.file).then(a.start_byte.cmp(&b.start_byte))
+    });
+
+    let folded: HashSet<&DeclarationId> = containers
+        .iter()
+        .flat_map(|(_, inside)| inside.iter())
+        .collect();
+    let mut findings: Vec<DeadCode> = Vec::new();
+    let mut kept: Vec<DeadCode> = Vec::new();
+    for dc in dead_code {
+        if folded.contains(&dc.declaration.id) && dc.issue == DeadCodeIssue::Unreferenced {
+            findings.push(dc);
+        } else {
+            kept.push(dc);
+        }
+    }
+
+    for (decl, inside) in &containers {
+        let members: Vec<&DeadCode> = findings
+            .iter()
+            .filter(|dc| inside.contains(&dc.declaration.id))
+            .collect();
+        kept.push(dead_container(graph, decl, inside, &members));
+    }
+    kept
+}
+
+/// Ids of everything declared inside a declaration, at any depth
+fn descendants(graph: &Graph, id: &DeclarationId) -> HashSet<DeclarationId> {
+    let mut found = HashSet::new();
+    let mut stack = vec![id];
+    while let Some(id) = stack.pop() {
+        for child in graph.get_children(id) {
+            if found.insert(child.clone()) {
+                stack.push(child);
+            }
+        }
+    }
+    found
+}
+
+/// Members that make up a container's code (constructors and parameters
+/// come with the container)
+fn is_member(graph: &Graph, id: &DeclarationId) -> bool {
+    graph.get_declaration(id).is_some_and(|decl| {
+        !matches!(
+            decl.kind,
+            DeclarationKind::Constructor | DeclarationKind::Parameter
+        )
+    })
+}
+
+/// Whether no member is used from live code outside the container, and the
+/// container itself is only imported or used as a qualifier
+fn is_effectively_dead(
+    graph: &Graph,
+    entry_points: &HashSet<DeclarationId>,
+    reachable: &HashSet<DeclarationId>,
+    container: &Declaration,
+    inside: &HashSet<DeclarationId>,
+) -> bool {
+    if !inside.iter().any(|id| is_member(graph, id)) {
+        return false;
+    }
+    // References from dead code or from the container's own members do not
+    // keep anything alive
+    let live_source = |source: &Declaration| {
+        source.id != container.id && !inside.contains(&source.id) && reachable.contains(&source.id)
+    };
+
+    let members_unused = inside.iter().all(|id| {
+        !entry_points.contains(id)
+            && !graph
+                .get_references_to(id)
+                .iter()
+                .any(|(source, _)| live_source(source))
+    });
+    members_unused
+        && graph
+            .get_references_to(&container.id)
+            .iter()
+            .filter(|(source, _)| live_source(source))
+            .all(|(source, reference)| is_namespace_use(graph, source, reference))
+}
+
+/// Whether a reference only names the container: an import, or a qualifier
+/// directly followed by a member name (`Legacy.format`, `Legacy?.format`)
+fn is_namespace_use(graph: &Graph, source: &Declaration, reference: &Reference) -> bool {
+    match reference.kind {
+        ReferenceKind::Import => true,
+        ReferenceKind::Read | ReferenceKind::Type => {
+            let end = reference.location.end_byte;
+            graph
+                .get_references_from(&source.id)
+                .iter()
+                .any(|(_, next)| {
+                    next.location.file == reference.location.file
+                        && next.location.start_byte > end
+                        && next.location.start_byte <= end + 2
+                })
+        }
+        _ => false,
+    }
+}
+
+/// The finding for a dead container, as confident as its least confident
+/// member finding; public containers may be used by modules that were not
+/// analyzed
+fn dead_container(
+    graph: &Graph,
+    decl: &Declaration,
+    inside: &HashSet<DeclarationId>,
+    members: &[&DeadCode],
+) -> DeadCode {
+    let count = inside.iter().filter(|id| is_member(graph, id)).count();
+    let owner = decl
+        .parent
+        .as_ref()
+        .and_then(|parent| graph.get_declaration(parent))
+        .filter(|_| decl.modifiers.iter().any(|m| m == "companion"));
+    let subject = match owner {
+        Some(owner) => format!("Companion object of '{}'", owner.name),
+        None => format!("{} '{}'", decl.kind.display_name(), decl.name),
+    };
+    let confidence = members
+        .iter()
+        .min_by(|a, b| a.confidence_score().total_cmp(&b.confidence_score()))
+        .map(|weakest| weakest.confidence)
+        .unwrap_or(if decl.visibility == Visibility::Public {
+            Confidence::Medium
+        } else {
+            Confidence::High
+        });
+
+    DeadCode::new(decl.clone(), DeadCodeIssue::EffectivelyDeadObject)
+        .with_message(format!(
+            "{} is only used as a namespace, and none of its {} members is used",
+            subject, count
+        ))
+        .with_confidence(confidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Language, Location};
+    use std::path::PathBuf;
+
+    fn declaration(
+        name: &str,
+        kind: DeclarationKind,
+        start: usize,
+        parent: Option<&Declaration>,
+    ) -> Declaration {
+        let path = PathBuf::from("Legacy.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), start, start + 10),
+            name.to_string(),
+            kind,
+            Location::new(path, start / 10 + 1, 1, start, start + 10),
+            Language::Kotlin,
+        );
+        decl.parent = parent.map(|p| p.id.clone());
+        decl
+    }
+
+    fn add(
+        graph: &mut Graph,
+        name: &str,
+        kind: DeclarationKind,
+        start: usize,
+        parent: Option<&Declaration>,
+    ) -> Declaration {
+        let decl = declaration(name, kind, start, parent);
+        graph.add_declaration(decl.clone());
+        decl
+    }
+
+    fn reference(graph: &mut Graph, from: &Declaration, to: &Declaration, start: usize) {
+        let location = Location::new(from.location.file.clone(), 1, 1, start, start + 6);
+        graph.add_reference(
+            &from.id,
+            &to.id,
+            Reference::new(ReferenceKind::Read, location, to.name.clone()),
+        );
+    }
+
+    #[test]
+    fn test_aggregate_dead_containers() {
+        use DeclarationKind::{Class, Function, Object};
+
+        let mut graph = Graph::new();
+        let main = add(&mut graph, "main", Function, 0, None);
+        let helper = add(&mut graph, "helper", Function, 50, None);
+        let legacy = add(&mut graph, "Legacy", Object, 100, None);
+        let format = add(&mut graph, "format", Function, 110, Some(&legacy));
+        let parse = add(&mut graph, "parse", Function, 120, Some(&legacy));
+        let screen = add(&mut graph, "Screen", Class, 200, None);
+        let show = add(&mut graph, "show", Function, 210, Some(&screen));
+        let mut companion = declaration("Companion", Object, 220, Some(&screen));
+        companion.modifiers.push("companion".to_string());
+        graph.add_declaration(companion.clone());
+        let create = add(&mut graph, "create", Function, 230, Some(&companion));
+        let used = add(&mut graph, "Used", Object, 300, None);
+        let run = add(&mut graph, "run", Function, 310, Some(&used));
+
+        // `Legacy` only qualifies an extension, and its members only use
+        // each other; the companion object is never referenced at all
+        reference(&mut graph, &main, &legacy, 1);
+        reference(&mut graph, &main, &helper, 8);
+        reference(&mut graph, &format, &parse, 111);
+        reference(&mut graph, &main, &screen, 20);
+        reference(&mut graph, &main, &show, 30);
+        reference(&mut graph, &create, &screen, 231);
+        reference(&mut graph, &main, &used, 40);
+        reference(&mut graph, &main, &run, 47);
+
+        let entry_points: HashSet<DeclarationId> = [main.id.clone()].into_iter().collect();
+        let reachable: HashSet<DeclarationId> = graph.declaration_ids().cloned().collect();
+        let dead_code = vec![
+            DeadCode::new(parse, DeadCodeIssue::Unreferenced).with_confidence(Confidence::Low),
+            DeadCode::new(create, DeadCodeIssue::Unreferenced),
+        ];
+
+        let result = aggregate_dead_containers(&graph, &entry_points, &reachable, dead_code);
+        let found: Vec<(&str, DeadCodeIssue)> = result
+            .iter()
+            .map(|dc| (dc.declaration.name.as_str(), dc.issue))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Legacy", DeadCodeIssue::EffectivelyDeadObject),
+                ("Companion", DeadCodeIssue::EffectivelyDeadObject),
+            ]
+        );
+        assert_eq!(result[0].confidence, Confidence::Low);
+        assert_eq!(
+            result[0].message,
+            "object 'Legacy' is only used as a namespace, and none of its 2 members is used"
+        );
+        assert_eq!(
+            result[1].message,
+            "Companion object of 'Screen' is only used as a namespace, and none of its 1 members is used"
+        );
+    }
+}
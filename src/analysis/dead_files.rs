@@ -0,0 +1,177 @@
+//! Whole-file dead code
+//!
+//! When every top-level declaration of a file is reported as unreferenced,
+//! the file's findings are replaced by a single [`DeadCodeIssue::DeadFile`]
+//! finding, so the report and the safe deleter deal with the file as a whole
+//! instead of dozens of separate declarations. The aggregation is opt-in
+//! (`--dead-files`), since the file finding replaces each declaration's own
+//! message and evidence.
+
+use super::{DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph, Location};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Replace the findings of entirely unused files with one finding per file
+pub fn aggregate_dead_files(graph: &Graph, dead_code: Vec<DeadCode>) -> Vec<DeadCode> {
+    let unreferenced: HashSet<&DeclarationId> = dead_code
+        .iter()
+        .filter(|dc| dc.issue == DeadCodeIssue::Unreferenced)
+        .map(|dc| &dc.declaration.id)
+        .collect();
+
+    let mut top_level: HashMap<&PathBuf, Vec<&Declaration>> = HashMap::new();
+    // Scripts run as a whole, even when none of their declarations is used
+    let mut scripts: HashSet<&PathBuf> = HashSet::new();
+    for decl in graph.declarations() {
+        if decl.is_script() {
+            scripts.insert(&decl.location.file);
+        } else if decl.parent.is_none() && is_code(decl.kind) {
+            top_level.entry(&decl.location.file).or_default().push(decl);
+        }
+    }
+
+    let dead_files: HashSet<PathBuf> = top_level
+        .into_iter()
+        .filter(|(file, _)| !scripts.contains(file))
+        .filter(|(_, decls)| decls.iter().all(|d| unreferenced.contains(&d.id)))
+        .map(|(file, _)| file.clone())
+        .collect();
+    if dead_files.is_empty() {
+        return dead_code;
+    }
+
+    let (in_dead_files, mut kept): (Vec<DeadCode>, Vec<DeadCode>) = dead_code
+        .into_iter()
+        .partition(|dc| dead_files.contains(&dc.declaration.location.file));
+
+    let mut by_file: HashMap<PathBuf, Vec<DeadCode>> = HashMap::new();
+    for dc in in_dead_files {
+        by_file
+            .entry(dc.declaration.location.file.clone())
+            .or_default()
+            .push(dc);
+    }
+
+    let mut files: Vec<_> = by_file.into_iter().collect();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    kept.extend(
+        files
+            .into_iter()
+            .map(|(file, findings)| dead_file(file, &findings)),
+    );
+    kept
+}
+
+/// Declarations that make up a file's code (imports and packages don't)
+fn is_code(kind: DeclarationKind) -> bool {
+    !matches!(
+        kind,
+        DeclarationKind::Import | DeclarationKind::Package | DeclarationKind::File
+    )
+}
+
+/// The finding for a dead file, as confident as its least confident finding
+/// and with that finding's evidence
+fn dead_file(file: PathBuf, findings: &[DeadCode]) -> DeadCode {
+    let start = findings
+        .iter()
+        .map(|dc| dc.declaration.id.start)
+        .min()
+        .unwrap_or(0);
+    let end = findings
+        .iter()
+        .map(|dc| dc.declaration.id.end)
+        .max()
+        .unwrap_or(0);
+    let name = file
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let language = findings[0].declaration.language;
+    let weakest = findings
+        .iter()
+        .min_by(|a, b| a.confidence_score().total_cmp(&b.confidence_score()))
+        .expect("dead files have findings");
+    let declarations = findings
+        .iter()
+        .filter(|dc| dc.declaration.parent.is_none() && is_code(dc.declaration.kind))
+        .count();
+
+    let decl = Declaration::new(
+        DeclarationId::new(file.clone(), start, end),
+        name.clone(),
+        DeclarationKind::File,
+        Location::new(file, 1, 1, start, end),
+        language,
+    );
+    let mut dead_file = DeadCode::new(decl, DeadCodeIssue::DeadFile)
+        .with_message(format!(
+            "File '{}' is entirely unused ({} top-level declarations)",
+            name, declarations
+        ))
+        .with_confidence(weakest.confidence);
+    dead_file.evidence = weakest.evidence.clone();
+    dead_file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::Confidence;
+    use crate::graph::Language;
+
+    fn add(graph: &mut Graph, file: &str, name: &str, start: usize) -> Declaration {
+        let path = PathBuf::from(file);
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), start, start + 10),
+            name.to_string(),
+            DeclarationKind::Class,
+            Location::new(path, start / 10 + 1, 1, start, start + 10),
+            Language::Kotlin,
+        );
+        graph.add_declaration(decl.clone());
+        decl
+    }
+
+    #[test]
+    fn test_aggregate_dead_files() {
+        let mut graph = Graph::new();
+        let legacy_a = add(&mut graph, "Legacy.kt", "LegacyA", 0);
+        let legacy_b = add(&mut graph, "Legacy.kt", "LegacyB", 20);
+        let mut member = legacy_b.clone();
+        member.id = DeclarationId::new(PathBuf::from("Legacy.kt"), 22, 28);
+        member.name = "run".to_string();
+        member.kind = DeclarationKind::Method;
+        member.parent = Some(legacy_b.id.clone());
+        graph.add_declaration(member.clone());
+        let mut import = legacy_a.clone();
+        import.id = DeclarationId::new(PathBuf::from("Legacy.kt"), 0, 5);
+        import.name = "java.io.File".to_string();
+        import.kind = DeclarationKind::Import;
+        let unused = add(&mut graph, "Used.kt", "Unused", 0);
+        add(&mut graph, "Used.kt", "Used", 20);
+
+        let dead_code = vec![
+            DeadCode::new(legacy_a, DeadCodeIssue::Unreferenced),
+            DeadCode::new(legacy_b, DeadCodeIssue::Unreferenced).with_confidence(Confidence::Low),
+            DeadCode::new(member, DeadCodeIssue::Unreferenced),
+            DeadCode::new(import, DeadCodeIssue::UnusedImport),
+            DeadCode::new(unused, DeadCodeIssue::Unreferenced),
+        ];
+
+        let result = aggregate_dead_files(&graph, dead_code);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].declaration.name, "Unused");
+
+        let file = &result[1];
+        assert_eq!(file.issue, DeadCodeIssue::DeadFile);
+        assert_eq!(file.declaration.name, "Legacy.kt");
+        assert_eq!(file.declaration.kind, DeclarationKind::File);
+        assert_eq!(file.confidence, Confidence::Low);
+        assert_eq!(
+            file.message,
+            "File 'Legacy.kt' is entirely unused (2 top-level declarations)"
+        );
+    }
+}
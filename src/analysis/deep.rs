@@ -5,8 +5,14 @@
 // 2. Tracks actual references to each member individually
 // 3. Detects unused members even in reachable classes
 // 4. Uses heuristics for common dead code patterns
+//
+// Members overriding a super type member that is used polymorphically (or a
+// library member the framework calls) cannot be deleted on their own. They
+// are reported as such, or kept alive with `with_respect_overrides`.
 
-use super::{Confidence, DeadCode, DeadCodeIssue};
+use super::hierarchy::ClassHierarchy;
+use super::{Confidence, DeadCode, DeadCodeIssue, MemberRetention};
+use crate::config::RetainMembers;
 use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph, Language, ReferenceKind};
 use petgraph::visit::Dfs;
 use rayon::prelude::*;
@@ -20,6 +26,11 @@ pub struct DeepAnalyzer {
     detect_unused_members: bool,
     /// Use parallel processing
     parallel: bool,
+    /// Which members of reachable classes are kept without a reference
+    retention: MemberRetention,
+    /// Keep overrides bound by a used contract alive instead of reporting
+    /// them as not deletable on their own
+    respect_overrides: bool,
 }
 
 impl DeepAnalyzer {
@@ -27,6 +38,8 @@ impl DeepAnalyzer {
         Self {
             detect_unused_members: true,
             parallel: true,
+            retention: MemberRetention::new(RetainMembers::None),
+            respect_overrides: false,
         }
     }
 
@@ -40,6 +53,20 @@ impl DeepAnalyzer {
         self
     }
 
+    /// Keep members of reachable classes by policy instead of only by
+    /// reference (by default none are kept)
+    pub fn with_member_retention(mut self, retention: MemberRetention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Treat overrides of used super type members as alive, along with
+    /// everything they reference
+    pub fn with_respect_overrides(mut self, respect: bool) -> Self {
+        self.respect_overrides = respect;
+        self
+    }
+
     /// Analyze the graph and find dead code
     pub fn analyze(
         &self,
@@ -49,7 +76,11 @@ impl DeepAnalyzer {
         info!("Running deep analysis...");
 
         // Step 1: Find truly reachable declarations (not all class members)
-        let reachable = self.find_reachable_strict(graph, entry_points);
+        let mut reachable = self.find_reachable_strict(graph, entry_points);
+        let hierarchy = ClassHierarchy::build(graph, entry_points);
+        if self.respect_overrides {
+            self.keep_contract_overrides(graph, &hierarchy, &mut reachable);
+        }
 
         info!(
             "Deep reachability: {} strictly reachable, {} total",
@@ -62,7 +93,7 @@ impl DeepAnalyzer {
 
         // Step 3: Find unused members in reachable classes
         if self.detect_unused_members {
-            let unused_members = self.find_unused_members(graph, &reachable);
+            let unused_members = self.find_unused_members(graph, &hierarchy, &reachable);
             info!(
                 "Found {} unused members in reachable classes",
                 unused_members.len()
@@ -94,6 +125,21 @@ impl DeepAnalyzer {
         let mut seen = HashSet::new();
         dead_code.retain(|dc| seen.insert(dc.declaration.id.clone()));
 
+        // Step 5: Flag overrides that a used contract still needs
+        for dc in &mut dead_code {
+            if let Some(contract) =
+                Self::contract_of(graph, &hierarchy, &dc.declaration, &reachable)
+            {
+                dc.confidence = Confidence::Low;
+                dc.message = format!(
+                    "{} '{}' overrides {}, called through its super type; cannot delete independently",
+                    dc.declaration.kind.display_name(),
+                    dc.declaration.name,
+                    contract
+                );
+            }
+        }
+
         info!("Deep analysis found {} dead code items", dead_code.len());
 
         (dead_code, reachable)
@@ -167,6 +213,12 @@ impl DeepAnalyzer {
             // Check if this is an override method in a reachable class
             if let Some(parent_id) = &decl.parent {
                 if all_reachable.contains(parent_id) {
+                    // Members the retention policy keeps
+                    if self.retention.retains(decl, graph) {
+                        additional.insert(decl.id.clone());
+                        continue;
+                    }
+
                     // Override methods are reachable via polymorphism
                     if decl.modifiers.iter().any(|m| m == "override")
                         || decl.annotations.iter().any(|a| a.contains("Override"))
@@ -248,6 +300,76 @@ impl DeepAnalyzer {
         all_reachable
     }
 
+    /// Mark overrides of used super type members in reachable classes as
+    /// reachable, then what they reference, until nothing changes
+    fn keep_contract_overrides(
+        &self,
+        graph: &Graph,
+        hierarchy: &ClassHierarchy,
+        reachable: &mut HashSet<DeclarationId>,
+    ) {
+        let inner_graph = graph.inner();
+        loop {
+            let kept: Vec<DeclarationId> = graph
+                .declarations()
+                .filter(|decl| !reachable.contains(&decl.id))
+                .filter(|decl| Self::contract_of(graph, hierarchy, decl, reachable).is_some())
+                .map(|decl| decl.id.clone())
+                .collect();
+            if kept.is_empty() {
+                return;
+            }
+            for id in kept {
+                reachable.insert(id.clone());
+                if let Some(start_idx) = graph.node_index(&id) {
+                    let mut dfs = Dfs::new(inner_graph, start_idx);
+                    while let Some(node_idx) = dfs.next(inner_graph) {
+                        if let Some(node_id) = inner_graph.node_weight(node_idx) {
+                            if reachable.insert(node_id.clone()) {
+                                Self::collect_ancestors(graph, node_id, reachable);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// The contract an override of a reachable, instantiated class fulfils,
+    /// if it is in use: a reachable super type member it overrides, or a
+    /// library member
+    fn contract_of(
+        graph: &Graph,
+        hierarchy: &ClassHierarchy,
+        decl: &Declaration,
+        reachable: &HashSet<DeclarationId>,
+    ) -> Option<String> {
+        if !hierarchy.is_override(&decl.id) {
+            return None;
+        }
+        let owner = decl.parent.as_ref()?;
+        if !reachable.contains(owner) || !hierarchy.is_instantiated(owner, reachable) {
+            return None;
+        }
+        if hierarchy.overrides_library_member(&decl.id) {
+            return Some("a library member".to_string());
+        }
+        hierarchy
+            .overridden(&decl.id)
+            .iter()
+            .find(|member| reachable.contains(*member))
+            .and_then(|member| graph.get_declaration(member))
+            .map(|member| {
+                let super_type = member
+                    .parent
+                    .as_ref()
+                    .and_then(|p| graph.get_declaration(p))
+                    .map(|p| format!("{}.", p.name))
+                    .unwrap_or_default();
+                format!("'{}{}'", super_type, member.name)
+            })
+    }
+
     /// Check if a class is actually instantiated (has Call references)
     fn is_class_instantiated(&self, graph: &Graph, class_id: &DeclarationId) -> bool {
         let refs = graph.get_references_to(class_id);
@@ -353,6 +475,7 @@ impl DeepAnalyzer {
     fn find_unused_members(
         &self,
         graph: &Graph,
+        hierarchy: &ClassHierarchy,
         reachable: &HashSet<DeclarationId>,
     ) -> Vec<DeadCode> {
         let mut unused = Vec::new();
@@ -383,9 +506,10 @@ impl DeepAnalyzer {
                 continue;
             }
 
-            // Skip override methods
+            // Skip override methods, marked or found through the hierarchy
             if decl.modifiers.iter().any(|m| m == "override")
                 || decl.annotations.iter().any(|a| a.contains("Override"))
+                || hierarchy.is_override(&decl.id)
             {
                 continue;
             }
@@ -395,6 +519,11 @@ impl DeepAnalyzer {
                 continue;
             }
 
+            // Skip members the retention policy keeps
+            if self.retention.retains(decl, graph) {
+                continue;
+            }
+
             // Skip serialization members
             if self.is_serialization_member(decl) {
                 continue;
@@ -693,16 +822,12 @@ impl DeepAnalyzer {
         decl.modifiers.iter().any(|m| m == "data")
     }
 
-    /// Check if a declaration is a sealed class
+    /// Check if a declaration is a sealed class or interface (Kotlin or Java 17+)
     fn is_sealed_class(&self, decl: &Declaration) -> bool {
         if decl.kind != DeclarationKind::Class && decl.kind != DeclarationKind::Interface {
             return false;
         }
 
-        if decl.language != Language::Kotlin {
-            return false;
-        }
-
         decl.modifiers.iter().any(|m| m == "sealed")
     }
 
@@ -949,4 +1074,116 @@ mod tests {
         let (dead_code, _) = analyzer.analyze(&graph, &entry_points);
         assert!(dead_code.is_empty());
     }
+
+    #[test]
+    fn test_member_retention() {
+        use crate::graph::Location;
+        use std::path::PathBuf;
+
+        let make = |name: &str, kind: DeclarationKind, start: usize| {
+            let file = PathBuf::from("SettingsActivity.kt");
+            Declaration::new(
+                DeclarationId::new(file.clone(), start, start + 10),
+                name.to_string(),
+                kind,
+                Location::new(file, start / 10 + 1, 1, start, start + 10),
+                Language::Kotlin,
+            )
+        };
+
+        let mut graph = Graph::new();
+        let activity = graph.add_declaration(make("SettingsActivity", DeclarationKind::Class, 0));
+        let mut bind = make("bindPreferences", DeclarationKind::Method, 10);
+        bind.parent = Some(activity.clone());
+        bind.annotations.push("@JvmStatic".to_string());
+        graph.add_declaration(bind);
+        let mut reset = make("reset", DeclarationKind::Method, 20);
+        reset.parent = Some(activity.clone());
+        graph.add_declaration(reset);
+        let entry_points: HashSet<_> = [activity.clone()].into_iter().collect();
+
+        let dead_names = |analyzer: DeepAnalyzer| {
+            let (dead_code, _) = analyzer.with_parallel(false).analyze(&graph, &entry_points);
+            let mut names: Vec<_> = dead_code
+                .into_iter()
+                .map(|dc| dc.declaration.name)
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(
+            dead_names(DeepAnalyzer::new()),
+            vec!["bindPreferences", "reset"]
+        );
+        let annotated_only = MemberRetention::default()
+            .with_override(activity, RetainMembers::AnnotatedOnly)
+            .with_default(RetainMembers::None);
+        assert_eq!(
+            dead_names(DeepAnalyzer::new().with_member_retention(annotated_only)),
+            vec!["reset"]
+        );
+    }
+
+    #[test]
+    fn test_respect_overrides() {
+        use crate::graph::{Location, Reference};
+        use std::path::PathBuf;
+
+        let file = PathBuf::from("Shapes.java");
+        let mut graph = Graph::new();
+        let mut declare = |name: &str, kind, line: usize, parent: Option<&DeclarationId>| {
+            let start = line * 100;
+            let mut decl = Declaration::new(
+                DeclarationId::new(file.clone(), start, start + 50),
+                name.to_string(),
+                kind,
+                Location::new(file.clone(), line, 1, start, start + 50),
+                Language::Java,
+            );
+            decl.parent = parent.cloned();
+            if name == "Circle" {
+                decl.super_types.push("Shape".to_string());
+            }
+            graph.add_declaration(decl)
+        };
+        let main = declare("main", DeclarationKind::Method, 1, None);
+        let shape = declare("Shape", DeclarationKind::Interface, 5, None);
+        let shape_area = declare("area", DeclarationKind::Method, 6, Some(&shape));
+        let circle = declare("Circle", DeclarationKind::Class, 10, None);
+        let circle_area = declare("area", DeclarationKind::Method, 11, Some(&circle));
+        let square = declare("square", DeclarationKind::Method, 12, Some(&circle));
+        let call = |line| {
+            Reference::new(
+                ReferenceKind::Call,
+                Location::new(file.clone(), line, 1, 0, 0),
+                String::new(),
+            )
+        };
+        graph.add_reference(&main, &circle, call(2));
+        graph.add_reference(&main, &shape_area, call(3));
+        graph.add_reference(&circle_area, &square, call(11));
+        let entry_points: HashSet<_> = [main].into_iter().collect();
+
+        let (dead_code, _) = DeepAnalyzer::new()
+            .with_parallel(false)
+            .analyze(&graph, &entry_points);
+        let area = dead_code
+            .iter()
+            .find(|dc| dc.declaration.id == circle_area)
+            .unwrap();
+        assert_eq!(area.confidence, Confidence::Low);
+        assert!(area.message.contains("'Shape.area'"));
+        assert!(area.message.contains("cannot delete independently"));
+        assert!(dead_code.iter().any(|dc| dc.declaration.id == square));
+
+        let (dead_code, reachable) = DeepAnalyzer::new()
+            .with_parallel(false)
+            .with_respect_overrides(true)
+            .analyze(&graph, &entry_points);
+        assert!(reachable.contains(&circle_area));
+        assert!(!dead_code
+            .iter()
+            .any(|dc| dc.declaration.id == circle_area || dc.declaration.id == square));
+    }
 }
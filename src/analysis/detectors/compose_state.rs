@@ -0,0 +1,201 @@
+//! Compose State Detector
+//!
+//! Detects dead code specific to Jetpack Compose:
+//!
+//! - `remember { mutableStateOf(...) }` locals that are assigned but whose
+//!   value is never read, so the writes only trigger useless recompositions
+//! - parameters of `@Composable` functions whose default value no call site
+//!   ever overrides, so the parameter can become a local
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Scan every Kotlin source for composables (see [`crate::parser::scan_compose`]):
+//!    their state locals with the reads and writes of each, their defaulted
+//!    parameters, and the arguments of every call and function reference
+//! 2. Report state locals with writes but no reads. Passing the state object
+//!    anywhere counts as a read
+//! 3. Match calls to composables by name; a default is overridden by a call
+//!    that names the parameter, passes enough positional arguments to reach
+//!    it, or fills it with a trailing lambda. Function references pass every
+//!    argument. Calls of private composables only count in their own file
+//! 4. Report defaults of called composables that no call overrides
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! @Composable
+//! fun Counter(label: String, step: Int = 1) {   // step is always 1
+//!     var clicks by remember { mutableStateOf(0) }  // DEAD: never read
+//!     Button(onClick = { clicks++ }) { Text(label) }
+//! }
+//!
+//! Counter("Taps")
+//! ```
+
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Visibility};
+use crate::parser::{scan_compose, CallArguments, ComposeFacts, DefaultedParameter};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Detector for unread Compose state and composable defaults never overridden
+pub struct ComposeStateDetector;
+
+impl ComposeStateDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Find unread state and unused defaults in Kotlin sources (path and contents)
+    pub fn analyze(&self, sources: &[(PathBuf, String)]) -> Vec<DeadCode> {
+        let facts: Vec<(PathBuf, ComposeFacts)> = sources
+            .iter()
+            .filter_map(|(path, source)| match scan_compose(path, source) {
+                Ok(facts) => Some((path.clone(), facts)),
+                Err(e) => {
+                    debug!("Failed to scan {} for composables: {}", path.display(), e);
+                    None
+                }
+            })
+            .collect();
+
+        let mut calls: HashMap<&str, Vec<(&PathBuf, &CallArguments)>> = HashMap::new();
+        for (path, file) in &facts {
+            for call in &file.calls {
+                calls
+                    .entry(call.callee.as_str())
+                    .or_default()
+                    .push((path, call));
+            }
+        }
+
+        let mut dead_code = Vec::new();
+        for (_, file) in &facts {
+            for state in file.states.iter().filter(|s| s.writes > 0 && s.reads == 0) {
+                let mut decl = Declaration::new(
+                    DeclarationId::new(
+                        state.location.file.clone(),
+                        state.location.start_byte,
+                        state.location.end_byte,
+                    ),
+                    state.name.clone(),
+                    DeclarationKind::Property,
+                    state.location.clone(),
+                    Language::Kotlin,
+                );
+                decl.visibility = Visibility::Private;
+                decl.parent = Some(state.function.clone());
+                let message = format!(
+                    "Compose state '{}' is written {} time(s) but its value is never read",
+                    state.name, state.writes
+                );
+                dead_code.push(
+                    DeadCode::new(decl, DeadCodeIssue::UnusedComposeState)
+                        .with_message(message)
+                        .with_confidence(Confidence::High),
+                );
+            }
+
+            for default in &file.defaults {
+                let callers: Vec<&CallArguments> = calls
+                    .get(default.function.as_str())
+                    .into_iter()
+                    .flatten()
+                    .filter(|(path, _)| !default.private || **path == default.location.file)
+                    .map(|(_, call)| *call)
+                    .collect();
+                // Composables that are never called are reported as unreferenced
+                if callers.is_empty() || callers.iter().any(|call| overrides(call, default)) {
+                    continue;
+                }
+
+                let mut decl = Declaration::new(
+                    DeclarationId::new(
+                        default.location.file.clone(),
+                        default.location.start_byte,
+                        default.location.end_byte,
+                    ),
+                    default.name.clone(),
+                    DeclarationKind::Parameter,
+                    default.location.clone(),
+                    Language::Kotlin,
+                );
+                decl.parent = Some(default.function_id.clone());
+                let message = format!(
+                    "Default value of parameter '{}' of {} is never overridden by its {} call(s)",
+                    default.name,
+                    default.function,
+                    callers.len()
+                );
+                // Public composables may be called from modules that were not analyzed
+                let confidence = if default.private {
+                    Confidence::High
+                } else {
+                    Confidence::Medium
+                };
+                dead_code.push(
+                    DeadCode::new(decl, DeadCodeIssue::UnusedComposableDefault)
+                        .with_message(message)
+                        .with_confidence(confidence),
+                );
+            }
+        }
+        dead_code
+    }
+}
+
+impl Default for ComposeStateDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a call passes a value for a defaulted parameter
+fn overrides(call: &CallArguments, default: &DefaultedParameter) -> bool {
+    call.reference
+        || call.named.contains(&default.name)
+        || default.index < call.positional
+        || (default.last && call.trailing_lambda)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Location;
+
+    fn default(index: usize, last: bool) -> DefaultedParameter {
+        let file = PathBuf::from("Counter.kt");
+        DefaultedParameter {
+            name: "step".to_string(),
+            location: Location::new(file.clone(), 2, 30, 40, 52),
+            function: "Counter".to_string(),
+            function_id: DeclarationId::new(file, 10, 200),
+            index,
+            last,
+            private: false,
+        }
+    }
+
+    #[test]
+    fn test_overrides() {
+        let call = |positional: usize, named: &[&str], trailing_lambda: bool| CallArguments {
+            callee: "Counter".to_string(),
+            positional,
+            named: named.iter().map(|n| n.to_string()).collect(),
+            trailing_lambda,
+            reference: false,
+        };
+
+        assert!(!overrides(&call(1, &[], false), &default(1, false)));
+        assert!(overrides(&call(2, &[], false), &default(1, false)));
+        assert!(overrides(&call(1, &["step"], false), &default(1, false)));
+        assert!(!overrides(&call(1, &[], true), &default(1, false)));
+        assert!(overrides(&call(1, &[], true), &default(1, true)));
+        let reference = CallArguments {
+            reference: true,
+            ..call(0, &[], false)
+        };
+        assert!(overrides(&reference, &default(1, false)));
+    }
+}
@@ -0,0 +1,117 @@
+//! Custom Rule Detector
+//!
+//! Runs the user-defined rules of a [`RuleSet`] against the graph. Every
+//! declaration matching a rule is reported once, with the message and
+//! severity of the first rule it matches.
+//!
+//! Rules are loaded from `.searchdeadcode-rules.toml`; see
+//! [`crate::config::RuleSet`] for the rule format.
+
+use super::Detector;
+use crate::analysis::{DeadCode, DeadCodeIssue, Severity};
+use crate::config::RuleSet;
+use crate::graph::Graph;
+
+/// Detector for declarations matching user-defined rules
+pub struct CustomRuleDetector {
+    rule_set: RuleSet,
+}
+
+impl CustomRuleDetector {
+    pub fn new(rule_set: RuleSet) -> Self {
+        Self { rule_set }
+    }
+}
+
+impl Detector for CustomRuleDetector {
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        // Rule sets are validated when loaded
+        let Ok(matchers) = self.rule_set.compile() else {
+            return Vec::new();
+        };
+
+        let mut issues: Vec<DeadCode> = graph
+            .declarations()
+            .filter_map(|decl| {
+                let matcher = matchers.iter().find(|m| m.matches(decl, graph))?;
+                let rule = matcher.rule();
+                Some(
+                    DeadCode::new(decl.clone(), DeadCodeIssue::CustomRule)
+                        .with_message(rule.message_for(decl))
+                        .with_severity(rule.severity.unwrap_or(Severity::Warning)),
+                )
+            })
+            .collect();
+
+        issues.sort_by(|a, b| {
+            a.declaration
+                .location
+                .file
+                .cmp(&b.declaration.location.file)
+                .then(
+                    a.declaration
+                        .location
+                        .line
+                        .cmp(&b.declaration.location.line),
+                )
+        });
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_first_matching_rule_reports() {
+        let rule_set = RuleSet::parse(
+            r#"
+            [[rule]]
+            id = "deprecated-api"
+            annotated_with = "Deprecated"
+            severity = "error"
+
+            [[rule]]
+            id = "legacy"
+            name_regex = ".*Legacy"
+            "#,
+        )
+        .unwrap();
+
+        let mut graph = Graph::new();
+        for (i, (name, annotations)) in [
+            ("OldLegacy", vec!["@Deprecated(\"use New\")"]),
+            ("PaymentLegacy", vec![]),
+            ("Payment", vec![]),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let path = PathBuf::from("Legacy.kt");
+            let mut decl = Declaration::new(
+                DeclarationId::new(path.clone(), i * 20, i * 20 + 10),
+                name.to_string(),
+                DeclarationKind::Class,
+                Location::new(path, i + 1, 1, i * 20, i * 20 + 10),
+                Language::Kotlin,
+            );
+            decl.annotations = annotations.into_iter().map(String::from).collect();
+            graph.add_declaration(decl);
+        }
+
+        let issues = CustomRuleDetector::new(rule_set).detect(&graph);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].declaration.name, "OldLegacy");
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(
+            issues[0].message,
+            "class 'OldLegacy' matches rule 'deprecated-api'"
+        );
+        assert_eq!(issues[1].declaration.name, "PaymentLegacy");
+        assert_eq!(issues[1].issue, DeadCodeIssue::CustomRule);
+    }
+}
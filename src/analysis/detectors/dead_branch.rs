@@ -1,20 +1,1021 @@
-use super::Detector;
-use crate::analysis::DeadCode;
-use crate::graph::Graph;
+//! Dead Branch Detector
+//!
+//! Detects `if` branches that can never run because their condition is
+//! constant for the analyzed variant.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Collect compile-time constants from all sources: Kotlin `const val` and
+//!    Java `static final` fields initialized from literals or other constants
+//! 2. Add the `BuildConfig` values of the analyzed variant (`android.build_config`,
+//!    a release build with `DEBUG = false` by default)
+//! 3. Walk each file, propagating local `val` / `final` values initialized from
+//!    constants through the block that declares them
+//! 4. Evaluate `if` conditions (`!`, `&&`, `||`, comparisons): a condition that
+//!    is always false makes its branch dead, one that is always true its `else`
+//!
+//! Unqualified names only resolve to project constants when written in
+//! UPPER_SNAKE_CASE, so parameters and fields never pass for flags.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! object FeatureFlags {
+//!     const val NEW_CHECKOUT = false
+//! }
+//!
+//! fun checkout() {
+//!     if (FeatureFlags.NEW_CHECKOUT) {  // DEAD: NEW_CHECKOUT is always false
+//!         newCheckout()
+//!     }
+//! }
+//! ```
 
-pub struct DeadBranchDetector;
-impl DeadBranchDetector {
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Value of a compile-time constant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ConstValue {
+    Bool(bool),
+    Int(i64),
+    Str(String),
+}
+
+/// A constant value, remembering whether it depends on the build variant
+#[derive(Debug, Clone, PartialEq)]
+struct Known {
+    value: ConstValue,
+    /// Derived from a BuildConfig field
+    variant: bool,
+}
+
+/// Constants defined in the project and in BuildConfig
+#[derive(Debug, Default)]
+pub struct ConstantTable {
+    /// Project constants by simple name; None when defined with different values
+    constants: HashMap<String, Option<Known>>,
+    build_config: HashMap<String, ConstValue>,
+    /// Collected definitions not evaluated yet: name and initializer
+    pending: Vec<(String, String)>,
+}
+
+impl ConstantTable {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Use the BuildConfig values of the analyzed variant
+    pub fn with_build_config(mut self, values: &BTreeMap<String, ConstValue>) -> Self {
+        self.build_config = values.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        self
+    }
+
+    /// Collect the constant definitions of a source file
+    pub fn collect(&mut self, source: &str, language: Language) {
+        let code = mask(source, false);
+        let skeleton = mask(source, true);
+        let definitions = match language {
+            Language::Kotlin => kotlin_constants(&code, &skeleton),
+            Language::Java => java_constants(&code, &skeleton),
+        };
+        self.pending.extend(definitions);
+    }
+
+    /// Evaluate the collected definitions, following constants defined from
+    /// other constants
+    pub fn resolve(&mut self) {
+        let mut by_name: HashMap<String, Option<String>> = HashMap::new();
+        for (name, initializer) in std::mem::take(&mut self.pending) {
+            by_name
+                .entry(name)
+                .and_modify(|existing| {
+                    if existing.as_deref() != Some(initializer.as_str()) {
+                        *existing = None;
+                    }
+                })
+                .or_insert(Some(initializer));
+        }
+
+        let mut pending = Vec::new();
+        for (name, initializer) in by_name {
+            match initializer {
+                Some(initializer) => pending.push((name, initializer)),
+                // Conflicting definitions never resolve
+                None => {
+                    self.constants.insert(name, None);
+                }
+            }
+        }
+
+        loop {
+            let (resolved, unresolved): (Vec<_>, Vec<_>) = pending
+                .into_iter()
+                .map(|(name, initializer)| {
+                    let value = evaluate(&initializer, &|path| self.lookup(path));
+                    (name, initializer, value)
+                })
+                .partition(|(_, _, value)| value.is_some());
+            if resolved.is_empty() {
+                break;
+            }
+            for (name, _, value) in resolved {
+                self.constants.insert(name, value);
+            }
+            pending = unresolved.into_iter().map(|(n, i, _)| (n, i)).collect();
+        }
+    }
+
+    /// Value of a name as written in code: `DEBUG`, `BuildConfig.DEBUG`,
+    /// `FeatureFlags.NEW_CHECKOUT`
+    fn lookup(&self, path: &str) -> Option<Known> {
+        let mut segments = path.rsplit('.');
+        let name = segments.next()?;
+        if segments.next() == Some("BuildConfig") {
+            let value = self.build_config.get(name)?.clone();
+            return Some(Known {
+                value,
+                variant: true,
+            });
+        }
+        if !is_constant_name(name) {
+            return None;
+        }
+        self.constants.get(name).cloned().flatten()
     }
 }
-impl Detector for DeadBranchDetector {
-    fn detect(&self, _graph: &Graph) -> Vec<DeadCode> {
-        Vec::new()
+
+/// A branch that never runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadBranch {
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub start_byte: usize,
+    pub end_byte: usize,
+    /// The `if` condition as written
+    pub condition: String,
+    /// Value the condition always has: false kills the `if` branch, true the `else`
+    pub value: bool,
+    /// Whether the value comes from BuildConfig, i.e. only holds for this variant
+    pub depends_on_variant: bool,
+}
+
+impl DeadBranch {
+    pub fn to_dead_code(&self) -> DeadCode {
+        let (language, kind) = match self.file.extension().and_then(|e| e.to_str()) {
+            Some("java") => (Language::Java, DeclarationKind::Method),
+            _ => (Language::Kotlin, DeclarationKind::Function),
+        };
+        let (name, message) = if self.value {
+            (
+                format!("else of if ({})", self.condition),
+                format!(
+                    "Condition `{}` is always true; the else branch never runs",
+                    self.condition
+                ),
+            )
+        } else {
+            (
+                format!("if ({})", self.condition),
+                format!(
+                    "Condition `{}` is always false; the branch never runs",
+                    self.condition
+                ),
+            )
+        };
+        let (message, confidence) = if self.depends_on_variant {
+            (format!("{} in this variant", message), Confidence::Medium)
+        } else {
+            (message, Confidence::High)
+        };
+
+        let decl = Declaration::new(
+            DeclarationId::new(self.file.clone(), self.start_byte, self.end_byte),
+            name,
+            kind,
+            Location::new(
+                self.file.clone(),
+                self.line,
+                self.column,
+                self.start_byte,
+                self.end_byte,
+            ),
+            language,
+        );
+        DeadCode::new(decl, DeadCodeIssue::DeadBranch)
+            .with_message(message)
+            .with_confidence(confidence)
+    }
+}
+
+/// Detector for branches whose condition is constant
+pub struct DeadBranchDetector {
+    build_config: BTreeMap<String, ConstValue>,
+}
+
+impl DeadBranchDetector {
+    pub fn new() -> Self {
+        Self {
+            build_config: BTreeMap::new(),
+        }
+    }
+
+    /// BuildConfig field values of the analyzed variant (e.g. `DEBUG`)
+    pub fn with_build_config(mut self, values: BTreeMap<String, ConstValue>) -> Self {
+        self.build_config = values;
+        self
+    }
+
+    /// Find dead branches in Kotlin and Java sources (path and contents)
+    pub fn analyze(&self, sources: &[(PathBuf, String)]) -> Vec<DeadBranch> {
+        let mut constants = ConstantTable::new().with_build_config(&self.build_config);
+        for (path, source) in sources {
+            constants.collect(source, language_of(path));
+        }
+        constants.resolve();
+
+        sources
+            .iter()
+            .flat_map(|(path, source)| self.analyze_source(source, path, &constants))
+            .collect()
+    }
+
+    /// Find the dead branches of one file
+    pub fn analyze_source(
+        &self,
+        source: &str,
+        path: &Path,
+        constants: &ConstantTable,
+    ) -> Vec<DeadBranch> {
+        let code = mask(source, false);
+        let skeleton = mask(source, true);
+        let bytes = skeleton.as_bytes();
+        let language = language_of(path);
+
+        let mut branches = Vec::new();
+        let mut dead_ranges: Vec<(usize, usize)> = Vec::new();
+        // Local values in scope: brace depth, name and value (None shadows)
+        let mut locals: Vec<(usize, String, Option<Known>)> = Vec::new();
+        let mut depth = 0usize;
+
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'{' => depth += 1,
+                b'}' => {
+                    depth = depth.saturating_sub(1);
+                    locals.retain(|(d, _, _)| *d <= depth);
+                }
+                _ if !is_word_start(bytes, i) => {}
+                _ => {
+                    let word = word_at(&skeleton, i);
+                    let resolve = |path: &str| resolve_name(path, &locals, constants);
+                    match (word, language) {
+                        ("val" | "var", Language::Kotlin) => {
+                            if let Some(local) = kotlin_local(&code, &skeleton, i, &resolve) {
+                                locals.push((depth, local.0, local.1));
+                            }
+                        }
+                        ("final", Language::Java) => {
+                            if let Some(local) = java_local(&code, &skeleton, i, &resolve) {
+                                locals.push((depth, local.0, local.1));
+                            }
+                        }
+                        ("if", _) => {
+                            let in_dead_code = dead_ranges.iter().any(|&(s, e)| s <= i && i < e);
+                            if let (false, Some(branch)) =
+                                (in_dead_code, dead_branch(&code, &skeleton, i, &resolve))
+                            {
+                                dead_ranges.push((branch.start_byte, branch.end_byte));
+                                let (line, column) = line_column(source, branch.start_byte);
+                                branches.push(DeadBranch {
+                                    file: path.to_path_buf(),
+                                    line,
+                                    column,
+                                    ..branch
+                                });
+                            }
+                        }
+                        _ => {}
+                    }
+                    i += word.len();
+                    continue;
+                }
+            }
+            i += 1;
+        }
+
+        branches
     }
 }
+
 impl Default for DeadBranchDetector {
     fn default() -> Self {
         Self::new()
     }
 }
+
+fn language_of(path: &Path) -> Language {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("java") => Language::Java,
+        _ => Language::Kotlin,
+    }
+}
+
+/// Resolve a name against the locals in scope, innermost first, then the
+/// project constants
+fn resolve_name(
+    path: &str,
+    locals: &[(usize, String, Option<Known>)],
+    constants: &ConstantTable,
+) -> Option<Known> {
+    if !path.contains('.') {
+        if let Some((_, _, value)) = locals.iter().rev().find(|(_, name, _)| name == path) {
+            return value.clone();
+        }
+    }
+    constants.lookup(path)
+}
+
+/// The branch of the `if` at `start` that never runs, if its condition is constant
+fn dead_branch(
+    code: &str,
+    skeleton: &str,
+    start: usize,
+    resolve: &dyn Fn(&str) -> Option<Known>,
+) -> Option<DeadBranch> {
+    let bytes = skeleton.as_bytes();
+    let open = skip_whitespace(bytes, start + 2);
+    if bytes.get(open) != Some(&b'(') {
+        return None;
+    }
+    let close = matching(bytes, open)?;
+    let condition = &code[open + 1..close];
+    let known = evaluate(condition, resolve)?;
+    let ConstValue::Bool(value) = known.value else {
+        return None;
+    };
+
+    let then_end = body_end(bytes, skip_whitespace(bytes, close + 1));
+    let else_start = skip_whitespace(bytes, then_end);
+    let has_else = is_word_start(bytes, else_start) && word_at(skeleton, else_start) == "else";
+    let (start_byte, end_byte) = match (value, has_else) {
+        (false, true) => (start, else_start),
+        (false, false) => (start, then_end),
+        (true, true) => (
+            else_start,
+            body_end(bytes, skip_whitespace(bytes, else_start + 4)),
+        ),
+        (true, false) => return None,
+    };
+
+    Some(DeadBranch {
+        file: PathBuf::new(),
+        line: 0,
+        column: 0,
+        start_byte,
+        end_byte,
+        condition: condition.split_whitespace().collect::<Vec<_>>().join(" "),
+        value,
+        depends_on_variant: known.variant,
+    })
+}
+
+/// End of the branch body starting at `start`: a block, an `if` with its
+/// `else` chain, or a single statement
+fn body_end(bytes: &[u8], start: usize) -> usize {
+    if bytes.get(start) == Some(&b'{') {
+        return matching(bytes, start).map_or(bytes.len(), |close| close + 1);
+    }
+    if is_word_start(bytes, start) && bytes[start..].starts_with(b"if") {
+        let open = skip_whitespace(bytes, start + 2);
+        if bytes.get(open) == Some(&b'(') {
+            let Some(close) = matching(bytes, open) else {
+                return bytes.len();
+            };
+            let then_end = body_end(bytes, skip_whitespace(bytes, close + 1));
+            let else_start = skip_whitespace(bytes, then_end);
+            if is_word_start(bytes, else_start) && bytes[else_start..].starts_with(b"else") {
+                return body_end(bytes, skip_whitespace(bytes, else_start + 4));
+            }
+            return then_end;
+        }
+    }
+
+    // A single statement ends at a line break, `;` or an `else` outside parentheses
+    let mut depth = 0usize;
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' if depth == 0 => return i,
+            b')' | b']' | b'}' => depth -= 1,
+            b'\n' if depth == 0 => return i,
+            b';' if depth == 0 => return i + 1,
+            b'e' if depth == 0 && is_word_start(bytes, i) && bytes[i..].starts_with(b"else") => {
+                return i;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// A Kotlin `val`/`var` at `start`: its name and, for a `val` initialized from
+/// constants, its value
+fn kotlin_local(
+    code: &str,
+    skeleton: &str,
+    start: usize,
+    resolve: &dyn Fn(&str) -> Option<Known>,
+) -> Option<(String, Option<Known>)> {
+    let bytes = skeleton.as_bytes();
+    let name_start = skip_whitespace(bytes, start + 3);
+    if !is_word_start(bytes, name_start) {
+        return None;
+    }
+    let name = word_at(skeleton, name_start);
+
+    let line_start = skeleton[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = skeleton[start..]
+        .find('\n')
+        .map_or(skeleton.len(), |i| start + i);
+    let modifiers = &skeleton[line_start..start];
+    // Constants are resolved project-wide
+    if modifiers.trim_end().ends_with("const") {
+        return None;
+    }
+    let overridable = ["open ", "override ", "abstract "]
+        .iter()
+        .any(|m| modifiers.contains(m));
+    let value = match (&skeleton[start..start + 3], overridable) {
+        ("val", false) => skeleton[start..line_end]
+            .find('=')
+            .map(|eq| start + eq)
+            .filter(|&eq| !matches!(bytes.get(eq + 1), Some(b'=' | b'>')))
+            .and_then(|eq| evaluate(&code[eq + 1..line_end], resolve)),
+        _ => None,
+    };
+    Some((name.to_string(), value))
+}
+
+/// A Java `final` local at `start`: its name and value, if initialized from
+/// constants
+fn java_local(
+    code: &str,
+    skeleton: &str,
+    start: usize,
+    resolve: &dyn Fn(&str) -> Option<Known>,
+) -> Option<(String, Option<Known>)> {
+    let end = skeleton[start..].find(';').map(|i| start + i)?;
+    let statement = &skeleton[start..end];
+    if statement.contains('(') && statement.find('(') < statement.find('=') {
+        return None;
+    }
+    let (declaration, value) = match statement.find('=') {
+        Some(eq) => (
+            &statement[..eq],
+            evaluate(&code[start + eq + 1..end], resolve),
+        ),
+        None => (statement, None),
+    };
+    let name = declaration.split_whitespace().last()?;
+    Some((name.to_string(), value))
+}
+
+/// Kotlin `const val NAME = initializer` definitions
+fn kotlin_constants(code: &str, skeleton: &str) -> Vec<(String, String)> {
+    let mut definitions = Vec::new();
+    let mut search = 0;
+    while let Some(found) = skeleton[search..].find("const val ") {
+        let start = search + found;
+        search = start + "const val ".len();
+        let line_end = skeleton[start..]
+            .find('\n')
+            .map_or(skeleton.len(), |i| start + i);
+        let Some(eq) = skeleton[start..line_end].find('=').map(|eq| start + eq) else {
+            continue;
+        };
+        let name = skeleton[search..eq]
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .trim();
+        if is_identifier(name) {
+            definitions.push((name.to_string(), code[eq + 1..line_end].trim().to_string()));
+        }
+    }
+    definitions
+}
+
+/// Java `static final Type NAME = initializer;` definitions
+fn java_constants(code: &str, skeleton: &str) -> Vec<(String, String)> {
+    let mut definitions = Vec::new();
+    let mut statement_start = 0;
+    for (i, byte) in skeleton.bytes().enumerate() {
+        match byte {
+            b'{' | b'}' => statement_start = i + 1,
+            b';' => {
+                let statement = &skeleton[statement_start..i];
+                statement_start = i + 1;
+                let words: Vec<&str> = statement.split_whitespace().collect();
+                if !words.contains(&"static") || !words.contains(&"final") {
+                    continue;
+                }
+                let Some(eq) = statement.find('=') else {
+                    continue;
+                };
+                if statement[..eq].contains('(') {
+                    continue;
+                }
+                let name = statement[..eq]
+                    .split_whitespace()
+                    .last()
+                    .unwrap_or_default();
+                let offset = statement_start - statement.len() - 1;
+                let initializer = &code[offset + eq + 1..i];
+                if is_identifier(name) {
+                    definitions.push((name.to_string(), initializer.trim().to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+    definitions
+}
+
+/// Blank out comments, and string contents when `strings` is set, keeping
+/// byte offsets and line breaks
+fn mask(source: &str, strings: bool) -> String {
+    let bytes = source.as_bytes();
+    let mut out = bytes.to_vec();
+    let blank = |out: &mut [u8], from: usize, to: usize| {
+        for byte in &mut out[from..to.min(bytes.len())] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    };
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"//") {
+            let end = source[i..].find('\n').map_or(bytes.len(), |e| i + e);
+            blank(&mut out, i, end);
+            i = end;
+        } else if bytes[i..].starts_with(b"/*") {
+            let end = source[i + 2..]
+                .find("*/")
+                .map_or(bytes.len(), |e| i + 2 + e + 2);
+            blank(&mut out, i, end);
+            i = end;
+        } else if bytes[i..].starts_with(b"\"\"\"") {
+            let end = source[i + 3..]
+                .find("\"\"\"")
+                .map_or(bytes.len(), |e| i + 3 + e);
+            if strings {
+                blank(&mut out, i + 3, end);
+            }
+            i = end + 3;
+        } else if bytes[i] == b'"' || bytes[i] == b'\'' {
+            let quote = bytes[i];
+            let mut end = i + 1;
+            while end < bytes.len() && bytes[end] != quote && bytes[end] != b'\n' {
+                end += if bytes[end] == b'\\' { 2 } else { 1 };
+            }
+            if strings {
+                blank(&mut out, i + 1, end);
+            }
+            i = end + 1;
+        } else {
+            i += 1;
+        }
+    }
+
+    // Only ASCII bytes were replaced, and only whole comments and strings
+    String::from_utf8(out).unwrap_or_else(|_| source.to_string())
+}
+
+/// Evaluate a condition or initializer; None when it isn't constant
+fn evaluate(expression: &str, resolve: &dyn Fn(&str) -> Option<Known>) -> Option<Known> {
+    let tokens = tokenize(expression)?;
+    let mut parser = ExpressionParser {
+        tokens: &tokens,
+        position: 0,
+        resolve,
+    };
+    let value = parser.or()?;
+    if parser.position == tokens.len() {
+        value
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Name(String),
+    Literal(ConstValue),
+    /// A call or literal whose value isn't tracked
+    Opaque,
+    Operator(&'static str),
+    Open,
+    Close,
+}
+
+fn tokenize(expression: &str) -> Option<Vec<Token>> {
+    const OPERATORS: &[&str] = &["&&", "||", "==", "!=", "<=", ">=", "<", ">", "!"];
+
+    let bytes = expression.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &expression[i..];
+        let byte = bytes[i];
+        if byte.is_ascii_whitespace() {
+            i += 1;
+        } else if byte == b'(' {
+            tokens.push(Token::Open);
+            i += 1;
+        } else if byte == b')' {
+            tokens.push(Token::Close);
+            i += 1;
+        } else if byte == b'"' {
+            let end = rest[1..].find('"')? + 1;
+            let text = &rest[1..end];
+            // Templates and escapes aren't evaluated
+            if text.contains(['$', '\\']) {
+                tokens.push(Token::Opaque);
+            } else {
+                tokens.push(Token::Literal(ConstValue::Str(text.to_string())));
+            }
+            i += end + 1;
+        } else if byte.is_ascii_digit() {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let digits = rest[..end].trim_end_matches(['L', 'l']).replace('_', "");
+            tokens.push(match digits.parse() {
+                Ok(value) => Token::Literal(ConstValue::Int(value)),
+                Err(_) => Token::Opaque,
+            });
+            i += end;
+        } else if byte.is_ascii_alphabetic() || byte == b'_' {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(rest.len());
+            let name = rest[..end].trim_end_matches('.');
+            i += name.len();
+            let next = skip_whitespace(bytes, i);
+            if bytes.get(next) == Some(&b'(') {
+                i = matching(bytes, next)? + 1;
+                tokens.push(Token::Opaque);
+                continue;
+            }
+            tokens.push(match name {
+                "true" => Token::Literal(ConstValue::Bool(true)),
+                "false" => Token::Literal(ConstValue::Bool(false)),
+                _ => Token::Name(name.to_string()),
+            });
+        } else {
+            let operator = OPERATORS.iter().find(|op| rest.starts_with(**op))?;
+            // `===`, `!==` compare identity
+            if rest[operator.len()..].starts_with('=') {
+                return None;
+            }
+            tokens.push(Token::Operator(operator));
+            i += operator.len();
+        }
+    }
+    Some(tokens)
+}
+
+/// Recursive descent over condition tokens. Each rule returns None on a
+/// syntax it doesn't support, and Some(None) for an operand with an unknown
+/// value, so `false && unknown()` still folds to false.
+struct ExpressionParser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    resolve: &'a dyn Fn(&str) -> Option<Known>,
+}
+
+impl ExpressionParser<'_> {
+    fn or(&mut self) -> Option<Option<Known>> {
+        let mut operands = vec![self.and()?];
+        while self.eat_operator("||") {
+            operands.push(self.and()?);
+        }
+        Some(fold(operands, true))
+    }
+
+    fn and(&mut self) -> Option<Option<Known>> {
+        let mut operands = vec![self.comparison()?];
+        while self.eat_operator("&&") {
+            operands.push(self.comparison()?);
+        }
+        Some(fold(operands, false))
+    }
+
+    fn comparison(&mut self) -> Option<Option<Known>> {
+        let left = self.unary()?;
+        let Some(Token::Operator(operator)) = self.tokens.get(self.position) else {
+            return Some(left);
+        };
+        let operator = *operator;
+        if !matches!(operator, "==" | "!=" | "<" | "<=" | ">" | ">=") {
+            return Some(left);
+        }
+        self.position += 1;
+        let right = self.unary()?;
+
+        let (Some(left), Some(right)) = (left, right) else {
+            return Some(None);
+        };
+        let result = match (&left.value, &right.value, operator) {
+            (l, r, "==") if same_type(l, r) => l == r,
+            (l, r, "!=") if same_type(l, r) => l != r,
+            (ConstValue::Int(l), ConstValue::Int(r), _) => match operator {
+                "<" => l < r,
+                "<=" => l <= r,
+                ">" => l > r,
+                _ => l >= r,
+            },
+            _ => return Some(None),
+        };
+        Some(Some(Known {
+            value: ConstValue::Bool(result),
+            variant: left.variant || right.variant,
+        }))
+    }
+
+    fn unary(&mut self) -> Option<Option<Known>> {
+        if self.eat_operator("!") {
+            let operand = self.unary()?;
+            return Some(operand.and_then(|known| match known.value {
+                ConstValue::Bool(value) => Some(Known {
+                    value: ConstValue::Bool(!value),
+                    ..known
+                }),
+                _ => None,
+            }));
+        }
+
+        let token = self.tokens.get(self.position)?.clone();
+        self.position += 1;
+        match token {
+            Token::Open => {
+                let value = self.or()?;
+                (self.tokens.get(self.position) == Some(&Token::Close)).then(|| {
+                    self.position += 1;
+                    value
+                })
+            }
+            Token::Literal(value) => Some(Some(Known {
+                value,
+                variant: false,
+            })),
+            Token::Name(name) => Some((self.resolve)(&name)),
+            Token::Opaque => Some(None),
+            Token::Operator(_) | Token::Close => None,
+        }
+    }
+
+    fn eat_operator(&mut self, operator: &'static str) -> bool {
+        let matches = self.tokens.get(self.position) == Some(&Token::Operator(operator));
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+}
+
+/// Fold the operands of `||` (`absorbing` true) or `&&` (`absorbing` false):
+/// one absorbing operand decides the result, otherwise all must be known
+fn fold(operands: Vec<Option<Known>>, absorbing: bool) -> Option<Known> {
+    if operands.len() == 1 {
+        return operands.into_iter().next().flatten();
+    }
+    let decisive = operands
+        .iter()
+        .flatten()
+        .find(|known| known.value == ConstValue::Bool(absorbing));
+    if let Some(known) = decisive {
+        return Some(known.clone());
+    }
+
+    let mut variant = false;
+    for operand in &operands {
+        match operand {
+            Some(Known {
+                value: ConstValue::Bool(_),
+                variant: v,
+            }) => variant |= v,
+            _ => return None,
+        }
+    }
+    Some(Known {
+        value: ConstValue::Bool(!absorbing),
+        variant,
+    })
+}
+
+fn same_type(left: &ConstValue, right: &ConstValue) -> bool {
+    std::mem::discriminant(left) == std::mem::discriminant(right)
+}
+
+/// UPPER_SNAKE_CASE, the naming convention of constants
+fn is_constant_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_word_start(bytes: &[u8], i: usize) -> bool {
+    let is_word = |b: &u8| b.is_ascii_alphanumeric() || *b == b'_';
+    bytes.get(i).is_some_and(is_word)
+        && (i == 0 || !(is_word(&bytes[i - 1]) || bytes[i - 1] == b'.'))
+}
+
+fn word_at(text: &str, start: usize) -> &str {
+    let end = text[start..]
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .map_or(text.len(), |e| start + e);
+    &text[start..end]
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+        i += 1;
+    }
+    i
+}
+
+/// Index of the bracket closing the one at `open`
+fn matching(bytes: &[u8], open: usize) -> Option<usize> {
+    let (opening, closing) = match bytes[open] {
+        b'(' => (b'(', b')'),
+        b'{' => (b'{', b'}'),
+        _ => return None,
+    };
+    let mut depth = 0;
+    for (i, &byte) in bytes.iter().enumerate().skip(open) {
+        if byte == opening {
+            depth += 1;
+        } else if byte == closing {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+/// 1-based line and column of a byte offset
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = offset - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze(files: &[(&str, &str)], debug: bool) -> Vec<DeadBranch> {
+        let sources: Vec<(PathBuf, String)> = files
+            .iter()
+            .map(|(path, source)| (PathBuf::from(path), source.to_string()))
+            .collect();
+        let build_config = BTreeMap::from([("DEBUG".to_string(), ConstValue::Bool(debug))]);
+        DeadBranchDetector::new()
+            .with_build_config(build_config)
+            .analyze(&sources)
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let resolve = |name: &str| match name {
+            "ON" => Some(Known {
+                value: ConstValue::Bool(true),
+                variant: false,
+            }),
+            "LEVEL" => Some(Known {
+                value: ConstValue::Int(3),
+                variant: true,
+            }),
+            _ => None,
+        };
+        let value = |expression: &str| evaluate(expression, &resolve).map(|k| k.value);
+
+        assert_eq!(value("!ON"), Some(ConstValue::Bool(false)));
+        assert_eq!(value("ON && LEVEL >= 2"), Some(ConstValue::Bool(true)));
+        assert_eq!(value("!ON && isEnabled()"), Some(ConstValue::Bool(false)));
+        assert_eq!(value("ON || user.isAdmin"), Some(ConstValue::Bool(true)));
+        assert_eq!(value("(LEVEL == 3) != ON"), Some(ConstValue::Bool(false)));
+        assert_eq!(value("ON && isEnabled()"), None);
+        assert_eq!(value("user is Admin"), None);
+        assert!(evaluate("LEVEL > 1", &resolve).unwrap().variant);
+    }
+
+    #[test]
+    fn test_build_config_and_feature_flags() {
+        let flags = r#"
+            object FeatureFlags {
+                const val NEW_CHECKOUT = false
+                const val CHECKOUT_V2: Boolean = !NEW_CHECKOUT // derived
+            }
+        "#;
+        let screen = r#"
+            fun checkout() {
+                if (FeatureFlags.NEW_CHECKOUT) {
+                    newCheckout()
+                }
+                if (CHECKOUT_V2) {
+                    checkoutV2()
+                } else {
+                    legacyCheckout()
+                }
+                if (BuildConfig.DEBUG) Log.d("checkout", "done")
+                if (user.isAdmin) adminCheckout()
+            }
+        "#;
+
+        let branches = analyze(&[("Flags.kt", flags), ("Screen.kt", screen)], false);
+        let found: Vec<_> = branches
+            .iter()
+            .map(|b| (b.line, b.condition.as_str(), b.value, b.depends_on_variant))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (3, "FeatureFlags.NEW_CHECKOUT", false, false),
+                (8, "CHECKOUT_V2", true, false),
+                (11, "BuildConfig.DEBUG", false, true),
+            ]
+        );
+
+        let debug = analyze(&[("Flags.kt", flags), ("Screen.kt", screen)], true);
+        assert!(!debug.iter().any(|b| b.condition == "BuildConfig.DEBUG"));
+    }
+
+    #[test]
+    fn test_local_propagation_and_java() {
+        let source = r#"
+            class Logger {
+                private static final boolean VERBOSE = false;
+                static final int LEVEL = 2;
+
+                void log(String message) {
+                    final boolean enabled = VERBOSE || LEVEL > 3;
+                    if (enabled) {
+                        print(message);
+                    }
+                    // if (VERBOSE) in a comment is ignored
+                    String text = "if (VERBOSE) in a string too";
+                }
+            }
+        "#;
+
+        let branches = analyze(&[("Logger.java", source)], false);
+        assert_eq!(branches.len(), 1);
+        assert_eq!(branches[0].condition, "enabled");
+        assert_eq!(branches[0].line, 8);
+        assert_eq!(branches[0].to_dead_code().confidence, Confidence::High);
+    }
+
+    #[test]
+    fn test_conflicting_and_shadowed_constants() {
+        let first = "const val ENABLED = true\nconst val MODE = 1\n";
+        let second = r#"
+            const val ENABLED = false
+
+            fun run(enabled: Boolean) {
+                if (ENABLED) start()
+                if (MODE == 1) {
+                    var mode = MODE
+                    val MODE = load()
+                    if (MODE == 1) restart()
+                }
+            }
+        "#;
+
+        let branches = analyze(&[("A.kt", first), ("B.kt", second)], false);
+        // ENABLED is ambiguous, MODE is shadowed by a local; MODE == 1 is
+        // always true but has no else branch
+        assert!(branches.is_empty(), "{:?}", branches);
+    }
+}
@@ -0,0 +1,797 @@
+//! Deep Link Detector
+//!
+//! Cross-references the deep links an app handles with the deep links it opens.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Collect routes handled by the app:
+//!    - `<intent-filter>`s of manifest activities with the `VIEW` action and
+//!      `<data android:scheme/host/path*>` elements (deep links and app links)
+//!    - `<deepLink app:uri>` elements of navigation graphs
+//!    - `navDeepLink { uriPattern = "..." }` of Compose destinations
+//! 2. Collect deep links opened by the app and its tests: URI string literals
+//!    in Kotlin/Java code (`Uri.parse("myapp://item/$id")`) and other XML
+//!    (shortcuts, widgets)
+//! 3. Report:
+//!    - routes that nothing opens
+//!    - URIs opened on a scheme or host the app owns that no route handles
+//!    - activities only reachable through a deep link: not launchers, not
+//!      navigation destinations and never referenced from production code
+//!
+//! ## Examples Detected
+//!
+//! ```xml
+//! <activity android:name=".ItemActivity">
+//!     <intent-filter>
+//!         <action android:name="android.intent.action.VIEW" />
+//!         <category android:name="android.intent.category.BROWSABLE" />
+//!         <data android:scheme="myapp" android:host="item" />
+//!     </intent-filter>
+//! </activity>
+//! ```
+//!
+//! ```kotlin
+//! startActivity(Intent(Intent.ACTION_VIEW, Uri.parse("myapp://item/42")))  // Handled above
+//! startActivity(Intent(Intent.ACTION_VIEW, Uri.parse("myapp://cart")))     // DEAD: no route
+//! ```
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// A deep link route handled by the app
+#[derive(Debug, Clone)]
+pub struct DeepLinkRoute {
+    /// Route as declared, e.g. `myapp://item/{id}` or `https://example.com/help*`
+    pub uri: String,
+    /// Handling activity class, navigation destination or Compose destination
+    pub handler: String,
+    pub scheme: String,
+    pub host: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+    matcher: Regex,
+}
+
+impl DeepLinkRoute {
+    /// Whether an opened URI is handled by this route
+    pub fn matches(&self, uri: &str) -> bool {
+        self.matcher.is_match(strip_query(uri))
+    }
+
+    /// App links are `http`/`https` deep links, usually opened from outside the app
+    pub fn is_app_link(&self) -> bool {
+        is_web_scheme(&self.scheme)
+    }
+}
+
+/// A deep link URI opened by code or XML
+#[derive(Debug, Clone)]
+pub struct DeepLinkLocation {
+    pub uri: String,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// Result of deep link analysis
+#[derive(Debug, Default)]
+pub struct DeepLinkAnalysis {
+    pub routes: Vec<DeepLinkRoute>,
+    pub opened: Vec<DeepLinkLocation>,
+    /// Routes never opened by the app or its tests
+    pub unopened_routes: Vec<DeepLinkRoute>,
+    /// URIs opened on the app's schemes or hosts that no route handles
+    pub unhandled_links: Vec<DeepLinkLocation>,
+    /// Routes of activities that are only reachable through deep links
+    pub deep_link_only: Vec<DeepLinkRoute>,
+}
+
+/// Manifest activity with its deep link routes
+#[derive(Debug, Default)]
+struct LinkedActivity {
+    class_name: String,
+    launcher: bool,
+    routes: Vec<DeepLinkRoute>,
+}
+
+/// `<intent-filter>` being read
+#[derive(Debug, Default)]
+struct IntentFilter {
+    line: usize,
+    view: bool,
+    launcher: bool,
+    schemes: Vec<String>,
+    hosts: Vec<String>,
+    /// (display, regex) of each path element
+    paths: Vec<(String, String)>,
+}
+
+/// Everything collected while walking the project
+#[derive(Debug, Default)]
+struct DeepLinkScan {
+    activities: Vec<LinkedActivity>,
+    routes: Vec<DeepLinkRoute>,
+    opened: Vec<DeepLinkLocation>,
+    /// Text of navigation graphs, where activities can be destinations
+    navigation_xml: Vec<String>,
+    /// Production Kotlin/Java sources
+    sources: Vec<(PathBuf, String)>,
+}
+
+/// Detector for deep links without a handler and handlers never deep-linked
+pub struct DeepLinkDetector {
+    uri_literal_pattern: Regex,
+    uri_pattern_pattern: Regex,
+    template_pattern: Regex,
+}
+
+impl DeepLinkDetector {
+    pub fn new() -> Self {
+        Self {
+            uri_literal_pattern: Regex::new(r#""([a-zA-Z][\w+.-]*://[^"\s]*)"(\s*\+)?"#).unwrap(),
+            uri_pattern_pattern: Regex::new(r#"uriPattern\s*=\s*"([^"]+)""#).unwrap(),
+            template_pattern: Regex::new(r"\$\{[^}]*\}|\$\w+").unwrap(),
+        }
+    }
+
+    /// Analyze a project for unmatched deep links
+    pub fn analyze(&self, root: &Path) -> DeepLinkAnalysis {
+        use ignore::WalkBuilder;
+
+        let mut scan = DeepLinkScan::default();
+
+        let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
+        for entry in walker.flatten() {
+            let path = entry.path();
+            let ext = path.extension().and_then(|e| e.to_str());
+
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+
+            match ext {
+                Some("xml") if path.file_name().is_some_and(|n| n == "AndroidManifest.xml") => {
+                    self.parse_manifest(path, &content, &mut scan);
+                }
+                Some("xml") if Self::is_navigation_file(path) => {
+                    self.parse_navigation_xml(path, &content, &mut scan);
+                    scan.navigation_xml.push(content);
+                }
+                Some("xml") => self.collect_opened(path, &content, &mut scan),
+                Some("kt") | Some("java") => {
+                    self.collect_compose_routes(path, &content, &mut scan);
+                    // Links opened from tests count, references from tests don't
+                    self.collect_opened(path, &content, &mut scan);
+                    let path_str = path.to_string_lossy();
+                    if !path_str.contains("/test/") && !path_str.contains("/androidTest/") {
+                        scan.sources.push((path.to_path_buf(), content));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self::build_analysis(scan)
+    }
+
+    fn is_navigation_file(path: &Path) -> bool {
+        path.parent()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().starts_with("navigation"))
+            .unwrap_or(false)
+    }
+
+    fn attribute(e: &BytesStart, suffix: &str) -> Option<String> {
+        e.attributes().filter_map(|a| a.ok()).find_map(|attr| {
+            let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+            if key == suffix || key.ends_with(&format!(":{}", suffix)) {
+                Some(String::from_utf8_lossy(&attr.value).to_string())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Collect the deep link routes of manifest activities
+    fn parse_manifest(&self, path: &Path, content: &str, scan: &mut DeepLinkScan) {
+        let mut reader = Reader::from_str(content);
+        reader.trim_text(true);
+
+        let mut buf = Vec::new();
+        let mut package = String::new();
+        let mut activity: Option<LinkedActivity> = None;
+        let mut filter: Option<IntentFilter> = None;
+
+        loop {
+            let event = reader.read_event_into(&mut buf);
+            let position = reader.buffer_position();
+            let line = content[..position.min(content.len())].matches('\n').count() + 1;
+
+            match event {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                    let is_empty = matches!(event, Ok(Event::Empty(_)));
+                    let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    match tag_name.as_str() {
+                        "manifest" => {
+                            package = Self::attribute(e, "package").unwrap_or_default();
+                        }
+                        "activity" | "activity-alias" if !is_empty => {
+                            let name = Self::attribute(e, "targetActivity")
+                                .or_else(|| Self::attribute(e, "name"))
+                                .unwrap_or_default();
+                            activity = Some(LinkedActivity {
+                                class_name: resolve_class_name(&name, &package),
+                                ..Default::default()
+                            });
+                        }
+                        "intent-filter" if !is_empty => {
+                            filter = Some(IntentFilter {
+                                line,
+                                ..Default::default()
+                            });
+                        }
+                        "action" | "category" => {
+                            if let (Some(filter), Some(name)) =
+                                (filter.as_mut(), Self::attribute(e, "name"))
+                            {
+                                match name.as_str() {
+                                    "android.intent.action.VIEW" => filter.view = true,
+                                    "android.intent.action.MAIN"
+                                    | "android.intent.category.LAUNCHER" => filter.launcher = true,
+                                    _ => {}
+                                }
+                            }
+                        }
+                        "data" => {
+                            if let Some(filter) = filter.as_mut() {
+                                filter.add_data(e);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::End(ref e)) => {
+                    let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    match tag_name.as_str() {
+                        "intent-filter" => {
+                            if let (Some(filter), Some(activity)) =
+                                (filter.take(), activity.as_mut())
+                            {
+                                activity.launcher |= filter.launcher;
+                                if filter.view {
+                                    activity
+                                        .routes
+                                        .extend(filter.routes(&activity.class_name, path));
+                                }
+                            }
+                        }
+                        "activity" | "activity-alias" => {
+                            scan.activities.extend(activity.take());
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+    }
+
+    /// Collect the `<deepLink>` routes of a navigation graph
+    fn parse_navigation_xml(&self, path: &Path, content: &str, scan: &mut DeepLinkScan) {
+        let mut reader = Reader::from_str(content);
+        reader.trim_text(true);
+
+        let mut buf = Vec::new();
+        // Stack of enclosing destinations (None for elements that aren't destinations)
+        let mut stack: Vec<Option<String>> = Vec::new();
+
+        loop {
+            let event = reader.read_event_into(&mut buf);
+            let position = reader.buffer_position();
+            let line = content[..position.min(content.len())].matches('\n').count() + 1;
+
+            match event {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                    let is_empty = matches!(event, Ok(Event::Empty(_)));
+                    let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+
+                    let mut pushed = None;
+                    match tag_name.as_str() {
+                        "fragment" | "dialog" | "activity" | "navigation" | "composable" => {
+                            pushed = Self::attribute(e, "name").or_else(|| {
+                                Self::attribute(e, "id").map(|id| id.replace("@+id/", ""))
+                            });
+                        }
+                        "deepLink" => {
+                            let handler = stack.iter().rev().flatten().next().cloned();
+                            if let Some(uri) = Self::attribute(e, "uri") {
+                                scan.routes.extend(nav_route(
+                                    &uri,
+                                    handler.unwrap_or_default(),
+                                    path,
+                                    line,
+                                ));
+                            }
+                        }
+                        _ => {}
+                    }
+
+                    if !is_empty {
+                        stack.push(pushed);
+                    }
+                }
+                Ok(Event::End(_)) => {
+                    stack.pop();
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+    }
+
+    /// Collect `navDeepLink { uriPattern = "..." }` routes of Compose destinations
+    fn collect_compose_routes(&self, path: &Path, content: &str, scan: &mut DeepLinkScan) {
+        for (line_num, line) in content.lines().enumerate() {
+            for caps in self.uri_pattern_pattern.captures_iter(line) {
+                // Templates (`"$BASE_URI/item/{id}"`) stand for any text
+                let uri = self.template_pattern.replace_all(&caps[1], "*");
+                let handler = path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                scan.routes
+                    .extend(nav_route(&uri, handler, path, line_num + 1));
+            }
+        }
+    }
+
+    /// Collect URI literals opened by code or XML
+    fn collect_opened(&self, path: &Path, content: &str, scan: &mut DeepLinkScan) {
+        for (line_num, line) in content.lines().enumerate() {
+            if self.uri_pattern_pattern.is_match(line) {
+                continue;
+            }
+            for caps in self.uri_literal_pattern.captures_iter(line) {
+                // Interpolated or concatenated parts become a placeholder value
+                let mut uri = self.template_pattern.replace_all(&caps[1], "0").to_string();
+                if caps.get(2).is_some() {
+                    uri.push('0');
+                }
+                scan.opened.push(DeepLinkLocation {
+                    uri,
+                    file: path.to_path_buf(),
+                    line: line_num + 1,
+                });
+            }
+        }
+    }
+
+    fn build_analysis(scan: DeepLinkScan) -> DeepLinkAnalysis {
+        let mut routes = scan.routes;
+        let mut deep_link_only = Vec::new();
+
+        for activity in scan.activities {
+            let simple = activity
+                .class_name
+                .rsplit('.')
+                .next()
+                .unwrap_or(&activity.class_name)
+                .to_string();
+            let reference = Regex::new(&format!(r"\b{}\b", regex::escape(&simple))).unwrap();
+            let referenced = scan
+                .navigation_xml
+                .iter()
+                .any(|xml| xml.contains(&activity.class_name))
+                || scan.sources.iter().any(|(path, content)| {
+                    path.file_stem().is_some_and(|stem| *stem != *simple)
+                        && reference.is_match(content)
+                });
+            if !activity.launcher && !referenced {
+                deep_link_only.extend(activity.routes.iter().cloned());
+            }
+            routes.extend(activity.routes);
+        }
+
+        // Only links on a scheme or web host the app handles are meant for it
+        let is_owned = |uri: &str| {
+            let Some((scheme, rest)) = uri.split_once("://") else {
+                return false;
+            };
+            let host = rest.split(['/', '?', '#', ':']).next().unwrap_or_default();
+            routes.iter().any(|route| {
+                route.scheme.eq_ignore_ascii_case(scheme)
+                    && (!is_web_scheme(scheme)
+                        || route.host.as_deref().is_some_and(|h| host_matches(h, host)))
+            })
+        };
+
+        let opened: Vec<DeepLinkLocation> = scan
+            .opened
+            .into_iter()
+            .filter(|link| is_owned(&link.uri))
+            .collect();
+
+        let mut unopened_routes: Vec<DeepLinkRoute> = routes
+            .iter()
+            .filter(|route| !opened.iter().any(|link| route.matches(&link.uri)))
+            .cloned()
+            .collect();
+        let mut unhandled_links: Vec<DeepLinkLocation> = opened
+            .iter()
+            .filter(|link| !routes.iter().any(|route| route.matches(&link.uri)))
+            .cloned()
+            .collect();
+
+        unopened_routes.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        unhandled_links.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        deep_link_only.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+
+        DeepLinkAnalysis {
+            routes,
+            opened,
+            unopened_routes,
+            unhandled_links,
+            deep_link_only,
+        }
+    }
+}
+
+impl Default for DeepLinkDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IntentFilter {
+    /// Record the scheme, host and path of a `<data>` element
+    fn add_data(&mut self, e: &BytesStart) {
+        if let Some(scheme) = DeepLinkDetector::attribute(e, "scheme") {
+            self.schemes.push(scheme);
+        }
+        if let Some(host) = DeepLinkDetector::attribute(e, "host") {
+            self.hosts.push(host);
+        }
+        if let Some(path) = DeepLinkDetector::attribute(e, "path") {
+            self.paths.push((path.clone(), regex::escape(&path)));
+        }
+        if let Some(prefix) = DeepLinkDetector::attribute(e, "pathPrefix") {
+            self.paths.push((
+                format!("{}*", prefix),
+                format!("{}.*", regex::escape(&prefix)),
+            ));
+        }
+        if let Some(suffix) = DeepLinkDetector::attribute(e, "pathSuffix") {
+            self.paths.push((
+                format!("*{}", suffix),
+                format!(".*{}", regex::escape(&suffix)),
+            ));
+        }
+        for attr in ["pathPattern", "pathAdvancedPattern"] {
+            if let Some(pattern) = DeepLinkDetector::attribute(e, attr) {
+                self.paths
+                    .push((pattern.clone(), path_pattern_regex(&pattern)));
+            }
+        }
+    }
+
+    /// Routes of the filter: Android matches any combination of its schemes,
+    /// hosts and paths
+    fn routes(&self, handler: &str, file: &Path) -> Vec<DeepLinkRoute> {
+        let any_host = [None];
+        let hosts: Vec<Option<&String>> = if self.hosts.is_empty() {
+            any_host.to_vec()
+        } else {
+            self.hosts.iter().map(Some).collect()
+        };
+        let any_path = [(String::new(), "(/.*)?".to_string())];
+        let paths = if self.paths.is_empty() {
+            &any_path[..]
+        } else {
+            &self.paths[..]
+        };
+
+        let mut routes = Vec::new();
+        for scheme in &self.schemes {
+            for host in &hosts {
+                for (display, path_regex) in paths {
+                    let host_regex = match host {
+                        Some(host) => regex::escape(host).replace(r"\*", "[^/]*"),
+                        None => "[^/]*".to_string(),
+                    };
+                    let matcher = format!(
+                        "(?i:{}://{}){}",
+                        regex::escape(scheme),
+                        host_regex,
+                        path_regex
+                    );
+                    let Ok(matcher) = Regex::new(&format!("^{}$", matcher)) else {
+                        continue;
+                    };
+                    routes.push(DeepLinkRoute {
+                        uri: format!(
+                            "{}://{}{}",
+                            scheme,
+                            host.map(String::as_str).unwrap_or("*"),
+                            display
+                        ),
+                        handler: handler.to_string(),
+                        scheme: scheme.clone(),
+                        host: host.cloned(),
+                        file: file.to_path_buf(),
+                        line: self.line,
+                        matcher,
+                    });
+                }
+            }
+        }
+        routes
+    }
+}
+
+/// Routes of a navigation `uri`/`uriPattern`: `{arg}` placeholders match a
+/// path segment, `*` any text, and a URI without scheme is an http and https
+/// app link
+fn nav_route(uri: &str, handler: String, file: &Path, line: usize) -> Vec<DeepLinkRoute> {
+    let uri = strip_query(uri);
+    let (schemes, rest) = match uri.split_once("://") {
+        Some((scheme, rest)) => (vec![scheme.to_string()], rest),
+        // A template standing for scheme and host matches any of them
+        None if uri.starts_with('*') => (vec!["*".to_string()], uri),
+        None => (vec!["http".to_string(), "https".to_string()], uri),
+    };
+    let host = rest.split('/').next().unwrap_or_default();
+
+    let mut rest_regex = String::new();
+    let mut chars = rest.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                }
+                rest_regex.push_str("[^/]+");
+            }
+            '*' => rest_regex.push_str(".*"),
+            '.' if chars.peek() == Some(&'*') => {
+                chars.next();
+                rest_regex.push_str(".*");
+            }
+            c => rest_regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    schemes
+        .into_iter()
+        .filter_map(|scheme| {
+            let matcher = if scheme == "*" {
+                Regex::new(&format!("^{}/?$", rest_regex))
+            } else {
+                Regex::new(&format!(
+                    "^(?i:{})://{}/?$",
+                    regex::escape(&scheme),
+                    rest_regex
+                ))
+            }
+            .ok()?;
+            Some(DeepLinkRoute {
+                uri: uri.to_string(),
+                handler: handler.clone(),
+                scheme,
+                host: (!host.is_empty() && !host.contains('*')).then(|| host.to_string()),
+                file: file.to_path_buf(),
+                line,
+                matcher,
+            })
+        })
+        .collect()
+}
+
+/// Regex of a manifest `pathPattern`: `.` is any character, `*` repeats the
+/// previous one and `\` escapes
+fn path_pattern_regex(pattern: &str) -> String {
+    let mut regex = String::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => regex.push('.'),
+            '*' => regex.push('*'),
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    regex.push_str(&regex::escape(&escaped.to_string()));
+                }
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex
+}
+
+/// Resolve a manifest class name, handling relative names like ".MainActivity"
+fn resolve_class_name(name: &str, package: &str) -> String {
+    match name.strip_prefix('.') {
+        Some(relative) if !package.is_empty() => format!("{}.{}", package, relative),
+        Some(relative) => relative.to_string(),
+        None if !name.contains('.') && !package.is_empty() => format!("{}.{}", package, name),
+        None => name.to_string(),
+    }
+}
+
+/// Whether a route host (possibly `*.example.com`) matches a URI host
+fn host_matches(route_host: &str, host: &str) -> bool {
+    match route_host.strip_prefix('*') {
+        Some(suffix) => host.to_lowercase().ends_with(&suffix.to_lowercase()),
+        None => route_host.eq_ignore_ascii_case(host),
+    }
+}
+
+fn is_web_scheme(scheme: &str) -> bool {
+    scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https")
+}
+
+/// URI without its query and fragment
+fn strip_query(uri: &str) -> &str {
+    uri.split(['?', '#']).next().unwrap_or(uri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<manifest xmlns:android="http://schemas.android.com/apk/res/android"
+    package="com.example">
+    <application>
+        <activity android:name=".MainActivity">
+            <intent-filter>
+                <action android:name="android.intent.action.MAIN" />
+                <category android:name="android.intent.category.LAUNCHER" />
+            </intent-filter>
+        </activity>
+        <activity android:name=".ItemActivity">
+            <intent-filter>
+                <action android:name="android.intent.action.VIEW" />
+                <category android:name="android.intent.category.BROWSABLE" />
+                <data android:scheme="myapp" android:host="item" />
+            </intent-filter>
+        </activity>
+        <activity android:name=".PromoActivity">
+            <intent-filter android:autoVerify="true">
+                <action android:name="android.intent.action.VIEW" />
+                <data android:scheme="https" />
+                <data android:host="example.com" />
+                <data android:pathPrefix="/promo" />
+            </intent-filter>
+        </activity>
+    </application>
+</manifest>"#;
+
+    const NAV_GRAPH: &str = r#"<navigation xmlns:android="http://schemas.android.com/apk/res/android"
+    xmlns:app="http://schemas.android.com/apk/res-auto">
+    <fragment android:id="@+id/profile" android:name="com.example.ProfileFragment">
+        <deepLink app:uri="myapp://profile/{userId}" />
+    </fragment>
+    <fragment android:id="@+id/settings" android:name="com.example.SettingsFragment">
+        <deepLink app:uri="example.com/settings" />
+    </fragment>
+</navigation>"#;
+
+    fn scan(sources: &[(&str, &str)]) -> DeepLinkAnalysis {
+        let detector = DeepLinkDetector::new();
+        let mut scan = DeepLinkScan::default();
+        detector.parse_manifest(Path::new("AndroidManifest.xml"), MANIFEST, &mut scan);
+        detector.parse_navigation_xml(Path::new("navigation/main.xml"), NAV_GRAPH, &mut scan);
+        for (path, content) in sources {
+            detector.collect_compose_routes(Path::new(path), content, &mut scan);
+            detector.collect_opened(Path::new(path), content, &mut scan);
+            scan.sources
+                .push((PathBuf::from(path), content.to_string()));
+        }
+        DeepLinkDetector::build_analysis(scan)
+    }
+
+    #[test]
+    fn test_routes() {
+        let analysis = scan(&[]);
+        let uris: Vec<&str> = analysis.routes.iter().map(|r| r.uri.as_str()).collect();
+        assert!(uris.contains(&"myapp://item"));
+        assert!(uris.contains(&"https://example.com/promo*"));
+        assert!(uris.contains(&"myapp://profile/{userId}"));
+
+        let item = analysis
+            .routes
+            .iter()
+            .find(|r| r.uri == "myapp://item")
+            .unwrap();
+        assert_eq!(item.handler, "com.example.ItemActivity");
+        assert_eq!(item.line, 12);
+        assert!(item.matches("myapp://item/42?ref=push"));
+        assert!(!item.matches("myapp://cart"));
+
+        let settings: Vec<&DeepLinkRoute> = analysis
+            .routes
+            .iter()
+            .filter(|r| r.uri == "example.com/settings")
+            .collect();
+        assert_eq!(settings.len(), 2);
+        assert!(settings
+            .iter()
+            .any(|r| r.matches("https://example.com/settings")));
+    }
+
+    #[test]
+    fn test_unopened_and_unhandled() {
+        let analysis = scan(&[(
+            "Links.kt",
+            r#"
+fun openProfile(id: String) = Uri.parse("myapp://profile/$id")
+fun openItem(id: Int) = Uri.parse("myapp://item/" + id)
+fun openCart() = Uri.parse("myapp://cart")
+fun docs() = Uri.parse("https://developer.android.com/guide")
+"#,
+        )]);
+
+        let unhandled: Vec<&str> = analysis
+            .unhandled_links
+            .iter()
+            .map(|l| l.uri.as_str())
+            .collect();
+        assert_eq!(unhandled, vec!["myapp://cart"]);
+
+        let unopened: Vec<&str> = analysis
+            .unopened_routes
+            .iter()
+            .map(|r| r.uri.as_str())
+            .collect();
+        assert!(unopened.contains(&"https://example.com/promo*"));
+        assert!(unopened.contains(&"example.com/settings"));
+        assert!(!unopened.contains(&"myapp://item"));
+        assert!(!unopened.contains(&"myapp://profile/{userId}"));
+    }
+
+    #[test]
+    fn test_deep_link_only_activities() {
+        let analysis = scan(&[
+            (
+                "ItemActivity.kt",
+                "class ItemActivity : AppCompatActivity()",
+            ),
+            (
+                "Home.kt",
+                "fun promo(context: Context) = Intent(context, PromoActivity::class.java)",
+            ),
+        ]);
+        let handlers: Vec<&str> = analysis
+            .deep_link_only
+            .iter()
+            .map(|r| r.handler.as_str())
+            .collect();
+        assert_eq!(handlers, vec!["com.example.ItemActivity"]);
+    }
+
+    #[test]
+    fn test_compose_routes() {
+        let analysis = scan(&[(
+            "NavHost.kt",
+            r#"
+composable(
+    route = "order/{id}",
+    deepLinks = listOf(navDeepLink { uriPattern = "$BASE_URI/order/{id}" })
+)
+fun share() = "myapp://orders/order/17"
+"#,
+        )]);
+        let order = analysis
+            .routes
+            .iter()
+            .find(|r| r.uri.ends_with("/order/{id}"))
+            .unwrap();
+        assert_eq!(order.handler, "NavHost");
+        assert!(order.matches("myapp://orders/order/17"));
+    }
+}
@@ -0,0 +1,173 @@
+//! Key tracking shared by the detectors that match string keys written in one
+//! place with the reads of the same key elsewhere (preferences, extras)
+//!
+//! Keys are written as literals or as constants, possibly defined in another
+//! file (`prefs.putString(Keys.TOKEN, ...)`), so the string constants of the
+//! project are collected first and key arguments resolved through them.
+
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Where a key is written, read or defined
+#[derive(Debug, Clone)]
+pub struct KeyLocation {
+    pub file: PathBuf,
+    pub line: usize,
+    pub key: String,
+}
+
+/// String constants of the project, by simple name
+#[derive(Debug)]
+pub struct KeyConstants {
+    // `const val NAME = "value"` / `static final String NAME = "value"`
+    pattern: Regex,
+    /// None when the name is defined with different values
+    values: HashMap<String, Option<String>>,
+}
+
+impl KeyConstants {
+    pub fn new() -> Self {
+        Self {
+            pattern: Regex::new(
+                r#"(?:const\s+val|static\s+final\s+String)\s+(\w+)\s*(?::\s*String\s*)?=\s*"([^"]*)""#,
+            )
+            .unwrap(),
+            values: HashMap::new(),
+        }
+    }
+
+    /// Collect the string constants of a source file
+    pub fn collect(&mut self, source: &str) {
+        for caps in self.pattern.captures_iter(source) {
+            let value = caps[2].to_string();
+            self.values
+                .entry(caps[1].to_string())
+                .and_modify(|existing| {
+                    if existing.as_ref() != Some(&value) {
+                        *existing = None;
+                    }
+                })
+                .or_insert(Some(value));
+        }
+    }
+
+    /// Value of a constant as written in code: `KEY_TOKEN`, `Keys.KEY_TOKEN`
+    pub fn lookup(&self, name: &str) -> Option<&str> {
+        let name = name.rsplit('.').next().unwrap_or(name);
+        self.values.get(name)?.as_deref()
+    }
+
+    /// The key captured by a pattern whose group 1 is a string literal and
+    /// group 2 a constant name
+    pub fn resolve(&self, caps: &Captures) -> Option<String> {
+        if let Some(literal) = caps.get(1) {
+            return Some(literal.as_str().to_string());
+        }
+        self.lookup(caps.get(2)?.as_str()).map(str::to_string)
+    }
+}
+
+impl Default for KeyConstants {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writes and reads of keys, by key
+#[derive(Debug, Default)]
+pub struct KeyUsage {
+    pub writes: HashMap<String, Vec<KeyLocation>>,
+    pub reads: HashMap<String, Vec<KeyLocation>>,
+}
+
+impl KeyUsage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_write(&mut self, key: String, file: &Path, line: usize) {
+        record(&mut self.writes, key, file, line);
+    }
+
+    pub fn add_read(&mut self, key: String, file: &Path, line: usize) {
+        record(&mut self.reads, key, file, line);
+    }
+
+    pub fn is_read(&self, key: &str) -> bool {
+        self.reads.contains_key(key)
+    }
+
+    pub fn is_written(&self, key: &str) -> bool {
+        self.writes.contains_key(key)
+    }
+
+    /// The first write of each key that is never read, in file and line order
+    pub fn unread(&self) -> Vec<KeyLocation> {
+        let mut unread: Vec<KeyLocation> = self
+            .writes
+            .iter()
+            .filter(|(key, _)| !self.is_read(key))
+            .filter_map(|(_, locations)| locations.first().cloned())
+            .collect();
+        sort_locations(&mut unread);
+        unread
+    }
+}
+
+fn record(keys: &mut HashMap<String, Vec<KeyLocation>>, key: String, file: &Path, line: usize) {
+    keys.entry(key.clone()).or_default().push(KeyLocation {
+        file: file.to_path_buf(),
+        line,
+        key,
+    });
+}
+
+/// Sort key locations by file and line
+pub fn sort_locations(locations: &mut [KeyLocation]) {
+    locations.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_constants() {
+        let mut constants = KeyConstants::new();
+        constants.collect(
+            r#"
+            object Keys {
+                const val TOKEN = "auth_token"
+                const val LEGACY: String = "legacy"
+            }
+            "#,
+        );
+        constants.collect(r#"static final String TOKEN = "auth_token";"#);
+        constants.collect(r#"private static final String LEGACY = "other";"#);
+
+        assert_eq!(constants.lookup("Keys.TOKEN"), Some("auth_token"));
+        assert_eq!(constants.lookup("TOKEN"), Some("auth_token"));
+        // Conflicting definitions never resolve
+        assert_eq!(constants.lookup("LEGACY"), None);
+        assert_eq!(constants.lookup("MISSING"), None);
+    }
+
+    #[test]
+    fn test_unread_keys() {
+        let mut usage = KeyUsage::new();
+        let file = PathBuf::from("Prefs.kt");
+        usage.add_write("b".to_string(), &file, 9);
+        usage.add_write("a".to_string(), &file, 3);
+        usage.add_write("a".to_string(), &file, 4);
+        usage.add_write("read".to_string(), &file, 5);
+        usage.add_read("read".to_string(), &file, 12);
+
+        let unread: Vec<(String, usize)> = usage
+            .unread()
+            .into_iter()
+            .map(|loc| (loc.key, loc.line))
+            .collect();
+        assert_eq!(unread, vec![("a".to_string(), 3), ("b".to_string(), 9)]);
+    }
+}
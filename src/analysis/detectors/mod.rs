@@ -4,36 +4,66 @@
 #![allow(unused_imports)]
 
 mod assign_only;
+mod compose_state;
+mod custom_rule;
 mod dead_branch;
+mod deep_link;
 mod ignored_return;
+mod key_usage;
+mod plugin;
+mod property_accessor;
 mod redundant_override;
 mod redundant_public;
+mod registry;
 mod sealed_variant;
+mod uninvoked_param;
+mod unused_broadcast_action;
+mod unused_bundle_argument;
 mod unused_class;
 mod unused_enum_case;
 mod unused_import;
 mod unused_intent_extra;
 mod unused_method;
+mod unused_nav_argument;
 mod unused_param;
+mod unused_pref_key;
 mod unused_property;
+mod unused_typealias;
 mod write_only;
 mod write_only_dao;
 mod write_only_prefs;
 
 // These detectors are reserved for future advanced analysis modes
 pub use assign_only::AssignOnlyDetector;
-pub use dead_branch::DeadBranchDetector;
+pub use compose_state::ComposeStateDetector;
+pub use custom_rule::CustomRuleDetector;
+pub use dead_branch::{ConstValue, ConstantTable, DeadBranch, DeadBranchDetector};
+pub use deep_link::{DeepLinkAnalysis, DeepLinkDetector, DeepLinkLocation, DeepLinkRoute};
 pub use ignored_return::IgnoredReturnValueDetector;
+pub use key_usage::{KeyConstants, KeyLocation, KeyUsage};
+pub use plugin::{PluginDetector, PLUGIN_PROTOCOL_VERSION};
+pub use property_accessor::PropertyAccessorDetector;
 pub use redundant_override::RedundantOverrideDetector;
 pub use redundant_public::RedundantPublicDetector;
+pub use registry::{DetectorInput, DetectorRegistration, DetectorRegistry};
 pub use sealed_variant::UnusedSealedVariantDetector;
+pub use uninvoked_param::UninvokedParamDetector;
+pub use unused_broadcast_action::{
+    ActionLocation, BroadcastActionAnalysis, UnusedBroadcastActionDetector,
+};
+pub use unused_bundle_argument::{BundleArgumentAnalysis, UnusedBundleArgumentDetector};
 pub use unused_class::UnusedClassDetector;
 pub use unused_enum_case::UnusedEnumCaseDetector;
-pub use unused_import::UnusedImportDetector;
+pub use unused_import::{ImportDirective, UnusedImportDetector};
 pub use unused_intent_extra::{ExtraLocation, IntentExtraAnalysis, UnusedIntentExtraDetector};
 pub use unused_method::UnusedMethodDetector;
+pub use unused_nav_argument::{
+    NavAction, NavArgument, NavDestination, NavigationAnalysis, UnusedNavArgumentDetector,
+};
 pub use unused_param::UnusedParamDetector;
+pub use unused_pref_key::{PrefKeyAnalysis, UnusedPrefKeyDetector};
 pub use unused_property::UnusedPropertyDetector;
+pub use unused_typealias::UnusedTypeAliasDetector;
 pub use write_only::WriteOnlyDetector;
 pub use write_only_dao::{DaoAnalysis, DaoCollectionAnalysis, WriteOnlyDaoDetector};
 pub use write_only_prefs::{SharedPrefsAnalysis, WriteOnlyPrefsDetector};
@@ -0,0 +1,285 @@
+//! External Detector Plugins
+//!
+//! Runs detectors shipped outside this crate as separate programs. The
+//! plugin receives the reference graph as one JSON document on stdin and
+//! writes its findings as one JSON document to stdout; anything it prints to
+//! stderr is passed through. A non-zero exit status fails the plugin.
+//!
+//! ## Protocol (version 1)
+//!
+//! Request, on the plugin's stdin:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "root": "/path/to/project",
+//!   "declarations": [{ "id": { "file": "...", "start": 120, "end": 480 }, "name": "...", ... }],
+//!   "references": [{ "from": { ... }, "to": { ... }, "kind": "Call", "line": 12 }],
+//!   "entry_points": [{ "file": "...", "start": 0, "end": 900 }]
+//! }
+//! ```
+//!
+//! Declarations are serialized as the tool stores them. Response, on stdout:
+//!
+//! ```json
+//! {
+//!   "findings": [
+//!     { "id": { "file": "...", "start": 120, "end": 480 },
+//!       "message": "Legacy analytics call", "severity": "warning", "confidence": "high" }
+//!   ]
+//! }
+//! ```
+//!
+//! `id` is a declaration id from the request; `message`, `severity`
+//! (`info`, `warning`, `error`) and `confidence` (`low`, `medium`, `high`,
+//! `confirmed`) are optional. Findings are reported as custom rule findings.
+
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue, Severity};
+use crate::config::PluginConfig;
+use crate::graph::{Declaration, DeclarationId, Graph, ReferenceKind};
+use miette::{miette, IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Version of the plugin protocol sent with every request
+pub const PLUGIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Graph sent to a plugin
+#[derive(Serialize)]
+struct PluginRequest<'a> {
+    version: u32,
+    root: &'a Path,
+    declarations: Vec<&'a Declaration>,
+    references: Vec<PluginReference<'a>>,
+    entry_points: Vec<&'a DeclarationId>,
+}
+
+#[derive(Serialize)]
+struct PluginReference<'a> {
+    from: &'a DeclarationId,
+    to: &'a DeclarationId,
+    kind: ReferenceKind,
+    line: usize,
+}
+
+/// Findings returned by a plugin
+#[derive(Debug, Deserialize)]
+struct PluginResponse {
+    #[serde(default)]
+    findings: Vec<PluginFinding>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginFinding {
+    id: DeclarationId,
+    message: Option<String>,
+    severity: Option<Severity>,
+    confidence: Option<Confidence>,
+}
+
+/// Detector running an external program over the graph
+pub struct PluginDetector {
+    plugin: PluginConfig,
+}
+
+impl PluginDetector {
+    pub fn new(plugin: PluginConfig) -> Self {
+        Self { plugin }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.plugin.name
+    }
+
+    /// Send the graph to the plugin and collect its findings
+    pub fn run(
+        &self,
+        graph: &Graph,
+        entry_points: &HashSet<DeclarationId>,
+        root: &Path,
+    ) -> Result<Vec<DeadCode>> {
+        let request =
+            serde_json::to_vec(&build_request(graph, entry_points, root)).into_diagnostic()?;
+
+        // Relative paths like `./tools/check` are relative to the project
+        let command = if self.plugin.command.contains('/') {
+            root.join(&self.plugin.command)
+        } else {
+            self.plugin.command.clone().into()
+        };
+        let mut child = Command::new(command)
+            .args(&self.plugin.args)
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| miette!("Failed to start plugin '{}': {}", self.plugin.command, e))?;
+
+        // Write from another thread so a plugin answering while it reads
+        // cannot fill the stdout pipe and block both sides
+        let mut stdin = child.stdin.take().expect("piped stdin");
+        let writer = std::thread::spawn(move || stdin.write_all(&request));
+        let output = child.wait_with_output().into_diagnostic()?;
+        // A plugin may exit without reading the whole graph
+        let _ = writer.join();
+
+        if !output.status.success() {
+            return Err(miette!(
+                "Plugin '{}' exited with {}",
+                self.plugin.name,
+                output.status
+            ));
+        }
+        self.parse_response(&output.stdout, graph)
+    }
+
+    /// Turn a plugin response into findings on the graph's declarations
+    fn parse_response(&self, stdout: &[u8], graph: &Graph) -> Result<Vec<DeadCode>> {
+        let response: PluginResponse = serde_json::from_slice(stdout).map_err(|e| {
+            miette!(
+                "Plugin '{}' returned invalid findings: {}",
+                self.plugin.name,
+                e
+            )
+        })?;
+
+        let mut findings = Vec::new();
+        for finding in response.findings {
+            let Some(decl) = graph.get_declaration(&finding.id) else {
+                return Err(miette!(
+                    "Plugin '{}' reported an unknown declaration {}",
+                    self.plugin.name,
+                    finding.id
+                ));
+            };
+            let message = finding.message.unwrap_or_else(|| {
+                format!(
+                    "{} '{}' is reported by plugin '{}'",
+                    decl.kind.display_name(),
+                    decl.name,
+                    self.plugin.name
+                )
+            });
+            let mut dc = DeadCode::new(decl.clone(), DeadCodeIssue::CustomRule)
+                .with_message(message)
+                .with_severity(finding.severity.unwrap_or(Severity::Warning));
+            if let Some(confidence) = finding.confidence {
+                dc = dc.with_confidence(confidence);
+            }
+            findings.push(dc);
+        }
+        Ok(findings)
+    }
+}
+
+fn build_request<'a>(
+    graph: &'a Graph,
+    entry_points: &'a HashSet<DeclarationId>,
+    root: &'a Path,
+) -> PluginRequest<'a> {
+    let mut declarations: Vec<&Declaration> = graph.declarations().collect();
+    declarations.sort_by(|a, b| a.id.file.cmp(&b.id.file).then(a.id.start.cmp(&b.id.start)));
+
+    let references = declarations
+        .iter()
+        .flat_map(|from| {
+            graph
+                .get_references_from(&from.id)
+                .into_iter()
+                .map(move |(to, reference)| PluginReference {
+                    from: &from.id,
+                    to: &to.id,
+                    kind: reference.kind,
+                    line: reference.location.line,
+                })
+        })
+        .collect();
+
+    let mut entry_points: Vec<&DeclarationId> = entry_points.iter().collect();
+    entry_points.sort_by(|a, b| a.file.cmp(&b.file).then(a.start.cmp(&b.start)));
+
+    PluginRequest {
+        version: PLUGIN_PROTOCOL_VERSION,
+        root,
+        declarations,
+        references,
+        entry_points,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationKind, Language, Location, Reference};
+    use std::path::PathBuf;
+
+    fn graph() -> (Graph, DeclarationId, DeclarationId) {
+        let mut graph = Graph::new();
+        let file = PathBuf::from("Tracker.kt");
+        let mut declare = |name: &str, start: usize| {
+            graph.add_declaration(Declaration::new(
+                DeclarationId::new(file.clone(), start, start + 50),
+                name.to_string(),
+                DeclarationKind::Function,
+                Location::new(file.clone(), start / 100 + 1, 1, start, start + 50),
+                Language::Kotlin,
+            ))
+        };
+        let main = declare("main", 0);
+        let track = declare("trackLegacy", 100);
+        let location = Location::new(file.clone(), 1, 1, 10, 20);
+        graph.add_reference(
+            &main,
+            &track,
+            Reference::new(ReferenceKind::Call, location, "trackLegacy".to_string()),
+        );
+        (graph, main, track)
+    }
+
+    #[test]
+    fn test_plugin_request() {
+        let (graph, main, _) = graph();
+        let entry_points: HashSet<DeclarationId> = [main].into_iter().collect();
+        let request = build_request(&graph, &entry_points, Path::new("/project"));
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["declarations"].as_array().unwrap().len(), 2);
+        assert_eq!(json["declarations"][1]["name"], "trackLegacy");
+        assert_eq!(json["references"][0]["kind"], "Call");
+        assert_eq!(json["references"][0]["to"]["start"], 100);
+        assert_eq!(json["entry_points"][0]["start"], 0);
+    }
+
+    #[test]
+    fn test_plugin_response() {
+        let (graph, _, track) = graph();
+        let detector = PluginDetector::new(PluginConfig {
+            name: "legacy-analytics".to_string(),
+            command: "legacy-analytics".to_string(),
+            args: Vec::new(),
+        });
+
+        let stdout = format!(
+            r#"{{"findings": [{{"id": {}, "severity": "error", "confidence": "high"}}]}}"#,
+            serde_json::to_string(&track).unwrap()
+        );
+        let findings = detector.parse_response(stdout.as_bytes(), &graph).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].declaration.name, "trackLegacy");
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].confidence, Confidence::High);
+        assert_eq!(
+            findings[0].message,
+            "function 'trackLegacy' is reported by plugin 'legacy-analytics'"
+        );
+
+        let unknown = r#"{"findings": [{"id": {"file": "Other.kt", "start": 1, "end": 2}}]}"#;
+        assert!(detector.parse_response(unknown.as_bytes(), &graph).is_err());
+        assert!(detector.parse_response(b"not json", &graph).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+//! Property Accessor Detector
+//!
+//! Looks at how Kotlin properties are used rather than whether they are used:
+//! a `var` that is never reassigned could be a `val`, and a custom accessor
+//! is dead when the property is only ever read (setter) or only ever written
+//! (getter).
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Find private Kotlin properties declared with `var` or custom accessors
+//!    (others may be written from outside the project)
+//! 2. Count writes (assignments, `++`/`--`, Java `setX()` calls) and reads
+//!    (every other reference, including Java `getX()` calls)
+//! 3. Report:
+//!    - a `var` that is read but never written, and has no custom setter, as
+//!      [`DeadCodeIssue::VarCouldBeVal`]
+//!    - a custom setter of a property that is read but never written as
+//!      [`DeadCodeIssue::UnusedSetter`]
+//!    - a custom getter of a property that is written but never read as
+//!      [`DeadCodeIssue::UnusedGetter`]
+//!
+//! Properties with no references at all are left to the unreferenced findings.
+//! `lateinit`, delegated, annotated (injected) and overridable properties are
+//! assigned in ways the graph does not see, so they are never reported.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! class Session {
+//!     private var token = load()  // could be val: never reassigned
+//!
+//!     private var name: String = ""
+//!         set(value) { field = value.trim() }  // DEAD: name is never written
+//!
+//!     fun header() = "$token for $name"
+//! }
+//! ```
+
+use super::Detector;
+use crate::analysis::{DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationKind, Graph, Language, ReferenceKind, Visibility};
+
+/// Modifiers of properties assigned outside the code the graph sees
+const SKIPPED_MODIFIERS: &[&str] = &[
+    "lateinit",
+    "delegated",
+    "override",
+    "open",
+    "abstract",
+    "expect",
+    "actual",
+];
+
+/// Detector for `var` properties that could be `val` and unused custom accessors
+pub struct PropertyAccessorDetector {
+    /// Only check private properties (others might be written externally)
+    private_only: bool,
+}
+
+impl PropertyAccessorDetector {
+    pub fn new() -> Self {
+        Self { private_only: true }
+    }
+
+    /// Include non-private properties in detection (use with caution)
+    #[allow(dead_code)]
+    pub fn include_public(mut self) -> Self {
+        self.private_only = false;
+        self
+    }
+
+    /// Check if a declaration is a property whose accessors can be checked
+    fn is_candidate(&self, decl: &Declaration) -> bool {
+        if decl.kind != DeclarationKind::Property || decl.language != Language::Kotlin {
+            return false;
+        }
+        if self.private_only && decl.visibility != Visibility::Private {
+            return false;
+        }
+        if decl.is_abstract || !decl.annotations.is_empty() {
+            return false;
+        }
+
+        !decl
+            .modifiers
+            .iter()
+            .any(|m| SKIPPED_MODIFIERS.contains(&m.as_str()))
+    }
+
+    /// Count the reads and writes of a property
+    fn count_accesses(&self, decl: &Declaration, graph: &Graph) -> Option<(usize, usize)> {
+        let (mut reads, mut writes) = (0, 0);
+        for (_, reference) in graph.get_references_to(&decl.id) {
+            // `::name` can be used to set the property
+            if reference.kind == ReferenceKind::Reflection {
+                return None;
+            }
+
+            // Java reaches Kotlin properties through their accessors
+            let java_setter = reference.name != decl.name && reference.name.starts_with("set");
+            if reference.kind.is_write() || java_setter {
+                writes += 1;
+            } else {
+                reads += 1;
+            }
+        }
+        Some((reads, writes))
+    }
+}
+
+impl Default for PropertyAccessorDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Detector for PropertyAccessorDetector {
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        let mut issues = Vec::new();
+
+        for decl in graph.declarations() {
+            if !self.is_candidate(decl) {
+                continue;
+            }
+            let Some((reads, writes)) = self.count_accesses(decl, graph) else {
+                continue;
+            };
+            let has = |modifier: &str| decl.modifiers.iter().any(|m| m == modifier);
+
+            let issue = if has("custom-setter") {
+                (writes == 0 && reads > 0).then_some(DeadCodeIssue::UnusedSetter)
+            } else if has("var") && writes == 0 && reads > 0 {
+                Some(DeadCodeIssue::VarCouldBeVal)
+            } else {
+                None
+            };
+            let issue = issue.or_else(|| {
+                (has("custom-getter") && reads == 0 && writes > 0)
+                    .then_some(DeadCodeIssue::UnusedGetter)
+            });
+
+            if let Some(issue) = issue {
+                issues.push(DeadCode::new(decl.clone(), issue));
+            }
+        }
+
+        // Sort by file and line for consistent output
+        issues.sort_by(|a, b| {
+            let a = &a.declaration.location;
+            let b = &b.declaration.location;
+            a.file.cmp(&b.file).then(a.line.cmp(&b.line))
+        });
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, Location, Reference};
+    use std::path::PathBuf;
+
+    fn property(graph: &mut Graph, name: &str, line: usize, modifiers: &[&str]) -> DeclarationId {
+        let file = PathBuf::from("Session.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            DeclarationKind::Property,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        );
+        decl.visibility = Visibility::Private;
+        decl.modifiers = modifiers.iter().map(|m| m.to_string()).collect();
+        graph.add_declaration(decl)
+    }
+
+    fn access(graph: &mut Graph, from: &DeclarationId, to: &DeclarationId, kind: ReferenceKind) {
+        let name = graph.get_declaration(to).unwrap().name.clone();
+        let location = Location::new(PathBuf::from("Session.kt"), 90, 1, 9000, 9010);
+        graph.add_reference(from, to, Reference::new(kind, location, name));
+    }
+
+    #[test]
+    fn test_property_accessors() {
+        let mut graph = Graph::new();
+        let header = property(&mut graph, "header", 90, &[]);
+        let token = property(&mut graph, "token", 1, &["private", "var"]);
+        let count = property(&mut graph, "count", 2, &["private", "var"]);
+        let name = property(&mut graph, "name", 3, &["private", "var", "custom-setter"]);
+        let cache = property(&mut graph, "cache", 4, &["private", "var", "custom-getter"]);
+        let view = property(&mut graph, "view", 5, &["private", "lateinit", "var"]);
+
+        access(&mut graph, &header, &token, ReferenceKind::Read);
+        access(&mut graph, &header, &count, ReferenceKind::Read);
+        access(&mut graph, &header, &count, ReferenceKind::Write);
+        access(&mut graph, &header, &name, ReferenceKind::Read);
+        access(&mut graph, &header, &cache, ReferenceKind::Write);
+        access(&mut graph, &header, &view, ReferenceKind::Read);
+
+        let issues = PropertyAccessorDetector::new().detect(&graph);
+        let found: Vec<(&str, DeadCodeIssue)> = issues
+            .iter()
+            .map(|dc| (dc.declaration.name.as_str(), dc.issue))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("token", DeadCodeIssue::VarCouldBeVal),
+                ("name", DeadCodeIssue::UnusedSetter),
+                ("cache", DeadCodeIssue::UnusedGetter),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_java_setter_is_a_write() {
+        let mut graph = Graph::new();
+        let caller = property(&mut graph, "caller", 90, &[]);
+        let token = property(&mut graph, "token", 1, &["var"]);
+        access(&mut graph, &caller, &token, ReferenceKind::Read);
+        let location = Location::new(PathBuf::from("Login.java"), 3, 1, 30, 38);
+        graph.add_reference(
+            &caller,
+            &token,
+            Reference::new(ReferenceKind::Call, location, "setToken".to_string()),
+        );
+
+        let detector = PropertyAccessorDetector::new().include_public();
+        assert!(detector.detect(&graph).is_empty());
+    }
+}
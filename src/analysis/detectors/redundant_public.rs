@@ -1,20 +1,332 @@
+//! Redundant Public Detector
+//!
+//! Detects public declarations (explicitly `public`, or Kotlin's default) that
+//! are only used close to where they are declared, and suggests the narrowest
+//! visibility that keeps every use compiling.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Find public declarations that are used, skipping overrides, open or
+//!    abstract members, interface members, entry points and annotated code
+//! 2. Suggest `private` when every use is inside the declaring class (members)
+//!    or file (Kotlin top-level declarations)
+//! 3. Otherwise suggest Kotlin `internal` when every use is in the same Gradle
+//!    module and none comes from Java (internal members get mangled JVM names)
+//! 4. Drop the suggestion if it would make a more visible signature or public
+//!    inline function expose the declaration
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! class Cart {
+//!     fun total() = items.sumOf { price(it) }
+//!     fun price(item: Item) = item.cost * item.count   // Could be private
+//! }
+//! ```
+
 use super::Detector;
-use crate::analysis::DeadCode;
-use crate::graph::Graph;
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{
+    Declaration, DeclarationId, DeclarationKind, Graph, Language, ReferenceKind, Visibility,
+};
+use std::path::{Path, PathBuf};
 
+/// Detector for public declarations that could be private or internal
 pub struct RedundantPublicDetector;
+
 impl RedundantPublicDetector {
     pub fn new() -> Self {
         Self
     }
-}
-impl Detector for RedundantPublicDetector {
-    fn detect(&self, _graph: &Graph) -> Vec<DeadCode> {
-        Vec::new()
+
+    /// Check if a declaration should be skipped
+    fn should_skip(&self, decl: &Declaration, graph: &Graph) -> bool {
+        if decl.visibility != Visibility::Public
+            || !matches!(
+                decl.kind,
+                DeclarationKind::Class
+                    | DeclarationKind::Interface
+                    | DeclarationKind::Object
+                    | DeclarationKind::Enum
+                    | DeclarationKind::TypeAlias
+                    | DeclarationKind::Function
+                    | DeclarationKind::Method
+                    | DeclarationKind::Property
+                    | DeclarationKind::Field
+            )
+        {
+            return true;
+        }
+
+        // Overridable or overriding members are part of a type's contract
+        if decl.is_abstract
+            || decl.modifiers.iter().any(|m| {
+                matches!(
+                    m.as_str(),
+                    "override" | "open" | "abstract" | "expect" | "actual" | "external"
+                )
+            })
+        {
+            return true;
+        }
+
+        // Frameworks, reflection and generated code find annotated declarations
+        if !decl.annotations.is_empty() || decl.is_android_entry_point() {
+            return true;
+        }
+
+        let parent = decl
+            .parent
+            .as_ref()
+            .and_then(|id| graph.get_declaration(id));
+        match parent {
+            Some(parent) => {
+                parent.kind == DeclarationKind::Interface
+                    // Java methods may implement an interface without @Override
+                    || (decl.language == Language::Java && !parent.super_types.is_empty())
+            }
+            // Java top-level classes can't be private
+            None => decl.language == Language::Java,
+        }
+    }
+
+    /// The narrowest visibility every use of the declaration allows, if it
+    /// is narrower than public
+    pub fn narrowest_visibility(&self, decl: &Declaration, graph: &Graph) -> Option<Visibility> {
+        if self.should_skip(decl, graph) {
+            return None;
+        }
+
+        let references = graph.get_references_to(&decl.id);
+        if references.is_empty()
+            || references
+                .iter()
+                .any(|(_, reference)| reference.kind == ReferenceKind::Reflection)
+        {
+            return None;
+        }
+
+        // Private members are visible in their class, top-level ones in their file
+        let scope_contains = |from: &Declaration| match &decl.parent {
+            Some(parent) => is_within(graph, from, parent),
+            None => from.location.file == decl.location.file,
+        };
+        let module = module_dir(&decl.location.file);
+
+        let visibility = if references.iter().all(|(from, _)| scope_contains(from)) {
+            Visibility::Private
+        } else if decl.language == Language::Kotlin
+            && references.iter().all(|(from, _)| {
+                from.language == Language::Kotlin && module_dir(&from.location.file) == module
+            })
+        {
+            Visibility::Internal
+        } else {
+            return None;
+        };
+
+        // A user that stays more visible must not expose the declaration
+        let exposes = references.iter().any(|(from, reference)| {
+            if is_within(graph, from, &decl.id) || rank(effective(graph, from)) <= rank(visibility)
+            {
+                return false;
+            }
+            from.modifiers.iter().any(|m| m == "inline")
+                || (decl.kind.is_type() && is_signature_reference(reference.kind))
+        });
+
+        (!exposes).then_some(visibility)
     }
 }
+
 impl Default for RedundantPublicDetector {
     fn default() -> Self {
         Self::new()
     }
 }
+
+impl Detector for RedundantPublicDetector {
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        let mut issues: Vec<DeadCode> = graph
+            .declarations()
+            .filter_map(|decl| {
+                let visibility = self.narrowest_visibility(decl, graph)?;
+                let (keyword, scope) = match (visibility, &decl.parent) {
+                    (Visibility::Private, Some(_)) => ("private", "in its class"),
+                    (Visibility::Private, None) => ("private", "in this file"),
+                    _ => ("internal", "in this module"),
+                };
+                Some(
+                    DeadCode::new(decl.clone(), DeadCodeIssue::RedundantPublic)
+                        .with_message(format!(
+                            "{} '{}' could be {} (only used {})",
+                            decl.kind.display_name(),
+                            decl.name,
+                            keyword,
+                            scope
+                        ))
+                        .with_confidence(Confidence::Medium),
+                )
+            })
+            .collect();
+
+        // Sort by file and line for consistent output
+        issues.sort_by(|a, b| {
+            a.declaration
+                .location
+                .file
+                .cmp(&b.declaration.location.file)
+                .then(
+                    a.declaration
+                        .location
+                        .line
+                        .cmp(&b.declaration.location.line),
+                )
+        });
+
+        issues
+    }
+}
+
+/// Whether `decl` is `ancestor` or nested in it
+fn is_within(graph: &Graph, decl: &Declaration, ancestor: &DeclarationId) -> bool {
+    let mut current = Some(decl);
+    while let Some(d) = current {
+        if &d.id == ancestor {
+            return true;
+        }
+        current = d.parent.as_ref().and_then(|id| graph.get_declaration(id));
+    }
+    false
+}
+
+/// Visibility of a declaration once its enclosing declarations are considered
+fn effective(graph: &Graph, decl: &Declaration) -> Visibility {
+    let mut visibility = decl.visibility;
+    let mut parent = decl
+        .parent
+        .as_ref()
+        .and_then(|id| graph.get_declaration(id));
+    while let Some(p) = parent {
+        if rank(p.visibility) < rank(visibility) {
+            visibility = p.visibility;
+        }
+        parent = p.parent.as_ref().and_then(|id| graph.get_declaration(id));
+    }
+    visibility
+}
+
+fn rank(visibility: Visibility) -> u8 {
+    match visibility {
+        Visibility::Private => 0,
+        Visibility::Internal | Visibility::PackagePrivate => 1,
+        Visibility::Protected => 2,
+        Visibility::Public => 3,
+    }
+}
+
+/// References that put a type in a declaration's signature
+fn is_signature_reference(kind: ReferenceKind) -> bool {
+    matches!(
+        kind,
+        ReferenceKind::Type
+            | ReferenceKind::Inheritance
+            | ReferenceKind::TypeArgument
+            | ReferenceKind::ReturnType
+            | ReferenceKind::ParameterType
+            | ReferenceKind::GenericArgument
+            | ReferenceKind::SealedSubtype
+            | ReferenceKind::ExtensionReceiver
+    )
+}
+
+/// Gradle module of a source file: the directory holding its `src/`
+fn module_dir(path: &Path) -> Option<PathBuf> {
+    let components: Vec<_> = path.components().collect();
+    let src = components.iter().rposition(|c| c.as_os_str() == "src")?;
+    Some(components[..src].iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Location, Reference};
+
+    fn add(
+        graph: &mut Graph,
+        file: &str,
+        name: &str,
+        kind: DeclarationKind,
+        parent: Option<&Declaration>,
+    ) -> Declaration {
+        let path = PathBuf::from(file);
+        let start = graph.declaration_count() * 20;
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), start, start + 10),
+            name.to_string(),
+            kind,
+            Location::new(path, start / 20 + 1, 1, start, start + 10),
+            if file.ends_with(".java") {
+                Language::Java
+            } else {
+                Language::Kotlin
+            },
+        );
+        decl.parent = parent.map(|p| p.id.clone());
+        graph.add_declaration(decl.clone());
+        decl
+    }
+
+    fn call(graph: &mut Graph, from: &Declaration, to: &Declaration) {
+        graph.add_reference(
+            &from.id,
+            &to.id,
+            Reference::new(ReferenceKind::Call, from.location.clone(), to.name.clone()),
+        );
+    }
+
+    #[test]
+    fn test_suggests_narrowest_visibility() {
+        let mut graph = Graph::new();
+        let (class, method) = (DeclarationKind::Class, DeclarationKind::Method);
+        let cart_kt = "app/src/main/Cart.kt";
+        let cart = add(&mut graph, cart_kt, "Cart", class, None);
+        let total = add(&mut graph, cart_kt, "total", method, Some(&cart));
+        let price = add(&mut graph, cart_kt, "price", method, Some(&cart));
+        let tax = add(&mut graph, cart_kt, "tax", method, Some(&cart));
+        let fee = add(&mut graph, cart_kt, "fee", method, Some(&cart));
+        let screen = add(&mut graph, "app/src/main/Screen.kt", "Screen", class, None);
+        let legacy = add(
+            &mut graph,
+            "app/src/main/Legacy.java",
+            "Legacy",
+            class,
+            None,
+        );
+        let other = add(&mut graph, "lib/src/main/Other.kt", "Other", class, None);
+
+        call(&mut graph, &total, &price);
+        call(&mut graph, &screen, &tax);
+        call(&mut graph, &legacy, &fee);
+        call(&mut graph, &screen, &total);
+        call(&mut graph, &other, &total);
+
+        let detector = RedundantPublicDetector::new();
+        let suggest = |decl: &Declaration| detector.narrowest_visibility(decl, &graph);
+        assert_eq!(suggest(&price), Some(Visibility::Private));
+        assert_eq!(suggest(&tax), Some(Visibility::Internal));
+        assert_eq!(suggest(&fee), None, "Java caller");
+        assert_eq!(suggest(&total), None, "other module");
+
+        let issues = detector.detect(&graph);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            issues[0].message,
+            "method 'price' could be private (only used in its class)"
+        );
+        assert_eq!(
+            issues[1].message,
+            "method 'tax' could be internal (only used in this module)"
+        );
+    }
+}
@@ -0,0 +1,386 @@
+//! Detector Registry
+//!
+//! Every optional detector is registered here with an id, a description, the
+//! severity of its findings and the inputs it needs. The CLI (`--detect`,
+//! `--skip-detect`, `list-detectors`) and the pipeline select detectors by
+//! id, so adding a detector only means adding a registration.
+//!
+//! Graph detectors carry a factory and are run generically by the detector
+//! stage. Analyses that need more than the graph (resource files, coverage)
+//! run in their own pipeline stage and are only selected through the registry.
+//! Per-file detectors only look at one file's declarations and references, so
+//! with `--stream` they run on each file as soon as it is parsed.
+
+use super::{
+    Detector, PropertyAccessorDetector, RedundantOverrideDetector, RedundantPublicDetector,
+    UnusedEnumCaseDetector, UnusedImportDetector, UnusedParamDetector, UnusedSealedVariantDetector,
+    UnusedTypeAliasDetector, WriteOnlyDetector,
+};
+use crate::analysis::Severity;
+
+/// Input a detector reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetectorInput {
+    /// The declaration and reference graph
+    Graph,
+    /// Files on disk besides the parsed sources (resources, navigation graphs)
+    Filesystem,
+    /// Runtime coverage data
+    Coverage,
+}
+
+impl DetectorInput {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DetectorInput::Graph => "graph",
+            DetectorInput::Filesystem => "filesystem",
+            DetectorInput::Coverage => "coverage",
+        }
+    }
+}
+
+/// A registered detector
+#[derive(Clone)]
+pub struct DetectorRegistration {
+    /// Id used by `--detect`, `--skip-detect` and `list-detectors`
+    pub id: &'static str,
+    pub description: &'static str,
+    /// Default severity of the findings
+    pub severity: Severity,
+    pub inputs: &'static [DetectorInput],
+    /// Builds the detector; None for analyses run by their own pipeline stage
+    pub create: Option<fn() -> Box<dyn Detector>>,
+    /// Findings replace generic unreferenced findings for the same declaration
+    pub supersedes_unreferenced: bool,
+    /// Runs unless skipped with `--skip-detect`
+    pub enabled_by_default: bool,
+    /// Findings in a file only depend on that file's declarations and references
+    pub per_file: bool,
+}
+
+impl DetectorRegistration {
+    /// Registration of an analysis that runs in its own pipeline stage
+    pub fn new(
+        id: &'static str,
+        description: &'static str,
+        severity: Severity,
+        inputs: &'static [DetectorInput],
+    ) -> Self {
+        Self {
+            id,
+            description,
+            severity,
+            inputs,
+            create: None,
+            supersedes_unreferenced: false,
+            enabled_by_default: false,
+            per_file: false,
+        }
+    }
+
+    /// Registration of a graph detector run by the detector stage
+    pub fn graph(
+        id: &'static str,
+        description: &'static str,
+        severity: Severity,
+        create: fn() -> Box<dyn Detector>,
+    ) -> Self {
+        Self {
+            create: Some(create),
+            ..Self::new(id, description, severity, &[DetectorInput::Graph])
+        }
+    }
+
+    pub fn with_supersedes_unreferenced(mut self, supersedes: bool) -> Self {
+        self.supersedes_unreferenced = supersedes;
+        self
+    }
+
+    pub fn with_enabled_by_default(mut self, enabled: bool) -> Self {
+        self.enabled_by_default = enabled;
+        self
+    }
+
+    pub fn with_per_file(mut self, per_file: bool) -> Self {
+        self.per_file = per_file;
+        self
+    }
+}
+
+/// Detectors available for selection, in the order they run
+#[derive(Clone, Default)]
+pub struct DetectorRegistry {
+    detectors: Vec<DetectorRegistration>,
+}
+
+impl DetectorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All built-in detectors
+    pub fn builtin() -> Self {
+        use DetectorInput::{Coverage, Filesystem, Graph};
+
+        Self::new()
+            .with(
+                DetectorRegistration::graph(
+                    "unused-params",
+                    "Function parameters that are declared but never used",
+                    Severity::Info,
+                    || Box::new(UnusedParamDetector::new()),
+                )
+                .with_per_file(true),
+            )
+            .with(
+                DetectorRegistration::graph(
+                    "write-only",
+                    "Variables that are assigned but never read",
+                    Severity::Warning,
+                    || Box::new(WriteOnlyDetector::new()),
+                )
+                .with_per_file(true),
+            )
+            .with(DetectorRegistration::graph(
+                "sealed-variants",
+                "Sealed class variants that are never instantiated",
+                Severity::Warning,
+                || Box::new(UnusedSealedVariantDetector::new()),
+            ))
+            .with(
+                DetectorRegistration::graph(
+                    "unused-enum-cases",
+                    "Enum constants that are never used",
+                    Severity::Warning,
+                    || Box::new(UnusedEnumCaseDetector::new()),
+                )
+                .with_supersedes_unreferenced(true),
+            )
+            .with(DetectorRegistration::graph(
+                "redundant-overrides",
+                "Overrides that only call super",
+                Severity::Info,
+                || Box::new(RedundantOverrideDetector::new()),
+            ))
+            .with(DetectorRegistration::graph(
+                "redundant-public",
+                "Public declarations only used in their file, class or module",
+                Severity::Info,
+                || Box::new(RedundantPublicDetector::new()),
+            ))
+            .with(
+                DetectorRegistration::graph(
+                    "unused-imports",
+                    "Imports whose name is never used in the file",
+                    Severity::Info,
+                    || Box::new(UnusedImportDetector::new()),
+                )
+                .with_per_file(true),
+            )
+            .with(
+                DetectorRegistration::graph(
+                    "unused-typealiases",
+                    "Kotlin typealiases that are declared but never used",
+                    Severity::Info,
+                    || Box::new(UnusedTypeAliasDetector::new()),
+                )
+                .with_supersedes_unreferenced(true),
+            )
+            .with(DetectorRegistration::graph(
+                "property-accessors",
+                "Properties declared var but never reassigned, and custom accessors never used",
+                Severity::Info,
+                || Box::new(PropertyAccessorDetector::new()),
+            ))
+            .with(DetectorRegistration::new(
+                "dead-branches",
+                "Branches whose condition is constant (BuildConfig, const val, feature flags)",
+                Severity::Warning,
+                &[Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "only-used-in-tests",
+                "Production declarations only referenced from test source sets",
+                Severity::Warning,
+                &[Graph, Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "preview-only",
+                "Production composables only called from @Preview functions or tests",
+                Severity::Warning,
+                &[Graph, Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "unused-resources",
+                "Android resources that are never referenced",
+                Severity::Warning,
+                &[Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "unused-extras",
+                "Intent extras that are put but never retrieved",
+                Severity::Warning,
+                &[Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "unused-bundle-args",
+                "Bundle arguments and SavedStateHandle keys that are set but never read",
+                Severity::Warning,
+                &[Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "unused-broadcasts",
+                "Broadcast actions sent but never received, or received but never sent",
+                Severity::Warning,
+                &[Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "write-only-prefs",
+                "SharedPreferences keys that are written but never read",
+                Severity::Warning,
+                &[Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "unused-pref-keys",
+                "SharedPreferences and DataStore keys written but never read, or never used",
+                Severity::Warning,
+                &[Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "write-only-dao",
+                "Room DAOs that insert data but never query it",
+                Severity::Warning,
+                &[Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "unused-module-deps",
+                "Gradle project dependencies the declaring module never imports from",
+                Severity::Warning,
+                &[Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "unused-nav-args",
+                "Navigation arguments never read and destinations never navigated to",
+                Severity::Warning,
+                &[Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "deep-links",
+                "Deep links never opened, opened without a handler, or the only way into an activity",
+                Severity::Warning,
+                &[Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "cycles",
+                "Zombie code cycles: dead declarations that only use each other",
+                Severity::Warning,
+                &[Graph],
+            ))
+            .with(DetectorRegistration::new(
+                "dead-objects",
+                "Objects and classes only used as a namespace, none of whose members is used",
+                Severity::Warning,
+                &[Graph],
+            ))
+            .with(DetectorRegistration::new(
+                "dead-doc-links",
+                "KDoc and Javadoc links to dead declarations",
+                Severity::Info,
+                &[Graph],
+            ))
+            .with(DetectorRegistration::new(
+                "uninvoked-params",
+                "Function-typed parameters that are never invoked or passed on",
+                Severity::Warning,
+                &[Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "compose-state",
+                "Compose state never read and composable defaults no call overrides",
+                Severity::Warning,
+                &[Filesystem],
+            ))
+            .with(DetectorRegistration::new(
+                "runtime-dead",
+                "Code that is reachable but never executed at runtime",
+                Severity::Warning,
+                &[Graph, Coverage],
+            ))
+            .with(
+                DetectorRegistration::new(
+                    "custom-rules",
+                    "User-defined rules from .searchdeadcode-rules.toml or --rules",
+                    Severity::Warning,
+                    &[Graph, Filesystem],
+                )
+                .with_enabled_by_default(true),
+            )
+            .with(DetectorRegistration::new(
+                "dead-files",
+                "Files whose declarations are all unused, reported as one finding",
+                Severity::Warning,
+                &[Graph],
+            ))
+    }
+
+    /// Register a detector, replacing any registration with the same id
+    pub fn with(mut self, registration: DetectorRegistration) -> Self {
+        self.detectors.retain(|d| d.id != registration.id);
+        self.detectors.push(registration);
+        self
+    }
+
+    pub fn get(&self, id: &str) -> Option<&DetectorRegistration> {
+        self.detectors.iter().find(|d| d.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &DetectorRegistration> {
+        self.detectors.iter()
+    }
+
+    pub fn ids(&self) -> Vec<&'static str> {
+        self.detectors.iter().map(|d| d.id).collect()
+    }
+
+    /// Ids of the detectors that run unless skipped
+    pub fn default_ids(&self) -> Vec<&'static str> {
+        self.detectors
+            .iter()
+            .filter(|d| d.enabled_by_default)
+            .map(|d| d.id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_registry() {
+        let registry = DetectorRegistry::builtin();
+        let ids = registry.ids();
+        let unique: std::collections::HashSet<_> = ids.iter().collect();
+        assert_eq!(unique.len(), ids.len(), "Detector ids must be unique");
+
+        let typealiases = registry.get("unused-typealiases").unwrap();
+        assert!(typealiases.create.is_some());
+        assert!(typealiases.supersedes_unreferenced);
+
+        let resources = registry.get("unused-resources").unwrap();
+        assert!(resources.create.is_none());
+        assert_eq!(resources.inputs, &[DetectorInput::Filesystem]);
+        assert!(registry.get("unknown").is_none());
+        assert_eq!(registry.default_ids(), vec!["custom-rules"]);
+
+        // Registering an existing id replaces it
+        let registry = registry.with(DetectorRegistration::new(
+            "cycles",
+            "Custom cycles",
+            Severity::Error,
+            &[DetectorInput::Graph],
+        ));
+        assert_eq!(registry.ids().len(), ids.len());
+        assert_eq!(registry.get("cycles").unwrap().severity, Severity::Error);
+    }
+}
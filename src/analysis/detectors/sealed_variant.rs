@@ -7,11 +7,13 @@
 //! ## Detection Algorithm
 //!
 //! 1. Find all sealed classes/interfaces (have "sealed" modifier)
-//! 2. Find all their subclasses (classes extending the sealed type)
-//! 3. For each subclass, check if it's ever instantiated:
+//! 2. Build the full hierarchy below them across files: direct and indirect
+//!    subtypes, including nested sealed types (`ErrorState.NetworkError`) and
+//!    variants declared in other files of the same package (Kotlin 1.5+)
+//! 3. For each variant, check if it or any of its subtypes is instantiated:
 //!    - Constructor called directly
 //!    - Referenced via Instantiation
-//! 4. Report never-instantiated subclasses
+//! 4. Report never-instantiated variants
 //!
 //! ## Examples Detected
 //!
@@ -25,8 +27,8 @@
 
 use super::Detector;
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
-use crate::graph::{DeclarationKind, Graph, ReferenceKind};
-use std::collections::HashSet;
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph, ReferenceKind};
+use std::collections::{HashMap, HashSet};
 
 /// Detector for unused sealed class/interface variants
 pub struct UnusedSealedVariantDetector {
@@ -53,15 +55,48 @@ impl UnusedSealedVariantDetector {
         sealed_types: &HashSet<String>,
     ) -> bool {
         // Check if any of the super types is a sealed class/interface
-        decl.super_types.iter().any(|st| {
-            // Handle generic types like "Foo<Bar>" -> "Foo"
-            let base_type = st.split('<').next().unwrap_or(st);
-            // Strip constructor parens: "UiState()" -> "UiState"
-            let base_type = base_type.split('(').next().unwrap_or(base_type);
-            // Trim whitespace
-            let base_type = base_type.trim();
-            sealed_types.contains(base_type)
-        })
+        decl.super_types
+            .iter()
+            .any(|st| sealed_types.contains(base_type_name(st)))
+    }
+
+    /// Resolve a super type to the type it names
+    ///
+    /// Qualified names (`ErrorState.NetworkError`) must match the nesting of
+    /// the candidate; candidates in the subtype's own package are preferred,
+    /// as sealed hierarchies cannot span packages.
+    fn resolve_super_type<'a>(
+        &self,
+        super_type: &str,
+        decl: &Declaration,
+        types_by_name: &HashMap<&str, Vec<&'a Declaration>>,
+        graph: &Graph,
+    ) -> Option<&'a Declaration> {
+        let base_type = base_type_name(super_type);
+        let simple_name = base_type.rsplit('.').next().unwrap_or(base_type);
+        let candidates: Vec<&Declaration> = types_by_name
+            .get(simple_name)?
+            .iter()
+            .copied()
+            .filter(|c| c.id != decl.id)
+            .filter(|c| {
+                let nested = nested_name(c, graph);
+                let qualified = match package_of(c) {
+                    Some(package) => format!("{}.{}", package, nested),
+                    None => nested.clone(),
+                };
+                qualified == base_type
+                    || nested == base_type
+                    || nested.ends_with(&format!(".{}", base_type))
+            })
+            .collect();
+
+        let package = package_of(decl);
+        candidates
+            .iter()
+            .find(|c| package_of(c) == package)
+            .or(candidates.first())
+            .copied()
     }
 
     /// Check if a declaration is ever instantiated
@@ -97,6 +132,30 @@ impl UnusedSealedVariantDetector {
 
         false
     }
+
+    /// Check if a variant or any of its subtypes is instantiated
+    fn is_live<'a>(
+        &self,
+        decl: &'a Declaration,
+        graph: &Graph,
+        subtypes: &HashMap<&DeclarationId, Vec<&'a Declaration>>,
+        live: &mut HashMap<&'a DeclarationId, bool>,
+    ) -> bool {
+        if let Some(&known) = live.get(&decl.id) {
+            return known;
+        }
+        // Guards against inheritance cycles in malformed code
+        live.insert(&decl.id, false);
+
+        let is_live = self.is_instantiated(decl, graph)
+            || subtypes
+                .get(&decl.id)
+                .into_iter()
+                .flatten()
+                .any(|&sub| self.is_live(sub, graph, subtypes, live));
+        live.insert(&decl.id, is_live);
+        is_live
+    }
 }
 
 impl Default for UnusedSealedVariantDetector {
@@ -110,61 +169,64 @@ impl Detector for UnusedSealedVariantDetector {
         let mut issues = Vec::new();
 
         // Step 1: Find all sealed classes/interfaces
-        let sealed_types: HashSet<String> = graph
-            .declarations()
-            .filter(|d| self.is_sealed(d))
-            .filter_map(|d| {
-                d.fully_qualified_name
-                    .clone()
-                    .or_else(|| Some(d.name.clone()))
-            })
-            .collect();
-
-        // Also collect simple names for matching
-        let sealed_simple_names: HashSet<String> = graph
+        let sealed_roots: Vec<&DeclarationId> = graph
             .declarations()
             .filter(|d| self.is_sealed(d))
-            .map(|d| d.name.clone())
+            .map(|d| &d.id)
             .collect();
 
-        if sealed_types.is_empty() {
+        if sealed_roots.is_empty() {
             return issues;
         }
 
-        // Step 2: Find all subclasses of sealed types
-        for decl in graph.declarations() {
-            // Skip if not a class or object (interfaces can't be instantiated)
-            if !matches!(decl.kind, DeclarationKind::Class | DeclarationKind::Object) {
-                continue;
+        // Step 2: Link every type to its direct subtypes, across files
+        let mut types_by_name: HashMap<&str, Vec<&Declaration>> = HashMap::new();
+        for decl in graph.declarations().filter(|d| is_hierarchy_kind(d.kind)) {
+            types_by_name
+                .entry(decl.name.as_str())
+                .or_default()
+                .push(decl);
+        }
+        let mut subtypes: HashMap<&DeclarationId, Vec<&Declaration>> = HashMap::new();
+        for decl in graph.declarations().filter(|d| is_hierarchy_kind(d.kind)) {
+            for super_type in &decl.super_types {
+                if let Some(parent) =
+                    self.resolve_super_type(super_type, decl, &types_by_name, graph)
+                {
+                    subtypes.entry(&parent.id).or_default().push(decl);
+                }
             }
+        }
 
-            // Skip sealed classes themselves - we only care about variants (subclasses)
-            if self.is_sealed(decl) {
-                continue;
+        // Collect the hierarchy below the sealed types
+        let mut variants: Vec<&Declaration> = Vec::new();
+        let mut seen: HashSet<&DeclarationId> = sealed_roots.iter().copied().collect();
+        let mut queue = sealed_roots;
+        while let Some(id) = queue.pop() {
+            for &sub in subtypes.get(id).into_iter().flatten() {
+                if seen.insert(&sub.id) {
+                    variants.push(sub);
+                    queue.push(&sub.id);
+                }
             }
+        }
 
-            // Skip enum classes - their constants are referenced, not instantiated
-            if decl.modifiers.iter().any(|m| m == "enum") {
+        // Step 3: A variant is used when it or any of its subtypes is instantiated
+        let mut live: HashMap<&DeclarationId, bool> = HashMap::new();
+        for &decl in &variants {
+            // Skip interfaces (can't be instantiated) and intermediate sealed types
+            if !matches!(decl.kind, DeclarationKind::Class | DeclarationKind::Object)
+                || self.is_sealed(decl)
+            {
                 continue;
             }
 
-            // Check if this is a subclass of a sealed type
-            let is_sealed_sub = decl.super_types.iter().any(|st| {
-                // Strip generic args: "Foo<Bar>" -> "Foo"
-                let base_type = st.split('<').next().unwrap_or(st);
-                // Strip constructor parens: "UiState()" -> "UiState"
-                let base_type = base_type.split('(').next().unwrap_or(base_type);
-                // Trim whitespace
-                let base_type = base_type.trim();
-                sealed_types.contains(base_type) || sealed_simple_names.contains(base_type)
-            });
-
-            if !is_sealed_sub {
+            // Skip enum classes - their constants are referenced, not instantiated
+            if decl.modifiers.iter().any(|m| m == "enum") {
                 continue;
             }
 
-            // Step 3: Check if this variant is ever instantiated
-            if !self.is_instantiated(decl, graph) {
+            if !self.is_live(decl, graph, &subtypes, &mut live) {
                 let mut dead = DeadCode::new(decl.clone(), DeadCodeIssue::UnusedSealedVariant);
                 dead = dead.with_message(format!(
                     "Sealed variant '{}' is never instantiated",
@@ -193,6 +255,41 @@ impl Detector for UnusedSealedVariantDetector {
     }
 }
 
+/// Kinds that take part in a sealed hierarchy
+fn is_hierarchy_kind(kind: DeclarationKind) -> bool {
+    matches!(
+        kind,
+        DeclarationKind::Class | DeclarationKind::Object | DeclarationKind::Interface
+    )
+}
+
+/// Type name without generic arguments or constructor call:
+/// `UiState<String>()` -> `UiState`
+fn base_type_name(super_type: &str) -> &str {
+    let base_type = super_type.split('<').next().unwrap_or(super_type);
+    let base_type = base_type.split('(').next().unwrap_or(base_type);
+    base_type.trim()
+}
+
+/// Name qualified by the enclosing types, e.g. `ErrorState.NetworkError`
+fn nested_name(decl: &Declaration, graph: &Graph) -> String {
+    let mut name = decl.name.clone();
+    let mut parent = decl.parent.as_ref().and_then(|p| graph.get_declaration(p));
+    while let Some(outer) = parent.filter(|p| p.kind.is_type()) {
+        name = format!("{}.{}", outer.name, name);
+        parent = outer.parent.as_ref().and_then(|p| graph.get_declaration(p));
+    }
+    name
+}
+
+/// Package of a declaration, from its fully qualified name
+fn package_of(decl: &Declaration) -> Option<&str> {
+    decl.fully_qualified_name
+        .as_deref()?
+        .strip_suffix(decl.name.as_str())?
+        .strip_suffix('.')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -278,4 +375,89 @@ mod tests {
         );
         assert!(detector.is_sealed_subclass(&generic, &sealed_types));
     }
+
+    #[test]
+    fn test_sealed_hierarchy_across_files() {
+        use crate::graph::Reference;
+
+        let mut graph = Graph::new();
+        let add = |name: &str, file: &str, start: usize, modifiers, super_types| {
+            let mut decl = make_declaration(name, DeclarationKind::Class, modifiers, super_types);
+            decl.id = DeclarationId::new(PathBuf::from(file), start, start + 10);
+            decl.location.file = PathBuf::from(file);
+            decl.fully_qualified_name = Some(format!("com.example.{}", name));
+            decl
+        };
+
+        // sealed interface UiState (UiState.kt)
+        let mut ui_state = add("UiState", "UiState.kt", 0, vec!["sealed"], vec![]);
+        ui_state.kind = DeclarationKind::Interface;
+        // sealed class ErrorState : UiState { sealed class NetworkError : ErrorState() }
+        let error_state = add(
+            "ErrorState",
+            "Errors.kt",
+            0,
+            vec!["sealed"],
+            vec!["UiState"],
+        );
+        let mut network_error = add(
+            "NetworkError",
+            "Errors.kt",
+            20,
+            vec!["sealed"],
+            vec!["ErrorState()"],
+        );
+        network_error.parent = Some(error_state.id.clone());
+        // Variants in a separate file
+        let timeout = add(
+            "Timeout",
+            "Network.kt",
+            0,
+            vec![],
+            vec!["ErrorState.NetworkError()"],
+        );
+        let offline = add(
+            "Offline",
+            "Network.kt",
+            20,
+            vec![],
+            vec!["ErrorState.NetworkError()"],
+        );
+        // abstract class Loading : UiState, used only through its subclass
+        let loading = add(
+            "Loading",
+            "Loading.kt",
+            0,
+            vec!["abstract"],
+            vec!["UiState"],
+        );
+        let spinner = add("Spinner", "Loading.kt", 20, vec![], vec!["Loading()"]);
+        let caller = add("Screen", "Screen.kt", 0, vec![], vec![]);
+
+        for decl in [
+            &ui_state,
+            &error_state,
+            &network_error,
+            &timeout,
+            &offline,
+            &loading,
+            &spinner,
+            &caller,
+        ] {
+            graph.add_declaration(decl.clone());
+        }
+        for target in [&timeout, &spinner] {
+            let location = Location::new(PathBuf::from("Screen.kt"), 2, 1, 0, 10);
+            let reference = Reference::new(ReferenceKind::Call, location, target.name.clone());
+            graph.add_reference(&caller.id, &target.id, reference);
+        }
+
+        let detector = UnusedSealedVariantDetector::new();
+        let names: Vec<String> = detector
+            .detect(&graph)
+            .into_iter()
+            .map(|dc| dc.declaration.name)
+            .collect();
+        assert_eq!(names, vec!["Offline"]);
+    }
 }
@@ -0,0 +1,102 @@
+//! Uninvoked Function Parameter Detector
+//!
+//! Detects function-typed parameters (`onDone: () -> Unit`) that the function
+//! never invokes and never hands on, so the lambdas callers pass never run.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Scan every Kotlin source for functions with a body (see
+//!    [`crate::parser::scan_function_parameters`]) and their function-typed
+//!    parameters; overrides are skipped, as their signature is not theirs
+//! 2. Classify each use of such a parameter: invoking it (`onDone()`,
+//!    `onDone?.invoke()`), handing it on (passing, storing, returning or
+//!    capturing it) or comparing it with `null`
+//! 3. Report parameters with neither invocations nor hand-offs
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! fun save(item: Item, onSaved: () -> Unit) {   // DEAD: onSaved never runs
+//!     repository.insert(item)
+//! }
+//!
+//! fun load(onError: ((Throwable) -> Unit)?) {   // DEAD: only null-checked
+//!     if (onError != null) log("with error handler")
+//! }
+//! ```
+
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language};
+use crate::parser::{scan_function_parameters, FunctionParameter};
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Detector for function-typed parameters that are never invoked
+pub struct UninvokedParamDetector;
+
+impl UninvokedParamDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Find uninvoked function parameters in Kotlin sources (path and contents)
+    pub fn analyze(&self, sources: &[(PathBuf, String)]) -> Vec<DeadCode> {
+        let mut dead_code = Vec::new();
+        for (path, source) in sources {
+            let parameters = match scan_function_parameters(path, source) {
+                Ok(parameters) => parameters,
+                Err(e) => {
+                    debug!(
+                        "Failed to scan {} for function parameters: {}",
+                        path.display(),
+                        e
+                    );
+                    continue;
+                }
+            };
+            dead_code.extend(
+                parameters
+                    .iter()
+                    .filter(|p| p.is_never_invoked())
+                    .map(to_dead_code),
+            );
+        }
+        dead_code
+    }
+}
+
+impl Default for UninvokedParamDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn to_dead_code(parameter: &FunctionParameter) -> DeadCode {
+    let mut decl = Declaration::new(
+        DeclarationId::new(
+            parameter.location.file.clone(),
+            parameter.location.start_byte,
+            parameter.location.end_byte,
+        ),
+        parameter.name.clone(),
+        DeclarationKind::Parameter,
+        parameter.location.clone(),
+        Language::Kotlin,
+    );
+    decl.parent = Some(parameter.function_id.clone());
+
+    let message = if parameter.null_checks > 0 {
+        format!(
+            "Function parameter '{}' of {} is only compared with null, never invoked",
+            parameter.name, parameter.function
+        )
+    } else {
+        format!(
+            "Function parameter '{}' of {} is never invoked",
+            parameter.name, parameter.function
+        )
+    };
+    DeadCode::new(decl, DeadCodeIssue::UninvokedFunctionParameter)
+        .with_message(message)
+        .with_confidence(Confidence::High)
+}
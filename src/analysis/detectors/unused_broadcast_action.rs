@@ -0,0 +1,335 @@
+//! Unused Broadcast Action Detector
+//!
+//! Links implicit broadcasts to the receivers listening for them by action
+//! string, and reports both ends that have no counterpart.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Collect string constants (`const val ACTION_SYNC = "..."`,
+//!    `static final String ACTION_SYNC = "..."`) so actions can be named by them
+//! 2. Find sent actions: `Intent("ACTION")` and `setAction("ACTION")` in files
+//!    that send broadcasts (`sendBroadcast`, `PendingIntent.getBroadcast`, ...)
+//! 3. Find received actions: `IntentFilter("ACTION")`, `addAction("ACTION")`
+//!    and the `<action>` elements of manifest `<receiver>`s
+//! 4. Report actions sent but never received, and received but never sent
+//!
+//! Actions of the platform and Google Play services are sent and received by
+//! the system, so they are never reported.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! // In SyncService.kt
+//! sendBroadcast(Intent("com.example.SYNC_DONE"))       // Received below
+//! sendBroadcast(Intent("com.example.LEGACY_REFRESH"))  // DEAD: nobody listens
+//!
+//! // In MainActivity.kt
+//! registerReceiver(receiver, IntentFilter("com.example.SYNC_DONE"))
+//! registerReceiver(receiver, IntentFilter("com.example.LOGOUT"))  // DEAD: never sent
+//! ```
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Prefixes of actions broadcast or received by the system, not by our code
+const SYSTEM_ACTION_PREFIXES: &[&str] = &["android.", "com.android.", "com.google.android."];
+
+/// Calls that send the intents of a file as broadcasts
+const SEND_MARKERS: &[&str] = &[
+    "sendBroadcast",
+    "sendOrderedBroadcast",
+    "sendStickyBroadcast",
+    "getBroadcast(",
+];
+
+/// Location info for a broadcast action
+#[derive(Debug, Clone)]
+pub struct ActionLocation {
+    pub file: PathBuf,
+    pub line: usize,
+    pub action: String,
+}
+
+/// Result of broadcast action analysis
+#[derive(Debug)]
+pub struct BroadcastActionAnalysis {
+    /// Actions broadcast but never received
+    pub unreceived: Vec<ActionLocation>,
+    /// Actions received but never broadcast
+    pub unsent: Vec<ActionLocation>,
+    /// Distinct actions sent
+    pub total_sent: usize,
+    /// Distinct actions received
+    pub total_received: usize,
+}
+
+/// Detector for broadcast actions without a sender or receiver
+pub struct UnusedBroadcastActionDetector {
+    // `const val NAME = "value"` / `static final String NAME = "value"`
+    constant_pattern: Regex,
+    // Intent("ACTION"), setAction("ACTION"), `.action = "ACTION"`
+    send_pattern: Regex,
+    // IntentFilter("ACTION"), addAction("ACTION")
+    receive_pattern: Regex,
+    // <action android:name="ACTION" /> in the manifest
+    manifest_action_pattern: Regex,
+}
+
+impl UnusedBroadcastActionDetector {
+    pub fn new() -> Self {
+        let constant_pattern = Regex::new(
+            r#"(?:const\s+val|static\s+final\s+String)\s+(\w+)\s*(?::\s*String\s*)?=\s*"([^"]+)""#,
+        )
+        .unwrap();
+
+        let send_pattern = Regex::new(
+            r#"(?:\bIntent\s*\(|setAction\s*\(|\.action\s*=)\s*(?:"([^"]+)"|([A-Za-z_][\w.]*))"#,
+        )
+        .unwrap();
+
+        let receive_pattern = Regex::new(
+            r#"(?:\bIntentFilter\s*\(|addAction\s*\()\s*(?:"([^"]+)"|([A-Za-z_][\w.]*))"#,
+        )
+        .unwrap();
+
+        let manifest_action_pattern =
+            Regex::new(r#"<action\s+android:name\s*=\s*"([^"]+)""#).unwrap();
+
+        Self {
+            constant_pattern,
+            send_pattern,
+            receive_pattern,
+            manifest_action_pattern,
+        }
+    }
+
+    /// Analyze a directory for unmatched broadcast actions
+    pub fn analyze(&self, root: &Path) -> BroadcastActionAnalysis {
+        use ignore::WalkBuilder;
+
+        let mut sources = Vec::new();
+        let mut manifests = Vec::new();
+
+        let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
+        for entry in walker.flatten() {
+            let path = entry.path();
+
+            // Skip test files
+            let path_str = path.to_string_lossy();
+            if path_str.contains("/test/") || path_str.contains("/androidTest/") {
+                continue;
+            }
+
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            let ext = path.extension().and_then(|e| e.to_str());
+            if matches!(ext, Some("kt") | Some("java")) {
+                sources.push((path.to_path_buf(), content));
+            } else if path.file_name().is_some_and(|n| n == "AndroidManifest.xml") {
+                manifests.push((path.to_path_buf(), content));
+            }
+        }
+
+        self.analyze_sources(&sources, &manifests)
+    }
+
+    /// Match the actions sent and received by source files and manifests
+    pub fn analyze_sources(
+        &self,
+        sources: &[(PathBuf, String)],
+        manifests: &[(PathBuf, String)],
+    ) -> BroadcastActionAnalysis {
+        // Constants naming actions, by simple name
+        let mut constants: HashMap<String, String> = HashMap::new();
+        for (_, content) in sources {
+            for caps in self.constant_pattern.captures_iter(content) {
+                constants.insert(caps[1].to_string(), caps[2].to_string());
+            }
+        }
+        let resolve = |caps: &regex::Captures| -> Option<String> {
+            if let Some(literal) = caps.get(1) {
+                return Some(literal.as_str().to_string());
+            }
+            let name = caps.get(2)?.as_str();
+            let name = name.rsplit('.').next().unwrap_or(name);
+            constants.get(name).cloned()
+        };
+
+        let mut sent: HashMap<String, Vec<ActionLocation>> = HashMap::new();
+        let mut received: HashMap<String, Vec<ActionLocation>> = HashMap::new();
+        let record = |actions: &mut HashMap<String, Vec<ActionLocation>>,
+                      action: String,
+                      file: &Path,
+                      line| {
+            actions
+                .entry(action.clone())
+                .or_default()
+                .push(ActionLocation {
+                    file: file.to_path_buf(),
+                    line,
+                    action,
+                });
+        };
+
+        for (path, content) in sources {
+            let sends = SEND_MARKERS.iter().any(|marker| content.contains(marker));
+            for (line_num, line) in content.lines().enumerate() {
+                if sends {
+                    for caps in self.send_pattern.captures_iter(line) {
+                        if let Some(action) = resolve(&caps) {
+                            record(&mut sent, action, path, line_num + 1);
+                        }
+                    }
+                }
+                for caps in self.receive_pattern.captures_iter(line) {
+                    if let Some(action) = resolve(&caps) {
+                        record(&mut received, action, path, line_num + 1);
+                    }
+                }
+            }
+        }
+
+        for (path, content) in manifests {
+            let mut in_receiver = false;
+            for (line_num, line) in content.lines().enumerate() {
+                if line.contains("<receiver") {
+                    in_receiver = true;
+                }
+                if in_receiver {
+                    for caps in self.manifest_action_pattern.captures_iter(line) {
+                        record(&mut received, caps[1].to_string(), path, line_num + 1);
+                    }
+                }
+                if line.contains("</receiver>") {
+                    in_receiver = false;
+                }
+            }
+        }
+
+        let is_system = |action: &str| {
+            SYSTEM_ACTION_PREFIXES
+                .iter()
+                .any(|prefix| action.starts_with(prefix))
+        };
+        // Report only the first location for each action
+        let unmatched = |from: &HashMap<String, Vec<ActionLocation>>,
+                         to: &HashMap<String, Vec<ActionLocation>>| {
+            let mut unmatched: Vec<ActionLocation> = from
+                .iter()
+                .filter(|(action, _)| !is_system(action) && !to.contains_key(*action))
+                .filter_map(|(_, locations)| locations.first().cloned())
+                .collect();
+            unmatched.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+            unmatched
+        };
+
+        BroadcastActionAnalysis {
+            unreceived: unmatched(&sent, &received),
+            unsent: unmatched(&received, &sent),
+            total_sent: sent.len(),
+            total_received: received.len(),
+        }
+    }
+}
+
+impl Default for UnusedBroadcastActionDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze(sources: &[(&str, &str)], manifest: &str) -> BroadcastActionAnalysis {
+        let sources: Vec<(PathBuf, String)> = sources
+            .iter()
+            .map(|(path, content)| (PathBuf::from(path), content.to_string()))
+            .collect();
+        let manifests = vec![(PathBuf::from("AndroidManifest.xml"), manifest.to_string())];
+        UnusedBroadcastActionDetector::new().analyze_sources(&sources, &manifests)
+    }
+
+    #[test]
+    fn test_match_actions() {
+        let analysis = analyze(
+            &[
+                (
+                    "SyncService.kt",
+                    r#"
+object Actions {
+    const val SYNC_DONE = "com.example.SYNC_DONE"
+}
+
+class SyncService {
+    fun finish() {
+        sendBroadcast(Intent(Actions.SYNC_DONE))
+        sendBroadcast(Intent("com.example.LEGACY_REFRESH"))
+        sendBroadcast(Intent(Intent.ACTION_VIEW))
+    }
+}
+"#,
+                ),
+                (
+                    "MainActivity.kt",
+                    r#"
+class MainActivity {
+    fun onStart() {
+        registerReceiver(receiver, IntentFilter(Actions.SYNC_DONE))
+        val filter = IntentFilter()
+        filter.addAction("com.example.LOGOUT")
+        registerReceiver(receiver, IntentFilter("android.intent.action.SCREEN_ON"))
+        startActivity(Intent("com.example.OPEN_SETTINGS"))
+    }
+}
+"#,
+                ),
+            ],
+            r#"<manifest>
+    <application>
+        <receiver android:name=".BootReceiver">
+            <intent-filter>
+                <action android:name="android.intent.action.BOOT_COMPLETED" />
+                <action android:name="com.example.WIDGET_TICK" />
+            </intent-filter>
+        </receiver>
+    </application>
+</manifest>"#,
+        );
+
+        let unreceived: Vec<&str> = analysis
+            .unreceived
+            .iter()
+            .map(|a| a.action.as_str())
+            .collect();
+        assert_eq!(unreceived, vec!["com.example.LEGACY_REFRESH"]);
+        assert_eq!(analysis.unreceived[0].line, 9);
+
+        let unsent: Vec<&str> = analysis.unsent.iter().map(|a| a.action.as_str()).collect();
+        assert_eq!(
+            unsent,
+            vec!["com.example.WIDGET_TICK", "com.example.LOGOUT"]
+        );
+    }
+
+    #[test]
+    fn test_pending_intent_sends() {
+        let analysis = analyze(
+            &[(
+                "Alarms.java",
+                r#"
+static final String ACTION_REMIND = "com.example.REMIND";
+PendingIntent pi = PendingIntent.getBroadcast(context, 0, new Intent(ACTION_REMIND), 0);
+registerReceiver(receiver, new IntentFilter(ACTION_REMIND));
+"#,
+            )],
+            "<manifest />",
+        );
+        assert!(analysis.unreceived.is_empty());
+        assert!(analysis.unsent.is_empty());
+        assert_eq!(analysis.total_sent, 1);
+    }
+}
@@ -0,0 +1,355 @@
+//! Unused Bundle Argument Detector
+//!
+//! Detects argument keys set on a Bundle or a SavedStateHandle that the
+//! destination never reads. This is the Fragment counterpart of unused Intent
+//! extras: arguments outlive the code that read them when screens change.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Collect string constants so keys can be named by them, across files
+//! 2. Find keys set on Bundles: `putString("KEY", ...)`, `putInt`, ... and
+//!    `bundleOf("KEY" to value)`, in files that build Bundles
+//! 3. Find keys set on a SavedStateHandle: `handle["KEY"] = value`,
+//!    `handle.set("KEY", value)`
+//! 4. Find reads: `requireArguments().getString("KEY")`, `get("KEY")`,
+//!    `containsKey("KEY")`, `savedStateHandle.get<T>("KEY")`, `handle["KEY"]`,
+//!    `getLiveData("KEY")`, `getStateFlow("KEY", ...)`, and the `<argument>`s
+//!    of navigation graphs, which Safe Args and the navigation library read
+//! 5. Report keys set but never read
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! // In ListFragment.kt
+//! findNavController().navigate(
+//!     R.id.detail,
+//!     bundleOf(ARG_ITEM_ID to id, "legacy_source" to "list"),  // DEAD: legacy_source
+//! )
+//!
+//! // In DetailViewModel.kt
+//! val itemId: String = savedStateHandle[ARG_ITEM_ID]!!
+//! ```
+
+use super::key_usage::{KeyConstants, KeyLocation, KeyUsage};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// Marks a file whose `put*` calls are Bundle calls
+const BUNDLE_MARKERS: &[&str] = &["Bundle", "bundleOf", "arguments"];
+
+/// Result of Bundle argument analysis
+#[derive(Debug)]
+pub struct BundleArgumentAnalysis {
+    /// Argument keys set but never read
+    pub unread: Vec<KeyLocation>,
+    /// Distinct argument keys set
+    pub total_set: usize,
+    /// Distinct argument keys read
+    pub total_read: usize,
+}
+
+/// Detector for Bundle and SavedStateHandle keys that are never read
+pub struct UnusedBundleArgumentDetector {
+    // putString("KEY", ...), putParcelable(KEY, ...), ...
+    put_pattern: Regex,
+    // "KEY" to value / KEY to value, inside bundleOf(...)
+    pair_pattern: Regex,
+    // handle["KEY"] = value, handle.set("KEY", value)
+    handle_set_pattern: Regex,
+    // getString("KEY"), get<T>("KEY"), containsKey("KEY"), getLiveData("KEY"), ...
+    get_pattern: Regex,
+    // handle["KEY"] in any other position
+    handle_index_pattern: Regex,
+    // <argument android:name="KEY" /> in a navigation graph
+    nav_argument_pattern: Regex,
+}
+
+impl UnusedBundleArgumentDetector {
+    pub fn new() -> Self {
+        let put_pattern =
+            Regex::new(r#"\bput([A-Z]\w*)\s*\(\s*(?:"([^"]+)"|([A-Za-z_][\w.]*))"#).unwrap();
+
+        let pair_pattern = Regex::new(r#"(?:"([^"]+)"|\b([A-Za-z_][\w.]*))\s+to\b"#).unwrap();
+
+        let handle_set_pattern = Regex::new(
+            r#"\w*StateHandle\s*(?:\[\s*(?:"([^"]+)"|([A-Za-z_][\w.]*))\s*\]\s*=(?:[^=]|$)|\.set\s*(?:<[^>]*>)?\s*\(\s*(?:"([^"]+)"|([A-Za-z_][\w.]*)))"#,
+        )
+        .unwrap();
+
+        let get_pattern = Regex::new(
+            r#"\.(?:get\w*|containsKey|contains)\s*(?:<[^>]*>)?\s*\(\s*(?:"([^"]+)"|([A-Za-z_][\w.]*))"#,
+        )
+        .unwrap();
+
+        let handle_index_pattern =
+            Regex::new(r#"\w*StateHandle\s*\[\s*(?:"([^"]+)"|([A-Za-z_][\w.]*))\s*\]"#).unwrap();
+
+        let nav_argument_pattern =
+            Regex::new(r#"<argument\s+android:name\s*=\s*"([^"]+)""#).unwrap();
+
+        Self {
+            put_pattern,
+            pair_pattern,
+            handle_set_pattern,
+            get_pattern,
+            handle_index_pattern,
+            nav_argument_pattern,
+        }
+    }
+
+    /// Analyze a directory for unused Bundle arguments
+    pub fn analyze(&self, root: &Path) -> BundleArgumentAnalysis {
+        use ignore::WalkBuilder;
+
+        let mut sources = Vec::new();
+        let mut nav_graphs = Vec::new();
+
+        let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
+        for entry in walker.flatten() {
+            let path = entry.path();
+
+            // Skip test files
+            let path_str = path.to_string_lossy();
+            if path_str.contains("/test/") || path_str.contains("/androidTest/") {
+                continue;
+            }
+
+            let ext = path.extension().and_then(|e| e.to_str());
+            if !matches!(ext, Some("kt") | Some("java") | Some("xml")) {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            if ext == Some("xml") {
+                if content.contains("<navigation") {
+                    nav_graphs.push((path.to_path_buf(), content));
+                }
+            } else {
+                sources.push((path.to_path_buf(), content));
+            }
+        }
+
+        self.analyze_sources(&sources, &nav_graphs)
+    }
+
+    /// Match the argument keys set and read by source files and navigation graphs
+    pub fn analyze_sources(
+        &self,
+        sources: &[(PathBuf, String)],
+        nav_graphs: &[(PathBuf, String)],
+    ) -> BundleArgumentAnalysis {
+        let mut constants = KeyConstants::new();
+        for (_, content) in sources {
+            constants.collect(content);
+        }
+
+        let mut usage = KeyUsage::new();
+        for (path, content) in sources {
+            let builds_bundles = BUNDLE_MARKERS.iter().any(|marker| content.contains(marker));
+            // Depth of the open bundleOf( calls, for pairs spanning lines
+            let mut bundle_depth = 0usize;
+
+            for (line_num, line) in content.lines().enumerate() {
+                let line_no = line_num + 1;
+
+                if builds_bundles {
+                    for caps in self.put_pattern.captures_iter(line) {
+                        // Intent extras have their own detector
+                        if caps[1].starts_with("Extra") {
+                            continue;
+                        }
+                        if let Some(key) = resolve_at(&constants, &caps, 2) {
+                            usage.add_write(key, path, line_no);
+                        }
+                    }
+
+                    let pairs_from = match line.find("bundleOf(") {
+                        Some(index) => Some(index),
+                        None if bundle_depth > 0 => Some(0),
+                        None => None,
+                    };
+                    if let Some(from) = pairs_from {
+                        for caps in self.pair_pattern.captures_iter(&line[from..]) {
+                            if let Some(key) = constants.resolve(&caps) {
+                                usage.add_write(key, path, line_no);
+                            }
+                        }
+                        bundle_depth = paren_depth(bundle_depth, &line[from..]);
+                    }
+                }
+
+                let mut assigned = Vec::new();
+                for caps in self.handle_set_pattern.captures_iter(line) {
+                    let key = resolve_at(&constants, &caps, 1)
+                        .or_else(|| resolve_at(&constants, &caps, 3));
+                    if let Some(key) = key {
+                        usage.add_write(key, path, line_no);
+                    }
+                    assigned.push(caps.get(0).map_or(0, |m| m.start()));
+                }
+
+                for caps in self.get_pattern.captures_iter(line) {
+                    if let Some(key) = constants.resolve(&caps) {
+                        usage.add_read(key, path, line_no);
+                    }
+                }
+                for caps in self.handle_index_pattern.captures_iter(line) {
+                    let start = caps.get(0).map_or(0, |m| m.start());
+                    if assigned.contains(&start) {
+                        continue;
+                    }
+                    if let Some(key) = constants.resolve(&caps) {
+                        usage.add_read(key, path, line_no);
+                    }
+                }
+            }
+        }
+
+        for (path, content) in nav_graphs {
+            for (line_num, line) in content.lines().enumerate() {
+                for caps in self.nav_argument_pattern.captures_iter(line) {
+                    usage.add_read(caps[1].to_string(), path, line_num + 1);
+                }
+            }
+        }
+
+        let unread = usage
+            .unread()
+            .into_iter()
+            .filter(|arg| !arg.key.starts_with("android."))
+            .collect();
+
+        BundleArgumentAnalysis {
+            unread,
+            total_set: usage.writes.len(),
+            total_read: usage.reads.len(),
+        }
+    }
+}
+
+impl Default for UnusedBundleArgumentDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The key of a capture whose group `literal` is a string literal and the
+/// group after it a constant name
+fn resolve_at(constants: &KeyConstants, caps: &regex::Captures, literal: usize) -> Option<String> {
+    if let Some(key) = caps.get(literal) {
+        return Some(key.as_str().to_string());
+    }
+    constants
+        .lookup(caps.get(literal + 1)?.as_str())
+        .map(str::to_string)
+}
+
+/// Parenthesis depth after a line, starting from `depth`
+fn paren_depth(depth: usize, line: &str) -> usize {
+    line.chars().fold(depth, |depth, c| match c {
+        '(' => depth + 1,
+        ')' => depth.saturating_sub(1),
+        _ => depth,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze(sources: &[(&str, &str)], nav_graph: &str) -> BundleArgumentAnalysis {
+        let sources: Vec<(PathBuf, String)> = sources
+            .iter()
+            .map(|(path, content)| (PathBuf::from(path), content.to_string()))
+            .collect();
+        let nav_graphs = vec![(PathBuf::from("nav_graph.xml"), nav_graph.to_string())];
+        UnusedBundleArgumentDetector::new().analyze_sources(&sources, &nav_graphs)
+    }
+
+    fn unread(analysis: &BundleArgumentAnalysis) -> Vec<(&str, usize)> {
+        analysis
+            .unread
+            .iter()
+            .map(|arg| (arg.key.as_str(), arg.line))
+            .collect()
+    }
+
+    #[test]
+    fn test_fragment_arguments() {
+        let analysis = analyze(
+            &[
+                (
+                    "DetailFragment.kt",
+                    r#"
+class DetailFragment : Fragment() {
+    companion object {
+        const val ARG_ITEM_ID = "item_id"
+
+        fun newInstance(id: String) = DetailFragment().apply {
+            arguments = Bundle().apply {
+                putString(ARG_ITEM_ID, id)
+                putBoolean("legacy_mode", true)
+            }
+        }
+    }
+
+    private val itemId by lazy { requireArguments().getString(ARG_ITEM_ID) }
+}
+"#,
+                ),
+                (
+                    "ListFragment.kt",
+                    r#"
+class ListFragment : Fragment() {
+    fun open(id: String) {
+        findNavController().navigate(
+            R.id.detail,
+            bundleOf(
+                "item_id" to id,
+                "source" to "list",
+                "scroll" to 12,
+            ),
+        )
+    }
+}
+"#,
+                ),
+            ],
+            r#"<navigation>
+    <fragment android:id="@+id/detail">
+        <argument android:name="scroll" app:argType="integer" />
+    </fragment>
+</navigation>"#,
+        );
+
+        assert_eq!(unread(&analysis), vec![("legacy_mode", 9), ("source", 8)]);
+        assert_eq!(analysis.total_set, 4);
+    }
+
+    #[test]
+    fn test_saved_state_handle_keys() {
+        let analysis = analyze(
+            &[(
+                "DetailViewModel.kt",
+                r#"
+class DetailViewModel(private val savedStateHandle: SavedStateHandle) : ViewModel() {
+    val query = savedStateHandle.getStateFlow("query", "")
+    val itemId: String = savedStateHandle["item_id"]!!
+
+    fun onSearch(text: String) {
+        savedStateHandle["query"] = text
+        savedStateHandle["item_id"] = text
+        savedStateHandle.set("draft", text)
+        savedStateHandle["scroll"] = 0
+    }
+}
+"#,
+            )],
+            "<navigation />",
+        );
+
+        assert_eq!(unread(&analysis), vec![("draft", 9), ("scroll", 10)]);
+        assert_eq!(analysis.total_read, 2);
+    }
+}
@@ -1,20 +1,169 @@
+//! Unused Enum Case Detector
+//!
+//! Detects enum constants that are never used although their enum is.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Find enums that are referenced somewhere
+//! 2. Skip enums whose constants are all reachable without a direct
+//!    reference: iterated (`values()`, `entries`, `enumValues<T>()`,
+//!    `EnumSet.allOf`) or looked up by name (`valueOf`, `enumValueOf<T>()`)
+//! 3. Skip enums and constants marked for reflection (`@SerializedName`,
+//!    `@Keep`, `Serializable`), which are looked up by name at runtime
+//! 4. Report constants that are never referenced; `when`/`switch` branches
+//!    count as reads, so exhaustive matches keep every constant alive
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! enum class Status { ACTIVE, INACTIVE, LEGACY }  // LEGACY: DEAD
+//!
+//! fun label(status: Status) = when (status) {
+//!     Status.ACTIVE -> "on"
+//!     else -> "off"
+//! }
+//! ```
+
 use super::Detector;
-use crate::analysis::DeadCode;
-use crate::graph::Graph;
+use crate::analysis::{risk, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationKind, Graph, ReferenceKind};
 
+/// Detector for enum constants that are never used
 pub struct UnusedEnumCaseDetector;
+
 impl UnusedEnumCaseDetector {
     pub fn new() -> Self {
         Self
     }
-}
-impl Detector for UnusedEnumCaseDetector {
-    fn detect(&self, _graph: &Graph) -> Vec<DeadCode> {
-        Vec::new()
+
+    /// Whether every constant of the enum may be used without being named
+    fn is_used_in_bulk(&self, enum_decl: &Declaration, graph: &Graph) -> bool {
+        graph.get_references_to(&enum_decl.id).iter().any(|(_, r)| {
+            matches!(
+                r.kind,
+                ReferenceKind::EnumIteration | ReferenceKind::EnumLookup
+            )
+        })
     }
 }
+
 impl Default for UnusedEnumCaseDetector {
     fn default() -> Self {
         Self::new()
     }
 }
+
+impl Detector for UnusedEnumCaseDetector {
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        let mut issues = Vec::new();
+
+        for enum_decl in graph.declarations() {
+            if enum_decl.kind != DeclarationKind::Enum {
+                continue;
+            }
+            // Unused enums are reported as a whole by reachability analysis
+            if !graph.is_referenced(&enum_decl.id) || self.is_used_in_bulk(enum_decl, graph) {
+                continue;
+            }
+            if is_reflection_prone(enum_decl) {
+                continue;
+            }
+
+            for case_id in graph.get_children(&enum_decl.id) {
+                let Some(case) = graph.get_declaration(case_id) else {
+                    continue;
+                };
+                if case.kind != DeclarationKind::EnumCase || is_reflection_prone(case) {
+                    continue;
+                }
+                if !graph.is_referenced(case_id) {
+                    issues.push(DeadCode::new(case.clone(), DeadCodeIssue::UnusedEnumCase));
+                }
+            }
+        }
+
+        issues.sort_by(|a, b| {
+            let a = &a.declaration.location;
+            let b = &b.declaration.location;
+            a.file.cmp(&b.file).then(a.line.cmp(&b.line))
+        });
+        issues
+    }
+}
+
+fn is_reflection_prone(decl: &Declaration) -> bool {
+    risk::risk_tags(decl)
+        .iter()
+        .any(|tag| tag == risk::REFLECTION_PRONE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, Language, Location, Reference};
+    use std::path::PathBuf;
+
+    fn declaration(name: &str, kind: DeclarationKind, start: usize) -> Declaration {
+        let path = PathBuf::from("Status.kt");
+        Declaration::new(
+            DeclarationId::new(path.clone(), start, start + 5),
+            name.to_string(),
+            kind,
+            Location::new(path, start, 1, start, start + 5),
+            Language::Kotlin,
+        )
+    }
+
+    fn reference(graph: &mut Graph, from: &Declaration, to: &Declaration, kind: ReferenceKind) {
+        let location = from.location.clone();
+        graph.add_reference(
+            &from.id,
+            &to.id,
+            Reference::new(kind, location, to.name.clone()),
+        );
+    }
+
+    /// Status { ACTIVE, LEGACY } used by `label`, which reads ACTIVE
+    fn status_graph(annotations: &[&str]) -> (Graph, Declaration, Declaration) {
+        let mut graph = Graph::new();
+        let status = declaration("Status", DeclarationKind::Enum, 0);
+        let mut active = declaration("ACTIVE", DeclarationKind::EnumCase, 10);
+        active.parent = Some(status.id.clone());
+        let mut legacy = declaration("LEGACY", DeclarationKind::EnumCase, 20);
+        legacy.parent = Some(status.id.clone());
+        legacy.annotations = annotations.iter().map(|a| a.to_string()).collect();
+        let label = declaration("label", DeclarationKind::Function, 30);
+
+        for decl in [&status, &active, &legacy, &label] {
+            graph.add_declaration(decl.clone());
+        }
+        reference(&mut graph, &label, &status, ReferenceKind::Type);
+        reference(&mut graph, &label, &active, ReferenceKind::Read);
+        (graph, status, label)
+    }
+
+    #[test]
+    fn test_reports_unreferenced_constants() {
+        let (graph, _, _) = status_graph(&[]);
+        let issues = UnusedEnumCaseDetector::new().detect(&graph);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].declaration.name, "LEGACY");
+        assert_eq!(issues[0].issue, DeadCodeIssue::UnusedEnumCase);
+    }
+
+    #[test]
+    fn test_bulk_access_keeps_all_constants() {
+        for kind in [ReferenceKind::EnumIteration, ReferenceKind::EnumLookup] {
+            let (mut graph, status, label) = status_graph(&[]);
+            reference(&mut graph, &label, &status, kind);
+            assert!(UnusedEnumCaseDetector::new().detect(&graph).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_skips_serialized_constants() {
+        let (graph, _, _) = status_graph(&["@SerializedName(\"legacy\")"]);
+        assert!(UnusedEnumCaseDetector::new().detect(&graph).is_empty());
+    }
+}
@@ -5,10 +5,11 @@
 //!
 //! ## Detection Algorithm
 //!
-//! 1. Find all `intent.putExtra("KEY", value)` calls
-//! 2. Find all `intent.getStringExtra("KEY")`, `intent.getIntExtra("KEY", default)`, etc.
-//! 3. Match keys that are put but never retrieved
-//! 4. Report unused extras
+//! 1. Collect string constants so keys can be named by them, across files
+//! 2. Find all `intent.putExtra("KEY", value)` calls
+//! 3. Find all `intent.getStringExtra("KEY")`, `intent.getIntExtra("KEY", default)`, etc.
+//! 4. Match keys that are put but never retrieved
+//! 5. Report unused extras
 //!
 //! ## Examples Detected
 //!
@@ -23,9 +24,10 @@
 //! // LEGACY_FLAG is never read anywhere!
 //! ```
 
+use super::key_usage::{KeyConstants, KeyLocation, KeyUsage};
 use regex::Regex;
-use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 /// Android system extras that are read by external apps (not our code)
 /// These should NOT be flagged as unused
@@ -50,12 +52,7 @@ const SYSTEM_EXTRAS: &[&str] = &[
 ];
 
 /// Location info for an extra
-#[derive(Debug, Clone)]
-pub struct ExtraLocation {
-    pub file: std::path::PathBuf,
-    pub line: usize,
-    pub key: String,
-}
+pub type ExtraLocation = KeyLocation;
 
 /// Result of intent extra analysis
 #[derive(Debug)]
@@ -81,15 +78,17 @@ pub struct UnusedIntentExtraDetector {
 impl UnusedIntentExtraDetector {
     pub fn new() -> Self {
         // Match: putExtra("KEY", value) or putExtra(KEY_CONST, value)
-        let put_extra_pattern = Regex::new(r#"putExtra\s*\(\s*"([^"]+)""#).unwrap();
+        let put_extra_pattern =
+            Regex::new(r#"putExtra\s*\(\s*(?:"([^"]+)"|([A-Za-z_][\w.]*))"#).unwrap();
 
         // Match: getStringExtra("KEY"), getIntExtra("KEY", ...), getBooleanExtra("KEY", ...), etc.
         let get_extra_pattern = Regex::new(
-            r#"get(?:String|Int|Long|Float|Double|Boolean|Char|Byte|Short|Serializable|Parcelable|Bundle)?Extra(?:s)?\s*\(\s*"([^"]+)""#
+            r#"get(?:String|Int|Long|Float|Double|Boolean|Char|Byte|Short|Serializable|Parcelable|Bundle)?Extra(?:s)?\s*\(\s*(?:"([^"]+)"|([A-Za-z_][\w.]*))"#
         ).unwrap();
 
         // Match: hasExtra("KEY")
-        let has_extra_pattern = Regex::new(r#"hasExtra\s*\(\s*"([^"]+)""#).unwrap();
+        let has_extra_pattern =
+            Regex::new(r#"hasExtra\s*\(\s*(?:"([^"]+)"|([A-Za-z_][\w.]*))"#).unwrap();
 
         Self {
             put_extra_pattern,
@@ -102,10 +101,7 @@ impl UnusedIntentExtraDetector {
     pub fn analyze(&self, root: &Path) -> IntentExtraAnalysis {
         use ignore::WalkBuilder;
 
-        // Collect all put_extra keys with locations
-        let mut put_extras: HashMap<String, Vec<ExtraLocation>> = HashMap::new();
-        // Collect all get_extra keys (including hasExtra)
-        let mut get_extras: HashSet<String> = HashSet::new();
+        let mut sources = Vec::new();
 
         let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
 
@@ -125,66 +121,58 @@ impl UnusedIntentExtraDetector {
             }
 
             if let Ok(content) = std::fs::read_to_string(path) {
-                for (line_num, line) in content.lines().enumerate() {
-                    // Find putExtra calls
-                    for caps in self.put_extra_pattern.captures_iter(line) {
-                        if let Some(key) = caps.get(1) {
-                            let key_str = key.as_str().to_string();
-                            put_extras
-                                .entry(key_str.clone())
-                                .or_default()
-                                .push(ExtraLocation {
-                                    file: path.to_path_buf(),
-                                    line: line_num + 1,
-                                    key: key_str,
-                                });
-                        }
-                    }
+                sources.push((path.to_path_buf(), content));
+            }
+        }
+
+        self.analyze_sources(&sources)
+    }
 
-                    // Find getXxxExtra calls
-                    for caps in self.get_extra_pattern.captures_iter(line) {
-                        if let Some(key) = caps.get(1) {
-                            get_extras.insert(key.as_str().to_string());
-                        }
+    /// Match the extras put and retrieved by source files
+    pub fn analyze_sources(&self, sources: &[(PathBuf, String)]) -> IntentExtraAnalysis {
+        let mut constants = KeyConstants::new();
+        for (_, content) in sources {
+            constants.collect(content);
+        }
+
+        let mut usage = KeyUsage::new();
+        for (path, content) in sources {
+            for (line_num, line) in content.lines().enumerate() {
+                // Find putExtra calls
+                for caps in self.put_extra_pattern.captures_iter(line) {
+                    if let Some(key) = constants.resolve(&caps) {
+                        usage.add_write(key, path, line_num + 1);
                     }
+                }
 
-                    // Find hasExtra calls
-                    for caps in self.has_extra_pattern.captures_iter(line) {
-                        if let Some(key) = caps.get(1) {
-                            get_extras.insert(key.as_str().to_string());
-                        }
+                // Find getXxxExtra and hasExtra calls
+                for caps in self
+                    .get_extra_pattern
+                    .captures_iter(line)
+                    .chain(self.has_extra_pattern.captures_iter(line))
+                {
+                    if let Some(key) = constants.resolve(&caps) {
+                        usage.add_read(key, path, line_num + 1);
                     }
                 }
             }
         }
 
-        let total_put = put_extras.values().map(|v| v.len()).sum();
-        let total_get = get_extras.len();
+        let total_put = usage.writes.values().map(|v| v.len()).sum();
+        let total_get = usage.reads.len();
 
         // Build a set of system extras for fast lookup
         let system_extras: HashSet<&str> = SYSTEM_EXTRAS.iter().copied().collect();
 
-        // Find unused extras (put but never get)
-        let mut unused_extras = Vec::new();
-        for (key, locations) in &put_extras {
-            // Skip system extras that are read by external apps
-            if system_extras.contains(key.as_str()) {
-                continue;
-            }
-            // Skip keys that look like Android system extras
-            if key.starts_with("android.") {
-                continue;
-            }
-            if !get_extras.contains(key) {
-                // Report only the first location for each key
-                if let Some(first_loc) = locations.first() {
-                    unused_extras.push(first_loc.clone());
-                }
-            }
-        }
-
-        // Sort by file and line
-        unused_extras.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        // Unused extras (put but never get), reported at their first location;
+        // system extras are read by external apps
+        let unused_extras = usage
+            .unread()
+            .into_iter()
+            .filter(|extra| {
+                !system_extras.contains(extra.key.as_str()) && !extra.key.starts_with("android.")
+            })
+            .collect();
 
         IntentExtraAnalysis {
             unused_extras,
@@ -228,4 +216,39 @@ mod tests {
         assert!(caps2.is_some());
         assert_eq!(caps2.unwrap().get(1).unwrap().as_str(), "COUNT");
     }
+
+    #[test]
+    fn test_extra_keys_through_constants() {
+        let sources = vec![
+            (
+                PathBuf::from("Extras.kt"),
+                "object Extras {\n    const val USER_ID = \"user_id\"\n}\n".to_string(),
+            ),
+            (
+                PathBuf::from("ListActivity.kt"),
+                r#"
+fun open(context: Context, id: String) {
+    val intent = Intent(context, DetailActivity::class.java)
+    intent.putExtra(Extras.USER_ID, id)
+    intent.putExtra("LEGACY_FLAG", true)
+    intent.putExtra(Intent.EXTRA_TEXT, id)
+}
+"#
+                .to_string(),
+            ),
+            (
+                PathBuf::from("DetailActivity.java"),
+                "String id = getIntent().getStringExtra(\"user_id\");\n".to_string(),
+            ),
+        ];
+
+        let analysis = UnusedIntentExtraDetector::new().analyze_sources(&sources);
+        let unused: Vec<(&str, usize)> = analysis
+            .unused_extras
+            .iter()
+            .map(|extra| (extra.key.as_str(), extra.line))
+            .collect();
+        assert_eq!(unused, vec![("LEGACY_FLAG", 5)]);
+        assert_eq!(analysis.total_put, 2);
+    }
 }
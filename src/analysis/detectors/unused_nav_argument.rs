@@ -0,0 +1,593 @@
+//! Unused Navigation Argument, Action and Destination Detector
+//!
+//! Detects Navigation Component arguments that are declared in a nav graph but
+//! never read by the destination, actions that are never navigated, and
+//! destinations that nothing navigates to.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Parse `res/navigation/*.xml` for destinations (`<fragment>`, `<dialog>`,
+//!    `<activity>`, nested `<navigation>`), their `<argument>`s and `<action>`s
+//! 2. Scan Kotlin/Java code for argument reads:
+//!    - Safe Args: members accessed in files that use the generated `*Args` class
+//!      (`args.userId`, `getUserId()`)
+//!    - Bundle / SavedStateHandle keys: `getString("userId")`, `handle["userId"]`
+//! 3. Scan code and other XML for navigation: `R.id.<destination>`, `R.id.<action>`,
+//!    generated `*Directions.actionXToY()` calls and `@id/<destination>` menu items
+//! 4. Report arguments never read, actions never navigated (neither `R.id.<action>`
+//!    nor the `*Directions` call is used), and destinations that are not a start
+//!    destination, have no deep link and are never the target of a used action
+//!
+//! ## Examples Detected
+//!
+//! ```xml
+//! <fragment android:id="@+id/detailFragment" android:name="com.example.DetailFragment">
+//!     <argument android:name="itemId" app:argType="string" />
+//!     <argument android:name="legacyMode" app:argType="boolean" />  <!-- DEAD: never read -->
+//!     <action android:id="@+id/action_detail_to_share" app:destination="@id/shareFragment" />  <!-- DEAD -->
+//! </fragment>
+//! <fragment android:id="@+id/oldFragment" android:name="com.example.OldFragment" />  <!-- DEAD -->
+//! ```
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// A destination declared in a navigation graph
+#[derive(Debug, Clone)]
+pub struct NavDestination {
+    /// Destination id without the `@+id/` prefix
+    pub id: String,
+    /// Destination class (android:name), if any
+    pub class_name: Option<String>,
+    /// Tag name (fragment, dialog, activity, navigation)
+    pub kind: String,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+impl NavDestination {
+    /// Simple class name, e.g. "com.example.DetailFragment" -> "DetailFragment"
+    pub fn simple_class_name(&self) -> Option<&str> {
+        self.class_name
+            .as_deref()
+            .map(|c| c.rsplit('.').next().unwrap_or(c))
+    }
+}
+
+/// An `<argument>` declared on a destination
+#[derive(Debug, Clone)]
+pub struct NavArgument {
+    pub name: String,
+    /// Id of the destination declaring the argument
+    pub destination_id: String,
+    /// Class of the destination declaring the argument
+    pub destination_class: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// An `<action>` declared in a navigation graph
+#[derive(Debug, Clone)]
+pub struct NavAction {
+    pub id: String,
+    /// Target destination id
+    pub destination: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// Result of navigation analysis
+#[derive(Debug, Default)]
+pub struct NavigationAnalysis {
+    pub destinations: Vec<NavDestination>,
+    pub arguments: Vec<NavArgument>,
+    pub actions: Vec<NavAction>,
+    /// Arguments that are declared but never read
+    pub unused_arguments: Vec<NavArgument>,
+    /// Actions that are never navigated
+    pub unused_actions: Vec<NavAction>,
+    /// Destinations that are never navigated to
+    pub unreachable_destinations: Vec<NavDestination>,
+}
+
+/// Everything parsed from the navigation XML files
+#[derive(Debug, Default)]
+struct NavGraphs {
+    destinations: Vec<NavDestination>,
+    arguments: Vec<NavArgument>,
+    actions: Vec<NavAction>,
+    start_destinations: HashSet<String>,
+    deep_linked: HashSet<String>,
+}
+
+/// Usage collected from code and non-navigation XML
+#[derive(Debug, Default)]
+struct NavUsage {
+    /// R.id.x and @id/x references
+    ids: HashSet<String>,
+    /// Generated Directions calls, e.g. "actionHomeToDetail"
+    direction_calls: HashSet<String>,
+    /// Bundle/SavedStateHandle keys read via string literals
+    bundle_keys: HashSet<String>,
+    /// Safe Args class simple name -> members accessed in files using it
+    safe_args_members: HashMap<String, HashSet<String>>,
+}
+
+/// Detector for unused navigation arguments and destinations
+pub struct UnusedNavArgumentDetector {
+    r_id_pattern: Regex,
+    xml_id_pattern: Regex,
+    direction_call_pattern: Regex,
+    bundle_key_pattern: Regex,
+    index_key_pattern: Regex,
+    args_class_pattern: Regex,
+    member_pattern: Regex,
+    getter_pattern: Regex,
+}
+
+impl UnusedNavArgumentDetector {
+    pub fn new() -> Self {
+        Self {
+            r_id_pattern: Regex::new(r"R\.id\.(\w+)").unwrap(),
+            xml_id_pattern: Regex::new(r"@\+?id/(\w+)").unwrap(),
+            direction_call_pattern: Regex::new(r"\b(action[A-Z_]\w*)\s*\(").unwrap(),
+            bundle_key_pattern: Regex::new(
+                r#"(?:get\w*|contains\w*|remove)(?:<[^>]*>)?\s*\(\s*"([^"]+)""#,
+            )
+            .unwrap(),
+            index_key_pattern: Regex::new(r#"\[\s*"([^"]+)"\s*\]"#).unwrap(),
+            args_class_pattern: Regex::new(r"\b(\w+Args)\b").unwrap(),
+            member_pattern: Regex::new(r"\.(\w+)\b").unwrap(),
+            getter_pattern: Regex::new(r"\bget([A-Z]\w*)\s*\(").unwrap(),
+        }
+    }
+
+    /// Analyze a project for unused navigation arguments and destinations
+    pub fn analyze(&self, root: &Path) -> NavigationAnalysis {
+        use ignore::WalkBuilder;
+
+        let mut graphs = NavGraphs::default();
+        let mut usage = NavUsage::default();
+
+        let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+            let ext = path.extension().and_then(|e| e.to_str());
+
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+
+            match ext {
+                Some("xml") if Self::is_navigation_file(path) => {
+                    self.parse_navigation_xml(path, &content, &mut graphs);
+                }
+                Some("xml") => {
+                    for caps in self.xml_id_pattern.captures_iter(&content) {
+                        usage.ids.insert(caps[1].to_string());
+                    }
+                }
+                Some("kt") | Some("java") => {
+                    // Navigating from tests doesn't keep a destination alive
+                    let path_str = path.to_string_lossy();
+                    if path_str.contains("/test/") || path_str.contains("/androidTest/") {
+                        continue;
+                    }
+                    self.collect_code_usage(&content, &mut usage);
+                }
+                _ => {}
+            }
+        }
+
+        Self::build_analysis(graphs, &usage)
+    }
+
+    fn is_navigation_file(path: &Path) -> bool {
+        path.parent()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().starts_with("navigation"))
+            .unwrap_or(false)
+    }
+
+    /// Strip "@+id/" or "@id/" from an id attribute value
+    fn strip_id(value: &str) -> String {
+        value
+            .trim_start_matches("@+id/")
+            .trim_start_matches("@id/")
+            .to_string()
+    }
+
+    /// Convert an action id to its generated Directions method name
+    /// e.g., "action_home_to_detail" -> "actionHomeToDetail"
+    fn to_camel_case(id: &str) -> String {
+        let mut result = String::with_capacity(id.len());
+        let mut upper_next = false;
+        for c in id.chars() {
+            if c == '_' {
+                upper_next = true;
+            } else if upper_next {
+                result.extend(c.to_uppercase());
+                upper_next = false;
+            } else {
+                result.push(c);
+            }
+        }
+        result
+    }
+
+    fn attribute(e: &BytesStart, suffix: &str) -> Option<String> {
+        e.attributes().filter_map(|a| a.ok()).find_map(|attr| {
+            let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+            if key == suffix || key.ends_with(&format!(":{}", suffix)) {
+                Some(String::from_utf8_lossy(&attr.value).to_string())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Parse a navigation graph XML file
+    fn parse_navigation_xml(&self, path: &Path, content: &str, graphs: &mut NavGraphs) {
+        let mut reader = Reader::from_str(content);
+        reader.trim_text(true);
+
+        let mut buf = Vec::new();
+        // Stack of enclosing destination ids (None for elements that aren't destinations)
+        let mut stack: Vec<Option<String>> = Vec::new();
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        loop {
+            let event = reader.read_event_into(&mut buf);
+            let position = reader.buffer_position();
+            let line = line_starts.partition_point(|&start| start <= position);
+
+            match event {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                    let is_empty = matches!(event, Ok(Event::Empty(_)));
+                    let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    let current = stack.iter().rev().flatten().next().cloned();
+
+                    let mut pushed = None;
+                    match tag_name.as_str() {
+                        "fragment" | "dialog" | "activity" | "navigation" => {
+                            if let Some(start) = Self::attribute(e, "startDestination") {
+                                graphs.start_destinations.insert(Self::strip_id(&start));
+                            }
+                            // The root graph is reached through app:navGraph, not navigate()
+                            let is_root = tag_name == "navigation" && stack.is_empty();
+                            if let Some(id) = Self::attribute(e, "id") {
+                                let id = Self::strip_id(&id);
+                                if is_root {
+                                    graphs.start_destinations.insert(id.clone());
+                                }
+                                graphs.destinations.push(NavDestination {
+                                    id: id.clone(),
+                                    class_name: Self::attribute(e, "name"),
+                                    kind: tag_name.clone(),
+                                    file: path.to_path_buf(),
+                                    line,
+                                });
+                                pushed = Some(id);
+                            }
+                        }
+                        "argument" => {
+                            if let (Some(name), Some(dest_id)) =
+                                (Self::attribute(e, "name"), current.clone())
+                            {
+                                let destination_class = graphs
+                                    .destinations
+                                    .iter()
+                                    .rev()
+                                    .find(|d| d.id == dest_id)
+                                    .and_then(|d| d.class_name.clone());
+                                graphs.arguments.push(NavArgument {
+                                    name,
+                                    destination_id: dest_id,
+                                    destination_class,
+                                    file: path.to_path_buf(),
+                                    line,
+                                });
+                            }
+                        }
+                        "action" => {
+                            if let Some(id) = Self::attribute(e, "id") {
+                                graphs.actions.push(NavAction {
+                                    id: Self::strip_id(&id),
+                                    destination: Self::attribute(e, "destination")
+                                        .map(|d| Self::strip_id(&d)),
+                                    file: path.to_path_buf(),
+                                    line,
+                                });
+                            }
+                        }
+                        "deepLink" => {
+                            if let Some(dest_id) = current {
+                                graphs.deep_linked.insert(dest_id);
+                            }
+                        }
+                        _ => {}
+                    }
+
+                    if !is_empty {
+                        stack.push(pushed);
+                    }
+                }
+                Ok(Event::End(_)) => {
+                    stack.pop();
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+    }
+
+    /// Collect navigation and argument usage from Kotlin/Java source
+    fn collect_code_usage(&self, content: &str, usage: &mut NavUsage) {
+        for caps in self.r_id_pattern.captures_iter(content) {
+            usage.ids.insert(caps[1].to_string());
+        }
+        for caps in self.direction_call_pattern.captures_iter(content) {
+            usage.direction_calls.insert(caps[1].to_string());
+        }
+        for caps in self.bundle_key_pattern.captures_iter(content) {
+            usage.bundle_keys.insert(caps[1].to_string());
+        }
+        for caps in self.index_key_pattern.captures_iter(content) {
+            usage.bundle_keys.insert(caps[1].to_string());
+        }
+
+        // Safe Args: attribute member accesses to every *Args class used in this file
+        let args_classes: HashSet<&str> = self
+            .args_class_pattern
+            .captures_iter(content)
+            .filter_map(|c| c.get(1).map(|m| m.as_str()))
+            .collect();
+        if args_classes.is_empty() {
+            return;
+        }
+
+        let mut members: HashSet<String> = self
+            .member_pattern
+            .captures_iter(content)
+            .map(|c| c[1].to_string())
+            .collect();
+        for caps in self.getter_pattern.captures_iter(content) {
+            // Java accessors: getUserId() -> userId
+            let mut chars = caps[1].chars();
+            if let Some(first) = chars.next() {
+                members.insert(first.to_lowercase().chain(chars).collect());
+            }
+        }
+
+        for class in args_classes {
+            usage
+                .safe_args_members
+                .entry(class.to_string())
+                .or_default()
+                .extend(members.iter().cloned());
+        }
+    }
+
+    fn is_argument_read(argument: &NavArgument, usage: &NavUsage) -> bool {
+        if usage.bundle_keys.contains(&argument.name) {
+            return true;
+        }
+
+        let Some(class) = argument.destination_class.as_deref() else {
+            return false;
+        };
+        let simple = class.rsplit('.').next().unwrap_or(class);
+
+        usage
+            .safe_args_members
+            .get(&format!("{}Args", simple))
+            .map(|members| members.contains(&argument.name))
+            .unwrap_or(false)
+    }
+
+    /// Whether an action is navigated: `navigate(R.id.action_x)`, an `@id/`
+    /// reference in XML or the generated `*Directions.actionX()` call
+    fn is_action_used(action: &NavAction, usage: &NavUsage) -> bool {
+        usage.ids.contains(&action.id)
+            || usage
+                .direction_calls
+                .contains(&Self::to_camel_case(&action.id))
+    }
+
+    fn build_analysis(graphs: NavGraphs, usage: &NavUsage) -> NavigationAnalysis {
+        // Destinations targeted by an action that is used from code or XML
+        let mut navigated: HashSet<String> = graphs
+            .actions
+            .iter()
+            .filter(|a| Self::is_action_used(a, usage))
+            .filter_map(|a| a.destination.clone())
+            .collect();
+        navigated.extend(usage.ids.iter().cloned());
+
+        let mut unused_arguments: Vec<NavArgument> = graphs
+            .arguments
+            .iter()
+            .filter(|a| !Self::is_argument_read(a, usage))
+            .cloned()
+            .collect();
+
+        let mut unused_actions: Vec<NavAction> = graphs
+            .actions
+            .iter()
+            .filter(|a| !Self::is_action_used(a, usage))
+            .cloned()
+            .collect();
+
+        let mut unreachable_destinations: Vec<NavDestination> = graphs
+            .destinations
+            .iter()
+            .filter(|d| {
+                !graphs.start_destinations.contains(&d.id)
+                    && !graphs.deep_linked.contains(&d.id)
+                    && !navigated.contains(&d.id)
+            })
+            .cloned()
+            .collect();
+
+        unused_arguments.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        unused_actions.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        unreachable_destinations.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+
+        NavigationAnalysis {
+            destinations: graphs.destinations,
+            arguments: graphs.arguments,
+            actions: graphs.actions,
+            unused_arguments,
+            unused_actions,
+            unreachable_destinations,
+        }
+    }
+}
+
+impl Default for UnusedNavArgumentDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAV_GRAPH: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<navigation xmlns:android="http://schemas.android.com/apk/res/android"
+    xmlns:app="http://schemas.android.com/apk/res-auto"
+    android:id="@+id/main_graph"
+    app:startDestination="@id/homeFragment">
+
+    <fragment
+        android:id="@+id/homeFragment"
+        android:name="com.example.HomeFragment">
+        <action
+            android:id="@+id/action_home_to_detail"
+            app:destination="@id/detailFragment" />
+        <action
+            android:id="@+id/action_home_to_old"
+            app:destination="@id/oldFragment" />
+    </fragment>
+
+    <fragment
+        android:id="@+id/detailFragment"
+        android:name="com.example.DetailFragment">
+        <argument android:name="itemId" app:argType="string" />
+        <argument android:name="legacyMode" app:argType="boolean" />
+        <argument android:name="source" app:argType="string" />
+    </fragment>
+
+    <fragment
+        android:id="@+id/oldFragment"
+        android:name="com.example.OldFragment" />
+
+    <fragment
+        android:id="@+id/linkFragment"
+        android:name="com.example.LinkFragment">
+        <deepLink app:uri="example://link" />
+    </fragment>
+</navigation>"#;
+
+    fn parse_graph() -> NavGraphs {
+        let detector = UnusedNavArgumentDetector::new();
+        let mut graphs = NavGraphs::default();
+        detector.parse_navigation_xml(
+            Path::new("res/navigation/main_graph.xml"),
+            NAV_GRAPH,
+            &mut graphs,
+        );
+        graphs
+    }
+
+    #[test]
+    fn test_parse_navigation_graph() {
+        let graphs = parse_graph();
+
+        assert_eq!(graphs.destinations.len(), 5);
+        assert!(graphs.start_destinations.contains("main_graph"));
+        assert_eq!(graphs.arguments.len(), 3);
+        assert!(graphs
+            .arguments
+            .iter()
+            .all(|a| a.destination_id == "detailFragment"));
+        assert_eq!(
+            graphs.arguments[0].destination_class.as_deref(),
+            Some("com.example.DetailFragment")
+        );
+        assert_eq!(graphs.arguments[0].line, 21);
+        assert!(graphs.start_destinations.contains("homeFragment"));
+        assert!(graphs.deep_linked.contains("linkFragment"));
+        assert_eq!(
+            graphs.actions[0].destination.as_deref(),
+            Some("detailFragment")
+        );
+    }
+
+    #[test]
+    fn test_to_camel_case() {
+        assert_eq!(
+            UnusedNavArgumentDetector::to_camel_case("action_home_to_detail"),
+            "actionHomeToDetail"
+        );
+        assert_eq!(
+            UnusedNavArgumentDetector::to_camel_case("actionGlobal"),
+            "actionGlobal"
+        );
+    }
+
+    #[test]
+    fn test_unused_arguments_actions_and_destinations() {
+        let detector = UnusedNavArgumentDetector::new();
+        let mut usage = NavUsage::default();
+        detector.collect_code_usage(
+            r#"
+class HomeFragment : Fragment() {
+    fun open() = findNavController().navigate(
+        HomeFragmentDirections.actionHomeToDetail("42")
+    )
+}
+
+class DetailFragment : Fragment() {
+    private val args: DetailFragmentArgs by navArgs()
+    fun bind() {
+        show(args.itemId)
+        log(requireArguments().getString("source"))
+    }
+}
+"#,
+            &mut usage,
+        );
+
+        let analysis = UnusedNavArgumentDetector::build_analysis(parse_graph(), &usage);
+
+        let unused_args: Vec<_> = analysis
+            .unused_arguments
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect();
+        assert_eq!(unused_args, vec!["legacyMode"]);
+
+        let unused_actions: Vec<_> = analysis
+            .unused_actions
+            .iter()
+            .map(|a| a.id.as_str())
+            .collect();
+        assert_eq!(unused_actions, vec!["action_home_to_old"]);
+
+        let unreachable: Vec<_> = analysis
+            .unreachable_destinations
+            .iter()
+            .map(|d| d.id.as_str())
+            .collect();
+        assert_eq!(unreachable, vec!["oldFragment"]);
+    }
+}
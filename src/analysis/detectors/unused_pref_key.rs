@@ -0,0 +1,372 @@
+//! Unused Preference Key Detector
+//!
+//! Detects SharedPreferences and Jetpack DataStore keys that are written but
+//! never read, and DataStore keys that are defined but never used.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Collect string constants so keys can be named by them, across files
+//! 2. Find SharedPreferences writes (`putString("KEY", ...)`, `putInt`, ...)
+//!    and reads (`getString("KEY", ...)`, `contains("KEY")`, ...) in files
+//!    that use SharedPreferences
+//! 3. Find DataStore key definitions (`val KEY = stringPreferencesKey("key")`)
+//!    and their uses: `prefs[KEY] = value` writes, any other use reads
+//! 4. Report keys written but never read, and DataStore keys never used
+//!
+//! Writes and reads are matched by key string, so a SharedPreferences key read
+//! back through a DataStore key of the same name (a `SharedPreferencesMigration`)
+//! counts as read.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! // In SessionStore.kt
+//! prefs.edit().putString(Keys.TOKEN, token).apply()  // Read below
+//! prefs.edit().putLong("last_sync", now).apply()      // DEAD: never read
+//!
+//! // In SettingsRepository.kt
+//! val DARK_MODE = booleanPreferencesKey("dark_mode")  // DEAD: never used
+//!
+//! // In AuthInterceptor.kt
+//! val token = prefs.getString(Keys.TOKEN, null)
+//! ```
+
+use super::key_usage::{sort_locations, KeyConstants, KeyLocation, KeyUsage};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Marks a file whose `put*`/`get*` calls are SharedPreferences calls
+const PREFS_MARKERS: &[&str] = &[
+    "SharedPreferences",
+    "PreferenceManager",
+    "getSharedPreferences",
+    "getPreferences(",
+];
+
+/// Result of preference key analysis
+#[derive(Debug)]
+pub struct PrefKeyAnalysis {
+    /// Keys written but never read
+    pub unread: Vec<KeyLocation>,
+    /// DataStore keys defined but never read or written
+    pub unused_definitions: Vec<KeyLocation>,
+    /// Distinct keys written
+    pub total_written: usize,
+    /// Distinct keys read
+    pub total_read: usize,
+}
+
+/// A DataStore key variable
+struct KeyDefinition {
+    name: String,
+    location: KeyLocation,
+}
+
+/// Detector for unused SharedPreferences and DataStore keys
+pub struct UnusedPrefKeyDetector {
+    // putString("KEY", ...), putInt(KEY, ...), ...
+    put_pattern: Regex,
+    // getString("KEY", ...), getInt(KEY, ...), contains("KEY")
+    get_pattern: Regex,
+    // val KEY = stringPreferencesKey("key")
+    datastore_key_pattern: Regex,
+}
+
+impl UnusedPrefKeyDetector {
+    pub fn new() -> Self {
+        let put_pattern = Regex::new(
+            r#"\.put(?:String|Int|Boolean|Long|Float|StringSet)\s*\(\s*(?:"([^"]+)"|([A-Za-z_][\w.]*))"#,
+        )
+        .unwrap();
+
+        let get_pattern = Regex::new(
+            r#"\.(?:get(?:String|Int|Boolean|Long|Float|StringSet)|contains)\s*\(\s*(?:"([^"]+)"|([A-Za-z_][\w.]*))"#,
+        )
+        .unwrap();
+
+        let datastore_key_pattern = Regex::new(
+            r#"\b(?:val|var)\s+(\w+)\s*(?::[^=]+)?=\s*(?:string|int|boolean|long|float|double|stringSet|byteArray)PreferencesKey\s*(?:<[^>]*>)?\s*\(\s*(?:"([^"]+)"|([A-Za-z_][\w.]*))"#,
+        )
+        .unwrap();
+
+        Self {
+            put_pattern,
+            get_pattern,
+            datastore_key_pattern,
+        }
+    }
+
+    /// Analyze a directory for unused preference keys
+    pub fn analyze(&self, root: &Path) -> PrefKeyAnalysis {
+        use ignore::WalkBuilder;
+
+        let mut sources = Vec::new();
+
+        let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
+        for entry in walker.flatten() {
+            let path = entry.path();
+
+            // Skip test files
+            let path_str = path.to_string_lossy();
+            if path_str.contains("/test/") || path_str.contains("/androidTest/") {
+                continue;
+            }
+
+            let ext = path.extension().and_then(|e| e.to_str());
+            if !matches!(ext, Some("kt") | Some("java")) {
+                continue;
+            }
+            if let Ok(content) = std::fs::read_to_string(path) {
+                sources.push((path.to_path_buf(), content));
+            }
+        }
+
+        self.analyze_sources(&sources)
+    }
+
+    /// Match the preference keys written and read by source files
+    pub fn analyze_sources(&self, sources: &[(PathBuf, String)]) -> PrefKeyAnalysis {
+        let mut constants = KeyConstants::new();
+        for (_, content) in sources {
+            constants.collect(content);
+        }
+
+        let mut usage = KeyUsage::new();
+        let mut definitions: Vec<KeyDefinition> = Vec::new();
+
+        for (path, content) in sources {
+            let uses_prefs = PREFS_MARKERS.iter().any(|marker| content.contains(marker));
+            for (line_num, line) in content.lines().enumerate() {
+                if uses_prefs {
+                    for caps in self.put_pattern.captures_iter(line) {
+                        if let Some(key) = constants.resolve(&caps) {
+                            usage.add_write(key, path, line_num + 1);
+                        }
+                    }
+                    for caps in self.get_pattern.captures_iter(line) {
+                        if let Some(key) = constants.resolve(&caps) {
+                            usage.add_read(key, path, line_num + 1);
+                        }
+                    }
+                }
+                for caps in self.datastore_key_pattern.captures_iter(line) {
+                    let key = match (caps.get(2), caps.get(3)) {
+                        (Some(literal), _) => Some(literal.as_str().to_string()),
+                        (None, Some(constant)) => {
+                            constants.lookup(constant.as_str()).map(str::to_string)
+                        }
+                        _ => None,
+                    };
+                    if let Some(key) = key {
+                        definitions.push(KeyDefinition {
+                            name: caps[1].to_string(),
+                            location: KeyLocation {
+                                file: path.clone(),
+                                line: line_num + 1,
+                                key,
+                            },
+                        });
+                    }
+                }
+            }
+        }
+
+        let used = self.datastore_uses(sources, &definitions, &mut usage);
+
+        let mut unused_definitions: Vec<KeyLocation> = definitions
+            .into_iter()
+            .filter(|def| !used.contains(&def.name))
+            .map(|def| def.location)
+            .collect();
+        sort_locations(&mut unused_definitions);
+
+        PrefKeyAnalysis {
+            unread: usage.unread(),
+            unused_definitions,
+            total_written: usage.writes.len(),
+            total_read: usage.reads.len(),
+        }
+    }
+
+    /// Record the writes and reads through DataStore key variables, and return
+    /// the names of the variables used anywhere besides their definition
+    fn datastore_uses(
+        &self,
+        sources: &[(PathBuf, String)],
+        definitions: &[KeyDefinition],
+        usage: &mut KeyUsage,
+    ) -> HashSet<String> {
+        let mut used = HashSet::new();
+        if definitions.is_empty() {
+            return used;
+        }
+
+        // A variable name defined with different keys is ambiguous
+        let mut keys: HashMap<&str, Option<&str>> = HashMap::new();
+        for def in definitions {
+            keys.entry(def.name.as_str())
+                .and_modify(|key| {
+                    if *key != Some(def.location.key.as_str()) {
+                        *key = None;
+                    }
+                })
+                .or_insert(Some(def.location.key.as_str()));
+        }
+        let defined_at: HashSet<(&Path, usize, &str)> = definitions
+            .iter()
+            .map(|def| {
+                (
+                    def.location.file.as_path(),
+                    def.location.line,
+                    def.name.as_str(),
+                )
+            })
+            .collect();
+
+        // `[KEY] = value` assigns; `[KEY] == value` compares
+        let names: Vec<String> = keys.keys().map(|name| regex::escape(name)).collect();
+        let use_pattern = Regex::new(&format!(
+            r"(\[\s*)?\b((?:[A-Za-z_]\w*\.)*)({})\b(\s*\]\s*=(?:[^=]|$))?",
+            names.join("|")
+        ))
+        .unwrap();
+
+        for (path, content) in sources {
+            for (line_num, line) in content.lines().enumerate() {
+                for caps in use_pattern.captures_iter(line) {
+                    let name = &caps[3];
+                    if defined_at.contains(&(path.as_path(), line_num + 1, name)) {
+                        continue;
+                    }
+                    used.insert(name.to_string());
+                    let Some(key) = keys.get(name).copied().flatten() else {
+                        continue;
+                    };
+                    if caps.get(1).is_some() && caps.get(4).is_some() {
+                        usage.add_write(key.to_string(), path, line_num + 1);
+                    } else {
+                        usage.add_read(key.to_string(), path, line_num + 1);
+                    }
+                }
+            }
+        }
+        used
+    }
+}
+
+impl Default for UnusedPrefKeyDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze(sources: &[(&str, &str)]) -> PrefKeyAnalysis {
+        let sources: Vec<(PathBuf, String)> = sources
+            .iter()
+            .map(|(path, content)| (PathBuf::from(path), content.to_string()))
+            .collect();
+        UnusedPrefKeyDetector::new().analyze_sources(&sources)
+    }
+
+    #[test]
+    fn test_shared_preferences_keys() {
+        let analysis = analyze(&[
+            (
+                "Keys.kt",
+                r#"
+object Keys {
+    const val TOKEN = "auth_token"
+}
+"#,
+            ),
+            (
+                "SessionStore.kt",
+                r#"
+class SessionStore(private val prefs: SharedPreferences) {
+    fun save(token: String, now: Long) {
+        prefs.edit()
+            .putString(Keys.TOKEN, token)
+            .putLong("last_sync", now)
+            .apply()
+    }
+}
+"#,
+            ),
+            (
+                "AuthInterceptor.java",
+                r#"
+class AuthInterceptor {
+    private final SharedPreferences prefs;
+    String token() { return prefs.getString("auth_token", null); }
+}
+"#,
+            ),
+            (
+                "Cache.kt",
+                r#"
+class Cache {
+    fun store(bundle: Bundle) = bundle.putString("not_a_pref", "")
+}
+"#,
+            ),
+        ]);
+
+        let unread: Vec<(&str, usize)> = analysis
+            .unread
+            .iter()
+            .map(|loc| (loc.key.as_str(), loc.line))
+            .collect();
+        assert_eq!(unread, vec![("last_sync", 6)]);
+        assert_eq!(analysis.total_written, 2);
+        assert_eq!(analysis.total_read, 1);
+    }
+
+    #[test]
+    fn test_datastore_keys() {
+        let analysis = analyze(&[
+            (
+                "SettingsKeys.kt",
+                r#"
+object SettingsKeys {
+    val DARK_MODE = booleanPreferencesKey("dark_mode")
+    val LAST_TAB = stringPreferencesKey("last_tab")
+    val ONBOARDED = booleanPreferencesKey("onboarded")
+    val LEGACY_THEME = intPreferencesKey("legacy_theme")
+}
+"#,
+            ),
+            (
+                "SettingsRepository.kt",
+                r#"
+class SettingsRepository(private val dataStore: DataStore<Preferences>) {
+    val darkMode = dataStore.data.map { it[SettingsKeys.DARK_MODE] ?: false }
+
+    suspend fun save(tab: String) {
+        dataStore.edit { prefs ->
+            prefs[SettingsKeys.LAST_TAB] = tab
+            prefs[SettingsKeys.DARK_MODE] = true
+        }
+    }
+
+    fun isOnboarded(prefs: Preferences) = prefs[SettingsKeys.ONBOARDED] == true
+}
+"#,
+            ),
+        ]);
+
+        let unread: Vec<&str> = analysis.unread.iter().map(|l| l.key.as_str()).collect();
+        assert_eq!(unread, vec!["last_tab"]);
+
+        let unused: Vec<(&str, usize)> = analysis
+            .unused_definitions
+            .iter()
+            .map(|loc| (loc.key.as_str(), loc.line))
+            .collect();
+        assert_eq!(unused, vec![("legacy_theme", 6)]);
+    }
+}
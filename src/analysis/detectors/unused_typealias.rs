@@ -0,0 +1,152 @@
+//! Unused Type Alias Detector
+//!
+//! Detects Kotlin `typealias` declarations that are never used. A type alias
+//! has no runtime representation, so every use is a reference to the alias
+//! name in a type position (parameter, return, property or generic type),
+//! a constructor call through the alias, or a `::class` literal.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Find all TypeAlias declarations in the graph
+//! 2. Collect references to each alias, ignoring references from the alias itself
+//! 3. Report aliases with no remaining references
+//!
+//! References through an alias name count toward the aliased type: the alias
+//! declaration holds the reference to its target, so a used alias keeps the
+//! aliased type reachable as well.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! typealias UserId = String            // Used below
+//! typealias Callback = (Int) -> Unit   // DEAD: never used
+//!
+//! fun load(id: UserId) { }
+//! ```
+
+use super::Detector;
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationKind, Graph};
+
+/// Detector for unused Kotlin type aliases
+pub struct UnusedTypeAliasDetector {
+    /// Skip `actual typealias` declarations (they satisfy an `expect` declaration)
+    skip_actual: bool,
+}
+
+impl UnusedTypeAliasDetector {
+    pub fn new() -> Self {
+        Self { skip_actual: true }
+    }
+
+    /// Check if a type alias should be skipped
+    fn should_skip(&self, decl: &Declaration) -> bool {
+        if self.skip_actual && decl.modifiers.iter().any(|m| m == "actual") {
+            return true;
+        }
+
+        // Suppressed aliases are intentionally kept
+        decl.annotations.iter().any(|a| a.contains("Suppress"))
+    }
+
+    /// Check if the alias is used anywhere other than its own declaration
+    fn is_used(&self, decl: &Declaration, graph: &Graph) -> bool {
+        graph
+            .get_references_to(&decl.id)
+            .iter()
+            .any(|(from, reference)| from.id != decl.id && reference.kind.counts_as_usage())
+    }
+}
+
+impl Default for UnusedTypeAliasDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Detector for UnusedTypeAliasDetector {
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        let mut issues: Vec<DeadCode> = graph
+            .declarations()
+            .filter(|d| d.kind == DeclarationKind::TypeAlias)
+            .filter(|d| !self.should_skip(d))
+            .filter(|d| !self.is_used(d, graph))
+            .map(|d| {
+                DeadCode::new(d.clone(), DeadCodeIssue::UnusedTypeAlias)
+                    .with_confidence(Confidence::High)
+            })
+            .collect();
+
+        // Sort by file and line for consistent output
+        issues.sort_by(|a, b| {
+            a.declaration
+                .location
+                .file
+                .cmp(&b.declaration.location.file)
+                .then(
+                    a.declaration
+                        .location
+                        .line
+                        .cmp(&b.declaration.location.line),
+                )
+        });
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, Language, Location, Reference, ReferenceKind};
+    use std::path::PathBuf;
+
+    fn make_declaration(name: &str, kind: DeclarationKind, start: usize) -> Declaration {
+        let file = PathBuf::from("test.kt");
+        Declaration::new(
+            DeclarationId::new(file.clone(), start, start + 10),
+            name.to_string(),
+            kind,
+            Location::new(file, start / 10 + 1, 1, start, start + 10),
+            Language::Kotlin,
+        )
+    }
+
+    #[test]
+    fn test_unused_alias_reported() {
+        let mut graph = Graph::new();
+        graph.add_declaration(make_declaration("Callback", DeclarationKind::TypeAlias, 0));
+
+        let issues = UnusedTypeAliasDetector::new().detect(&graph);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue, DeadCodeIssue::UnusedTypeAlias);
+    }
+
+    #[test]
+    fn test_used_alias_not_reported() {
+        let mut graph = Graph::new();
+        let alias = make_declaration("UserId", DeclarationKind::TypeAlias, 0);
+        let func = make_declaration("load", DeclarationKind::Function, 20);
+        let alias_id = graph.add_declaration(alias);
+        let func_id = graph.add_declaration(func.clone());
+        graph.add_reference(
+            &func_id,
+            &alias_id,
+            Reference::new(ReferenceKind::Type, func.location, "UserId".to_string()),
+        );
+
+        let issues = UnusedTypeAliasDetector::new().detect(&graph);
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_actual_alias_skipped() {
+        let mut graph = Graph::new();
+        let mut alias = make_declaration("Platform", DeclarationKind::TypeAlias, 0);
+        alias.modifiers.push("actual".to_string());
+        graph.add_declaration(alias);
+
+        let issues = UnusedTypeAliasDetector::new().detect(&graph);
+        assert!(issues.is_empty());
+    }
+}
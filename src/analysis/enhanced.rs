@@ -1,7 +1,9 @@
 // Enhanced dead code analyzer with parallel processing
 // and ProGuard cross-validation
 
+use super::MemberRetention;
 use super::{Confidence, DeadCode, DeadCodeIssue};
+use crate::config::RetainMembers;
 use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph};
 use crate::proguard::ProguardUsage;
 use rayon::prelude::*;
@@ -15,6 +17,8 @@ pub struct EnhancedAnalyzer {
     proguard: Option<Arc<ProguardUsage>>,
     /// Whether to use strict mode (report more items)
     strict_mode: bool,
+    /// Which members of reachable classes are kept without a reference
+    retention: MemberRetention,
 }
 
 impl EnhancedAnalyzer {
@@ -22,6 +26,7 @@ impl EnhancedAnalyzer {
         Self {
             proguard: None,
             strict_mode: false,
+            retention: MemberRetention::new(RetainMembers::All),
         }
     }
 
@@ -35,6 +40,18 @@ impl EnhancedAnalyzer {
         self
     }
 
+    pub fn with_retain_members(mut self, retain_members: RetainMembers) -> Self {
+        self.retention = self.retention.with_default(retain_members);
+        self
+    }
+
+    /// Retention policy with per-class overrides, from
+    /// [`EntryPointDetector::member_retention`](super::EntryPointDetector::member_retention)
+    pub fn with_member_retention(mut self, retention: MemberRetention) -> Self {
+        self.retention = retention;
+        self
+    }
+
     /// Analyze the graph and find dead code with parallel processing
     pub fn analyze(
         &self,
@@ -111,7 +128,10 @@ impl EnhancedAnalyzer {
             let mut class_members = HashSet::new();
             for decl in graph.declarations() {
                 if let Some(parent_id) = &decl.parent {
-                    if reachable.contains(parent_id) && !reachable.contains(&decl.id) {
+                    if reachable.contains(parent_id)
+                        && !reachable.contains(&decl.id)
+                        && self.retention.retains(decl, graph)
+                    {
                         class_members.insert(decl.id.clone());
                     }
                 }
@@ -1,14 +1,422 @@
-use crate::config::Config;
-use crate::discovery::FileFinder;
-use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph};
+use super::event_bus::EventBusUsage;
+use super::scheduled_work::{ScheduledWork, SchedulingApi};
+use crate::config::{Config, RetainMembers};
+use crate::discovery::{FileFinder, FileType, SourceFile};
+use crate::graph::{simple_type_name, Declaration, DeclarationId, DeclarationKind, Graph};
 use crate::parser::xml::{
-    LayoutParser, ManifestParser, MenuParser, NavigationParser, XmlParseResult,
+    LayoutParser, ManifestComponent, ManifestParser, MenuParser, NavigationParser, XmlParseResult,
+    XmlResourceParser,
 };
+use crate::parser::{JniReferences, JniScanner};
 use miette::Result;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use tracing::{debug, info};
 
+/// Why a declaration is an entry point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntryPointCategory {
+    Activity,
+    Fragment,
+    Service,
+    BroadcastReceiver,
+    ContentProvider,
+    Application,
+    ViewModel,
+    AppWidget,
+    QuickSettingsTile,
+    WearTile,
+    WearComplication,
+    CarApp,
+    AccessibilityService,
+    Shortcut,
+    Worker,
+    Job,
+    EventSubscriber,
+    /// Other class named in AndroidManifest.xml (meta-data values)
+    Manifest,
+    Layout,
+    Navigation,
+    Menu,
+    /// Other class named in a res/xml resource
+    XmlResource,
+    Annotation,
+    Serialization,
+    Native,
+    Main,
+    Script,
+    Configured,
+    RetainPattern,
+    RetainRule,
+    Jni,
+    Generated,
+    /// Outside the `--only-package` scope, in a run that did not parse the
+    /// sources that could use it
+    OutsidePackageScope,
+    /// Compose `@Preview`, which only runs in the IDE
+    Preview,
+    /// Test method or fixture, with `--entry-set tests`
+    Test,
+}
+
+impl EntryPointCategory {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EntryPointCategory::Activity => "activity",
+            EntryPointCategory::Fragment => "fragment",
+            EntryPointCategory::Service => "service",
+            EntryPointCategory::BroadcastReceiver => "broadcast receiver",
+            EntryPointCategory::ContentProvider => "content provider",
+            EntryPointCategory::Application => "application",
+            EntryPointCategory::ViewModel => "view model",
+            EntryPointCategory::AppWidget => "app widget",
+            EntryPointCategory::QuickSettingsTile => "quick settings tile",
+            EntryPointCategory::WearTile => "Wear tile",
+            EntryPointCategory::WearComplication => "Wear complication",
+            EntryPointCategory::CarApp => "Android Auto app",
+            EntryPointCategory::AccessibilityService => "accessibility service",
+            EntryPointCategory::Shortcut => "app shortcut",
+            EntryPointCategory::Worker => "enqueued WorkManager worker",
+            EntryPointCategory::Job => "scheduled JobScheduler job",
+            EntryPointCategory::EventSubscriber => "subscriber of a posted event",
+            EntryPointCategory::Manifest => "manifest",
+            EntryPointCategory::Layout => "layout",
+            EntryPointCategory::Navigation => "navigation graph",
+            EntryPointCategory::Menu => "menu",
+            EntryPointCategory::XmlResource => "XML resource",
+            EntryPointCategory::Annotation => "annotation",
+            EntryPointCategory::Serialization => "serialization",
+            EntryPointCategory::Native => "native method",
+            EntryPointCategory::Main => "main function",
+            EntryPointCategory::Script => "script",
+            EntryPointCategory::Configured => "configured entry point",
+            EntryPointCategory::RetainPattern => "retain pattern",
+            EntryPointCategory::RetainRule => "retain rule",
+            EntryPointCategory::Jni => "JNI",
+            EntryPointCategory::Generated => "generated code",
+            EntryPointCategory::OutsidePackageScope => "outside package scope",
+            EntryPointCategory::Preview => "preview-only",
+            EntryPointCategory::Test => "test",
+        }
+    }
+
+    /// Category of a class extending a framework type, by supertype as written
+    fn from_super_types(super_types: &[String]) -> Option<Self> {
+        // More specific types come before the ones they contain
+        const SUPER_TYPES: &[(&str, EntryPointCategory)] = &[
+            ("AppWidgetProvider", EntryPointCategory::AppWidget),
+            ("GlanceAppWidgetReceiver", EntryPointCategory::AppWidget),
+            (
+                "ComplicationDataSourceService",
+                EntryPointCategory::WearComplication,
+            ),
+            (
+                "ComplicationProviderService",
+                EntryPointCategory::WearComplication,
+            ),
+            ("GlanceTileService", EntryPointCategory::WearTile),
+            ("SuspendingTileService", EntryPointCategory::WearTile),
+            ("wear.tiles.TileService", EntryPointCategory::WearTile),
+            ("TileService", EntryPointCategory::QuickSettingsTile),
+            ("CarAppService", EntryPointCategory::CarApp),
+            (
+                "AccessibilityService",
+                EntryPointCategory::AccessibilityService,
+            ),
+            ("Activity", EntryPointCategory::Activity),
+            ("Fragment", EntryPointCategory::Fragment),
+            ("Service", EntryPointCategory::Service),
+            ("BroadcastReceiver", EntryPointCategory::BroadcastReceiver),
+            ("ContentProvider", EntryPointCategory::ContentProvider),
+            ("Application", EntryPointCategory::Application),
+            ("ViewModel", EntryPointCategory::ViewModel),
+        ];
+
+        super_types.iter().find_map(|super_type| {
+            SUPER_TYPES
+                .iter()
+                .find(|(name, _)| super_type.contains(name))
+                .map(|(_, category)| *category)
+        })
+    }
+
+    /// Category of a manifest component, by the intent filter actions and
+    /// permission the system binds it with
+    fn from_manifest(component: &ManifestComponent) -> Self {
+        const BINDINGS: &[(&str, EntryPointCategory)] = &[
+            (
+                "android.appwidget.action.APPWIDGET_UPDATE",
+                EntryPointCategory::AppWidget,
+            ),
+            (
+                "android.service.quicksettings.action.QS_TILE",
+                EntryPointCategory::QuickSettingsTile,
+            ),
+            (
+                "android.permission.BIND_QUICK_SETTINGS_TILE",
+                EntryPointCategory::QuickSettingsTile,
+            ),
+            (
+                "androidx.wear.tiles.action.BIND_TILE_PROVIDER",
+                EntryPointCategory::WearTile,
+            ),
+            (
+                "com.google.android.wearable.permission.BIND_TILE_PROVIDER",
+                EntryPointCategory::WearTile,
+            ),
+            (
+                "android.support.wearable.complications.ACTION_COMPLICATION_UPDATE_REQUEST",
+                EntryPointCategory::WearComplication,
+            ),
+            (
+                "com.google.android.wearable.permission.BIND_COMPLICATION_PROVIDER",
+                EntryPointCategory::WearComplication,
+            ),
+            ("androidx.car.app.CarAppService", EntryPointCategory::CarApp),
+            (
+                "android.accessibilityservice.AccessibilityService",
+                EntryPointCategory::AccessibilityService,
+            ),
+            (
+                "android.permission.BIND_ACCESSIBILITY_SERVICE",
+                EntryPointCategory::AccessibilityService,
+            ),
+        ];
+
+        let binding = BINDINGS.iter().find(|(name, _)| {
+            component.actions.contains(*name) || component.permission.as_deref() == Some(*name)
+        });
+        if let Some((_, category)) = binding {
+            return *category;
+        }
+
+        match component.tag.as_str() {
+            "activity" => EntryPointCategory::Activity,
+            "service" => EntryPointCategory::Service,
+            "receiver" => EntryPointCategory::BroadcastReceiver,
+            "provider" => EntryPointCategory::ContentProvider,
+            "application" => EntryPointCategory::Application,
+            _ => EntryPointCategory::Manifest,
+        }
+    }
+}
+
+/// Entry points with the reason each was detected (the first one found)
+pub type EntryPoints = HashMap<DeclarationId, EntryPointCategory>;
+
+fn mark(entry_points: &mut EntryPoints, id: &DeclarationId, category: EntryPointCategory) {
+    entry_points.entry(id.clone()).or_insert(category);
+}
+
+/// Simple name of an annotation as written (`@ui.tooling.preview.Preview(...)`
+/// is `Preview`, `@field:Json` is `Json`)
+fn annotation_name(annotation: &str) -> &str {
+    let name = annotation.trim_start_matches('@');
+    let name = name.split('(').next().unwrap_or(name).trim();
+    let name = name.rsplit(':').next().unwrap_or(name);
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Declarations a configured entry point names: `@Annotation` matches the
+/// declarations carrying it, a pattern with `*` matches qualified names (or
+/// simple names, without a `.`), and anything else a qualified name, falling
+/// back to a simple name
+fn configured_matches<'g>(entry_point: &str, graph: &'g Graph) -> Vec<&'g Declaration> {
+    if entry_point.starts_with('@') {
+        let name = annotation_name(entry_point);
+        return graph
+            .declarations()
+            .filter(|decl| decl.annotations.iter().any(|a| annotation_name(a) == name))
+            .collect();
+    }
+    if entry_point.contains('*') {
+        let qualified = entry_point.contains('.');
+        return graph
+            .declarations()
+            .filter(|decl| {
+                let text = if qualified {
+                    decl.fully_qualified_name.as_deref()
+                } else {
+                    Some(decl.name.as_str())
+                };
+                text.is_some_and(|text| wildcard_match(entry_point, text))
+            })
+            .collect();
+    }
+    match graph.find_by_fqn(entry_point) {
+        Some(decl) => vec![decl],
+        None => graph.find_by_name(entry_point),
+    }
+}
+
+/// Match `text` against a pattern where each `*` stands for any characters
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No `*` at all
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Whether an annotation is `@Preview` or one of the multipreviews built on
+/// it (`@PreviewLightDark`, `@PreviewScreenSizes`, ...)
+pub fn is_preview_annotation(annotation: &str) -> bool {
+    let name = annotation_name(annotation);
+    name.starts_with("Preview") && name != "PreviewParameter"
+}
+
+/// Which members of reachable classes are kept without a reference: one
+/// policy for all classes, overridden for the classes some retain rules keep
+#[derive(Debug, Clone, Default)]
+pub struct MemberRetention {
+    default: RetainMembers,
+    overrides: HashMap<DeclarationId, RetainMembers>,
+}
+
+impl MemberRetention {
+    pub fn new(default: RetainMembers) -> Self {
+        Self {
+            default,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Use `policy` for the members of `class` and of the classes nested in it
+    pub fn with_override(mut self, class: DeclarationId, policy: RetainMembers) -> Self {
+        self.overrides.insert(class, policy);
+        self
+    }
+
+    /// Same overrides, but a different policy for the other classes
+    pub fn with_default(mut self, default: RetainMembers) -> Self {
+        self.default = default;
+        self
+    }
+
+    /// Policy for the members of `class`: that of the innermost enclosing
+    /// class with an override, or the default
+    pub fn policy_for(&self, class: &DeclarationId, graph: &Graph) -> RetainMembers {
+        if self.overrides.is_empty() {
+            return self.default;
+        }
+
+        let mut current = Some(class);
+        while let Some(id) = current {
+            if let Some(policy) = self.overrides.get(id) {
+                return *policy;
+            }
+            current = graph
+                .get_declaration(id)
+                .and_then(|decl| decl.parent.as_ref());
+        }
+        self.default
+    }
+
+    /// Whether a member of a reachable declaration is kept without a reference
+    ///
+    /// Serialization hooks are kept under every policy: the runtime looks
+    /// them up by name once it serializes an instance of the live class.
+    pub fn retains(&self, member: &Declaration, graph: &Graph) -> bool {
+        if is_serialization_hook(member, graph) {
+            return true;
+        }
+        match &member.parent {
+            Some(parent) => self.policy_for(parent, graph).retains(member),
+            None => self.default.retains(member),
+        }
+    }
+}
+
+/// Members the serialization runtime looks up by name on their class: the
+/// `CREATOR` of a Parcelable, the `serialVersionUID`, `readObject` and
+/// `writeObject` hooks of a `java.io.Serializable`, and the companion
+/// `serializer()` of a kotlinx `@Serializable` class. Members of a companion
+/// object count as members of the class it belongs to, and a companion
+/// holding a hook is one itself.
+fn is_serialization_hook(member: &Declaration, graph: &Graph) -> bool {
+    let Some(parent) = member
+        .parent
+        .as_ref()
+        .and_then(|p| graph.get_declaration(p))
+    else {
+        return false;
+    };
+
+    if is_companion(member) {
+        return (member.name == "CREATOR" && is_parcelable(parent))
+            || is_kotlinx_serializable(parent)
+            || graph
+                .get_children(&member.id)
+                .into_iter()
+                .filter_map(|id| graph.get_declaration(id))
+                .any(|child| is_serialization_hook(child, graph));
+    }
+
+    let owner = if is_companion(parent) {
+        match parent
+            .parent
+            .as_ref()
+            .and_then(|p| graph.get_declaration(p))
+        {
+            Some(owner) => owner,
+            None => return false,
+        }
+    } else {
+        parent
+    };
+    match member.name.as_str() {
+        "CREATOR" => is_parcelable(owner),
+        "serialVersionUID"
+        | "serialPersistentFields"
+        | "readObject"
+        | "readObjectNoData"
+        | "writeObject"
+        | "writeReplace"
+        | "readResolve" => is_java_serializable(owner),
+        "serializer" => is_companion(parent) && is_kotlinx_serializable(owner),
+        _ => false,
+    }
+}
+
+fn is_companion(decl: &Declaration) -> bool {
+    decl.kind == DeclarationKind::Object && decl.modifiers.iter().any(|m| m == "companion")
+}
+
+fn is_parcelable(decl: &Declaration) -> bool {
+    decl.super_types
+        .iter()
+        .any(|t| simple_type_name(t) == "Parcelable")
+        || decl
+            .annotations
+            .iter()
+            .any(|a| annotation_name(a) == "Parcelize")
+}
+
+fn is_java_serializable(decl: &Declaration) -> bool {
+    decl.super_types
+        .iter()
+        .any(|t| matches!(simple_type_name(t), "Serializable" | "Externalizable"))
+}
+
+/// `@Serializable` is kotlinx; `java.io.Serializable` is an interface
+fn is_kotlinx_serializable(decl: &Declaration) -> bool {
+    decl.annotations
+        .iter()
+        .any(|a| annotation_name(a) == "Serializable")
+}
+
 /// Detects entry points in an Android project
 pub struct EntryPointDetector<'a> {
     config: &'a Config,
@@ -16,6 +424,10 @@ pub struct EntryPointDetector<'a> {
     layout_parser: LayoutParser,
     navigation_parser: NavigationParser,
     menu_parser: MenuParser,
+    resource_parser: XmlResourceParser,
+    scheduled_work: Option<&'a ScheduledWork>,
+    event_bus: Option<&'a EventBusUsage>,
+    files: Option<&'a [SourceFile]>,
 }
 
 impl<'a> EntryPointDetector<'a> {
@@ -26,74 +438,132 @@ impl<'a> EntryPointDetector<'a> {
             layout_parser: LayoutParser::new(),
             navigation_parser: NavigationParser::new(),
             menu_parser: MenuParser::new(),
+            resource_parser: XmlResourceParser::new(),
+            scheduled_work: None,
+            event_bus: None,
+            files: None,
         }
     }
 
+    /// Keep workers and jobs only where they are scheduled, instead of every
+    /// subclass of Worker and JobService
+    pub fn with_scheduled_work(mut self, scheduled_work: &'a ScheduledWork) -> Self {
+        self.scheduled_work = Some(scheduled_work);
+        self
+    }
+
+    /// Keep event bus subscribers only if their event is posted, instead of
+    /// every `@Subscribe` method
+    pub fn with_event_bus(mut self, event_bus: &'a EventBusUsage) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Read XML entry points from already discovered files instead of
+    /// scanning each root again
+    pub fn with_files(mut self, files: &'a [SourceFile]) -> Self {
+        self.files = Some(files);
+        self
+    }
+
     /// Detect all entry points in the project
     pub fn detect(&self, graph: &Graph, root: &Path) -> Result<HashSet<DeclarationId>> {
-        let mut entry_points = HashSet::new();
+        Ok(self.detect_categorized(graph, root)?.into_keys().collect())
+    }
+
+    /// Detect all entry points in the project, with the reason for each
+    pub fn detect_categorized(&self, graph: &Graph, root: &Path) -> Result<EntryPoints> {
+        let mut entry_points = EntryPoints::new();
 
         // 1. Detect entry points from code analysis
         self.detect_code_entry_points(graph, &mut entry_points);
 
+        let scanned;
+        let files: Vec<&SourceFile> = match self.files {
+            Some(files) => files.iter().filter(|f| f.path.starts_with(root)).collect(),
+            None => {
+                scanned = FileFinder::new(self.config).find_files(root)?;
+                scanned.iter().collect()
+            }
+        };
+
         // 2. Detect entry points from AndroidManifest.xml
         if self.config.android.parse_manifest {
-            self.detect_manifest_entry_points(graph, root, &mut entry_points)?;
+            self.detect_manifest_entry_points(graph, &files, &mut entry_points)?;
         }
 
         // 3. Detect entry points from layout XMLs
         if self.config.android.parse_layouts {
-            self.detect_layout_entry_points(graph, root, &mut entry_points)?;
+            self.detect_layout_entry_points(graph, &files, &mut entry_points)?;
         }
 
         // 4. Detect entry points from navigation XMLs
-        self.detect_navigation_entry_points(graph, root, &mut entry_points)?;
+        self.detect_navigation_entry_points(graph, &files, &mut entry_points)?;
 
         // 5. Detect entry points from menu XMLs
-        self.detect_menu_entry_points(graph, root, &mut entry_points)?;
+        self.detect_menu_entry_points(graph, &files, &mut entry_points)?;
+
+        // 6. Detect entry points from res/xml (shortcuts, widgets, services)
+        self.detect_xml_resource_entry_points(graph, &files, &mut entry_points)?;
+
+        // 7. Keep workers and jobs only where they are scheduled
+        if let Some(scheduled_work) = self.scheduled_work {
+            self.apply_scheduled_work(graph, scheduled_work, &mut entry_points);
+        }
 
-        // 6. Add explicitly configured entry points
+        // 8. Keep event subscribers only if their event is posted
+        if let Some(event_bus) = self.event_bus {
+            self.apply_event_bus(graph, event_bus, &mut entry_points);
+        }
+
+        // 9. Add explicitly configured entry points
         self.add_configured_entry_points(graph, &mut entry_points);
 
-        // 7. Apply retain patterns
+        // 10. Apply retain patterns
         self.apply_retain_patterns(graph, &mut entry_points);
 
+        // 11. Apply structured retain rules
+        self.apply_retain_rules(graph, &mut entry_points)?;
+
+        // 12. Detect entry points from JNI native code
+        if self.config.android.parse_native_sources {
+            let references = JniScanner::new().scan(root);
+            self.add_jni_references(graph, &references, &mut entry_points);
+        }
+
         info!("Detected {} entry points", entry_points.len());
 
         Ok(entry_points)
     }
 
     /// Detect entry points from code analysis (annotations, inheritance)
-    fn detect_code_entry_points(&self, graph: &Graph, entry_points: &mut HashSet<DeclarationId>) {
+    fn detect_code_entry_points(&self, graph: &Graph, entry_points: &mut EntryPoints) {
         for decl in graph.declarations() {
-            if self.is_code_entry_point(decl) {
+            if let Some(category) = self.code_entry_point_category(decl) {
                 debug!(
-                    "Code entry point: {} ({})",
+                    "Code entry point: {} ({}, {})",
                     decl.name,
-                    decl.kind.display_name()
+                    decl.kind.display_name(),
+                    category.label()
                 );
-                entry_points.insert(decl.id.clone());
+                mark(entry_points, &decl.id, category);
             }
         }
     }
 
-    /// Check if a declaration is an entry point based on code analysis
-    fn is_code_entry_point(&self, decl: &Declaration) -> bool {
+    /// Category of a declaration that is an entry point based on code analysis
+    fn code_entry_point_category(&self, decl: &Declaration) -> Option<EntryPointCategory> {
         // Check Android components by inheritance
-        if decl.is_android_entry_point() {
-            return true;
-        }
-
-        // Check annotations
-        for annotation in &decl.annotations {
-            if self.is_entry_point_annotation(annotation) {
-                return true;
-            }
+        if let Some(category) = EntryPointCategory::from_super_types(&decl.super_types) {
+            return Some(category);
         }
 
-        // Check for main functions
+        // Check for main functions and Kotlin scripts
         if decl.kind == DeclarationKind::Function && decl.name == "main" {
-            return true;
+            return Some(EntryPointCategory::Main);
+        }
+        if decl.is_script() {
+            return Some(EntryPointCategory::Script);
         }
 
         // Check for serialization
@@ -103,14 +573,43 @@ impl<'a> EntryPointDetector<'a> {
                 || a.contains("Entity")
                 || a.contains("JsonClass")
         }) {
-            return true;
+            return Some(EntryPointCategory::Serialization);
+        }
+
+        // Previews are rendered by the IDE, never by the app
+        if decl.annotations.iter().any(|a| is_preview_annotation(a)) {
+            return Some(EntryPointCategory::Preview);
         }
 
-        false
+        // Check annotations
+        if decl.is_android_entry_point()
+            || decl
+                .annotations
+                .iter()
+                .any(|a| self.is_entry_point_annotation(a))
+        {
+            return Some(EntryPointCategory::Annotation);
+        }
+
+        // Native methods are implemented in C/C++ and bound at runtime
+        if decl
+            .modifiers
+            .iter()
+            .any(|m| m == "external" || m == "native")
+        {
+            return Some(EntryPointCategory::Native);
+        }
+
+        None
     }
 
     /// Check if an annotation marks an entry point
     fn is_entry_point_annotation(&self, annotation: &str) -> bool {
+        // With compose previews, composables are only alive through calls
+        if self.config.android.compose_previews && annotation_name(annotation) == "Composable" {
+            return false;
+        }
+
         let entry_annotations = [
             // Testing
             "Test",
@@ -231,30 +730,35 @@ impl<'a> EntryPointDetector<'a> {
             "Single",
         ];
 
-        for entry in &entry_annotations {
-            if annotation.contains(entry) {
-                return true;
-            }
+        // Exact names, so `@VisibleForTesting` isn't taken for `@Test`
+        if entry_annotations.contains(&annotation_name(annotation)) {
+            return true;
         }
 
-        false
+        // Subscribers of custom event buses
+        self.config
+            .event_bus
+            .subscribe_annotations
+            .iter()
+            .any(|name| annotation.contains(name.as_str()))
     }
 
     /// Detect entry points from AndroidManifest.xml
     fn detect_manifest_entry_points(
         &self,
         graph: &Graph,
-        root: &Path,
-        entry_points: &mut HashSet<DeclarationId>,
+        files: &[&SourceFile],
+        entry_points: &mut EntryPoints,
     ) -> Result<()> {
-        let finder = FileFinder::new(self.config);
-        let manifests = finder.find_manifests(root)?;
+        let manifests = files
+            .iter()
+            .filter(|f| f.file_type == FileType::XmlManifest);
 
         for manifest in manifests {
             let contents = manifest.read_contents()?;
             let result = self.manifest_parser.parse(&manifest.path, &contents)?;
 
-            self.add_xml_references(graph, &result, entry_points);
+            self.add_xml_references(graph, &result, EntryPointCategory::Manifest, entry_points);
         }
 
         Ok(())
@@ -264,17 +768,16 @@ impl<'a> EntryPointDetector<'a> {
     fn detect_layout_entry_points(
         &self,
         graph: &Graph,
-        root: &Path,
-        entry_points: &mut HashSet<DeclarationId>,
+        files: &[&SourceFile],
+        entry_points: &mut EntryPoints,
     ) -> Result<()> {
-        let finder = FileFinder::new(self.config);
-        let layouts = finder.find_layouts(root)?;
+        let layouts = files.iter().filter(|f| f.file_type == FileType::XmlLayout);
 
         for layout in layouts {
             let contents = layout.read_contents()?;
             let result = self.layout_parser.parse(&layout.path, &contents)?;
 
-            self.add_xml_references(graph, &result, entry_points);
+            self.add_xml_references(graph, &result, EntryPointCategory::Layout, entry_points);
         }
 
         Ok(())
@@ -284,11 +787,13 @@ impl<'a> EntryPointDetector<'a> {
     fn detect_navigation_entry_points(
         &self,
         graph: &Graph,
-        root: &Path,
-        entry_points: &mut HashSet<DeclarationId>,
+        files: &[&SourceFile],
+        entry_points: &mut EntryPoints,
     ) -> Result<()> {
-        let finder = FileFinder::new(self.config);
-        let navigation_files = finder.find_navigation(root)?;
+        let navigation_files: Vec<_> = files
+            .iter()
+            .filter(|f| f.file_type == FileType::XmlNavigation)
+            .collect();
 
         if !navigation_files.is_empty() {
             debug!("Found {} navigation XML files", navigation_files.len());
@@ -298,7 +803,7 @@ impl<'a> EntryPointDetector<'a> {
             let contents = nav_file.read_contents()?;
             let result = self.navigation_parser.parse(&nav_file.path, &contents)?;
 
-            self.add_xml_references(graph, &result, entry_points);
+            self.add_xml_references(graph, &result, EntryPointCategory::Navigation, entry_points);
         }
 
         Ok(())
@@ -308,11 +813,13 @@ impl<'a> EntryPointDetector<'a> {
     fn detect_menu_entry_points(
         &self,
         graph: &Graph,
-        root: &Path,
-        entry_points: &mut HashSet<DeclarationId>,
+        files: &[&SourceFile],
+        entry_points: &mut EntryPoints,
     ) -> Result<()> {
-        let finder = FileFinder::new(self.config);
-        let menu_files = finder.find_menus(root)?;
+        let menu_files: Vec<_> = files
+            .iter()
+            .filter(|f| f.file_type == FileType::XmlMenu)
+            .collect();
 
         if !menu_files.is_empty() {
             debug!("Found {} menu XML files", menu_files.len());
@@ -322,24 +829,58 @@ impl<'a> EntryPointDetector<'a> {
             let contents = menu_file.read_contents()?;
             let result = self.menu_parser.parse(&menu_file.path, &contents)?;
 
-            self.add_xml_references(graph, &result, entry_points);
+            self.add_xml_references(graph, &result, EntryPointCategory::Menu, entry_points);
         }
 
         Ok(())
     }
 
-    /// Add entry points from XML parse results
+    /// Detect entry points from res/xml resources: shortcut targets, app
+    /// widget configuration activities and service settings activities
+    fn detect_xml_resource_entry_points(
+        &self,
+        graph: &Graph,
+        files: &[&SourceFile],
+        entry_points: &mut EntryPoints,
+    ) -> Result<()> {
+        let resources = files.iter().filter(|f| f.is_xml_resource());
+
+        for resource in resources {
+            let contents = resource.read_contents()?;
+            let result = self.resource_parser.parse(&resource.path, &contents)?;
+
+            let category = match result.root_tag.as_deref() {
+                Some("shortcuts") => EntryPointCategory::Shortcut,
+                Some("appwidget-provider") => EntryPointCategory::AppWidget,
+                Some("accessibility-service") => EntryPointCategory::AccessibilityService,
+                _ => EntryPointCategory::XmlResource,
+            };
+            self.add_xml_references(graph, &result, category, entry_points);
+        }
+
+        Ok(())
+    }
+
+    /// Add entry points from XML parse results. Manifest components are
+    /// categorized by how the system binds them, other classes get `category`.
     fn add_xml_references(
         &self,
         graph: &Graph,
         result: &XmlParseResult,
-        entry_points: &mut HashSet<DeclarationId>,
+        category: EntryPointCategory,
+        entry_points: &mut EntryPoints,
     ) {
         for class_ref in &result.class_references {
+            let category = result
+                .components
+                .get(class_ref)
+                .map(EntryPointCategory::from_manifest)
+                .unwrap_or(category);
+
             // Try to find by fully qualified name
             if let Some(decl) = graph.find_by_fqn(class_ref) {
-                debug!("XML entry point: {} (fqn)", decl.name);
-                entry_points.insert(decl.id.clone());
+                debug!("XML entry point: {} (fqn, {})", decl.name, category.label());
+                mark(entry_points, &decl.id, category);
                 continue;
             }
 
@@ -347,54 +888,229 @@ impl<'a> EntryPointDetector<'a> {
             let simple_name = class_ref.split('.').next_back().unwrap_or(class_ref);
             let candidates = graph.find_by_name(simple_name);
             for candidate in candidates {
-                debug!("XML entry point: {} (simple)", candidate.name);
-                entry_points.insert(candidate.id.clone());
+                debug!(
+                    "XML entry point: {} (simple, {})",
+                    candidate.name,
+                    category.label()
+                );
+                mark(entry_points, &candidate.id, category);
             }
         }
     }
 
-    /// Add explicitly configured entry points
-    fn add_configured_entry_points(
+    /// Replace the entry points of workers and jobs by their schedule call
+    /// sites: scheduled ones are entry points, the others are left to
+    /// reachability unless explicitly kept
+    fn apply_scheduled_work(
         &self,
         graph: &Graph,
-        entry_points: &mut HashSet<DeclarationId>,
+        scheduled_work: &ScheduledWork,
+        entry_points: &mut EntryPoints,
     ) {
-        for entry_point in &self.config.entry_points {
-            if let Some(decl) = graph.find_by_fqn(entry_point) {
-                debug!("Configured entry point: {}", decl.name);
-                entry_points.insert(decl.id.clone());
-            } else {
-                // Try as simple name
-                for decl in graph.find_by_name(entry_point) {
-                    debug!("Configured entry point (by name): {}", decl.name);
-                    entry_points.insert(decl.id.clone());
+        for decl in graph.declarations() {
+            let category = match SchedulingApi::required_by(decl) {
+                Some(SchedulingApi::WorkManager) => EntryPointCategory::Worker,
+                Some(SchedulingApi::JobScheduler) => EntryPointCategory::Job,
+                _ => continue,
+            };
+
+            if !scheduled_work.sites(decl).is_empty() {
+                debug!(
+                    "Scheduled entry point: {} ({})",
+                    decl.name,
+                    category.label()
+                );
+                entry_points.insert(decl.id.clone(), category);
+            } else if !decl.annotations.iter().any(|a| a.contains("Keep")) {
+                debug!("Never scheduled: {}", decl.name);
+                entry_points.remove(&decl.id);
+            }
+        }
+    }
+
+    /// Replace the entry points of event subscribers by the post sites of
+    /// their event: subscribers of posted events are entry points, the others
+    /// are left to reachability unless explicitly kept
+    fn apply_event_bus(
+        &self,
+        graph: &Graph,
+        event_bus: &EventBusUsage,
+        entry_points: &mut EntryPoints,
+    ) {
+        for decl in graph.declarations() {
+            if !decl.kind.is_member() && decl.kind != DeclarationKind::Function {
+                continue;
+            }
+            let Some(event) = event_bus.subscribed_event(decl) else {
+                continue;
+            };
+
+            if event_bus.is_posted(event, graph) {
+                debug!("Event subscriber: {} ({})", decl.name, event);
+                entry_points.insert(decl.id.clone(), EntryPointCategory::EventSubscriber);
+            } else if !decl.annotations.iter().any(|a| a.contains("Keep")) {
+                debug!(
+                    "Subscriber of an event never posted: {} ({})",
+                    decl.name, event
+                );
+                entry_points.remove(&decl.id);
+            }
+        }
+    }
+
+    /// Add entry points for classes and members referenced from native code
+    fn add_jni_references(
+        &self,
+        graph: &Graph,
+        references: &JniReferences,
+        entry_points: &mut EntryPoints,
+    ) {
+        if references.is_empty() {
+            return;
+        }
+        debug!(
+            "Found JNI references in {} native files",
+            references.files.len()
+        );
+
+        for decl in graph.declarations() {
+            if decl.kind.is_type() {
+                if let Some(fqn) = decl.fully_qualified_name.as_deref() {
+                    if references.classes.contains(fqn) {
+                        debug!("JNI entry point: {} (class)", decl.name);
+                        mark(entry_points, &decl.id, EntryPointCategory::Jni);
+                    }
                 }
+                continue;
+            }
+            if !decl.kind.is_member() && decl.kind != DeclarationKind::Function {
+                continue;
+            }
+
+            let owner = decl
+                .parent
+                .as_ref()
+                .and_then(|p| graph.get_declaration(p))
+                .and_then(|p| p.fully_qualified_name.as_deref());
+            let name = if decl.kind == DeclarationKind::Constructor {
+                "<init>"
+            } else {
+                decl.name.as_str()
+            };
+            if references.references_member(owner, name) {
+                debug!("JNI entry point: {} (member)", decl.name);
+                mark(entry_points, &decl.id, EntryPointCategory::Jni);
+            }
+        }
+    }
+
+    /// Add explicitly configured entry points
+    fn add_configured_entry_points(&self, graph: &Graph, entry_points: &mut EntryPoints) {
+        for entry_point in &self.config.entry_points {
+            for decl in configured_matches(entry_point, graph) {
+                debug!("Configured entry point '{}': {}", entry_point, decl.name);
+                mark(entry_points, &decl.id, EntryPointCategory::Configured);
             }
         }
     }
 
+    /// Configured entry points that match no declaration, likely typos
+    pub fn unmatched_entry_points(&self, graph: &Graph) -> Vec<&'a str> {
+        self.config
+            .entry_points
+            .iter()
+            .filter(|entry_point| configured_matches(entry_point, graph).is_empty())
+            .map(String::as_str)
+            .collect()
+    }
+
     /// Apply retain patterns to mark additional entry points
-    fn apply_retain_patterns(&self, graph: &Graph, entry_points: &mut HashSet<DeclarationId>) {
+    fn apply_retain_patterns(&self, graph: &Graph, entry_points: &mut EntryPoints) {
         for decl in graph.declarations() {
             // Check config retain patterns
             for pattern in &self.config.retain_patterns {
                 if decl.matches_pattern(pattern) {
                     debug!("Retained by pattern '{}': {}", pattern, decl.name);
-                    entry_points.insert(decl.id.clone());
+                    mark(entry_points, &decl.id, EntryPointCategory::RetainPattern);
                 }
             }
 
             // Check Android component patterns
-            if self.config.android.auto_retain_components {
+            // Workers and jobs are kept by their schedule call sites instead
+            let scheduled =
+                self.scheduled_work.is_some() && SchedulingApi::required_by(decl).is_some();
+            if self.config.android.auto_retain_components && !scheduled {
                 for pattern in &self.config.android.component_patterns {
                     if decl.matches_pattern(pattern) {
                         debug!("Retained by component pattern '{}': {}", pattern, decl.name);
-                        entry_points.insert(decl.id.clone());
+                        mark(entry_points, &decl.id, EntryPointCategory::RetainPattern);
                     }
                 }
             }
         }
     }
+
+    /// Member retention policy: `retain_members`, overridden for the classes
+    /// kept by retain rules that set `members`
+    pub fn member_retention(&self, graph: &Graph) -> Result<MemberRetention> {
+        let mut retention = MemberRetention::new(self.config.retain_members);
+        let rules: Vec<_> = self
+            .config
+            .retain_rules
+            .iter()
+            .filter(|rule| rule.members.is_some())
+            .collect();
+        if rules.is_empty() {
+            return Ok(retention);
+        }
+
+        let matchers = rules
+            .into_iter()
+            .map(|rule| {
+                rule.compile()
+                    .map_err(|e| miette::miette!("Invalid retain rule {}: {}", rule, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for decl in graph.declarations().filter(|decl| decl.kind.is_type()) {
+            let policy = matchers
+                .iter()
+                .find(|m| m.matches(decl, graph))
+                .and_then(|m| m.rule().members);
+            if let Some(policy) = policy {
+                debug!("Members of {} retained: {:?}", decl.name, policy);
+                retention = retention.with_override(decl.id.clone(), policy);
+            }
+        }
+
+        Ok(retention)
+    }
+
+    /// Apply structured retain rules (annotation, supertype, package, name regex)
+    fn apply_retain_rules(&self, graph: &Graph, entry_points: &mut EntryPoints) -> Result<()> {
+        if self.config.retain_rules.is_empty() {
+            return Ok(());
+        }
+
+        let matchers = self
+            .config
+            .retain_rules
+            .iter()
+            .map(|rule| {
+                rule.compile()
+                    .map_err(|e| miette::miette!("Invalid retain rule {}: {}", rule, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for decl in graph.declarations() {
+            if let Some(matcher) = matchers.iter().find(|m| m.matches(decl, graph)) {
+                debug!("Retained by rule {}: {}", matcher.rule(), decl.name);
+                mark(entry_points, &decl.id, EntryPointCategory::RetainRule);
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -410,5 +1126,375 @@ mod tests {
         assert!(detector.is_entry_point_annotation("@Composable"));
         assert!(detector.is_entry_point_annotation("@HiltViewModel"));
         assert!(!detector.is_entry_point_annotation("@Override"));
+
+        let mut config = Config::default();
+        config.android.compose_previews = true;
+        let detector = EntryPointDetector::new(&config);
+        assert!(!detector.is_entry_point_annotation("@Composable"));
+        assert!(is_preview_annotation("@Preview(showBackground = true)"));
+        assert!(is_preview_annotation(
+            "@androidx.compose.ui.tooling.preview.PreviewLightDark"
+        ));
+        assert!(!is_preview_annotation(
+            "@PreviewParameter(UserProvider::class)"
+        ));
+        assert!(!is_preview_annotation("@FlowPreview"));
+    }
+
+    #[test]
+    fn test_entry_point_categories() {
+        use crate::graph::{Language, Location};
+        use std::path::PathBuf;
+
+        let config = Config::default();
+        let detector = EntryPointDetector::new(&config);
+        let class = |name: &str, super_type: &str| {
+            let path = PathBuf::from(format!("{}.kt", name));
+            let mut decl = Declaration::new(
+                DeclarationId::new(path.clone(), 0, 10),
+                name.to_string(),
+                DeclarationKind::Class,
+                Location::new(path, 1, 1, 0, 10),
+                Language::Kotlin,
+            );
+            decl.super_types.push(super_type.to_string());
+            decl
+        };
+
+        for (super_type, category) in [
+            ("AppWidgetProvider", EntryPointCategory::AppWidget),
+            ("GlanceAppWidgetReceiver", EntryPointCategory::AppWidget),
+            ("TileService", EntryPointCategory::QuickSettingsTile),
+            (
+                "androidx.wear.tiles.TileService",
+                EntryPointCategory::WearTile,
+            ),
+            (
+                "ComplicationDataSourceService",
+                EntryPointCategory::WearComplication,
+            ),
+            ("CarAppService", EntryPointCategory::CarApp),
+            (
+                "AccessibilityService",
+                EntryPointCategory::AccessibilityService,
+            ),
+            ("AppCompatActivity", EntryPointCategory::Activity),
+            ("Service", EntryPointCategory::Service),
+        ] {
+            assert_eq!(
+                detector.code_entry_point_category(&class("Entry", super_type)),
+                Some(category),
+                "{}",
+                super_type
+            );
+        }
+        assert_eq!(
+            detector.code_entry_point_category(&class("Plain", "Comparable")),
+            None
+        );
+
+        let component =
+            |tag: &str, action: Option<&str>, permission: Option<&str>| ManifestComponent {
+                tag: tag.to_string(),
+                actions: action.into_iter().map(String::from).collect(),
+                permission: permission.map(String::from),
+            };
+        assert_eq!(
+            EntryPointCategory::from_manifest(&component(
+                "receiver",
+                Some("android.appwidget.action.APPWIDGET_UPDATE"),
+                None
+            )),
+            EntryPointCategory::AppWidget
+        );
+        assert_eq!(
+            EntryPointCategory::from_manifest(&component(
+                "service",
+                None,
+                Some("android.permission.BIND_QUICK_SETTINGS_TILE")
+            )),
+            EntryPointCategory::QuickSettingsTile
+        );
+        assert_eq!(
+            EntryPointCategory::from_manifest(&component("service", None, None)),
+            EntryPointCategory::Service
+        );
+    }
+
+    #[test]
+    fn test_configured_entry_points() {
+        use crate::graph::{Language, Location};
+        use std::path::PathBuf;
+
+        let mut graph = Graph::new();
+        for (i, (fqn, annotation)) in [
+            ("com.example.api.SdkFacade", None),
+            ("com.example.api.internal.Session", None),
+            ("com.example.Exported", Some("@com.example.PublicApi")),
+            ("com.example.Helper", None),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let path = PathBuf::from(format!("File{}.kt", i));
+            let name = fqn.rsplit('.').next().unwrap();
+            let mut decl = Declaration::new(
+                DeclarationId::new(path.clone(), 0, 10),
+                name.to_string(),
+                DeclarationKind::Class,
+                Location::new(path, 1, 1, 0, 10),
+                Language::Kotlin,
+            );
+            decl.fully_qualified_name = Some(fqn.to_string());
+            decl.annotations.extend(annotation.map(String::from));
+            graph.add_declaration(decl);
+        }
+
+        let names = |entry_point: &str| -> Vec<String> {
+            let mut names: Vec<String> = configured_matches(entry_point, &graph)
+                .iter()
+                .map(|d| d.name.clone())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names("com.example.api.SdkFacade"), ["SdkFacade"]);
+        assert_eq!(names("Helper"), ["Helper"]);
+        assert_eq!(names("com.example.api.*"), ["SdkFacade", "Session"]);
+        assert_eq!(names("*Facade"), ["SdkFacade"]);
+        assert_eq!(names("@PublicApi"), ["Exported"]);
+
+        let config = Config {
+            entry_points: vec![
+                "com.example.api.*".to_string(),
+                "com.example.SdkFasade".to_string(),
+                "@PublicAPI".to_string(),
+            ],
+            ..Config::default()
+        };
+        let detector = EntryPointDetector::new(&config);
+        assert_eq!(
+            detector.unmatched_entry_points(&graph),
+            ["com.example.SdkFasade", "@PublicAPI"]
+        );
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("com.example.*", "com.example.Foo"));
+        assert!(wildcard_match("*.api.*Facade", "com.example.api.SdkFacade"));
+        assert!(!wildcard_match(
+            "*.api.*Facade",
+            "com.example.api.SdkSession"
+        ));
+        assert!(!wildcard_match("ab*b", "ab"));
+        assert!(wildcard_match("Foo", "Foo"));
+    }
+
+    #[test]
+    fn test_jni_references_mark_entry_points() {
+        use crate::graph::{Language, Location};
+        use std::path::PathBuf;
+
+        let path = PathBuf::from("Bridge.kt");
+        let mut graph = Graph::new();
+        let mut class = Declaration::new(
+            DeclarationId::new(path.clone(), 0, 100),
+            "Bridge".to_string(),
+            DeclarationKind::Class,
+            Location::new(path.clone(), 1, 1, 0, 100),
+            Language::Kotlin,
+        );
+        class.fully_qualified_name = Some("com.example.Bridge".to_string());
+        let class_id = class.id.clone();
+        graph.add_declaration(class);
+        for (i, name) in ["onEvent", "unused"].into_iter().enumerate() {
+            let mut method = Declaration::new(
+                DeclarationId::new(path.clone(), 10 + i * 10, 15 + i * 10),
+                name.to_string(),
+                DeclarationKind::Method,
+                Location::new(path.clone(), 2 + i, 1, 10 + i * 10, 15 + i * 10),
+                Language::Kotlin,
+            );
+            method.parent = Some(class_id.clone());
+            graph.add_declaration(method);
+        }
+
+        let mut references = JniReferences::default();
+        JniScanner::new().scan_source(
+            r#"jclass c = env->FindClass("com/example/Bridge");
+               env->GetMethodID(c, "onEvent", "()V");"#,
+            &mut references,
+        );
+
+        let config = Config::default();
+        let detector = EntryPointDetector::new(&config);
+        let mut entry_points = EntryPoints::new();
+        detector.add_jni_references(&graph, &references, &mut entry_points);
+
+        assert!(entry_points.contains_key(&class_id));
+        assert!(graph
+            .find_by_name("onEvent")
+            .iter()
+            .any(|d| entry_points.contains_key(&d.id)));
+        assert!(graph
+            .find_by_name("unused")
+            .iter()
+            .all(|d| !entry_points.contains_key(&d.id)));
+    }
+
+    #[test]
+    fn test_member_retention_from_retain_rules() {
+        use crate::config::RetainRule;
+        use crate::graph::{Language, Location};
+        use std::path::PathBuf;
+
+        let path = PathBuf::from("Dto.kt");
+        let make =
+            |name: &str, kind: DeclarationKind, start: usize, parent: Option<&Declaration>| {
+                let mut decl = Declaration::new(
+                    DeclarationId::new(path.clone(), start, start + 5),
+                    name.to_string(),
+                    kind,
+                    Location::new(path.clone(), start / 10 + 1, 1, start, start + 5),
+                    Language::Kotlin,
+                );
+                decl.parent = parent.map(|p| p.id.clone());
+                decl
+            };
+        let mut dto = make("UserDto", DeclarationKind::Class, 0, None);
+        dto.annotations.push("@JsonClass".to_string());
+        let address = make("Address", DeclarationKind::Class, 10, Some(&dto));
+        let street = make("street", DeclarationKind::Property, 20, Some(&address));
+        let helper = make("Helper", DeclarationKind::Class, 30, None);
+        let format = make("format", DeclarationKind::Method, 40, Some(&helper));
+        let mut graph = Graph::new();
+        for decl in [&dto, &address, &street, &helper, &format] {
+            graph.add_declaration(decl.clone());
+        }
+
+        let config = Config {
+            retain_members: RetainMembers::ReferencedOnly,
+            retain_rules: vec![RetainRule {
+                annotated_with: Some("JsonClass".to_string()),
+                members: Some(RetainMembers::All),
+                ..RetainRule::default()
+            }],
+            ..Config::default()
+        };
+        let retention = EntryPointDetector::new(&config)
+            .member_retention(&graph)
+            .unwrap();
+
+        // The rule's policy reaches into nested classes
+        assert_eq!(
+            retention.policy_for(&address.id, &graph),
+            RetainMembers::All
+        );
+        assert!(retention.retains(&street, &graph));
+        assert!(!retention.retains(&format, &graph));
+
+        let deep = retention.with_default(RetainMembers::None);
+        assert!(deep.retains(&street, &graph));
+        assert!(!deep.retains(&format, &graph));
+    }
+
+    #[test]
+    fn test_serialization_hooks_retained() {
+        use crate::graph::{Language, Location};
+        use std::path::PathBuf;
+
+        let path = PathBuf::from("Models.kt");
+        let make =
+            |name: &str, kind: DeclarationKind, start: usize, parent: Option<&Declaration>| {
+                let mut decl = Declaration::new(
+                    DeclarationId::new(path.clone(), start, start + 5),
+                    name.to_string(),
+                    kind,
+                    Location::new(path.clone(), start / 10 + 1, 1, start, start + 5),
+                    Language::Kotlin,
+                );
+                decl.parent = parent.map(|p| p.id.clone());
+                decl
+            };
+        let companion = |name: &str, start: usize, parent: &Declaration| {
+            let mut decl = make(name, DeclarationKind::Object, start, Some(parent));
+            decl.modifiers.push("companion".to_string());
+            decl
+        };
+
+        let mut ticket = make("Ticket", DeclarationKind::Class, 0, None);
+        ticket.super_types.push("Parcelable".to_string());
+        let ticket_companion = companion("Companion", 10, &ticket);
+        let creator = make(
+            "CREATOR",
+            DeclarationKind::Property,
+            20,
+            Some(&ticket_companion),
+        );
+
+        let mut session = make("Session", DeclarationKind::Class, 100, None);
+        session.super_types.push("java.io.Serializable".to_string());
+        let uid = make(
+            "serialVersionUID",
+            DeclarationKind::Field,
+            110,
+            Some(&session),
+        );
+        let read_object = make("readObject", DeclarationKind::Method, 120, Some(&session));
+        let cleanup = make("cleanup", DeclarationKind::Method, 130, Some(&session));
+
+        let mut user = make("UserDto", DeclarationKind::Class, 200, None);
+        user.annotations.push("@Serializable".to_string());
+        let user_companion = companion("Companion", 210, &user);
+        let serializer = make(
+            "serializer",
+            DeclarationKind::Method,
+            220,
+            Some(&user_companion),
+        );
+
+        let plain = make("Plain", DeclarationKind::Class, 300, None);
+        let plain_uid = make(
+            "serialVersionUID",
+            DeclarationKind::Field,
+            310,
+            Some(&plain),
+        );
+        let plain_companion = companion("Companion", 320, &plain);
+
+        let mut graph = Graph::new();
+        for decl in [
+            &ticket,
+            &ticket_companion,
+            &creator,
+            &session,
+            &uid,
+            &read_object,
+            &cleanup,
+            &user,
+            &user_companion,
+            &serializer,
+            &plain,
+            &plain_uid,
+            &plain_companion,
+        ] {
+            graph.add_declaration(decl.clone());
+        }
+
+        let deep = MemberRetention::new(RetainMembers::None);
+        for hook in [
+            &ticket_companion,
+            &creator,
+            &uid,
+            &read_object,
+            &user_companion,
+            &serializer,
+        ] {
+            assert!(deep.retains(hook, &graph), "{} is a hook", hook.name);
+        }
+        for other in [&cleanup, &plain_uid, &plain_companion] {
+            assert!(!deep.retains(other, &graph), "{} is not a hook", other.name);
+        }
     }
 }
@@ -0,0 +1,219 @@
+//! Entry Point Sets
+//!
+//! By default reachability starts from everything that runs the app: Android
+//! components, framework annotations, keep rules. With `--entry-set tests` it
+//! starts from the test suite instead (JUnit 4 and 5 tests and fixtures,
+//! Robolectric and instrumentation tests), so the findings are the code no
+//! test can reach. `--entry-set diff` runs the app analysis and compares both
+//! sets: production code only the app reaches is untested, production code
+//! only tests reach is kept alive by them alone.
+
+use super::ReachabilityAnalyzer;
+use crate::discovery::SourceSet;
+use crate::graph::{Declaration, DeclarationId, Graph};
+use std::collections::HashSet;
+
+/// Which entry points reachability starts from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EntrySet {
+    /// What runs the app
+    #[default]
+    App,
+    /// Test methods and fixtures
+    Tests,
+    /// The app's, compared with reachability from tests
+    Diff,
+}
+
+/// Annotations JUnit 4 and 5 run
+const TEST_ANNOTATIONS: &[&str] = &[
+    "Test",
+    "ParameterizedTest",
+    "RepeatedTest",
+    "TestFactory",
+    "TestTemplate",
+    "Before",
+    "After",
+    "BeforeClass",
+    "AfterClass",
+    "BeforeEach",
+    "AfterEach",
+    "BeforeAll",
+    "AfterAll",
+    "Rule",
+    "ClassRule",
+    "RunWith",
+    "ExtendWith",
+];
+
+/// Entry points of the test suite: the detected entry points in test source
+/// sets and every declaration carrying a test annotation, with the classes
+/// declaring them
+pub fn test_entry_points(
+    graph: &Graph,
+    entry_points: &HashSet<DeclarationId>,
+) -> HashSet<DeclarationId> {
+    let mut tests: HashSet<DeclarationId> = graph
+        .declarations()
+        .filter(|decl| {
+            (entry_points.contains(&decl.id) && is_test_source(decl)) || is_test_annotated(decl)
+        })
+        .map(|decl| decl.id.clone())
+        .collect();
+
+    let classes: Vec<DeclarationId> = tests
+        .iter()
+        .filter_map(|id| graph.get_declaration(id)?.parent.clone())
+        .collect();
+    tests.extend(classes);
+    tests
+}
+
+/// Production code reached from only one of the app and the test suite
+#[derive(Debug, Clone, Default)]
+pub struct EntrySetDiff {
+    /// Reachable from the app, but not from any test
+    pub untested: Vec<Declaration>,
+    /// Reachable from tests, but not from the app
+    pub test_only: Vec<Declaration>,
+}
+
+impl EntrySetDiff {
+    /// Compare reachability from the app's entry points with reachability
+    /// from the tests among them
+    pub fn compute(
+        graph: &Graph,
+        entry_points: &HashSet<DeclarationId>,
+        analyzer: &ReachabilityAnalyzer,
+    ) -> Self {
+        let tests = test_entry_points(graph, entry_points);
+        let app: HashSet<DeclarationId> = entry_points
+            .iter()
+            .filter(|id| !tests.contains(*id) && !SourceSet::from_path(&id.file).is_test())
+            .cloned()
+            .collect();
+
+        let (_, app_reachable) = analyzer.find_unreachable_with_reachable(graph, &app);
+        let (_, test_reachable) = analyzer.find_unreachable_with_reachable(graph, &tests);
+
+        Self {
+            untested: only_in(graph, &app_reachable, &test_reachable),
+            test_only: only_in(graph, &test_reachable, &app_reachable),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.untested.is_empty() && self.test_only.is_empty()
+    }
+}
+
+/// Production declarations in `reachable` but not in `other`, members folded
+/// into their class when the whole class is
+fn only_in(
+    graph: &Graph,
+    reachable: &HashSet<DeclarationId>,
+    other: &HashSet<DeclarationId>,
+) -> Vec<Declaration> {
+    let only: HashSet<&DeclarationId> = graph
+        .declarations()
+        .filter(|decl| reachable.contains(&decl.id) && !other.contains(&decl.id))
+        .filter(|decl| !is_test_source(decl))
+        .map(|decl| &decl.id)
+        .collect();
+
+    let mut declarations: Vec<Declaration> = only
+        .iter()
+        .filter_map(|id| graph.get_declaration(id))
+        .filter(|decl| !decl.parent.as_ref().is_some_and(|p| only.contains(p)))
+        .cloned()
+        .collect();
+    declarations.sort_by(|a, b| {
+        let a = &a.location;
+        let b = &b.location;
+        a.file.cmp(&b.file).then(a.line.cmp(&b.line))
+    });
+    declarations
+}
+
+fn is_test_source(decl: &Declaration) -> bool {
+    SourceSet::from_path(&decl.location.file).is_test()
+}
+
+fn is_test_annotated(decl: &Declaration) -> bool {
+    decl.annotations.iter().any(|annotation| {
+        let name = annotation.trim_start_matches('@');
+        let name = name.split('(').next().unwrap_or(name).trim();
+        TEST_ANNOTATIONS.contains(&name.rsplit('.').next().unwrap_or(name))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationKind, Language, Location, Reference, ReferenceKind};
+    use std::path::PathBuf;
+
+    fn declare(graph: &mut Graph, file: &str, name: &str, start: usize) -> DeclarationId {
+        let file = PathBuf::from(file);
+        let kind = if name.starts_with(char::is_uppercase) {
+            DeclarationKind::Class
+        } else {
+            DeclarationKind::Function
+        };
+        graph.add_declaration(Declaration::new(
+            DeclarationId::new(file.clone(), start, start + 10),
+            name.to_string(),
+            kind,
+            Location::new(file, start + 1, 1, start, start + 10),
+            Language::Kotlin,
+        ))
+    }
+
+    fn call(graph: &mut Graph, from: &DeclarationId, to: &DeclarationId) {
+        let location = Location::new(from.file.clone(), 1, 1, 0, 1);
+        graph.add_reference(
+            from,
+            to,
+            Reference::new(ReferenceKind::Call, location, String::new()),
+        );
+    }
+
+    #[test]
+    fn test_entry_set_diff() {
+        let mut graph = Graph::new();
+        let main = "app/src/main/java/Main.kt";
+        let activity = declare(&mut graph, main, "MainActivity", 0);
+        let checkout = declare(&mut graph, main, "checkout", 20);
+        let format = declare(&mut graph, main, "format", 40);
+        let legacy = declare(&mut graph, main, "legacyFormat", 60);
+
+        let test_file = "app/src/test/java/FormatTest.kt";
+        let test_class = declare(&mut graph, test_file, "FormatTest", 0);
+        let mut test = Declaration::new(
+            DeclarationId::new(PathBuf::from(test_file), 20, 30),
+            "formatsPrices".to_string(),
+            DeclarationKind::Method,
+            Location::new(PathBuf::from(test_file), 3, 5, 20, 30),
+            Language::Kotlin,
+        );
+        test.annotations.push("@Test".to_string());
+        test.parent = Some(test_class.clone());
+        let test = graph.add_declaration(test);
+
+        call(&mut graph, &activity, &checkout);
+        call(&mut graph, &checkout, &format);
+        call(&mut graph, &test, &format);
+        call(&mut graph, &test, &legacy);
+
+        let entry_points: HashSet<DeclarationId> = [activity.clone(), test.clone()].into();
+        let tests = test_entry_points(&graph, &entry_points);
+        assert_eq!(tests, [test, test_class].into());
+
+        let diff = EntrySetDiff::compute(&graph, &entry_points, &ReachabilityAnalyzer::new());
+        let names = |decls: &[Declaration]| -> Vec<String> {
+            decls.iter().map(|d| d.name.clone()).collect()
+        };
+        assert_eq!(names(&diff.untested), vec!["MainActivity", "checkout"]);
+        assert_eq!(names(&diff.test_only), vec!["legacyFormat"]);
+    }
+}
@@ -0,0 +1,355 @@
+//! Event bus linkage
+//!
+//! greenrobot EventBus, Otto and similar buses call `@Subscribe` methods with
+//! every posted event whose type is (a subtype of) the method's parameter
+//! type. Nothing references the subscriber in code, so instead of keeping all
+//! of them, post sites are matched to subscribers by event type:
+//!
+//! ```kotlin
+//! EventBus.getDefault().post(UserLoggedIn(user))   // post site of UserLoggedIn
+//!
+//! @Subscribe(threadMode = ThreadMode.MAIN)
+//! fun onLogin(event: UserLoggedIn) { ... }          // subscriber of UserLoggedIn
+//! ```
+//!
+//! Subscribers of events that are never posted are dead, and so are events
+//! posted without any subscriber.
+
+use crate::config::EventBusConfig;
+use crate::discovery::SourceFile;
+use crate::graph::{Declaration, Graph};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Subscriber method or post site of an event type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSite {
+    /// Subscriber method name (empty for post sites)
+    pub method: String,
+    pub file: PathBuf,
+    /// Line of the annotation or the post call
+    pub line: usize,
+    /// Line of the subscriber's signature (same as `line` for post sites)
+    pub end_line: usize,
+}
+
+/// Subscribers and post sites found in the analyzed sources, by event type
+#[derive(Debug, Default)]
+pub struct EventBusUsage {
+    subscribers: HashMap<String, Vec<EventSite>>,
+    posts: HashMap<String, Vec<EventSite>>,
+    /// Post sites whose event type cannot be told from the source
+    /// (`bus.post(event)`)
+    pub unresolved_posts: usize,
+}
+
+impl EventBusUsage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scan Kotlin and Java sources for subscribers and post sites
+    pub fn scan(files: &[SourceFile], config: &EventBusConfig) -> Self {
+        let scanned: Vec<Self> = files
+            .par_iter()
+            .filter(|file| file.file_type.is_source())
+            .filter_map(|file| {
+                let contents = file.read_contents().ok()?;
+                let mut usage = Self::new();
+                usage.add_source(&contents, &file.path, config);
+                Some(usage)
+            })
+            .collect();
+
+        let mut usage = Self::new();
+        for file_usage in scanned {
+            for (event, sites) in file_usage.subscribers {
+                usage.subscribers.entry(event).or_default().extend(sites);
+            }
+            for (event, sites) in file_usage.posts {
+                usage.posts.entry(event).or_default().extend(sites);
+            }
+            usage.unresolved_posts += file_usage.unresolved_posts;
+        }
+        usage
+    }
+
+    /// Record the subscribers and post sites of a source file
+    pub fn add_source(&mut self, source: &str, file: &Path, config: &EventBusConfig) {
+        let lines: Vec<&str> = source.lines().collect();
+        // `post(` is also Handler.post and View.post; only count it in files
+        // that talk to a bus
+        let uses_bus = source.to_lowercase().contains("bus");
+
+        for (idx, line) in lines.iter().enumerate() {
+            if has_annotation(line, &config.subscribe_annotations) {
+                // The signature follows the annotation, possibly on the same line
+                let signature = lines
+                    .iter()
+                    .enumerate()
+                    .skip(idx)
+                    .take(4)
+                    .find_map(|(sig_idx, sig)| Some((sig_idx, parse_subscriber(sig)?)));
+                if let Some((sig_idx, (method, event))) = signature {
+                    self.subscribers
+                        .entry(event.to_string())
+                        .or_default()
+                        .push(EventSite {
+                            method: method.to_string(),
+                            file: file.to_path_buf(),
+                            line: idx + 1,
+                            end_line: sig_idx + 1,
+                        });
+                }
+            }
+
+            if !uses_bus {
+                continue;
+            }
+            for method in &config.post_methods {
+                let pattern = format!(".{}(", method);
+                for (pos, _) in line.match_indices(&pattern) {
+                    match posted_event(&line[pos + pattern.len()..]) {
+                        Some(event) => {
+                            self.posts
+                                .entry(event.to_string())
+                                .or_default()
+                                .push(EventSite {
+                                    method: String::new(),
+                                    file: file.to_path_buf(),
+                                    line: idx + 1,
+                                    end_line: idx + 1,
+                                })
+                        }
+                        None => self.unresolved_posts += 1,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Event type handled by a subscriber method
+    pub fn subscribed_event(&self, decl: &Declaration) -> Option<&str> {
+        self.subscribers.iter().find_map(|(event, sites)| {
+            sites
+                .iter()
+                .any(|site| {
+                    site.method == decl.name
+                        && site.file == decl.location.file
+                        && (site.line..=site.end_line).contains(&decl.location.line)
+                })
+                .then_some(event.as_str())
+        })
+    }
+
+    /// Whether an event, or a subtype of it, is posted anywhere
+    pub fn is_posted(&self, event: &str, graph: &Graph) -> bool {
+        if matches!(event, "Object" | "Any") {
+            return !self.posts.is_empty() || self.unresolved_posts > 0;
+        }
+        self.posts
+            .keys()
+            .any(|posted| ancestors(graph, posted).contains(event))
+    }
+
+    /// Whether a subscriber receives an event: one of the event type, a
+    /// supertype of it, or `Object`/`Any`
+    pub fn is_subscribed(&self, event: &str, graph: &Graph) -> bool {
+        ["Object", "Any"]
+            .iter()
+            .any(|catch_all| self.subscribers.contains_key(*catch_all))
+            || ancestors(graph, event)
+                .iter()
+                .any(|ancestor| self.subscribers.contains_key(ancestor))
+    }
+
+    /// Post sites by event type
+    pub fn posts(&self) -> impl Iterator<Item = (&String, &Vec<EventSite>)> {
+        self.posts.iter()
+    }
+
+    pub fn has_subscribers(&self) -> bool {
+        !self.subscribers.is_empty()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.subscribers.is_empty() && self.posts.is_empty()
+    }
+}
+
+/// A type and the simple names of all its supertypes declared in the project
+fn ancestors(graph: &Graph, name: &str) -> HashSet<String> {
+    let mut seen = HashSet::new();
+    let mut pending = vec![name.to_string()];
+    while let Some(current) = pending.pop() {
+        if !seen.insert(current.clone()) {
+            continue;
+        }
+        for decl in graph.find_by_name(&current) {
+            if decl.kind.is_type() {
+                pending.extend(decl.super_types.iter().map(|s| type_name(s).to_string()));
+            }
+        }
+    }
+    seen
+}
+
+/// Simple name of a type as written: `com.example.Event<T>?` is `Event`
+fn type_name(written: &str) -> &str {
+    let name = written
+        .split(['<', '(', '?'])
+        .next()
+        .unwrap_or(written)
+        .trim();
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Whether a line carries one of the annotations (`@Subscribe`,
+/// `@Subscribe(threadMode = ...)`, `@org.greenrobot.eventbus.Subscribe`)
+fn has_annotation(line: &str, annotations: &[String]) -> bool {
+    line.split('@').skip(1).any(|annotation| {
+        let name = annotation
+            .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+            .next()
+            .unwrap_or_default();
+        let name = name.rsplit('.').next().unwrap_or(name);
+        annotations.iter().any(|a| a == name)
+    })
+}
+
+/// Method name and first parameter type of a signature:
+/// `fun onLogin(event: UserLoggedIn)` or `public void onLogin(UserLoggedIn event)`
+fn parse_subscriber(line: &str) -> Option<(&str, &str)> {
+    let (head, params) = line.split_once('(')?;
+    if !head.contains("fun ") && !head.contains("void ") {
+        return None;
+    }
+    let method = head.trim_end().rsplit([' ', '.']).next()?;
+    let param = params.split([',', ')']).next()?.trim();
+
+    let event = if head.contains("fun ") {
+        param.split_once(':')?.1
+    } else {
+        // Java: drop modifiers and annotations, the type precedes the name
+        let tokens: Vec<&str> = param
+            .split_whitespace()
+            .filter(|t| *t != "final" && !t.starts_with('@'))
+            .collect();
+        tokens.len().checked_sub(2).map(|i| tokens[i])?
+    };
+    let event = type_name(event);
+    (!method.is_empty() && event.starts_with(char::is_uppercase)).then_some((method, event))
+}
+
+/// Event type of a post call's argument: `UserLoggedIn(user)`,
+/// `new UserLoggedIn(user)` or an object `Events.Refresh`
+fn posted_event(args: &str) -> Option<&str> {
+    let arg = args.trim_start();
+    let arg = arg.strip_prefix("new ").unwrap_or(arg);
+    let end = arg
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        .unwrap_or(arg.len());
+    let event = type_name(&arg[..end]);
+    event.starts_with(char::is_uppercase).then_some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, DeclarationKind, Language, Location};
+
+    fn usage(source: &str) -> EventBusUsage {
+        let mut usage = EventBusUsage::new();
+        usage.add_source(source, Path::new("Events.kt"), &EventBusConfig::default());
+        usage
+    }
+
+    fn class(graph: &mut Graph, name: &str, super_type: Option<&str>) {
+        let path = PathBuf::from("Events.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), name.len(), name.len() + 1),
+            name.to_string(),
+            DeclarationKind::Class,
+            Location::new(path, 1, 1, name.len(), name.len() + 1),
+            Language::Kotlin,
+        );
+        decl.super_types.extend(super_type.map(String::from));
+        graph.add_declaration(decl);
+    }
+
+    #[test]
+    fn test_subscribers_and_posts() {
+        let usage = usage(
+            r#"
+class Session(private val bus: EventBus) {
+    fun login(user: User) {
+        bus.post(UserLoggedIn(user))
+        EventBus.getDefault().postSticky(Events.Refresh)
+        bus.post(pending)
+        handler.post { refresh() }
+    }
+
+    @Subscribe(threadMode = ThreadMode.MAIN)
+    fun onLogin(event: UserLoggedIn) {}
+
+    @Subscribe
+    fun onLogout(event: UserLoggedOut?) {}
+}
+"#,
+        );
+
+        assert!(usage.posts.contains_key("UserLoggedIn"));
+        assert!(usage.posts.contains_key("Refresh"));
+        assert_eq!(usage.unresolved_posts, 1);
+        assert_eq!(usage.subscribers["UserLoggedIn"][0].method, "onLogin");
+        assert_eq!(usage.subscribers["UserLoggedOut"][0].line, 13);
+        assert_eq!(usage.subscribers["UserLoggedOut"][0].end_line, 14);
+
+        let graph = Graph::new();
+        assert!(usage.is_posted("UserLoggedIn", &graph));
+        assert!(!usage.is_posted("UserLoggedOut", &graph));
+        assert!(usage.is_subscribed("UserLoggedIn", &graph));
+        assert!(!usage.is_subscribed("Refresh", &graph));
+    }
+
+    #[test]
+    fn test_java_subscriber_and_event_hierarchy() {
+        let usage = usage(
+            r#"
+public class Cart {
+    @Subscribe public void onChanged(final CartEvent event) {}
+
+    void add(Item item) {
+        bus.post(new ItemAdded(item));
+    }
+}
+"#,
+        );
+        assert_eq!(usage.subscribers["CartEvent"][0].method, "onChanged");
+
+        // Subscribers of a supertype receive its subtypes
+        let mut graph = Graph::new();
+        class(&mut graph, "CartEvent", None);
+        class(&mut graph, "ItemAdded", Some("CartEvent()"));
+        assert!(usage.is_posted("CartEvent", &graph));
+        assert!(usage.is_subscribed("ItemAdded", &graph));
+    }
+
+    #[test]
+    fn test_custom_annotation() {
+        let config = EventBusConfig {
+            subscribe_annotations: vec!["OnEvent".to_string()],
+            post_methods: vec!["dispatch".to_string()],
+            ..EventBusConfig::default()
+        };
+        let mut usage = EventBusUsage::new();
+        usage.add_source(
+            "@OnEvent fun onSync(e: SyncDone) {}\nfun go() { appBus.dispatch(SyncDone()) }\n",
+            Path::new("Sync.kt"),
+            &config,
+        );
+        assert!(usage.subscribers.contains_key("SyncDone"));
+        assert!(usage.posts.contains_key("SyncDone"));
+    }
+}
@@ -0,0 +1,382 @@
+//! Class hierarchy analysis
+//!
+//! A call through an interface or base class can dispatch to any override of
+//! the called member in a subtype, but only in subtypes that are actually
+//! instantiated:
+//!
+//! ```kotlin
+//! interface Shape { fun area(): Double }
+//! class Circle(val r: Double) : Shape { override fun area() = PI * r * r }
+//! class Square(val s: Double) : Shape { override fun area() = s * s }
+//!
+//! shapes.sumOf { it.area() }   // calls Circle.area() if Circle(...) is created
+//! Circle(1.0)                  // Square is never created: Square.area() is dead
+//! ```
+//!
+//! The hierarchy resolves the written super types of project types to their
+//! declarations, indexes where each type is instantiated, and links members
+//! to the super type members they override.
+
+use super::risk;
+use crate::graph::{simple_type_name, Declaration, DeclarationId, DeclarationKind, Graph};
+use crate::graph::{Reference, ReferenceKind};
+use petgraph::visit::EdgeRef;
+use std::collections::{HashMap, HashSet};
+
+/// Super and subtype links, instantiation sites and overrides of the
+/// declarations in a graph
+#[derive(Debug, Default)]
+pub struct ClassHierarchy {
+    /// Super types declared in the project, by type
+    supertypes: HashMap<DeclarationId, Vec<DeclarationId>>,
+
+    /// Direct subtypes declared in the project, by type
+    subtypes: HashMap<DeclarationId, Vec<DeclarationId>>,
+
+    /// Declarations creating instances (constructor calls, `new`, `::class`),
+    /// by type
+    instantiators: HashMap<DeclarationId, Vec<DeclarationId>>,
+
+    /// Types created without a visible constructor call: objects, enums,
+    /// entry points, and types created by frameworks or reflection
+    implicitly_instantiated: HashSet<DeclarationId>,
+
+    /// Members overriding a member of a project super type, by the member
+    /// they override
+    implementations: HashMap<DeclarationId, Vec<DeclarationId>>,
+
+    /// Project super type members each member overrides
+    overridden: HashMap<DeclarationId, Vec<DeclarationId>>,
+
+    /// Members overriding a member of a super type, declared in the project
+    /// or not
+    overrides: HashSet<DeclarationId>,
+}
+
+impl ClassHierarchy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the hierarchy of the declarations in a graph; entry points are
+    /// created by the framework
+    pub fn build(graph: &Graph, entry_points: &HashSet<DeclarationId>) -> Self {
+        let mut hierarchy = Self::new();
+
+        // Enum entries with a body override members of their enum
+        let owners = graph
+            .declarations()
+            .filter(|d| d.kind.is_type() || d.kind == DeclarationKind::EnumCase);
+        for decl in owners {
+            let supertypes: Vec<DeclarationId> = decl
+                .super_types
+                .iter()
+                .flat_map(|written| graph.find_by_name(simple_type_name(written)))
+                .filter(|s| s.kind.is_type() && s.id != decl.id)
+                .map(|s| s.id.clone())
+                .collect();
+            for supertype in &supertypes {
+                hierarchy
+                    .subtypes
+                    .entry(supertype.clone())
+                    .or_default()
+                    .push(decl.id.clone());
+            }
+            hierarchy.supertypes.insert(decl.id.clone(), supertypes);
+
+            if is_implicitly_instantiated(graph, decl, entry_points) {
+                hierarchy.implicitly_instantiated.insert(decl.id.clone());
+            }
+        }
+
+        let inner = graph.inner();
+        for edge in inner.edge_references() {
+            if !creates_instance(edge.weight()) {
+                continue;
+            }
+            let (Some(from), Some(to)) = (
+                inner.node_weight(edge.source()),
+                inner
+                    .node_weight(edge.target())
+                    .and_then(|id| graph.get_declaration(id)),
+            ) else {
+                continue;
+            };
+            let created = match to.kind {
+                DeclarationKind::Constructor => to.parent.as_ref(),
+                kind if kind.is_type() => Some(&to.id),
+                _ => None,
+            };
+            if let Some(created) = created {
+                hierarchy
+                    .instantiators
+                    .entry(created.clone())
+                    .or_default()
+                    .push(from.clone());
+            }
+        }
+
+        for decl in graph.declarations() {
+            if !is_overridable(decl.kind) {
+                continue;
+            }
+            let Some(owner) = decl.parent.as_ref() else {
+                continue;
+            };
+            let overridden = hierarchy.overridden_members(graph, owner, decl);
+            if is_marked_override(decl) || !overridden.is_empty() {
+                hierarchy.overrides.insert(decl.id.clone());
+            }
+            if overridden.is_empty() {
+                continue;
+            }
+            for member in &overridden {
+                hierarchy
+                    .implementations
+                    .entry(member.clone())
+                    .or_default()
+                    .push(decl.id.clone());
+            }
+            hierarchy.overridden.insert(decl.id.clone(), overridden);
+        }
+
+        hierarchy
+    }
+
+    /// Super types of a type that are declared in the project
+    pub fn supertypes(&self, id: &DeclarationId) -> &[DeclarationId] {
+        self.supertypes.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Direct subtypes of a type
+    pub fn subtypes(&self, id: &DeclarationId) -> &[DeclarationId] {
+        self.subtypes.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Members of subtypes overriding a member
+    pub fn implementations(&self, member: &DeclarationId) -> &[DeclarationId] {
+        self.implementations
+            .get(member)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Super type members declared in the project that a member overrides
+    pub fn overridden(&self, member: &DeclarationId) -> &[DeclarationId] {
+        self.overridden
+            .get(member)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Whether a member overrides a member of a super type
+    pub fn is_override(&self, member: &DeclarationId) -> bool {
+        self.overrides.contains(member)
+    }
+
+    /// Whether a member overrides only members declared outside the project
+    /// (`onCreate`, `toString`), which the library or framework calls
+    pub fn overrides_library_member(&self, member: &DeclarationId) -> bool {
+        self.overrides.contains(member) && !self.overridden.contains_key(member)
+    }
+
+    /// Whether instances of a type, or of one of its subtypes, are created by
+    /// reachable code or implicitly
+    ///
+    /// Owners that are not types (members of local declarations) are assumed
+    /// to be instantiated.
+    pub fn is_instantiated(&self, id: &DeclarationId, reachable: &HashSet<DeclarationId>) -> bool {
+        if !self.supertypes.contains_key(id) {
+            return true;
+        }
+        let mut seen = HashSet::new();
+        let mut pending = vec![id];
+        while let Some(current) = pending.pop() {
+            if !seen.insert(current) {
+                continue;
+            }
+            let created = self.implicitly_instantiated.contains(current)
+                || self
+                    .instantiators
+                    .get(current)
+                    .is_some_and(|from| from.iter().any(|f| reachable.contains(f)));
+            if created {
+                return true;
+            }
+            pending.extend(self.subtypes(current));
+        }
+        false
+    }
+
+    /// Members of the transitive super types of `owner` that `member`
+    /// overrides: same name and kind, and the same number of parameters when
+    /// both are known
+    fn overridden_members(
+        &self,
+        graph: &Graph,
+        owner: &DeclarationId,
+        member: &Declaration,
+    ) -> Vec<DeclarationId> {
+        let mut overridden = Vec::new();
+        let mut seen = HashSet::new();
+        let mut pending: Vec<&DeclarationId> = self.supertypes(owner).iter().collect();
+        while let Some(supertype) = pending.pop() {
+            if !seen.insert(supertype) {
+                continue;
+            }
+            for child in graph.get_children(supertype) {
+                let Some(candidate) = graph.get_declaration(child) else {
+                    continue;
+                };
+                let same_arity = match (&candidate.signature, &member.signature) {
+                    (Some(a), Some(b)) => a.parameter_names.len() == b.parameter_names.len(),
+                    _ => true,
+                };
+                if candidate.name == member.name
+                    && candidate.kind.is_callable() == member.kind.is_callable()
+                    && is_overridable(candidate.kind)
+                    && same_arity
+                {
+                    overridden.push(candidate.id.clone());
+                }
+            }
+            pending.extend(self.supertypes(supertype));
+        }
+        overridden
+    }
+}
+
+/// Members that can override or be overridden
+fn is_overridable(kind: DeclarationKind) -> bool {
+    matches!(
+        kind,
+        DeclarationKind::Method | DeclarationKind::Function | DeclarationKind::Property
+    )
+}
+
+/// `override fun` in Kotlin, `@Override` in Java
+fn is_marked_override(decl: &Declaration) -> bool {
+    decl.modifiers.iter().any(|m| m == "override")
+        || decl
+            .annotations
+            .iter()
+            .any(|a| simple_type_name(a) == "Override")
+}
+
+/// References that create an instance of their target: `new Foo()`,
+/// `Foo()` in Kotlin, and class literals, which reflection may instantiate
+fn creates_instance(reference: &Reference) -> bool {
+    matches!(
+        reference.kind,
+        ReferenceKind::Instantiation | ReferenceKind::Call | ReferenceKind::Reflection
+    )
+}
+
+/// Objects and enums create their instances themselves, entry points are
+/// created by the framework, and annotated types or constructors by
+/// frameworks (dependency injection) or reflection (serialization)
+fn is_implicitly_instantiated(
+    graph: &Graph,
+    decl: &Declaration,
+    entry_points: &HashSet<DeclarationId>,
+) -> bool {
+    let created_by_framework = risk::risk_tags(decl)
+        .iter()
+        .any(|tag| tag == risk::REFLECTION_PRONE || tag == risk::FRAMEWORK_ANNOTATED);
+    let annotated_constructor = graph
+        .get_children(&decl.id)
+        .into_iter()
+        .filter_map(|id| graph.get_declaration(id))
+        .any(|c| c.kind == DeclarationKind::Constructor && !c.annotations.is_empty());
+
+    matches!(
+        decl.kind,
+        DeclarationKind::Object | DeclarationKind::Enum | DeclarationKind::EnumCase
+    ) || entry_points.contains(&decl.id)
+        || created_by_framework
+        || annotated_constructor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Language, Location};
+    use std::path::PathBuf;
+
+    fn declaration(name: &str, kind: DeclarationKind, line: usize) -> Declaration {
+        let file = PathBuf::from("Shapes.kt");
+        let start = line * 100;
+        Declaration::new(
+            DeclarationId::new(file.clone(), start, start + 50),
+            name.to_string(),
+            kind,
+            Location::new(file, line, 1, start, start + 50),
+            Language::Kotlin,
+        )
+    }
+
+    fn class(
+        graph: &mut Graph,
+        name: &str,
+        line: usize,
+        super_type: Option<&str>,
+    ) -> DeclarationId {
+        let mut decl = declaration(name, DeclarationKind::Class, line);
+        decl.super_types.extend(super_type.map(String::from));
+        graph.add_declaration(decl)
+    }
+
+    fn method(graph: &mut Graph, name: &str, line: usize, owner: &DeclarationId) -> DeclarationId {
+        let mut decl = declaration(name, DeclarationKind::Method, line);
+        decl.parent = Some(owner.clone());
+        decl.modifiers.push("override".to_string());
+        graph.add_declaration(decl)
+    }
+
+    #[test]
+    fn test_implementations_and_instantiation() {
+        let mut graph = Graph::new();
+        let main = graph.add_declaration(declaration("main", DeclarationKind::Function, 1));
+        let shape = graph.add_declaration(declaration("Shape", DeclarationKind::Interface, 2));
+        let mut area = declaration("area", DeclarationKind::Method, 3);
+        area.parent = Some(shape.clone());
+        let area = graph.add_declaration(area);
+        let circle = class(&mut graph, "Circle", 4, Some("Shape"));
+        let circle_area = method(&mut graph, "area", 5, &circle);
+        let square = class(&mut graph, "Square", 6, Some("Shape"));
+        let square_area = method(&mut graph, "area", 7, &square);
+        let to_string = method(&mut graph, "toString", 8, &square);
+        graph.add_reference(
+            &main,
+            &circle,
+            Reference::new(
+                ReferenceKind::Call,
+                Location::new(PathBuf::from("Shapes.kt"), 1, 1, 0, 0),
+                "Circle".to_string(),
+            ),
+        );
+
+        let hierarchy = ClassHierarchy::build(&graph, &HashSet::new());
+        assert_eq!(hierarchy.supertypes(&circle), std::slice::from_ref(&shape));
+        assert_eq!(hierarchy.subtypes(&shape).len(), 2);
+        let mut implementations = hierarchy.implementations(&area).to_vec();
+        implementations.sort_by_key(|id| id.start);
+        assert_eq!(implementations, vec![circle_area, square_area.clone()]);
+        assert!(hierarchy.is_override(&square_area));
+        assert!(!hierarchy.overrides_library_member(&square_area));
+        assert!(hierarchy.overrides_library_member(&to_string));
+        assert!(!hierarchy.is_override(&area));
+        assert_eq!(
+            hierarchy.overridden(&square_area),
+            std::slice::from_ref(&area)
+        );
+
+        let reachable: HashSet<DeclarationId> = [main].into_iter().collect();
+        assert!(hierarchy.is_instantiated(&circle, &reachable));
+        assert!(!hierarchy.is_instantiated(&square, &reachable));
+        // Instances of a subtype are instances of the interface
+        assert!(hierarchy.is_instantiated(&shape, &reachable));
+        // Constructor calls from unreachable code do not count
+        assert!(!hierarchy.is_instantiated(&circle, &HashSet::new()));
+    }
+}
@@ -7,11 +7,12 @@
 // 2. Reduce false positives from dynamic dispatch
 // 3. Identify code that is reachable but never actually executed
 
+use super::scoring::{self, Evidence, EvidenceKind};
 use super::{Confidence, DeadCode, DeadCodeIssue};
 use crate::coverage::CoverageData;
-use crate::graph::{Declaration, DeclarationKind, Graph, Visibility};
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph};
 use crate::proguard::ProguardUsage;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// Hybrid analyzer that combines static and dynamic analysis
 pub struct HybridAnalyzer {
@@ -19,6 +20,10 @@ pub struct HybridAnalyzer {
     coverage: Option<CoverageData>,
     /// ProGuard/R8 usage.txt data (optional)
     proguard: Option<ProguardUsage>,
+    /// Declarations retained by keep rules, with the rule (when rules were loaded)
+    kept: Option<HashMap<DeclarationId, String>>,
+    /// Whether the compiled classes reach each declaration they contain
+    bytecode: Option<HashMap<DeclarationId, bool>>,
 }
 
 impl HybridAnalyzer {
@@ -26,6 +31,8 @@ impl HybridAnalyzer {
         Self {
             coverage: None,
             proguard: None,
+            kept: None,
+            bytecode: None,
         }
     }
 
@@ -39,6 +46,16 @@ impl HybridAnalyzer {
         self
     }
 
+    pub fn with_keep_rules(mut self, kept: HashMap<DeclarationId, String>) -> Self {
+        self.kept = Some(kept);
+        self
+    }
+
+    pub fn with_bytecode(mut self, reachable: HashMap<DeclarationId, bool>) -> Self {
+        self.bytecode = Some(reachable);
+        self
+    }
+
     /// Check if we have any enhancement data
     pub fn has_data(&self) -> bool {
         self.coverage.is_some() || self.proguard.is_some()
@@ -51,8 +68,8 @@ impl HybridAnalyzer {
 
     /// Enhance dead code findings with runtime coverage and/or ProGuard data
     ///
-    /// This method takes static analysis results and cross-references them
-    /// with runtime coverage and ProGuard usage.txt to adjust confidence levels.
+    /// This method scores static analysis results with the evidence from the
+    /// declaration itself, keep rules, runtime coverage and ProGuard usage.txt.
     pub fn enhance_findings(&self, dead_code: Vec<DeadCode>) -> Vec<DeadCode> {
         dead_code
             .into_iter()
@@ -61,37 +78,102 @@ impl HybridAnalyzer {
     }
 
     fn enhance_single_full(&self, mut dc: DeadCode) -> DeadCode {
-        let decl = &dc.declaration;
+        let mut evidence = scoring::static_evidence(&dc);
+
+        if let Some(ref kept) = self.kept {
+            evidence.push(match kept.get(&dc.declaration.id) {
+                Some(rule) => Evidence::new(
+                    EvidenceKind::KeepRule,
+                    format!("Retained by keep rule {}", rule),
+                ),
+                None => Evidence::new(EvidenceKind::NoKeepRule, "Not retained by any keep rule"),
+            });
+        }
 
-        // Check ProGuard data first (strongest signal)
         if let Some(ref proguard) = self.proguard {
+            let decl = &dc.declaration;
             let class_name = decl.fully_qualified_name.as_deref();
-            if let Some(confidence_boost) = proguard.get_confidence_for(class_name, &decl.name) {
-                if confidence_boost >= 1.0 {
-                    dc.confidence = Confidence::Confirmed;
+            match proguard.get_confidence_for(class_name, &decl.name) {
+                Some(boost) if boost >= 1.0 => {
+                    evidence.push(Evidence::new(
+                        EvidenceKind::RemovedByProguard,
+                        "Removed as unused by R8/ProGuard (usage.txt)",
+                    ));
                     dc.message = format!("{} (confirmed by R8/ProGuard)", dc.message);
-                    return dc;
-                } else if confidence_boost >= 0.8 {
-                    dc.confidence = Confidence::High;
                 }
+                Some(boost) if boost >= 0.8 => evidence.push(Evidence::new(
+                    EvidenceKind::ProguardNameMatch,
+                    "R8/ProGuard removed a declaration with the same name",
+                )),
+                _ => {}
             }
         }
 
-        // Then check coverage data
         if let Some(ref coverage) = self.coverage {
-            return self.enhance_single(dc, coverage);
+            match self.coverage_status(&dc.declaration, coverage) {
+                CoverageStatus::NeverExecuted => {
+                    dc.runtime_confirmed = true;
+                    dc.message = format!("{} (confirmed by runtime coverage)", dc.message);
+                    evidence.push(Evidence::new(
+                        EvidenceKind::NotExecuted,
+                        "Never executed according to runtime coverage",
+                    ));
+                }
+                CoverageStatus::Executed => {
+                    // Static analysis missed a use, e.g. through dynamic dispatch
+                    dc.message = format!(
+                        "{} (but was executed at runtime - may be dynamically called)",
+                        dc.message
+                    );
+                    evidence.push(Evidence::new(
+                        EvidenceKind::Executed,
+                        "Executed according to runtime coverage",
+                    ));
+                }
+                CoverageStatus::PartiallyExecuted => evidence.push(Evidence::new(
+                    EvidenceKind::PartiallyExecuted,
+                    "Partially executed according to runtime coverage",
+                )),
+                CoverageStatus::Unknown => {}
+            }
         }
 
-        // No enhancement data - use heuristics
-        dc.confidence = self.estimate_confidence(decl);
+        let confirmed_by_bytecode = match self
+            .bytecode
+            .as_ref()
+            .and_then(|bytecode| bytecode.get(&dc.declaration.id))
+        {
+            Some(false) => {
+                evidence.push(Evidence::new(
+                    EvidenceKind::BytecodeUnreachable,
+                    "Not reachable in the compiled classes either",
+                ));
+                true
+            }
+            Some(true) => {
+                // The front-ends disagree, which usually means a parser bug
+                dc.message = format!(
+                    "{} (but reachable in the compiled classes - possible parser bug)",
+                    dc.message
+                );
+                evidence.push(Evidence::new(
+                    EvidenceKind::BytecodeReachable,
+                    "Reachable in the compiled classes",
+                ));
+                false
+            }
+            None => false,
+        };
+
+        let mut dc = dc.with_evidence(evidence);
+        if confirmed_by_bytecode {
+            dc.confidence = dc.confidence.max(Confidence::High);
+        }
         dc
     }
 
-    fn enhance_single(&self, mut dc: DeadCode, coverage: &CoverageData) -> DeadCode {
-        let decl = &dc.declaration;
-
-        // Check if this declaration appears in coverage data
-        let coverage_status = match decl.kind {
+    fn coverage_status(&self, decl: &Declaration, coverage: &CoverageData) -> CoverageStatus {
+        match decl.kind {
             DeclarationKind::Class | DeclarationKind::Object | DeclarationKind::Interface => {
                 self.check_class_coverage(decl, coverage)
             }
@@ -102,35 +184,7 @@ impl HybridAnalyzer {
                 self.check_line_coverage(decl, coverage)
             }
             _ => CoverageStatus::Unknown,
-        };
-
-        match coverage_status {
-            CoverageStatus::NeverExecuted => {
-                // Runtime confirms this is dead code
-                dc.runtime_confirmed = true;
-                dc.confidence = Confidence::Confirmed;
-                dc.message = format!("{} (confirmed by runtime coverage)", dc.message);
-            }
-            CoverageStatus::Executed => {
-                // Runtime shows this WAS executed - false positive from static analysis
-                // This shouldn't normally happen, but could with dynamic dispatch
-                dc.confidence = Confidence::Low;
-                dc.message = format!(
-                    "{} (but was executed at runtime - may be dynamically called)",
-                    dc.message
-                );
-            }
-            CoverageStatus::PartiallyExecuted => {
-                // Some parts executed, some not
-                dc.confidence = Confidence::Medium;
-            }
-            CoverageStatus::Unknown => {
-                // Not in coverage data - use heuristics
-                dc.confidence = self.estimate_confidence(decl);
-            }
         }
-
-        dc
     }
 
     fn check_class_coverage(&self, decl: &Declaration, coverage: &CoverageData) -> CoverageStatus {
@@ -221,34 +275,6 @@ impl HybridAnalyzer {
             .unwrap_or_else(|| decl.name.clone())
     }
 
-    fn estimate_confidence(&self, decl: &Declaration) -> Confidence {
-        // Heuristics for confidence when no coverage data available
-        match decl.kind {
-            // Private members are less likely to be dynamically called
-            DeclarationKind::Function | DeclarationKind::Method
-                if decl.visibility == Visibility::Private =>
-            {
-                Confidence::High
-            }
-            // Internal members somewhat less likely
-            DeclarationKind::Function | DeclarationKind::Method
-                if decl.visibility == Visibility::Internal =>
-            {
-                Confidence::Medium
-            }
-            // Public methods could be called via reflection/dynamic dispatch
-            DeclarationKind::Function | DeclarationKind::Method => Confidence::Medium,
-            // Properties with getters could be accessed via reflection
-            DeclarationKind::Property => Confidence::Medium,
-            // Classes could be instantiated via reflection
-            DeclarationKind::Class | DeclarationKind::Object => Confidence::Medium,
-            // Parameters, imports, etc. - high confidence
-            DeclarationKind::Parameter | DeclarationKind::Import => Confidence::High,
-            // Default
-            _ => Confidence::Medium,
-        }
-    }
-
     /// Find code that is statically reachable but never executed at runtime
     ///
     /// This is the "dynamic unreachable" code - code that passes static analysis
@@ -256,7 +282,7 @@ impl HybridAnalyzer {
     pub fn find_runtime_dead_code(
         &self,
         graph: &Graph,
-        reachable: &HashSet<crate::graph::DeclarationId>,
+        reachable: &HashSet<DeclarationId>,
     ) -> Vec<DeadCode> {
         let Some(ref coverage) = self.coverage else {
             return Vec::new();
@@ -281,8 +307,12 @@ impl HybridAnalyzer {
             };
 
             if coverage_status == CoverageStatus::NeverExecuted {
+                let evidence = vec![Evidence::new(
+                    EvidenceKind::NotExecuted,
+                    "Reachable from an entry point, but never executed at runtime",
+                )];
                 let mut dc = DeadCode::new(decl.clone(), DeadCodeIssue::Unreferenced)
-                    .with_confidence(Confidence::High)
+                    .with_evidence(evidence)
                     .with_runtime_confirmed(true);
 
                 dc.message = format!(
@@ -316,7 +346,7 @@ enum CoverageStatus {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::graph::{DeclarationId, Language, Location};
+    use crate::graph::{Language, Location};
     use std::path::PathBuf;
 
     fn make_test_decl(name: &str, kind: DeclarationKind) -> Declaration {
@@ -356,4 +386,38 @@ mod tests {
         assert_eq!(enhanced[0].confidence, Confidence::Confirmed);
         assert!(enhanced[0].runtime_confirmed);
     }
+
+    #[test]
+    fn test_keep_rule_lowers_confidence() {
+        let decl = make_test_decl("MyClass", DeclarationKind::Class);
+        let kept = HashMap::from([(decl.id.clone(), "-keep class MyClass".to_string())]);
+        let analyzer = HybridAnalyzer::new().with_keep_rules(kept);
+
+        let enhanced =
+            analyzer.enhance_findings(vec![DeadCode::new(decl, DeadCodeIssue::Unreferenced)]);
+        assert_eq!(enhanced[0].confidence, Confidence::Low);
+        assert!(enhanced[0]
+            .evidence
+            .iter()
+            .any(|e| e.kind == EvidenceKind::KeepRule && e.description.contains("-keep class")));
+    }
+
+    #[test]
+    fn test_bytecode_cross_check() {
+        let dead = make_test_decl("render", DeclarationKind::Method);
+        let mut alive = make_test_decl("bind", DeclarationKind::Method);
+        alive.id = DeclarationId::new(PathBuf::from("test.kt"), 20, 30);
+        let analyzer = HybridAnalyzer::new().with_bytecode(HashMap::from([
+            (dead.id.clone(), false),
+            (alive.id.clone(), true),
+        ]));
+
+        let enhanced = analyzer.enhance_findings(vec![
+            DeadCode::new(dead, DeadCodeIssue::Unreferenced),
+            DeadCode::new(alive, DeadCodeIssue::Unreferenced),
+        ]);
+        assert_eq!(enhanced[0].confidence, Confidence::High);
+        assert_eq!(enhanced[1].confidence, Confidence::Low);
+        assert!(enhanced[1].message.contains("possible parser bug"));
+    }
 }
@@ -1,30 +1,55 @@
 // Analysis module - some types and variants reserved for future use
 #![allow(dead_code)]
 
+mod clusters;
 mod cycles;
+mod dead_containers;
+mod dead_files;
 mod deep;
 pub mod detectors;
 mod enhanced;
 mod entry_points;
+mod entry_sets;
+pub mod event_bus;
+pub mod hierarchy;
 mod hybrid;
+pub mod metrics;
+pub mod module_deps;
 mod reachability;
+pub mod reflection;
 pub mod resources;
-
-pub use cycles::CycleDetector;
+pub mod risk;
+pub mod scheduled_work;
+pub mod scoring;
+mod simulate;
+pub mod size;
+pub mod suppression;
+pub mod variants;
+mod visibility_hints;
+
+pub use clusters::{cluster_findings, DeletionUnit};
+pub use cycles::{CycleDetector, CycleInfo, CycleStats};
+pub use dead_containers::aggregate_dead_containers;
+pub use dead_files::aggregate_dead_files;
 pub use deep::DeepAnalyzer;
 pub use enhanced::EnhancedAnalyzer;
-pub use entry_points::EntryPointDetector;
+pub use entry_points::{EntryPointCategory, EntryPointDetector, MemberRetention};
+pub use entry_sets::{test_entry_points, EntrySet, EntrySetDiff};
 pub use hybrid::HybridAnalyzer;
 pub use reachability::ReachabilityAnalyzer;
 pub use resources::ResourceDetector;
+pub use simulate::DeletionSimulator;
+pub use visibility_hints::{classify_by_annotations, is_library_restricted};
 
 use crate::graph::Declaration;
+use serde::{Deserialize, Serialize};
 
 /// Confidence level for dead code detection
 ///
 /// Combines static analysis with optional runtime coverage data
 /// to provide confidence scores for dead code findings.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Confidence {
     /// Low confidence - static analysis only, may have dynamic dispatch
     Low,
@@ -55,6 +80,19 @@ impl Confidence {
             Confidence::Confirmed => 1.0,
         }
     }
+
+    /// Confidence level for an evidence score (see [`scoring::score`])
+    pub fn from_score(score: f64) -> Self {
+        if score >= 0.95 {
+            Confidence::Confirmed
+        } else if score >= 0.7 {
+            Confidence::High
+        } else if score >= 0.4 {
+            Confidence::Medium
+        } else {
+            Confidence::Low
+        }
+    }
 }
 
 impl std::fmt::Display for Confidence {
@@ -83,12 +121,33 @@ pub struct DeadCode {
 
     /// Whether runtime coverage data confirmed this is unused
     pub runtime_confirmed: bool,
+
+    /// Owner from CODEOWNERS or git blame, if ownership attribution ran
+    pub owner: Option<String>,
+
+    /// Inline comment or annotation suppressing the finding, if any
+    pub suppressed_by: Option<String>,
+
+    /// Project root the finding comes from, when several roots are analyzed
+    /// together
+    pub root: Option<String>,
+
+    /// False positive risk tags (see [`risk::RISK_TAGS`])
+    pub risk_tags: Vec<String>,
+
+    /// Evidence behind the confidence level, empty when it was set directly
+    pub evidence: Vec<scoring::Evidence>,
+
+    /// Keep rule retaining the declaration, for findings reported only
+    /// because it is kept (`--report-kept`)
+    pub kept_by: Option<String>,
 }
 
 impl DeadCode {
     pub fn new(declaration: Declaration, issue: DeadCodeIssue) -> Self {
         let severity = issue.default_severity();
         let message = issue.default_message(&declaration);
+        let risk_tags = risk::risk_tags(&declaration);
 
         Self {
             declaration,
@@ -97,6 +156,12 @@ impl DeadCode {
             confidence: Confidence::Medium, // Default for static-only analysis
             message,
             runtime_confirmed: false,
+            owner: None,
+            suppressed_by: None,
+            root: None,
+            risk_tags,
+            evidence: Vec::new(),
+            kept_by: None,
         }
     }
 
@@ -115,6 +180,23 @@ impl DeadCode {
         self
     }
 
+    /// Set the evidence and derive the confidence level from its score
+    pub fn with_evidence(mut self, evidence: Vec<scoring::Evidence>) -> Self {
+        self.confidence = Confidence::from_score(scoring::score(&evidence));
+        self.evidence = evidence;
+        self
+    }
+
+    /// Score from 0.0 to 1.0: the evidence score, or the confidence level's
+    /// score for findings without evidence
+    pub fn confidence_score(&self) -> f64 {
+        if self.evidence.is_empty() {
+            self.confidence.score()
+        } else {
+            scoring::score(&self.evidence)
+        }
+    }
+
     pub fn with_runtime_confirmed(mut self, confirmed: bool) -> Self {
         self.runtime_confirmed = confirmed;
         if confirmed {
@@ -122,6 +204,44 @@ impl DeadCode {
         }
         self
     }
+
+    pub fn with_owner(mut self, owner: Option<String>) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    pub fn with_root(mut self, root: Option<String>) -> Self {
+        self.root = root;
+        self
+    }
+
+    pub fn with_kept_by(mut self, rule: Option<String>) -> Self {
+        self.kept_by = rule;
+        self
+    }
+
+    pub fn has_risk_tag(&self, tag: &str) -> bool {
+        self.risk_tags.iter().any(|t| t == tag)
+    }
+
+    /// Report order: by file, line and rule code, then column and name to
+    /// break ties. Every reporter follows it, so the output does not depend
+    /// on the order analyses ran in or on hash map iteration
+    pub fn report_order(&self, other: &Self) -> std::cmp::Ordering {
+        let (a, b) = (&self.declaration, &other.declaration);
+        a.location
+            .file
+            .cmp(&b.location.file)
+            .then(a.location.line.cmp(&b.location.line))
+            .then(self.issue.code().cmp(other.issue.code()))
+            .then(a.location.column.cmp(&b.location.column))
+            .then(a.name.cmp(&b.name))
+    }
+}
+
+/// Sort findings into report order (see [`DeadCode::report_order`])
+pub fn sort_findings(findings: &mut [DeadCode]) {
+    findings.sort_by(DeadCode::report_order);
 }
 
 /// Types of dead code issues
@@ -159,9 +279,76 @@ pub enum DeadCodeIssue {
 
     /// Room DAO method writes data but the DAO has no read queries
     WriteOnlyDao,
+
+    /// Type alias is declared but never used
+    UnusedTypeAlias,
+
+    /// Production declaration referenced only from test source sets
+    OnlyUsedInTests,
+
+    /// Declaration matches a user-defined rule
+    CustomRule,
+
+    /// Every declaration in the file is unused
+    DeadFile,
+
+    /// `var` property that is never reassigned after initialization
+    VarCouldBeVal,
+
+    /// Custom getter of a property that is only ever written
+    UnusedGetter,
+
+    /// Custom setter of a property that is only ever read
+    UnusedSetter,
+
+    /// KDoc or Javadoc link to a dead declaration
+    DeadDocLink,
+
+    /// Remembered Compose state that is written but never read
+    UnusedComposeState,
+
+    /// Default value of a composable parameter that no call overrides
+    UnusedComposableDefault,
+
+    /// Production composable reachable only from previews or tests
+    OnlyUsedInPreviews,
+
+    /// Function-typed parameter the function never invokes or hands on
+    UninvokedFunctionParameter,
+
+    /// Object or class only used as a namespace, none of whose members is used
+    EffectivelyDeadObject,
 }
 
 impl DeadCodeIssue {
+    /// Every issue type, in the order of their codes
+    pub const ALL: [DeadCodeIssue; 24] = [
+        DeadCodeIssue::Unreferenced,
+        DeadCodeIssue::AssignOnly,
+        DeadCodeIssue::UnusedParameter,
+        DeadCodeIssue::UnusedImport,
+        DeadCodeIssue::UnusedEnumCase,
+        DeadCodeIssue::RedundantPublic,
+        DeadCodeIssue::DeadBranch,
+        DeadCodeIssue::UnusedSealedVariant,
+        DeadCodeIssue::RedundantOverride,
+        DeadCodeIssue::WriteOnlyPreference,
+        DeadCodeIssue::WriteOnlyDao,
+        DeadCodeIssue::UnusedTypeAlias,
+        DeadCodeIssue::OnlyUsedInTests,
+        DeadCodeIssue::CustomRule,
+        DeadCodeIssue::DeadFile,
+        DeadCodeIssue::VarCouldBeVal,
+        DeadCodeIssue::UnusedGetter,
+        DeadCodeIssue::UnusedSetter,
+        DeadCodeIssue::DeadDocLink,
+        DeadCodeIssue::UnusedComposeState,
+        DeadCodeIssue::UnusedComposableDefault,
+        DeadCodeIssue::OnlyUsedInPreviews,
+        DeadCodeIssue::UninvokedFunctionParameter,
+        DeadCodeIssue::EffectivelyDeadObject,
+    ];
+
     pub fn default_severity(&self) -> Severity {
         match self {
             DeadCodeIssue::Unreferenced => Severity::Warning,
@@ -175,6 +362,19 @@ impl DeadCodeIssue {
             DeadCodeIssue::RedundantOverride => Severity::Info,
             DeadCodeIssue::WriteOnlyPreference => Severity::Warning,
             DeadCodeIssue::WriteOnlyDao => Severity::Warning,
+            DeadCodeIssue::UnusedTypeAlias => Severity::Info,
+            DeadCodeIssue::OnlyUsedInTests => Severity::Warning,
+            DeadCodeIssue::CustomRule => Severity::Warning,
+            DeadCodeIssue::DeadFile => Severity::Warning,
+            DeadCodeIssue::VarCouldBeVal => Severity::Info,
+            DeadCodeIssue::UnusedGetter => Severity::Warning,
+            DeadCodeIssue::UnusedSetter => Severity::Warning,
+            DeadCodeIssue::DeadDocLink => Severity::Info,
+            DeadCodeIssue::UnusedComposeState => Severity::Warning,
+            DeadCodeIssue::UnusedComposableDefault => Severity::Info,
+            DeadCodeIssue::OnlyUsedInPreviews => Severity::Warning,
+            DeadCodeIssue::UninvokedFunctionParameter => Severity::Warning,
+            DeadCodeIssue::EffectivelyDeadObject => Severity::Warning,
         }
     }
 
@@ -228,6 +428,76 @@ impl DeadCodeIssue {
                     decl.name
                 )
             }
+            DeadCodeIssue::UnusedTypeAlias => {
+                format!("Type alias '{}' is never used", decl.name)
+            }
+            DeadCodeIssue::OnlyUsedInTests => {
+                format!(
+                    "{} '{}' is only used from tests",
+                    decl.kind.display_name(),
+                    decl.name
+                )
+            }
+            DeadCodeIssue::CustomRule => {
+                format!(
+                    "{} '{}' matches a custom rule",
+                    decl.kind.display_name(),
+                    decl.name
+                )
+            }
+            DeadCodeIssue::DeadFile => {
+                format!("File '{}' is entirely unused", decl.name)
+            }
+            DeadCodeIssue::VarCouldBeVal => {
+                format!(
+                    "Property '{}' is never reassigned and could be a val",
+                    decl.name
+                )
+            }
+            DeadCodeIssue::UnusedGetter => {
+                format!(
+                    "Custom getter of property '{}' is never used (the property is only written)",
+                    decl.name
+                )
+            }
+            DeadCodeIssue::UnusedSetter => {
+                format!(
+                    "Custom setter of property '{}' is never used (the property is only read)",
+                    decl.name
+                )
+            }
+            DeadCodeIssue::DeadDocLink => {
+                format!(
+                    "Documentation links to {} '{}', which is dead",
+                    decl.kind.display_name(),
+                    decl.name
+                )
+            }
+            DeadCodeIssue::UnusedComposeState => {
+                format!("Compose state '{}' is never read", decl.name)
+            }
+            DeadCodeIssue::UnusedComposableDefault => {
+                format!(
+                    "Default value of parameter '{}' is never overridden",
+                    decl.name
+                )
+            }
+            DeadCodeIssue::OnlyUsedInPreviews => {
+                format!(
+                    "Composable '{}' is only used from previews or tests",
+                    decl.name
+                )
+            }
+            DeadCodeIssue::UninvokedFunctionParameter => {
+                format!("Function parameter '{}' is never invoked", decl.name)
+            }
+            DeadCodeIssue::EffectivelyDeadObject => {
+                format!(
+                    "{} '{}' is only used as a namespace, and none of its members is used",
+                    decl.kind.display_name(),
+                    decl.name
+                )
+            }
         }
     }
 
@@ -244,12 +514,26 @@ impl DeadCodeIssue {
             DeadCodeIssue::RedundantOverride => "DC009",
             DeadCodeIssue::WriteOnlyPreference => "DC010",
             DeadCodeIssue::WriteOnlyDao => "DC011",
+            DeadCodeIssue::UnusedTypeAlias => "DC012",
+            DeadCodeIssue::OnlyUsedInTests => "DC013",
+            DeadCodeIssue::CustomRule => "DC014",
+            DeadCodeIssue::DeadFile => "DC015",
+            DeadCodeIssue::VarCouldBeVal => "DC016",
+            DeadCodeIssue::UnusedGetter => "DC017",
+            DeadCodeIssue::UnusedSetter => "DC018",
+            DeadCodeIssue::DeadDocLink => "DC019",
+            DeadCodeIssue::UnusedComposeState => "DC020",
+            DeadCodeIssue::UnusedComposableDefault => "DC021",
+            DeadCodeIssue::OnlyUsedInPreviews => "DC022",
+            DeadCodeIssue::UninvokedFunctionParameter => "DC023",
+            DeadCodeIssue::EffectivelyDeadObject => "DC024",
         }
     }
 }
 
 /// Severity levels for dead code issues
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     Info,
     Warning,
@@ -0,0 +1,389 @@
+//! Gradle module dependencies
+//!
+//! Builds the module-level dependency graph of a multi-module project from
+//! the `project(...)` dependencies its build files declare, and checks each
+//! of them against the packages the depending module actually imports:
+//!
+//! ```kotlin
+//! // app/build.gradle.kts
+//! dependencies {
+//!     implementation(project(":core:network"))  // app imports com.example.network
+//!     implementation(project(":core:legacy"))   // UNUSED: nothing imports it
+//! }
+//! ```
+//!
+//! A module uses another when one of its main sources imports from a package
+//! the other declares (or its `namespace`, for `R`), declares the same
+//! package, or names one of its packages fully qualified (layouts, the
+//! manifest). Modules exposed through an `api` dependency of the target count
+//! as the target. Targets without any Kotlin or Java package are never
+//! reported, since their use cannot be told from imports. Only `implementation`
+//! and `api` dependencies on project modules are checked; external artifacts
+//! do not tell which packages they contain.
+
+use crate::discovery::declared_package;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// File names of Gradle build scripts
+const BUILD_FILES: &[&str] = &["build.gradle", "build.gradle.kts"];
+
+/// A Gradle module of the project
+#[derive(Debug, Clone, Serialize)]
+pub struct GradleModule {
+    /// Gradle path, e.g. `:core:network`
+    pub path: String,
+    /// Module directory
+    pub dir: PathBuf,
+    /// Packages declared by the module's sources, and its namespace
+    pub packages: BTreeSet<String>,
+}
+
+/// A `project(...)` dependency declared in a build file
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleDependency {
+    /// Gradle path of the declaring module
+    pub from: String,
+    /// Gradle path of the dependency
+    pub to: String,
+    /// `implementation` or `api`
+    pub configuration: String,
+    pub file: PathBuf,
+    pub line: usize,
+    /// Whether the declaring module imports from the dependency
+    pub used: bool,
+}
+
+/// Modules of a project and the dependencies between them
+#[derive(Debug, Default, Serialize)]
+pub struct ModuleDependencyGraph {
+    pub modules: Vec<GradleModule>,
+    pub dependencies: Vec<ModuleDependency>,
+}
+
+impl ModuleDependencyGraph {
+    /// Discover the modules under `root` and check their dependencies
+    pub fn analyze(root: &Path) -> Self {
+        use ignore::WalkBuilder;
+
+        let mut build_files = Vec::new();
+        let mut sources = Vec::new();
+
+        let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
+        for entry in walker.flatten() {
+            let path = entry.path();
+
+            // Skip test sources and build outputs
+            let path_str = path.to_string_lossy();
+            if path_str.contains("/test/")
+                || path_str.contains("/androidTest/")
+                || path_str.contains("/build/")
+            {
+                continue;
+            }
+
+            let is_build_file = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| BUILD_FILES.contains(&name));
+            let ext = path.extension().and_then(|e| e.to_str());
+            let is_source = matches!(ext, Some("kt") | Some("java") | Some("xml"));
+            if !is_build_file && !is_source {
+                continue;
+            }
+
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            if is_build_file {
+                build_files.push((path.to_path_buf(), content));
+            } else {
+                sources.push((path.to_path_buf(), content));
+            }
+        }
+
+        Self::from_files(root, &build_files, &sources)
+    }
+
+    /// Build the graph from build files and the sources of all modules
+    pub fn from_files(
+        root: &Path,
+        build_files: &[(PathBuf, String)],
+        sources: &[(PathBuf, String)],
+    ) -> Self {
+        let namespace_pattern = Regex::new(r#"\bnamespace\s*=?\s*["']([\w.]+)["']"#).unwrap();
+        let project_pattern = Regex::new(
+            r#"\b(implementation|api)\s*\(?\s*project\s*\(\s*(?:path\s*[:=]\s*)?["'](:[\w:.-]*)["']"#,
+        )
+        .unwrap();
+        let accessor_pattern =
+            Regex::new(r#"\b(implementation|api)\s*\(\s*projects\.([\w.]+)\s*\)"#).unwrap();
+        let import_pattern = Regex::new(
+            r#"(?m)^\s*import\s+(?:static\s+)?([\w.]+?)(?:\.\*)?(?:\s+as\s+\w+)?\s*;?\s*$"#,
+        )
+        .unwrap();
+
+        // Modules, deepest directories first so files belong to the innermost
+        let mut modules: Vec<GradleModule> = build_files
+            .iter()
+            .filter_map(|(file, content)| {
+                let dir = file.parent()?.to_path_buf();
+                let relative = dir.strip_prefix(root).unwrap_or(&dir);
+                let path = format!(
+                    ":{}",
+                    relative
+                        .components()
+                        .map(|c| c.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join(":")
+                );
+                let packages = namespace_pattern
+                    .captures_iter(content)
+                    .map(|caps| caps[1].to_string())
+                    .collect();
+                Some(GradleModule {
+                    path,
+                    dir,
+                    packages,
+                })
+            })
+            .collect();
+        modules.sort_by(|a, b| {
+            b.dir
+                .components()
+                .count()
+                .cmp(&a.dir.components().count())
+                .then(a.path.cmp(&b.path))
+        });
+        for (file, content) in sources {
+            let index = module_of(&modules, file);
+            if let (Some(index), Some(package)) = (index, declared_package(content)) {
+                modules[index].packages.insert(package.to_string());
+            }
+        }
+
+        // Modules declaring each package
+        let mut by_package: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (index, module) in modules.iter().enumerate() {
+            for package in &module.packages {
+                by_package.entry(package).or_default().push(index);
+            }
+        }
+
+        // Modules each module imports from
+        let mut imports: Vec<HashSet<usize>> = vec![HashSet::new(); modules.len()];
+        for (file, content) in sources {
+            let Some(index) = module_of(&modules, file) else {
+                continue;
+            };
+            let used = &mut imports[index];
+            if let Some(package) = declared_package(content) {
+                used.extend(by_package.get(package).into_iter().flatten());
+            }
+            for caps in import_pattern.captures_iter(content) {
+                let mut name = &caps[1];
+                loop {
+                    used.extend(by_package.get(name).into_iter().flatten());
+                    match name.rfind('.') {
+                        Some(dot) => name = &name[..dot],
+                        None => break,
+                    }
+                }
+            }
+            for (package, owners) in &by_package {
+                if content.contains(&format!("{}.", package)) {
+                    used.extend(owners);
+                }
+            }
+        }
+
+        // Declared dependencies
+        let index_of: HashMap<String, usize> = modules
+            .iter()
+            .enumerate()
+            .map(|(index, module)| (accessor_key(&module.path), index))
+            .collect();
+        let mut declared: Vec<(usize, usize, ModuleDependency)> = Vec::new();
+        for (file, content) in build_files {
+            let Some(from) = file
+                .parent()
+                .and_then(|dir| modules.iter().position(|m| m.dir == dir))
+            else {
+                continue;
+            };
+            for (line_num, line) in content.lines().enumerate() {
+                if line.trim_start().starts_with("//") {
+                    continue;
+                }
+                let targets = project_pattern
+                    .captures_iter(line)
+                    .chain(accessor_pattern.captures_iter(line));
+                for caps in targets {
+                    let Some(&to) = index_of.get(&accessor_key(&caps[2])) else {
+                        continue;
+                    };
+                    declared.push((
+                        from,
+                        to,
+                        ModuleDependency {
+                            from: modules[from].path.clone(),
+                            to: modules[to].path.clone(),
+                            configuration: caps[1].to_string(),
+                            file: file.clone(),
+                            line: line_num + 1,
+                            used: true,
+                        },
+                    ));
+                }
+            }
+        }
+
+        // A dependency also provides what it exposes through `api`
+        let mut exposes: Vec<Vec<usize>> = vec![Vec::new(); modules.len()];
+        for (from, to, dep) in &declared {
+            if dep.configuration == "api" {
+                exposes[*from].push(*to);
+            }
+        }
+        let dependencies = declared
+            .into_iter()
+            .map(|(from, to, mut dep)| {
+                let mut provided = vec![to];
+                let mut seen: HashSet<usize> = HashSet::new();
+                while let Some(module) = provided.pop() {
+                    if seen.insert(module) {
+                        provided.extend(&exposes[module]);
+                    }
+                }
+                let opaque = seen.iter().all(|&m| modules[m].packages.is_empty());
+                dep.used = opaque || seen.iter().any(|m| imports[from].contains(m));
+                dep
+            })
+            .collect();
+
+        modules.sort_by(|a, b| a.path.cmp(&b.path));
+        Self {
+            modules,
+            dependencies,
+        }
+    }
+
+    /// Declared dependencies the declaring module never imports from, in
+    /// build file and line order
+    pub fn unused(&self) -> Vec<&ModuleDependency> {
+        let mut unused: Vec<&ModuleDependency> =
+            self.dependencies.iter().filter(|dep| !dep.used).collect();
+        unused.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        unused
+    }
+}
+
+/// Index of the innermost module containing a file; modules are sorted
+/// deepest directory first
+fn module_of(modules: &[GradleModule], file: &Path) -> Option<usize> {
+    modules.iter().position(|m| file.starts_with(&m.dir))
+}
+
+/// Key matching a Gradle path and its type-safe accessor:
+/// `:core:network-api` and `core.networkApi` both become `core.networkapi`
+fn accessor_key(path: &str) -> String {
+    path.trim_start_matches(':')
+        .replace(':', ".")
+        .replace(['-', '_'], "")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(files: &[(&str, &str)]) -> Vec<(PathBuf, String)> {
+        files
+            .iter()
+            .map(|(path, content)| (PathBuf::from(path), content.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_module_dependencies() {
+        let build_files = files(&[
+            (
+                "/p/app/build.gradle.kts",
+                r#"
+android { namespace = "com.example.app" }
+dependencies {
+    implementation(project(":core:network"))
+    implementation(project(":core:legacy"))
+    implementation(projects.core.designSystem)
+    implementation(project(":feature:home"))
+    // implementation(project(":core:unused"))
+}
+"#,
+            ),
+            (
+                "/p/core/network/build.gradle.kts",
+                "dependencies {\n    api(project(\":core:model\"))\n}\n",
+            ),
+            ("/p/core/model/build.gradle.kts", ""),
+            (
+                "/p/core/legacy/build.gradle",
+                "android { namespace 'com.example.legacy' }\n",
+            ),
+            ("/p/core/design-system/build.gradle.kts", ""),
+            ("/p/feature/home/build.gradle.kts", ""),
+        ]);
+        let sources = files(&[
+            (
+                "/p/app/src/main/java/com/example/app/App.kt",
+                "package com.example.app\n\nimport com.example.model.User\n",
+            ),
+            (
+                "/p/app/src/main/res/layout/main.xml",
+                "<com.example.design.Card android:id=\"@+id/card\" />",
+            ),
+            (
+                "/p/core/network/src/main/java/com/example/network/Api.kt",
+                "package com.example.network\n\nimport com.example.model.User\n",
+            ),
+            (
+                "/p/core/model/src/main/java/com/example/model/User.kt",
+                "package com.example.model\n",
+            ),
+            (
+                "/p/core/legacy/src/main/java/com/example/legacy/Old.java",
+                "package com.example.legacy;\n",
+            ),
+            (
+                "/p/core/design-system/src/main/java/com/example/design/Card.kt",
+                "package com.example.design\n",
+            ),
+        ]);
+
+        let graph = ModuleDependencyGraph::from_files(Path::new("/p"), &build_files, &sources);
+
+        let paths: Vec<&str> = graph.modules.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                ":app",
+                ":core:design-system",
+                ":core:legacy",
+                ":core:model",
+                ":core:network",
+                ":feature:home"
+            ]
+        );
+        assert_eq!(graph.dependencies.len(), 5);
+
+        // :core:network is used through the :core:model it exposes, and
+        // :feature:home has no sources to tell
+        let unused: Vec<(&str, usize)> = graph
+            .unused()
+            .iter()
+            .map(|dep| (dep.to.as_str(), dep.line))
+            .collect();
+        assert_eq!(unused, vec![(":core:legacy", 5)]);
+    }
+}
@@ -1,15 +1,35 @@
+use super::hierarchy::ClassHierarchy;
+use super::MemberRetention;
 use super::{DeadCode, DeadCodeIssue};
-use crate::graph::{DeclarationId, DeclarationKind, Graph};
-use petgraph::visit::Dfs;
+use crate::config::RetainMembers;
+use crate::discovery::SourceSet;
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph};
 use std::collections::HashSet;
 use tracing::debug;
 
 /// Analyzer for finding unreachable/dead code via graph traversal
-pub struct ReachabilityAnalyzer;
+pub struct ReachabilityAnalyzer {
+    /// Which members of reachable classes are kept without a reference
+    retention: MemberRetention,
+}
 
 impl ReachabilityAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self {
+            retention: MemberRetention::new(RetainMembers::All),
+        }
+    }
+
+    pub fn with_retain_members(mut self, retain_members: RetainMembers) -> Self {
+        self.retention = self.retention.with_default(retain_members);
+        self
+    }
+
+    /// Retention policy with per-class overrides, from
+    /// [`EntryPointDetector::member_retention`](super::EntryPointDetector::member_retention)
+    pub fn with_member_retention(mut self, retention: MemberRetention) -> Self {
+        self.retention = retention;
+        self
     }
 
     /// Find all unreachable declarations starting from entry points
@@ -30,7 +50,8 @@ impl ReachabilityAnalyzer {
         entry_points: &HashSet<DeclarationId>,
     ) -> (Vec<DeadCode>, HashSet<DeclarationId>) {
         // First, find all reachable nodes via DFS from entry points
-        let reachable = self.find_reachable(graph, entry_points);
+        let hierarchy = ClassHierarchy::build(graph, entry_points);
+        let reachable = self.find_reachable(graph, &hierarchy, entry_points);
 
         // Collect unreachable declarations
         let mut dead_code = Vec::new();
@@ -42,14 +63,25 @@ impl ReachabilityAnalyzer {
             }
 
             // Skip certain kinds that shouldn't be reported
-            if self.should_skip_declaration(decl, graph) {
+            if self.should_skip_declaration(decl, graph, &hierarchy, &reachable) {
                 continue;
             }
 
             debug!("Unreachable: {} ({})", decl.name, decl.kind.display_name());
 
             let issue = self.determine_issue_type(decl);
-            dead_code.push(DeadCode::new(decl.clone(), issue));
+            let mut finding = DeadCode::new(decl.clone(), issue);
+            if hierarchy.is_override(&decl.id) {
+                if let Some(owner) = decl.parent.as_ref().and_then(|p| graph.get_declaration(p)) {
+                    finding = finding.with_message(format!(
+                        "{} '{}' overrides a super type member, but '{}' is never instantiated",
+                        decl.kind.display_name(),
+                        decl.name,
+                        owner.name
+                    ));
+                }
+            }
+            dead_code.push(finding);
         }
 
         // Sort by file and location for consistent output
@@ -71,38 +103,110 @@ impl ReachabilityAnalyzer {
         (dead_code, reachable)
     }
 
+    /// Find production declarations that are reachable only through tests
+    ///
+    /// Reachability is recomputed from the entry points outside of test source
+    /// sets; main-source declarations in `reachable` that this misses are only
+    /// alive because test code references them. Members of such a class are
+    /// folded into the class finding.
+    pub fn find_test_only(
+        &self,
+        graph: &Graph,
+        entry_points: &HashSet<DeclarationId>,
+        reachable: &HashSet<DeclarationId>,
+    ) -> Vec<DeadCode> {
+        let production_entry_points: HashSet<DeclarationId> = entry_points
+            .iter()
+            .filter(|id| !is_test_declaration(id))
+            .cloned()
+            .collect();
+        let hierarchy = ClassHierarchy::build(graph, &production_entry_points);
+        let production_reachable = self.find_reachable(graph, &hierarchy, &production_entry_points);
+
+        let test_only: HashSet<&DeclarationId> = graph
+            .declarations()
+            .filter(|decl| reachable.contains(&decl.id))
+            .filter(|decl| !production_reachable.contains(&decl.id))
+            .filter(|decl| !is_test_declaration(&decl.id))
+            .filter(|decl| {
+                !self.should_skip_declaration(decl, graph, &hierarchy, &production_reachable)
+            })
+            .map(|decl| &decl.id)
+            .collect();
+
+        let mut dead_code: Vec<DeadCode> = test_only
+            .iter()
+            .filter_map(|id| graph.get_declaration(id))
+            .filter(|decl| !decl.parent.as_ref().is_some_and(|p| test_only.contains(p)))
+            .map(|decl| DeadCode::new(decl.clone(), DeadCodeIssue::OnlyUsedInTests))
+            .collect();
+
+        dead_code.sort_by(|a, b| {
+            let a = &a.declaration.location;
+            let b = &b.declaration.location;
+            a.file.cmp(&b.file).then(a.line.cmp(&b.line))
+        });
+        dead_code
+    }
+
+    /// Find production composables that are reachable only through previews
+    /// or tests
+    ///
+    /// Previews are rendered by the IDE and tests never ship, so neither
+    /// keeps a composable alive in the app. Needs entry points detected with
+    /// `compose_previews`, as otherwise every composable is an entry point.
+    pub fn find_preview_only(
+        &self,
+        graph: &Graph,
+        entry_points: &HashSet<DeclarationId>,
+        previews: &HashSet<DeclarationId>,
+        reachable: &HashSet<DeclarationId>,
+    ) -> Vec<DeadCode> {
+        let production_entry_points: HashSet<DeclarationId> = entry_points
+            .iter()
+            .filter(|id| !previews.contains(*id) && !is_test_declaration(id))
+            .cloned()
+            .collect();
+        let hierarchy = ClassHierarchy::build(graph, &production_entry_points);
+        let production_reachable = self.find_reachable(graph, &hierarchy, &production_entry_points);
+
+        let mut dead_code: Vec<DeadCode> = graph
+            .declarations()
+            .filter(|decl| reachable.contains(&decl.id))
+            .filter(|decl| !production_reachable.contains(&decl.id))
+            .filter(|decl| !previews.contains(&decl.id) && !is_test_declaration(&decl.id))
+            .filter(|decl| decl.annotations.iter().any(|a| a.contains("Composable")))
+            .map(|decl| DeadCode::new(decl.clone(), DeadCodeIssue::OnlyUsedInPreviews))
+            .collect();
+
+        dead_code.sort_by(|a, b| {
+            let a = &a.declaration.location;
+            let b = &b.declaration.location;
+            a.file.cmp(&b.file).then(a.line.cmp(&b.line))
+        });
+        dead_code
+    }
+
     /// Find all reachable nodes from entry points using DFS
     fn find_reachable(
         &self,
         graph: &Graph,
+        hierarchy: &ClassHierarchy,
         entry_points: &HashSet<DeclarationId>,
     ) -> HashSet<DeclarationId> {
         let mut reachable = HashSet::new();
-        let inner_graph = graph.inner();
+        // Overrides reached through a reference before an instance of their
+        // class was, left to the dispatch step
+        let mut deferred = HashSet::new();
 
         // Step 1: Initial DFS from entry points
-        for entry_id in entry_points {
-            if let Some(start_idx) = graph.node_index(entry_id) {
-                // Add entry point itself
-                reachable.insert(entry_id.clone());
-
-                // Perform DFS from this entry point
-                let mut dfs = Dfs::new(inner_graph, start_idx);
-
-                while let Some(node_idx) = dfs.next(inner_graph) {
-                    if let Some(node_id) = inner_graph.node_weight(node_idx) {
-                        reachable.insert(node_id.clone());
-
-                        // Also mark parent declarations as reachable
-                        if let Some(decl) = graph.get_declaration(node_id) {
-                            if let Some(parent_id) = &decl.parent {
-                                reachable.insert(parent_id.clone());
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let entries: Vec<DeclarationId> = entry_points
+            .iter()
+            .filter(|id| graph.node_index(id).is_some())
+            .cloned()
+            .collect();
+        reachable.extend(entries.iter().cloned());
+        Self::visit(graph, hierarchy, entries, &mut reachable, &mut deferred);
 
         // Step 2: Mark all ancestors of reachable nodes as reachable
         let mut ancestors = HashSet::new();
@@ -113,30 +217,101 @@ impl ReachabilityAnalyzer {
 
         // Step 3: Mark all children of reachable classes as reachable (optimized)
         // Use a worklist instead of iterating all declarations
-        self.mark_children_reachable(graph, &mut reachable);
+        self.mark_children_reachable(graph, hierarchy, &mut reachable);
 
         // Step 4: DFS from newly reachable nodes
-        let mut additional_reachable = HashSet::new();
-        for id in &reachable {
-            if let Some(start_idx) = graph.node_index(id) {
-                let mut dfs = Dfs::new(inner_graph, start_idx);
-                while let Some(node_idx) = dfs.next(inner_graph) {
-                    if let Some(node_id) = inner_graph.node_weight(node_idx) {
-                        additional_reachable.insert(node_id.clone());
-                    }
-                }
-            }
-        }
-        reachable.extend(additional_reachable);
+        let from: Vec<DeclarationId> = reachable.iter().cloned().collect();
+        Self::visit(graph, hierarchy, from, &mut reachable, &mut deferred);
 
         // Step 5: Mark children again (for newly discovered reachable classes)
-        self.mark_children_reachable(graph, &mut reachable);
+        self.mark_children_reachable(graph, hierarchy, &mut reachable);
+
+        // Step 6: Dispatch through interfaces and base classes until nothing
+        // new is reached. An override runs only on instances of its class, so
+        // it is kept once its class is instantiated and it is referenced, the
+        // member it overrides is reachable, it overrides a library member the
+        // framework calls, or its class retains it.
+        loop {
+            let dispatched: Vec<DeclarationId> = graph
+                .declarations()
+                .filter(|decl| !reachable.contains(&decl.id) && hierarchy.is_override(&decl.id))
+                .filter(|decl| {
+                    let Some(owner) = &decl.parent else {
+                        return false;
+                    };
+                    let called = deferred.contains(&decl.id)
+                        || hierarchy
+                            .overridden(&decl.id)
+                            .iter()
+                            .any(|member| reachable.contains(member));
+                    let retained = reachable.contains(owner) && self.retention.retains(decl, graph);
+                    (called || retained || hierarchy.overrides_library_member(&decl.id))
+                        && hierarchy.is_instantiated(owner, &reachable)
+                })
+                .map(|decl| decl.id.clone())
+                .collect();
+            if dispatched.is_empty() {
+                break;
+            }
+            reachable.extend(dispatched.iter().cloned());
+            Self::visit(graph, hierarchy, dispatched, &mut reachable, &mut deferred);
+            self.mark_children_reachable(graph, hierarchy, &mut reachable);
+        }
 
         reachable
     }
 
+    /// Add everything reachable from `from` through references. Overrides
+    /// whose class has no instance yet are deferred instead.
+    fn visit(
+        graph: &Graph,
+        hierarchy: &ClassHierarchy,
+        from: Vec<DeclarationId>,
+        reachable: &mut HashSet<DeclarationId>,
+        deferred: &mut HashSet<DeclarationId>,
+    ) {
+        let inner_graph = graph.inner();
+        let mut stack = from;
+        while let Some(id) = stack.pop() {
+            let Some(node_idx) = graph.node_index(&id) else {
+                continue;
+            };
+            for target_idx in inner_graph.neighbors(node_idx) {
+                let Some(target) = inner_graph.node_weight(target_idx) else {
+                    continue;
+                };
+                if reachable.contains(target) {
+                    continue;
+                }
+                let Some(decl) = graph.get_declaration(target) else {
+                    continue;
+                };
+                if hierarchy.is_override(target)
+                    && !decl
+                        .parent
+                        .as_ref()
+                        .is_none_or(|owner| hierarchy.is_instantiated(owner, reachable))
+                {
+                    deferred.insert(target.clone());
+                    continue;
+                }
+
+                reachable.insert(target.clone());
+                stack.push(target.clone());
+            }
+        }
+    }
+
     /// Mark all children of reachable declarations as reachable (optimized with children_index)
-    fn mark_children_reachable(&self, graph: &Graph, reachable: &mut HashSet<DeclarationId>) {
+    ///
+    /// Overrides are left to the dispatch step, which keeps them only in
+    /// instantiated classes.
+    fn mark_children_reachable(
+        &self,
+        graph: &Graph,
+        hierarchy: &ClassHierarchy,
+        reachable: &mut HashSet<DeclarationId>,
+    ) {
         // Use a worklist approach instead of iterating all declarations
         let mut worklist: Vec<DeclarationId> = reachable.iter().cloned().collect();
         let mut processed: HashSet<DeclarationId> = HashSet::new();
@@ -149,7 +324,12 @@ impl ReachabilityAnalyzer {
 
             // Get children of this declaration using the index
             for child_id in graph.get_children(&id) {
-                if !reachable.contains(child_id) {
+                let retained = !hierarchy.is_override(child_id)
+                    && graph
+                        .get_declaration(child_id)
+                        .map(|child| self.retention.retains(child, graph))
+                        .unwrap_or(true);
+                if retained && !reachable.contains(child_id) {
                     reachable.insert(child_id.clone());
                     worklist.push(child_id.clone());
                 }
@@ -173,7 +353,13 @@ impl ReachabilityAnalyzer {
     }
 
     /// Check if a declaration should be skipped from dead code reporting
-    fn should_skip_declaration(&self, decl: &crate::graph::Declaration, graph: &Graph) -> bool {
+    fn should_skip_declaration(
+        &self,
+        decl: &Declaration,
+        graph: &Graph,
+        hierarchy: &ClassHierarchy,
+        reachable: &HashSet<DeclarationId>,
+    ) -> bool {
         // Skip file-level declarations
         if decl.kind == DeclarationKind::File || decl.kind == DeclarationKind::Package {
             return true;
@@ -184,8 +370,13 @@ impl ReachabilityAnalyzer {
         if let Some(parent_id) = &decl.parent {
             if let Some(parent) = graph.get_declaration(parent_id) {
                 // If parent is a class/object and also unreferenced,
-                // skip the member (parent will be reported instead)
-                if parent.kind.is_type() && !graph.is_referenced(parent_id) {
+                // skip the member (parent will be reported instead).
+                // Kept classes (e.g. entry points) are reachable without a
+                // reference, so their members are reported individually.
+                if parent.kind.is_type()
+                    && !graph.is_referenced(parent_id)
+                    && !reachable.contains(parent_id)
+                {
                     return true;
                 }
             }
@@ -201,19 +392,23 @@ impl ReachabilityAnalyzer {
         }
 
         // Skip overridden methods (they might be called via interface/base class)
-        // Check both Java-style @Override annotation and Kotlin override modifier
-        if decl.annotations.iter().any(|a| a.contains("Override")) {
-            return true;
-        }
-        if decl.modifiers.iter().any(|m| m == "override") {
-            return true;
+        // Check both Java-style @Override annotation and Kotlin override modifier,
+        // unless no instance of their class is ever created
+        let is_override = hierarchy.is_override(&decl.id)
+            || decl.annotations.iter().any(|a| a.contains("Override"))
+            || decl.modifiers.iter().any(|m| m == "override");
+        if is_override {
+            return decl
+                .parent
+                .as_ref()
+                .is_none_or(|owner| hierarchy.is_instantiated(owner, reachable));
         }
 
         false
     }
 
     /// Determine the specific issue type for a dead code declaration
-    fn determine_issue_type(&self, decl: &crate::graph::Declaration) -> DeadCodeIssue {
+    fn determine_issue_type(&self, decl: &Declaration) -> DeadCodeIssue {
         match decl.kind {
             DeclarationKind::Import => DeadCodeIssue::UnusedImport,
             DeclarationKind::Parameter => DeadCodeIssue::UnusedParameter,
@@ -223,6 +418,11 @@ impl ReachabilityAnalyzer {
     }
 }
 
+/// Whether a declaration lives in a test source set
+fn is_test_declaration(id: &DeclarationId) -> bool {
+    SourceSet::from_path(&id.file).is_test()
+}
+
 impl Default for ReachabilityAnalyzer {
     fn default() -> Self {
         Self::new()
@@ -242,4 +442,221 @@ mod tests {
         let dead_code = analyzer.find_unreachable(&graph, &entry_points);
         assert!(dead_code.is_empty());
     }
+
+    #[test]
+    fn test_retain_members_referenced_only() {
+        use crate::graph::{Declaration, Language, Location, Reference, ReferenceKind};
+        use std::path::PathBuf;
+
+        let make = |name: &str, kind: DeclarationKind, start: usize| {
+            let file = PathBuf::from("MainActivity.kt");
+            Declaration::new(
+                DeclarationId::new(file.clone(), start, start + 10),
+                name.to_string(),
+                kind,
+                Location::new(file, start / 10 + 1, 1, start, start + 10),
+                Language::Kotlin,
+            )
+        };
+
+        let mut graph = Graph::new();
+        let activity = graph.add_declaration(make("MainActivity", DeclarationKind::Class, 0));
+        let mut on_create = make("onCreate", DeclarationKind::Method, 10);
+        on_create.parent = Some(activity.clone());
+        on_create.modifiers.push("override".to_string());
+        let on_create = graph.add_declaration(on_create);
+        let mut setup = make("setupViews", DeclarationKind::Method, 20);
+        setup.parent = Some(activity.clone());
+        let setup_id = graph.add_declaration(setup.clone());
+        let mut legacy = make("legacyHelper", DeclarationKind::Method, 30);
+        legacy.parent = Some(activity.clone());
+        graph.add_declaration(legacy);
+        graph.add_reference(
+            &on_create,
+            &setup_id,
+            Reference::new(
+                ReferenceKind::Call,
+                setup.location,
+                "setupViews".to_string(),
+            ),
+        );
+
+        let entry_points: HashSet<_> = [activity].into_iter().collect();
+
+        let keep_all = ReachabilityAnalyzer::new().find_unreachable(&graph, &entry_points);
+        assert!(keep_all.is_empty());
+
+        let dead: Vec<_> = ReachabilityAnalyzer::new()
+            .with_retain_members(RetainMembers::ReferencedOnly)
+            .find_unreachable(&graph, &entry_points)
+            .into_iter()
+            .map(|dc| dc.declaration.name)
+            .collect();
+        assert_eq!(dead, vec!["legacyHelper"]);
+    }
+
+    #[test]
+    fn test_find_test_only() {
+        use crate::graph::{Language, Location, Reference, ReferenceKind};
+        use std::path::PathBuf;
+
+        let make = |file: &str, name: &str, kind: DeclarationKind| {
+            let file = PathBuf::from(file);
+            Declaration::new(
+                DeclarationId::new(file.clone(), 0, 10),
+                name.to_string(),
+                kind,
+                Location::new(file, 1, 1, 0, 10),
+                Language::Kotlin,
+            )
+        };
+        let mut graph = Graph::new();
+        let app = graph.add_declaration(make(
+            "app/src/main/java/App.kt",
+            "App",
+            DeclarationKind::Class,
+        ));
+        let formatter = make(
+            "app/src/main/java/Formatter.kt",
+            "Formatter",
+            DeclarationKind::Class,
+        );
+        let formatter_id = graph.add_declaration(formatter.clone());
+        let test = graph.add_declaration(make(
+            "app/src/test/java/FormatterTest.kt",
+            "FormatterTest",
+            DeclarationKind::Class,
+        ));
+        graph.add_reference(
+            &test,
+            &formatter_id,
+            Reference::new(
+                ReferenceKind::Type,
+                formatter.location,
+                "Formatter".to_string(),
+            ),
+        );
+
+        let entry_points: HashSet<_> = [app, test].into_iter().collect();
+        let analyzer = ReachabilityAnalyzer::new();
+        let (dead_code, reachable) =
+            analyzer.find_unreachable_with_reachable(&graph, &entry_points);
+        assert!(dead_code.is_empty());
+
+        let test_only = analyzer.find_test_only(&graph, &entry_points, &reachable);
+        assert_eq!(test_only.len(), 1);
+        assert_eq!(test_only[0].declaration.name, "Formatter");
+        assert_eq!(test_only[0].issue, DeadCodeIssue::OnlyUsedInTests);
+    }
+
+    #[test]
+    fn test_find_preview_only() {
+        use crate::graph::{Language, Location, Reference, ReferenceKind};
+        use std::path::PathBuf;
+
+        let make = |name: &str, annotation: &str, line: usize| {
+            let file = PathBuf::from("app/src/main/java/Home.kt");
+            let mut decl = Declaration::new(
+                DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+                name.to_string(),
+                DeclarationKind::Function,
+                Location::new(file, line, 1, line * 100, line * 100 + 50),
+                Language::Kotlin,
+            );
+            decl.annotations.push(annotation.to_string());
+            decl
+        };
+        let mut graph = Graph::new();
+        let home = graph.add_declaration(make("HomeScreen", "@Composable", 1));
+        let header = make("Header", "@Composable", 2);
+        let header_id = graph.add_declaration(header.clone());
+        let badge = make("Badge", "@Composable", 3);
+        let badge_id = graph.add_declaration(badge.clone());
+        let preview = graph.add_declaration(make("BadgePreview", "@Preview", 4));
+        for (from, to, decl) in [(&home, &header_id, &header), (&preview, &badge_id, &badge)] {
+            graph.add_reference(
+                from,
+                to,
+                Reference::new(
+                    ReferenceKind::Call,
+                    decl.location.clone(),
+                    decl.name.clone(),
+                ),
+            );
+        }
+
+        let entry_points: HashSet<_> = [home, preview.clone()].into_iter().collect();
+        let previews: HashSet<_> = [preview].into_iter().collect();
+        let analyzer = ReachabilityAnalyzer::new();
+        let (dead_code, reachable) =
+            analyzer.find_unreachable_with_reachable(&graph, &entry_points);
+        assert!(dead_code.is_empty());
+
+        let preview_only = analyzer.find_preview_only(&graph, &entry_points, &previews, &reachable);
+        assert_eq!(preview_only.len(), 1);
+        assert_eq!(preview_only[0].declaration.name, "Badge");
+        assert_eq!(preview_only[0].issue, DeadCodeIssue::OnlyUsedInPreviews);
+    }
+
+    #[test]
+    fn test_overrides_need_an_instance() {
+        use crate::graph::{Language, Location, Reference, ReferenceKind};
+        use std::path::PathBuf;
+
+        let make = |name: &str, kind: DeclarationKind, line: usize| {
+            let file = PathBuf::from("Shapes.kt");
+            Declaration::new(
+                DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+                name.to_string(),
+                kind,
+                Location::new(file, line, 1, line * 100, line * 100 + 50),
+                Language::Kotlin,
+            )
+        };
+        let mut graph = Graph::new();
+        let mut add_class = |name: &str, line: usize| {
+            let mut class = make(name, DeclarationKind::Class, line);
+            class.super_types.push("Shape".to_string());
+            let class = graph.add_declaration(class);
+            let mut area = make("area", DeclarationKind::Method, line + 1);
+            area.parent = Some(class.clone());
+            area.modifiers.push("override".to_string());
+            let area = graph.add_declaration(area);
+            (class, area)
+        };
+        let (circle, circle_area) = add_class("Circle", 10);
+        let (square, square_area) = add_class("Square", 20);
+        let shape = graph.add_declaration(make("Shape", DeclarationKind::Interface, 1));
+        let mut area = make("area", DeclarationKind::Method, 2);
+        area.parent = Some(shape.clone());
+        let area = graph.add_declaration(area);
+        let main = graph.add_declaration(make("main", DeclarationKind::Function, 30));
+
+        let reference = |kind, name: &str| {
+            Reference::new(
+                kind,
+                Location::new(PathBuf::from("Shapes.kt"), 31, 1, 0, 0),
+                name.to_string(),
+            )
+        };
+        graph.add_reference(&main, &circle, reference(ReferenceKind::Call, "Circle"));
+        graph.add_reference(&main, &square, reference(ReferenceKind::Type, "Square"));
+        // `shape.area()` resolves by name to every `area`
+        for target in [&area, &circle_area, &square_area] {
+            graph.add_reference(&main, target, reference(ReferenceKind::Call, "area"));
+        }
+
+        let entry_points: HashSet<_> = [main].into_iter().collect();
+        let (dead, reachable) =
+            ReachabilityAnalyzer::new().find_unreachable_with_reachable(&graph, &entry_points);
+
+        assert!(reachable.contains(&circle_area));
+        assert!(reachable.contains(&square));
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].declaration.id, square_area);
+        assert_eq!(
+            dead[0].message,
+            "method 'area' overrides a super type member, but 'Square' is never instantiated"
+        );
+    }
 }
@@ -0,0 +1,221 @@
+//! Reflection heuristics
+//!
+//! Code reached through reflection has no reference in the graph. Files that
+//! look classes or members up by name are where such uses hide, so findings
+//! in them (or, if configured, in their package) get a lower confidence and
+//! name the reflection that was found.
+
+use super::scoring::{self, Evidence, EvidenceKind};
+use super::{Confidence, DeadCode};
+use crate::config::{ReflectionConfig, ReflectionScope};
+use crate::discovery::SourceFile;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Reflection found in the analyzed sources, by file and by package
+#[derive(Debug, Default)]
+pub struct ReflectionUsage {
+    by_file: HashMap<PathBuf, Vec<&'static str>>,
+    by_package: HashMap<String, Vec<&'static str>>,
+    packages: HashMap<PathBuf, String>,
+}
+
+impl ReflectionUsage {
+    /// Scan Kotlin and Java sources for reflection
+    pub fn scan(files: &[SourceFile]) -> Self {
+        let scanned: Vec<(PathBuf, Option<String>, Vec<&'static str>)> = files
+            .par_iter()
+            .filter(|file| file.file_type.is_source())
+            .filter_map(|file| {
+                let contents = file.read_contents().ok()?;
+                Some((
+                    file.path.clone(),
+                    package_of(&contents),
+                    indicators(&contents),
+                ))
+            })
+            .collect();
+
+        let mut usage = Self::default();
+        for (path, package, found) in scanned {
+            usage.add(path, package, found);
+        }
+        usage
+    }
+
+    /// Record the reflection found in a file
+    pub fn add(&mut self, path: PathBuf, package: Option<String>, found: Vec<&'static str>) {
+        if let Some(package) = package {
+            if !found.is_empty() {
+                let in_package = self.by_package.entry(package.clone()).or_default();
+                for &indicator in &found {
+                    if !in_package.contains(&indicator) {
+                        in_package.push(indicator);
+                    }
+                }
+            }
+            self.packages.insert(path.clone(), package);
+        }
+        if !found.is_empty() {
+            self.by_file.insert(path, found);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_file.is_empty()
+    }
+
+    /// Lower the confidence of findings near reflection, noting it in their
+    /// message and evidence
+    pub fn apply(&self, dead_code: &mut [DeadCode], config: &ReflectionConfig) {
+        for dc in dead_code.iter_mut() {
+            // Runtime coverage already settled whether it runs
+            if dc.runtime_confirmed {
+                continue;
+            }
+
+            let file = &dc.declaration.location.file;
+            let package = self.packages.get(file).map(String::as_str);
+            let in_file = self.by_file.get(file);
+            let (found, place) = match config.scope(package) {
+                ReflectionScope::Ignore => continue,
+                ReflectionScope::File => (in_file, "file"),
+                ReflectionScope::Package => match package.and_then(|p| self.by_package.get(p)) {
+                    Some(found) if in_file.is_none() => (Some(found), "package"),
+                    _ => (in_file, "file"),
+                },
+            };
+            let Some(found) = found else {
+                continue;
+            };
+
+            let found = found.join(", ");
+            dc.message = format!("{} ({} uses reflection: {})", dc.message, place, found);
+            if dc.evidence.is_empty() {
+                dc.confidence = lower(dc.confidence);
+            } else {
+                let description = format!("Its {} uses reflection: {}", place, found);
+                dc.evidence
+                    .retain(|e| e.kind != EvidenceKind::NoReflectionMarkers);
+                dc.evidence
+                    .push(Evidence::new(EvidenceKind::ReflectionUsage, description));
+                dc.confidence = Confidence::from_score(scoring::score(&dc.evidence));
+            }
+        }
+    }
+}
+
+/// Reflection used in a source file, by the name reported to users
+pub fn indicators(contents: &str) -> Vec<&'static str> {
+    let mut found = Vec::new();
+    if contents.contains("Class.forName(") {
+        found.push("Class.forName");
+    }
+    if contents.contains("::class.java") {
+        found.push("::class.java");
+    }
+    if contents.contains("kotlin.reflect") {
+        found.push("kotlin.reflect");
+    }
+    // `invoke` alone is common (operator functions, lambdas); require a
+    // method lookup or the reflection package as well
+    let looks_up_methods = contents.contains("getMethod(")
+        || contents.contains("getDeclaredMethod(")
+        || contents.contains("java.lang.reflect");
+    if looks_up_methods && contents.contains(".invoke(") {
+        found.push("Method.invoke");
+    }
+    found
+}
+
+/// Package declared by a source file
+fn package_of(contents: &str) -> Option<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix("package "))
+        .map(|package| package.trim().trim_end_matches(';').trim().to_string())
+}
+
+pub(super) fn lower(confidence: Confidence) -> Confidence {
+    match confidence {
+        Confidence::Confirmed => Confidence::High,
+        Confidence::High => Confidence::Medium,
+        Confidence::Medium | Confidence::Low => Confidence::Low,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+    use std::collections::BTreeMap;
+
+    fn finding(file: &str) -> DeadCode {
+        let path = PathBuf::from(file);
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), 0, 10),
+            "Plugin".to_string(),
+            DeclarationKind::Class,
+            Location::new(path, 1, 1, 0, 10),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, DeadCodeIssue::Unreferenced).with_evidence(vec![
+            Evidence::new(EvidenceKind::StaticUnreferenced, ""),
+            Evidence::new(EvidenceKind::NoReflectionMarkers, ""),
+        ])
+    }
+
+    #[test]
+    fn test_indicators() {
+        let source = r#"
+            import kotlin.reflect.full.createInstance
+            val type = Class.forName(name)
+            val method = type.getDeclaredMethod("run")
+            method.invoke(null)
+        "#;
+        assert_eq!(
+            indicators(source),
+            vec!["Class.forName", "kotlin.reflect", "Method.invoke"]
+        );
+        assert!(indicators("val result = callback.invoke(value)").is_empty());
+        assert_eq!(
+            package_of("package com.example;\n"),
+            Some("com.example".to_string())
+        );
+    }
+
+    #[test]
+    fn test_apply_by_scope() {
+        let mut usage = ReflectionUsage::default();
+        let package = Some("com.example.plugins".to_string());
+        usage.add("Loader.kt".into(), package.clone(), vec!["Class.forName"]);
+        usage.add("Plugin.kt".into(), package, Vec::new());
+
+        let mut config = ReflectionConfig::default();
+        let mut findings = vec![finding("Loader.kt"), finding("Plugin.kt")];
+        usage.apply(&mut findings, &config);
+        assert_eq!(findings[0].confidence, Confidence::Low);
+        assert!(findings[0]
+            .message
+            .ends_with("(file uses reflection: Class.forName)"));
+        assert!(!findings[0]
+            .evidence
+            .iter()
+            .any(|e| e.kind == EvidenceKind::NoReflectionMarkers));
+        assert_eq!(findings[1].confidence, Confidence::Medium);
+
+        config.packages = BTreeMap::from([("com.example".to_string(), ReflectionScope::Package)]);
+        let mut findings = vec![finding("Plugin.kt")];
+        usage.apply(&mut findings, &config);
+        assert_eq!(findings[0].confidence, Confidence::Low);
+        assert!(findings[0].message.contains("package uses reflection"));
+
+        config.packages = BTreeMap::from([("com.example".to_string(), ReflectionScope::Ignore)]);
+        let mut findings = vec![finding("Loader.kt")];
+        usage.apply(&mut findings, &config);
+        assert_eq!(findings[0].confidence, Confidence::Medium);
+    }
+}
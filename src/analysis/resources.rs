@@ -2,13 +2,58 @@
 //!
 //! This module detects unused Android resources like strings, colors, dimensions,
 //! drawables, etc. by cross-referencing resource definitions with code references.
+//!
+//! Whole layout, menu and navigation graph files are reported too when they are
+//! never referenced via `R.layout.*`/`R.menu.*`/`R.navigation.*`, `@layout/...`
+//! style XML references (including `<include>` tags), or generated data/view
+//! binding class names (`ActivityMainBinding` -> `activity_main`).
+//!
+//! Drawable, mipmap, raw and font files (including binary ones) are resources
+//! named after the file, and the files under a source set's `assets/` are
+//! `asset` resources named by their path, used when code opens them by name
+//! (`assets.open("data/cities.json")`). Both carry their size on disk, the APK
+//! space deleting them saves.
+//!
+//! View ids declared in layouts (`android:id="@+id/toolbar"`) are tracked as
+//! `id` resources and count as used through `R.id.*`, `@id/...` references or
+//! binding field accesses (`binding.toolbarTitle` -> `toolbar_title`).
+//!
+//! Every `res` directory is indexed with its [`ResourceVariant`]: the Gradle
+//! source set it belongs to (`src/debug/res`, `src/androidTest/res`) and the
+//! configuration qualifiers of the resource folder (`values-night-v31`). A
+//! resource defined in several variants is reported once, at its most general
+//! definition, with the others attached as overlays.
+//!
+//! References made by resource definitions (a style's parent and items, the
+//! strings of a `string-array` or `plurals`, what a layout includes) are edges
+//! of a resource graph rather than uses: a resource only referenced by unused
+//! resources is unused too. Strings left behind in a translation (`values-fr`)
+//! after their default definition was removed are flagged as locale-only.
+//!
+//! Styles and themes are used when applied: `android:theme` in the manifest,
+//! `style="@style/..."` in a layout, `R.style.*` in code (for a
+//! `ContextThemeWrapper` or `setTheme`), or as the parent of a used style.
+//! Custom attrs are used when read: through the `R.styleable.View_attr` index
+//! into the `TypedArray` of `obtainStyledAttributes`, `R.attr.*`, or a
+//! `?attr/...` theme reference. Declaring or setting an attr is not a read.
 
+use crate::parser::xml::LayoutParser;
 use quick_xml::events::Event;
 use quick_xml::Reader;
+use regex::Regex;
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// A `@type/name` reference in XML (style names contain dots)
+const RESOURCE_REFERENCE: &str = r"@(\w+)/([\w.]+)";
+
+/// A `?attr/name` or `?name` theme attribute reference in XML; the second
+/// group is a `:` for framework attributes (`?android:attr/textColorPrimary`)
+const THEME_ATTRIBUTE_REFERENCE: &str = r"\?(?:attr/)?(\w+)(:?)";
+
 /// Represents an Android resource
 #[derive(Debug, Clone)]
 pub struct AndroidResource {
@@ -20,6 +65,122 @@ pub struct AndroidResource {
     pub file: PathBuf,
     /// Line number in the file
     pub line: usize,
+    /// Source set and qualifiers of the defining folder
+    pub variant: ResourceVariant,
+    /// Definitions of the same resource in other variants (e.g. `values-night`)
+    pub overlays: Vec<AndroidResource>,
+    /// Bytes on disk of a file-based resource
+    pub size: Option<u64>,
+}
+
+impl AndroidResource {
+    /// Whether every definition is in a locale folder (`values-fr`), so the
+    /// default definition is gone
+    pub fn is_locale_only(&self) -> bool {
+        std::iter::once(self)
+            .chain(&self.overlays)
+            .all(|resource| resource.variant.locale().is_some())
+    }
+
+    fn new(name: String, resource_type: &str, file: &Path, line: usize) -> Self {
+        Self {
+            name,
+            resource_type: resource_type.to_string(),
+            file: file.to_path_buf(),
+            line,
+            variant: ResourceVariant::from_path(file),
+            overlays: Vec::new(),
+            size: None,
+        }
+    }
+
+    /// Bytes on disk of a file-based resource and all its overlays
+    pub fn total_size(&self) -> Option<u64> {
+        let overlays: u64 = self.overlays.iter().filter_map(|o| o.size).sum();
+        self.size.map(|size| size + overlays)
+    }
+}
+
+/// Where a resource definition lives
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ResourceVariant {
+    /// Gradle source set of the `res` directory ("main", "debug", "test", ...)
+    pub source_set: String,
+    /// Configuration qualifiers of the folder (`values-night-v31` -> night, v31)
+    pub qualifiers: Vec<String>,
+}
+
+impl ResourceVariant {
+    /// Derive the variant from a resource file path such as
+    /// `app/src/debug/res/values-night/colors.xml`
+    pub fn from_path(file: &Path) -> Self {
+        let type_dir = file.parent();
+        let qualifiers: Vec<String> = type_dir
+            .and_then(|dir| dir.file_name())
+            .map(|name| {
+                name.to_string_lossy()
+                    .split('-')
+                    .skip(1)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // <module>/src/<source set>/res/<type>/<file>
+        let source_set_dir = type_dir.and_then(Path::parent).and_then(Path::parent);
+        let source_set = source_set_dir
+            .filter(|dir| dir.parent().and_then(Path::file_name) == Some(OsStr::new("src")))
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "main".to_string());
+
+        Self {
+            source_set,
+            qualifiers,
+        }
+    }
+
+    /// Whether the resource belongs to a unit or instrumentation test source set
+    pub fn is_test(&self) -> bool {
+        self.source_set.starts_with("test") || self.source_set.starts_with("androidTest")
+    }
+
+    /// Language qualifier of the folder (`values-fr-rCA` is fr), if any
+    pub fn locale(&self) -> Option<&str> {
+        self.qualifiers
+            .iter()
+            .map(String::as_str)
+            .find(|qualifier| {
+                qualifier.starts_with("b+")
+                    || (matches!(qualifier.len(), 2 | 3)
+                        && *qualifier != "car"
+                        && qualifier.chars().all(|c| c.is_ascii_lowercase()))
+            })
+    }
+
+    /// Whether this is the unqualified definition of the main source set
+    pub fn is_default(&self) -> bool {
+        self.source_set == "main" && self.qualifiers.is_empty()
+    }
+
+    /// Short label such as `debug` or `main, night`, or None for the default
+    pub fn label(&self) -> Option<String> {
+        if self.is_default() {
+            return None;
+        }
+        let mut parts = vec![self.source_set.clone()];
+        parts.extend(self.qualifiers.iter().cloned());
+        Some(parts.join(", "))
+    }
+
+    /// Lower ranks are more general and become the primary definition
+    fn rank(&self) -> (bool, bool, usize) {
+        (
+            self.is_test(),
+            self.source_set != "main",
+            self.qualifiers.len(),
+        )
+    }
 }
 
 /// Result of resource analysis
@@ -27,12 +188,49 @@ pub struct AndroidResource {
 pub struct ResourceAnalysis {
     /// All defined resources by type -> name
     pub defined: HashMap<String, HashMap<String, AndroidResource>>,
-    /// Resources referenced in code
+    /// Resources referenced in code and in XML other than resource
+    /// definitions (manifest, drawables, ...)
     pub referenced: HashSet<(String, String)>, // (type, name)
-    /// Unused resources (defined but not referenced)
+    /// Resources referenced by the definition of each resource
+    pub resource_references: HashMap<(String, String), HashSet<(String, String)>>,
+    /// Asset path prefixes opened with a name built at runtime
+    /// (`"images/$name.png"`, `assets.list("images")`)
+    pub asset_prefixes: HashSet<String>,
+    /// Unused resources (not reachable from a reference)
     pub unused: Vec<AndroidResource>,
 }
 
+impl ResourceAnalysis {
+    /// Unused resources referencing a resource, which is why it looked used
+    pub fn unused_referrers(&self, resource: &AndroidResource) -> Vec<&(String, String)> {
+        let key = (resource.resource_type.clone(), resource.name.clone());
+        let mut referrers: Vec<_> = self
+            .resource_references
+            .iter()
+            .filter(|(from, to)| **from != key && to.contains(&key))
+            .map(|(from, _)| from)
+            .collect();
+        referrers.sort();
+        referrers
+    }
+
+    /// Record a reference made by the definition of `from`, or a use when
+    /// it is made outside of any resource
+    fn add_reference(&mut self, from: Option<&(String, String)>, to: (String, String)) {
+        match from {
+            Some(from) => {
+                self.resource_references
+                    .entry(from.clone())
+                    .or_default()
+                    .insert(to);
+            }
+            None => {
+                self.referenced.insert(to);
+            }
+        }
+    }
+}
+
 /// Detector for unused Android resources
 pub struct ResourceDetector {
     /// Minimum reference count to consider a resource as used
@@ -55,21 +253,59 @@ impl ResourceDetector {
         for res_dir in &res_dirs {
             self.parse_resource_dir(res_dir, &mut analysis);
         }
+        for assets_dir in self.find_asset_dirs(project_root) {
+            self.parse_asset_dir(&assets_dir, &mut analysis);
+        }
 
         // Collect all references from Kotlin/Java files
         self.collect_code_references(project_root, &mut analysis);
+        Self::resolve_styleable_indices(&mut analysis);
+
+        // R.style.Theme_App in code is @style/Theme.App in XML
+        let key = |res_type: &str, name: &str| match res_type {
+            "style" => (res_type.to_string(), name.replace('.', "_")),
+            _ => (res_type.to_string(), name.to_string()),
+        };
+        let mut edges: HashMap<(String, String), Vec<(String, String)>> = HashMap::new();
+        for (from, to) in &analysis.resource_references {
+            edges
+                .entry(key(&from.0, &from.1))
+                .or_default()
+                .extend(to.iter().map(|(res_type, name)| key(res_type, name)));
+        }
+
+        // Resources assumed used keep what they reference alive too
+        let mut reachable: HashSet<(String, String)> = analysis
+            .referenced
+            .iter()
+            .map(|(res_type, name)| key(res_type, name))
+            .collect();
+        for (res_type, resources) in &analysis.defined {
+            for name in resources.keys() {
+                let opened = res_type == "asset"
+                    && analysis
+                        .asset_prefixes
+                        .iter()
+                        .any(|prefix| name.starts_with(prefix.as_str()));
+                if opened || self.should_skip_resource(name, res_type) {
+                    reachable.insert(key(res_type, name));
+                }
+            }
+        }
+        let mut queue: Vec<(String, String)> = reachable.iter().cloned().collect();
+        while let Some(from) = queue.pop() {
+            for next in edges.get(&from).into_iter().flatten() {
+                if reachable.insert(next.clone()) {
+                    queue.push(next.clone());
+                }
+            }
+        }
 
         // Find unused resources
         for (res_type, resources) in &analysis.defined {
             for (name, resource) in resources {
-                if !analysis
-                    .referenced
-                    .contains(&(res_type.clone(), name.clone()))
-                {
-                    // Check for common false positives
-                    if !self.should_skip_resource(name, res_type) {
-                        analysis.unused.push(resource.clone());
-                    }
+                if !reachable.contains(&key(res_type, name)) {
+                    analysis.unused.push(resource.clone());
                 }
             }
         }
@@ -84,9 +320,26 @@ impl ResourceDetector {
 
     /// Find all res/ directories in the project
     fn find_resource_dirs(&self, project_root: &Path) -> Vec<PathBuf> {
+        Self::find_dirs_named(project_root, "res")
+    }
+
+    /// Find the assets/ directories of source sets (`src/main/assets`)
+    fn find_asset_dirs(&self, project_root: &Path) -> Vec<PathBuf> {
+        Self::find_dirs_named(project_root, "assets")
+            .into_iter()
+            .filter(|dir| {
+                dir.parent()
+                    .and_then(Path::parent)
+                    .and_then(Path::file_name)
+                    == Some(OsStr::new("src"))
+            })
+            .collect()
+    }
+
+    fn find_dirs_named(project_root: &Path, dir_name: &str) -> Vec<PathBuf> {
         let mut dirs = Vec::new();
 
-        // Walk the project looking for res/ directories
+        // Walk the project looking for matching directories
         let walker = walkdir::WalkDir::new(project_root)
             .into_iter()
             .filter_entry(|e| {
@@ -97,7 +350,7 @@ impl ResourceDetector {
         for entry in walker.flatten() {
             if entry.file_type().is_dir() {
                 let name = entry.file_name().to_string_lossy();
-                if name == "res" {
+                if name == dir_name {
                     dirs.push(entry.path().to_path_buf());
                 }
             }
@@ -106,32 +359,154 @@ impl ResourceDetector {
         dirs
     }
 
-    /// Parse all resource files in a res directory
+    /// Parse all resource files in a res directory, including every qualified
+    /// variant of a resource folder (values-night, layout-land, ...)
     fn parse_resource_dir(&self, res_dir: &Path, analysis: &mut ResourceAnalysis) {
-        // Check common resource subdirectories
-        let subdirs = [
-            "values",
-            "values-en",
-            "values-fr",
-            "values-es",
-            "values-de",
-            "values-night",
-            "values-v21",
-            "values-w600dp",
-        ];
-
-        for subdir in subdirs {
-            let values_dir = res_dir.join(subdir);
-            if values_dir.exists() && values_dir.is_dir() {
-                if let Ok(entries) = fs::read_dir(&values_dir) {
-                    for entry in entries.flatten() {
-                        let path = entry.path();
-                        if path.extension().map(|e| e == "xml").unwrap_or(false) {
-                            self.parse_values_xml(&path, analysis);
+        let Ok(entries) = fs::read_dir(res_dir) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            let dir = entry.path();
+            if !dir.is_dir() {
+                continue;
+            }
+            let dir_name = entry.file_name().to_string_lossy().to_string();
+            let res_type = dir_name.split('-').next().unwrap_or(&dir_name);
+
+            match res_type {
+                "values" => {
+                    if let Ok(files) = fs::read_dir(&dir) {
+                        for file in files.flatten() {
+                            let path = file.path();
+                            if path.extension().map(|e| e == "xml").unwrap_or(false) {
+                                self.parse_values_xml(&path, analysis);
+                            }
                         }
                     }
                 }
+                // File-based resources (layout, layout-land, menu, navigation, ...)
+                "layout" | "menu" | "navigation" | "drawable" | "mipmap" | "raw" | "font" => {
+                    self.parse_file_resource_dir(&dir, res_type, analysis);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Record a resource definition, attaching it as an overlay when the same
+    /// resource is already defined in another variant
+    fn define(analysis: &mut ResourceAnalysis, resource: AndroidResource) {
+        let by_name = analysis
+            .defined
+            .entry(resource.resource_type.clone())
+            .or_default();
+
+        match by_name.entry(resource.name.clone()) {
+            Entry::Vacant(entry) => {
+                entry.insert(resource);
+            }
+            Entry::Occupied(mut entry) => {
+                let existing = entry.get_mut();
+                if existing.file == resource.file {
+                    return;
+                }
+                if resource.variant.rank() < existing.variant.rank() {
+                    let mut previous = std::mem::replace(existing, resource);
+                    existing.overlays.append(&mut previous.overlays);
+                    existing.overlays.push(previous);
+                } else {
+                    existing.overlays.push(resource);
+                }
+            }
+        }
+    }
+
+    /// Register every file in a file-based resource directory as a resource
+    /// named after the file (e.g., res/layout/activity_main.xml -> layout/activity_main,
+    /// res/drawable-hdpi/ic_badge.9.png -> drawable/ic_badge). Layouts, menus
+    /// and navigation graphs are XML; drawables, raw files and fonts can be anything.
+    fn parse_file_resource_dir(&self, dir: &Path, res_type: &str, analysis: &mut ResourceAnalysis) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+        let xml_only = matches!(res_type, "layout" | "menu" | "navigation");
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if !metadata.is_file()
+                || (xml_only && !path.extension().map(|e| e == "xml").unwrap_or(false))
+            {
+                continue;
             }
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let Some(name) = file_name.split('.').next().filter(|n| !n.is_empty()) else {
+                continue;
+            };
+
+            // The same layout can exist in several qualified directories
+            let mut resource = AndroidResource::new(name.to_string(), res_type, &path, 1);
+            resource.size = Some(metadata.len());
+            Self::define(analysis, resource);
+
+            if res_type == "layout" {
+                self.parse_layout_ids(&path, analysis);
+            }
+        }
+    }
+
+    /// Register every file under an assets/ directory as an asset named by its
+    /// path (`src/main/assets/data/cities.json` -> asset/data/cities.json)
+    fn parse_asset_dir(&self, assets_dir: &Path, analysis: &mut ResourceAnalysis) {
+        let source_set = assets_dir
+            .parent()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "main".to_string());
+
+        for entry in walkdir::WalkDir::new(assets_dir).into_iter().flatten() {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(relative) = entry.path().strip_prefix(assets_dir) else {
+                continue;
+            };
+            let name = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            let mut resource = AndroidResource::new(name, "asset", entry.path(), 1);
+            resource.variant = ResourceVariant {
+                source_set: source_set.clone(),
+                qualifiers: Vec::new(),
+            };
+            resource.size = entry.metadata().ok().map(|m| m.len());
+            Self::define(analysis, resource);
+        }
+    }
+
+    /// Register view ids declared in a layout (`android:id="@+id/name"`)
+    fn parse_layout_ids(&self, file_path: &Path, analysis: &mut ResourceAnalysis) {
+        let content = match fs::read_to_string(file_path) {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+
+        let id_pattern = Regex::new(r#":id\s*=\s*"@\+id/(\w+)""#).unwrap();
+
+        for cap in id_pattern.captures_iter(&content) {
+            let name = cap[1].to_string();
+            let offset = cap.get(0).map(|m| m.start()).unwrap_or(0);
+            let line = content[..offset].matches('\n').count() + 1;
+
+            // Ids are shared across layouts
+            Self::define(analysis, AndroidResource::new(name, "id", file_path, line));
         }
     }
 
@@ -143,12 +518,18 @@ impl ResourceDetector {
         };
 
         let mut reader = Reader::from_str(&content);
+        let ref_pattern = Regex::new(RESOURCE_REFERENCE).unwrap();
+        let attr_pattern = Regex::new(&format!("^{}", THEME_ATTRIBUTE_REFERENCE)).unwrap();
 
         let mut line = 1;
         let mut buf = Vec::new();
+        // Open elements, and the top-level resource being defined
+        let mut depth = 0;
+        let mut owner: Option<(String, String)> = None;
 
         loop {
-            match reader.read_event_into(&mut buf) {
+            let event = reader.read_event_into(&mut buf);
+            match event {
                 Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                     let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
 
@@ -169,31 +550,81 @@ impl ResourceDetector {
                         _ => None,
                     };
 
+                    let mut defined = None;
                     if let Some(res_type) = resource_type {
                         // Get the name attribute
                         for attr in e.attributes().flatten() {
                             if attr.key.as_ref() == b"name" {
                                 let name = String::from_utf8_lossy(&attr.value).to_string();
-
-                                let resource = AndroidResource {
-                                    name: name.clone(),
-                                    resource_type: res_type.to_string(),
-                                    file: file_path.to_path_buf(),
-                                    line,
-                                };
-
-                                analysis
-                                    .defined
-                                    .entry(res_type.to_string())
-                                    .or_default()
-                                    .insert(name, resource);
+                                // `<attr name="android:textColor"/>` reuses a framework attr
+                                if res_type == "attr" && name.contains(':') {
+                                    break;
+                                }
+                                let resource =
+                                    AndroidResource::new(name.clone(), res_type, file_path, line);
+                                Self::define(analysis, resource);
+                                defined = Some((res_type.to_string(), name));
 
                                 break;
                             }
                         }
                     }
+
+                    // Top-level resources own the references in their definition
+                    if depth == 1 {
+                        owner = defined;
+                    }
+                    for attr in e.attributes().flatten() {
+                        let value = String::from_utf8_lossy(&attr.value);
+                        if attr.key.as_ref() == b"parent" && !value.starts_with('@') {
+                            if !value.is_empty() {
+                                let parent = ("style".to_string(), value.to_string());
+                                analysis.add_reference(owner.as_ref(), parent);
+                            }
+                            continue;
+                        }
+                        for cap in ref_pattern.captures_iter(&value) {
+                            analysis.add_reference(
+                                owner.as_ref(),
+                                (cap[1].to_string(), cap[2].to_string()),
+                            );
+                        }
+                        Self::add_theme_attribute(&attr_pattern, &value, owner.as_ref(), analysis);
+                    }
+                    // `AppTheme.Dark` inherits from `AppTheme` without naming it
+                    if let Some((res_type, name)) = owner.clone().filter(|_| depth == 1) {
+                        if let Some((parent, _)) =
+                            name.rsplit_once('.').filter(|_| res_type == "style")
+                        {
+                            let parent = ("style".to_string(), parent.to_string());
+                            analysis.add_reference(owner.as_ref(), parent);
+                        }
+                    }
+
+                    if matches!(event, Ok(Event::Start(_))) {
+                        depth += 1;
+                    } else if depth == 1 {
+                        owner = None;
+                    }
+                }
+                Ok(Event::End(_)) => {
+                    depth -= 1;
+                    if depth == 1 {
+                        owner = None;
+                    }
                 }
                 Ok(Event::Text(ref e)) => {
+                    // `<item>@string/choice</item>` in arrays, plurals and styles
+                    let text = String::from_utf8_lossy(e.as_ref());
+                    for cap in ref_pattern.captures_iter(&text) {
+                        analysis.add_reference(
+                            owner.as_ref(),
+                            (cap[1].to_string(), cap[2].to_string()),
+                        );
+                    }
+                    // `<item name="colorAccent">?attr/colorSecondary</item>`
+                    Self::add_theme_attribute(&attr_pattern, &text, owner.as_ref(), analysis);
+
                     // Count newlines in text content to track line number
                     let bytes: &[u8] = e.as_ref();
                     line += bytes.iter().filter(|&&b| b == b'\n').count();
@@ -206,6 +637,50 @@ impl ResourceDetector {
         }
     }
 
+    /// Record the attr read by a `?attr/name` value, unless it is a framework attr
+    fn add_theme_attribute(
+        attr_pattern: &Regex,
+        value: &str,
+        owner: Option<&(String, String)>,
+        analysis: &mut ResourceAnalysis,
+    ) {
+        if let Some(cap) = attr_pattern.captures(value.trim()) {
+            if cap[2].is_empty() {
+                analysis.add_reference(owner, ("attr".to_string(), cap[1].to_string()));
+            }
+        }
+    }
+
+    /// Resolve `R.styleable.MyView_cornerRadius` code references: the index of
+    /// an attr in a styleable, which is how `obtainStyledAttributes` callers
+    /// read it from the `TypedArray`. Declaring an attr in a styleable or
+    /// setting it in a layout or style does not read it.
+    fn resolve_styleable_indices(analysis: &mut ResourceAnalysis) {
+        let Some(styleables) = analysis.defined.get("styleable") else {
+            return;
+        };
+        let mut reads = Vec::new();
+        for (res_type, index) in &analysis.referenced {
+            if res_type != "styleable" || styleables.contains_key(index) {
+                continue;
+            }
+            // Styleable names may contain underscores too: take the longest match
+            let styleable = styleables
+                .keys()
+                .filter(|name| {
+                    index.len() > name.len()
+                        && index.starts_with(name.as_str())
+                        && index.as_bytes()[name.len()] == b'_'
+                })
+                .max_by_key(|name| name.len());
+            if let Some(styleable) = styleable {
+                reads.push(("styleable".to_string(), styleable.clone()));
+                reads.push(("attr".to_string(), index[styleable.len() + 1..].to_string()));
+            }
+        }
+        analysis.referenced.extend(reads);
+    }
+
     /// Collect resource references from Kotlin/Java code
     fn collect_code_references(&self, project_root: &Path, analysis: &mut ResourceAnalysis) {
         // Patterns for resource references:
@@ -253,37 +728,188 @@ impl ResourceDetector {
                 .referenced
                 .insert((res_type.to_string(), res_name.to_string()));
         }
+
+        // Pattern: generated binding classes (ActivityMainBinding -> layout/activity_main)
+        let binding_pattern = regex::Regex::new(r"\b([A-Z]\w*Binding)\b").unwrap();
+
+        for cap in binding_pattern.captures_iter(&content) {
+            if let Some(layout) = LayoutParser::layout_name_for_binding(&cap[1]) {
+                analysis.referenced.insert(("layout".to_string(), layout));
+            }
+        }
+
+        // Pattern: binding field accesses (binding.toolbarTitle -> id/toolbar_title)
+        for field in Self::binding_field_accesses(&content) {
+            analysis
+                .referenced
+                .insert(("id".to_string(), LayoutParser::id_for_binding_field(&field)));
+            analysis.referenced.insert(("id".to_string(), field));
+        }
+
+        // Pattern: Kotlin synthetics (kotlinx.android.synthetic.main.activity_main.*)
+        let synthetic_pattern = regex::Regex::new(r"synthetic\.main\.(\w+)").unwrap();
+
+        for cap in synthetic_pattern.captures_iter(&content) {
+            analysis
+                .referenced
+                .insert(("layout".to_string(), cap[1].to_string()));
+        }
+
+        Self::collect_asset_references(&content, analysis);
+    }
+
+    /// Collect assets opened by name (`assets.open("data/cities.json")`,
+    /// `Typeface.createFromAsset(assets, "fonts/inter.ttf")`) or by URL
+    /// (`file:///android_asset/help.html`). A name built at runtime
+    /// (`"images/$name.png"`, `"images/" + name`) or a listed directory
+    /// (`assets.list("images")`) keeps every asset under its literal prefix.
+    fn collect_asset_references(content: &str, analysis: &mut ResourceAnalysis) {
+        let call_pattern = Regex::new(
+            r#"\b(open|openFd|openNonAssetFd|list|createFromAsset)\s*\([^)"]*"([^"]*)"(\s*\+)?"#,
+        )
+        .unwrap();
+        let url_pattern = Regex::new(r#"file:///android_asset/([^"'\s)]+)"#).unwrap();
+
+        for cap in call_pattern.captures_iter(content) {
+            let literal = &cap[2];
+            if let Some(end) = literal.find('$') {
+                analysis.asset_prefixes.insert(literal[..end].to_string());
+            } else if &cap[1] == "list" {
+                let dir = literal.trim_end_matches('/');
+                let prefix = if dir.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}/", dir)
+                };
+                analysis.asset_prefixes.insert(prefix);
+            } else if cap.get(3).is_some() {
+                analysis.asset_prefixes.insert(literal.to_string());
+            } else {
+                analysis
+                    .referenced
+                    .insert(("asset".to_string(), literal.to_string()));
+            }
+        }
+
+        for cap in url_pattern.captures_iter(content) {
+            analysis
+                .referenced
+                .insert(("asset".to_string(), cap[1].to_string()));
+        }
+    }
+
+    /// Collect fields accessed on binding variables
+    ///
+    /// Binding variables are the conventional `binding`/`_binding` plus anything
+    /// declared with a `*Binding` type. Inside `with(binding) { }` or
+    /// `binding.apply { }` views are accessed unqualified, so every identifier in
+    /// such a file is treated as a possible field access.
+    fn binding_field_accesses(content: &str) -> HashSet<String> {
+        let kotlin_var_pattern =
+            Regex::new(r"\b(\w+)\s*(?::\s*\w+Binding\b|=\s*\w+Binding\s*\.|by\s+viewBinding)")
+                .unwrap();
+        let java_var_pattern = Regex::new(r"\b\w+Binding\s+(\w+)\s*[;=]").unwrap();
+        let access_pattern = Regex::new(r"\b(\w+)\s*(?:\?|!!)?\.\s*([a-z]\w*)").unwrap();
+        let scope_pattern =
+            Regex::new(r"\bwith\s*\(\s*(\w+)\s*\)|\b(\w+)\s*(?:\?|!!)?\.(?:apply|run)\s*\{")
+                .unwrap();
+
+        let mut vars: HashSet<&str> = ["binding", "_binding"].into_iter().collect();
+        for cap in kotlin_var_pattern
+            .captures_iter(content)
+            .chain(java_var_pattern.captures_iter(content))
+        {
+            if let Some(m) = cap.get(1) {
+                vars.insert(m.as_str());
+            }
+        }
+
+        let scoped = scope_pattern.captures_iter(content).any(|cap| {
+            cap.get(1)
+                .or_else(|| cap.get(2))
+                .is_some_and(|m| vars.contains(m.as_str()))
+        });
+        if scoped {
+            return content
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .filter(|token| token.starts_with(|c: char| c.is_ascii_lowercase()))
+                .map(|token| token.to_string())
+                .collect();
+        }
+
+        access_pattern
+            .captures_iter(content)
+            .filter(|cap| vars.contains(&cap[1]))
+            .map(|cap| cap[2].to_string())
+            .collect()
     }
 
     /// Extract @type/name references from XML files
+    ///
+    /// References in a layout, menu or navigation graph are made by that
+    /// resource; values files are handled while parsing their definitions.
     fn extract_xml_references(&self, file_path: &Path, analysis: &mut ResourceAnalysis) {
         let content = match fs::read_to_string(file_path) {
             Ok(c) => c,
             Err(_) => return,
         };
+        Self::collect_asset_references(&content, analysis);
+
+        let res_type = Self::resource_dir_type(file_path);
+        if res_type == Some("values") {
+            return;
+        }
+        let owner = res_type
+            .filter(|res_type| {
+                matches!(
+                    *res_type,
+                    "layout" | "menu" | "navigation" | "drawable" | "mipmap" | "font"
+                )
+            })
+            .zip(file_path.file_stem())
+            .map(|(res_type, name)| (res_type.to_string(), name.to_string_lossy().to_string()));
 
         // Pattern: @type/name
-        let ref_pattern = regex::Regex::new(r"@(\w+)/(\w+)").unwrap();
+        let ref_pattern = Regex::new(RESOURCE_REFERENCE).unwrap();
 
         for cap in ref_pattern.captures_iter(&content) {
             let res_type = &cap[1];
             let res_name = &cap[2];
-            analysis
-                .referenced
-                .insert((res_type.to_string(), res_name.to_string()));
+            analysis.add_reference(owner.as_ref(), (res_type.to_string(), res_name.to_string()));
         }
-    }
 
-    /// Check if a resource should be skipped (common false positives)
-    fn should_skip_resource(&self, name: &str, res_type: &str) -> bool {
-        // Skip resources that are likely framework-required
-        if res_type == "style" {
-            // Base themes are often required
-            if name.starts_with("Theme.") || name.starts_with("Base.") {
-                return true;
+        // Pattern: ?attr/name theme attribute reads (`android:textColor="?attr/colorOnSurface"`)
+        let attr_pattern = Regex::new(&format!("\"{}", THEME_ATTRIBUTE_REFERENCE)).unwrap();
+
+        for cap in attr_pattern.captures_iter(&content) {
+            if cap[2].is_empty() {
+                analysis.add_reference(owner.as_ref(), ("attr".to_string(), cap[1].to_string()));
             }
         }
 
+        // Pattern: @+id/name outside of android:id (e.g., constraint references)
+        let new_id_pattern = Regex::new(r#"([\w:]+)\s*=\s*"@\+id/(\w+)""#).unwrap();
+
+        for cap in new_id_pattern.captures_iter(&content) {
+            if !cap[1].ends_with(":id") {
+                analysis.add_reference(owner.as_ref(), ("id".to_string(), cap[2].to_string()));
+            }
+        }
+    }
+
+    /// Resource type of the folder an XML file is in (`res/values-fr/strings.xml`
+    /// is values), or None outside of a res directory
+    fn resource_dir_type(file_path: &Path) -> Option<&str> {
+        let dir = file_path.parent()?;
+        if dir.parent()?.file_name() != Some(OsStr::new("res")) {
+            return None;
+        }
+        let dir_name = dir.file_name()?.to_str()?;
+        dir_name.split('-').next()
+    }
+
+    /// Check if a resource should be skipped (common false positives)
+    fn should_skip_resource(&self, name: &str, res_type: &str) -> bool {
         // Skip common Android-required resources
         let required_strings = ["app_name", "content_description"];
         if res_type == "string" && required_strings.contains(&name) {
@@ -342,4 +968,391 @@ mod tests {
         assert!(strings.contains_key("test_string"));
         assert!(strings.contains_key("another_string"));
     }
+
+    #[test]
+    fn test_binding_field_accesses() {
+        let fields = ResourceDetector::binding_field_accesses(
+            r#"
+class ProfileFragment : Fragment() {
+    private var _binding: FragmentProfileBinding? = null
+    private val views get() = _binding!!
+
+    fun bind() {
+        _binding?.toolbarTitle?.text = "Profile"
+        views.avatar.load(url)
+    }
+}
+"#,
+        );
+
+        assert!(fields.contains("toolbarTitle"));
+        assert!(!fields.contains("avatar"));
+        assert!(!fields.contains("load"));
+    }
+
+    #[test]
+    fn test_view_ids_referenced_through_binding() {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("resources")
+            .tempdir()
+            .unwrap();
+        let layout_dir = temp_dir.path().join("res").join("layout");
+        fs::create_dir_all(&layout_dir).unwrap();
+
+        fs::write(
+            layout_dir.join("activity_main.xml"),
+            r#"<androidx.constraintlayout.widget.ConstraintLayout>
+    <TextView android:id="@+id/toolbar_title" />
+    <TextView android:id="@+id/subtitle"
+        app:layout_constraintTop_toBottomOf="@+id/header" />
+    <View android:id="@+id/header" />
+    <View android:id="@+id/stale_divider" />
+</androidx.constraintlayout.widget.ConstraintLayout>"#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("MainActivity.kt"),
+            r#"
+class MainActivity {
+    private lateinit var binding: ActivityMainBinding
+    fun bind() {
+        binding = ActivityMainBinding.inflate(layoutInflater)
+        binding.toolbarTitle.text = "Home"
+        binding.subtitle.isVisible = false
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        let analysis = ResourceDetector::new().analyze(temp_dir.path());
+        let unused: HashSet<_> = analysis
+            .unused
+            .iter()
+            .map(|r| format!("{}/{}", r.resource_type, r.name))
+            .collect();
+
+        assert!(!unused.contains("layout/activity_main"));
+        assert!(!unused.contains("id/toolbar_title"));
+        assert!(!unused.contains("id/subtitle"));
+        assert!(!unused.contains("id/header"));
+        assert!(unused.contains("id/stale_divider"));
+    }
+
+    #[test]
+    fn test_unreferenced_layout_files() {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("resources")
+            .tempdir()
+            .unwrap();
+        let res_dir = temp_dir.path().join("src").join("main").join("res");
+        for dir in ["layout", "menu", "navigation"] {
+            fs::create_dir_all(res_dir.join(dir)).unwrap();
+        }
+
+        fs::write(
+            res_dir.join("layout").join("activity_main.xml"),
+            r#"<LinearLayout><include layout="@layout/view_header" /></LinearLayout>"#,
+        )
+        .unwrap();
+        fs::write(res_dir.join("layout").join("view_header.xml"), "<View />").unwrap();
+        fs::write(res_dir.join("layout").join("item_unused.xml"), "<View />").unwrap();
+        fs::write(res_dir.join("menu").join("main_menu.xml"), "<menu />").unwrap();
+        fs::write(
+            res_dir.join("navigation").join("nav_graph.xml"),
+            "<navigation />",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("MainActivity.kt"),
+            r#"
+class MainActivity {
+    val binding = ActivityMainBinding.inflate(layoutInflater)
+    fun onCreateOptionsMenu() = menuInflater.inflate(R.menu.main_menu, menu)
+}
+"#,
+        )
+        .unwrap();
+
+        let analysis = ResourceDetector::new().analyze(temp_dir.path());
+        let unused: HashSet<_> = analysis
+            .unused
+            .iter()
+            .map(|r| format!("{}/{}", r.resource_type, r.name))
+            .collect();
+
+        assert!(unused.contains("layout/item_unused"));
+        assert!(unused.contains("navigation/nav_graph"));
+        assert!(!unused.contains("layout/activity_main"));
+        assert!(!unused.contains("layout/view_header"));
+        assert!(!unused.contains("menu/main_menu"));
+    }
+
+    #[test]
+    fn test_variant_and_overlay_indexing() {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("resources")
+            .tempdir()
+            .unwrap();
+        let src = temp_dir.path().join("app").join("src");
+        let write = |dir: &str, content: &str| {
+            let dir = src.join(dir);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("colors.xml"), content).unwrap();
+        };
+        write(
+            "main/res/values",
+            r#"<resources><color name="surface">#FFF</color></resources>"#,
+        );
+        write(
+            "main/res/values-night-v31",
+            r#"<resources>
+    <color name="surface">#000</color>
+    <color name="night_only">#111</color>
+</resources>"#,
+        );
+        write(
+            "debug/res/values",
+            r#"<resources><color name="debug_banner">#F00</color></resources>"#,
+        );
+        fs::write(temp_dir.path().join("Theme.kt"), "val c = R.color.surface").unwrap();
+
+        let analysis = ResourceDetector::new().analyze(temp_dir.path());
+
+        let surface = &analysis.defined["color"]["surface"];
+        assert!(surface.variant.is_default());
+        assert_eq!(surface.overlays.len(), 1);
+        assert_eq!(surface.overlays[0].variant.qualifiers, vec!["night", "v31"]);
+
+        let unused: HashMap<_, _> = analysis
+            .unused
+            .iter()
+            .map(|r| (r.name.as_str(), r.variant.label()))
+            .collect();
+        assert_eq!(unused.len(), 2);
+        assert!(!unused.contains_key("surface"));
+        assert_eq!(unused["night_only"].as_deref(), Some("main, night, v31"));
+        assert_eq!(unused["debug_banner"].as_deref(), Some("debug"));
+    }
+
+    #[test]
+    fn test_resource_reference_graph() {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("resources")
+            .tempdir()
+            .unwrap();
+        let main = temp_dir.path().join("app").join("src").join("main");
+        let write = |dir: &str, file: &str, content: &str| {
+            let dir = main.join("res").join(dir);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join(file), content).unwrap();
+        };
+        write(
+            "values",
+            "strings.xml",
+            r#"<resources>
+    <string name="choice_a">A</string>
+    <string name="choice_b">B</string>
+    <string-array name="old_choices">
+        <item>@string/choice_a</item>
+    </string-array>
+    <string-array name="choices">
+        <item>@string/choice_b</item>
+    </string-array>
+    <plurals name="photos">
+        <item quantity="one">@string/choice_b</item>
+        <item quantity="other">%d photos</item>
+    </plurals>
+    <style name="AppTheme.Dark">
+        <item name="android:textColor">@color/text</item>
+    </style>
+</resources>"#,
+        );
+        write(
+            "values",
+            "colors.xml",
+            r#"<resources><color name="text">#000</color></resources>"#,
+        );
+        write(
+            "values-fr-rCA",
+            "strings.xml",
+            r#"<resources>
+    <string name="choice_a">A</string>
+    <string name="removed_label">Ancien</string>
+</resources>"#,
+        );
+        fs::write(
+            main.join("AndroidManifest.xml"),
+            r#"<manifest><application android:theme="@style/AppTheme.Dark" /></manifest>"#,
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("Main.kt"), "val c = R.array.choices").unwrap();
+
+        let analysis = ResourceDetector::new().analyze(temp_dir.path());
+        let unused: HashMap<_, _> = analysis
+            .unused
+            .iter()
+            .map(|r| (format!("{}/{}", r.resource_type, r.name), r))
+            .collect();
+        let mut names: Vec<&str> = unused.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "array/old_choices",
+                "plurals/photos",
+                "string/choice_a",
+                "string/removed_label"
+            ]
+        );
+
+        let choice_a = unused["string/choice_a"];
+        assert_eq!(
+            analysis.unused_referrers(choice_a),
+            vec![&("array".to_string(), "old_choices".to_string())]
+        );
+        assert!(!choice_a.is_locale_only());
+        assert!(unused["string/removed_label"].is_locale_only());
+    }
+
+    #[test]
+    fn test_file_resources_and_assets() {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("resources")
+            .tempdir()
+            .unwrap();
+        let main = temp_dir.path().join("app").join("src").join("main");
+        let write = |path: &str, content: &[u8]| {
+            let path = main.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write("res/drawable/ic_used.png", &[0; 10]);
+        write("res/drawable-hdpi/ic_unused.png", &[0; 100]);
+        write("res/drawable-xhdpi/ic_unused.png", &[0; 200]);
+        write(
+            "res/drawable/bg_card.xml",
+            br#"<layer-list><item android:drawable="@drawable/ic_nested" /></layer-list>"#,
+        );
+        write("res/drawable/ic_nested.9.png", &[0; 10]);
+        write("res/raw/intro.mp3", &[0; 10]);
+        write("res/font/inter.ttf", &[0; 10]);
+        write("assets/data/cities.json", b"[]");
+        write("assets/images/flag_fr.png", &[0; 10]);
+        write("assets/help.html", b"<html />");
+        write("assets/legacy/old.html", b"<html />");
+        fs::write(
+            temp_dir.path().join("Main.kt"),
+            r#"
+val icon = R.drawable.ic_used
+val sound = R.raw.intro
+val cities = assets.open("data/cities.json")
+fun flag(country: String) = assets.open("images/flag_$country.png")
+val help = "file:///android_asset/help.html"
+"#,
+        )
+        .unwrap();
+
+        let analysis = ResourceDetector::new().analyze(temp_dir.path());
+        let unused: HashMap<_, _> = analysis
+            .unused
+            .iter()
+            .map(|r| (format!("{}/{}", r.resource_type, r.name), r))
+            .collect();
+        let mut names: Vec<&str> = unused.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "asset/legacy/old.html",
+                "drawable/bg_card",
+                "drawable/ic_nested",
+                "drawable/ic_unused",
+                "font/inter"
+            ]
+        );
+        assert_eq!(unused["drawable/ic_unused"].total_size(), Some(300));
+        assert_eq!(
+            analysis.unused_referrers(unused["drawable/ic_nested"]),
+            vec![&("drawable".to_string(), "bg_card".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_styles_applied_and_attrs_read() {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("resources")
+            .tempdir()
+            .unwrap();
+        let main = temp_dir.path().join("app").join("src").join("main");
+        let write = |dir: &str, file: &str, content: &str| {
+            let dir = main.join("res").join(dir);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join(file), content).unwrap();
+        };
+        write(
+            "values",
+            "themes.xml",
+            r#"<resources>
+    <style name="Theme.App" parent="Theme.MaterialComponents">
+        <item name="colorPrimary">?attr/brandColor</item>
+        <item name="cardRadius">8dp</item>
+    </style>
+    <style name="Theme.App.Dialog" />
+    <style name="Theme.Legacy" />
+    <style name="Widget.Card" />
+    <style name="Widget.Chip" />
+</resources>"#,
+        );
+        write(
+            "values",
+            "attrs.xml",
+            r#"<resources>
+    <attr name="brandColor" format="color" />
+    <declare-styleable name="RoundedView">
+        <attr name="cardRadius" format="dimension" />
+        <attr name="cardElevation" format="dimension" />
+        <attr name="android:textColor" />
+    </declare-styleable>
+</resources>"#,
+        );
+        write(
+            "layout",
+            "activity_main.xml",
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<RoundedView style="@style/Widget.Card" app:cardElevation="2dp"
+    android:textColor="?android:attr/textColorPrimary" />"#,
+        );
+        fs::write(
+            main.join("AndroidManifest.xml"),
+            r#"<manifest><application android:theme="@style/Theme.App" /></manifest>"#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("RoundedView.kt"),
+            r#"
+setContentView(R.layout.activity_main)
+val dialog = ContextThemeWrapper(context, R.style.Theme_App_Dialog)
+val a = context.obtainStyledAttributes(attrs, R.styleable.RoundedView)
+val radius = a.getDimension(R.styleable.RoundedView_cardRadius, 0f)
+"#,
+        )
+        .unwrap();
+
+        let analysis = ResourceDetector::new().analyze(temp_dir.path());
+        let mut names: Vec<String> = analysis
+            .unused
+            .iter()
+            .map(|r| format!("{}/{}", r.resource_type, r.name))
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "attr/cardElevation",
+                "style/Theme.Legacy",
+                "style/Widget.Chip"
+            ]
+        );
+    }
 }
@@ -0,0 +1,160 @@
+//! False positive risk tags
+//!
+//! Each tag names a way a declaration can be used without a reference in the
+//! code graph. Tags are derived from declaration metadata, plus how calls to
+//! the declaration were resolved, so they say how much to trust a finding
+//! rather than whether it is dead.
+
+use super::visibility_hints::is_library_restricted;
+use crate::graph::{simple_type_name, Declaration, Visibility};
+
+/// Instantiated or accessed through reflection (serializers, Parcelable, @Keep)
+pub const REFLECTION_PRONE: &str = "reflection-prone";
+
+/// Carries an annotation processed by a framework (DI, event buses, Compose)
+pub const FRAMEWORK_ANNOTATED: &str = "framework-annotated";
+
+/// Public and therefore usable from modules that were not analyzed (unless
+/// `@RestrictTo` keeps other libraries out)
+pub const PUBLIC_API: &str = "public-api";
+
+/// Exposed to Java callers through a `@Jvm*` annotation
+pub const KOTLIN_JAVA_BRIDGE: &str = "kotlin-java-bridge";
+
+/// Calls with its name were resolved to another overload by argument count
+pub const OVERLOAD_RESOLUTION: &str = "overload-resolution";
+
+/// Linked from KDoc or Javadoc, which may still describe it as in use
+pub const DOC_LINKED: &str = "doc-linked";
+
+/// All risk tags, in the order they are reported
+pub const RISK_TAGS: &[&str] = &[
+    REFLECTION_PRONE,
+    FRAMEWORK_ANNOTATED,
+    PUBLIC_API,
+    KOTLIN_JAVA_BRIDGE,
+    OVERLOAD_RESOLUTION,
+    DOC_LINKED,
+];
+
+const REFLECTION_ANNOTATIONS: &[&str] = &[
+    "Keep",
+    "SerializedName",
+    "Expose",
+    "Json",
+    "JsonClass",
+    "JsonProperty",
+    "Serializable",
+    "SerialName",
+    "Parcelize",
+    "Entity",
+    "ColumnInfo",
+];
+
+const REFLECTION_SUPER_TYPES: &[&str] = &["Serializable", "Parcelable", "Externalizable"];
+
+const FRAMEWORK_ANNOTATIONS: &[&str] = &[
+    "Inject",
+    "Provides",
+    "Binds",
+    "BindsInstance",
+    "IntoMap",
+    "IntoSet",
+    "Module",
+    "Component",
+    "Subcomponent",
+    "AndroidEntryPoint",
+    "HiltAndroidApp",
+    "HiltViewModel",
+    "EntryPoint",
+    "Subscribe",
+    "BindingAdapter",
+    "InverseBindingAdapter",
+    "JavascriptInterface",
+    "Composable",
+    "Preview",
+    "OnLifecycleEvent",
+    "Query",
+    "Insert",
+    "Update",
+    "Delete",
+    "GET",
+    "POST",
+    "PUT",
+    "PATCH",
+    "DELETE",
+];
+
+/// Risk tags for a declaration reported as dead
+pub fn risk_tags(decl: &Declaration) -> Vec<String> {
+    let annotations: Vec<&str> = decl
+        .annotations
+        .iter()
+        .map(|a| simple_type_name(a))
+        .collect();
+    let has_annotation = |names: &[&str]| annotations.iter().any(|a| names.contains(a));
+
+    let reflection_prone = has_annotation(REFLECTION_ANNOTATIONS)
+        || decl
+            .super_types
+            .iter()
+            .any(|s| REFLECTION_SUPER_TYPES.contains(&simple_type_name(s)));
+    let framework_annotated = has_annotation(FRAMEWORK_ANNOTATIONS);
+    let public_api = decl.visibility == Visibility::Public && !is_library_restricted(decl);
+    let bridge = annotations.iter().any(|a| a.starts_with("Jvm"));
+
+    [
+        (REFLECTION_PRONE, reflection_prone),
+        (FRAMEWORK_ANNOTATED, framework_annotated),
+        (PUBLIC_API, public_api),
+        (KOTLIN_JAVA_BRIDGE, bridge),
+    ]
+    .into_iter()
+    .filter(|(_, applies)| *applies)
+    .map(|(tag, _)| tag.to_string())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, DeclarationKind, Language, Location};
+    use std::path::PathBuf;
+
+    fn declaration(annotations: &[&str], visibility: Visibility) -> Declaration {
+        let path = PathBuf::from("Model.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), 0, 10),
+            "field".to_string(),
+            DeclarationKind::Property,
+            Location::new(path, 1, 1, 0, 10),
+            Language::Kotlin,
+        );
+        decl.annotations = annotations.iter().map(|a| a.to_string()).collect();
+        decl.visibility = visibility;
+        decl
+    }
+
+    #[test]
+    fn test_risk_tags() {
+        let decl = declaration(
+            &["@SerializedName(\"id\")", "@JvmField"],
+            Visibility::Public,
+        );
+        assert_eq!(
+            risk_tags(&decl),
+            vec![REFLECTION_PRONE, PUBLIC_API, KOTLIN_JAVA_BRIDGE]
+        );
+
+        let decl = declaration(
+            &["@RestrictTo(RestrictTo.Scope.LIBRARY_GROUP)"],
+            Visibility::Public,
+        );
+        assert!(risk_tags(&decl).is_empty());
+
+        let decl = declaration(&["@javax.inject.Inject"], Visibility::Private);
+        assert_eq!(risk_tags(&decl), vec![FRAMEWORK_ANNOTATED]);
+
+        assert!(risk_tags(&declaration(&[], Visibility::Internal)).is_empty());
+    }
+}
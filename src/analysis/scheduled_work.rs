@@ -0,0 +1,259 @@
+//! Scheduled work
+//!
+//! WorkManager workers and JobScheduler jobs are instantiated by the system,
+//! but only after something schedules them. Rather than keeping every
+//! subclass alive, the call sites that schedule them are found in the sources:
+//!
+//! - `OneTimeWorkRequestBuilder<SyncWorker>()`, `PeriodicWorkRequestBuilder<SyncWorker>(...)`
+//! - `OneTimeWorkRequest.Builder(SyncWorker::class.java)`, `OneTimeWorkRequest.from(SyncWorker.class)`
+//! - `JobInfo.Builder(id, ComponentName(context, CleanupJob::class.java))`
+//! - `JobIntentService.enqueueWork(context, UploadService::class.java, id, intent)`
+//! - `Intent(context, AlarmReceiver::class.java)` in files using AlarmManager
+//!
+//! A worker or job without any such call site never runs.
+
+use crate::discovery::SourceFile;
+use crate::graph::Declaration;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// API that schedules a component to be started by the system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchedulingApi {
+    WorkManager,
+    JobScheduler,
+    AlarmManager,
+}
+
+impl SchedulingApi {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SchedulingApi::WorkManager => "WorkManager",
+            SchedulingApi::JobScheduler => "JobScheduler",
+            SchedulingApi::AlarmManager => "AlarmManager",
+        }
+    }
+
+    /// API that has to schedule a class before it runs, by its supertypes:
+    /// WorkManager for workers, JobScheduler for job services
+    pub fn required_by(decl: &Declaration) -> Option<Self> {
+        decl.super_types.iter().find_map(|super_type| {
+            let name = super_type.split(['<', '(']).next().unwrap_or(super_type);
+            match name.trim().rsplit('.').next().unwrap_or(name) {
+                "Worker"
+                | "CoroutineWorker"
+                | "ListenableWorker"
+                | "RxWorker"
+                | "RemoteCoroutineWorker"
+                | "RemoteListenableWorker" => Some(SchedulingApi::WorkManager),
+                "JobService" | "JobIntentService" => Some(SchedulingApi::JobScheduler),
+                _ => None,
+            }
+        })
+    }
+}
+
+/// Call site scheduling a class
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleSite {
+    pub api: SchedulingApi,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// Schedule call sites found in the analyzed sources
+#[derive(Debug, Default)]
+pub struct ScheduledWork {
+    /// Call sites by simple name of the scheduled class
+    sites: HashMap<String, Vec<ScheduleSite>>,
+}
+
+impl ScheduledWork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scan Kotlin and Java sources for schedule call sites
+    pub fn scan(files: &[SourceFile]) -> Self {
+        let scanned: Vec<(PathBuf, String)> = files
+            .par_iter()
+            .filter(|file| file.file_type.is_source())
+            .filter_map(|file| Some((file.path.clone(), file.read_contents().ok()?)))
+            .collect();
+
+        let mut work = Self::new();
+        for (path, contents) in scanned {
+            work.add_source(&contents, &path);
+        }
+        work
+    }
+
+    /// Record the schedule call sites of a source file
+    pub fn add_source(&mut self, source: &str, file: &Path) {
+        let uses_job_scheduler = source.contains("JobInfo");
+        let uses_alarm_manager = source.contains("AlarmManager");
+
+        for (line_num, line) in source.lines().enumerate() {
+            let mut found = Vec::new();
+
+            // OneTimeWorkRequestBuilder<SyncWorker>()
+            for (idx, _) in line.match_indices("WorkRequestBuilder<") {
+                let rest = &line[idx + "WorkRequestBuilder<".len()..];
+                let class = rest.split(['>', ',']).next().unwrap_or_default();
+                found.push((SchedulingApi::WorkManager, simple_name(class)));
+            }
+
+            let calls = [
+                ("WorkRequest.Builder(", SchedulingApi::WorkManager, true),
+                ("WorkRequest.from(", SchedulingApi::WorkManager, true),
+                ("enqueueWork(", SchedulingApi::JobScheduler, true),
+                (
+                    "ComponentName(",
+                    SchedulingApi::JobScheduler,
+                    uses_job_scheduler,
+                ),
+                ("Intent(", SchedulingApi::AlarmManager, uses_alarm_manager),
+            ];
+            for (pattern, api, applies) in calls {
+                if !applies {
+                    continue;
+                }
+                for (idx, _) in line.match_indices(pattern) {
+                    if let Some(class) = first_class_literal(&line[idx + pattern.len()..]) {
+                        found.push((api, class));
+                    }
+                }
+            }
+
+            for (api, class) in found {
+                if class.is_empty() {
+                    continue;
+                }
+                self.sites
+                    .entry(class.to_string())
+                    .or_default()
+                    .push(ScheduleSite {
+                        api,
+                        file: file.to_path_buf(),
+                        line: line_num + 1,
+                    });
+            }
+        }
+    }
+
+    /// Call sites scheduling a class
+    pub fn sites(&self, decl: &Declaration) -> &[ScheduleSite] {
+        self.sites.get(&decl.name).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sites.is_empty()
+    }
+}
+
+/// Last segment of a possibly qualified class name
+fn simple_name(class: &str) -> &str {
+    let class = class.trim();
+    class.rsplit('.').next().unwrap_or(class)
+}
+
+/// First class literal of a call's arguments: `SyncWorker::class.java`
+/// (Kotlin) or `SyncWorker.class` (Java)
+fn first_class_literal(args: &str) -> Option<&str> {
+    args.split(',').take(4).find_map(|arg| {
+        let arg = arg.split(')').next().unwrap_or(arg).trim();
+        let class = arg
+            .strip_suffix("::class.java")
+            .or_else(|| arg.strip_suffix("::class"))
+            .or_else(|| arg.strip_suffix(".class"))?;
+        let class = simple_name(class);
+        class
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_')
+            .then_some(class)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, DeclarationKind, Language, Location};
+
+    fn class(name: &str, super_type: &str) -> Declaration {
+        let path = PathBuf::from(format!("{}.kt", name));
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), 0, 10),
+            name.to_string(),
+            DeclarationKind::Class,
+            Location::new(path, 1, 1, 0, 10),
+            Language::Kotlin,
+        );
+        decl.super_types.push(super_type.to_string());
+        decl
+    }
+
+    #[test]
+    fn test_required_by() {
+        assert_eq!(
+            SchedulingApi::required_by(&class("SyncWorker", "CoroutineWorker(context, params)")),
+            Some(SchedulingApi::WorkManager)
+        );
+        assert_eq!(
+            SchedulingApi::required_by(&class("CleanupJob", "android.app.job.JobService")),
+            Some(SchedulingApi::JobScheduler)
+        );
+        assert_eq!(
+            SchedulingApi::required_by(&class("PlayerService", "Service")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_schedule_sites() {
+        let source = r#"
+            fun schedule(context: Context) {
+                val sync = OneTimeWorkRequestBuilder<SyncWorker>().build()
+                val digest = PeriodicWorkRequestBuilder<com.example.DigestWorker>(1, TimeUnit.DAYS)
+                val legacy = OneTimeWorkRequest.Builder(LegacyWorker::class.java).build()
+                val job = JobInfo.Builder(1, ComponentName(requireContext(), CleanupJob::class.java))
+                JobIntentService.enqueueWork(context, UploadService::class.java, 2, intent)
+            }
+        "#;
+        let mut work = ScheduledWork::new();
+        work.add_source(source, Path::new("Scheduler.kt"));
+
+        for name in ["SyncWorker", "DigestWorker", "LegacyWorker"] {
+            let sites = work.sites(&class(name, "Worker"));
+            assert_eq!(sites.len(), 1, "{}", name);
+            assert_eq!(sites[0].api, SchedulingApi::WorkManager);
+        }
+        let sites = work.sites(&class("CleanupJob", "JobService"));
+        assert_eq!(sites[0].api, SchedulingApi::JobScheduler);
+        assert_eq!(sites[0].line, 6);
+        assert_eq!(
+            work.sites(&class("UploadService", "JobIntentService"))
+                .len(),
+            1
+        );
+        assert!(work.sites(&class("UnusedWorker", "Worker")).is_empty());
+    }
+
+    #[test]
+    fn test_java_and_alarm_sites() {
+        let source = r#"
+            AlarmManager alarms = context.getSystemService(AlarmManager.class);
+            Intent intent = new Intent(context, ReminderReceiver.class);
+            WorkRequest request = OneTimeWorkRequest.from(ExportWorker.class);
+            ComponentName name = new ComponentName(context, MainActivity.class);
+        "#;
+        let mut work = ScheduledWork::new();
+        work.add_source(source, Path::new("Scheduler.java"));
+
+        let sites = work.sites(&class("ReminderReceiver", "BroadcastReceiver"));
+        assert_eq!(sites[0].api, SchedulingApi::AlarmManager);
+        assert_eq!(work.sites(&class("ExportWorker", "Worker")).len(), 1);
+        // ComponentName only schedules work in files using JobScheduler
+        assert!(work.sites(&class("MainActivity", "Activity")).is_empty());
+    }
+}
@@ -0,0 +1,301 @@
+//! Confidence scoring
+//!
+//! A finding's confidence is the sum of weighted evidence: facts that argue
+//! for or against the declaration being dead. The evidence is kept on the
+//! finding so reports can explain why it was flagged.
+
+use super::{is_library_restricted, risk, DeadCode, DeadCodeIssue};
+use crate::graph::{DeclarationKind, Visibility};
+
+/// A kind of evidence, with a fixed weight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvidenceKind {
+    /// Static analysis found no use (the starting point of every score)
+    StaticUnreferenced,
+    /// Private: only its own file can use it
+    Private,
+    /// Internal: only its own module can use it
+    Internal,
+    /// `@RestrictTo` its library or group: other libraries cannot use it
+    RestrictedApi,
+    /// Parameters and imports cannot be used dynamically
+    NotDynamicallyUsable,
+    /// No reflection or framework annotations or super types
+    NoReflectionMarkers,
+    /// See [`risk::REFLECTION_PRONE`]
+    ReflectionProne,
+    /// See [`risk::FRAMEWORK_ANNOTATED`]
+    FrameworkAnnotated,
+    /// See [`risk::PUBLIC_API`]
+    PublicApi,
+    /// See [`risk::KOTLIN_JAVA_BRIDGE`]
+    KotlinJavaBridge,
+    /// See [`risk::OVERLOAD_RESOLUTION`]
+    OverloadResolution,
+    /// See [`risk::DOC_LINKED`]
+    DocLinked,
+    /// Its file or package uses reflection (see [`super::reflection`])
+    ReflectionUsage,
+    /// Suppressed inline (see [`super::suppression`])
+    Suppressed,
+    /// Keep rules were loaded and none retains the declaration
+    NoKeepRule,
+    /// A keep rule retains the declaration
+    KeepRule,
+    /// Runtime coverage shows the declaration never ran
+    NotExecuted,
+    /// Runtime coverage shows the declaration ran
+    Executed,
+    /// Runtime coverage shows parts of the declaration ran
+    PartiallyExecuted,
+    /// R8/ProGuard usage.txt lists the declaration as removed
+    RemovedByProguard,
+    /// R8/ProGuard usage.txt lists a declaration with the same name
+    ProguardNameMatch,
+    /// The compiled classes do not reach the declaration either
+    BytecodeUnreachable,
+    /// The compiled classes reach the declaration; the source graph may have
+    /// missed a reference
+    BytecodeReachable,
+}
+
+impl EvidenceKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EvidenceKind::StaticUnreferenced => "static-unreferenced",
+            EvidenceKind::Private => "private",
+            EvidenceKind::Internal => "internal",
+            EvidenceKind::RestrictedApi => "restricted-api",
+            EvidenceKind::NotDynamicallyUsable => "not-dynamically-usable",
+            EvidenceKind::NoReflectionMarkers => "no-reflection-markers",
+            EvidenceKind::ReflectionProne => risk::REFLECTION_PRONE,
+            EvidenceKind::FrameworkAnnotated => risk::FRAMEWORK_ANNOTATED,
+            EvidenceKind::PublicApi => risk::PUBLIC_API,
+            EvidenceKind::KotlinJavaBridge => risk::KOTLIN_JAVA_BRIDGE,
+            EvidenceKind::OverloadResolution => risk::OVERLOAD_RESOLUTION,
+            EvidenceKind::DocLinked => risk::DOC_LINKED,
+            EvidenceKind::ReflectionUsage => "reflection-usage",
+            EvidenceKind::Suppressed => "suppressed",
+            EvidenceKind::NoKeepRule => "no-keep-rule",
+            EvidenceKind::KeepRule => "keep-rule",
+            EvidenceKind::NotExecuted => "not-executed",
+            EvidenceKind::Executed => "executed",
+            EvidenceKind::PartiallyExecuted => "partially-executed",
+            EvidenceKind::RemovedByProguard => "removed-by-proguard",
+            EvidenceKind::ProguardNameMatch => "proguard-name-match",
+            EvidenceKind::BytecodeUnreachable => "bytecode-unreachable",
+            EvidenceKind::BytecodeReachable => "bytecode-reachable",
+        }
+    }
+
+    /// Contribution to the score; negative when the code may still be used
+    pub fn weight(&self) -> f64 {
+        match self {
+            EvidenceKind::StaticUnreferenced => 0.5,
+            EvidenceKind::Private => 0.25,
+            EvidenceKind::Internal => 0.05,
+            EvidenceKind::RestrictedApi => 0.05,
+            EvidenceKind::NotDynamicallyUsable => 0.25,
+            EvidenceKind::NoReflectionMarkers => 0.1,
+            EvidenceKind::ReflectionProne => -0.2,
+            EvidenceKind::FrameworkAnnotated => -0.2,
+            EvidenceKind::PublicApi => -0.1,
+            EvidenceKind::KotlinJavaBridge => -0.05,
+            EvidenceKind::OverloadResolution => -0.15,
+            EvidenceKind::DocLinked => -0.1,
+            EvidenceKind::ReflectionUsage => -0.2,
+            EvidenceKind::Suppressed => -0.3,
+            EvidenceKind::NoKeepRule => 0.05,
+            EvidenceKind::KeepRule => -0.4,
+            EvidenceKind::NotExecuted => 0.6,
+            EvidenceKind::Executed => -0.6,
+            EvidenceKind::PartiallyExecuted => -0.1,
+            EvidenceKind::RemovedByProguard => 0.6,
+            EvidenceKind::ProguardNameMatch => 0.2,
+            EvidenceKind::BytecodeUnreachable => 0.3,
+            EvidenceKind::BytecodeReachable => -0.5,
+        }
+    }
+}
+
+/// A fact that raises or lowers the confidence in a finding
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evidence {
+    pub kind: EvidenceKind,
+    /// Explanation shown in reports
+    pub description: String,
+}
+
+impl Evidence {
+    pub fn new(kind: EvidenceKind, description: impl Into<String>) -> Self {
+        Self {
+            kind,
+            description: description.into(),
+        }
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.kind.weight()
+    }
+}
+
+/// Score from 0.0 to 1.0: the sum of the evidence weights
+pub fn score(evidence: &[Evidence]) -> f64 {
+    evidence
+        .iter()
+        .map(Evidence::weight)
+        .sum::<f64>()
+        .clamp(0.0, 1.0)
+}
+
+/// Evidence available from the finding itself: the static analysis result,
+/// the declaration's visibility and kind, and its risk tags
+pub fn static_evidence(dc: &DeadCode) -> Vec<Evidence> {
+    let decl = &dc.declaration;
+    let finding = match dc.issue {
+        DeadCodeIssue::Unreferenced => "Not reachable from any entry point".to_string(),
+        DeadCodeIssue::OnlyUsedInTests => "Only referenced from test source sets".to_string(),
+        DeadCodeIssue::OnlyUsedInPreviews => {
+            "Only referenced from previews and test source sets".to_string()
+        }
+        DeadCodeIssue::CustomRule => "Matches a user-defined rule".to_string(),
+        DeadCodeIssue::DeadFile => "No declaration in the file is reachable".to_string(),
+        DeadCodeIssue::EffectivelyDeadObject => {
+            "Only used as a namespace; no member is used".to_string()
+        }
+        issue => format!("Flagged by static analysis ({})", issue.code()),
+    };
+    let mut evidence = vec![Evidence::new(EvidenceKind::StaticUnreferenced, finding)];
+
+    match decl.visibility {
+        Visibility::Private => evidence.push(Evidence::new(
+            EvidenceKind::Private,
+            "Private, so only its own file can use it",
+        )),
+        Visibility::Internal => evidence.push(Evidence::new(
+            EvidenceKind::Internal,
+            "Internal, so only its own module can use it",
+        )),
+        _ => {}
+    }
+    if is_library_restricted(decl) {
+        evidence.push(Evidence::new(
+            EvidenceKind::RestrictedApi,
+            "Restricted to its library or group with @RestrictTo",
+        ));
+    }
+    if matches!(
+        decl.kind,
+        DeclarationKind::Parameter | DeclarationKind::Import
+    ) {
+        evidence.push(Evidence::new(
+            EvidenceKind::NotDynamicallyUsable,
+            format!("A {} cannot be used dynamically", decl.kind.display_name()),
+        ));
+    }
+
+    if !dc.has_risk_tag(risk::REFLECTION_PRONE) && !dc.has_risk_tag(risk::FRAMEWORK_ANNOTATED) {
+        evidence.push(Evidence::new(
+            EvidenceKind::NoReflectionMarkers,
+            "No reflection or framework markers",
+        ));
+    }
+    for tag in &dc.risk_tags {
+        let (kind, description) = match tag.as_str() {
+            risk::REFLECTION_PRONE => (
+                EvidenceKind::ReflectionProne,
+                "May be instantiated or accessed through reflection",
+            ),
+            risk::FRAMEWORK_ANNOTATED => (
+                EvidenceKind::FrameworkAnnotated,
+                "Carries an annotation processed by a framework",
+            ),
+            risk::PUBLIC_API => (
+                EvidenceKind::PublicApi,
+                "Public, so modules that were not analyzed may use it",
+            ),
+            risk::KOTLIN_JAVA_BRIDGE => (
+                EvidenceKind::KotlinJavaBridge,
+                "Exposed to Java callers through a @Jvm annotation",
+            ),
+            risk::OVERLOAD_RESOLUTION => (
+                EvidenceKind::OverloadResolution,
+                "Calls with its name were matched to another overload by argument count",
+            ),
+            risk::DOC_LINKED => (
+                EvidenceKind::DocLinked,
+                "Linked from documentation, which may still describe it as in use",
+            ),
+            _ => continue,
+        };
+        evidence.push(Evidence::new(kind, description));
+    }
+
+    evidence
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::Confidence;
+    use crate::graph::{Declaration, DeclarationId, Language, Location};
+    use std::path::PathBuf;
+
+    fn finding(kind: DeclarationKind, visibility: Visibility, annotations: &[&str]) -> DeadCode {
+        let path = PathBuf::from("Api.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), 0, 10),
+            "api".to_string(),
+            kind,
+            Location::new(path, 1, 1, 0, 10),
+            Language::Kotlin,
+        );
+        decl.visibility = visibility;
+        decl.annotations = annotations.iter().map(|a| a.to_string()).collect();
+        DeadCode::new(decl, DeadCodeIssue::Unreferenced)
+    }
+
+    fn kinds(evidence: &[Evidence]) -> Vec<EvidenceKind> {
+        evidence.iter().map(|e| e.kind).collect()
+    }
+
+    #[test]
+    fn test_static_evidence() {
+        let private = static_evidence(&finding(DeclarationKind::Method, Visibility::Private, &[]));
+        assert_eq!(
+            kinds(&private),
+            vec![
+                EvidenceKind::StaticUnreferenced,
+                EvidenceKind::Private,
+                EvidenceKind::NoReflectionMarkers
+            ]
+        );
+        assert_eq!(Confidence::from_score(score(&private)), Confidence::High);
+
+        let public = static_evidence(&finding(DeclarationKind::Class, Visibility::Public, &[]));
+        assert_eq!(Confidence::from_score(score(&public)), Confidence::Medium);
+
+        let injected = finding(DeclarationKind::Class, Visibility::Public, &["@Inject"]);
+        let injected = static_evidence(&injected);
+        assert_eq!(
+            kinds(&injected),
+            vec![
+                EvidenceKind::StaticUnreferenced,
+                EvidenceKind::FrameworkAnnotated,
+                EvidenceKind::PublicApi
+            ]
+        );
+        assert_eq!(Confidence::from_score(score(&injected)), Confidence::Low);
+    }
+
+    #[test]
+    fn test_score_is_clamped() {
+        let evidence = vec![
+            Evidence::new(EvidenceKind::StaticUnreferenced, ""),
+            Evidence::new(EvidenceKind::NotExecuted, ""),
+            Evidence::new(EvidenceKind::RemovedByProguard, ""),
+        ];
+        assert_eq!(score(&evidence), 1.0);
+        assert_eq!(score(&[Evidence::new(EvidenceKind::Executed, "")]), 0.0);
+    }
+}
@@ -0,0 +1,175 @@
+//! Deletion simulation
+//!
+//! Answers "what else becomes dead if I delete X?" before anything is
+//! deleted: the targets and everything nested in them are removed from a
+//! copy of the graph, reachability runs again, and the declarations it now
+//! reports that the unchanged graph did not are the cascade of the deletion.
+
+use super::{DeadCode, MemberRetention, ReachabilityAnalyzer};
+use crate::graph::{DeclarationId, Graph};
+use std::collections::HashSet;
+
+/// Outcome of deleting a set of declarations
+#[derive(Debug)]
+pub struct DeletionSimulation {
+    /// The targets and the declarations nested in them, in source order
+    pub removed: Vec<DeclarationId>,
+    /// Declarations alive before the deletion that become dead code after it
+    pub newly_dead: Vec<DeadCode>,
+}
+
+/// Runs reachability with and without a set of declarations
+pub struct DeletionSimulator {
+    retention: MemberRetention,
+}
+
+impl DeletionSimulator {
+    pub fn new() -> Self {
+        Self {
+            retention: MemberRetention::default(),
+        }
+    }
+
+    /// Member retention policy used by both reachability runs
+    pub fn with_member_retention(mut self, retention: MemberRetention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Delete `targets` and report the declarations that become dead
+    pub fn simulate(
+        &self,
+        graph: &Graph,
+        entry_points: &HashSet<DeclarationId>,
+        targets: &[DeclarationId],
+    ) -> DeletionSimulation {
+        let removed = with_descendants(graph, targets);
+        let removed_set: HashSet<DeclarationId> = removed.iter().cloned().collect();
+
+        let analyzer = ReachabilityAnalyzer::new().with_member_retention(self.retention.clone());
+        let before: HashSet<DeclarationId> = analyzer
+            .find_unreachable(graph, entry_points)
+            .into_iter()
+            .map(|dc| dc.declaration.id)
+            .collect();
+
+        let pruned = graph.without(&removed_set);
+        let remaining_entry_points: HashSet<DeclarationId> =
+            entry_points.difference(&removed_set).cloned().collect();
+        let newly_dead = analyzer
+            .find_unreachable(&pruned, &remaining_entry_points)
+            .into_iter()
+            .filter(|dc| !before.contains(&dc.declaration.id))
+            .collect();
+
+        DeletionSimulation {
+            removed,
+            newly_dead,
+        }
+    }
+}
+
+impl Default for DeletionSimulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The targets and every declaration nested in them, deduplicated and in
+/// source order
+fn with_descendants(graph: &Graph, targets: &[DeclarationId]) -> Vec<DeclarationId> {
+    let mut seen: HashSet<&DeclarationId> = HashSet::new();
+    let mut stack: Vec<&DeclarationId> = targets.iter().collect();
+    while let Some(id) = stack.pop() {
+        if seen.insert(id) {
+            stack.extend(graph.get_children(id));
+        }
+    }
+    let mut removed: Vec<DeclarationId> = seen.into_iter().cloned().collect();
+    removed.sort_by(|a, b| a.file.cmp(&b.file).then(a.start.cmp(&b.start)));
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        Declaration, DeclarationKind, Language, Location, Reference, ReferenceKind,
+    };
+    use std::path::PathBuf;
+
+    fn declare(
+        graph: &mut Graph,
+        name: &str,
+        kind: DeclarationKind,
+        line: usize,
+        parent: Option<&DeclarationId>,
+    ) -> DeclarationId {
+        let path = PathBuf::from("Checkout.kt");
+        let start = line * 100;
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), start, start + 50),
+            name.to_string(),
+            kind,
+            Location::new(path, line, 1, start, start + 50),
+            Language::Kotlin,
+        );
+        decl.parent = parent.cloned();
+        graph.add_declaration(decl)
+    }
+
+    fn call(graph: &mut Graph, from: &DeclarationId, to: &DeclarationId) {
+        let location = Location::new(from.file.clone(), 1, 1, from.start, from.end);
+        graph.add_reference(
+            from,
+            to,
+            Reference::new(ReferenceKind::Call, location, String::new()),
+        );
+    }
+
+    #[test]
+    fn test_simulate_delete() {
+        let mut graph = Graph::new();
+        let main = declare(&mut graph, "main", DeclarationKind::Function, 1, None);
+        let screen = declare(&mut graph, "LegacyScreen", DeclarationKind::Class, 5, None);
+        let render = declare(
+            &mut graph,
+            "render",
+            DeclarationKind::Method,
+            6,
+            Some(&screen),
+        );
+        let format = declare(
+            &mut graph,
+            "formatTitle",
+            DeclarationKind::Function,
+            10,
+            None,
+        );
+        let shared = declare(
+            &mut graph,
+            "sharedUtil",
+            DeclarationKind::Function,
+            15,
+            None,
+        );
+        let orphan = declare(&mut graph, "orphan", DeclarationKind::Function, 20, None);
+        call(&mut graph, &main, &screen);
+        call(&mut graph, &main, &shared);
+        call(&mut graph, &render, &format);
+        call(&mut graph, &render, &shared);
+        call(&mut graph, &orphan, &format);
+
+        let entry_points: HashSet<DeclarationId> = [main.clone()].into_iter().collect();
+        let simulation =
+            DeletionSimulator::new().simulate(&graph, &entry_points, std::slice::from_ref(&screen));
+
+        assert_eq!(simulation.removed, vec![screen, render]);
+        let names: Vec<&str> = simulation
+            .newly_dead
+            .iter()
+            .map(|dc| dc.declaration.name.as_str())
+            .collect();
+        assert_eq!(names, ["formatTitle"]);
+    }
+}
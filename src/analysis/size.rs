@@ -0,0 +1,185 @@
+//! Size of findings: the lines and bytes their declarations span
+//!
+//! Declarations parsed from source carry their size; the others are measured
+//! from their byte ranges against the source files, so they only cost a read
+//! of each file with such findings.
+
+use super::{DeadCode, DeadCodeIssue};
+use crate::graph::Location;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Lines and bytes spanned by a finding's declaration
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FindingSize {
+    pub lines: usize,
+    pub bytes: usize,
+}
+
+impl FindingSize {
+    /// Size of the byte range of `location` in `source`
+    pub fn of(location: &Location, source: &str) -> Self {
+        let end = location.end_byte.min(source.len());
+        let start = location.start_byte.min(end);
+        let newlines = source.as_bytes()[start..end]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count();
+
+        Self {
+            lines: newlines + 1,
+            bytes: end - start,
+        }
+    }
+
+    /// Size when the source is unavailable: one line per finding
+    fn estimated(location: &Location) -> Self {
+        Self {
+            lines: 1,
+            bytes: location.end_byte.saturating_sub(location.start_byte),
+        }
+    }
+}
+
+/// Sizes of the findings, in the same order
+pub fn finding_sizes(dead_code: &[DeadCode]) -> Vec<FindingSize> {
+    let mut sources: HashMap<&PathBuf, Option<String>> = HashMap::new();
+    dead_code
+        .iter()
+        .map(|item| {
+            if let Some(size) = item.declaration.size {
+                return FindingSize {
+                    lines: size.lines,
+                    bytes: size.bytes,
+                };
+            }
+            let location = &item.declaration.location;
+            let source = sources
+                .entry(&location.file)
+                .or_insert_with(|| std::fs::read_to_string(&location.file).ok());
+            match source {
+                Some(source) => FindingSize::of(location, source),
+                None => FindingSize::estimated(location),
+            }
+        })
+        .collect()
+}
+
+/// Whether fixing the finding removes its declaration
+pub fn is_deletable(issue: DeadCodeIssue) -> bool {
+    !matches!(
+        issue,
+        DeadCodeIssue::RedundantPublic
+            | DeadCodeIssue::VarCouldBeVal
+            | DeadCodeIssue::UnusedGetter
+            | DeadCodeIssue::UnusedSetter
+            | DeadCodeIssue::DeadDocLink
+            | DeadCodeIssue::UnusedComposableDefault
+    )
+}
+
+/// Total size of the deletable findings, not counting findings nested in
+/// another finding (a dead class and its dead members) twice
+pub fn deletable_size(dead_code: &[DeadCode], sizes: &[FindingSize]) -> FindingSize {
+    let mut ranges: Vec<(&PathBuf, usize, usize, FindingSize)> = dead_code
+        .iter()
+        .zip(sizes)
+        .filter(|(item, _)| is_deletable(item.issue))
+        .map(|(item, size)| {
+            let location = &item.declaration.location;
+            (
+                &location.file,
+                location.start_byte,
+                location.end_byte,
+                *size,
+            )
+        })
+        .collect();
+    // Outer ranges first, so nested ones come right after their parent
+    ranges.sort_by(|a, b| a.0.cmp(b.0).then(a.1.cmp(&b.1)).then(b.2.cmp(&a.2)));
+
+    let mut total = FindingSize::default();
+    let mut outer: Option<(&PathBuf, usize)> = None;
+    for (file, _, end, size) in ranges {
+        if let Some((outer_file, outer_end)) = outer {
+            if outer_file == file && end <= outer_end {
+                continue;
+            }
+        }
+        outer = Some((file, end));
+        total.lines += size.lines;
+        total.bytes += size.bytes;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language};
+
+    fn finding(start: usize, end: usize, issue: DeadCodeIssue) -> DeadCode {
+        let file = PathBuf::from("Cache.kt");
+        let decl = Declaration::new(
+            DeclarationId::new(file.clone(), start, end),
+            "cache".to_string(),
+            DeclarationKind::Class,
+            Location::new(file, 1, 1, start, end),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, issue)
+    }
+
+    #[test]
+    fn test_size_of_range() {
+        let source = "class Cache {\n    fun get() = 1\n}\n";
+        let location = Location::new(PathBuf::from("Cache.kt"), 1, 1, 0, 33);
+        assert_eq!(
+            FindingSize::of(&location, source),
+            FindingSize {
+                lines: 3,
+                bytes: 33
+            }
+        );
+
+        // Ranges past the end of a stale file are clamped
+        let location = Location::new(PathBuf::from("Cache.kt"), 2, 5, 18, 500);
+        assert_eq!(FindingSize::of(&location, source).bytes, 16);
+    }
+
+    #[test]
+    fn test_nested_findings_counted_once() {
+        let dead_code = vec![
+            finding(0, 100, DeadCodeIssue::Unreferenced),
+            finding(20, 40, DeadCodeIssue::Unreferenced),
+            finding(200, 210, DeadCodeIssue::Unreferenced),
+            finding(300, 310, DeadCodeIssue::VarCouldBeVal),
+        ];
+        let sizes = vec![
+            FindingSize {
+                lines: 10,
+                bytes: 100,
+            },
+            FindingSize {
+                lines: 2,
+                bytes: 20,
+            },
+            FindingSize {
+                lines: 1,
+                bytes: 10,
+            },
+            FindingSize {
+                lines: 1,
+                bytes: 10,
+            },
+        ];
+
+        assert_eq!(
+            deletable_size(&dead_code, &sizes),
+            FindingSize {
+                lines: 11,
+                bytes: 110
+            }
+        );
+    }
+}
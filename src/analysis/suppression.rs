@@ -0,0 +1,198 @@
+//! Inline suppressions
+//!
+//! A `// searchdeadcode:ignore` comment directly above a declaration, or one
+//! of the configured annotations on it, suppresses the findings of the
+//! declaration and its members. Depending on the configured action they are
+//! dropped or downgraded; either way the reports count them.
+
+use super::reflection::lower;
+use super::scoring::{self, Evidence, EvidenceKind};
+use super::{Confidence, DeadCode};
+use crate::config::{SuppressionAction, SuppressionConfig};
+use crate::graph::{simple_type_name, Declaration, Graph};
+use crate::parser::IGNORE_COMMENT;
+
+/// What suppresses the declaration's findings, if anything: its own comment
+/// or annotation, or those of an enclosing declaration
+pub fn suppressed_by(
+    decl: &Declaration,
+    graph: &Graph,
+    config: &SuppressionConfig,
+) -> Option<String> {
+    if let Some(reason) = own_suppression(decl, config) {
+        return Some(reason);
+    }
+
+    let mut parent = decl
+        .parent
+        .as_ref()
+        .and_then(|id| graph.get_declaration(id));
+    while let Some(outer) = parent {
+        if let Some(reason) = own_suppression(outer, config) {
+            return Some(format!("{} on {}", reason, outer.name));
+        }
+        parent = outer
+            .parent
+            .as_ref()
+            .and_then(|id| graph.get_declaration(id));
+    }
+    None
+}
+
+/// Comment or annotation on the declaration itself suppressing its findings
+fn own_suppression(decl: &Declaration, config: &SuppressionConfig) -> Option<String> {
+    if config.comments && decl.modifiers.iter().any(|m| m == IGNORE_COMMENT) {
+        return Some(format!("// {}", IGNORE_COMMENT));
+    }
+
+    decl.annotations
+        .iter()
+        .find(|annotation| {
+            config
+                .annotations
+                .iter()
+                .any(|pattern| annotation_matches(pattern, annotation))
+        })
+        .cloned()
+}
+
+/// Whether an annotation has the pattern's name and, if the pattern has
+/// arguments, every one of them (`Suppress("DeadCode")` matches
+/// `@Suppress("unused", "DeadCode")`)
+fn annotation_matches(pattern: &str, annotation: &str) -> bool {
+    if simple_type_name(pattern) != simple_type_name(annotation) {
+        return false;
+    }
+
+    let arguments = |text: &str| -> Vec<String> {
+        let Some((_, rest)) = text.split_once('(') else {
+            return Vec::new();
+        };
+        rest.trim_end_matches(')')
+            .split(',')
+            .map(|argument| argument.trim().to_string())
+            .filter(|argument| !argument.is_empty())
+            .collect()
+    };
+    let found = arguments(annotation);
+    arguments(pattern)
+        .iter()
+        .all(|argument| found.contains(argument))
+}
+
+/// Apply the suppressions to the findings: suppressed findings are marked,
+/// then downgraded in place or removed and returned
+pub fn apply(
+    dead_code: &mut Vec<DeadCode>,
+    graph: &Graph,
+    config: &SuppressionConfig,
+) -> Vec<DeadCode> {
+    for dc in dead_code.iter_mut() {
+        dc.suppressed_by = suppressed_by(&dc.declaration, graph, config);
+    }
+
+    match config.action {
+        SuppressionAction::Exclude => {
+            let (suppressed, kept): (Vec<DeadCode>, Vec<DeadCode>) = std::mem::take(dead_code)
+                .into_iter()
+                .partition(|dc| dc.suppressed_by.is_some());
+            *dead_code = kept;
+            suppressed
+        }
+        SuppressionAction::Downgrade => {
+            for dc in dead_code.iter_mut() {
+                let Some(reason) = dc.suppressed_by.clone() else {
+                    continue;
+                };
+                dc.message = format!("{} (suppressed by {})", dc.message, reason);
+                if dc.evidence.is_empty() {
+                    dc.confidence = lower(dc.confidence);
+                } else {
+                    let description = format!("Suppressed by {}", reason);
+                    dc.evidence
+                        .push(Evidence::new(EvidenceKind::Suppressed, description));
+                    dc.confidence = Confidence::from_score(scoring::score(&dc.evidence));
+                }
+            }
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{DeclarationId, DeclarationKind, Language, Location};
+    use std::path::PathBuf;
+
+    fn declaration(name: &str, line: usize, parent: Option<&Declaration>) -> Declaration {
+        let file = PathBuf::from("Legacy.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            DeclarationKind::Function,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        );
+        decl.parent = parent.map(|p| p.id.clone());
+        decl
+    }
+
+    #[test]
+    fn test_annotation_matches() {
+        assert!(annotation_matches(
+            "Suppress(\"DeadCode\")",
+            "@Suppress(\"unused\", \"DeadCode\")"
+        ));
+        assert!(!annotation_matches(
+            "Suppress(\"DeadCode\")",
+            "@Suppress(\"unused\")"
+        ));
+        assert!(annotation_matches("DeadCodeOk", "@com.example.DeadCodeOk"));
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut legacy = declaration("Legacy", 1, None);
+        legacy.modifiers.push(IGNORE_COMMENT.to_string());
+        let render = declaration("render", 2, Some(&legacy));
+        let mut cleanup = declaration("cleanup", 5, None);
+        cleanup
+            .annotations
+            .push("@Suppress(\"DeadCode\")".to_string());
+        let dead = declaration("dead", 9, None);
+        let mut graph = Graph::new();
+        for decl in [&legacy, &render, &cleanup, &dead] {
+            graph.add_declaration(decl.clone());
+        }
+
+        let findings = || {
+            vec![
+                DeadCode::new(render.clone(), DeadCodeIssue::Unreferenced),
+                DeadCode::new(cleanup.clone(), DeadCodeIssue::Unreferenced),
+                DeadCode::new(dead.clone(), DeadCodeIssue::Unreferenced),
+            ]
+        };
+
+        let mut config = SuppressionConfig::default();
+        let mut dead_code = findings();
+        let suppressed = apply(&mut dead_code, &graph, &config);
+        assert_eq!(dead_code.len(), 1);
+        assert_eq!(
+            suppressed[0].suppressed_by.as_deref(),
+            Some("// searchdeadcode:ignore on Legacy")
+        );
+        assert_eq!(
+            suppressed[1].suppressed_by.as_deref(),
+            Some("@Suppress(\"DeadCode\")")
+        );
+
+        config.action = SuppressionAction::Downgrade;
+        let mut dead_code = findings();
+        assert!(apply(&mut dead_code, &graph, &config).is_empty());
+        assert_eq!(dead_code[0].confidence, Confidence::Low);
+        assert_eq!(dead_code[2].confidence, Confidence::Medium);
+        assert!(dead_code[2].suppressed_by.is_none());
+    }
+}
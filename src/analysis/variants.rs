@@ -0,0 +1,133 @@
+//! Comparison of build variants
+//!
+//! Flavors and build types compile different source sets, so code can be
+//! dead in one variant and used in another. Each variant is analyzed on its
+//! own; code dead in every variant that compiles it is reported as usual,
+//! code dead in only some of them is kept with a low confidence and names
+//! the variants that still use it.
+
+use super::{Confidence, DeadCode};
+use crate::graph::DeclarationId;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Findings of one variant's analysis
+#[derive(Debug)]
+pub struct VariantFindings {
+    pub variant: String,
+    /// Files compiled into the variant
+    pub files: HashSet<PathBuf>,
+    pub dead_code: Vec<DeadCode>,
+}
+
+impl VariantFindings {
+    pub fn new(variant: String, files: HashSet<PathBuf>, dead_code: Vec<DeadCode>) -> Self {
+        Self {
+            variant,
+            files,
+            dead_code,
+        }
+    }
+}
+
+/// Findings of all variants, merged
+#[derive(Debug, Default)]
+pub struct VariantComparison {
+    pub dead_code: Vec<DeadCode>,
+    /// Findings dead in every variant compiling them
+    pub dead_in_all: usize,
+    /// Findings dead in some variants and used in others
+    pub dead_in_some: usize,
+}
+
+/// Merge the findings of several variants
+pub fn compare_variants(results: Vec<VariantFindings>) -> VariantComparison {
+    let mut comparison = VariantComparison::default();
+    let mut dead_in: HashMap<DeclarationId, Vec<&str>> = HashMap::new();
+    let mut first: Vec<&DeadCode> = Vec::new();
+    for result in &results {
+        for dc in &result.dead_code {
+            let variants = dead_in.entry(dc.declaration.id.clone()).or_default();
+            if variants.is_empty() {
+                first.push(dc);
+            }
+            variants.push(&result.variant);
+        }
+    }
+
+    for dc in first {
+        let dead = &dead_in[&dc.declaration.id];
+        let used: Vec<&str> = results
+            .iter()
+            .filter(|r| r.files.contains(&dc.declaration.location.file))
+            .map(|r| r.variant.as_str())
+            .filter(|variant| !dead.contains(variant))
+            .collect();
+
+        let mut dc = dc.clone();
+        if used.is_empty() {
+            comparison.dead_in_all += 1;
+        } else {
+            comparison.dead_in_some += 1;
+            dc.message = format!(
+                "{} (dead in {}; used in {})",
+                dc.message,
+                dead.join(", "),
+                used.join(", ")
+            );
+            dc.confidence = Confidence::Low;
+        }
+        comparison.dead_code.push(dc);
+    }
+    comparison
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{Declaration, DeclarationKind, Language, Location};
+
+    fn finding(file: &str, name: &str, start: usize) -> DeadCode {
+        let path = PathBuf::from(file);
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), start, start + 10),
+            name.to_string(),
+            DeclarationKind::Function,
+            Location::new(path, 1, 1, start, start + 10),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, DeadCodeIssue::Unreferenced)
+    }
+
+    #[test]
+    fn test_compare_variants() {
+        let files = |names: &[&str]| names.iter().map(PathBuf::from).collect::<HashSet<_>>();
+        let results = vec![
+            VariantFindings::new(
+                "freeDebug".to_string(),
+                files(&["main/Util.kt", "free/Ads.kt"]),
+                vec![
+                    finding("main/Util.kt", "formatPrice", 0),
+                    finding("main/Util.kt", "legacy", 20),
+                ],
+            ),
+            VariantFindings::new(
+                "paidDebug".to_string(),
+                files(&["main/Util.kt", "paid/Billing.kt"]),
+                vec![finding("main/Util.kt", "legacy", 20)],
+            ),
+        ];
+
+        let comparison = compare_variants(results);
+        assert_eq!(comparison.dead_in_all, 1);
+        assert_eq!(comparison.dead_in_some, 1);
+        let price = &comparison.dead_code[0];
+        assert_eq!(price.declaration.name, "formatPrice");
+        assert_eq!(price.confidence, Confidence::Low);
+        assert!(price
+            .message
+            .ends_with("(dead in freeDebug; used in paidDebug)"));
+        assert_ne!(comparison.dead_code[1].confidence, Confidence::Low);
+    }
+}
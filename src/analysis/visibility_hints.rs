@@ -0,0 +1,177 @@
+//! Visibility annotations
+//!
+//! `@VisibleForTesting` widens a declaration for tests only: its `otherwise`
+//! argument (private when left out) is the visibility production code should
+//! see, so a declaration only tests use is a candidate to narrow rather than
+//! dead code. `@RestrictTo` limits a public API to its own library or library
+//! group, so code outside the workspace cannot be using it either.
+
+use super::{DeadCode, DeadCodeIssue};
+use crate::graph::{simple_type_name, Declaration, Language, Visibility};
+
+/// `@RestrictTo` scopes that keep other libraries out
+const LIBRARY_SCOPES: &[&str] = &[
+    "LIBRARY",
+    "LIBRARY_GROUP",
+    "LIBRARY_GROUP_PREFIX",
+    "GROUP_ID",
+];
+
+/// Keyword of the visibility `@VisibleForTesting` asks production code to
+/// see, e.g. `private`; None without the annotation, or with
+/// `otherwise = NONE`, as production code should then not use it at all
+pub fn intended_visibility(decl: &Declaration) -> Option<&'static str> {
+    let annotation = annotation(decl, "VisibleForTesting")?;
+    let otherwise = argument(annotation, "otherwise").unwrap_or("PRIVATE");
+    // The constants of androidx.annotation.VisibleForTesting, by name or value
+    match otherwise.rsplit('.').next().unwrap_or(otherwise) {
+        "PRIVATE" | "2" => Some("private"),
+        "PACKAGE_PRIVATE" | "3" if decl.language == Language::Kotlin => Some("internal"),
+        "PACKAGE_PRIVATE" | "3" => Some("package-private"),
+        "PROTECTED" | "4" => Some("protected"),
+        _ => None,
+    }
+}
+
+/// Whether `@RestrictTo` keeps libraries outside the declaration's own
+/// library or group from using it
+pub fn is_library_restricted(decl: &Declaration) -> bool {
+    annotation(decl, "RestrictTo").is_some_and(|annotation| {
+        annotation
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .any(|word| LIBRARY_SCOPES.contains(&word))
+    })
+}
+
+/// Reclassify a finding by the visibility annotations of its declaration:
+/// test-only `@VisibleForTesting` declarations become candidates to narrow,
+/// and unused public `@RestrictTo` APIs say that nothing outside can use them
+pub fn classify_by_annotations(dc: DeadCode) -> DeadCode {
+    let decl = &dc.declaration;
+    match dc.issue {
+        DeadCodeIssue::OnlyUsedInTests => match intended_visibility(decl) {
+            Some(keyword) => {
+                let message = format!(
+                    "{} '{}' could be {} (@VisibleForTesting, only used from tests)",
+                    decl.kind.display_name(),
+                    decl.name,
+                    keyword
+                );
+                let mut narrowed = DeadCode::new(dc.declaration, DeadCodeIssue::RedundantPublic)
+                    .with_message(message)
+                    .with_confidence(dc.confidence);
+                narrowed.evidence = dc.evidence;
+                narrowed
+            }
+            None => dc,
+        },
+        DeadCodeIssue::Unreferenced
+            if decl.visibility == Visibility::Public && is_library_restricted(decl) =>
+        {
+            let message = format!(
+                "{} '{}' is never used, and @RestrictTo keeps other libraries from using it",
+                decl.kind.display_name(),
+                decl.name
+            );
+            dc.with_message(message)
+        }
+        _ => dc,
+    }
+}
+
+/// The annotation with this simple name, as written
+fn annotation<'a>(decl: &'a Declaration, name: &str) -> Option<&'a str> {
+    decl.annotations
+        .iter()
+        .map(String::as_str)
+        .find(|a| simple_type_name(a) == name)
+}
+
+/// Value of a named annotation argument (`otherwise = PRIVATE`)
+fn argument<'a>(annotation: &'a str, name: &str) -> Option<&'a str> {
+    let start = annotation.find('(')?;
+    annotation[start + 1..]
+        .trim_end_matches(')')
+        .split(',')
+        .filter_map(|arg| arg.split_once('='))
+        .find(|(key, _)| key.trim() == name)
+        .map(|(_, value)| value.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, DeclarationKind, Location};
+    use std::path::PathBuf;
+
+    fn declaration(annotations: &[&str], language: Language) -> Declaration {
+        let path = PathBuf::from("Cache.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), 0, 10),
+            "evictAll".to_string(),
+            DeclarationKind::Method,
+            Location::new(path, 1, 1, 0, 10),
+            language,
+        );
+        decl.annotations = annotations.iter().map(|a| a.to_string()).collect();
+        decl
+    }
+
+    #[test]
+    fn test_intended_visibility() {
+        let visibility =
+            |annotation: &str, language| intended_visibility(&declaration(&[annotation], language));
+        assert_eq!(
+            visibility("@VisibleForTesting", Language::Kotlin),
+            Some("private")
+        );
+        assert_eq!(
+            visibility(
+                "@VisibleForTesting(otherwise = VisibleForTesting.PACKAGE_PRIVATE)",
+                Language::Kotlin
+            ),
+            Some("internal")
+        );
+        assert_eq!(
+            visibility(
+                "@androidx.annotation.VisibleForTesting(otherwise = 4)",
+                Language::Java
+            ),
+            Some("protected")
+        );
+        assert_eq!(
+            visibility(
+                "@VisibleForTesting(otherwise = VisibleForTesting.NONE)",
+                Language::Kotlin
+            ),
+            None
+        );
+        assert_eq!(visibility("@Keep", Language::Kotlin), None);
+    }
+
+    #[test]
+    fn test_classify_by_annotations() {
+        let decl = declaration(&["@VisibleForTesting"], Language::Kotlin);
+        let dc = classify_by_annotations(DeadCode::new(decl, DeadCodeIssue::OnlyUsedInTests));
+        assert_eq!(dc.issue, DeadCodeIssue::RedundantPublic);
+        assert_eq!(
+            dc.message,
+            "method 'evictAll' could be private (@VisibleForTesting, only used from tests)"
+        );
+
+        let decl = declaration(
+            &["@RestrictTo(RestrictTo.Scope.LIBRARY_GROUP)"],
+            Language::Kotlin,
+        );
+        assert!(is_library_restricted(&decl));
+        let dc = classify_by_annotations(DeadCode::new(decl, DeadCodeIssue::Unreferenced));
+        assert_eq!(dc.issue, DeadCodeIssue::Unreferenced);
+        assert_eq!(
+            dc.message,
+            "method 'evictAll' is never used, and @RestrictTo keeps other libraries from using it"
+        );
+
+        let decl = declaration(&["@RestrictTo(RestrictTo.Scope.TESTS)"], Language::Kotlin);
+        assert!(!is_library_restricted(&decl));
+    }
+}
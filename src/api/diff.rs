@@ -0,0 +1,255 @@
+//! Source declarations compared with a recorded API surface
+//!
+//! Public declarations of the sources that the dump does not list were made
+//! public by accident, or the dump is out of date. Entries of the dump that
+//! nothing in the workspace uses are only there for other projects, which
+//! makes them candidates for deprecation.
+
+use super::ApiSurface;
+use crate::discovery::SourceSet;
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph, Visibility};
+use std::collections::HashSet;
+
+/// Differences between the sources and a recorded API surface
+#[derive(Debug, Clone, Default)]
+pub struct ApiDiff {
+    /// Public declarations missing from the surface
+    pub accidental: Vec<Declaration>,
+    /// Declarations of the surface that nothing else in the workspace uses
+    pub unused: Vec<Declaration>,
+    /// Classes and members of the surface without a declaration in the
+    /// sources, as `class` or `class#member`
+    pub missing: Vec<String>,
+}
+
+impl ApiDiff {
+    /// Compare the production declarations of the graph with the surface;
+    /// entry points count as used
+    pub fn compute(
+        graph: &Graph,
+        entry_points: &HashSet<DeclarationId>,
+        surface: &ApiSurface,
+    ) -> Self {
+        let mut diff = ApiDiff::default();
+        let mut found: HashSet<String> = HashSet::new();
+
+        for decl in graph.declarations() {
+            if !is_api_candidate(graph, decl) {
+                continue;
+            }
+            let Some(entry) = surface_entry(graph, surface, decl) else {
+                diff.accidental.push(decl.clone());
+                continue;
+            };
+            if !entry_points.contains(&decl.id) && !is_used(graph, &decl.id) {
+                diff.unused.push(decl.clone());
+            }
+            // File classes (`UtilsKt`) have no declaration of their own
+            if let Some((class, _)) = entry.split_once('#') {
+                found.insert(class.to_string());
+            }
+            found.insert(entry);
+        }
+
+        diff.missing = surface
+            .classes()
+            .map(String::from)
+            .chain(
+                surface
+                    .members()
+                    .map(|(class, member)| format!("{}#{}", class, member)),
+            )
+            .filter(|entry| !found.contains(entry))
+            // Members of missing classes are covered by the class
+            .filter(|entry| {
+                entry
+                    .split_once('#')
+                    .is_none_or(|(class, _)| found.contains(class))
+            })
+            .collect();
+
+        for declarations in [&mut diff.accidental, &mut diff.unused] {
+            declarations.sort_by(|a, b| {
+                let a = &a.location;
+                let b = &b.location;
+                a.file.cmp(&b.file).then(a.line.cmp(&b.line))
+            });
+        }
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accidental.is_empty() && self.unused.is_empty() && self.missing.is_empty()
+    }
+}
+
+/// Whether a declaration is visible outside its library: a class or member
+/// in production sources that it and every enclosing type expose
+fn is_api_candidate(graph: &Graph, decl: &Declaration) -> bool {
+    let kind_listed = decl.kind.is_type() && decl.kind != DeclarationKind::TypeAlias
+        || matches!(
+            decl.kind,
+            DeclarationKind::Function
+                | DeclarationKind::Method
+                | DeclarationKind::Property
+                | DeclarationKind::Field
+                | DeclarationKind::EnumCase
+        );
+    // Dumps list overrides with the member they override, if at all
+    let overrides = decl.modifiers.iter().any(|m| m == "override")
+        || decl.annotations.iter().any(|a| a.contains("Override"));
+    if !kind_listed || overrides || SourceSet::from_path(&decl.location.file).is_test() {
+        return false;
+    }
+
+    let mut current = Some(decl);
+    while let Some(decl) = current {
+        if !matches!(decl.visibility, Visibility::Public | Visibility::Protected) {
+            return false;
+        }
+        current = decl.parent.as_ref().and_then(|p| graph.get_declaration(p));
+    }
+    true
+}
+
+/// The surface entry of a declaration (`class` or `class#member`), if the
+/// surface lists it
+fn surface_entry(graph: &Graph, surface: &ApiSurface, decl: &Declaration) -> Option<String> {
+    if decl.kind.is_type() {
+        let class = qualified_name(graph, decl)?;
+        return surface.contains_class(&class).then_some(class);
+    }
+
+    let names: Vec<String> = std::iter::once(decl.name.clone())
+        .chain(decl.jvm_names())
+        .collect();
+    let member_of = |class: &str| {
+        names
+            .iter()
+            .find(|name| surface.contains_member(class, name))
+            .map(|name| format!("{}#{}", class, name))
+    };
+    match decl.parent.as_ref().and_then(|p| graph.get_declaration(p)) {
+        Some(owner) => member_of(&qualified_name(graph, owner)?),
+        // Top-level functions and properties live in a file class (`UtilsKt`)
+        None => {
+            let fqn = decl.fully_qualified_name.as_deref()?;
+            let package = fqn
+                .strip_suffix(decl.name.as_str())
+                .map(|p| p.trim_end_matches('.'))
+                .unwrap_or_default();
+            surface.classes_in_package(package).find_map(member_of)
+        }
+    }
+}
+
+/// Qualified name of a type, with `.` between nested types
+fn qualified_name(graph: &Graph, decl: &Declaration) -> Option<String> {
+    match decl.parent.as_ref().and_then(|p| graph.get_declaration(p)) {
+        Some(owner) => Some(format!("{}.{}", qualified_name(graph, owner)?, decl.name)),
+        None => decl.fully_qualified_name.clone(),
+    }
+}
+
+/// Whether anything outside a declaration references it or one of its
+/// members
+fn is_used(graph: &Graph, id: &DeclarationId) -> bool {
+    let mut inside: HashSet<&DeclarationId> = HashSet::from([id]);
+    let mut stack = vec![id];
+    while let Some(id) = stack.pop() {
+        for child in graph.get_children(id) {
+            if inside.insert(child) {
+                stack.push(child);
+            }
+        }
+    }
+    graph
+        .get_references_to(id)
+        .iter()
+        .any(|(source, _)| !inside.contains(&source.id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Language, Location, Reference, ReferenceKind};
+    use std::path::PathBuf;
+
+    fn declaration(
+        name: &str,
+        kind: DeclarationKind,
+        start: usize,
+        parent: Option<&Declaration>,
+    ) -> Declaration {
+        let path = PathBuf::from("lib/src/main/kotlin/Client.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), start, start + 10),
+            name.to_string(),
+            kind,
+            Location::new(path, start / 10 + 1, 1, start, start + 10),
+            Language::Kotlin,
+        );
+        decl.parent = parent.map(|p| p.id.clone());
+        if parent.is_none() {
+            decl.fully_qualified_name = Some(format!("com.example.{}", name));
+        }
+        decl
+    }
+
+    fn add(
+        graph: &mut Graph,
+        name: &str,
+        kind: DeclarationKind,
+        start: usize,
+        parent: Option<&Declaration>,
+    ) -> Declaration {
+        let decl = declaration(name, kind, start, parent);
+        graph.add_declaration(decl.clone());
+        decl
+    }
+
+    #[test]
+    fn test_api_diff() {
+        use DeclarationKind::{Class, Function, Method, Property};
+
+        let mut graph = Graph::new();
+        let client = add(&mut graph, "Client", Class, 0, None);
+        let fetch = add(&mut graph, "fetch", Method, 10, Some(&client));
+        add(&mut graph, "retries", Property, 20, Some(&client));
+        let mut cache = declaration("cache", Method, 30, Some(&client));
+        cache.visibility = Visibility::Private;
+        graph.add_declaration(cache);
+        add(&mut graph, "debugDump", Method, 40, Some(&client));
+        add(&mut graph, "slugify", Function, 100, None);
+        let run = add(&mut graph, "run", Function, 150, None);
+        let main = add(&mut graph, "main", Function, 200, None);
+        let location = Location::new(main.location.file.clone(), 21, 1, 200, 206);
+        for target in [&client, &fetch] {
+            graph.add_reference(
+                &main.id,
+                &target.id,
+                Reference::new(ReferenceKind::Call, location.clone(), target.name.clone()),
+            );
+        }
+
+        let surface = ApiSurface::parse_content(
+            "public final class com/example/Client {\n\
+             \tpublic final fun fetch ()V\n\
+             \tpublic final fun getRetries ()I\n\
+             \tpublic final fun close ()V\n\
+             }\n\n\
+             public final class com/example/ClientKt {\n\
+             \tpublic static final fun run ()V\n\
+             \tpublic static final fun slugify (Ljava/lang/String;)Ljava/lang/String;\n\
+             }\n",
+        );
+        let entry_points = HashSet::from([run.id.clone()]);
+        let diff = ApiDiff::compute(&graph, &entry_points, &surface);
+        let names = |decls: &[Declaration]| -> Vec<String> {
+            decls.iter().map(|d| d.name.clone()).collect()
+        };
+        assert_eq!(names(&diff.accidental), vec!["debugDump", "main"]);
+        assert_eq!(names(&diff.unused), vec!["retries", "slugify"]);
+        assert_eq!(diff.missing, vec!["com.example.Client#close"]);
+    }
+}
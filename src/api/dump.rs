@@ -0,0 +1,318 @@
+//! API dump parsers
+//!
+//! Two formats record a library's public surface:
+//!
+//! Metalava signature files (`api/current.txt`, `api/current.api`):
+//! ```text
+//! // Signature format: 4.0
+//! package com.example {
+//!   public final class Client {
+//!     ctor public Client();
+//!     method public void fetch(String url);
+//!     property public final int retries;
+//!   }
+//!   public static final class Client.Companion {
+//!   }
+//! }
+//! ```
+//!
+//! Binary compatibility validator dumps (`api/<module>.api`):
+//! ```text
+//! public final class com/example/Client {
+//!     public fun <init> ()V
+//!     public final fun fetch (Ljava/lang/String;)V
+//!     public final fun getRetries ()I
+//! }
+//! ```
+
+use miette::{IntoDiagnostic, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+/// Member name used for constructors
+const CONSTRUCTOR: &str = "<init>";
+
+/// Classes and members of a recorded API surface
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiSurface {
+    /// Classes by qualified name, with `.` for nested classes
+    classes: BTreeSet<String>,
+    /// Member names by class; constructors are left out
+    members: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ApiSurface {
+    /// Parse an API dump, telling the format from its contents
+    pub fn parse(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).into_diagnostic()?;
+        Ok(Self::parse_content(&content))
+    }
+
+    /// Parse a metalava signature file or a binary compatibility validator
+    /// dump
+    pub fn parse_content(content: &str) -> Self {
+        let metalava = content.lines().any(|line| {
+            let line = line.trim();
+            line.starts_with("// Signature format") || line.starts_with("package ")
+        });
+        if metalava {
+            Self::parse_metalava(content)
+        } else {
+            Self::parse_bcv(content)
+        }
+    }
+
+    /// Add the classes and members of another dump, e.g. of another module
+    pub fn merge(&mut self, other: ApiSurface) {
+        self.classes.extend(other.classes);
+        for (class, members) in other.members {
+            self.members.entry(class).or_default().extend(members);
+        }
+    }
+
+    pub fn contains_class(&self, class: &str) -> bool {
+        self.classes.contains(class)
+    }
+
+    pub fn contains_member(&self, class: &str, member: &str) -> bool {
+        self.members
+            .get(class)
+            .is_some_and(|members| members.contains(member))
+    }
+
+    /// Classes of a package, e.g. to find the file classes (`UtilsKt`)
+    /// holding top-level functions
+    pub fn classes_in_package<'a>(&'a self, package: &'a str) -> impl Iterator<Item = &'a str> {
+        self.classes
+            .iter()
+            .map(String::as_str)
+            .filter(move |class| {
+                class
+                    .strip_prefix(package)
+                    .and_then(|rest| rest.strip_prefix('.'))
+                    .is_some_and(|rest| !rest.contains('.'))
+            })
+    }
+
+    pub fn classes(&self) -> impl Iterator<Item = &str> {
+        self.classes.iter().map(String::as_str)
+    }
+
+    /// Members of every class, as class and member name
+    pub fn members(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.members.iter().flat_map(|(class, members)| {
+            members
+                .iter()
+                .map(move |member| (class.as_str(), member.as_str()))
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    fn add_member(&mut self, class: &str, member: &str) {
+        if member != CONSTRUCTOR {
+            self.members
+                .entry(class.to_string())
+                .or_default()
+                .insert(member.to_string());
+        }
+    }
+
+    fn parse_metalava(content: &str) -> Self {
+        let mut surface = Self::default();
+        let mut package = String::new();
+        let mut class: Option<String> = None;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            if let Some(rest) = line.strip_prefix("package ") {
+                package = rest.trim_end_matches('{').trim().to_string();
+                continue;
+            }
+            if line == "}" {
+                // Closes the current class, or the package after it
+                class = None;
+                continue;
+            }
+
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let Some(&keyword) = words.first() else {
+                continue;
+            };
+            match keyword {
+                "ctor" => {}
+                "method" => {
+                    if let (Some(class), Some(name)) = (&class, metalava_method(line)) {
+                        surface.add_member(class, name);
+                    }
+                }
+                "field" | "property" | "enum_constant" => {
+                    if let (Some(class), Some(name)) = (&class, metalava_field(line)) {
+                        surface.add_member(class, name);
+                    }
+                }
+                _ => {
+                    if let Some(name) = metalava_class(&words) {
+                        let qualified = if package.is_empty() {
+                            name.to_string()
+                        } else {
+                            format!("{}.{}", package, name)
+                        };
+                        surface.classes.insert(qualified.clone());
+                        class = Some(qualified);
+                    }
+                }
+            }
+        }
+        surface
+    }
+
+    fn parse_bcv(content: &str) -> Self {
+        let mut surface = Self::default();
+        let mut class: Option<String> = None;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line == "}" {
+                class = None;
+                continue;
+            }
+
+            let words: Vec<&str> = line.split_whitespace().collect();
+            if line.ends_with('{') {
+                if let Some(name) = bcv_class(&words) {
+                    let qualified = name.replace(['/', '$'], ".");
+                    surface.classes.insert(qualified.clone());
+                    class = Some(qualified);
+                }
+                continue;
+            }
+            let Some(class) = &class else {
+                continue;
+            };
+            // `public final fun fetch (Ljava/lang/String;)V`,
+            // `public static final field TAG Ljava/lang/String;`
+            if let Some(index) = words.iter().position(|w| *w == "fun" || *w == "field") {
+                if let Some(name) = words.get(index + 1) {
+                    surface.add_member(class, name);
+                }
+            }
+        }
+        surface
+    }
+}
+
+/// Simple name of a metalava class line, e.g. `Client.Companion` in
+/// `public static final class Client.Companion {`
+fn metalava_class<'a>(words: &[&'a str]) -> Option<&'a str> {
+    let index = words
+        .iter()
+        .position(|w| matches!(*w, "class" | "interface" | "enum" | "object" | "@interface"))?;
+    let name = words.get(index + 1)?;
+    Some(name.split('<').next().unwrap_or(name))
+}
+
+/// Name of a metalava method, the word before the parameter list
+fn metalava_method(line: &str) -> Option<&str> {
+    let before = &line[..line.find('(')?];
+    before.split_whitespace().last()
+}
+
+/// Name of a metalava field or property, the word before any initializer
+fn metalava_field(line: &str) -> Option<&str> {
+    let declaration = line.split(" = ").next()?.trim_end_matches(';');
+    declaration.split_whitespace().last()
+}
+
+/// Internal name of a binary compatibility validator class line, e.g.
+/// `com/example/Client$Companion` in
+/// `public final class com/example/Client$Companion {`; interfaces are
+/// `public abstract interface class com/example/Api {`
+fn bcv_class<'a>(words: &[&'a str]) -> Option<&'a str> {
+    let index = words.iter().rposition(|w| *w == "class")?;
+    words.get(index + 1).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_metalava() {
+        let surface = ApiSurface::parse_content(
+            r#"// Signature format: 4.0
+package com.example {
+
+  public final class Client {
+    ctor public Client();
+    method public void fetch(String url);
+    method public static <T> T decode(String json, Class<T> type);
+    field public static final String TAG = "Client";
+    property public final int retries;
+  }
+
+  public static final class Client.Companion {
+    method public Client create();
+  }
+
+  public enum Mode {
+    enum_constant public static final com.example.Mode FAST;
+  }
+
+}
+"#,
+        );
+        assert!(surface.contains_class("com.example.Client"));
+        assert!(surface.contains_class("com.example.Client.Companion"));
+        assert!(surface.contains_member("com.example.Client", "fetch"));
+        assert!(surface.contains_member("com.example.Client", "decode"));
+        assert!(surface.contains_member("com.example.Client", "TAG"));
+        assert!(surface.contains_member("com.example.Client", "retries"));
+        assert!(surface.contains_member("com.example.Client.Companion", "create"));
+        assert!(surface.contains_member("com.example.Mode", "FAST"));
+        assert!(!surface.contains_member("com.example.Client", "Client"));
+    }
+
+    #[test]
+    fn test_parse_bcv() {
+        let surface = ApiSurface::parse_content(
+            "public final class com/example/Client {\n\
+             \tpublic static final field Companion Lcom/example/Client$Companion;\n\
+             \tpublic fun <init> ()V\n\
+             \tpublic final fun fetch (Ljava/lang/String;)V\n\
+             \tpublic final fun getRetries ()I\n\
+             }\n\n\
+             public final class com/example/Client$Companion {\n\
+             \tpublic final fun create ()Lcom/example/Client;\n\
+             }\n\n\
+             public abstract interface class com/example/Api {\n\
+             \tpublic abstract fun close ()V\n\
+             }\n\n\
+             public final class com/example/UtilsKt {\n\
+             \tpublic static final fun slugify (Ljava/lang/String;)Ljava/lang/String;\n\
+             }\n",
+        );
+        assert!(surface.contains_class("com.example.Client.Companion"));
+        assert!(surface.contains_member("com.example.Client", "getRetries"));
+        assert!(surface.contains_member("com.example.Client.Companion", "create"));
+        assert!(!surface.contains_member("com.example.Client", "<init>"));
+        let classes: Vec<&str> = surface.classes_in_package("com.example").collect();
+        assert_eq!(
+            classes,
+            vec![
+                "com.example.Api",
+                "com.example.Client",
+                "com.example.UtilsKt"
+            ]
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! Public API surface checks for library modules
+//!
+//! SDK authors record their public API with metalava or the Kotlin binary
+//! compatibility validator. Comparing those dumps with the sources shows
+//! declarations that became public without being added to the dump, and API
+//! that nothing in the workspace uses.
+
+mod diff;
+mod dump;
+
+pub use diff::ApiDiff;
+pub use dump::ApiSurface;
@@ -4,12 +4,14 @@
 //! to ignore existing dead code issues and only report new ones.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::io::{BufReader, BufWriter};
 use std::path::Path;
 use thiserror::Error;
 
 use crate::analysis::DeadCode;
+use crate::discovery::NormalizedPath;
 
 /// Baseline errors
 #[derive(Error, Debug)]
@@ -28,7 +30,7 @@ const BASELINE_VERSION: u32 = 1;
 /// A fingerprint for a dead code issue that can be matched across runs
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct IssueFingerprint {
-    /// Relative file path
+    /// File path relative to the project root, with `/` separators
     pub file: String,
     /// Declaration name
     pub name: String,
@@ -43,14 +45,8 @@ pub struct IssueFingerprint {
 impl IssueFingerprint {
     /// Create a fingerprint from a dead code issue
     pub fn from_dead_code(dc: &DeadCode, project_root: &Path) -> Self {
-        let file = dc
-            .declaration
-            .location
-            .file
-            .strip_prefix(project_root)
-            .unwrap_or(&dc.declaration.location.file)
-            .to_string_lossy()
-            .to_string();
+        let file =
+            NormalizedPath::relative_to(&dc.declaration.location.file, project_root).to_string();
 
         Self {
             file,
@@ -61,19 +57,35 @@ impl IssueFingerprint {
         }
     }
 
+    /// Stable hex digest of the fingerprint, for tools that track issues by
+    /// id across runs. The line is left out so moved code keeps its digest.
+    pub fn digest(&self) -> String {
+        // FNV-1a, which unlike DefaultHasher is stable across Rust releases
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let fqn = self.fqn.as_deref().unwrap_or_default();
+        for field in [
+            self.file.as_str(),
+            self.name.as_str(),
+            self.kind.as_str(),
+            fqn,
+        ] {
+            for byte in field.bytes().chain([0]) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+        format!("{:016x}", hash)
+    }
+
     /// Check if this fingerprint matches a dead code issue (with some tolerance)
     pub fn matches(&self, dc: &DeadCode, project_root: &Path) -> bool {
-        let dc_file = dc
-            .declaration
-            .location
-            .file
-            .strip_prefix(project_root)
-            .unwrap_or(&dc.declaration.location.file)
-            .to_string_lossy()
-            .to_string();
+        // Baselines written before paths were normalized may hold
+        // backslashes
+        let file = NormalizedPath::from_string(&self.file);
+        let dc_file = NormalizedPath::relative_to(&dc.declaration.location.file, project_root);
 
         // Must match file, name, and kind exactly
-        if self.file != dc_file || self.name != dc.declaration.name {
+        if file != dc_file || self.name != dc.declaration.name {
             return false;
         }
 
@@ -93,6 +105,45 @@ impl IssueFingerprint {
     }
 }
 
+/// Stable id of a finding across runs: its rule code and fingerprint digest
+/// (`DC001-340514b22064b5db`). Findings sharing both, like overloads, are
+/// numbered in report order (`DC001-340514b22064b5db-2`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FindingId(String);
+
+impl FindingId {
+    /// Id of a single finding, without numbering duplicates
+    pub fn new(dc: &DeadCode, project_root: &Path) -> Self {
+        let fingerprint = IssueFingerprint::from_dead_code(dc, project_root);
+        Self(format!("{}-{}", dc.issue.code(), fingerprint.digest()))
+    }
+
+    /// Ids of findings in report order, numbering duplicates so every id is
+    /// unique within the report
+    pub fn assign(findings: &[DeadCode], project_root: &Path) -> Vec<Self> {
+        let mut seen: HashMap<FindingId, usize> = HashMap::new();
+        findings
+            .iter()
+            .map(|dc| {
+                let id = Self::new(dc, project_root);
+                let count = seen.entry(id.clone()).or_default();
+                *count += 1;
+                match *count {
+                    1 => id,
+                    n => Self(format!("{}-{}", id.0, n)),
+                }
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for FindingId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 /// A baseline containing known dead code issues to ignore
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Baseline {
@@ -148,18 +199,6 @@ impl Baseline {
         Ok(())
     }
 
-    /// Filter out findings that are in the baseline
-    pub fn filter_new<'a>(
-        &self,
-        findings: &'a [DeadCode],
-        project_root: &Path,
-    ) -> Vec<&'a DeadCode> {
-        findings
-            .iter()
-            .filter(|dc| !self.is_baselined(dc, project_root))
-            .collect()
-    }
-
     /// Check if a finding is in the baseline
     pub fn is_baselined(&self, dc: &DeadCode, project_root: &Path) -> bool {
         self.issues.iter().any(|fp| fp.matches(dc, project_root))
@@ -261,6 +300,54 @@ mod tests {
         assert!(!fp.matches(&dc4, &project_root));
     }
 
+    #[test]
+    fn test_fingerprint_paths_across_platforms() {
+        let project_root = PathBuf::from("/project");
+        let dc = make_dead_code("TestClass", "/project/src/test.kt", 10);
+
+        // Written on Windows
+        let mut fp = IssueFingerprint::from_dead_code(&dc, &project_root);
+        fp.file = r"src\test.kt".to_string();
+        assert!(fp.matches(&dc, &project_root));
+
+        // The root typed with another casing
+        let fp = IssueFingerprint::from_dead_code(&dc, Path::new("/Project"));
+        assert_eq!(fp.file, "src/test.kt");
+        assert_eq!(
+            fp.digest(),
+            IssueFingerprint::from_dead_code(&dc, &project_root).digest()
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_digest() {
+        let project_root = PathBuf::from("/project");
+        let digest = |dc: &DeadCode| IssueFingerprint::from_dead_code(dc, &project_root).digest();
+
+        let dc = make_dead_code("TestClass", "/project/src/test.kt", 10);
+        assert_eq!(digest(&dc), "340514b22064b5db");
+        let moved = make_dead_code("TestClass", "/project/src/test.kt", 50);
+        assert_eq!(digest(&moved), digest(&dc));
+        let other = make_dead_code("OtherClass", "/project/src/test.kt", 10);
+        assert_ne!(digest(&other), digest(&dc));
+    }
+
+    #[test]
+    fn test_finding_ids() {
+        let project_root = PathBuf::from("/project");
+        let findings = vec![
+            make_dead_code("TestClass", "/project/src/test.kt", 10),
+            make_dead_code("TestClass", "/project/src/test.kt", 30),
+            make_dead_code("OtherClass", "/project/src/test.kt", 50),
+        ];
+
+        let ids = FindingId::assign(&findings, &project_root);
+        assert_eq!(ids[0].to_string(), "DC001-340514b22064b5db");
+        assert_eq!(ids[1].to_string(), "DC001-340514b22064b5db-2");
+        assert_eq!(ids[0], FindingId::new(&findings[1], &project_root));
+        assert_ne!(ids[2], ids[0]);
+    }
+
     #[test]
     fn test_baseline_save_load() {
         let temp_dir = TempDir::new().unwrap();
@@ -282,15 +369,14 @@ mod tests {
     #[test]
     fn test_baseline_filter() {
         let project_root = PathBuf::from("/project");
-        let findings = vec![
+        let findings = [
             make_dead_code("ClassA", "/project/src/a.kt", 10),
             make_dead_code("ClassB", "/project/src/b.kt", 20),
         ];
 
         let baseline = Baseline::from_findings(&findings[..1], &project_root);
 
-        let new_findings = baseline.filter_new(&findings, &project_root);
-        assert_eq!(new_findings.len(), 1);
-        assert_eq!(new_findings[0].declaration.name, "ClassB");
+        assert!(baseline.is_baselined(&findings[0], &project_root));
+        assert!(!baseline.is_baselined(&findings[1], &project_root));
     }
 }
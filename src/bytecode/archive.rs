@@ -0,0 +1,175 @@
+// Minimal zip reader for .jar and .aar files
+//
+// Only what prebuilt Android artifacts use is supported: stored and deflated
+// entries listed in the central directory, without ZIP64 or encryption.
+
+use miette::{miette, Result};
+
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+const CENTRAL_DIRECTORY_HEADER: u32 = 0x0201_4b50;
+const LOCAL_FILE_HEADER: u32 = 0x0403_4b50;
+
+/// Size of the end of central directory record without its comment
+const END_RECORD_SIZE: usize = 22;
+const CENTRAL_HEADER_SIZE: usize = 46;
+const LOCAL_HEADER_SIZE: usize = 30;
+
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+
+/// A decompressed file in an archive
+#[derive(Debug)]
+pub struct ZipEntry {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// Read the entries whose name matches `filter`
+pub fn read_entries(bytes: &[u8], filter: impl Fn(&str) -> bool) -> Result<Vec<ZipEntry>> {
+    let end = find_end_record(bytes).ok_or_else(|| miette!("Not a zip archive"))?;
+    let entry_count = read_u16(bytes, end + 10)? as usize;
+    let mut offset = read_u32(bytes, end + 16)? as usize;
+
+    let mut entries = Vec::new();
+    for _ in 0..entry_count {
+        if read_u32(bytes, offset)? != CENTRAL_DIRECTORY_HEADER {
+            return Err(miette!("Corrupt central directory at offset {}", offset));
+        }
+        let method = read_u16(bytes, offset + 10)?;
+        let compressed_size = read_u32(bytes, offset + 20)?;
+        let name_len = read_u16(bytes, offset + 28)? as usize;
+        let extra_len = read_u16(bytes, offset + 30)? as usize;
+        let comment_len = read_u16(bytes, offset + 32)? as usize;
+        let local_offset = read_u32(bytes, offset + 42)?;
+        let name_start = offset + CENTRAL_HEADER_SIZE;
+        let name = String::from_utf8_lossy(slice(bytes, name_start, name_len)?).to_string();
+        offset = name_start + name_len + extra_len + comment_len;
+
+        if name.ends_with('/') || !filter(&name) {
+            continue;
+        }
+        if compressed_size == u32::MAX || local_offset == u32::MAX {
+            return Err(miette!("ZIP64 archives are not supported ({})", name));
+        }
+
+        let data = read_data(
+            bytes,
+            local_offset as usize,
+            compressed_size as usize,
+            method,
+        )
+        .map_err(|e| miette!("Failed to read {}: {}", name, e))?;
+        entries.push(ZipEntry { name, data });
+    }
+
+    Ok(entries)
+}
+
+fn read_data(bytes: &[u8], local_offset: usize, size: usize, method: u16) -> Result<Vec<u8>> {
+    if read_u32(bytes, local_offset)? != LOCAL_FILE_HEADER {
+        return Err(miette!("missing local file header"));
+    }
+    // Local name and extra lengths may differ from the central directory's
+    let name_len = read_u16(bytes, local_offset + 26)? as usize;
+    let extra_len = read_u16(bytes, local_offset + 28)? as usize;
+    let data = slice(
+        bytes,
+        local_offset + LOCAL_HEADER_SIZE + name_len + extra_len,
+        size,
+    )?;
+
+    match method {
+        STORED => Ok(data.to_vec()),
+        DEFLATED => miniz_oxide::inflate::decompress_to_vec(data).map_err(|e| miette!("{}", e)),
+        other => Err(miette!("unsupported compression method {}", other)),
+    }
+}
+
+/// Locate the end of central directory record, which is followed by a comment
+/// of up to 64 KiB
+fn find_end_record(bytes: &[u8]) -> Option<usize> {
+    let last = bytes.len().checked_sub(END_RECORD_SIZE)?;
+    let first = last.saturating_sub(u16::MAX as usize);
+    (first..=last)
+        .rev()
+        .find(|&offset| read_u32(bytes, offset).ok() == Some(END_OF_CENTRAL_DIRECTORY))
+}
+
+fn slice(bytes: &[u8], offset: usize, len: usize) -> Result<&[u8]> {
+    offset
+        .checked_add(len)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or_else(|| miette!("Truncated archive"))
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16> {
+    let b = slice(bytes, offset, 2)?;
+    Ok(u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32> {
+    let b = slice(bytes, offset, 4)?;
+    Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Build a zip archive with stored entries, for tests
+#[cfg(test)]
+pub fn build_stored(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+
+    for (name, data) in entries {
+        let local_offset = out.len() as u32;
+        let sizes = (data.len() as u32).to_le_bytes();
+
+        out.extend(LOCAL_FILE_HEADER.to_le_bytes());
+        out.extend([20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        out.extend(sizes);
+        out.extend(sizes);
+        out.extend((name.len() as u16).to_le_bytes());
+        out.extend([0, 0]);
+        out.extend(name.as_bytes());
+        out.extend(*data);
+
+        central.extend(CENTRAL_DIRECTORY_HEADER.to_le_bytes());
+        central.extend([20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        central.extend(sizes);
+        central.extend(sizes);
+        central.extend((name.len() as u16).to_le_bytes());
+        central.extend([0; 12]);
+        central.extend(local_offset.to_le_bytes());
+        central.extend(name.as_bytes());
+    }
+
+    let central_offset = out.len() as u32;
+    let count = (entries.len() as u16).to_le_bytes();
+    out.extend(&central);
+    out.extend(END_OF_CENTRAL_DIRECTORY.to_le_bytes());
+    out.extend([0, 0, 0, 0]);
+    out.extend(count);
+    out.extend(count);
+    out.extend((central.len() as u32).to_le_bytes());
+    out.extend(central_offset.to_le_bytes());
+    out.extend([0, 0]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_stored_entries() {
+        let archive = build_stored(&[
+            ("META-INF/MANIFEST.MF", &b"Manifest-Version: 1.0"[..]),
+            ("com/example/Foo.class", &b"\xCA\xFE\xBA\xBE"[..]),
+        ]);
+
+        let entries = read_entries(&archive, |name| name.ends_with(".class")).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "com/example/Foo.class");
+        assert_eq!(entries[0].data, b"\xCA\xFE\xBA\xBE");
+
+        assert!(read_entries(b"not a zip", |_| true).is_err());
+    }
+}
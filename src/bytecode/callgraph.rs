@@ -0,0 +1,367 @@
+// Call graph of the project's own compiled classes
+//
+// The source graph resolves calls by name, so a parser gap shows up as dead
+// code. The compiler has already resolved every call: each invoke and field
+// instruction names its owner class and member. Walking those from the same
+// entry points gives a second opinion on the findings.
+//
+// Members are identified by owner and name only, so overloads share a node,
+// and a call reaches every override in the project's class hierarchy. Both
+// keep the bytecode view on the side of "reachable".
+
+use super::archive;
+use super::classfile::{parse_class_code, ClassCode};
+use super::{class_fqn, member_names, owner_fqn};
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph};
+use miette::{miette, IntoDiagnostic, Result};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+/// Methods every class inherits from `java.lang.Object` and the runtime calls
+const OBJECT_METHODS: &[&str] = &["<init>", "<clinit>", "toString", "equals", "hashCode"];
+
+/// The project's compiled classes, keyed by fully qualified name
+#[derive(Debug, Default)]
+pub struct BytecodeGraph {
+    classes: HashMap<String, ClassCode>,
+    /// Direct subclasses and implementations of each class
+    subtypes: HashMap<String, Vec<String>>,
+}
+
+impl BytecodeGraph {
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    pub fn class_count(&self) -> usize {
+        self.classes.len()
+    }
+
+    /// Add the classes of a class output directory, or of a .jar, .aar or
+    /// .class file
+    pub fn add_path(&mut self, path: &Path) -> Result<()> {
+        if path.is_dir() {
+            for entry in walkdir::WalkDir::new(path)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+            {
+                let path = entry.path();
+                if path.extension().is_some_and(|ext| ext == "class") {
+                    self.add_class_file(path)?;
+                }
+            }
+            return Ok(());
+        }
+
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        match extension {
+            "class" => self.add_class_file(path),
+            "jar" | "aar" => {
+                let bytes = std::fs::read(path).into_diagnostic()?;
+                self.add_archive(&bytes)
+            }
+            "dex" | "apk" => Err(miette!(
+                "Dex bytecode is not supported; pass the class output directory or convert with dex2jar"
+            )),
+            _ => Err(miette!("Expected a directory or a .jar, .aar or .class file")),
+        }
+    }
+
+    fn add_class_file(&mut self, path: &Path) -> Result<()> {
+        let bytes = std::fs::read(path).into_diagnostic()?;
+        let class = parse_class_code(&bytes)
+            .map_err(|e| miette!("Failed to parse {}: {}", path.display(), e))?;
+        self.add_class(class);
+        Ok(())
+    }
+
+    fn add_archive(&mut self, bytes: &[u8]) -> Result<()> {
+        let entries = archive::read_entries(bytes, |name| {
+            name.ends_with(".class") || name.ends_with(".jar")
+        })?;
+        for entry in entries {
+            if entry.name.ends_with(".jar") {
+                self.add_archive(&entry.data)?;
+            } else if !entry.name.ends_with("module-info.class") {
+                let class = parse_class_code(&entry.data)
+                    .map_err(|e| miette!("Failed to parse {}: {}", entry.name, e))?;
+                self.add_class(class);
+            }
+        }
+        Ok(())
+    }
+
+    fn add_class(&mut self, class: ClassCode) {
+        let name = class_fqn(&class.this_class);
+        for supertype in class.super_class.iter().chain(&class.interfaces) {
+            self.subtypes
+                .entry(class_fqn(supertype))
+                .or_default()
+                .push(name.clone());
+        }
+        self.classes.insert(name, class);
+    }
+
+    /// Walk the bytecode from the source graph's entry points
+    pub fn reachability(
+        &self,
+        graph: &Graph,
+        entry_points: &HashSet<DeclarationId>,
+    ) -> BytecodeReachability<'_> {
+        let mut walk = Walk {
+            bytecode: self,
+            classes: HashSet::new(),
+            members: HashSet::new(),
+            queue: VecDeque::new(),
+        };
+
+        for decl in entry_points
+            .iter()
+            .filter_map(|id| graph.get_declaration(id))
+        {
+            if decl.kind.is_type() {
+                // The framework may call any method of an entry point class
+                let Some(fqn) = decl.fully_qualified_name.as_ref() else {
+                    continue;
+                };
+                if let Some(class) = self.classes.get(fqn) {
+                    for method in &class.methods {
+                        walk.queue
+                            .push_back(Node::Member(fqn.clone(), method.name.clone()));
+                    }
+                }
+                walk.queue.push_back(Node::Class(fqn.clone()));
+            } else if let Some(owner) = owner_fqn(decl, graph) {
+                for name in member_names(decl) {
+                    walk.queue.push_back(Node::Member(owner.clone(), name));
+                }
+            }
+        }
+        walk.run();
+
+        BytecodeReachability {
+            bytecode: self,
+            classes: walk.classes,
+            members: walk.members,
+        }
+    }
+
+    /// Whether a class extends or implements anything outside the project
+    /// other than `java.lang.Object`, so code we cannot see may call its methods
+    fn has_external_supertype(&self, class: &ClassCode) -> bool {
+        let external = |name: &String| !self.classes.contains_key(&class_fqn(name));
+        class
+            .super_class
+            .iter()
+            .filter(|name| name.as_str() != "java/lang/Object")
+            .chain(&class.interfaces)
+            .any(external)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Node {
+    Class(String),
+    /// Owner class and member name
+    Member(String, String),
+}
+
+struct Walk<'a> {
+    bytecode: &'a BytecodeGraph,
+    classes: HashSet<String>,
+    members: HashSet<(String, String)>,
+    queue: VecDeque<Node>,
+}
+
+impl Walk<'_> {
+    fn run(&mut self) {
+        while let Some(node) = self.queue.pop_front() {
+            match node {
+                Node::Class(name) => self.visit_class(name),
+                Node::Member(owner, name) => self.visit_member(owner, name),
+            }
+        }
+    }
+
+    fn visit_class(&mut self, name: String) {
+        let Some(class) = self.bytecode.classes.get(&name) else {
+            return;
+        };
+        if !self.classes.insert(name.clone()) {
+            return;
+        }
+
+        let external = self.bytecode.has_external_supertype(class);
+        for method in &class.methods {
+            if external || OBJECT_METHODS.contains(&method.name.as_str()) {
+                self.queue
+                    .push_back(Node::Member(name.clone(), method.name.clone()));
+            }
+        }
+        for (owner, member) in &class.handles {
+            self.queue
+                .push_back(Node::Member(class_fqn(owner), member.clone()));
+        }
+        for supertype in class.super_class.iter().chain(&class.interfaces) {
+            self.queue.push_back(Node::Class(class_fqn(supertype)));
+        }
+    }
+
+    fn visit_member(&mut self, owner: String, name: String) {
+        let Some(class) = self.bytecode.classes.get(&owner) else {
+            return;
+        };
+        if !self.members.insert((owner.clone(), name.clone())) {
+            return;
+        }
+        self.queue.push_back(Node::Class(owner.clone()));
+
+        let mut defined = class.fields.contains(&name);
+        for method in class.methods.iter().filter(|m| m.name == name) {
+            defined = true;
+            for referenced in &method.classes {
+                self.queue.push_back(Node::Class(class_fqn(referenced)));
+            }
+            for (member_owner, member) in &method.members {
+                self.queue
+                    .push_back(Node::Member(class_fqn(member_owner), member.clone()));
+            }
+        }
+
+        // Inherited: the implementation is in a supertype
+        if !defined {
+            for supertype in class.super_class.iter().chain(&class.interfaces) {
+                self.queue
+                    .push_back(Node::Member(class_fqn(supertype), name.clone()));
+            }
+        }
+        // Virtual dispatch may land in any override
+        if !name.starts_with('<') {
+            for subtype in self.bytecode.subtypes.get(&owner).into_iter().flatten() {
+                self.queue
+                    .push_back(Node::Member(subtype.clone(), name.clone()));
+            }
+        }
+    }
+}
+
+/// What the bytecode walk reached
+#[derive(Debug)]
+pub struct BytecodeReachability<'a> {
+    bytecode: &'a BytecodeGraph,
+    classes: HashSet<String>,
+    members: HashSet<(String, String)>,
+}
+
+impl BytecodeReachability<'_> {
+    /// Whether the bytecode reaches a declaration; None when it is not a
+    /// class or member, or its class is not among the compiled classes
+    pub fn is_reachable(&self, decl: &Declaration, graph: &Graph) -> Option<bool> {
+        if decl.kind.is_type() {
+            let fqn = decl.fully_qualified_name.as_ref()?;
+            if !self.bytecode.classes.contains_key(fqn) {
+                return None;
+            }
+            return Some(self.classes.contains(fqn));
+        }
+        if !decl.kind.is_member() && decl.kind != DeclarationKind::Function {
+            return None;
+        }
+
+        let owner = owner_fqn(decl, graph)?;
+        let class = self.bytecode.classes.get(&owner)?;
+        let names = member_names(decl);
+        let compiled = names.iter().any(|name| {
+            class.fields.contains(name) || class.methods.iter().any(|m| &m.name == name)
+        });
+        if !compiled {
+            // Inlined, synthesized or renamed by the compiler
+            return None;
+        }
+        Some(
+            names
+                .into_iter()
+                .any(|name| self.members.contains(&(owner.clone(), name))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bytecode::classfile::{build_class_with_methods, utf8_payload};
+    use crate::graph::{Language, Location};
+    use std::path::PathBuf;
+
+    fn declaration(name: &str, fqn: &str, kind: DeclarationKind, line: usize) -> Declaration {
+        let file = PathBuf::from("Formatter.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            kind,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        );
+        decl.fully_qualified_name = Some(fqn.to_string());
+        decl
+    }
+
+    #[test]
+    fn test_bytecode_reachability() {
+        // Formatter.format calls Formatter.pad; Formatter.legacy calls nothing
+        let class = build_class_with_methods(
+            &[
+                (1, utf8_payload("com/example/Formatter")), // 1
+                (7, vec![0, 1]),                            // 2
+                (1, utf8_payload("java/lang/Object")),      // 3
+                (7, vec![0, 3]),                            // 4
+                (1, utf8_payload("Code")),                  // 5
+                (1, utf8_payload("format")),                // 6
+                (1, utf8_payload("pad")),                   // 7
+                (1, utf8_payload("legacy")),                // 8
+                (12, vec![0, 7, 0, 7]),                     // 9
+                (10, vec![0, 2, 0, 9]),                     // 10: Formatter.pad
+            ],
+            2,
+            4,
+            &[
+                (6, 5, vec![0xb8, 0, 10, 0xb1]),
+                (7, 5, vec![0xb1]),
+                (8, 5, vec![0xb1]),
+            ],
+        );
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Formatter.class"), class).unwrap();
+
+        let mut bytecode = BytecodeGraph::default();
+        bytecode.add_path(dir.path()).unwrap();
+        assert_eq!(bytecode.class_count(), 1);
+
+        let mut graph = Graph::new();
+        let formatter = graph.add_declaration(declaration(
+            "Formatter",
+            "com.example.Formatter",
+            DeclarationKind::Class,
+            1,
+        ));
+        let mut members = Vec::new();
+        for (line, name) in [(2, "format"), (3, "pad"), (4, "legacy"), (5, "inlined")] {
+            let mut decl = declaration(name, name, DeclarationKind::Method, line);
+            decl.parent = Some(formatter.clone());
+            graph.add_declaration(decl.clone());
+            members.push(decl);
+        }
+        let entry_points = HashSet::from([members[0].id.clone()]);
+
+        let reachability = bytecode.reachability(&graph, &entry_points);
+        let reachable = |decl: &Declaration| reachability.is_reachable(decl, &graph);
+        assert_eq!(
+            reachable(graph.get_declaration(&formatter).unwrap()),
+            Some(true)
+        );
+        assert_eq!(reachable(&members[1]), Some(true));
+        assert_eq!(reachable(&members[2]), Some(false));
+        assert_eq!(reachable(&members[3]), None);
+    }
+}
@@ -0,0 +1,506 @@
+// Class file reader
+//
+// Everything a class refers to is named in its constant pool: referenced
+// classes, field and method references, and the descriptors of its own
+// members. For the project's own classes the method bodies are read too, to
+// tell which method makes which reference.
+
+use miette::{miette, Result};
+use regex::Regex;
+use std::sync::OnceLock;
+
+const MAGIC: u32 = 0xCAFE_BABE;
+
+/// Names a class file refers to, as JVM internal names (`com/example/Foo$Bar`)
+#[derive(Debug, Default)]
+pub struct ClassReferences {
+    /// The class defined by the file
+    pub this_class: String,
+    /// Referenced classes, including types in descriptors
+    pub classes: Vec<String>,
+    /// Referenced fields and methods as (owner class, member name)
+    pub members: Vec<(String, String)>,
+}
+
+/// A class file's members and what the code of each method refers to, as
+/// JVM internal names
+#[derive(Debug, Default)]
+pub struct ClassCode {
+    pub this_class: String,
+    /// None for `java/lang/Object` itself
+    pub super_class: Option<String>,
+    pub interfaces: Vec<String>,
+    /// Names of the fields the class defines
+    pub fields: Vec<String>,
+    pub methods: Vec<MethodCode>,
+    /// Members referenced through method handles, which is how lambdas and
+    /// method references reach their implementation
+    pub handles: Vec<(String, String)>,
+}
+
+/// A method and the references made by its instructions
+#[derive(Debug, Default)]
+pub struct MethodCode {
+    pub name: String,
+    /// Classes instantiated, cast to or loaded as constants
+    pub classes: Vec<String>,
+    /// Fields read or written and methods invoked, as (owner class, member name)
+    pub members: Vec<(String, String)>,
+}
+
+enum Constant {
+    Utf8(String),
+    Class(u16),
+    NameAndType(u16),
+    MemberRef(u16, u16),
+    MethodHandle(u16),
+    Other,
+}
+
+/// A class file's constant pool
+struct Pool(Vec<Constant>);
+
+impl Pool {
+    fn read(reader: &mut Reader) -> Result<Self> {
+        let count = reader.u16()? as usize;
+        let mut pool: Vec<Constant> = Vec::with_capacity(count);
+        pool.push(Constant::Other); // Index 0 is unused
+        while pool.len() < count {
+            let tag = reader.u8()?;
+            let constant = match tag {
+                1 => {
+                    let len = reader.u16()? as usize;
+                    // Modified UTF-8 only differs for NUL and supplementary characters
+                    Constant::Utf8(String::from_utf8_lossy(reader.take(len)?).to_string())
+                }
+                7 => Constant::Class(reader.u16()?),
+                9..=11 => Constant::MemberRef(reader.u16()?, reader.u16()?),
+                12 => {
+                    let name = reader.u16()?;
+                    reader.skip(2)?;
+                    Constant::NameAndType(name)
+                }
+                15 => {
+                    reader.skip(1)?; // reference kind
+                    Constant::MethodHandle(reader.u16()?)
+                }
+                3 | 4 | 17 | 18 => reader.skip(4).map(|_| Constant::Other)?,
+                8 | 16 | 19 | 20 => reader.skip(2).map(|_| Constant::Other)?,
+                5 | 6 => {
+                    // Longs and doubles take two pool slots
+                    reader.skip(8)?;
+                    pool.push(Constant::Other);
+                    Constant::Other
+                }
+                other => return Err(miette!("Unknown constant pool tag {}", other)),
+            };
+            pool.push(constant);
+        }
+        Ok(Self(pool))
+    }
+
+    fn utf8(&self, index: u16) -> Option<&str> {
+        match self.0.get(index as usize) {
+            Some(Constant::Utf8(value)) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    fn class_name(&self, index: u16) -> Option<&str> {
+        match self.0.get(index as usize) {
+            Some(Constant::Class(name)) => self.utf8(*name),
+            _ => None,
+        }
+    }
+
+    /// Owner and name of a field or method reference
+    fn member(&self, index: u16) -> Option<(String, String)> {
+        let Some(Constant::MemberRef(class, name_and_type)) = self.0.get(index as usize) else {
+            return None;
+        };
+        let name = match self.0.get(*name_and_type as usize) {
+            Some(Constant::NameAndType(name)) => self.utf8(*name)?,
+            _ => return None,
+        };
+        Some((self.class_name(*class)?.to_string(), name.to_string()))
+    }
+}
+
+/// Read the constant pool of a class file
+pub fn parse_class(bytes: &[u8]) -> Result<ClassReferences> {
+    let mut reader = Reader::header(bytes)?;
+    let pool = Pool::read(&mut reader)?;
+
+    reader.skip(2)?; // access flags
+    let this_class = pool
+        .class_name(reader.u16()?)
+        .ok_or_else(|| miette!("Invalid this_class index"))?
+        .to_string();
+
+    let mut references = ClassReferences {
+        this_class,
+        ..ClassReferences::default()
+    };
+    for (index, constant) in pool.0.iter().enumerate() {
+        match constant {
+            Constant::Class(name) => {
+                if let Some(name) = pool.utf8(*name) {
+                    match name.strip_prefix('[') {
+                        Some(_) => references.classes.extend(descriptor_classes(name)),
+                        None => references.classes.push(name.to_string()),
+                    }
+                }
+            }
+            Constant::MemberRef(..) => {
+                if let Some(member) = pool.member(index as u16) {
+                    references.members.push(member);
+                }
+            }
+            // Descriptors of fields, methods and signatures of this class
+            Constant::Utf8(value) if value.contains(';') => {
+                references.classes.extend(descriptor_classes(value));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(references)
+}
+
+/// Read a class file's super types, members and method code
+pub fn parse_class_code(bytes: &[u8]) -> Result<ClassCode> {
+    let mut reader = Reader::header(bytes)?;
+    let pool = Pool::read(&mut reader)?;
+    let class_name = |index: u16| pool.class_name(index).map(str::to_string);
+
+    reader.skip(2)?; // access flags
+    let this_class =
+        class_name(reader.u16()?).ok_or_else(|| miette!("Invalid this_class index"))?;
+    let super_class = class_name(reader.u16()?);
+    let interface_count = reader.u16()?;
+    let mut interfaces = Vec::new();
+    for _ in 0..interface_count {
+        interfaces.extend(class_name(reader.u16()?));
+    }
+
+    let mut fields = Vec::new();
+    for _ in 0..reader.u16()? {
+        reader.skip(2)?; // access flags
+        let name = pool.utf8(reader.u16()?).unwrap_or_default().to_string();
+        reader.skip(2)?; // descriptor
+        for _ in 0..reader.u16()? {
+            reader.skip(2)?;
+            let len = reader.u32()? as usize;
+            reader.skip(len)?;
+        }
+        fields.push(name);
+    }
+
+    let mut methods = Vec::new();
+    for _ in 0..reader.u16()? {
+        reader.skip(2)?; // access flags
+        let mut method = MethodCode {
+            name: pool.utf8(reader.u16()?).unwrap_or_default().to_string(),
+            ..MethodCode::default()
+        };
+        reader.skip(2)?; // descriptor
+        for _ in 0..reader.u16()? {
+            let attribute = pool.utf8(reader.u16()?);
+            let len = reader.u32()? as usize;
+            let body = reader.take(len)?;
+            if attribute == Some("Code") {
+                read_code(body, &pool, &mut method)
+                    .map_err(|e| miette!("Invalid code in {}: {}", method.name, e))?;
+            }
+        }
+        methods.push(method);
+    }
+
+    let handles = pool
+        .0
+        .iter()
+        .filter_map(|constant| match constant {
+            Constant::MethodHandle(reference) => pool.member(*reference),
+            _ => None,
+        })
+        .collect();
+
+    Ok(ClassCode {
+        this_class,
+        super_class,
+        interfaces,
+        fields,
+        methods,
+        handles,
+    })
+}
+
+/// Record the classes and members the instructions of a Code attribute use
+fn read_code(attribute: &[u8], pool: &Pool, method: &mut MethodCode) -> Result<()> {
+    let mut reader = Reader {
+        bytes: attribute,
+        offset: 4, // max stack and max locals
+    };
+    let len = reader.u32()? as usize;
+    let code = reader.take(len)?;
+
+    let index_at = |pc: usize| u16::from_be_bytes([code[pc + 1], code[pc + 2]]);
+    let mut pc = 0;
+    while pc < code.len() {
+        let len = instruction_length(code, pc)?;
+        if pc + len > code.len() {
+            return Err(miette!("Truncated instruction at {}", pc));
+        }
+        match code[pc] {
+            // getstatic, putstatic, getfield, putfield and the invokes
+            0xb2..=0xb9 => method.members.extend(pool.member(index_at(pc))),
+            // new, anewarray, checkcast, instanceof, multianewarray
+            0xbb | 0xbd | 0xc0 | 0xc1 | 0xc5 => method
+                .classes
+                .extend(pool.class_name(index_at(pc)).map(str::to_string)),
+            // ldc of a class literal
+            0x12 => method
+                .classes
+                .extend(pool.class_name(code[pc + 1] as u16).map(str::to_string)),
+            0x13 => method
+                .classes
+                .extend(pool.class_name(index_at(pc)).map(str::to_string)),
+            _ => {}
+        }
+        pc += len;
+    }
+    Ok(())
+}
+
+/// Length in bytes of the instruction at `pc`, including its operands
+fn instruction_length(code: &[u8], pc: usize) -> Result<usize> {
+    let operand = |offset: usize| -> Result<i32> {
+        let b = code
+            .get(offset..offset + 4)
+            .ok_or_else(|| miette!("Truncated switch at {}", pc))?;
+        Ok(i32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    };
+    // Switch operands are aligned to four bytes from the start of the code
+    let padding = (4 - (pc + 1) % 4) % 4;
+
+    Ok(match code[pc] {
+        0x10 | 0x12 | 0x15..=0x19 | 0x36..=0x3a | 0xa9 | 0xbc => 2,
+        0x11 | 0x13 | 0x14 | 0x84 | 0x99..=0xa8 | 0xb2..=0xb8 => 3,
+        0xbb | 0xbd | 0xc0 | 0xc1 | 0xc6 | 0xc7 => 3,
+        0xc5 => 4,
+        0xb9 | 0xba | 0xc8 | 0xc9 => 5,
+        0xaa => {
+            let low = operand(pc + 1 + padding + 4)?;
+            let high = operand(pc + 1 + padding + 8)?;
+            let count = (high as i64 - low as i64 + 1).max(0) as usize;
+            1 + padding + 12 + count * 4
+        }
+        0xab => {
+            let pairs = operand(pc + 1 + padding + 4)?.max(0) as usize;
+            1 + padding + 8 + pairs * 8
+        }
+        // wide iinc has two wide operands, other wide loads and stores one
+        0xc4 => match code.get(pc + 1) {
+            Some(0x84) => 6,
+            _ => 4,
+        },
+        0x00..=0x0f | 0x1a..=0x35 | 0x3b..=0x83 | 0x85..=0x98 | 0xac..=0xb1 => 1,
+        0xbe | 0xbf | 0xc2 | 0xc3 => 1,
+        other => return Err(miette!("Unknown opcode {:#04x} at {}", other, pc)),
+    })
+}
+
+/// Class names in a descriptor or signature, e.g. `(Lcom/example/Foo;I)V`
+fn descriptor_classes(descriptor: &str) -> impl Iterator<Item = String> + '_ {
+    static OBJECT_TYPE: OnceLock<Regex> = OnceLock::new();
+    let re = OBJECT_TYPE.get_or_init(|| Regex::new(r"L([\w$]+(?:/[\w$]+)+)[;<]").unwrap());
+    re.captures_iter(descriptor).map(|cap| cap[1].to_string())
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// A reader past the magic number and version of a class file
+    fn header(bytes: &'a [u8]) -> Result<Self> {
+        let mut reader = Reader { bytes, offset: 0 };
+        if reader.u32()? != MAGIC {
+            return Err(miette!("Not a class file"));
+        }
+        reader.skip(4)?; // minor and major version
+        Ok(reader)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.offset + len;
+        let slice = self
+            .bytes
+            .get(self.offset..end)
+            .ok_or_else(|| miette!("Truncated class file"))?;
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<()> {
+        self.take(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+}
+
+/// Build a class file with the given constant pool entries, for tests
+///
+/// Entries are (tag, payload); `this_class` is the pool index of a Class entry.
+#[cfg(test)]
+pub fn build_class(pool: &[(u8, Vec<u8>)], this_class: u16) -> Vec<u8> {
+    build_class_with_methods(pool, this_class, 0, &[])
+}
+
+/// Build a class file with methods, for tests
+///
+/// Methods are (pool index of the name, pool index of the Utf8 `Code`,
+/// instructions); descriptors point at the method name, which is never read.
+#[cfg(test)]
+pub fn build_class_with_methods(
+    pool: &[(u8, Vec<u8>)],
+    this_class: u16,
+    super_class: u16,
+    methods: &[(u16, u16, Vec<u8>)],
+) -> Vec<u8> {
+    let mut out = MAGIC.to_be_bytes().to_vec();
+    out.extend([0, 0, 0, 52]);
+    // Longs and doubles take two slots
+    let slots: usize = pool
+        .iter()
+        .map(|(tag, _)| if matches!(tag, 5 | 6) { 2 } else { 1 })
+        .sum();
+    out.extend((slots as u16 + 1).to_be_bytes());
+    for (tag, payload) in pool {
+        out.push(*tag);
+        out.extend(payload);
+    }
+    out.extend([0, 33]);
+    out.extend(this_class.to_be_bytes());
+    out.extend(super_class.to_be_bytes());
+    out.extend([0, 0, 0, 0]); // interfaces and fields
+    out.extend((methods.len() as u16).to_be_bytes());
+    for (name, code_name, code) in methods {
+        out.extend([0, 1]);
+        out.extend(name.to_be_bytes());
+        out.extend(name.to_be_bytes());
+        out.extend([0, 1]);
+        out.extend(code_name.to_be_bytes());
+        out.extend((code.len() as u32 + 12).to_be_bytes());
+        out.extend([0, 2, 0, 2]);
+        out.extend((code.len() as u32).to_be_bytes());
+        out.extend(code);
+        out.extend([0, 0, 0, 0]); // exception table and attributes
+    }
+    out.extend([0, 0]);
+    out
+}
+
+/// Payload of a CONSTANT_Utf8 entry, for tests
+#[cfg(test)]
+pub fn utf8_payload(value: &str) -> Vec<u8> {
+    let mut payload = (value.len() as u16).to_be_bytes().to_vec();
+    payload.extend(value.as_bytes());
+    payload
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_constant_pool() {
+        let class = build_class(
+            &[
+                (1, utf8_payload("com/consumer/Screen")),    // 1
+                (7, vec![0, 1]),                             // 2: this class
+                (1, utf8_payload("com/example/Formatter")),  // 3
+                (7, vec![0, 3]),                             // 4
+                (1, utf8_payload("format")),                 // 5
+                (1, utf8_payload("(Lcom/example/Value;)V")), // 6
+                (12, vec![0, 5, 0, 6]),                      // 7
+                (10, vec![0, 4, 0, 7]),                      // 8: Formatter.format
+                (5, vec![0; 8]),                             // 9-10: long
+                (1, utf8_payload("Ljava/util/List<Lcom/example/Item;>;")), // 11
+            ],
+            2,
+        );
+
+        let references = parse_class(&class).unwrap();
+        assert_eq!(references.this_class, "com/consumer/Screen");
+        assert!(references
+            .classes
+            .contains(&"com/example/Formatter".to_string()));
+        assert!(references
+            .classes
+            .contains(&"com/example/Value".to_string()));
+        assert!(references.classes.contains(&"com/example/Item".to_string()));
+        assert_eq!(
+            references.members,
+            vec![("com/example/Formatter".to_string(), "format".to_string())]
+        );
+        assert!(parse_class(b"\x00\x01").is_err());
+    }
+
+    #[test]
+    fn test_parse_method_code() {
+        let class = build_class_with_methods(
+            &[
+                (1, utf8_payload("com/example/Screen")),    // 1
+                (7, vec![0, 1]),                            // 2: this class
+                (1, utf8_payload("java/lang/Object")),      // 3
+                (7, vec![0, 3]),                            // 4: super class
+                (1, utf8_payload("Code")),                  // 5
+                (1, utf8_payload("render")),                // 6
+                (1, utf8_payload("com/example/Formatter")), // 7
+                (7, vec![0, 7]),                            // 8
+                (1, utf8_payload("format")),                // 9
+                (12, vec![0, 9, 0, 9]),                     // 10
+                (10, vec![0, 8, 0, 10]),                    // 11: Formatter.format
+                (15, vec![6, 0, 11]),                       // 12: handle to it
+            ],
+            2,
+            4,
+            &[(
+                6,
+                5,
+                vec![
+                    0xbb, 0, 8,    // new Formatter
+                    0x59, // dup
+                    0xaa, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                    0, // tableswitch, one case
+                    0xb8, 0, 11,   // invokestatic Formatter.format
+                    0xb1, // return
+                ],
+            )],
+        );
+
+        let code = parse_class_code(&class).unwrap();
+        assert_eq!(code.this_class, "com/example/Screen");
+        assert_eq!(code.super_class.as_deref(), Some("java/lang/Object"));
+        assert_eq!(code.methods.len(), 1);
+        assert_eq!(code.methods[0].name, "render");
+        assert_eq!(code.methods[0].classes, vec!["com/example/Formatter"]);
+        let format = ("com/example/Formatter".to_string(), "format".to_string());
+        assert_eq!(code.methods[0].members, vec![format.clone()]);
+        assert_eq!(code.handles, vec![format]);
+    }
+}
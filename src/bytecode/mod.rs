@@ -0,0 +1,241 @@
+// Bytecode scanning
+//
+// Declarations used only by prebuilt .jar/.aar dependencies (plugins, SDK
+// callbacks, code compiled by another build) have no references in the code
+// graph. This module reads the constant pools of the classes in those
+// artifacts and records which classes and members they refer to, so the
+// matching declarations can be treated as entry points.
+//
+// The project's own compiled classes are read as a call graph instead, to
+// cross-check the findings of the source graph (see `callgraph`).
+
+mod archive;
+mod callgraph;
+mod classfile;
+
+pub use callgraph::BytecodeGraph;
+use classfile::{parse_class, ClassReferences};
+
+use crate::graph::{Declaration, DeclarationKind, Graph};
+use miette::{miette, IntoDiagnostic, Result};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Classes and members referenced by prebuilt artifacts
+#[derive(Debug, Clone, Default)]
+pub struct ExternalUsage {
+    /// Referenced classes as fully qualified names (nested classes use `.`)
+    pub classes: HashSet<String>,
+    /// Referenced member names, keyed by the fully qualified name of their owner
+    pub members: HashMap<String, HashSet<String>>,
+    /// Number of class files read
+    pub class_count: usize,
+}
+
+impl ExternalUsage {
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty() && self.members.is_empty()
+    }
+
+    /// Record the references of a .jar, .aar or .class file
+    pub fn add_artifact(&mut self, path: &Path) -> Result<()> {
+        let bytes = std::fs::read(path).into_diagnostic()?;
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        match extension {
+            "class" => self.add_classes(vec![parse_class(&bytes)?]),
+            "jar" | "aar" => self.add_classes(read_archive(&bytes)?),
+            _ => return Err(miette!("Expected a .jar, .aar or .class file")),
+        }
+        Ok(())
+    }
+
+    /// Record references from the classes of one artifact; classes the
+    /// artifact defines itself are not usages of ours
+    fn add_classes(&mut self, classes: Vec<ClassReferences>) {
+        let defined: HashSet<&str> = classes.iter().map(|c| c.this_class.as_str()).collect();
+        for class in &classes {
+            for name in &class.classes {
+                if !defined.contains(name.as_str()) {
+                    self.classes.insert(class_fqn(name));
+                }
+            }
+            for (owner, member) in &class.members {
+                if !defined.contains(owner.as_str()) {
+                    self.members
+                        .entry(class_fqn(owner))
+                        .or_default()
+                        .insert(member.clone());
+                }
+            }
+        }
+        self.class_count += classes.len();
+    }
+
+    /// Whether a declaration is referenced by any scanned artifact
+    pub fn uses(&self, decl: &Declaration, graph: &Graph) -> bool {
+        if decl.kind.is_type() {
+            return decl
+                .fully_qualified_name
+                .as_deref()
+                .is_some_and(|fqn| self.classes.contains(fqn));
+        }
+        if !decl.kind.is_member() && decl.kind != DeclarationKind::Function {
+            return false;
+        }
+
+        let Some(names) = owner_fqn(decl, graph).and_then(|owner| self.members.get(&owner)) else {
+            return false;
+        };
+        member_names(decl).iter().any(|name| names.contains(name))
+    }
+}
+
+/// Parse every class in an archive, including nested jars such as an aar's
+/// `classes.jar` and `libs/*.jar`
+fn read_archive(bytes: &[u8]) -> Result<Vec<ClassReferences>> {
+    let entries = archive::read_entries(bytes, |name| {
+        name.ends_with(".class") || name.ends_with(".jar")
+    })?;
+
+    let mut classes = Vec::new();
+    for entry in entries {
+        if entry.name.ends_with(".jar") {
+            classes.extend(read_archive(&entry.data)?);
+        } else if !entry.name.ends_with("module-info.class") {
+            let class = parse_class(&entry.data)
+                .map_err(|e| miette!("Failed to parse {}: {}", entry.name, e))?;
+            classes.push(class);
+        }
+    }
+    Ok(classes)
+}
+
+/// `com/example/Outer$Inner` -> `com.example.Outer.Inner`
+fn class_fqn(internal: &str) -> String {
+    internal.replace(['/', '$'], ".")
+}
+
+/// The JVM class a member compiles into: its enclosing type, or the `<File>Kt`
+/// facade class for Kotlin top-level declarations
+fn owner_fqn(decl: &Declaration, graph: &Graph) -> Option<String> {
+    if let Some(parent) = decl.parent.as_ref().and_then(|p| graph.get_declaration(p)) {
+        return parent.fully_qualified_name.clone();
+    }
+
+    let stem = decl.location.file.file_stem()?.to_str()?;
+    let mut facade = String::new();
+    let mut chars = stem.chars();
+    if let Some(first) = chars.next() {
+        facade.extend(first.to_uppercase());
+        facade.push_str(chars.as_str());
+    }
+    facade.push_str("Kt");
+
+    let package = decl
+        .fully_qualified_name
+        .as_deref()
+        .and_then(|fqn| fqn.strip_suffix(decl.name.as_str()))
+        .and_then(|prefix| prefix.strip_suffix('.'));
+    Some(match package {
+        Some(package) => format!("{}.{}", package, facade),
+        None => facade,
+    })
+}
+
+/// Names a member can be referenced by from bytecode
+fn member_names(decl: &Declaration) -> Vec<String> {
+    match decl.kind {
+        DeclarationKind::Constructor => vec!["<init>".to_string()],
+        _ => std::iter::once(decl.name.clone())
+            .chain(decl.jvm_names())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bytecode::classfile::{build_class, utf8_payload};
+    use crate::graph::{DeclarationId, Language, Location};
+    use std::path::PathBuf;
+
+    fn declaration(name: &str, fqn: &str, kind: DeclarationKind, file: &str) -> Declaration {
+        let path = PathBuf::from(file);
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), name.len(), name.len() + 10),
+            name.to_string(),
+            kind,
+            Location::new(path, 1, 1, 0, 10),
+            Language::Kotlin,
+        );
+        decl.fully_qualified_name = Some(fqn.to_string());
+        decl
+    }
+
+    #[test]
+    fn test_external_usage_from_jar() {
+        // A plugin class calling com.example.HostKt.register and Host.Callback.onEvent
+        let class = build_class(
+            &[
+                (1, utf8_payload("com/plugin/Plugin")),
+                (7, vec![0, 1]),
+                (1, utf8_payload("com/example/HostKt")),
+                (7, vec![0, 3]),
+                (1, utf8_payload("register")),
+                (1, utf8_payload("()V")),
+                (12, vec![0, 5, 0, 6]),
+                (10, vec![0, 4, 0, 7]),
+                (1, utf8_payload("com/example/Host$Callback")),
+                (7, vec![0, 9]),
+                (1, utf8_payload("onEvent")),
+                (12, vec![0, 11, 0, 6]),
+                (11, vec![0, 10, 0, 12]),
+            ],
+            2,
+        );
+        let jar = archive::build_stored(&[("com/plugin/Plugin.class", &class[..])]);
+        let aar = archive::build_stored(&[("classes.jar", &jar[..])]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plugin.aar");
+        std::fs::write(&path, aar).unwrap();
+
+        let mut usage = ExternalUsage::default();
+        usage.add_artifact(&path).unwrap();
+        assert_eq!(usage.class_count, 1);
+        assert!(!usage.classes.contains("com.plugin.Plugin"));
+
+        let mut graph = Graph::new();
+        let callback = declaration(
+            "Callback",
+            "com.example.Host.Callback",
+            DeclarationKind::Interface,
+            "Host.kt",
+        );
+        let callback_id = graph.add_declaration(callback.clone());
+        let mut on_event = declaration(
+            "onEvent",
+            "com.example.onEvent",
+            DeclarationKind::Method,
+            "Host.kt",
+        );
+        on_event.parent = Some(callback_id);
+        let register = declaration(
+            "register",
+            "com.example.register",
+            DeclarationKind::Function,
+            "host.kt",
+        );
+        let unused = declaration(
+            "unregister",
+            "com.example.unregister",
+            DeclarationKind::Function,
+            "host.kt",
+        );
+
+        assert!(usage.uses(&callback, &graph));
+        assert!(usage.uses(&on_event, &graph));
+        assert!(usage.uses(&register, &graph));
+        assert!(!usage.uses(&unused, &graph));
+    }
+}
@@ -5,6 +5,13 @@
 
 #![allow(dead_code)] // Cache infrastructure for future incremental analysis
 
+mod prime;
+
+pub use prime::CachePrimer;
+
+use crate::discovery::NormalizedPath;
+use crate::graph::{Declaration, UnresolvedReference};
+use crate::parser::ParseResult;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -109,6 +116,25 @@ pub struct FileCacheEntry {
     pub unresolved_references: Vec<CachedReference>,
 }
 
+impl FileCacheEntry {
+    /// Build a cache entry from a file's parse result
+    pub fn from_parse_result(metadata: FileMetadata, result: &ParseResult) -> Self {
+        Self {
+            metadata,
+            declarations: result
+                .declarations
+                .iter()
+                .map(CachedDeclaration::from_declaration)
+                .collect(),
+            unresolved_references: result
+                .references
+                .iter()
+                .map(|reference| CachedReference::from_unresolved(&result.declarations, reference))
+                .collect(),
+        }
+    }
+}
+
 /// Simplified declaration for caching
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedDeclaration {
@@ -125,6 +151,24 @@ pub struct CachedDeclaration {
     pub language: String,
 }
 
+impl CachedDeclaration {
+    pub fn from_declaration(decl: &Declaration) -> Self {
+        Self {
+            id: decl.id.to_string(),
+            name: decl.name.clone(),
+            kind: format!("{:?}", decl.kind),
+            line: decl.location.line,
+            column: decl.location.column,
+            fully_qualified_name: decl.fully_qualified_name.clone(),
+            parent_id: decl.parent.as_ref().map(|p| p.to_string()),
+            annotations: decl.annotations.clone(),
+            modifiers: decl.modifiers.clone(),
+            visibility: format!("{:?}", decl.visibility),
+            language: format!("{:?}", decl.language),
+        }
+    }
+}
+
 /// Simplified reference for caching
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedReference {
@@ -134,6 +178,34 @@ pub struct CachedReference {
     pub line: usize,
 }
 
+impl CachedReference {
+    /// Attribute a reference to the innermost declaration containing it
+    pub fn from_unresolved(declarations: &[Declaration], reference: &UnresolvedReference) -> Self {
+        let offset = reference.location.start_byte;
+        let from = declarations
+            .iter()
+            .filter(|d| d.id.start <= offset && d.id.end >= offset)
+            .min_by_key(|d| d.id.end - d.id.start)
+            .or_else(|| declarations.first());
+
+        Self {
+            from_id: from.map(|d| d.id.to_string()).unwrap_or_default(),
+            target_name: reference
+                .qualified_name
+                .clone()
+                .unwrap_or_else(|| reference.name.clone()),
+            kind: format!("{:?}", reference.kind),
+            line: reference.location.line,
+        }
+    }
+}
+
+/// Key of a file in the cache: its path relative to the project root with
+/// `/` separators, so a cache written on one OS is reused on another
+pub fn cache_key(file_path: &Path, project_root: &Path) -> PathBuf {
+    PathBuf::from(NormalizedPath::relative_to(file_path, project_root).as_str())
+}
+
 /// The complete cache structure
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AnalysisCache {
@@ -141,7 +213,7 @@ pub struct AnalysisCache {
     pub version: u32,
     /// Project root path
     pub project_root: PathBuf,
-    /// Cached file data, keyed by relative path
+    /// Cached file data, keyed by normalized relative path (see [`cache_key`])
     pub files: HashMap<PathBuf, FileCacheEntry>,
     /// Timestamp when cache was created
     pub created_at: u64,
@@ -194,9 +266,7 @@ impl AnalysisCache {
 
     /// Check if a file needs re-parsing
     pub fn needs_reparse(&self, file_path: &Path, project_root: &Path) -> bool {
-        let relative = file_path.strip_prefix(project_root).unwrap_or(file_path);
-
-        match self.files.get(relative) {
+        match self.files.get(&cache_key(file_path, project_root)) {
             Some(entry) => {
                 // Quick check first
                 if !entry.metadata.quick_changed(file_path) {
@@ -211,17 +281,12 @@ impl AnalysisCache {
 
     /// Get cached entry for a file
     pub fn get_entry(&self, file_path: &Path, project_root: &Path) -> Option<&FileCacheEntry> {
-        let relative = file_path.strip_prefix(project_root).unwrap_or(file_path);
-        self.files.get(relative)
+        self.files.get(&cache_key(file_path, project_root))
     }
 
     /// Update cache entry for a file
     pub fn update_entry(&mut self, file_path: &Path, project_root: &Path, entry: FileCacheEntry) {
-        let relative = file_path
-            .strip_prefix(project_root)
-            .unwrap_or(file_path)
-            .to_path_buf();
-        self.files.insert(relative, entry);
+        self.files.insert(cache_key(file_path, project_root), entry);
     }
 
     /// Remove entries for files that no longer exist
@@ -264,6 +329,94 @@ impl std::fmt::Display for CacheStats {
     }
 }
 
+/// Caches of several project roots used together
+///
+/// Each root keeps its own cache file, so a library checkout primed once can
+/// be shared by every app analyzed with it. Files are looked up in the cache
+/// of the innermost root containing them.
+pub struct WorkspaceCache {
+    caches: Vec<(PathBuf, PathBuf, AnalysisCache)>,
+}
+
+impl WorkspaceCache {
+    /// Load the cache at the default path of each root, starting empty for
+    /// roots without a usable cache
+    pub fn load(roots: &[PathBuf]) -> Self {
+        Self::load_from(
+            roots
+                .iter()
+                .map(|root| (root.clone(), AnalysisCache::default_cache_path(root))),
+        )
+    }
+
+    /// Load the cache of each root from the given path
+    pub fn load_from(roots: impl IntoIterator<Item = (PathBuf, PathBuf)>) -> Self {
+        let caches = roots
+            .into_iter()
+            .map(|(root, cache_path)| {
+                let cache = AnalysisCache::load(&cache_path)
+                    .unwrap_or_else(|_| AnalysisCache::new(root.clone()));
+                (root, cache_path, cache)
+            })
+            .collect();
+        Self { caches }
+    }
+
+    /// Roots, cache paths and caches
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&Path, &Path, &mut AnalysisCache)> {
+        self.caches
+            .iter_mut()
+            .map(|(root, cache_path, cache)| (root.as_path(), cache_path.as_path(), cache))
+    }
+
+    /// Root and cache responsible for a file
+    fn cache_for(&self, file_path: &Path) -> Option<(&Path, &AnalysisCache)> {
+        self.caches
+            .iter()
+            .filter(|(root, _, _)| file_path.starts_with(root))
+            .max_by_key(|(root, _, _)| root.components().count())
+            .map(|(root, _, cache)| (root.as_path(), cache))
+    }
+
+    /// Get the cached entry for a file from its root's cache
+    pub fn get_entry(&self, file_path: &Path) -> Option<&FileCacheEntry> {
+        let (root, cache) = self.cache_for(file_path)?;
+        cache.get_entry(file_path, root)
+    }
+
+    /// Check if a file needs re-parsing; files outside every root always do
+    pub fn needs_reparse(&self, file_path: &Path) -> bool {
+        match self.cache_for(file_path) {
+            Some((root, cache)) => cache.needs_reparse(file_path, root),
+            None => true,
+        }
+    }
+
+    /// Save every cache to its own path
+    pub fn save(&self) -> Result<(), CacheError> {
+        for (_, cache_path, cache) in &self.caches {
+            cache.save(cache_path)?;
+        }
+        Ok(())
+    }
+
+    /// Statistics of all caches combined
+    pub fn stats(&self) -> CacheStats {
+        let mut stats = CacheStats {
+            total_files: 0,
+            total_declarations: 0,
+            total_references: 0,
+        };
+        for (_, _, cache) in &self.caches {
+            let cache_stats = cache.stats();
+            stats.total_files += cache_stats.total_files;
+            stats.total_declarations += cache_stats.total_declarations;
+            stats.total_references += cache_stats.total_references;
+        }
+        stats
+    }
+}
+
 /// Incremental analyzer that uses caching
 pub struct IncrementalAnalyzer {
     cache: AnalysisCache,
@@ -388,4 +541,35 @@ mod tests {
         let loaded = AnalysisCache::load(&cache_path).unwrap();
         assert_eq!(loaded.files.len(), 1);
     }
+
+    #[test]
+    fn test_workspace_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let app = temp_dir.path().join("app");
+        let library = temp_dir.path().join("library");
+        fs::create_dir_all(&app).unwrap();
+        fs::create_dir_all(&library).unwrap();
+        let feed = library.join("Feed.kt");
+        fs::write(&feed, "class Feed").unwrap();
+
+        let mut library_cache = AnalysisCache::new(library.clone());
+        library_cache.update_entry(
+            &feed,
+            &library,
+            FileCacheEntry {
+                metadata: FileMetadata::from_path(&feed).unwrap(),
+                declarations: vec![],
+                unresolved_references: vec![],
+            },
+        );
+        library_cache
+            .save(&AnalysisCache::default_cache_path(&library))
+            .unwrap();
+
+        let workspace = WorkspaceCache::load(&[app.clone(), library]);
+        assert!(workspace.get_entry(&feed).is_some());
+        assert!(!workspace.needs_reparse(&feed));
+        assert!(workspace.needs_reparse(&app.join("Main.kt")));
+        assert_eq!(workspace.stats().total_files, 1);
+    }
 }
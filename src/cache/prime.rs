@@ -0,0 +1,217 @@
+//! Cache priming for CI images
+//!
+//! `searchdeadcode prime-cache` discovers and parses every source file and
+//! writes the results to the cache without running any analysis, so a nightly
+//! job can bake a hot cache into the image used by pull request jobs.
+
+use super::{cache_key, AnalysisCache, FileCacheEntry, FileMetadata};
+use crate::config::Config;
+use crate::discovery::{FileFinder, FileType, SourceFile};
+use crate::parser::{JavaParser, KotlinParser, ParseResult, Parser as SourceParser};
+use miette::{IntoDiagnostic, Result};
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Fills an [`AnalysisCache`] from the project's sources
+pub struct CachePrimer<'a> {
+    config: &'a Config,
+    root: PathBuf,
+}
+
+/// Outcome of a priming run
+#[derive(Debug, Clone, Default)]
+pub struct PrimeStats {
+    /// Source files found by discovery
+    pub discovered: usize,
+    /// Files parsed because they were new or changed
+    pub parsed: usize,
+    /// Files whose existing cache entry was still valid
+    pub reused: usize,
+    /// Files that could not be read or parsed
+    pub failed: usize,
+    /// Entries dropped because their file no longer exists
+    pub pruned: usize,
+}
+
+impl std::fmt::Display for PrimeStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} files discovered: {} parsed, {} reused, {} failed, {} pruned",
+            self.discovered, self.parsed, self.reused, self.failed, self.pruned
+        )
+    }
+}
+
+/// Consistency of a cache against the current sources
+#[derive(Debug, Clone, Default)]
+pub struct CacheVerification {
+    /// Entries whose file is unchanged
+    pub up_to_date: usize,
+    /// Entries whose file changed since it was cached
+    pub stale: Vec<PathBuf>,
+    /// Entries whose file no longer exists
+    pub missing: Vec<PathBuf>,
+    /// Source files without a cache entry
+    pub uncached: Vec<PathBuf>,
+}
+
+impl CacheVerification {
+    /// Whether every source file has an up-to-date entry and nothing else is cached
+    pub fn is_consistent(&self) -> bool {
+        self.stale.is_empty() && self.missing.is_empty() && self.uncached.is_empty()
+    }
+}
+
+impl<'a> CachePrimer<'a> {
+    pub fn new(config: &'a Config, root: PathBuf) -> Self {
+        Self { config, root }
+    }
+
+    /// Discover the source files that can be cached
+    fn discover(&self) -> Result<Vec<SourceFile>> {
+        FileFinder::new(self.config).find_source_files(&self.root)
+    }
+
+    /// Parse new or changed files into `cache` and drop entries of deleted files
+    pub fn prime(&self, cache: &mut AnalysisCache) -> Result<PrimeStats> {
+        let files = self.discover()?;
+        let mut stats = PrimeStats {
+            discovered: files.len(),
+            ..PrimeStats::default()
+        };
+
+        let to_parse: Vec<&SourceFile> = files
+            .iter()
+            .filter(|f| cache.needs_reparse(&f.path, &self.root))
+            .collect();
+        stats.reused = files.len() - to_parse.len();
+
+        let entries: Vec<(PathBuf, Result<FileCacheEntry>)> = to_parse
+            .par_iter()
+            .map(|file| (file.path.clone(), parse_entry(file)))
+            .collect();
+
+        for (path, entry) in entries {
+            match entry {
+                Ok(entry) => {
+                    cache.update_entry(&path, &self.root, entry);
+                    stats.parsed += 1;
+                }
+                Err(e) => {
+                    debug!("Failed to cache {}: {}", path.display(), e);
+                    stats.failed += 1;
+                }
+            }
+        }
+
+        let before = cache.files.len();
+        let discovered: HashSet<PathBuf> = files
+            .iter()
+            .map(|f| cache_key(&f.path, &self.root))
+            .collect();
+        cache
+            .files
+            .retain(|relative, _| discovered.contains(relative));
+        stats.pruned = before - cache.files.len();
+
+        Ok(stats)
+    }
+
+    /// Compare `cache` with the current sources without modifying it
+    pub fn verify(&self, cache: &AnalysisCache) -> Result<CacheVerification> {
+        let files = self.discover()?;
+        let mut verification = CacheVerification::default();
+
+        let mut discovered = HashSet::new();
+        for file in &files {
+            let relative = cache_key(&file.path, &self.root);
+
+            if cache.get_entry(&file.path, &self.root).is_none() {
+                verification.uncached.push(relative.clone());
+            } else if cache.needs_reparse(&file.path, &self.root) {
+                verification.stale.push(relative.clone());
+            } else {
+                verification.up_to_date += 1;
+            }
+            discovered.insert(relative);
+        }
+
+        verification.missing = cache
+            .files
+            .keys()
+            .filter(|relative| !discovered.contains(*relative))
+            .cloned()
+            .collect();
+
+        verification.stale.sort();
+        verification.missing.sort();
+        verification.uncached.sort();
+        Ok(verification)
+    }
+}
+
+/// Parse a single file into a cache entry
+fn parse_entry(file: &SourceFile) -> Result<FileCacheEntry> {
+    let metadata = FileMetadata::from_path(&file.path).into_diagnostic()?;
+    let contents = file.read_contents()?;
+
+    let result: ParseResult = match file.file_type {
+        FileType::Kotlin => KotlinParser::new().parse(&file.path, &contents)?,
+        FileType::Java => JavaParser::new().parse(&file.path, &contents)?,
+        _ => ParseResult::new(),
+    };
+
+    Ok(FileCacheEntry::from_parse_result(metadata, &result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_verify_reports_stale_missing_and_uncached() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        fs::write(root.join("Kept.kt"), "class Kept").unwrap();
+        fs::write(root.join("Changed.kt"), "class Changed").unwrap();
+        fs::write(root.join("New.kt"), "class New").unwrap();
+
+        let config = Config::default();
+        let primer = CachePrimer::new(&config, root.clone());
+        let mut cache = AnalysisCache::new(root.clone());
+        for name in ["Kept.kt", "Changed.kt"] {
+            let path = root.join(name);
+            let entry = FileCacheEntry {
+                metadata: FileMetadata::from_path(&path).unwrap(),
+                declarations: vec![],
+                unresolved_references: vec![],
+            };
+            cache.update_entry(&path, &root, entry);
+        }
+        cache
+            .files
+            .get_mut(Path::new("Changed.kt"))
+            .unwrap()
+            .metadata = FileMetadata {
+            mtime: 0,
+            size: 0,
+            content_hash: "0".to_string(),
+        };
+        let deleted = cache.files[Path::new("Kept.kt")].clone();
+        cache.files.insert(PathBuf::from("Deleted.kt"), deleted);
+
+        let verification = primer.verify(&cache).unwrap();
+
+        assert!(!verification.is_consistent());
+        assert_eq!(verification.up_to_date, 1);
+        assert_eq!(verification.stale, vec![PathBuf::from("Changed.kt")]);
+        assert_eq!(verification.missing, vec![PathBuf::from("Deleted.kt")]);
+        assert_eq!(verification.uncached, vec![PathBuf::from("New.kt")]);
+    }
+}
@@ -1,8 +1,14 @@
 // Configuration loader - some methods reserved for future use
 #![allow(dead_code)]
 
+use super::RetainRule;
+use crate::analysis::detectors::ConstValue;
+use crate::analysis::metrics::package_of;
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue, Severity};
+use crate::graph::{Declaration, DeclarationKind, Visibility};
 use miette::{IntoDiagnostic, Result, WrapErr};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 /// Configuration for SearchDeadCode analysis
@@ -15,10 +21,23 @@ pub struct Config {
     /// Patterns to exclude from analysis
     pub exclude: Vec<String>,
 
+    /// Gitignore-style globs; when set, only matching files are analyzed, even
+    /// if an ignore file excludes them (`exclude` still applies)
+    pub include: Vec<String>,
+
     /// Patterns to retain - never report as dead code
     pub retain_patterns: Vec<String>,
 
-    /// Explicit entry points (fully qualified class names)
+    /// Structured retain rules matched against annotations, supertypes,
+    /// packages and names
+    pub retain_rules: Vec<RetainRule>,
+
+    /// What retaining a class (via pattern or entry point) keeps of its
+    /// members; deep analysis keeps none unless a retain rule says otherwise
+    pub retain_members: RetainMembers,
+
+    /// Explicit entry points: fully qualified or simple names, patterns with
+    /// `*` (`com.example.api.*`) or annotations (`@PublicApi`)
     pub entry_points: Vec<String>,
 
     /// Report configuration
@@ -27,8 +46,32 @@ pub struct Config {
     /// Detection configuration
     pub detection: DetectionConfig,
 
+    /// Per-detector enable/disable and severity/confidence overrides
+    pub detectors: DetectorsConfig,
+
     /// Android-specific configuration
     pub android: AndroidConfig,
+
+    /// File discovery configuration
+    pub discovery: DiscoveryConfig,
+
+    /// Confidence downgrade for code near reflection
+    pub reflection: ReflectionConfig,
+
+    /// Event bus subscriber and post site linkage
+    pub event_bus: EventBusConfig,
+
+    /// Inline suppressions by comment or annotation
+    pub suppression: SuppressionConfig,
+
+    /// Matching of coverage reports to source files
+    pub coverage: CoverageConfig,
+
+    /// Severity overrides per package
+    pub severity: SeverityConfig,
+
+    /// External detector plugins run over the reference graph
+    pub plugins: Vec<PluginConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,6 +118,424 @@ pub struct DetectionConfig {
     pub redundant_public: bool,
 }
 
+/// Retention propagation from a kept class to its members
+///
+/// Applies to the members of every reachable class, and through them to
+/// nested classes. Retain rules can set a different policy for the classes
+/// they keep (see [`RetainRule::members`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RetainMembers {
+    /// Keep every member of a reachable class
+    #[default]
+    All,
+    /// Keep public members, plus members the runtime uses implicitly
+    PublicOnly,
+    /// Keep annotated members, plus members the runtime uses implicitly
+    AnnotatedOnly,
+    /// Keep only members that are referenced, plus members the runtime uses
+    /// implicitly (constructors, overrides, enum entries, companion objects)
+    ReferencedOnly,
+    /// Keep no member without a reference, not even the implicitly used ones
+    /// (the default of deep analysis). Serialization hooks like `CREATOR` and
+    /// `serialVersionUID` are still kept under every policy
+    None,
+}
+
+impl RetainMembers {
+    /// Whether a member of a reachable declaration is kept without a reference
+    pub fn retains(&self, member: &Declaration) -> bool {
+        match self {
+            RetainMembers::All => true,
+            RetainMembers::PublicOnly => {
+                member.visibility == Visibility::Public || Self::implicitly_used(member)
+            }
+            RetainMembers::AnnotatedOnly => {
+                !member.annotations.is_empty() || Self::implicitly_used(member)
+            }
+            RetainMembers::ReferencedOnly => Self::implicitly_used(member),
+            RetainMembers::None => false,
+        }
+    }
+
+    /// Members the runtime calls or creates without a reference in the code
+    fn implicitly_used(member: &Declaration) -> bool {
+        matches!(
+            member.kind,
+            DeclarationKind::Constructor | DeclarationKind::Parameter | DeclarationKind::EnumCase
+        ) || member.is_abstract
+            || member
+                .modifiers
+                .iter()
+                .any(|m| m == "override" || m == "companion" || m == "abstract")
+            || member.annotations.iter().any(|a| a.contains("Override"))
+    }
+}
+
+/// Settings for a single detector in the `detectors` section
+///
+/// ```yaml
+/// detectors:
+///   unused_params:
+///     enabled: true
+///     severity: warning
+///   unreferenced:
+///     confidence: high
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DetectorSettings {
+    /// Enable or disable the detector (unset: CLI flag or built-in default)
+    pub enabled: Option<bool>,
+
+    /// Override the severity of the detector's findings
+    pub severity: Option<Severity>,
+
+    /// Override the confidence of the detector's findings
+    pub confidence: Option<Confidence>,
+}
+
+/// Per-detector configuration, replacing the individual CLI flags
+///
+/// Severity and confidence overrides apply to detectors whose findings are part
+/// of the dead code report; the others (resources, extras, ...) only use `enabled`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DetectorsConfig {
+    /// Unreferenced declarations found by reachability analysis (always on)
+    pub unreferenced: DetectorSettings,
+    pub unused_params: DetectorSettings,
+    pub write_only: DetectorSettings,
+    pub sealed_variants: DetectorSettings,
+    pub unused_enum_cases: DetectorSettings,
+    pub dead_branches: DetectorSettings,
+    pub redundant_overrides: DetectorSettings,
+    pub redundant_public: DetectorSettings,
+    pub unused_imports: DetectorSettings,
+    pub unused_typealiases: DetectorSettings,
+    pub property_accessors: DetectorSettings,
+    pub only_used_in_tests: DetectorSettings,
+    pub unused_resources: DetectorSettings,
+    pub unused_extras: DetectorSettings,
+    pub unused_bundle_args: DetectorSettings,
+    pub unused_broadcasts: DetectorSettings,
+    pub write_only_prefs: DetectorSettings,
+    pub unused_pref_keys: DetectorSettings,
+    pub write_only_dao: DetectorSettings,
+    pub unused_module_deps: DetectorSettings,
+    pub unused_nav_args: DetectorSettings,
+    pub deep_links: DetectorSettings,
+    pub cycles: DetectorSettings,
+    pub dead_doc_links: DetectorSettings,
+    pub compose_state: DetectorSettings,
+    pub preview_only: DetectorSettings,
+    pub uninvoked_params: DetectorSettings,
+    pub dead_objects: DetectorSettings,
+}
+
+impl DetectorSettings {
+    /// Whether an optional detector runs: enabled by CLI flag or by config
+    pub fn is_enabled(&self, cli_flag: bool) -> bool {
+        cli_flag || self.enabled == Some(true)
+    }
+}
+
+impl DetectorsConfig {
+    /// Settings of a detector by its registry id (e.g. `unused-params`)
+    pub fn settings(&self, id: &str) -> Option<&DetectorSettings> {
+        match id {
+            "unused-params" => Some(&self.unused_params),
+            "write-only" => Some(&self.write_only),
+            "sealed-variants" => Some(&self.sealed_variants),
+            "unused-enum-cases" => Some(&self.unused_enum_cases),
+            "dead-branches" => Some(&self.dead_branches),
+            "redundant-overrides" => Some(&self.redundant_overrides),
+            "redundant-public" => Some(&self.redundant_public),
+            "unused-imports" => Some(&self.unused_imports),
+            "unused-typealiases" => Some(&self.unused_typealiases),
+            "property-accessors" => Some(&self.property_accessors),
+            "only-used-in-tests" => Some(&self.only_used_in_tests),
+            "unused-resources" => Some(&self.unused_resources),
+            "unused-extras" => Some(&self.unused_extras),
+            "unused-bundle-args" => Some(&self.unused_bundle_args),
+            "unused-broadcasts" => Some(&self.unused_broadcasts),
+            "write-only-prefs" => Some(&self.write_only_prefs),
+            "unused-pref-keys" => Some(&self.unused_pref_keys),
+            "write-only-dao" => Some(&self.write_only_dao),
+            "unused-module-deps" => Some(&self.unused_module_deps),
+            "unused-nav-args" => Some(&self.unused_nav_args),
+            "deep-links" => Some(&self.deep_links),
+            "cycles" => Some(&self.cycles),
+            "dead-doc-links" => Some(&self.dead_doc_links),
+            "compose-state" => Some(&self.compose_state),
+            "preview-only" => Some(&self.preview_only),
+            "uninvoked-params" => Some(&self.uninvoked_params),
+            "dead-objects" => Some(&self.dead_objects),
+            _ => None,
+        }
+    }
+
+    /// Settings of the detector that reports an issue type
+    pub fn settings_for(&self, issue: DeadCodeIssue) -> Option<&DetectorSettings> {
+        match issue {
+            DeadCodeIssue::Unreferenced => Some(&self.unreferenced),
+            DeadCodeIssue::AssignOnly => Some(&self.write_only),
+            DeadCodeIssue::UnusedParameter => Some(&self.unused_params),
+            DeadCodeIssue::UnusedImport => Some(&self.unused_imports),
+            DeadCodeIssue::UnusedSealedVariant => Some(&self.sealed_variants),
+            DeadCodeIssue::UnusedEnumCase => Some(&self.unused_enum_cases),
+            DeadCodeIssue::RedundantOverride => Some(&self.redundant_overrides),
+            DeadCodeIssue::RedundantPublic => Some(&self.redundant_public),
+            DeadCodeIssue::WriteOnlyPreference => Some(&self.write_only_prefs),
+            DeadCodeIssue::WriteOnlyDao => Some(&self.write_only_dao),
+            DeadCodeIssue::UnusedTypeAlias => Some(&self.unused_typealiases),
+            DeadCodeIssue::VarCouldBeVal
+            | DeadCodeIssue::UnusedGetter
+            | DeadCodeIssue::UnusedSetter => Some(&self.property_accessors),
+            DeadCodeIssue::OnlyUsedInTests => Some(&self.only_used_in_tests),
+            DeadCodeIssue::OnlyUsedInPreviews => Some(&self.preview_only),
+            DeadCodeIssue::DeadBranch => Some(&self.dead_branches),
+            DeadCodeIssue::DeadDocLink => Some(&self.dead_doc_links),
+            DeadCodeIssue::UnusedComposeState | DeadCodeIssue::UnusedComposableDefault => {
+                Some(&self.compose_state)
+            }
+            DeadCodeIssue::UninvokedFunctionParameter => Some(&self.uninvoked_params),
+            DeadCodeIssue::EffectivelyDeadObject => Some(&self.dead_objects),
+            DeadCodeIssue::CustomRule | DeadCodeIssue::DeadFile => None,
+        }
+    }
+
+    /// Apply overrides to findings, dropping those of disabled detectors
+    pub fn apply(&self, dead_code: &mut Vec<DeadCode>) {
+        dead_code.retain(|dc| {
+            self.settings_for(dc.issue)
+                .map(|settings| settings.enabled != Some(false))
+                .unwrap_or(true)
+        });
+
+        for dc in dead_code.iter_mut() {
+            let Some(settings) = self.settings_for(dc.issue) else {
+                continue;
+            };
+            if let Some(severity) = settings.severity {
+                dc.severity = severity;
+            }
+            // Runtime-confirmed findings keep their confirmed confidence
+            if let (Some(confidence), false) = (settings.confidence, dc.runtime_confirmed) {
+                dc.confidence = confidence;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiscoveryConfig {
+    /// Respect .gitignore, .ignore and .hgignore (glob syntax) files
+    pub respect_ignore_files: bool,
+
+    /// Directory names that are never descended into
+    pub skip_dirs: Vec<String>,
+
+    /// Number of walker threads (0 picks one based on available cores)
+    pub threads: usize,
+
+    /// Also parse KAPT/KSP output under `build/generated`; its references
+    /// keep handwritten code alive, but it is never reported
+    pub generated_sources: bool,
+}
+
+/// Findings in files that use reflection (`Class.forName`, `::class.java`,
+/// `kotlin.reflect`, `Method.invoke`) get a lower confidence
+///
+/// ```yaml
+/// reflection:
+///   packages:
+///     com.example.plugins: package   # reflection anywhere in the package
+///     com.example.debug: ignore
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReflectionConfig {
+    /// Scan source files for reflection
+    pub enabled: bool,
+
+    /// Scope of the downgrade per package and its subpackages; the longest
+    /// matching package wins, others use `file`
+    pub packages: BTreeMap<String, ReflectionScope>,
+}
+
+/// Event buses (greenrobot EventBus, Otto, custom ones) deliver events to
+/// subscriber methods by the type of their parameter
+///
+/// ```yaml
+/// event_bus:
+///   subscribe_annotations: [Subscribe, OnEvent]
+///   post_methods: [post, postSticky, dispatch]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventBusConfig {
+    /// Link post sites to subscribers; when off every subscriber is kept
+    pub enabled: bool,
+
+    /// Annotations marking subscriber methods
+    pub subscribe_annotations: Vec<String>,
+
+    /// Methods of the bus that post an event
+    pub post_methods: Vec<String>,
+}
+
+/// Findings of a declaration under a `// searchdeadcode:ignore` comment or
+/// carrying one of the annotations are suppressed, as are its members'
+///
+/// ```yaml
+/// suppression:
+///   comments: true
+///   annotations: ['Suppress("DeadCode")', DeadCodeOk]
+///   action: downgrade
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SuppressionConfig {
+    /// Honor `// searchdeadcode:ignore` comments
+    pub comments: bool,
+
+    /// Annotations suppressing findings; arguments, when given, must all
+    /// appear in the annotation's arguments
+    pub annotations: Vec<String>,
+
+    /// What happens to suppressed findings
+    pub action: SuppressionAction,
+}
+
+/// Findings in a package and its subpackages report with the package's
+/// severity, whatever the detector; the longest matching package wins. Applied
+/// after the per-detector overrides, so teams can adopt enforcement one
+/// package at a time
+///
+/// ```yaml
+/// severity:
+///   packages:
+///     com.example.experimental.*: info
+///     com.example.payments.*: error
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeverityConfig {
+    /// Severity per package (`com.example.payments` or `com.example.payments.*`)
+    pub packages: BTreeMap<String, Severity>,
+}
+
+impl SeverityConfig {
+    /// Severity for findings in a package, if one of the packages covers it
+    pub fn severity_for(&self, package: &str) -> Option<Severity> {
+        self.packages
+            .iter()
+            .map(|(pattern, severity)| (pattern.trim_end_matches(".*"), severity))
+            .filter(|(prefix, _)| {
+                package
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, severity)| *severity)
+    }
+
+    /// Override the severity of findings in the configured packages
+    pub fn apply(&self, dead_code: &mut [DeadCode]) {
+        if self.packages.is_empty() {
+            return;
+        }
+        for dc in dead_code.iter_mut() {
+            if let Some(severity) = package_of(dc).and_then(|p| self.severity_for(&p)) {
+                dc.severity = severity;
+            }
+        }
+    }
+}
+
+/// Coverage entries are matched to source files by declared package and
+/// path; an entry under a source root is compared by its path below the root
+///
+/// ```yaml
+/// coverage:
+///   source_roots: [app/src/main/java, core/src/main/kotlin]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CoverageConfig {
+    /// Directories, relative to the project root, holding sources by
+    /// package path
+    pub source_roots: Vec<PathBuf>,
+}
+
+/// An external detector run as a subprocess
+///
+/// The plugin reads the reference graph as JSON on stdin and writes its
+/// findings as JSON to stdout (see `analysis::detectors::plugin`):
+///
+/// ```yaml
+/// plugins:
+///   - name: legacy-analytics
+///     command: ./tools/legacy-analytics
+///     args: [--strict]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginConfig {
+    /// Name shown in findings and errors
+    pub name: String,
+
+    /// Program to run, resolved from the project root
+    pub command: String,
+
+    /// Arguments passed to the program
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// What happens to a suppressed finding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SuppressionAction {
+    /// Drop it from the findings; reports still count it
+    #[default]
+    Exclude,
+    /// Keep it with a lower confidence
+    Downgrade,
+}
+
+/// Which findings reflection in a file downgrades
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReflectionScope {
+    /// Findings in the same file
+    #[default]
+    File,
+    /// Findings anywhere in the file's package
+    Package,
+    /// None: the package's reflection is known not to reach dead code
+    Ignore,
+}
+
+impl ReflectionConfig {
+    /// Scope for findings in a package (`None` for the default package)
+    pub fn scope(&self, package: Option<&str>) -> ReflectionScope {
+        let package = package.unwrap_or_default();
+        self.packages
+            .iter()
+            .filter(|(prefix, _)| {
+                package == prefix.as_str()
+                    || package
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with('.'))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, scope)| *scope)
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct AndroidConfig {
@@ -84,11 +545,30 @@ pub struct AndroidConfig {
     /// Parse layout XMLs for class references
     pub parse_layouts: bool,
 
+    /// Parse proguard-rules.pro / consumer-rules.pro for -keep rules
+    pub parse_keep_rules: bool,
+
+    /// Scan C/C++ sources for JNI references (FindClass, RegisterNatives, ...)
+    pub parse_native_sources: bool,
+
     /// Auto-retain Android component patterns
     pub auto_retain_components: bool,
 
     /// Additional component patterns to retain
     pub component_patterns: Vec<String>,
+
+    /// BuildConfig field values of the analyzed variant, used to evaluate
+    /// branch conditions (default: a release build, `DEBUG: false`)
+    pub build_config: BTreeMap<String, ConstValue>,
+
+    /// Build variant to analyze (e.g. `freeDebug`): only its source sets are
+    /// discovered, and it sets `DEBUG`, `BUILD_TYPE` and `FLAVOR` in `build_config`
+    pub variant: Option<String>,
+
+    /// Keep composables alive only through calls, with `@Preview` functions
+    /// as IDE-only entry points, instead of treating every `@Composable` as
+    /// an entry point
+    pub compose_previews: bool,
 }
 
 impl Default for Config {
@@ -101,11 +581,22 @@ impl Default for Config {
                 "**/.gradle/**".to_string(),
                 "**/.idea/**".to_string(),
             ],
+            include: vec![],
             retain_patterns: vec![],
+            retain_rules: vec![],
+            retain_members: RetainMembers::default(),
             entry_points: vec![],
             report: ReportConfig::default(),
             detection: DetectionConfig::default(),
+            detectors: DetectorsConfig::default(),
             android: AndroidConfig::default(),
+            discovery: DiscoveryConfig::default(),
+            reflection: ReflectionConfig::default(),
+            event_bus: EventBusConfig::default(),
+            suppression: SuppressionConfig::default(),
+            coverage: CoverageConfig::default(),
+            severity: SeverityConfig::default(),
+            plugins: vec![],
         }
     }
 }
@@ -136,11 +627,57 @@ impl Default for DetectionConfig {
     }
 }
 
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            respect_ignore_files: true,
+            skip_dirs: vec![
+                "node_modules".to_string(),
+                "build".to_string(),
+                "generated".to_string(),
+            ],
+            threads: 0,
+            generated_sources: false,
+        }
+    }
+}
+
+impl Default for ReflectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            packages: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for EventBusConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            subscribe_annotations: vec!["Subscribe".to_string()],
+            post_methods: vec!["post".to_string(), "postSticky".to_string()],
+        }
+    }
+}
+
+impl Default for SuppressionConfig {
+    fn default() -> Self {
+        Self {
+            comments: true,
+            annotations: vec!["Suppress(\"DeadCode\")".to_string()],
+            action: SuppressionAction::default(),
+        }
+    }
+}
+
 impl Default for AndroidConfig {
     fn default() -> Self {
         Self {
             parse_manifest: true,
             parse_layouts: true,
+            parse_keep_rules: true,
+            parse_native_sources: true,
             auto_retain_components: true,
             component_patterns: vec![
                 "*Activity".to_string(),
@@ -151,6 +688,9 @@ impl Default for AndroidConfig {
                 "*ViewModel".to_string(),
                 "*Application".to_string(),
             ],
+            build_config: BTreeMap::from([("DEBUG".to_string(), ConstValue::Bool(false))]),
+            variant: None,
+            compose_previews: false,
         }
     }
 }
@@ -324,4 +864,226 @@ mod tests {
         assert!(config.detection.unused_class);
         assert!(config.android.parse_manifest);
     }
+
+    #[test]
+    fn test_retain_members_policy() {
+        use crate::graph::{DeclarationId, Language, Location};
+
+        let config: Config = serde_yaml::from_str("retain_members: referenced-only\n").unwrap();
+        assert_eq!(config.retain_members, RetainMembers::ReferencedOnly);
+        assert_eq!(Config::default().retain_members, RetainMembers::All);
+
+        let make = |name: &str, kind: DeclarationKind| {
+            let path = PathBuf::from("Test.kt");
+            Declaration::new(
+                DeclarationId::new(path.clone(), 0, 1),
+                name.to_string(),
+                kind,
+                Location::new(path, 1, 1, 0, 1),
+                Language::Kotlin,
+            )
+        };
+        let helper = make("helper", DeclarationKind::Method);
+        let mut on_create = make("onCreate", DeclarationKind::Method);
+        on_create.modifiers.push("override".to_string());
+
+        assert!(RetainMembers::All.retains(&helper));
+        assert!(!RetainMembers::ReferencedOnly.retains(&helper));
+        assert!(RetainMembers::ReferencedOnly.retains(&on_create));
+        assert!(
+            RetainMembers::ReferencedOnly.retains(&make("<init>", DeclarationKind::Constructor))
+        );
+
+        let config: Config = serde_yaml::from_str("retain_members: annotated-only\n").unwrap();
+        assert_eq!(config.retain_members, RetainMembers::AnnotatedOnly);
+        let mut bind = make("bind", DeclarationKind::Method);
+        bind.annotations.push("@BindingAdapter".to_string());
+        let mut hidden = make("hidden", DeclarationKind::Method);
+        hidden.visibility = Visibility::Private;
+        assert!(RetainMembers::AnnotatedOnly.retains(&bind));
+        assert!(!RetainMembers::AnnotatedOnly.retains(&helper));
+        assert!(RetainMembers::PublicOnly.retains(&helper));
+        assert!(!RetainMembers::PublicOnly.retains(&hidden));
+        assert!(!RetainMembers::None.retains(&on_create));
+    }
+
+    #[test]
+    fn test_retain_rules_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[[retain_rules]]
+annotated_with = "@Keep"
+
+[[retain_rules]]
+extends = "android.app.Activity"
+package = "com.example.generated.*"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.retain_rules.len(), 2);
+        assert_eq!(
+            config.retain_rules[0].annotated_with.as_deref(),
+            Some("@Keep")
+        );
+        assert_eq!(
+            config.retain_rules[1].package.as_deref(),
+            Some("com.example.generated.*")
+        );
+        assert!(config.retain_rules[1].name_regex.is_none());
+    }
+
+    #[test]
+    fn test_detectors_section_yaml() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+detectors:
+  unused_params:
+    enabled: true
+    severity: warning
+  write_only:
+    enabled: false
+  unreferenced:
+    confidence: high
+"#,
+        )
+        .unwrap();
+
+        let detectors = &config.detectors;
+        assert!(detectors.unused_params.is_enabled(false));
+        assert!(!detectors.write_only.is_enabled(false));
+        assert!(detectors.write_only.is_enabled(true));
+        assert_eq!(detectors.unused_params.severity, Some(Severity::Warning));
+        assert_eq!(detectors.unreferenced.confidence, Some(Confidence::High));
+    }
+
+    #[test]
+    fn test_detectors_section_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[detectors.sealed_variants]
+enabled = true
+confidence = "low"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.detectors.sealed_variants.enabled, Some(true));
+        assert_eq!(
+            config.detectors.sealed_variants.confidence,
+            Some(Confidence::Low)
+        );
+        // Other settings keep their defaults
+        assert!(!config.exclude.is_empty());
+    }
+
+    #[test]
+    fn test_reflection_scope_per_package() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+reflection:
+  packages:
+    com.example: package
+    com.example.debug: ignore
+"#,
+        )
+        .unwrap();
+
+        let reflection = &config.reflection;
+        assert!(reflection.enabled);
+        assert_eq!(
+            reflection.scope(Some("com.example")),
+            ReflectionScope::Package
+        );
+        assert_eq!(
+            reflection.scope(Some("com.example.ui")),
+            ReflectionScope::Package
+        );
+        assert_eq!(
+            reflection.scope(Some("com.example.debug.tools")),
+            ReflectionScope::Ignore
+        );
+        assert_eq!(
+            reflection.scope(Some("com.examples")),
+            ReflectionScope::File
+        );
+        assert_eq!(reflection.scope(None), ReflectionScope::File);
+    }
+
+    #[test]
+    fn test_apply_detector_overrides() {
+        use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+
+        let make = |name: &str, issue: DeadCodeIssue| {
+            let path = PathBuf::from("Test.kt");
+            let decl = Declaration::new(
+                DeclarationId::new(path.clone(), 0, 1),
+                name.to_string(),
+                DeclarationKind::Function,
+                Location::new(path, 1, 1, 0, 1),
+                Language::Kotlin,
+            );
+            DeadCode::new(decl, issue)
+        };
+
+        let detectors = DetectorsConfig {
+            unused_params: DetectorSettings {
+                severity: Some(Severity::Error),
+                ..Default::default()
+            },
+            redundant_overrides: DetectorSettings {
+                enabled: Some(false),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut findings = vec![
+            make("param", DeadCodeIssue::UnusedParameter),
+            make("onStart", DeadCodeIssue::RedundantOverride),
+            make("helper", DeadCodeIssue::Unreferenced),
+        ];
+        detectors.apply(&mut findings);
+
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_severity_per_package() {
+        use crate::graph::{DeclarationId, Language, Location};
+
+        let config: Config = serde_yaml::from_str(
+            "severity:\n  packages:\n    com.example.experimental.*: info\n    com.example.payments: error\n    com.example.payments.legacy.*: warning\n",
+        )
+        .unwrap();
+        let severity = &config.severity;
+        assert_eq!(
+            severity.severity_for("com.example.experimental.search"),
+            Some(Severity::Info)
+        );
+        assert_eq!(
+            severity.severity_for("com.example.payments"),
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            severity.severity_for("com.example.payments.legacy.v1"),
+            Some(Severity::Warning)
+        );
+        assert_eq!(severity.severity_for("com.example.paymentsx"), None);
+
+        let file = PathBuf::from("Checkout.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(file.clone(), 0, 1),
+            "charge".to_string(),
+            DeclarationKind::Function,
+            Location::new(file, 1, 1, 0, 1),
+            Language::Kotlin,
+        );
+        decl.fully_qualified_name = Some("com.example.payments.charge".to_string());
+        let mut findings = vec![DeadCode::new(decl, DeadCodeIssue::Unreferenced)];
+        severity.apply(&mut findings);
+        assert_eq!(findings[0].severity, Severity::Error);
+    }
 }
@@ -1,3 +1,10 @@
 mod loader;
+mod retain;
+mod rules;
 
-pub use loader::Config;
+pub use loader::{
+    Config, DetectorsConfig, EventBusConfig, PluginConfig, ReflectionConfig, ReflectionScope,
+    RetainMembers, SuppressionAction, SuppressionConfig,
+};
+pub use retain::RetainRule;
+pub use rules::RuleSet;
@@ -0,0 +1,258 @@
+//! Structured retain rules
+//!
+//! Unlike name-based `retain_patterns`, a [`RetainRule`] is evaluated against
+//! the graph, so framework-instantiated hierarchies can be kept precisely:
+//!
+//! ```yaml
+//! retain_rules:
+//!   - annotated_with: "@Keep"
+//!   - extends: android.app.Activity
+//!   - implements: java.io.Serializable
+//!     package: com.example.model.*
+//!   - name_regex: "^test[A-Z].*"
+//!   - annotated_with: "@JsonClass"
+//!     members: annotated-only
+//! ```
+//!
+//! All matchers set on a rule must match; a rule without matchers matches
+//! nothing. `members` overrides `retain_members` for the classes the rule
+//! keeps and the classes nested in them.
+
+use super::RetainMembers;
+use crate::graph::{simple_type_name, Declaration, Graph};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// A declaration matcher in the `retain_rules` section
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetainRule {
+    /// Annotation on the declaration, with or without `@` or package
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotated_with: Option<String>,
+
+    /// Superclass anywhere in the declaration's hierarchy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+
+    /// Interface anywhere in the declaration's hierarchy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub implements: Option<String>,
+
+    /// Package, where a trailing `.*` also matches subpackages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+
+    /// Regex the whole simple name must match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_regex: Option<String>,
+
+    /// Which members of the matched classes are kept without a reference,
+    /// instead of `retain_members` (in deep mode, instead of keeping none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members: Option<RetainMembers>,
+}
+
+impl RetainRule {
+    /// Compile the rule for matching against a graph
+    pub fn compile(&self) -> Result<RetainMatcher<'_>, regex::Error> {
+        let name_regex = self
+            .name_regex
+            .as_deref()
+            .map(|re| Regex::new(&format!("^(?:{})$", re)))
+            .transpose()?;
+        Ok(RetainMatcher {
+            rule: self,
+            name_regex,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.annotated_with.is_none()
+            && self.extends.is_none()
+            && self.implements.is_none()
+            && self.package.is_none()
+            && self.name_regex.is_none()
+    }
+}
+
+impl std::fmt::Display for RetainRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = [
+            ("annotated_with", &self.annotated_with),
+            ("extends", &self.extends),
+            ("implements", &self.implements),
+            ("package", &self.package),
+            ("name_regex", &self.name_regex),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.as_ref().map(|v| format!("{} = {}", key, v)))
+        .collect();
+        write!(f, "{{ {} }}", parts.join(", "))
+    }
+}
+
+/// A [`RetainRule`] with its regex compiled
+pub struct RetainMatcher<'a> {
+    rule: &'a RetainRule,
+    name_regex: Option<Regex>,
+}
+
+impl<'a> RetainMatcher<'a> {
+    pub fn rule(&self) -> &'a RetainRule {
+        self.rule
+    }
+
+    /// Whether the declaration matches every matcher of the rule
+    pub fn matches(&self, decl: &Declaration, graph: &Graph) -> bool {
+        let rule = self.rule;
+        if rule.is_empty() {
+            return false;
+        }
+
+        if let Some(annotation) = &rule.annotated_with {
+            let expected = simple_type_name(annotation);
+            let found = decl
+                .annotations
+                .iter()
+                .any(|a| simple_type_name(a) == expected);
+            if !found {
+                return false;
+            }
+        }
+
+        if let Some(regex) = &self.name_regex {
+            if !regex.is_match(&decl.name) {
+                return false;
+            }
+        }
+
+        if let Some(package) = &rule.package {
+            if !package_matches(package, &package_of(decl, graph)) {
+                return false;
+            }
+        }
+
+        for super_type in [&rule.extends, &rule.implements].into_iter().flatten() {
+            if !graph.inherits_from(decl, super_type) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Package of the top-level declaration enclosing `decl`
+fn package_of(decl: &Declaration, graph: &Graph) -> String {
+    let mut top = decl;
+    while let Some(parent) = top.parent.as_ref().and_then(|p| graph.get_declaration(p)) {
+        top = parent;
+    }
+
+    top.fully_qualified_name
+        .as_deref()
+        .and_then(|fqn| fqn.rsplit_once('.'))
+        .map(|(package, _)| package.to_string())
+        .unwrap_or_default()
+}
+
+fn package_matches(pattern: &str, package: &str) -> bool {
+    match pattern.strip_suffix(".*") {
+        Some(prefix) => {
+            package == prefix
+                || package
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with('.'))
+        }
+        None => package == pattern,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, DeclarationKind, Language, Location};
+    use std::path::PathBuf;
+
+    fn add_class(graph: &mut Graph, name: &str, fqn: &str, super_types: &[&str]) {
+        let path = PathBuf::from(format!("{}.kt", name));
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), 0, 10),
+            name.to_string(),
+            DeclarationKind::Class,
+            Location::new(path, 1, 1, 0, 10),
+            Language::Kotlin,
+        );
+        decl.fully_qualified_name = Some(fqn.to_string());
+        decl.super_types = super_types.iter().map(|s| s.to_string()).collect();
+        if name.starts_with("Kept") {
+            decl.annotations
+                .push("@androidx.annotation.Keep".to_string());
+        }
+        graph.add_declaration(decl);
+    }
+
+    fn matches(rule: &RetainRule, graph: &Graph, name: &str) -> bool {
+        let matcher = rule.compile().unwrap();
+        graph
+            .find_by_name(name)
+            .iter()
+            .any(|decl| matcher.matches(decl, graph))
+    }
+
+    #[test]
+    fn test_retain_rule_matchers() {
+        let mut graph = Graph::new();
+        add_class(
+            &mut graph,
+            "BaseActivity",
+            "com.example.ui.BaseActivity",
+            &["Activity()"],
+        );
+        add_class(
+            &mut graph,
+            "HomeActivity",
+            "com.example.ui.home.HomeActivity",
+            &["BaseActivity()"],
+        );
+        add_class(
+            &mut graph,
+            "KeptModel",
+            "com.example.model.KeptModel",
+            &["Serializable"],
+        );
+        add_class(&mut graph, "Helper", "com.example.util.Helper", &[]);
+
+        let extends = RetainRule {
+            extends: Some("android.app.Activity".to_string()),
+            ..RetainRule::default()
+        };
+        assert!(matches(&extends, &graph, "HomeActivity"));
+        assert!(!matches(&extends, &graph, "Helper"));
+
+        let annotated = RetainRule {
+            annotated_with: Some("@Keep".to_string()),
+            ..RetainRule::default()
+        };
+        assert!(matches(&annotated, &graph, "KeptModel"));
+        assert!(!matches(&annotated, &graph, "Helper"));
+
+        let combined = RetainRule {
+            implements: Some("java.io.Serializable".to_string()),
+            package: Some("com.example.*".to_string()),
+            name_regex: Some("Kept.*".to_string()),
+            ..RetainRule::default()
+        };
+        assert!(matches(&combined, &graph, "KeptModel"));
+
+        let package = RetainRule {
+            package: Some("com.example.ui".to_string()),
+            ..RetainRule::default()
+        };
+        assert!(matches(&package, &graph, "BaseActivity"));
+        assert!(!matches(&package, &graph, "HomeActivity"));
+
+        assert!(!matches(&RetainRule::default(), &graph, "Helper"));
+    }
+}
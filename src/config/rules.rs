@@ -0,0 +1,364 @@
+//! Custom rules
+//!
+//! Project-specific rules are loaded from `.searchdeadcode-rules.toml` (or the
+//! file passed to `--rules`) and run as one generic detector over the graph:
+//!
+//! ```toml
+//! [[rule]]
+//! id = "uncalled-legacy"
+//! description = "Legacy classes nobody instantiates"
+//! kind = ["class"]
+//! name_regex = ".*Legacy"
+//! message = "{kind} '{name}' is legacy code without callers"
+//!
+//! [rule.references]
+//! kinds = ["call", "instantiation"]
+//! max = 0
+//! ```
+//!
+//! All predicates set on a rule must match; a rule without predicates matches
+//! nothing.
+
+use crate::analysis::Severity;
+use crate::graph::{simple_type_name, Declaration, DeclarationKind, Graph, ReferenceKind};
+use miette::{miette, Context, IntoDiagnostic, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Default rule file name, looked up in the project root
+pub const RULES_FILE_NAME: &str = ".searchdeadcode-rules.toml";
+
+/// A rule file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleSet {
+    #[serde(rename = "rule")]
+    pub rules: Vec<CustomRule>,
+}
+
+/// A user-defined rule
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomRule {
+    /// Id shown in findings
+    pub id: String,
+
+    pub description: Option<String>,
+
+    /// Finding message; `{name}`, `{kind}` and `{fqn}` are replaced
+    pub message: Option<String>,
+
+    /// Severity of the findings (warning if unset)
+    pub severity: Option<Severity>,
+
+    /// Declaration kinds the rule applies to (`class`, `method`, `type alias`, ...)
+    pub kind: Vec<String>,
+
+    /// Regex the whole simple name must match
+    pub name_regex: Option<String>,
+
+    /// Annotation on the declaration, with or without `@` or package
+    pub annotated_with: Option<String>,
+
+    /// Bounds on the number of incoming references
+    pub references: Option<ReferenceCount>,
+}
+
+/// Bounds on the number of incoming references, counting only `kinds` if set
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReferenceCount {
+    /// Reference kinds to count (`call`, `read`, `instantiation`, ...)
+    pub kinds: Vec<String>,
+    pub min: Option<usize>,
+    pub max: Option<usize>,
+}
+
+impl RuleSet {
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to read rule file: {}", path.display()))?;
+        Self::parse(&contents).wrap_err_with(|| format!("Invalid rule file: {}", path.display()))
+    }
+
+    /// Parse and validate a rule file
+    pub fn parse(contents: &str) -> Result<Self> {
+        let rule_set: Self = toml::from_str(contents).into_diagnostic()?;
+        for rule in &rule_set.rules {
+            rule.compile()?;
+        }
+        Ok(rule_set)
+    }
+
+    /// The rule file in the project root, if there is one
+    pub fn discover(project_root: &Path) -> Option<PathBuf> {
+        let path = project_root.join(RULES_FILE_NAME);
+        path.is_file().then_some(path)
+    }
+
+    /// Compile every rule for matching against a graph
+    pub fn compile(&self) -> Result<Vec<RuleMatcher<'_>>> {
+        self.rules.iter().map(CustomRule::compile).collect()
+    }
+}
+
+impl CustomRule {
+    /// Compile the rule, rejecting unknown kinds and invalid regexes
+    pub fn compile(&self) -> Result<RuleMatcher<'_>> {
+        if self.id.is_empty() {
+            return Err(miette!("Rule without an id"));
+        }
+
+        let kinds = self
+            .kind
+            .iter()
+            .map(|kind| {
+                parse_declaration_kind(kind).ok_or_else(|| {
+                    miette!("Rule '{}': unknown declaration kind '{}'", self.id, kind)
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let name_regex = self
+            .name_regex
+            .as_deref()
+            .map(|re| Regex::new(&format!("^(?:{})$", re)))
+            .transpose()
+            .map_err(|e| miette!("Rule '{}': invalid name_regex: {}", self.id, e))?;
+
+        let reference_kinds = self
+            .references
+            .iter()
+            .flat_map(|references| &references.kinds)
+            .map(|kind| {
+                parse_reference_kind(kind)
+                    .ok_or_else(|| miette!("Rule '{}': unknown reference kind '{}'", self.id, kind))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(RuleMatcher {
+            rule: self,
+            kinds,
+            name_regex,
+            reference_kinds,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.kind.is_empty()
+            && self.name_regex.is_none()
+            && self.annotated_with.is_none()
+            && self.references.is_none()
+    }
+
+    /// Message for a declaration matching the rule
+    pub fn message_for(&self, decl: &Declaration) -> String {
+        match &self.message {
+            Some(template) => template
+                .replace("{name}", &decl.name)
+                .replace("{kind}", decl.kind.display_name())
+                .replace(
+                    "{fqn}",
+                    decl.fully_qualified_name.as_deref().unwrap_or(&decl.name),
+                ),
+            None => format!(
+                "{} '{}' matches rule '{}'",
+                decl.kind.display_name(),
+                decl.name,
+                self.id
+            ),
+        }
+    }
+}
+
+/// A [`CustomRule`] with its kinds and regex resolved
+pub struct RuleMatcher<'a> {
+    rule: &'a CustomRule,
+    kinds: Vec<DeclarationKind>,
+    name_regex: Option<Regex>,
+    reference_kinds: Vec<ReferenceKind>,
+}
+
+impl<'a> RuleMatcher<'a> {
+    pub fn rule(&self) -> &'a CustomRule {
+        self.rule
+    }
+
+    /// Whether the declaration matches every predicate of the rule
+    pub fn matches(&self, decl: &Declaration, graph: &Graph) -> bool {
+        let rule = self.rule;
+        if rule.is_empty() {
+            return false;
+        }
+
+        if !self.kinds.is_empty() && !self.kinds.contains(&decl.kind) {
+            return false;
+        }
+
+        if let Some(regex) = &self.name_regex {
+            if !regex.is_match(&decl.name) {
+                return false;
+            }
+        }
+
+        if let Some(annotation) = &rule.annotated_with {
+            let expected = simple_type_name(annotation);
+            if !decl
+                .annotations
+                .iter()
+                .any(|a| simple_type_name(a) == expected)
+            {
+                return false;
+            }
+        }
+
+        if let Some(references) = &rule.references {
+            let count = graph
+                .get_references_to(&decl.id)
+                .into_iter()
+                .filter(|(_, reference)| {
+                    self.reference_kinds.is_empty()
+                        || self.reference_kinds.contains(&reference.kind)
+                })
+                .count();
+            if references.min.is_some_and(|min| count < min)
+                || references.max.is_some_and(|max| count > max)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Declaration kind by its display name, with `-` or `_` for spaces
+fn parse_declaration_kind(name: &str) -> Option<DeclarationKind> {
+    let name = name.to_lowercase().replace(['-', '_'], " ");
+    [
+        DeclarationKind::Class,
+        DeclarationKind::Interface,
+        DeclarationKind::Object,
+        DeclarationKind::Enum,
+        DeclarationKind::EnumCase,
+        DeclarationKind::TypeAlias,
+        DeclarationKind::Annotation,
+        DeclarationKind::Function,
+        DeclarationKind::Method,
+        DeclarationKind::Constructor,
+        DeclarationKind::Property,
+        DeclarationKind::Field,
+        DeclarationKind::Parameter,
+        DeclarationKind::Import,
+    ]
+    .into_iter()
+    .find(|kind| kind.display_name() == name)
+}
+
+/// Reference kind by its snake_case name (`call`, `type_argument`, ...)
+fn parse_reference_kind(name: &str) -> Option<ReferenceKind> {
+    let kind = match name.to_lowercase().replace('-', "_").as_str() {
+        "call" => ReferenceKind::Call,
+        "read" => ReferenceKind::Read,
+        "write" => ReferenceKind::Write,
+        "type" => ReferenceKind::Type,
+        "inheritance" => ReferenceKind::Inheritance,
+        "import" => ReferenceKind::Import,
+        "instantiation" => ReferenceKind::Instantiation,
+        "annotation" => ReferenceKind::Annotation,
+        "cast" => ReferenceKind::Cast,
+        "type_argument" => ReferenceKind::TypeArgument,
+        "return_type" => ReferenceKind::ReturnType,
+        "parameter_type" => ReferenceKind::ParameterType,
+        "override" => ReferenceKind::Override,
+        "reflection" => ReferenceKind::Reflection,
+        "extension_receiver" => ReferenceKind::ExtensionReceiver,
+        "sealed_subtype" => ReferenceKind::SealedSubtype,
+        "delegation" => ReferenceKind::Delegation,
+        "generic_argument" => ReferenceKind::GenericArgument,
+        "sam_conversion" => ReferenceKind::SamConversion,
+        "enum_iteration" => ReferenceKind::EnumIteration,
+        "enum_lookup" => ReferenceKind::EnumLookup,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, Language, Location, Reference};
+
+    fn declaration(
+        graph: &mut Graph,
+        name: &str,
+        kind: DeclarationKind,
+        start: usize,
+    ) -> Declaration {
+        let path = PathBuf::from("Legacy.kt");
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), start, start + 10),
+            name.to_string(),
+            kind,
+            Location::new(path, 1, 1, start, start + 10),
+            Language::Kotlin,
+        );
+        graph.add_declaration(decl.clone());
+        decl
+    }
+
+    #[test]
+    fn test_parse_and_match_rules() {
+        let rule_set = RuleSet::parse(
+            r#"
+            [[rule]]
+            id = "uncalled-legacy"
+            kind = ["class"]
+            name_regex = ".*Legacy"
+            message = "{kind} '{name}' has no callers"
+
+            [rule.references]
+            kinds = ["call", "instantiation"]
+            max = 0
+            "#,
+        )
+        .unwrap();
+        assert_eq!(rule_set.rules.len(), 1);
+
+        let mut graph = Graph::new();
+        let unused = declaration(&mut graph, "PaymentLegacy", DeclarationKind::Class, 0);
+        let used = declaration(&mut graph, "AuthLegacy", DeclarationKind::Class, 20);
+        let caller = declaration(&mut graph, "main", DeclarationKind::Function, 40);
+        let other = declaration(&mut graph, "Payment", DeclarationKind::Class, 60);
+        graph.add_reference(
+            &caller.id,
+            &used.id,
+            Reference::new(
+                ReferenceKind::Instantiation,
+                caller.location.clone(),
+                "AuthLegacy".to_string(),
+            ),
+        );
+
+        let matchers = rule_set.compile().unwrap();
+        let matcher = &matchers[0];
+        assert!(matcher.matches(&unused, &graph));
+        assert!(!matcher.matches(&used, &graph));
+        assert!(!matcher.matches(&other, &graph));
+        assert_eq!(
+            matcher.rule().message_for(&unused),
+            "class 'PaymentLegacy' has no callers"
+        );
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        assert!(RuleSet::parse("[[rule]]\nid = \"a\"\nkind = [\"widget\"]").is_err());
+        assert!(RuleSet::parse("[[rule]]\nid = \"a\"\nname_regex = \"(\"").is_err());
+        assert!(RuleSet::parse("[[rule]]\nkind = [\"class\"]").is_err());
+        let rule_set = RuleSet::parse("[[rule]]\nid = \"a\"\nkind = [\"type-alias\"]").unwrap();
+        assert!(rule_set.compile().is_ok());
+    }
+}
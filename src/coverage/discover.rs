@@ -0,0 +1,207 @@
+// Coverage report discovery across Gradle modules
+//
+// Multi-module builds write one JaCoCo or Kover report per module, e.g.
+// `feature/home/build/reports/kover/report.xml` or
+// `core/build/reports/jacoco/jacocoTestReport/jacocoTestReport.xml`. Rather
+// than passing each with --coverage, --coverage-auto finds every module (a
+// directory with a Gradle build file) and the coverage XML under its
+// `build/reports`. Modules with sources but no line coverage after merging
+// are blind spots: findings in them get no runtime evidence.
+
+use super::{CoverageParser, JacocoParser, KoverParser};
+use std::path::{Path, PathBuf};
+
+/// File names of Gradle build scripts
+const BUILD_FILES: &[&str] = &["build.gradle", "build.gradle.kts"];
+
+/// Directories under `build/reports` the coverage plugins write to
+const REPORT_DIRS: &[&str] = &["jacoco", "kover", "coverage"];
+
+/// A Gradle module and the coverage reports found in it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleReports {
+    /// Gradle path, e.g. `:feature:home`
+    pub module: String,
+    /// Module directory
+    pub dir: PathBuf,
+    /// JaCoCo and Kover XML reports under the module's `build/reports`
+    pub reports: Vec<PathBuf>,
+}
+
+impl ModuleReports {
+    /// Whether the module has sources of its own, as opposed to an
+    /// aggregating root or platform project
+    pub fn has_sources(&self) -> bool {
+        self.dir.join("src").is_dir()
+    }
+}
+
+/// Find the Gradle modules under `root` and their coverage reports
+pub fn discover_module_reports(root: &Path) -> Vec<ModuleReports> {
+    let walker = walkdir::WalkDir::new(root).into_iter().filter_entry(|e| {
+        let name = e.file_name().to_string_lossy();
+        e.depth() == 0 || (!name.starts_with('.') && name != "build" && name != "src")
+    });
+
+    let mut modules = Vec::new();
+    for entry in walker.flatten() {
+        let name = entry.file_name().to_string_lossy();
+        if !entry.file_type().is_file() || !BUILD_FILES.contains(&name.as_ref()) {
+            continue;
+        }
+        let Some(dir) = entry.path().parent() else {
+            continue;
+        };
+        if modules.iter().any(|m: &ModuleReports| m.dir == dir) {
+            continue;
+        }
+        modules.push(ModuleReports {
+            module: gradle_path(dir, root),
+            dir: dir.to_path_buf(),
+            reports: find_reports(&dir.join("build").join("reports")),
+        });
+    }
+
+    modules.sort_by(|a, b| a.module.cmp(&b.module));
+    modules
+}
+
+/// Gradle path of a module directory: `feature/home` is `:feature:home`
+fn gradle_path(dir: &Path, root: &Path) -> String {
+    let relative = dir.strip_prefix(root).unwrap_or(dir);
+    format!(
+        ":{}",
+        relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(":")
+    )
+}
+
+/// JaCoCo and Kover XML reports under a module's report directory
+fn find_reports(reports_dir: &Path) -> Vec<PathBuf> {
+    if !reports_dir.is_dir() {
+        return Vec::new();
+    }
+
+    let jacoco = JacocoParser::new();
+    let kover = KoverParser::new();
+    let mut reports: Vec<PathBuf> = walkdir::WalkDir::new(reports_dir)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            // Other plugins (lint, detekt) also write XML to build/reports
+            path.strip_prefix(reports_dir)
+                .ok()
+                .and_then(|relative| relative.components().next())
+                .is_some_and(|dir| {
+                    REPORT_DIRS.contains(&dir.as_os_str().to_string_lossy().as_ref())
+                })
+        })
+        .filter(|path| kover.can_parse(path) || jacoco.can_parse(path))
+        .collect();
+    reports.sort();
+    reports
+}
+
+/// Gradle paths of the modules with sources but no line coverage among the
+/// files of `covered` (source paths coverage was resolved to)
+pub fn modules_without_coverage<'a>(
+    modules: &[ModuleReports],
+    covered: impl IntoIterator<Item = &'a PathBuf>,
+) -> Vec<String> {
+    // Each covered file belongs to its innermost module
+    let mut by_depth: Vec<&ModuleReports> = modules.iter().collect();
+    by_depth.sort_by_key(|m| std::cmp::Reverse(m.dir.components().count()));
+
+    let mut has_coverage = vec![false; by_depth.len()];
+    for file in covered {
+        if let Some(index) = by_depth.iter().position(|m| file.starts_with(&m.dir)) {
+            has_coverage[index] = true;
+        }
+    }
+
+    let mut blind: Vec<String> = by_depth
+        .iter()
+        .zip(has_coverage)
+        .filter(|(module, covered)| !covered && module.has_sources())
+        .map(|(module, _)| module.module.clone())
+        .collect();
+    blind.sort();
+    blind
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const KOVER_REPORT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<report name="Kover Report">
+    <package name="com/example/home">
+        <sourcefile name="Home.kt">
+            <line nr="3" mi="0" ci="2"/>
+        </sourcefile>
+    </package>
+</report>"#;
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_discover_module_reports() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "settings.gradle.kts",
+            "include(\":feature:home\", \":core\")",
+        );
+        write(root, "build.gradle.kts", "");
+        write(root, "feature/home/build.gradle.kts", "");
+        write(
+            root,
+            "feature/home/src/main/kotlin/Home.kt",
+            "package com.example.home",
+        );
+        write(
+            root,
+            "feature/home/build/reports/kover/report.xml",
+            KOVER_REPORT,
+        );
+        write(
+            root,
+            "feature/home/build/reports/lint-results.xml",
+            "<issues/>",
+        );
+        write(root, "core/build.gradle", "");
+        write(
+            root,
+            "core/src/main/java/Core.java",
+            "package com.example.core;",
+        );
+
+        let modules = discover_module_reports(root);
+        let paths: Vec<&str> = modules.iter().map(|m| m.module.as_str()).collect();
+        assert_eq!(paths, vec![":", ":core", ":feature:home"]);
+        assert!(modules[1].reports.is_empty());
+        assert_eq!(
+            modules[2].reports,
+            vec![root.join("feature/home/build/reports/kover/report.xml")]
+        );
+
+        let covered = [root.join("feature/home/src/main/kotlin/Home.kt")];
+        // The root project has no sources of its own, so it is no blind spot
+        assert_eq!(
+            modules_without_coverage(&modules, &covered),
+            vec![":core".to_string()]
+        );
+    }
+}
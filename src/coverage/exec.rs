@@ -0,0 +1,188 @@
+// JaCoCo execution data parser
+//
+// Firebase Test Lab (`--environment-variables coverage=true`) and on-device
+// instrumentation dump raw JaCoCo `.ec` / `.exec` files instead of an XML
+// report. They hold one probe array per loaded class but no method names, so
+// they give class-level evidence only.
+// https://www.jacoco.org/jacoco/trunk/doc/implementation.html
+
+use super::{CoverageData, CoverageParser};
+use miette::{IntoDiagnostic, Result};
+use std::path::Path;
+
+const BLOCK_HEADER: u8 = 0x01;
+const BLOCK_SESSION_INFO: u8 = 0x10;
+const BLOCK_EXECUTION_DATA: u8 = 0x11;
+const MAGIC_NUMBER: [u8; 2] = [0xC0, 0xC0];
+
+/// Parser for JaCoCo execution data files
+pub struct ExecParser;
+
+impl ExecParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse execution data blocks
+    fn parse_exec(&self, content: &[u8]) -> Result<CoverageData> {
+        let mut coverage_data = CoverageData::new();
+        let mut reader = Reader { content, pos: 0 };
+
+        while let Some(block) = reader.next_byte() {
+            match block {
+                BLOCK_HEADER => {
+                    if reader.bytes(2)? != MAGIC_NUMBER {
+                        miette::bail!("Invalid JaCoCo execution data header");
+                    }
+                    reader.bytes(2)?; // format version
+                }
+                BLOCK_SESSION_INFO => {
+                    reader.utf()?; // session id
+                    reader.bytes(16)?; // start and dump timestamps
+                }
+                BLOCK_EXECUTION_DATA => {
+                    reader.bytes(8)?; // class id
+                    let class = reader.utf()?.replace('/', ".");
+                    let executed = reader.probes()?;
+
+                    // A class may appear once per dump; any hit wins
+                    if executed {
+                        coverage_data.uncovered_classes.remove(&class);
+                        coverage_data.covered_classes.insert(class);
+                    } else if !coverage_data.covered_classes.contains(&class) {
+                        coverage_data.uncovered_classes.insert(class);
+                    }
+                }
+                other => miette::bail!("Unknown JaCoCo execution data block: {:#04x}", other),
+            }
+        }
+
+        Ok(coverage_data)
+    }
+}
+
+impl Default for ExecParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CoverageParser for ExecParser {
+    fn parse(&self, path: &Path) -> Result<CoverageData> {
+        let content = std::fs::read(path).into_diagnostic()?;
+        self.parse_exec(&content)
+    }
+
+    fn can_parse(&self, path: &Path) -> bool {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if matches!(extension, "ec" | "exec") {
+            return true;
+        }
+
+        std::fs::read(path).is_ok_and(|content| {
+            content.first() == Some(&BLOCK_HEADER) && content.get(1..3) == Some(&MAGIC_NUMBER)
+        })
+    }
+}
+
+/// Reader for the `DataOutputStream` encoding used by JaCoCo
+struct Reader<'a> {
+    content: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn next_byte(&mut self) -> Option<u8> {
+        let byte = self.content.get(self.pos).copied()?;
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let Some(bytes) = self.content.get(self.pos..self.pos + len) else {
+            miette::bail!("Truncated JaCoCo execution data");
+        };
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    /// Length-prefixed (modified) UTF-8 string
+    fn utf(&mut self) -> Result<String> {
+        let len = self.bytes(2)?;
+        let len = u16::from_be_bytes([len[0], len[1]]) as usize;
+        Ok(String::from_utf8_lossy(self.bytes(len)?).into_owned())
+    }
+
+    /// Variable-length integer, 7 bits per byte, low bits first
+    fn var_int(&mut self) -> Result<usize> {
+        let mut value = 0usize;
+        let mut shift = 0;
+        loop {
+            let byte = self.bytes(1)?[0];
+            value |= ((byte & 0x7F) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+            if shift > 28 {
+                miette::bail!("Invalid JaCoCo execution data length");
+            }
+        }
+    }
+
+    /// Probe array packed 8 per byte; returns whether any probe was hit
+    fn probes(&mut self) -> Result<bool> {
+        let count = self.var_int()?;
+        let bytes = self.bytes(count.div_ceil(8))?;
+        Ok(bytes.iter().any(|&b| b != 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf(s: &str) -> Vec<u8> {
+        let mut bytes = (s.len() as u16).to_be_bytes().to_vec();
+        bytes.extend_from_slice(s.as_bytes());
+        bytes
+    }
+
+    fn class(name: &str, probes: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![BLOCK_EXECUTION_DATA];
+        bytes.extend_from_slice(&[0; 8]);
+        bytes.extend(utf(name));
+        bytes.push((probes.len() * 8) as u8);
+        bytes.extend_from_slice(probes);
+        bytes
+    }
+
+    #[test]
+    fn test_parse_exec() {
+        let mut exec = vec![BLOCK_HEADER, 0xC0, 0xC0, 0x10, 0x07];
+        exec.push(BLOCK_SESSION_INFO);
+        exec.extend(utf("device-1"));
+        exec.extend_from_slice(&[0; 16]);
+        exec.extend(class("com/example/Checkout", &[0b0000_0101]));
+        exec.extend(class("com/example/LegacyExport", &[0, 0]));
+        exec.extend(class("com/example/Onboarding", &[0]));
+        exec.extend(class("com/example/Onboarding", &[0b1000_0000]));
+
+        let data = ExecParser::new().parse_exec(&exec).unwrap();
+        assert_eq!(data.is_class_covered("com.example.Checkout"), Some(true));
+        assert_eq!(
+            data.is_class_covered("com.example.LegacyExport"),
+            Some(false)
+        );
+        assert_eq!(data.is_class_covered("com.example.Onboarding"), Some(true));
+    }
+
+    #[test]
+    fn test_truncated_exec() {
+        let mut exec = vec![BLOCK_HEADER, 0xC0, 0xC0, 0x10, 0x07];
+        exec.extend(class("com/example/Checkout", &[1]));
+        exec.pop();
+
+        assert!(ExecParser::new().parse_exec(&exec).is_err());
+    }
+}
@@ -140,11 +140,9 @@ impl KoverParser {
                                             .insert(current_class.clone());
                                     }
                                 }
-                                "CLASS" => {
-                                    if covered > 0 {
-                                        coverage_data.covered_classes.insert(current_class.clone());
-                                        coverage_data.uncovered_classes.remove(&current_class);
-                                    }
+                                "CLASS" if covered > 0 => {
+                                    coverage_data.covered_classes.insert(current_class.clone());
+                                    coverage_data.uncovered_classes.remove(&current_class);
                                 }
                                 _ => {}
                             }
@@ -239,4 +239,15 @@ end_of_record
         assert_eq!(data.is_line_covered(file_path, 10), Some(true));
         assert_eq!(data.is_line_covered(file_path, 12), Some(false));
     }
+
+    #[test]
+    fn test_windows_paths_match_sources() {
+        let lcov = "SF:app\\src\\main\\java\\com\\example\\Main.kt\nDA:3,1\nend_of_record\nSF:lib\\Main.kt\nDA:3,0\nend_of_record\n";
+        let data = LcovParser::new().parse_lcov(lcov).unwrap();
+
+        let source = Path::new("/project/app/src/main/java/com/example/Main.kt");
+        assert_eq!(data.is_line_covered(source, 3), Some(true));
+        let other = Path::new("/project/lib/Main.kt");
+        assert_eq!(data.is_line_covered(other, 3), Some(false));
+    }
 }
@@ -4,17 +4,39 @@
 // - JaCoCo XML format (Android/Java standard)
 // - Kover XML format (Kotlin coverage)
 // - LCOV format (generic)
+// - JaCoCo execution data (Firebase Test Lab, on-device instrumentation)
+// - Method traces (Android profiler, simpleperf)
+// - Crash and ANR stack traces (Crashlytics, Play Console)
+//
+// Entries are matched to the analyzed source files by package and path (see
+// sources.rs). Reports of every Gradle module can be found automatically (see
+// discover.rs).
+//
+// Reports can be accumulated across runs in a timestamped store (see store.rs).
 
 #![allow(dead_code)] // Coverage API methods reserved for future use
 
+mod discover;
+mod exec;
 mod jacoco;
 mod kover;
 mod lcov;
+mod sources;
+mod stacktrace;
+mod store;
+mod trace;
 
+pub use discover::{discover_module_reports, modules_without_coverage};
+pub use exec::ExecParser;
 pub use jacoco::JacocoParser;
 pub use kover::KoverParser;
 pub use lcov::LcovParser;
+pub use sources::SourceIndex;
+pub use stacktrace::StackTraceParser;
+pub use store::{parse_max_age, report_timestamp, unix_now, CoverageStore};
+pub use trace::TraceParser;
 
+use crate::discovery::NormalizedPath;
 use miette::Result;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
@@ -105,6 +127,30 @@ impl FileCoverage {
         }
         (self.covered_methods.len() as f64 / total as f64) * 100.0
     }
+
+    /// Merge coverage of the same file - if covered in ANY run, it's covered
+    pub fn merge(&mut self, other: FileCoverage) {
+        self.covered_lines.extend(other.covered_lines);
+        self.covered_methods.extend(other.covered_methods);
+        self.covered_classes.extend(other.covered_classes);
+        self.uncovered_lines.extend(other.uncovered_lines);
+        self.uncovered_methods.extend(other.uncovered_methods);
+        self.uncovered_classes.extend(other.uncovered_classes);
+
+        // Remove from uncovered if now covered
+        for line in &self.covered_lines {
+            self.uncovered_lines.remove(line);
+        }
+        for method in &self.covered_methods {
+            self.uncovered_methods.remove(method);
+        }
+        for class in &self.covered_classes {
+            self.uncovered_classes.remove(class);
+        }
+        for (line, branches) in other.branch_coverage {
+            self.branch_coverage.entry(line).or_insert(branches);
+        }
+    }
 }
 
 /// Aggregated coverage data from all sources
@@ -170,26 +216,33 @@ impl CoverageData {
     pub fn merge(&mut self, other: CoverageData) {
         for (path, coverage) in other.files {
             if let Some(existing) = self.files.get_mut(&path) {
-                // Merge coverage - if covered in ANY run, it's covered
-                existing.covered_lines.extend(coverage.covered_lines);
-                existing.covered_methods.extend(coverage.covered_methods);
-                existing.covered_classes.extend(coverage.covered_classes);
-
-                // Remove from uncovered if now covered
-                for line in &existing.covered_lines {
-                    existing.uncovered_lines.remove(line);
-                }
-                for method in &existing.covered_methods {
-                    existing.uncovered_methods.remove(method);
-                }
-                for class in &existing.covered_classes {
-                    existing.uncovered_classes.remove(class);
-                }
+                existing.merge(coverage);
             } else {
                 self.add_file_coverage(coverage);
             }
         }
 
+        // Sources without per-file data (traces, stack traces) only fill the
+        // global sets
+        for class in other.covered_classes {
+            self.uncovered_classes.remove(&class);
+            self.covered_classes.insert(class);
+        }
+        for class in other.uncovered_classes {
+            if !self.covered_classes.contains(&class) {
+                self.uncovered_classes.insert(class);
+            }
+        }
+        for method in other.covered_methods {
+            self.uncovered_methods.remove(&method);
+            self.covered_methods.insert(method);
+        }
+        for method in other.uncovered_methods {
+            if !self.covered_methods.contains(&method) {
+                self.uncovered_methods.insert(method);
+            }
+        }
+
         self.source_roots.extend(other.source_roots);
     }
 
@@ -218,36 +271,32 @@ impl CoverageData {
 
     /// Check if a line in a file was covered
     pub fn is_line_covered(&self, file: &Path, line: u32) -> Option<bool> {
-        // Try exact match first
-        if let Some(coverage) = self.files.get(file) {
-            return coverage.is_line_covered(line);
-        }
-
-        // Try matching by filename only
-        if let Some(file_name) = file.file_name() {
-            for (path, coverage) in &self.files {
-                if path.file_name() == Some(file_name) {
-                    if let Some(result) = coverage.is_line_covered(line) {
-                        return Some(result);
-                    }
-                }
-            }
-        }
-
-        None
+        self.coverage_for(file)?.is_line_covered(line)
     }
 
     /// Get file coverage for a specific file
     pub fn get_file_coverage(&self, file: &Path) -> Option<&FileCoverage> {
-        self.files.get(file).or_else(|| {
-            // Try matching by filename
-            file.file_name().and_then(|file_name| {
-                self.files
-                    .iter()
-                    .find(|(path, _)| path.file_name() == Some(file_name))
-                    .map(|(_, coverage)| coverage)
-            })
-        })
+        self.coverage_for(file)
+    }
+
+    /// Coverage of a source file: the exact path, else the only file whose
+    /// path ends with the other's. Paths are compared normalized, so reports
+    /// written on another OS still match; a bare file name shared by several
+    /// entries matches none of them (see `resolve_sources`)
+    fn coverage_for(&self, file: &Path) -> Option<&FileCoverage> {
+        if let Some(coverage) = self.files.get(file) {
+            return Some(coverage);
+        }
+
+        let target = NormalizedPath::new(file);
+        let mut matches = self.files.iter().filter(|(path, _)| {
+            let path = NormalizedPath::new(path);
+            path.ends_with(&target) || target.ends_with(&path)
+        });
+        match (matches.next(), matches.next()) {
+            (Some((_, coverage)), None) => Some(coverage),
+            _ => None,
+        }
     }
 
     /// Get overall statistics
@@ -320,7 +369,16 @@ pub fn parse_coverage_file(path: &Path) -> Result<CoverageData> {
     let jacoco = JacocoParser::new();
     let kover = KoverParser::new();
     let lcov = LcovParser::new();
+    let exec = ExecParser::new();
+    let trace = TraceParser::new();
+    let stack_traces = StackTraceParser::new();
 
+    if exec.can_parse(path) {
+        return exec.parse(path);
+    }
+    if trace.can_parse(path) {
+        return trace.parse(path);
+    }
     if jacoco.can_parse(path) {
         return jacoco.parse(path);
     }
@@ -330,6 +388,9 @@ pub fn parse_coverage_file(path: &Path) -> Result<CoverageData> {
     if lcov.can_parse(path) {
         return lcov.parse(path);
     }
+    if stack_traces.can_parse(path) {
+        return stack_traces.parse(path);
+    }
 
     // Default to trying JaCoCo for XML files
     if path.extension().is_some_and(|e| e == "xml") {
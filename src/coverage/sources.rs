@@ -0,0 +1,227 @@
+// Matching coverage entries to analyzed source files
+//
+// Reports name files their own way: JaCoCo and Kover by package and file name
+// (`com/example/Utils.kt`), LCOV by whatever path the test run saw. Each
+// entry is matched to the source file sharing the longest path suffix with
+// it, where a source's declared package counts as its path: a Kotlin file in
+// `feature/src/main/kotlin/Utils.kt` declaring `package com.example` matches
+// `com/example/Utils.kt`. Entries under a configured source root are compared
+// by their path below that root.
+//
+// Only a unique best match is used. An entry matching several sources equally
+// well (`Utils.kt` in two modules) is reported as ambiguous rather than
+// credited to whichever file comes first.
+
+use super::{CoverageData, FileCoverage};
+use crate::discovery::NormalizedPath;
+use std::path::{Path, PathBuf};
+
+/// Analyzed source files coverage entries are matched against
+#[derive(Debug, Clone, Default)]
+pub struct SourceIndex {
+    /// Directories holding sources by package path
+    roots: Vec<PathBuf>,
+    sources: Vec<IndexedSource>,
+}
+
+#[derive(Debug, Clone)]
+struct IndexedSource {
+    path: PathBuf,
+    normalized: NormalizedPath,
+    /// Declared package and file name: `com/example/Utils.kt`
+    package_path: Option<NormalizedPath>,
+}
+
+/// Source file a coverage entry resolves to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceMatch {
+    Unique(PathBuf),
+    Ambiguous(Vec<PathBuf>),
+    Missing,
+}
+
+/// A coverage entry matching several source files equally well
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmbiguousCoverage {
+    pub entry: PathBuf,
+    pub candidates: Vec<PathBuf>,
+}
+
+impl SourceIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_source_roots(mut self, roots: Vec<PathBuf>) -> Self {
+        self.roots = roots;
+        self
+    }
+
+    /// Add an analyzed source file with the package it declares
+    pub fn add_source(&mut self, path: PathBuf, package: Option<&str>) {
+        let normalized = NormalizedPath::new(&path);
+        let package_path = package.map(|package| {
+            NormalizedPath::from_string(&format!(
+                "{}/{}",
+                package.replace('.', "/"),
+                normalized.file_name()
+            ))
+        });
+        self.sources.push(IndexedSource {
+            path,
+            normalized,
+            package_path,
+        });
+    }
+
+    /// Source file a coverage entry refers to
+    pub fn resolve(&self, entry: &Path) -> SourceMatch {
+        let normalized = NormalizedPath::new(entry);
+        let relative = self
+            .roots
+            .iter()
+            .find(|root| entry.starts_with(root))
+            .map(|root| NormalizedPath::relative_to(entry, root))
+            .unwrap_or(normalized);
+
+        let mut best = 0;
+        let mut candidates = Vec::new();
+        for source in &self.sources {
+            let by_path = source.normalized.common_suffix_len(&relative);
+            let by_package = source
+                .package_path
+                .as_ref()
+                .filter(|package_path| relative.ends_with(package_path))
+                .map_or(0, |package_path| package_path.common_suffix_len(&relative));
+            let score = by_path.max(by_package);
+            if score == 0 || score < best {
+                continue;
+            }
+            if score > best {
+                best = score;
+                candidates.clear();
+            }
+            candidates.push(source.path.clone());
+        }
+
+        match candidates.len() {
+            0 => SourceMatch::Missing,
+            1 => SourceMatch::Unique(candidates.remove(0)),
+            _ => {
+                candidates.sort();
+                SourceMatch::Ambiguous(candidates)
+            }
+        }
+    }
+}
+
+impl CoverageData {
+    /// Key line coverage by the analyzed source files it belongs to
+    ///
+    /// Entries resolving to a single source are moved under its path (merged
+    /// when several do), ambiguous ones are dropped and returned, and entries
+    /// matching no source are kept as they are.
+    pub fn resolve_sources(&mut self, index: &SourceIndex) -> Vec<AmbiguousCoverage> {
+        let mut ambiguous = Vec::new();
+        let mut resolved: Vec<(PathBuf, FileCoverage)> = Vec::new();
+
+        for (entry, mut coverage) in std::mem::take(&mut self.files) {
+            match index.resolve(&entry) {
+                SourceMatch::Unique(source) => {
+                    coverage.file_path = source.clone();
+                    resolved.push((source, coverage));
+                }
+                SourceMatch::Ambiguous(candidates) => {
+                    ambiguous.push(AmbiguousCoverage { entry, candidates });
+                }
+                SourceMatch::Missing => resolved.push((entry, coverage)),
+            }
+        }
+
+        for (path, coverage) in resolved {
+            match self.files.get_mut(&path) {
+                Some(existing) => existing.merge(coverage),
+                None => {
+                    self.files.insert(path, coverage);
+                }
+            }
+        }
+
+        ambiguous.sort_by(|a, b| a.entry.cmp(&b.entry));
+        ambiguous
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> SourceIndex {
+        let mut index =
+            SourceIndex::new().with_source_roots(vec![PathBuf::from("/project/app/src/main/java")]);
+        index.add_source(
+            PathBuf::from("/project/app/src/main/java/com/example/app/Utils.kt"),
+            Some("com.example.app"),
+        );
+        index.add_source(
+            PathBuf::from("/project/feature/src/main/kotlin/Utils.kt"),
+            Some("com.example.feature"),
+        );
+        index.add_source(
+            PathBuf::from("/project/lib/src/main/kotlin/Utils.kt"),
+            Some("com.example.lib"),
+        );
+        index
+    }
+
+    #[test]
+    fn test_resolve_by_package_and_root() {
+        let index = index();
+        assert_eq!(
+            index.resolve(Path::new("com/example/feature/Utils.kt")),
+            SourceMatch::Unique(PathBuf::from("/project/feature/src/main/kotlin/Utils.kt"))
+        );
+        assert_eq!(
+            index.resolve(Path::new(
+                "/project/app/src/main/java/com/example/app/Utils.kt"
+            )),
+            SourceMatch::Unique(PathBuf::from(
+                "/project/app/src/main/java/com/example/app/Utils.kt"
+            ))
+        );
+        // A path from another machine still shares its tail with one source
+        assert_eq!(
+            index.resolve(Path::new(r"C:\ci\lib\src\main\kotlin\Utils.kt")),
+            SourceMatch::Unique(PathBuf::from("/project/lib/src/main/kotlin/Utils.kt"))
+        );
+        assert_eq!(index.resolve(Path::new("Other.kt")), SourceMatch::Missing);
+    }
+
+    #[test]
+    fn test_ambiguous_entries_are_reported() {
+        let index = index();
+        let SourceMatch::Ambiguous(candidates) = index.resolve(Path::new("Utils.kt")) else {
+            panic!("expected an ambiguous match");
+        };
+        assert_eq!(candidates.len(), 3);
+
+        let mut data = CoverageData::new();
+        let mut utils = FileCoverage::new(PathBuf::from("Utils.kt"));
+        utils.covered_lines.insert(3);
+        data.add_file_coverage(utils);
+        let mut feature = FileCoverage::new(PathBuf::from("com/example/feature/Utils.kt"));
+        feature.uncovered_lines.insert(5);
+        data.add_file_coverage(feature);
+
+        let ambiguous = data.resolve_sources(&index);
+        assert_eq!(ambiguous.len(), 1);
+        assert_eq!(ambiguous[0].entry, PathBuf::from("Utils.kt"));
+
+        let feature = Path::new("/project/feature/src/main/kotlin/Utils.kt");
+        assert_eq!(data.is_line_covered(feature, 5), Some(false));
+        // The ambiguous entry's lines are credited to no file
+        assert_eq!(data.is_line_covered(feature, 3), None);
+        let lib = Path::new("/project/lib/src/main/kotlin/Utils.kt");
+        assert_eq!(data.is_line_covered(lib, 3), None);
+    }
+}
@@ -0,0 +1,256 @@
+// Coverage store - runtime evidence accumulated across runs
+//
+// Coverage reports are snapshots: a class executed in last quarter's
+// production traces is missing from today's unit-test report. The store
+// (.searchdeadcode-coverage.json) keeps every class and method ever reported
+// with the last time it was executed and the last time a report included it,
+// so old evidence keeps counting until it is older than `--coverage-max-age`.
+
+use super::CoverageData;
+use miette::{IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Current store format version
+const STORE_VERSION: u32 = 1;
+
+/// When a class or method was last reported (seconds since UNIX epoch)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoreEntry {
+    /// Last report in which it was executed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_executed: Option<u64>,
+    /// Last report that included it, executed or not
+    pub last_seen: u64,
+}
+
+impl StoreEntry {
+    fn record(&mut self, executed: bool, timestamp: u64) {
+        if executed {
+            self.last_executed = self.last_executed.max(Some(timestamp));
+        }
+        self.last_seen = self.last_seen.max(timestamp);
+    }
+}
+
+/// Merged coverage of every run, with per-entry timestamps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageStore {
+    pub version: u32,
+    pub classes: BTreeMap<String, StoreEntry>,
+    pub methods: BTreeMap<String, StoreEntry>,
+}
+
+impl CoverageStore {
+    pub fn new() -> Self {
+        Self {
+            version: STORE_VERSION,
+            classes: BTreeMap::new(),
+            methods: BTreeMap::new(),
+        }
+    }
+
+    /// Load the store, starting empty if it does not exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+        let content = std::fs::read_to_string(path).into_diagnostic()?;
+        let store: Self = serde_json::from_str(&content).into_diagnostic()?;
+        if store.version != STORE_VERSION {
+            miette::bail!(
+                "Unsupported coverage store version {} in {}",
+                store.version,
+                path.display()
+            );
+        }
+        Ok(store)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self).into_diagnostic()?;
+        std::fs::write(path, content).into_diagnostic()
+    }
+
+    /// Get the default store path for a project
+    pub fn default_path(project_root: &Path) -> PathBuf {
+        project_root.join(".searchdeadcode-coverage.json")
+    }
+
+    /// Record a coverage report produced at `timestamp`
+    pub fn record(&mut self, coverage: &CoverageData, timestamp: u64) {
+        let sets = [
+            (
+                &mut self.classes,
+                &coverage.covered_classes,
+                &coverage.uncovered_classes,
+            ),
+            (
+                &mut self.methods,
+                &coverage.covered_methods,
+                &coverage.uncovered_methods,
+            ),
+        ];
+        for (entries, covered, uncovered) in sets {
+            for name in covered {
+                entries
+                    .entry(name.clone())
+                    .or_default()
+                    .record(true, timestamp);
+            }
+            for name in uncovered {
+                entries
+                    .entry(name.clone())
+                    .or_default()
+                    .record(false, timestamp);
+            }
+        }
+    }
+
+    /// Coverage still backed by evidence no older than `max_age`. An entry
+    /// is covered if it was executed recently enough, uncovered if it was only
+    /// reported without executing since, and dropped otherwise.
+    pub fn coverage(&self, now: u64, max_age: Option<Duration>) -> CoverageData {
+        let fresh = |timestamp: u64| {
+            max_age.is_none_or(|age| now.saturating_sub(timestamp) <= age.as_secs())
+        };
+        let status = |entry: &StoreEntry| {
+            if entry.last_executed.is_some_and(fresh) {
+                Some(true)
+            } else if fresh(entry.last_seen) {
+                Some(false)
+            } else {
+                None
+            }
+        };
+
+        let mut data = CoverageData::new();
+        for (name, entry) in &self.classes {
+            match status(entry) {
+                Some(true) => data.covered_classes.insert(name.clone()),
+                Some(false) => data.uncovered_classes.insert(name.clone()),
+                None => false,
+            };
+        }
+        for (name, entry) in &self.methods {
+            match status(entry) {
+                Some(true) => data.covered_methods.insert(name.clone()),
+                Some(false) => data.uncovered_methods.insert(name.clone()),
+                None => false,
+            };
+        }
+        data
+    }
+}
+
+impl Default for CoverageStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Current time in seconds since UNIX epoch
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// When a coverage report was produced, from its modification time
+pub fn report_timestamp(path: &Path) -> Option<u64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    modified
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+/// Parse a maximum evidence age: `90d`, `12w`, `36h` (days without a unit)
+pub fn parse_max_age(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(pos) => value.split_at(pos),
+        None => (value, "d"),
+    };
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid age '{}', expected e.g. 90d", value))?;
+    let hours = match unit {
+        "h" => 1,
+        "d" => 24,
+        "w" => 24 * 7,
+        _ => return Err(format!("unknown unit '{}', expected h, d or w", unit)),
+    };
+    Ok(Duration::from_secs(number * hours * 3600))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 24 * 3600;
+
+    fn report(covered: &[&str], uncovered: &[&str]) -> CoverageData {
+        let mut data = CoverageData::new();
+        data.covered_methods = covered.iter().map(|m| m.to_string()).collect();
+        data.uncovered_methods = uncovered.iter().map(|m| m.to_string()).collect();
+        data
+    }
+
+    #[test]
+    fn test_store_staleness() {
+        let now = 1000 * DAY;
+        let mut store = CoverageStore::new();
+        // Production traces from 200 days ago
+        store.record(
+            &report(&["Export.legacy", "Cart.total"], &[]),
+            now - 200 * DAY,
+        );
+        // Last week's unit tests
+        store.record(&report(&["Cart.total"], &["Export.legacy"]), now - 7 * DAY);
+
+        let all = store.coverage(now, None);
+        assert_eq!(all.is_method_covered("Export", "legacy"), Some(true));
+
+        let recent = store.coverage(now, Some(Duration::from_secs(90 * DAY)));
+        assert_eq!(recent.is_method_covered("Export", "legacy"), Some(false));
+        assert_eq!(recent.is_method_covered("Cart", "total"), Some(true));
+
+        let none = store.coverage(now, Some(Duration::from_secs(DAY)));
+        assert!(none.covered_methods.is_empty());
+        assert!(none.uncovered_methods.is_empty());
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = CoverageStore::default_path(temp_dir.path());
+        assert!(CoverageStore::load(&path).unwrap().methods.is_empty());
+
+        let mut store = CoverageStore::new();
+        store.record(&report(&["Cart.total"], &[]), 42);
+        store.save(&path).unwrap();
+
+        let loaded = CoverageStore::load(&path).unwrap();
+        assert_eq!(
+            loaded.methods["Cart.total"],
+            StoreEntry {
+                last_executed: Some(42),
+                last_seen: 42
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_max_age() {
+        assert_eq!(parse_max_age("90d"), Ok(Duration::from_secs(90 * DAY)));
+        assert_eq!(parse_max_age("2w"), Ok(Duration::from_secs(14 * DAY)));
+        assert_eq!(parse_max_age("36h"), Ok(Duration::from_secs(36 * 3600)));
+        assert_eq!(parse_max_age("30"), Ok(Duration::from_secs(30 * DAY)));
+        assert!(parse_max_age("3m").is_err());
+        assert!(parse_max_age("d").is_err());
+    }
+}
@@ -0,0 +1,231 @@
+// Method trace parser
+//
+// Runtime traces record which methods ran, not which did not, so they only
+// ever add covered classes and methods. Supports:
+// - Android profiler / Debug.startMethodTracing() `.trace` files (text header
+//   listing every traced method, followed by binary records)
+// - simpleperf samples dumped as text (`report_sample.py`, `simpleperf
+//   report-sample`): one header line per sample followed by its frames
+
+use super::{CoverageData, CoverageParser, FileCoverage};
+use miette::{IntoDiagnostic, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Libraries holding compiled app code in simpleperf frames
+const JAVA_DSOS: &[&str] = &[".odex", ".oat", ".vdex", ".apk", ".jar", ".dex", "[JIT"];
+
+/// Parser for Android method traces and simpleperf samples
+pub struct TraceParser;
+
+impl TraceParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse trace content, dispatching on its format
+    fn parse_trace(&self, content: &[u8]) -> Result<CoverageData> {
+        if content.starts_with(b"*version") {
+            self.parse_method_trace(content)
+        } else {
+            let text = String::from_utf8_lossy(content);
+            Ok(self.parse_simpleperf(&text))
+        }
+    }
+
+    /// Parse the `*methods` section of an ART method trace. Every method listed
+    /// there was entered at least once while tracing.
+    fn parse_method_trace(&self, content: &[u8]) -> Result<CoverageData> {
+        let header_end = content
+            .windows(5)
+            .position(|w| w == b"*end\n")
+            .unwrap_or(content.len());
+        let header = String::from_utf8_lossy(&content[..header_end]);
+
+        let mut recorder = Recorder::default();
+        let mut in_methods = false;
+        for line in header.lines() {
+            if line.starts_with('*') {
+                in_methods = line == "*methods";
+                continue;
+            }
+            if !in_methods {
+                continue;
+            }
+
+            // 0x<id> \t class \t method \t signature \t source file
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 3 {
+                continue;
+            }
+            let class = fields[1].replace('/', ".");
+            recorder.record(&class, fields[2], fields.get(4).copied());
+        }
+
+        if recorder.data.covered_methods.is_empty() {
+            miette::bail!("Method trace has no *methods section");
+        }
+        Ok(recorder.finish())
+    }
+
+    /// Parse simpleperf text output, keeping frames from compiled app code
+    fn parse_simpleperf(&self, content: &str) -> CoverageData {
+        let mut recorder = Recorder::default();
+
+        for line in content.lines() {
+            // Frames are indented: `\t<address> <symbol> (<dso>)`
+            if !line.starts_with(char::is_whitespace) {
+                continue;
+            }
+            let line = line.trim();
+            let Some((frame, dso)) = line.rsplit_once(" (") else {
+                continue;
+            };
+            if !JAVA_DSOS.iter().any(|suffix| dso.contains(suffix)) {
+                continue;
+            }
+
+            let symbol = frame
+                .split_once(' ')
+                .map(|(_, symbol)| symbol)
+                .unwrap_or(frame);
+            // Drop the parameter list and return type: `void a.B.c(int)`
+            let symbol = symbol.split('(').next().unwrap_or(symbol);
+            let symbol = symbol.rsplit(' ').next().unwrap_or(symbol);
+            if let Some((class, method)) = symbol.rsplit_once('.') {
+                if !class.is_empty() && !method.is_empty() {
+                    recorder.record(class, method, None);
+                }
+            }
+        }
+
+        recorder.finish()
+    }
+}
+
+impl Default for TraceParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CoverageParser for TraceParser {
+    fn parse(&self, path: &Path) -> Result<CoverageData> {
+        let content = std::fs::read(path).into_diagnostic()?;
+        self.parse_trace(&content)
+    }
+
+    fn can_parse(&self, path: &Path) -> bool {
+        if path.extension().is_some_and(|e| e == "trace") {
+            return true;
+        }
+
+        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+            if name.contains("simpleperf") {
+                return true;
+            }
+        }
+
+        // Method traces start with their text header, simpleperf samples with
+        // a header line ending in the sampled event (`cpu-clock:`)
+        if let Ok(content) = std::fs::read(path) {
+            if content.starts_with(b"*version") {
+                return true;
+            }
+            let first_line = content.split(|&b| b == b'\n').next().unwrap_or(&[]);
+            let first_line = String::from_utf8_lossy(first_line);
+            let first_line = first_line.trim_end();
+            return first_line.ends_with("clock:") || first_line.ends_with("cycles:");
+        }
+
+        false
+    }
+}
+
+/// Accumulates executed methods, grouped by source file when known
+#[derive(Default)]
+pub(super) struct Recorder {
+    pub(super) data: CoverageData,
+    files: HashMap<PathBuf, FileCoverage>,
+}
+
+impl Recorder {
+    pub(super) fn record(&mut self, class: &str, method: &str, source_file: Option<&str>) {
+        let full_method = format!("{}.{}", class, method);
+        // Executions of a nested class keep its outer class alive
+        let outer = class.split('$').next().unwrap_or(class);
+
+        for name in [class, outer] {
+            self.data.covered_classes.insert(name.to_string());
+        }
+        self.data.covered_methods.insert(full_method.clone());
+
+        if let Some(source_file) = source_file.filter(|s| !s.is_empty()) {
+            let path = PathBuf::from(source_file);
+            let fc = self
+                .files
+                .entry(path.clone())
+                .or_insert_with(|| FileCoverage::new(path));
+            fc.covered_classes.insert(class.to_string());
+            fc.covered_methods.insert(full_method);
+        }
+    }
+
+    /// Record an executed line of a source file
+    pub(super) fn record_line(&mut self, source_file: &str, line: u32) {
+        let path = PathBuf::from(source_file);
+        self.files
+            .entry(path.clone())
+            .or_insert_with(|| FileCoverage::new(path))
+            .covered_lines
+            .insert(line);
+    }
+
+    pub(super) fn finish(mut self) -> CoverageData {
+        for (_, fc) in self.files {
+            self.data.add_file_coverage(fc);
+        }
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_method_trace() {
+        let mut trace = b"*version\n3\nclock=dual\n*threads\n1\tmain\n*methods\n\
+0x1000\tcom/example/Checkout\tsubmit\t()V\tCheckout.kt\n\
+0x1004\tcom.example.Checkout$Listener\tonDone\t(I)V\tCheckout.kt\n\
+*end\n"
+            .to_vec();
+        trace.extend_from_slice(b"SLOW\x03\x00\x20\x00\xff\xfe");
+
+        let data = TraceParser::new().parse_trace(&trace).unwrap();
+        assert!(data.covered_methods.contains("com.example.Checkout.submit"));
+        assert!(data
+            .covered_methods
+            .contains("com.example.Checkout$Listener.onDone"));
+        assert!(data.covered_classes.contains("com.example.Checkout"));
+        assert!(data.uncovered_methods.is_empty());
+        let file = data.get_file_coverage(Path::new("Checkout.kt")).unwrap();
+        assert_eq!(file.covered_methods.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_simpleperf() {
+        let samples = "com.example.app 4021/4021 [002] 8123.456: 250000 cpu-clock:
+\t7a1c2e10 com.example.Cart.total (/data/app/com.example.app/oat/arm64/base.odex)
+\t7a1c2f00 void com.example.Cart.refresh(int) ([JIT app cache])
+\t7b0e1000 art_quick_invoke_stub (/apex/com.android.art/lib64/libart.so)
+\t7b0e2000 art::ArtMethod::Invoke (/apex/com.android.art/lib64/libart.so)
+";
+
+        let data = TraceParser::new().parse_trace(samples.as_bytes()).unwrap();
+        assert!(data.covered_methods.contains("com.example.Cart.total"));
+        assert!(data.covered_methods.contains("com.example.Cart.refresh"));
+        assert_eq!(data.covered_methods.len(), 2);
+        assert!(data.covered_classes.contains("com.example.Cart"));
+    }
+}
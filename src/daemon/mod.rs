@@ -0,0 +1,368 @@
+//! Analysis daemon for SearchDeadCode
+//!
+//! `searchdeadcode daemon --socket <path>` keeps the parsed sources and the
+//! latest analysis in memory and answers JSON-RPC requests, so build tools
+//! that invoke the analysis repeatedly only pay to re-parse changed files.
+//!
+//! ## Methods
+//!
+//! - `analyze`: re-run the analysis, re-parsing only changed files
+//! - `declaration`: look up declarations by `name` or `fqn`
+//! - `findings`: list findings first reported after revision `since`
+//! - `shutdown`: stop the daemon
+
+mod rpc;
+
+pub use rpc::serve;
+
+use crate::analysis::DeadCode;
+use crate::baseline::FindingId;
+use crate::cache::FileMetadata;
+use crate::config::Config;
+use crate::discovery::{FileType, SourceFile};
+use crate::graph::{Declaration, Graph, GraphBuilder};
+use crate::parser::{JavaParser, KotlinParser, ParseResult, Parser as SourceParser};
+use crate::pipeline::{DiscoveryStage, Pipeline, PipelineContext, PipelineOptions, Stage};
+use miette::{IntoDiagnostic, Result};
+use rayon::prelude::*;
+use rpc::{Request, Response, RpcError};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::debug;
+
+/// A parsed source file and the metadata it was parsed from
+struct ParsedFile {
+    metadata: FileMetadata,
+    result: ParseResult,
+}
+
+/// Long-lived analysis state shared by all requests
+pub struct Daemon {
+    config: Config,
+    root: PathBuf,
+    options: PipelineOptions,
+    /// Parsed sources, reused while the file is unchanged
+    parsed: HashMap<PathBuf, ParsedFile>,
+    /// Latest analysis
+    analysis: Option<PipelineContext>,
+    /// Incremented by every analysis
+    revision: u64,
+    /// Revision each current finding was first reported in, by fingerprint
+    first_seen: HashMap<String, u64>,
+    shut_down: bool,
+}
+
+impl Daemon {
+    pub fn new(config: Config, root: PathBuf, options: PipelineOptions) -> Self {
+        Self {
+            config,
+            root,
+            options,
+            parsed: HashMap::new(),
+            analysis: None,
+            revision: 0,
+            first_seen: HashMap::new(),
+            shut_down: false,
+        }
+    }
+
+    pub fn is_shut_down(&self) -> bool {
+        self.shut_down
+    }
+
+    /// Handle one JSON-RPC request line, returning the response line (None
+    /// for notifications)
+    pub fn handle(&mut self, line: &str) -> Option<String> {
+        let request: Request = match serde_json::from_str::<Value>(line) {
+            Ok(value) => match serde_json::from_value(value) {
+                Ok(request) => request,
+                Err(e) => {
+                    let error = RpcError::new(rpc::INVALID_REQUEST, e.to_string());
+                    return Some(respond(Value::Null, Err(error)));
+                }
+            },
+            Err(e) => {
+                let error = RpcError::new(rpc::PARSE_ERROR, e.to_string());
+                return Some(respond(Value::Null, Err(error)));
+            }
+        };
+
+        let result = self.dispatch(&request.method, &request.params);
+        request.id.map(|id| respond(id, result))
+    }
+
+    fn dispatch(&mut self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        match method {
+            "analyze" => self
+                .analyze()
+                .map_err(|e| RpcError::new(rpc::SERVER_ERROR, e.to_string())),
+            "declaration" => self.declaration(params),
+            "findings" => self.findings(params),
+            "shutdown" => {
+                self.shut_down = true;
+                Ok(Value::Null)
+            }
+            _ => Err(RpcError::new(
+                rpc::METHOD_NOT_FOUND,
+                format!("Unknown method '{}'", method),
+            )),
+        }
+    }
+
+    /// Re-run the analysis, re-parsing only the files that changed
+    pub fn analyze(&mut self) -> Result<Value> {
+        let start = Instant::now();
+        let mut ctx =
+            PipelineContext::new(self.config.clone(), self.root.clone(), self.options.clone());
+
+        DiscoveryStage.run(&mut ctx)?;
+        let (parsed, reused) = self.refresh(&ctx.files);
+        ctx.graph = self.build_graph(&ctx.files);
+        Pipeline::standard()
+            .without_stage(DiscoveryStage.name())
+            .without_stage("parse")
+            .run(&mut ctx)?;
+
+        self.revision += 1;
+        let keys: HashSet<String> = ctx.dead_code.iter().map(|dc| self.key(dc)).collect();
+        self.first_seen.retain(|key, _| keys.contains(key));
+        for key in keys {
+            self.first_seen.entry(key).or_insert(self.revision);
+        }
+
+        let result = json!({
+            "revision": self.revision,
+            "files": ctx.files.len(),
+            "parsed": parsed,
+            "reused": reused,
+            "findings": ctx.dead_code.len(),
+            "elapsed_ms": start.elapsed().as_millis() as u64,
+        });
+        self.analysis = Some(ctx);
+        Ok(result)
+    }
+
+    /// Parse new and changed source files and forget deleted ones, returning
+    /// the number of files parsed and reused
+    fn refresh(&mut self, files: &[SourceFile]) -> (usize, usize) {
+        let sources: Vec<&SourceFile> = files
+            .iter()
+            .filter(|file| matches!(file.file_type, FileType::Kotlin | FileType::Java))
+            .collect();
+        let stale: Vec<&SourceFile> = sources
+            .iter()
+            .copied()
+            .filter(|file| match self.parsed.get(&file.path) {
+                Some(parsed) => {
+                    parsed.metadata.quick_changed(&file.path)
+                        && parsed.metadata.content_changed(&file.path)
+                }
+                None => true,
+            })
+            .collect();
+
+        let results: Vec<(PathBuf, Result<ParsedFile>)> = stale
+            .par_iter()
+            .map(|file| (file.path.clone(), parse(file)))
+            .collect();
+        for (path, result) in results {
+            match result {
+                Ok(parsed) => {
+                    self.parsed.insert(path, parsed);
+                }
+                Err(e) => {
+                    debug!("Failed to parse {}: {}", path.display(), e);
+                    self.parsed.remove(&path);
+                }
+            }
+        }
+
+        let current: HashSet<&Path> = files.iter().map(|file| file.path.as_path()).collect();
+        self.parsed
+            .retain(|path, _| current.contains(path.as_path()));
+        (stale.len(), sources.len() - stale.len())
+    }
+
+    fn build_graph(&self, files: &[SourceFile]) -> Graph {
+        let mut builder = GraphBuilder::new();
+        for file in files {
+            if let Some(parsed) = self.parsed.get(&file.path) {
+                builder.add_parse_result(parsed.result.clone());
+            }
+        }
+        builder.build()
+    }
+
+    /// Declarations matching `name` or `fqn`, with their reachability
+    fn declaration(&self, params: &Value) -> Result<Value, RpcError> {
+        let ctx = self.latest()?;
+        let matches: Vec<&Declaration> = if let Some(fqn) = params["fqn"].as_str() {
+            ctx.graph.find_by_fqn(fqn).into_iter().collect()
+        } else if let Some(name) = params["name"].as_str() {
+            ctx.graph.find_by_name(name)
+        } else {
+            return Err(RpcError::new(
+                rpc::INVALID_PARAMS,
+                "Expected a 'name' or 'fqn' parameter",
+            ));
+        };
+
+        let declarations: Vec<Value> = matches
+            .into_iter()
+            .map(|decl| {
+                let findings: Vec<Value> = ctx
+                    .dead_code
+                    .iter()
+                    .filter(|dc| dc.declaration.id == decl.id)
+                    .map(|dc| self.finding(dc))
+                    .collect();
+                json!({
+                    "name": decl.name,
+                    "fqn": decl.fully_qualified_name,
+                    "kind": decl.kind.display_name(),
+                    "visibility": decl.visibility,
+                    "file": decl.location.file,
+                    "line": decl.location.line,
+                    "entry_point": ctx.entry_points.contains(&decl.id),
+                    "reachable": ctx.reachable.contains(&decl.id),
+                    "references": ctx.graph.get_references_to(&decl.id).len(),
+                    "findings": findings,
+                })
+            })
+            .collect();
+        Ok(json!({ "revision": self.revision, "declarations": declarations }))
+    }
+
+    /// Findings first reported after revision `since` (all if omitted)
+    fn findings(&self, params: &Value) -> Result<Value, RpcError> {
+        let ctx = self.latest()?;
+        let since = match &params["since"] {
+            Value::Null => 0,
+            value => value.as_u64().ok_or_else(|| {
+                RpcError::new(rpc::INVALID_PARAMS, "'since' must be a revision number")
+            })?,
+        };
+
+        let findings: Vec<Value> = ctx
+            .dead_code
+            .iter()
+            .filter(|dc| {
+                self.first_seen
+                    .get(&self.key(dc))
+                    .is_some_and(|r| *r > since)
+            })
+            .map(|dc| self.finding(dc))
+            .collect();
+        Ok(json!({ "revision": self.revision, "findings": findings }))
+    }
+
+    fn latest(&self) -> Result<&PipelineContext, RpcError> {
+        self.analysis
+            .as_ref()
+            .ok_or_else(|| RpcError::new(rpc::SERVER_ERROR, "No analysis yet, call 'analyze'"))
+    }
+
+    fn finding(&self, dc: &DeadCode) -> Value {
+        json!({
+            "code": dc.issue.code(),
+            "severity": dc.severity,
+            "confidence": dc.confidence.as_str(),
+            "message": dc.message,
+            "name": dc.declaration.name,
+            "file": dc.declaration.location.file,
+            "line": dc.declaration.location.line,
+            "revision": self.first_seen.get(&self.key(dc)),
+        })
+    }
+
+    /// Identity of a finding across analyses
+    fn key(&self, dc: &DeadCode) -> String {
+        FindingId::new(dc, &self.root).to_string()
+    }
+}
+
+fn respond(id: Value, result: Result<Value, RpcError>) -> String {
+    let response = Response::new(id, result);
+    serde_json::to_string(&response).unwrap_or_default()
+}
+
+fn parse(file: &SourceFile) -> Result<ParsedFile> {
+    let metadata = FileMetadata::from_path(&file.path).into_diagnostic()?;
+    let contents = file.read_contents()?;
+    let result = match file.file_type {
+        FileType::Kotlin => KotlinParser::new().parse(&file.path, &contents)?,
+        FileType::Java => JavaParser::new().parse(&file.path, &contents)?,
+        _ => ParseResult::new(),
+    };
+    Ok(ParsedFile { metadata, result })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::detectors::DetectorRegistry;
+    use tempfile::TempDir;
+
+    fn request(daemon: &mut Daemon, id: u64, method: &str, params: Value) -> Value {
+        let line = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        let response = daemon.handle(&line.to_string()).unwrap();
+        serde_json::from_str(&response).unwrap()
+    }
+
+    #[test]
+    fn test_daemon_reuses_parsed_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        std::fs::write(
+            root.join("Main.kt"),
+            "fun main() { helper() }\nfun helper() {}\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("Legacy.kt"), "class Legacy\n").unwrap();
+
+        let options = PipelineOptions {
+            detectors: DetectorRegistry::builtin()
+                .default_ids()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            ..PipelineOptions::default()
+        };
+        let mut daemon = Daemon::new(Config::default(), root.clone(), options);
+
+        let response = request(&mut daemon, 1, "findings", Value::Null);
+        assert_eq!(response["error"]["code"], rpc::SERVER_ERROR);
+
+        let response = request(&mut daemon, 2, "analyze", Value::Null);
+        assert_eq!(response["result"]["parsed"], 2);
+        let first = response["result"]["findings"].as_u64().unwrap();
+
+        std::fs::write(root.join("Stale.kt"), "class Stale\n").unwrap();
+        let response = request(&mut daemon, 3, "analyze", Value::Null);
+        assert_eq!(response["result"]["revision"], 2);
+        assert_eq!(
+            response["result"]["parsed"], 1,
+            "Unchanged files are reused"
+        );
+        assert_eq!(response["result"]["findings"], first + 1);
+
+        let response = request(&mut daemon, 4, "findings", json!({ "since": 1 }));
+        let findings = response["result"]["findings"].as_array().unwrap();
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0]["file"].as_str().unwrap().ends_with("Stale.kt"));
+
+        let response = request(&mut daemon, 5, "declaration", json!({ "name": "helper" }));
+        let declaration = &response["result"]["declarations"][0];
+        assert_eq!(declaration["reachable"], true);
+        assert_eq!(declaration["references"], 1);
+
+        let response = request(&mut daemon, 6, "missing", Value::Null);
+        assert_eq!(response["error"]["code"], rpc::METHOD_NOT_FOUND);
+        assert!(daemon.handle("{not json").unwrap().contains("-32700"));
+
+        assert_eq!(daemon.handle(r#"{"method": "shutdown"}"#), None);
+        assert!(daemon.is_shut_down());
+    }
+}
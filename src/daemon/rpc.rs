@@ -0,0 +1,137 @@
+//! JSON-RPC 2.0 messages and the Unix socket server
+//!
+//! Each request and response is a single line of JSON, so clients can talk to
+//! the daemon with any line-oriented socket library.
+
+use super::Daemon;
+use miette::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// Implementation-defined: the request was valid but could not be served
+pub const SERVER_ERROR: i64 = -32000;
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    /// Missing for notifications, which get no response
+    #[serde(default)]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Response {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl Response {
+    pub fn new(id: Value, result: Result<Value, RpcError>) -> Self {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+/// Serve requests on a Unix socket until a `shutdown` request
+#[cfg(unix)]
+pub fn serve(mut daemon: Daemon, socket: &Path) -> Result<()> {
+    use miette::{miette, Context, IntoDiagnostic};
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixListener;
+    use tracing::warn;
+
+    // Replace the socket of a daemon that did not shut down cleanly
+    if let Ok(metadata) = std::fs::symlink_metadata(socket) {
+        if !metadata.file_type().is_socket() {
+            return Err(miette!("{} exists and is not a socket", socket.display()));
+        }
+        std::fs::remove_file(socket).into_diagnostic()?;
+    }
+    let listener = UnixListener::bind(socket)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to listen on {}", socket.display()))?;
+
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!("Failed to accept connection: {}", e);
+                continue;
+            }
+        };
+        let reader = match stream.try_clone() {
+            Ok(reader) => BufReader::new(reader),
+            Err(e) => {
+                warn!("Failed to read from connection: {}", e);
+                continue;
+            }
+        };
+
+        for line in reader.lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = daemon.handle(&line) {
+                // A client that went away only ends its own connection
+                if writeln!(stream, "{}", response).is_err() {
+                    break;
+                }
+            }
+            if daemon.is_shut_down() {
+                break;
+            }
+        }
+
+        if daemon.is_shut_down() {
+            break;
+        }
+    }
+
+    let _ = std::fs::remove_file(socket);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn serve(_daemon: Daemon, _socket: &Path) -> Result<()> {
+    Err(miette::miette!(
+        "The daemon needs Unix domain sockets, which are not supported on this platform"
+    ))
+}
@@ -0,0 +1,104 @@
+//! Files changed relative to a git base ref, for reporting only the dead code
+//! a branch introduces
+
+use super::NormalizedPath;
+use miette::{miette, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Files changed since a base ref, relative to the project root
+#[derive(Debug, Clone, Default)]
+pub struct ChangedFiles {
+    root: PathBuf,
+    files: HashSet<NormalizedPath>,
+}
+
+impl ChangedFiles {
+    /// Ask git for the files that differ from the merge base of `base` and
+    /// HEAD, including uncommitted and untracked files
+    pub fn since(root: &Path, base: &str) -> Result<Self> {
+        let dir = if root.is_file() {
+            root.parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .unwrap_or(Path::new("."))
+        } else {
+            root
+        };
+        let merge_base = git(dir, &["merge-base", base, "HEAD"])?;
+        let merge_base = merge_base.trim();
+
+        let diff = git(
+            dir,
+            &["diff", "--name-only", "--relative", merge_base, "--"],
+        )?;
+        let untracked = git(dir, &["ls-files", "--others", "--exclude-standard"])?;
+
+        let files = diff
+            .lines()
+            .chain(untracked.lines())
+            .filter(|line| !line.is_empty())
+            .map(|line| dir.join(line))
+            .collect();
+        Ok(Self::new(dir.to_path_buf(), files))
+    }
+
+    /// Changed files, relative to `root` or absolute
+    pub fn new(root: PathBuf, files: HashSet<PathBuf>) -> Self {
+        let files = files
+            .into_iter()
+            .map(|file| NormalizedPath::relative_to(&file, &root))
+            .collect();
+        Self { root, files }
+    }
+
+    /// Whether a source file (as discovered under the root) changed
+    pub fn contains(&self, file: &Path) -> bool {
+        self.files
+            .contains(&NormalizedPath::relative_to(file, &self.root))
+    }
+
+    /// Number of changed files
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+}
+
+/// Run git in `dir`, returning its stdout
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| miette!("Failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(miette!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains_relative_and_absolute() {
+        let root = PathBuf::from("/project");
+        let changed = ChangedFiles::new(
+            root,
+            HashSet::from([
+                PathBuf::from("/project/app/src/New.kt"),
+                PathBuf::from("lib/src/Edited.java"),
+            ]),
+        );
+
+        assert!(changed.contains(Path::new("/project/app/src/New.kt")));
+        assert!(changed.contains(Path::new("/project/lib/src/Edited.java")));
+        assert!(!changed.contains(Path::new("/project/app/src/Old.kt")));
+    }
+}
@@ -1,11 +1,14 @@
 // File discovery utilities - some reserved for future use
 #![allow(dead_code)]
 
+use super::SourceSet;
 use crate::config::Config;
-use ignore::WalkBuilder;
-use miette::{IntoDiagnostic, Result};
-use rayon::prelude::*;
-use std::path::{Path, PathBuf};
+use ignore::overrides::OverrideBuilder;
+use ignore::{DirEntry, WalkBuilder, WalkState};
+use miette::{miette, IntoDiagnostic, Result};
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
 use tracing::{debug, trace};
 
 /// Type of source file
@@ -27,6 +30,7 @@ impl FileType {
         let file_name = path.file_name()?.to_str()?;
 
         match extension {
+            // Scripts (build.gradle.kts, *.main.kts) can use project classes
             "kt" | "kts" => Some(FileType::Kotlin),
             "java" => Some(FileType::Java),
             "xml" => {
@@ -77,15 +81,20 @@ pub struct SourceFile {
     /// Type of source file
     pub file_type: FileType,
 
+    /// Gradle source set the file belongs to
+    pub source_set: SourceSet,
+
     /// Contents of the file (loaded lazily)
     contents: Option<String>,
 }
 
 impl SourceFile {
     pub fn new(path: PathBuf, file_type: FileType) -> Self {
+        let source_set = SourceSet::from_path(&path);
         Self {
             path,
             file_type,
+            source_set,
             contents: None,
         }
     }
@@ -108,6 +117,21 @@ impl SourceFile {
     pub fn read_contents(&self) -> Result<String> {
         std::fs::read_to_string(&self.path).into_diagnostic()
     }
+
+    /// Whether this is an XML resource under res/xml (shortcuts, widget and
+    /// service configuration), including qualified directories like res/xml-v25
+    pub fn is_xml_resource(&self) -> bool {
+        if self.file_type != FileType::XmlOther {
+            return false;
+        }
+        let dir = self.path.parent();
+        let in_res = dir
+            .and_then(Path::parent)
+            .and_then(Path::file_name)
+            .is_some_and(|name| name == "res");
+        let dir_name = dir.and_then(Path::file_name).and_then(|n| n.to_str());
+        in_res && dir_name.is_some_and(|name| name == "xml" || name.starts_with("xml-"))
+    }
 }
 
 /// File finder for discovering source files in a project
@@ -130,51 +154,145 @@ impl<'a> FileFinder<'a> {
             self.config.targets.iter().map(|t| root.join(t)).collect()
         };
 
-        let files: Vec<SourceFile> = targets
-            .par_iter()
-            .flat_map(|target| self.scan_directory(target))
-            .collect();
+        let mut files = Vec::new();
+        for target in &targets {
+            files.extend(self.scan_directory(target)?);
+        }
 
         debug!("Found {} files", files.len());
         Ok(files)
     }
 
-    /// Scan a single directory for source files
-    fn scan_directory(&self, dir: &Path) -> Vec<SourceFile> {
+    /// Scan a single directory for source files, walking it in parallel
+    fn scan_directory(&self, dir: &Path) -> Result<Vec<SourceFile>> {
         if !dir.exists() {
             trace!("Directory does not exist: {}", dir.display());
-            return Vec::new();
+            return Ok(Vec::new());
         }
 
-        let walker = WalkBuilder::new(dir)
+        let files = Mutex::new(Vec::new());
+        self.walker(dir)?.build_parallel().run(|| {
+            Box::new(|entry| {
+                if let Some(file) = entry.ok().and_then(|entry| self.source_file(&entry)) {
+                    files.lock().unwrap().push(file);
+                }
+                WalkState::Continue
+            })
+        });
+
+        // Parallel walking visits files in a nondeterministic order
+        let mut files = files.into_inner().unwrap();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// Configure the directory walker from the discovery settings
+    fn walker(&self, dir: &Path) -> Result<WalkBuilder> {
+        let discovery = &self.config.discovery;
+        let respect_ignore = discovery.respect_ignore_files;
+
+        let mut builder = WalkBuilder::new(dir);
+        builder
             .hidden(true) // Skip hidden files
-            .git_ignore(true) // Respect .gitignore
-            .git_global(true) // Respect global gitignore
-            .git_exclude(true) // Respect .git/info/exclude
-            .ignore(true) // Respect .ignore files
-            .parents(true) // Check parent directories for ignore files
+            .git_ignore(respect_ignore) // Respect .gitignore
+            .git_global(respect_ignore) // Respect global gitignore
+            .git_exclude(respect_ignore) // Respect .git/info/exclude
+            .ignore(respect_ignore) // Respect .ignore files
+            .parents(respect_ignore) // Check parent directories for ignore files
+            .require_git(false) // Respect .gitignore outside of git checkouts too
             .follow_links(false) // Don't follow symlinks
-            .build();
+            .threads(discovery.threads);
+        if respect_ignore {
+            builder.add_custom_ignore_filename(".hgignore");
+        }
 
-        walker
-            .filter_map(|entry| entry.ok())
-            .filter(|entry| entry.file_type().map(|t| t.is_file()).unwrap_or(false))
-            .filter_map(|entry| {
-                let path = entry.path();
+        if !self.config.include.is_empty() {
+            let mut overrides = OverrideBuilder::new(dir);
+            for pattern in &self.config.include {
+                overrides
+                    .add(pattern)
+                    .map_err(|e| miette!("Invalid include pattern '{}': {}", pattern, e))?;
+            }
+            builder.overrides(overrides.build().into_diagnostic()?);
+        }
 
-                // Check exclusion patterns
-                if self.config.should_exclude(path) {
-                    trace!("Excluding: {}", path.display());
-                    return None;
-                }
+        // Prune skipped directories instead of filtering their files afterwards
+        let skip_dirs = discovery.skip_dirs.clone();
+        builder.filter_entry(move |entry| {
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            !(is_dir
+                && entry.depth() > 0
+                && skip_dirs.iter().any(|d| entry.file_name() == d.as_str()))
+        });
 
-                // Determine file type
-                let file_type = FileType::from_path(path)?;
+        Ok(builder)
+    }
 
-                trace!("Found {:?}: {}", file_type, path.display());
-                Some(SourceFile::new(path.to_path_buf(), file_type))
-            })
-            .collect()
+    /// Turn a walked entry into a source file, if it is one we analyze
+    fn source_file(&self, entry: &DirEntry) -> Option<SourceFile> {
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            return None;
+        }
+        let path = entry.path();
+
+        // Check exclusion patterns
+        if self.config.should_exclude(path) {
+            trace!("Excluding: {}", path.display());
+            return None;
+        }
+
+        // Determine file type
+        let file_type = FileType::from_path(path)?;
+
+        trace!("Found {:?}: {}", file_type, path.display());
+        Some(SourceFile::new(path.to_path_buf(), file_type))
+    }
+
+    /// Find Kotlin and Java files generated by annotation processors (KAPT,
+    /// KSP) under `build/generated` of the root and of every module with
+    /// discovered sources
+    ///
+    /// Ignore files and exclude patterns don't apply, as build output is
+    /// normally ignored.
+    pub fn find_generated_files(
+        &self,
+        root: &Path,
+        files: &[SourceFile],
+    ) -> Result<Vec<SourceFile>> {
+        let mut modules: BTreeSet<PathBuf> = files
+            .iter()
+            .filter_map(|file| module_root(&file.path))
+            .map(Path::to_path_buf)
+            .collect();
+        if root.is_dir() {
+            modules.insert(root.to_path_buf());
+        }
+
+        let mut generated = Vec::new();
+        for module in modules {
+            let dir = module.join("build").join("generated");
+            if !dir.is_dir() {
+                continue;
+            }
+            let walker = WalkBuilder::new(&dir)
+                .standard_filters(false)
+                .follow_links(false)
+                .build();
+            for entry in walker.flatten() {
+                if !entry.file_type().is_some_and(|t| t.is_file()) {
+                    continue;
+                }
+                let path = entry.path();
+                if let Some(file_type) = FileType::from_path(path).filter(FileType::is_source) {
+                    trace!("Found generated {:?}: {}", file_type, path.display());
+                    generated.push(SourceFile::new(path.to_path_buf(), file_type));
+                }
+            }
+        }
+
+        generated.sort_by(|a, b| a.path.cmp(&b.path));
+        debug!("Found {} generated files", generated.len());
+        Ok(generated)
     }
 
     /// Find only Kotlin and Java source files
@@ -227,6 +345,39 @@ impl<'a> FileFinder<'a> {
             .filter(|f| f.file_type == FileType::XmlMenu)
             .collect())
     }
+
+    /// Find XML resources under res/xml (shortcuts, widget and service
+    /// configuration), including qualified directories like res/xml-v25
+    pub fn find_xml_resources(&self, root: &Path) -> Result<Vec<SourceFile>> {
+        let files = self.find_files(root)?;
+        Ok(files
+            .into_iter()
+            .filter(SourceFile::is_xml_resource)
+            .collect())
+    }
+}
+
+/// Whether a file is build output of an annotation processor or code
+/// generator, i.e. lies under a `build/generated` directory
+pub fn is_generated_source(path: &Path) -> bool {
+    let names: Vec<&str> = path
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+    names
+        .windows(2)
+        .any(|pair| pair[0] == "build" && pair[1] == "generated")
+}
+
+/// Module directory of a source file: the parent of its innermost `src/`
+fn module_root(path: &Path) -> Option<&Path> {
+    path.ancestors()
+        .find(|dir| dir.file_name().is_some_and(|name| name == "src"))
+        .and_then(Path::parent)
+        .filter(|dir| !dir.as_os_str().is_empty())
 }
 
 /// Statistics about discovered files
@@ -313,4 +464,78 @@ mod tests {
         assert_eq!(file.file_type, FileType::Kotlin);
         assert!(file.contents().is_none());
     }
+
+    #[test]
+    fn test_discovery_respects_ignore_files_and_skip_dirs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in ["src", "node_modules/pkg", "app/build", "src/ignored"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in [
+            "src/Main.kt",
+            "src/Util.java",
+            "src/ignored/Old.kt",
+            "node_modules/pkg/Vendored.kt",
+            "app/build/Generated.kt",
+        ] {
+            std::fs::write(root.join(file), "class X").unwrap();
+        }
+        std::fs::write(root.join(".gitignore"), "Old.kt\n").unwrap();
+
+        let mut config = Config::default();
+        config.exclude.clear();
+        let names = |config: &Config| -> Vec<String> {
+            FileFinder::new(config)
+                .find_files(root)
+                .unwrap()
+                .iter()
+                .map(|f| f.path.file_name().unwrap().to_string_lossy().to_string())
+                .collect()
+        };
+
+        assert_eq!(names(&config), vec!["Main.kt", "Util.java"]);
+
+        // Includes whitelist files and override ignore files
+        config.include = vec!["*.kt".to_string()];
+        assert_eq!(names(&config), vec!["Main.kt", "Old.kt"]);
+
+        config.include.clear();
+        config.discovery.respect_ignore_files = false;
+        config.discovery.skip_dirs.clear();
+        assert_eq!(names(&config).len(), 5);
+    }
+
+    #[test]
+    fn test_find_generated_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let files = [
+            "app/src/main/kotlin/Repo.kt",
+            "app/build/generated/ksp/debug/kotlin/Repo_Factory.kt",
+            "app/build/generated/source/kapt/debug/DaggerAppComponent.java",
+            "app/build/generated/res/values/values.xml",
+            "app/build/tmp/kapt3/stubs/debug/Repo.java",
+        ];
+        for file in files {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "class X").unwrap();
+        }
+
+        let config = Config::default();
+        let finder = FileFinder::new(&config);
+        let sources = finder.find_source_files(root).unwrap();
+        assert_eq!(sources.len(), 1);
+
+        let generated: Vec<PathBuf> = finder
+            .find_generated_files(root, &sources)
+            .unwrap()
+            .into_iter()
+            .map(|f| f.path)
+            .collect();
+        assert_eq!(generated, vec![root.join(files[1]), root.join(files[2])]);
+        assert!(generated.iter().all(|path| is_generated_source(path)));
+        assert!(!is_generated_source(&root.join(files[0])));
+    }
 }
@@ -1,3 +1,13 @@
+mod changed;
 mod file_finder;
+mod package_scope;
+mod paths;
+mod source_set;
+mod variant;
 
-pub use file_finder::{FileFinder, FileType, SourceFile};
+pub use changed::ChangedFiles;
+pub use file_finder::{is_generated_source, FileFinder, FileType, SourceFile};
+pub use package_scope::{declared_package, PackageScope};
+pub use paths::NormalizedPath;
+pub use source_set::SourceSet;
+pub use variant::Variant;
@@ -0,0 +1,85 @@
+//! Package subtrees selected with `--only-package`, for cleaning up a large
+//! codebase one package at a time
+
+/// Package subtrees the report is limited to; empty means everything
+#[derive(Debug, Clone, Default)]
+pub struct PackageScope {
+    packages: Vec<String>,
+}
+
+impl PackageScope {
+    /// Scope of the given packages, written as `com.example.feature` or
+    /// `com.example.feature.*`
+    pub fn new(packages: &[String]) -> Self {
+        let packages = packages
+            .iter()
+            .map(|package| package.trim().trim_end_matches(".*").trim_end_matches('.'))
+            .filter(|package| !package.is_empty())
+            .map(str::to_string)
+            .collect();
+        Self { packages }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+
+    pub fn packages(&self) -> &[String] {
+        &self.packages
+    }
+
+    /// Whether a package is one of the subtrees or nested in one
+    pub fn contains(&self, package: &str) -> bool {
+        self.packages.iter().any(|scope| {
+            package
+                .strip_prefix(scope.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+    }
+
+    /// Whether a Kotlin or Java source can reference declarations in scope.
+    /// Code outside a package has to import its declarations or name them
+    /// fully qualified, so a source that declares no package in scope and
+    /// never spells one out cannot use them.
+    pub fn is_relevant_source(&self, contents: &str) -> bool {
+        if declared_package(contents).is_some_and(|package| self.contains(package)) {
+            return true;
+        }
+        self.packages
+            .iter()
+            .any(|package| contents.contains(package.as_str()))
+    }
+}
+
+/// Package a Kotlin or Java source declares, if any
+pub fn declared_package(contents: &str) -> Option<&str> {
+    contents.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix("package ")?;
+        let package = rest.trim().trim_end_matches(';').trim();
+        (!package.is_empty()).then_some(package)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_scope() {
+        let scope = PackageScope::new(&["com.example.feature.*".to_string()]);
+        assert!(scope.contains("com.example.feature"));
+        assert!(scope.contains("com.example.feature.home"));
+        assert!(!scope.contains("com.example.featureflags"));
+        assert!(!scope.contains("com.example"));
+
+        assert!(scope.is_relevant_source("package com.example.feature.home\n\nclass Home"));
+        assert!(scope.is_relevant_source(
+            "package com.example.app;\n\nimport com.example.feature.home.Home;\n"
+        ));
+        assert!(!scope.is_relevant_source("package com.example.app\n\nclass App"));
+        assert_eq!(
+            declared_package("// header\npackage com.example.app;\n"),
+            Some("com.example.app")
+        );
+    }
+}
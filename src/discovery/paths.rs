@@ -0,0 +1,182 @@
+//! Normalized paths
+//!
+//! Baselines, caches and reports written on one machine are read on another:
+//! a baseline generated on macOS is applied on Windows CI, a coverage report
+//! produced on Windows is matched on Linux. Paths that outlive a run are
+//! therefore written in one form whatever the OS:
+//!
+//! - relative to the project root when under it
+//! - `/` separators, backslashes included
+//! - no `.` components, and `..` folded into the component before it
+//! - casing kept as written, since Linux file systems are case-sensitive; the
+//!   project root alone is stripped ignoring case, as Windows and macOS report
+//!   it with whatever casing it was typed with
+//! - names that are not UTF-8 converted lossily rather than failing the run
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// A path in the OS-independent form shared between runs
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NormalizedPath(String);
+
+impl NormalizedPath {
+    /// Normalize a path as it is, without making it relative
+    pub fn new(path: &Path) -> Self {
+        Self::from_string(&path.to_string_lossy())
+    }
+
+    /// Normalize `file` relative to `root`, or as it is when outside it
+    pub fn relative_to(file: &Path, root: &Path) -> Self {
+        match strip_root(file, root) {
+            Some(relative) => Self::new(&relative),
+            None => Self::new(file),
+        }
+    }
+
+    /// Normalize a path written on any OS: `.\app\src\Main.kt` becomes
+    /// `app/src/Main.kt`
+    pub fn from_string(path: &str) -> Self {
+        let path = path.replace('\\', "/");
+        let absolute = path.starts_with('/');
+
+        let mut components: Vec<&str> = Vec::new();
+        for component in path.split('/') {
+            match component {
+                "" | "." => {}
+                ".." if components.last().is_some_and(|last| *last != "..") => {
+                    components.pop();
+                }
+                _ => components.push(component),
+            }
+        }
+
+        let joined = components.join("/");
+        Self(if absolute {
+            format!("/{}", joined)
+        } else {
+            joined
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Last component
+    pub fn file_name(&self) -> &str {
+        self.0.rsplit('/').next().unwrap_or(&self.0)
+    }
+
+    /// Whether the last components of this path are all of `suffix`:
+    /// `app/src/Main.kt` ends with `src/Main.kt` but not with `in.kt`
+    pub fn ends_with(&self, suffix: &NormalizedPath) -> bool {
+        let suffix = suffix.0.trim_start_matches('/');
+        if suffix.is_empty() {
+            return false;
+        }
+        self.0 == suffix
+            || self
+                .0
+                .strip_suffix(suffix)
+                .is_some_and(|rest| rest.ends_with('/'))
+    }
+
+    /// Number of trailing components both paths share: 3 for
+    /// `app/com/example/Utils.kt` and `lib/com/example/Utils.kt`
+    pub fn common_suffix_len(&self, other: &NormalizedPath) -> usize {
+        self.0
+            .rsplit('/')
+            .zip(other.0.rsplit('/'))
+            .take_while(|(a, b)| !a.is_empty() && a == b)
+            .count()
+    }
+}
+
+impl fmt::Display for NormalizedPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for NormalizedPath {
+    fn from(path: &str) -> Self {
+        Self::from_string(path)
+    }
+}
+
+/// `file` relative to `root`, comparing the root's components ignoring case
+/// when they do not match exactly
+fn strip_root(file: &Path, root: &Path) -> Option<PathBuf> {
+    if let Ok(relative) = file.strip_prefix(root) {
+        return Some(relative.to_path_buf());
+    }
+
+    let root_components: Vec<String> = root
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_lowercase())
+        .collect();
+    if root_components.is_empty() {
+        return None;
+    }
+    let mut file_components = file.components();
+    for expected in &root_components {
+        let component = file_components.next()?;
+        if component.as_os_str().to_string_lossy().to_lowercase() != *expected {
+            return None;
+        }
+    }
+    Some(file_components.as_path().to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalized_forms() {
+        let normalize = |path: &str| NormalizedPath::from_string(path).to_string();
+        assert_eq!(normalize(r".\app\src\Main.kt"), "app/src/Main.kt");
+        assert_eq!(normalize("app/./src/../src//Main.kt"), "app/src/Main.kt");
+        assert_eq!(normalize("../shared/Util.kt"), "../shared/Util.kt");
+        assert_eq!(normalize("/home/dev/App.kt"), "/home/dev/App.kt");
+        assert_eq!(normalize(r"C:\Users\Dev\App.kt"), "C:/Users/Dev/App.kt");
+
+        let root = Path::new("/Users/Dev/Project");
+        let relative = |file: &str| NormalizedPath::relative_to(Path::new(file), root).to_string();
+        assert_eq!(relative("/Users/Dev/Project/app/Main.kt"), "app/Main.kt");
+        // The root is matched ignoring case, the rest keeps its casing
+        assert_eq!(relative("/users/dev/project/app/Main.kt"), "app/Main.kt");
+        assert_eq!(relative("/elsewhere/Main.kt"), "/elsewhere/Main.kt");
+        assert_eq!(
+            NormalizedPath::relative_to(Path::new("app/Main.kt"), Path::new("")).to_string(),
+            "app/Main.kt"
+        );
+    }
+
+    #[test]
+    fn test_suffix_matching() {
+        let path = NormalizedPath::from("app/src/main/java/com/example/Main.kt");
+        assert!(path.ends_with(&"com/example/Main.kt".into()));
+        assert!(path.ends_with(&r"com\example\Main.kt".into()));
+        assert!(path.ends_with(&"Main.kt".into()));
+        assert!(!path.ends_with(&"ain.kt".into()));
+        assert!(!path.ends_with(&"other/Main.kt".into()));
+        assert_eq!(path.file_name(), "Main.kt");
+        assert_eq!(path.common_suffix_len(&"lib/com/example/Main.kt".into()), 3);
+        assert_eq!(path.common_suffix_len(&"Other.kt".into()), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_names() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let file = Path::new("/project").join(OsStr::from_bytes(b"caf\xe9.kt"));
+        let normalized = NormalizedPath::relative_to(&file, Path::new("/project"));
+        assert_eq!(normalized.as_str(), "caf\u{fffd}.kt");
+    }
+}
@@ -0,0 +1,75 @@
+// Gradle source set classification
+
+use std::path::{Component, Path};
+
+/// Source set a file belongs to, from its `src/<name>/` directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SourceSet {
+    /// Production code: `main` and build type or flavor source sets
+    #[default]
+    Main,
+    /// Local unit tests and test fixtures: `test`, `testDebug`, `testFixtures`
+    Test,
+    /// Instrumented tests: `androidTest`, `androidTestDebug`
+    AndroidTest,
+}
+
+impl SourceSet {
+    /// Classify a path by the source set directory closest to the file
+    pub fn from_path(path: &Path) -> Self {
+        let names: Vec<&str> = path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect();
+
+        names
+            .windows(2)
+            .rev()
+            .find(|pair| pair[0] == "src")
+            .map(|pair| Self::from_name(pair[1]))
+            .unwrap_or_default()
+    }
+
+    /// Classify a source set by name
+    pub fn from_name(name: &str) -> Self {
+        if name.starts_with("androidTest") {
+            SourceSet::AndroidTest
+        } else if name.starts_with("test") {
+            SourceSet::Test
+        } else {
+            SourceSet::Main
+        }
+    }
+
+    pub fn is_test(&self) -> bool {
+        matches!(self, SourceSet::Test | SourceSet::AndroidTest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_set_from_path() {
+        let classify = |path: &str| SourceSet::from_path(Path::new(path));
+
+        assert_eq!(classify("app/src/main/java/Foo.kt"), SourceSet::Main);
+        assert_eq!(classify("app/src/debug/java/Foo.kt"), SourceSet::Main);
+        assert_eq!(classify("app/src/test/java/FooTest.kt"), SourceSet::Test);
+        assert_eq!(
+            classify("app/src/testDebug/java/FooTest.kt"),
+            SourceSet::Test
+        );
+        assert_eq!(
+            classify("app/src/androidTest/java/FooTest.kt"),
+            SourceSet::AndroidTest
+        );
+        // The innermost src/ wins, e.g. for projects checked out under src/
+        assert_eq!(classify("src/test/app/src/main/Foo.kt"), SourceSet::Main);
+        assert_eq!(classify("Foo.kt"), SourceSet::Main);
+    }
+}
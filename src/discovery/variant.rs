@@ -0,0 +1,158 @@
+// Android build variants and their source set overlay
+
+use super::SourceSet;
+use std::path::{Component, Path};
+
+/// A build variant: product flavors followed by a build type, e.g. `freeDebug`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    pub name: String,
+    /// Product flavors, one per flavor dimension (`free`)
+    pub flavors: Vec<String>,
+    /// Build type (`debug`)
+    pub build_type: String,
+}
+
+impl Variant {
+    /// Split a variant name into its flavors and build type. Each camelCase
+    /// word is a flavor, the last one is the build type.
+    pub fn parse(name: &str) -> Self {
+        let mut words: Vec<String> = Vec::new();
+        for c in name.chars() {
+            match words.last_mut() {
+                Some(word) if !c.is_uppercase() => word.push(c),
+                _ => words.push(c.to_lowercase().collect()),
+            }
+        }
+        let build_type = words.pop().unwrap_or_default();
+
+        Self {
+            name: name.to_string(),
+            flavors: words,
+            build_type,
+        }
+    }
+
+    pub fn is_debug(&self) -> bool {
+        self.build_type == "debug"
+    }
+
+    /// The flavors combined as Gradle names them (`freeArm`), empty without flavors
+    pub fn flavor_name(&self) -> String {
+        let mut name = String::new();
+        for flavor in &self.flavors {
+            if name.is_empty() {
+                name.push_str(flavor);
+            } else {
+                name.push_str(&capitalize(flavor));
+            }
+        }
+        name
+    }
+
+    /// Source sets compiled into the variant: `main`, each flavor, the flavor
+    /// combination, the build type and the variant itself
+    pub fn source_sets(&self) -> Vec<String> {
+        let mut names = vec!["main".to_string()];
+        names.extend(self.flavors.iter().cloned());
+        if self.flavors.len() > 1 {
+            names.push(self.flavor_name());
+        }
+        names.push(self.build_type.clone());
+        if !self.flavors.is_empty() {
+            names.push(self.name.clone());
+        }
+        names
+    }
+
+    /// Whether a file is part of the variant. Files outside a `src/<name>/`
+    /// directory belong to every variant; test source sets follow the source
+    /// set they test (`testDebug` goes with `debug`).
+    pub fn includes(&self, path: &Path) -> bool {
+        let names: Vec<&str> = path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect();
+        let Some(source_set) = names
+            .windows(2)
+            .rev()
+            .find(|pair| pair[0] == "src")
+            .map(|pair| pair[1])
+        else {
+            return true;
+        };
+
+        let tested = match SourceSet::from_name(source_set) {
+            SourceSet::Main => source_set.to_string(),
+            SourceSet::Test | SourceSet::AndroidTest => {
+                let rest = ["androidTest", "testFixtures", "test"]
+                    .iter()
+                    .find_map(|prefix| source_set.strip_prefix(prefix))
+                    .unwrap_or_default();
+                if rest.is_empty() {
+                    return true;
+                }
+                uncapitalize(rest)
+            }
+        };
+        self.source_sets().contains(&tested)
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+fn uncapitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_lowercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_variant() {
+        let variant = Variant::parse("freeArmDebug");
+        assert_eq!(variant.flavors, vec!["free", "arm"]);
+        assert_eq!(variant.build_type, "debug");
+        assert_eq!(variant.flavor_name(), "freeArm");
+        assert_eq!(
+            variant.source_sets(),
+            vec!["main", "free", "arm", "freeArm", "debug", "freeArmDebug"]
+        );
+
+        let release = Variant::parse("release");
+        assert!(release.flavors.is_empty());
+        assert!(!release.is_debug());
+        assert_eq!(release.source_sets(), vec!["main", "release"]);
+    }
+
+    #[test]
+    fn test_variant_includes() {
+        let variant = Variant::parse("freeDebug");
+        let includes = |path: &str| variant.includes(Path::new(path));
+
+        assert!(includes("app/src/main/java/Foo.kt"));
+        assert!(includes("app/src/free/java/Ads.kt"));
+        assert!(includes("app/src/freeDebug/java/Logger.kt"));
+        assert!(includes("app/src/debug/java/DevMenu.kt"));
+        assert!(!includes("app/src/paid/java/Billing.kt"));
+        assert!(!includes("app/src/release/java/Crashlytics.kt"));
+        assert!(includes("app/src/test/java/FooTest.kt"));
+        assert!(includes("app/src/testFree/java/AdsTest.kt"));
+        assert!(!includes("app/src/androidTestPaid/java/BillingTest.kt"));
+        assert!(includes("Foo.kt"));
+    }
+}
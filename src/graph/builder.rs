@@ -1,9 +1,27 @@
-use super::{Declaration, DeclarationId, Graph, Reference, ReferenceKind};
+use super::{Declaration, DeclarationId, Graph, Language, Location, Reference, ReferenceKind};
 use crate::discovery::{FileType, SourceFile};
-use crate::parser::{JavaParser, KotlinParser, Parser as SourceParser};
+use crate::parser::{JavaParser, KotlinParser, ParseResult, Parser as SourceParser};
 use miette::Result;
+use serde::Serialize;
+use std::path::PathBuf;
 use tracing::debug;
 
+/// A source file left out of the graph because it could not be read or parsed
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedFile {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+impl SkippedFile {
+    pub fn new(path: PathBuf, error: &miette::Report) -> Self {
+        Self {
+            path,
+            reason: error.to_string(),
+        }
+    }
+}
+
 /// Builder for constructing the reference graph
 pub struct GraphBuilder {
     /// The graph being built
@@ -25,6 +43,11 @@ struct UnresolvedRef {
     qualified_name: Option<String>,
     kind: ReferenceKind,
     imports: Vec<String>,
+    receiver_type: Option<String>,
+    argument_count: Option<usize>,
+    argument_names: Vec<String>,
+    /// Where the reference is, kept for doc links only
+    location: Location,
 }
 
 impl GraphBuilder {
@@ -39,34 +62,42 @@ impl GraphBuilder {
 
     /// Process a source file and add its declarations to the graph
     pub fn process_file(&mut self, file: &SourceFile) -> Result<()> {
+        if let Some(parse_result) = self.parse_file(file)? {
+            self.add_parse_result(parse_result);
+        }
+        Ok(())
+    }
+
+    /// Parse a source file without adding it to the graph; None for files
+    /// that are not Kotlin or Java
+    pub fn parse_file(&mut self, file: &SourceFile) -> Result<Option<ParseResult>> {
         let contents = file.read_contents()?;
 
         match file.file_type {
             FileType::Kotlin => {
-                self.process_kotlin_file(&file.path, &contents)?;
+                debug!("Parsing Kotlin file: {}", file.path.display());
+                Ok(Some(self.kotlin_parser.parse(&file.path, &contents)?))
             }
             FileType::Java => {
-                self.process_java_file(&file.path, &contents)?;
+                debug!("Parsing Java file: {}", file.path.display());
+                Ok(Some(self.java_parser.parse(&file.path, &contents)?))
             }
             FileType::XmlManifest
             | FileType::XmlLayout
             | FileType::XmlNavigation
             | FileType::XmlMenu => {
                 // XML files are processed separately for entry point detection
+                Ok(None)
             }
             FileType::XmlOther => {
                 // Ignore other XML files
+                Ok(None)
             }
         }
-
-        Ok(())
     }
 
-    fn process_kotlin_file(&mut self, path: &std::path::Path, contents: &str) -> Result<()> {
-        debug!("Parsing Kotlin file: {}", path.display());
-
-        let parse_result = self.kotlin_parser.parse(path, contents)?;
-
+    /// Add the declarations and references of an already parsed file
+    pub fn add_parse_result(&mut self, parse_result: ParseResult) {
         // Add declarations to graph (clone since we need to reference them later)
         let declarations = parse_result.declarations.clone();
         for decl in parse_result.declarations {
@@ -75,25 +106,6 @@ impl GraphBuilder {
 
         // Store unresolved references for later resolution
         self.store_unresolved_references(&declarations, parse_result.references);
-
-        Ok(())
-    }
-
-    fn process_java_file(&mut self, path: &std::path::Path, contents: &str) -> Result<()> {
-        debug!("Parsing Java file: {}", path.display());
-
-        let parse_result = self.java_parser.parse(path, contents)?;
-
-        // Add declarations to graph (clone since we need to reference them later)
-        let declarations = parse_result.declarations.clone();
-        for decl in parse_result.declarations {
-            self.graph.add_declaration(decl);
-        }
-
-        // Store unresolved references for later resolution
-        self.store_unresolved_references(&declarations, parse_result.references);
-
-        Ok(())
     }
 
     /// Store unresolved references, attributing each to the correct enclosing declaration
@@ -132,6 +144,10 @@ impl GraphBuilder {
                     qualified_name: unresolved.qualified_name,
                     kind: unresolved.kind,
                     imports: unresolved.imports,
+                    receiver_type: unresolved.receiver_type,
+                    argument_count: unresolved.argument_count,
+                    argument_names: unresolved.argument_names,
+                    location: unresolved.location,
                 });
             }
         }
@@ -148,7 +164,14 @@ impl GraphBuilder {
         let references = std::mem::take(&mut self.unresolved_references);
 
         for unresolved in references {
-            let resolved_ids = self.resolve_reference(&unresolved);
+            let (resolved_ids, excluded) = self.resolve_reference(&unresolved);
+            self.graph.mark_arity_excluded(excluded);
+            let ambiguous = resolved_ids
+                .iter()
+                .filter_map(|id| self.graph.get_declaration(id))
+                .filter(|d| d.kind.is_callable())
+                .count()
+                > 1;
             for to_id in resolved_ids {
                 // Skip self-references (e.g., property referencing itself in initialization)
                 // These are artifacts of parsing and don't represent actual code usage
@@ -176,29 +199,77 @@ impl GraphBuilder {
                     }
                 }
 
-                let reference = Reference::new(
-                    unresolved.kind,
-                    super::Location::new(
+                // Doc links keep their own location so the comment can be
+                // reported, and calls so their arguments can be rewritten
+                let location = if unresolved.kind.counts_as_usage()
+                    && unresolved.kind != ReferenceKind::Call
+                {
+                    Location::new(
                         unresolved.from.file.clone(),
                         0, // Line info not preserved in unresolved ref
                         0,
                         unresolved.from.start,
                         unresolved.from.end,
-                    ),
-                    unresolved.name.clone(),
-                );
+                    )
+                } else {
+                    unresolved.location.clone()
+                };
+                let reference = Reference::new(unresolved.kind, location, unresolved.name.clone())
+                    .with_ambiguous(ambiguous);
                 self.graph
-                    .add_reference(&unresolved.from, &to_id, reference);
+                    .add_reference(&unresolved.from, &to_id, reference.clone());
+
+                // A lambda or method reference implements the interface method
+                if unresolved.kind == ReferenceKind::SamConversion {
+                    let sam = self
+                        .graph
+                        .single_abstract_method(&to_id)
+                        .map(|m| m.id.clone());
+                    if let Some(sam) = sam {
+                        let call = Reference {
+                            kind: ReferenceKind::Call,
+                            ..reference
+                        };
+                        self.graph.add_reference(&unresolved.from, &sam, call);
+                    }
+                }
             }
         }
     }
 
     /// Try to resolve a reference to declarations (may return multiple for overloaded functions)
-    fn resolve_reference(&self, unresolved: &UnresolvedRef) -> Vec<DeclarationId> {
+    ///
+    /// Also returns the overloads passed over because they cannot take the
+    /// call's arguments.
+    fn resolve_reference(
+        &self,
+        unresolved: &UnresolvedRef,
+    ) -> (Vec<DeclarationId>, Vec<DeclarationId>) {
+        // Extensions of the same name are told apart by their receiver, and
+        // overloads by the arguments they take
+        let narrow = |candidates: Vec<&Declaration>| {
+            let candidates = self
+                .graph
+                .prefer_receiver(candidates, unresolved.receiver_type.as_deref());
+            let (kept, excluded) = self.graph.prefer_arity(
+                candidates,
+                unresolved.argument_count,
+                &unresolved.argument_names,
+            );
+            let ids = |decls: Vec<&Declaration>| -> Vec<DeclarationId> {
+                decls.iter().map(|d| d.id.clone()).collect()
+            };
+            (ids(kept), ids(excluded))
+        };
+        let by_fqn = |fqn: &str| {
+            let found = self.graph.find_all_by_fqn(fqn);
+            (!found.is_empty()).then(|| narrow(found))
+        };
+
         // Try fully qualified name first
         if let Some(fqn) = &unresolved.qualified_name {
-            if let Some(decl) = self.graph.find_by_fqn(fqn) {
-                return vec![decl.id.clone()];
+            if let Some(resolved) = by_fqn(fqn) {
+                return resolved;
             }
         }
 
@@ -208,14 +279,14 @@ impl GraphBuilder {
             if import.ends_with(".*") {
                 let package = &import[..import.len() - 2];
                 let fqn = format!("{}.{}", package, unresolved.name);
-                if let Some(decl) = self.graph.find_by_fqn(&fqn) {
-                    return vec![decl.id.clone()];
+                if let Some(resolved) = by_fqn(&fqn) {
+                    return resolved;
                 }
             }
             // Specific import
             else if import.ends_with(&format!(".{}", unresolved.name)) {
-                if let Some(decl) = self.graph.find_by_fqn(import) {
-                    return vec![decl.id.clone()];
+                if let Some(resolved) = by_fqn(import) {
+                    return resolved;
                 }
             }
             // Aliased import (Kotlin)
@@ -223,22 +294,29 @@ impl GraphBuilder {
                 let alias = &import[alias_start + 4..];
                 if alias == unresolved.name {
                     let original = &import[..alias_start];
-                    if let Some(decl) = self.graph.find_by_fqn(original) {
-                        return vec![decl.id.clone()];
+                    if let Some(resolved) = by_fqn(original) {
+                        return resolved;
                     }
                 }
             }
         }
 
         // Try simple name match - return ALL candidates for overloaded functions
-        let candidates = self.graph.find_by_name(&unresolved.name);
-        if !candidates.is_empty() {
-            // For ambiguous references (overloaded functions), mark all as referenced
-            // This is conservative but avoids false positives
-            return candidates.iter().map(|c| c.id.clone()).collect();
+        let mut candidates = self.graph.find_by_name(&unresolved.name);
+
+        // Java code calls Kotlin declarations by their JVM names as well
+        let from_java = self
+            .graph
+            .get_declaration(&unresolved.from)
+            .is_some_and(|d| d.language == Language::Java);
+        if from_java {
+            candidates.extend(self.graph.find_by_jvm_name(&unresolved.name));
         }
 
-        Vec::new()
+        // For ambiguous references (overloaded functions that all accept the
+        // arguments), mark all as referenced
+        // This is conservative but avoids false positives
+        narrow(candidates)
     }
 }
 
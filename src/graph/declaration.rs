@@ -202,6 +202,10 @@ pub struct Declaration {
     /// Location in source code
     pub location: Location,
 
+    /// Size and branch count of the source, measured at parse time (None
+    /// for declarations not parsed from source)
+    pub size: Option<DeclarationSize>,
+
     /// Parent declaration (e.g., class for a method)
     pub parent: Option<DeclarationId>,
 
@@ -217,6 +221,13 @@ pub struct Declaration {
     /// Extended/implemented types (for classes)
     pub super_types: Vec<String>,
 
+    /// Receiver type of an extension function or property (`String` for
+    /// `fun String.slugify()`)
+    pub receiver_type: Option<String>,
+
+    /// Parameters of a function or constructor, for telling overloads apart
+    pub signature: Option<Signature>,
+
     /// Modifiers (for additional analysis)
     pub modifiers: Vec<String>,
 
@@ -224,6 +235,43 @@ pub struct Declaration {
     pub language: Language,
 }
 
+/// How much source a declaration spans, for prioritizing deletions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeclarationSize {
+    /// Lines of code, including the first and last partial lines
+    pub lines: usize,
+
+    /// Bytes of source
+    pub bytes: usize,
+
+    /// Branch points (`if`, `when`/`switch` entries, loops, `catch`, `&&`,
+    /// `||`, `?:`): cyclomatic complexity minus one
+    pub branches: usize,
+}
+
+/// Parameter list of a function or constructor
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Signature {
+    /// Parameter names, in declaration order
+    pub parameter_names: Vec<String>,
+
+    /// Parameters without a default value (excluding a trailing vararg)
+    pub required: usize,
+
+    /// Whether the last parameter takes any number of arguments
+    /// (`vararg items: T`, `T... items`)
+    pub variadic: bool,
+}
+
+impl Signature {
+    /// Whether a call with `count` arguments, of which `named` are passed by
+    /// name, can target this signature
+    pub fn accepts(&self, count: usize, named: &[String]) -> bool {
+        let fits = count >= self.required && (self.variadic || count <= self.parameter_names.len());
+        fits && named.iter().all(|name| self.parameter_names.contains(name))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Language {
     Kotlin,
@@ -245,16 +293,30 @@ impl Declaration {
             kind,
             visibility: Visibility::default(),
             location,
+            size: None,
             parent: None,
             is_static: false,
             is_abstract: false,
             annotations: Vec::new(),
             super_types: Vec::new(),
+            receiver_type: None,
+            signature: None,
             modifiers: Vec::new(),
             language,
         }
     }
 
+    /// Check if this is the declaration of a Kotlin script (`build.gradle.kts`,
+    /// `*.main.kts`), which runs its top-level statements
+    pub fn is_script(&self) -> bool {
+        self.kind == DeclarationKind::File
+            && self
+                .location
+                .file
+                .extension()
+                .is_some_and(|ext| ext == "kts")
+    }
+
     /// Check if this declaration is an Android entry point
     pub fn is_android_entry_point(&self) -> bool {
         // Check super types for Android components
@@ -272,6 +334,8 @@ impl Declaration {
             "Application",
             "ViewModel",
             "AndroidViewModel",
+            "AppWidgetProvider",
+            "GlanceAppWidgetReceiver",
         ];
 
         for super_type in &self.super_types {
@@ -282,9 +346,9 @@ impl Declaration {
             }
         }
 
-        // Check annotations
+        // Check annotations. Composables are left to the entry point
+        // detector, where `compose_previews` decides whether they count.
         let entry_annotations = [
-            "Composable",
             "Test",
             "Before",
             "After",
@@ -300,12 +364,13 @@ impl Declaration {
             "HiltViewModel",
         ];
 
-        for annotation in &self.annotations {
-            for entry_ann in &entry_annotations {
-                if annotation.contains(entry_ann) {
-                    return true;
-                }
-            }
+        // Exact names, so `@VisibleForTesting` isn't taken for `@Test`
+        let is_entry_annotation = |annotation: &String| {
+            let name = super::simple_type_name(annotation);
+            entry_annotations.contains(&name.rsplit(':').next().unwrap_or(name))
+        };
+        if self.annotations.iter().any(is_entry_annotation) {
+            return true;
         }
 
         // Check for main function
@@ -342,6 +407,76 @@ impl Declaration {
             self.location
         )
     }
+
+    /// Names Java code uses for a Kotlin declaration besides its own name:
+    /// a `@JvmName` rename, or a property's getter and setter
+    ///
+    /// `@JvmStatic` bridges and `@JvmOverloads` variants keep the Kotlin
+    /// name, so they need no alternate name.
+    pub fn jvm_names(&self) -> Vec<String> {
+        if self.language != Language::Kotlin {
+            return Vec::new();
+        }
+        let jvm_name = |target: &str| {
+            self.annotations
+                .iter()
+                .find_map(|a| jvm_name_argument(a, target))
+        };
+
+        match self.kind {
+            DeclarationKind::Function | DeclarationKind::Method => {
+                jvm_name("").into_iter().collect()
+            }
+            DeclarationKind::Property => {
+                // Fields and constants are accessed directly by their name
+                let direct = self.modifiers.iter().any(|m| m == "const")
+                    || self.annotations.iter().any(|a| a.contains("JvmField"));
+                if direct {
+                    return Vec::new();
+                }
+
+                // `isActive` compiles to `isActive()` and `setActive()`
+                let (getter, setter) = match self.name.strip_prefix("is") {
+                    Some(rest) if rest.starts_with(char::is_uppercase) => {
+                        (self.name.clone(), format!("set{}", rest))
+                    }
+                    _ => {
+                        let capitalized = capitalize(&self.name);
+                        (format!("get{}", capitalized), format!("set{}", capitalized))
+                    }
+                };
+                [
+                    jvm_name("get").unwrap_or(getter),
+                    jvm_name("set").unwrap_or(setter),
+                ]
+                .into_iter()
+                .filter(|name| *name != self.name)
+                .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// The name set by a `@JvmName("...")` annotation with the given use-site
+/// target (`""`, `get` or `set`)
+fn jvm_name_argument(annotation: &str, target: &str) -> Option<String> {
+    let annotation = annotation.trim_start_matches('@');
+    let (name, args) = annotation.split_once('(')?;
+    let (site, name) = name.split_once(':').unwrap_or(("", name));
+    if site.trim() != target || name.trim().rsplit('.').next() != Some("JvmName") {
+        return None;
+    }
+    let value = args.split('"').nth(1)?;
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
 }
 
 #[cfg(test)]
@@ -409,4 +544,45 @@ mod tests {
         assert!(decl.matches_pattern("MainActivity"));
         assert!(!decl.matches_pattern("*Fragment"));
     }
+
+    #[test]
+    fn test_jvm_names() {
+        let declaration = |name: &str, kind: DeclarationKind, annotations: &[&str]| {
+            let mut decl = Declaration::new(
+                DeclarationId::new(PathBuf::from("Utils.kt"), 0, 100),
+                name.to_string(),
+                kind,
+                Location::new(PathBuf::from("Utils.kt"), 1, 1, 0, 100),
+                Language::Kotlin,
+            );
+            decl.annotations = annotations.iter().map(|a| a.to_string()).collect();
+            decl
+        };
+
+        let renamed = declaration(
+            "format",
+            DeclarationKind::Function,
+            &["@JvmName(\"formatDate\")", "@JvmOverloads"],
+        );
+        assert_eq!(renamed.jvm_names(), vec!["formatDate"]);
+        let plain = declaration("format", DeclarationKind::Function, &["@JvmStatic"]);
+        assert!(plain.jvm_names().is_empty());
+
+        let property = declaration("userName", DeclarationKind::Property, &[]);
+        assert_eq!(property.jvm_names(), vec!["getUserName", "setUserName"]);
+        let flag = declaration("isActive", DeclarationKind::Property, &[]);
+        assert_eq!(flag.jvm_names(), vec!["setActive"]);
+        let custom = declaration(
+            "enabled",
+            DeclarationKind::Property,
+            &["@get:JvmName(\"isEnabled\")"],
+        );
+        assert_eq!(custom.jvm_names(), vec!["isEnabled", "setEnabled"]);
+        let field = declaration("TAG", DeclarationKind::Property, &["@JvmField"]);
+        assert!(field.jvm_names().is_empty());
+
+        let mut java = declaration("userName", DeclarationKind::Field, &[]);
+        java.language = Language::Java;
+        assert!(java.jvm_names().is_empty());
+    }
 }
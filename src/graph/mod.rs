@@ -6,16 +6,47 @@ mod declaration;
 mod parallel_builder;
 pub mod reference;
 
-pub use builder::GraphBuilder;
+pub use builder::{GraphBuilder, SkippedFile};
 pub use declaration::{
-    Declaration, DeclarationId, DeclarationKind, Language, Location, Visibility,
+    Declaration, DeclarationId, DeclarationKind, DeclarationSize, Language, Location, Signature,
+    Visibility,
 };
 pub use parallel_builder::ParallelGraphBuilder;
 pub use reference::{Reference, ReferenceKind, UnresolvedReference};
 
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+/// Strip arguments, generics and the package from a type or annotation as
+/// written in source (`@androidx.annotation.Keep`, `BaseActivity()`,
+/// `List<String>` -> `Keep`, `BaseActivity`, `List`)
+pub fn simple_type_name(text: &str) -> &str {
+    let text = text.trim_start_matches('@');
+    let end = text.find(['(', '<', '?']).unwrap_or(text.len());
+    let name = text[..end].trim();
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// How a declaration on a path keeps the next one alive
+#[derive(Debug, Clone, Copy)]
+pub enum PathLink<'a> {
+    /// It references the next declaration
+    Reference(&'a Reference),
+    /// The next declaration is one of its members
+    Member,
+    /// The next declaration encloses it
+    Parent,
+}
+
+/// One hop of a path found by [`Graph::shortest_path`]
+#[derive(Debug, Clone, Copy)]
+pub struct PathStep<'a> {
+    pub from: &'a Declaration,
+    pub to: &'a Declaration,
+    pub link: PathLink<'a>,
+}
 
 /// The reference graph containing all declarations and their relationships
 #[derive(Debug)]
@@ -36,8 +67,18 @@ pub struct Graph {
     /// Map from fully qualified name to declaration
     fqn_index: HashMap<String, DeclarationId>,
 
+    /// Map from alternate JVM name (see [`Declaration::jvm_names`]) to Kotlin declarations
+    jvm_name_index: HashMap<String, Vec<DeclarationId>>,
+
     /// Map from parent to children (for fast member lookup)
     children_index: HashMap<DeclarationId, Vec<DeclarationId>>,
+
+    /// Overloads a call passed over because they cannot take its arguments
+    arity_excluded: HashSet<DeclarationId>,
+
+    /// References that do not count as usage (doc links), by target; kept
+    /// out of `inner` so reachability never follows them
+    soft_references: HashMap<DeclarationId, Vec<(DeclarationId, Reference)>>,
 }
 
 impl Graph {
@@ -49,7 +90,10 @@ impl Graph {
             declarations: HashMap::new(),
             name_index: HashMap::new(),
             fqn_index: HashMap::new(),
+            jvm_name_index: HashMap::new(),
             children_index: HashMap::new(),
+            arity_excluded: HashSet::new(),
+            soft_references: HashMap::new(),
         }
     }
 
@@ -72,6 +116,14 @@ impl Graph {
             self.fqn_index.insert(fqn.clone(), id.clone());
         }
 
+        // Index by the names Java callers use
+        for jvm_name in decl.jvm_names() {
+            self.jvm_name_index
+                .entry(jvm_name)
+                .or_default()
+                .push(id.clone());
+        }
+
         // Index by parent (for fast children lookup)
         if let Some(parent_id) = &decl.parent {
             self.children_index
@@ -87,12 +139,23 @@ impl Graph {
     }
 
     /// Add a reference between two declarations
+    ///
+    /// References that do not count as usage are stored as soft references.
     pub fn add_reference(
         &mut self,
         from: &DeclarationId,
         to: &DeclarationId,
         reference: Reference,
     ) {
+        if !reference.kind.counts_as_usage() {
+            if self.node_map.contains_key(from) && self.node_map.contains_key(to) {
+                self.soft_references
+                    .entry(to.clone())
+                    .or_default()
+                    .push((from.clone(), reference));
+            }
+            return;
+        }
         if let (Some(&from_idx), Some(&to_idx)) = (self.node_map.get(from), self.node_map.get(to)) {
             self.inner.add_edge(from_idx, to_idx, reference);
         }
@@ -125,6 +188,18 @@ impl Graph {
             .unwrap_or_default()
     }
 
+    /// Find Kotlin declarations Java code refers to by this name
+    pub fn find_by_jvm_name(&self, name: &str) -> Vec<&Declaration> {
+        self.jvm_name_index
+            .get(name)
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| self.declarations.get(id))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Find declaration by fully qualified name
     pub fn find_by_fqn(&self, fqn: &str) -> Option<&Declaration> {
         self.fqn_index
@@ -132,6 +207,186 @@ impl Graph {
             .and_then(|id| self.declarations.get(id))
     }
 
+    /// Find every declaration sharing a fully qualified name: top-level
+    /// overloads and extensions of the same name on different receivers
+    pub fn find_all_by_fqn(&self, fqn: &str) -> Vec<&Declaration> {
+        let Some(decl) = self.find_by_fqn(fqn) else {
+            return Vec::new();
+        };
+        if decl.kind.is_type() {
+            return vec![decl];
+        }
+        self.find_by_name(&decl.name)
+            .into_iter()
+            .filter(|d| d.fully_qualified_name.as_deref() == Some(fqn))
+            .collect()
+    }
+
+    /// Narrow the candidates of a reference to the extensions and members
+    /// whose receiver accepts the static type of the reference's receiver:
+    /// `repository.findById()` on a `UserRepository` is the `findById` of
+    /// `UserRepository` (or of a supertype), not of `OrderRepository`
+    ///
+    /// Candidates that are neither extensions nor members are kept. When the
+    /// receiver type is unknown or no candidate accepts it, all candidates are
+    /// kept: the type may come from a library or be inferred wrongly.
+    pub fn prefer_receiver<'a>(
+        &'a self,
+        candidates: Vec<&'a Declaration>,
+        receiver_type: Option<&str>,
+    ) -> Vec<&'a Declaration> {
+        let Some(receiver_type) = receiver_type else {
+            return candidates;
+        };
+        let accepts = |decl: &Declaration| match decl.receiver_type.as_deref() {
+            Some(extended) => self.accepts_receiver(extended, receiver_type),
+            None => self
+                .member_owner(decl)
+                .is_some_and(|owner| self.accepts_receiver(&owner.name, receiver_type)),
+        };
+        if !candidates.iter().any(|c| accepts(c)) {
+            return candidates;
+        }
+        candidates
+            .into_iter()
+            .filter(|c| (c.receiver_type.is_none() && self.member_owner(c).is_none()) || accepts(c))
+            .collect()
+    }
+
+    /// The class, interface or object declaring a member
+    fn member_owner(&self, decl: &Declaration) -> Option<&Declaration> {
+        self.get_declaration(decl.parent.as_ref()?)
+            .filter(|parent| parent.kind.is_type())
+    }
+
+    /// Narrow the candidates of a call to the overloads whose parameters accept
+    /// the call's arguments
+    ///
+    /// Returns the remaining candidates and the overloads passed over.
+    /// Candidates that are not callables or have no known signature are kept,
+    /// and so is every candidate when the arguments cannot be counted or no
+    /// overload accepts them.
+    pub fn prefer_arity<'a>(
+        &self,
+        candidates: Vec<&'a Declaration>,
+        argument_count: Option<usize>,
+        argument_names: &[String],
+    ) -> (Vec<&'a Declaration>, Vec<&'a Declaration>) {
+        let Some(count) = argument_count else {
+            return (candidates, Vec::new());
+        };
+        let accepts = |decl: &Declaration| match &decl.signature {
+            Some(signature) if decl.kind.is_callable() => signature.accepts(count, argument_names),
+            _ => true,
+        };
+        let has_overload = candidates
+            .iter()
+            .any(|c| c.kind.is_callable() && c.signature.is_some() && accepts(c));
+        if !has_overload {
+            return (candidates, Vec::new());
+        }
+        candidates.into_iter().partition(|c| accepts(c))
+    }
+
+    /// Record overloads a call was not resolved to because of its arguments
+    pub fn mark_arity_excluded(&mut self, ids: impl IntoIterator<Item = DeclarationId>) {
+        self.arity_excluded.extend(ids);
+    }
+
+    /// Whether a call with the same name was resolved to another overload
+    /// because of its argument count, so a finding on this declaration rests
+    /// on overload resolution
+    pub fn is_arity_excluded(&self, id: &DeclarationId) -> bool {
+        self.arity_excluded.contains(id)
+    }
+
+    /// Whether an extension on `extended` can be called on a `receiver_type`
+    fn accepts_receiver(&self, extended: &str, receiver_type: &str) -> bool {
+        // Supertypes of common Kotlin types that are not declared in the project
+        const LIBRARY_SUPERTYPES: &[(&str, &[&str])] = &[
+            ("String", &["CharSequence", "Comparable"]),
+            ("Int", &["Number", "Comparable"]),
+            ("Long", &["Number", "Comparable"]),
+            ("Short", &["Number", "Comparable"]),
+            ("Byte", &["Number", "Comparable"]),
+            ("Double", &["Number", "Comparable"]),
+            ("Float", &["Number", "Comparable"]),
+            ("Char", &["Comparable"]),
+            ("Boolean", &["Comparable"]),
+        ];
+
+        // `Any`, and type parameters such as `T` in `fun <T> T.also()`
+        let is_generic = extended == "Any"
+            || (extended.len() <= 2 && extended.chars().all(|c| c.is_ascii_uppercase()));
+
+        is_generic
+            || extended == receiver_type
+            || LIBRARY_SUPERTYPES
+                .iter()
+                .any(|(name, supers)| *name == receiver_type && supers.contains(&extended))
+            || self
+                .find_by_name(receiver_type)
+                .into_iter()
+                .filter(|d| d.kind.is_type())
+                .any(|d| self.inherits_from(d, extended))
+    }
+
+    /// Whether `type_name` is a direct or transitive supertype of `decl`
+    ///
+    /// Super types are recorded as written in source, so they are compared by
+    /// simple name and followed through the graph when declared in the project.
+    pub fn inherits_from(&self, decl: &Declaration, type_name: &str) -> bool {
+        let target = simple_type_name(type_name);
+        let mut visited = HashSet::new();
+        let mut queue: Vec<&Declaration> = vec![decl];
+
+        while let Some(current) = queue.pop() {
+            if !visited.insert(current.id.clone()) {
+                continue;
+            }
+            for super_type in &current.super_types {
+                let name = simple_type_name(super_type);
+                if name == target {
+                    return true;
+                }
+                queue.extend(
+                    self.find_by_name(name)
+                        .into_iter()
+                        .filter(|d| d.kind.is_type()),
+                );
+            }
+        }
+
+        false
+    }
+
+    /// The single abstract method of a functional interface
+    ///
+    /// Java interface methods without a body are abstract; Kotlin members are
+    /// not marked, so a Kotlin interface with one method qualifies as well.
+    pub fn single_abstract_method(&self, id: &DeclarationId) -> Option<&Declaration> {
+        let decl = self.get_declaration(id)?;
+        if decl.kind != DeclarationKind::Interface {
+            return None;
+        }
+
+        let methods: Vec<&Declaration> = self
+            .get_children(id)
+            .into_iter()
+            .filter_map(|child| self.get_declaration(child))
+            .filter(|m| m.kind.is_callable() && !m.is_static)
+            .filter(|m| !m.modifiers.iter().any(|modifier| modifier == "default"))
+            .collect();
+        let abstract_methods: Vec<&Declaration> =
+            methods.iter().copied().filter(|m| m.is_abstract).collect();
+
+        match (abstract_methods.as_slice(), methods.as_slice()) {
+            ([sam], _) => Some(sam),
+            ([], [only]) => Some(only),
+            _ => None,
+        }
+    }
+
     /// Get all declarations that reference the given declaration
     pub fn get_references_to(&self, id: &DeclarationId) -> Vec<(&Declaration, &Reference)> {
         let Some(&node_idx) = self.node_map.get(id) else {
@@ -164,6 +419,26 @@ impl Graph {
             .collect()
     }
 
+    /// Get the soft references (doc links) to a declaration
+    pub fn get_soft_references_to(&self, id: &DeclarationId) -> Vec<(&Declaration, &Reference)> {
+        self.soft_references
+            .get(id)
+            .into_iter()
+            .flatten()
+            .filter_map(|(from, reference)| Some((self.declarations.get(from)?, reference)))
+            .collect()
+    }
+
+    /// All soft references, as target and referencing declaration
+    pub fn soft_references(
+        &self,
+    ) -> impl Iterator<Item = (&DeclarationId, &DeclarationId, &Reference)> {
+        self.soft_references.iter().flat_map(|(to, refs)| {
+            refs.iter()
+                .map(move |(from, reference)| (to, from, reference))
+        })
+    }
+
     /// Check if a declaration is referenced by anything
     pub fn is_referenced(&self, id: &DeclarationId) -> bool {
         let Some(&node_idx) = self.node_map.get(id) else {
@@ -243,6 +518,136 @@ impl Graph {
         self.inner.edge_count()
     }
 
+    /// Find declarations by fully qualified name, JVM name, simple name or
+    /// `file:line` (the file may be any suffix of the path)
+    pub fn resolve(&self, query: &str) -> Vec<&Declaration> {
+        let mut found: Vec<&Declaration> = match query.rsplit_once(':') {
+            Some((file, line)) if line.parse::<usize>().is_ok() => {
+                let line: usize = line.parse().unwrap_or_default();
+                self.declarations
+                    .values()
+                    .filter(|d| {
+                        d.location.line == line && d.location.file.ends_with(Path::new(file))
+                    })
+                    .collect()
+            }
+            _ => match self.find_by_fqn(query) {
+                Some(decl) => vec![decl],
+                None => {
+                    let mut found = self.find_by_jvm_name(query);
+                    found.extend(self.find_by_name(query));
+                    found
+                }
+            },
+        };
+        found.sort_by(|a, b| {
+            let (a, b) = (&a.id, &b.id);
+            a.file.cmp(&b.file).then(a.start.cmp(&b.start))
+        });
+        found.dedup_by(|a, b| a.id == b.id);
+        found
+    }
+
+    /// Shortest path from any of `sources` to `target`, following references,
+    /// class members and enclosing declarations the way reachability does.
+    /// Empty if `target` is itself a source, `None` if it cannot be reached.
+    pub fn shortest_path(
+        &self,
+        sources: &HashSet<DeclarationId>,
+        target: &DeclarationId,
+    ) -> Option<Vec<PathStep<'_>>> {
+        // Start from sources in source order so ties resolve deterministically
+        let mut starts: Vec<&DeclarationId> = sources
+            .iter()
+            .filter_map(|id| self.declarations.get_key_value(id).map(|(id, _)| id))
+            .collect();
+        starts.sort_by(|a, b| a.file.cmp(&b.file).then(a.start.cmp(&b.start)));
+
+        let mut previous: HashMap<&DeclarationId, Option<(&DeclarationId, PathLink<'_>)>> =
+            starts.iter().map(|id| (*id, None)).collect();
+        let mut queue: VecDeque<&DeclarationId> = starts.into_iter().collect();
+
+        while let Some(id) = queue.pop_front() {
+            if id == target {
+                let mut steps = Vec::new();
+                let mut current = id;
+                while let Some(Some((from, link))) = previous.get(current) {
+                    steps.push(PathStep {
+                        from: self.declarations.get(*from)?,
+                        to: self.declarations.get(current)?,
+                        link: *link,
+                    });
+                    current = *from;
+                }
+                steps.reverse();
+                return Some(steps);
+            }
+
+            let mut next: Vec<(&DeclarationId, PathLink<'_>)> = Vec::new();
+            if let Some(&node_idx) = self.node_map.get(id) {
+                for edge in self
+                    .inner
+                    .edges_directed(node_idx, petgraph::Direction::Outgoing)
+                {
+                    next.push((
+                        &self.inner[edge.target()],
+                        PathLink::Reference(edge.weight()),
+                    ));
+                }
+            }
+            if let Some(children) = self.children_index.get(id) {
+                next.extend(children.iter().map(|child| (child, PathLink::Member)));
+            }
+            if let Some(parent) = self.declarations.get(id).and_then(|d| d.parent.as_ref()) {
+                next.push((parent, PathLink::Parent));
+            }
+
+            for (to, link) in next {
+                let Some((to, _)) = self.declarations.get_key_value(to) else {
+                    continue;
+                };
+                if !previous.contains_key(to) {
+                    previous.insert(to, Some((id, link)));
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// A copy of the graph without the given declarations and every
+    /// reference to or from them
+    pub fn without(&self, removed: &HashSet<DeclarationId>) -> Graph {
+        let mut graph = Graph::new();
+        for id in self.inner.node_weights() {
+            if removed.contains(id) {
+                continue;
+            }
+            if let Some(decl) = self.declarations.get(id) {
+                graph.add_declaration(decl.clone());
+            }
+        }
+        for edge in self.inner.edge_references() {
+            let (from, to) = (&self.inner[edge.source()], &self.inner[edge.target()]);
+            if !removed.contains(from) && !removed.contains(to) {
+                graph.add_reference(from, to, edge.weight().clone());
+            }
+        }
+        for (to, from, reference) in self.soft_references() {
+            if !removed.contains(from) && !removed.contains(to) {
+                graph.add_reference(from, to, reference.clone());
+            }
+        }
+        graph.arity_excluded = self
+            .arity_excluded
+            .iter()
+            .filter(|id| !removed.contains(*id))
+            .cloned()
+            .collect();
+        graph
+    }
+
     /// Get the underlying petgraph for advanced operations
     pub fn inner(&self) -> &DiGraph<DeclarationId, Reference> {
         &self.inner
@@ -259,3 +664,270 @@ impl Default for Graph {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn declaration(
+        graph: &mut Graph,
+        name: &str,
+        line: usize,
+        parent: Option<&DeclarationId>,
+    ) -> DeclarationId {
+        let file = PathBuf::from("src/main/Checkout.kt");
+        let start = line * 100;
+        let mut decl = Declaration::new(
+            DeclarationId::new(file.clone(), start, start + 50),
+            name.to_string(),
+            DeclarationKind::Function,
+            Location::new(file, line, 1, start, start + 50),
+            Language::Kotlin,
+        );
+        decl.fully_qualified_name = Some(format!("com.example.{}", name));
+        decl.parent = parent.cloned();
+        graph.add_declaration(decl)
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let mut graph = Graph::new();
+        let main = declaration(&mut graph, "main", 1, None);
+        let checkout = declaration(&mut graph, "Checkout", 5, None);
+        let submit = declaration(&mut graph, "submit", 6, Some(&checkout));
+        let unused = declaration(&mut graph, "unused", 20, None);
+        let call = |name: &str, line| {
+            Reference::new(
+                ReferenceKind::Call,
+                Location::new(PathBuf::from("src/main/Checkout.kt"), line, 5, 0, 0),
+                name.to_string(),
+            )
+        };
+        graph.add_reference(&main, &checkout, call("Checkout", 2));
+        graph.add_reference(&unused, &submit, call("submit", 21));
+
+        let entry_points: HashSet<DeclarationId> = [main.clone()].into_iter().collect();
+        let path = graph.shortest_path(&entry_points, &submit).unwrap();
+        let hops: Vec<(&str, &str)> = path
+            .iter()
+            .map(|step| (step.from.name.as_str(), step.to.name.as_str()))
+            .collect();
+        assert_eq!(hops, vec![("main", "Checkout"), ("Checkout", "submit")]);
+        assert!(matches!(path[0].link, PathLink::Reference(r) if r.location.line == 2));
+        assert!(matches!(path[1].link, PathLink::Member));
+
+        assert!(graph
+            .shortest_path(&entry_points, &main)
+            .unwrap()
+            .is_empty());
+        assert!(graph.shortest_path(&entry_points, &unused).is_none());
+    }
+
+    #[test]
+    fn test_doc_links_are_soft_references() {
+        let mut graph = Graph::new();
+        let load = declaration(&mut graph, "load", 1, None);
+        let legacy = declaration(&mut graph, "legacyLoad", 5, None);
+        let link = Reference::new(
+            ReferenceKind::Documentation,
+            Location::new(PathBuf::from("src/main/Checkout.kt"), 1, 12, 0, 0),
+            "legacyLoad".to_string(),
+        );
+        graph.add_reference(&load, &legacy, link);
+
+        assert!(!graph.is_referenced(&legacy));
+        let soft = graph.get_soft_references_to(&legacy);
+        assert_eq!(soft.len(), 1);
+        assert_eq!(soft[0].0.id, load);
+        assert_eq!(graph.soft_references().count(), 1);
+    }
+
+    #[test]
+    fn test_without() {
+        let mut graph = Graph::new();
+        let main = declaration(&mut graph, "main", 1, None);
+        let checkout = declaration(&mut graph, "Checkout", 5, None);
+        let submit = declaration(&mut graph, "submit", 6, Some(&checkout));
+        let call = Reference::new(
+            ReferenceKind::Call,
+            Location::new(PathBuf::from("src/main/Checkout.kt"), 2, 5, 0, 0),
+            "submit".to_string(),
+        );
+        graph.add_reference(&main, &submit, call.clone());
+        graph.add_reference(&checkout, &submit, call);
+
+        let removed: HashSet<DeclarationId> = [main.clone()].into_iter().collect();
+        let pruned = graph.without(&removed);
+        assert_eq!(pruned.declaration_count(), 2);
+        assert!(pruned.get_declaration(&main).is_none());
+        assert_eq!(pruned.reference_count(), 1);
+        assert_eq!(pruned.get_references_to(&submit)[0].0.id, checkout);
+        assert_eq!(pruned.get_children(&checkout), vec![&submit]);
+        assert_eq!(graph.reference_count(), 2);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mut graph = Graph::new();
+        let checkout = declaration(&mut graph, "Checkout", 5, None);
+        let submit = declaration(&mut graph, "submit", 6, Some(&checkout));
+
+        assert_eq!(graph.resolve("com.example.submit")[0].id, submit);
+        assert_eq!(graph.resolve("Checkout")[0].id, checkout);
+        assert_eq!(graph.resolve("Checkout.kt:6")[0].id, submit);
+        assert_eq!(graph.resolve("main/Checkout.kt:5")[0].id, checkout);
+        assert!(graph.resolve("Checkout.kt:7").is_empty());
+        assert!(graph.resolve("Cart").is_empty());
+    }
+
+    #[test]
+    fn test_prefer_receiver() {
+        let decl = |name: &str, line: usize, kind: DeclarationKind| {
+            let file = PathBuf::from("src/main/Money.kt");
+            let start = line * 100;
+            let mut decl = Declaration::new(
+                DeclarationId::new(file.clone(), start, start + 50),
+                name.to_string(),
+                kind,
+                Location::new(file, line, 1, start, start + 50),
+                Language::Kotlin,
+            );
+            decl.fully_qualified_name = Some(format!("com.example.{}", name));
+            decl
+        };
+        let extension = |line, receiver: &str| {
+            let mut format = decl("format", line, DeclarationKind::Function);
+            format.receiver_type = Some(receiver.to_string());
+            format
+        };
+
+        let mut graph = Graph::new();
+        graph.add_declaration(decl("Money", 1, DeclarationKind::Class));
+        let mut price = decl("Price", 2, DeclarationKind::Class);
+        price.super_types.push("Money".to_string());
+        graph.add_declaration(price);
+        let on_string = graph.add_declaration(extension(10, "String"));
+        let on_money = graph.add_declaration(extension(11, "Money"));
+        let on_any = graph.add_declaration(extension(12, "T"));
+
+        let resolve = |receiver: Option<&str>| {
+            let found = graph.find_all_by_fqn("com.example.format");
+            let mut ids: Vec<DeclarationId> = graph
+                .prefer_receiver(found, receiver)
+                .iter()
+                .map(|d| d.id.clone())
+                .collect();
+            ids.sort_by_key(|id| id.start);
+            ids
+        };
+
+        assert_eq!(
+            resolve(Some("String")),
+            vec![on_string.clone(), on_any.clone()]
+        );
+        assert_eq!(
+            resolve(Some("Price")),
+            vec![on_money.clone(), on_any.clone()]
+        );
+        // Unknown receivers keep every candidate
+        assert_eq!(resolve(None).len(), 3);
+        assert_eq!(resolve(Some("Boolean")), vec![on_any]);
+
+        // Members are told apart by the class declaring them
+        let member = |graph: &mut Graph, line, owner: &DeclarationId| {
+            let mut find = decl("findById", line, DeclarationKind::Method);
+            find.parent = Some(owner.clone());
+            graph.add_declaration(find)
+        };
+        let users = graph.add_declaration(decl("UserRepository", 20, DeclarationKind::Class));
+        let orders = graph.add_declaration(decl("OrderRepository", 30, DeclarationKind::Class));
+        let mut cached = decl("CachedUsers", 40, DeclarationKind::Class);
+        cached.super_types.push("UserRepository".to_string());
+        graph.add_declaration(cached);
+        let find_user = member(&mut graph, 21, &users);
+        let find_order = member(&mut graph, 31, &orders);
+        let find = |receiver: Option<&str>| -> Vec<DeclarationId> {
+            let found = graph.find_all_by_fqn("com.example.findById");
+            let mut ids: Vec<DeclarationId> = graph
+                .prefer_receiver(found, receiver)
+                .iter()
+                .map(|d| d.id.clone())
+                .collect();
+            ids.sort_by_key(|id| id.start);
+            ids
+        };
+        assert_eq!(find(Some("UserRepository")), vec![find_user.clone()]);
+        assert_eq!(find(Some("CachedUsers")), vec![find_user.clone()]);
+        assert_eq!(find(Some("OrderRepository")), vec![find_order.clone()]);
+        assert_eq!(find(Some("Session")), vec![find_user, find_order]);
+        assert_eq!(
+            graph.find_all_by_fqn("com.example.Money")[0].id,
+            graph.find_by_fqn("com.example.Money").unwrap().id
+        );
+    }
+
+    #[test]
+    fn test_prefer_arity() {
+        let overload = |line: usize, names: &[&str], required, variadic| {
+            let file = PathBuf::from("src/main/Loader.kt");
+            let start = line * 100;
+            let mut decl = Declaration::new(
+                DeclarationId::new(file.clone(), start, start + 50),
+                "load".to_string(),
+                DeclarationKind::Function,
+                Location::new(file, line, 1, start, start + 50),
+                Language::Kotlin,
+            );
+            decl.signature = Some(Signature {
+                parameter_names: names.iter().map(|n| n.to_string()).collect(),
+                required,
+                variadic,
+            });
+            decl
+        };
+
+        let mut graph = Graph::new();
+        let one = graph.add_declaration(overload(1, &["id"], 1, false));
+        let two = graph.add_declaration(overload(2, &["id", "retries"], 1, false));
+        let many = graph.add_declaration(overload(3, &["id", "tags"], 1, true));
+        let property = declaration(&mut graph, "load", 4, None);
+        graph.declarations.get_mut(&property).unwrap().kind = DeclarationKind::Property;
+
+        let resolve = |count: Option<usize>, names: &[&str]| {
+            let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+            let (kept, excluded) = graph.prefer_arity(graph.find_by_name("load"), count, &names);
+            let ids = |decls: Vec<&Declaration>| {
+                let mut ids: Vec<DeclarationId> = decls.iter().map(|d| d.id.clone()).collect();
+                ids.sort_by_key(|id| id.start);
+                ids
+            };
+            (ids(kept), ids(excluded))
+        };
+
+        assert_eq!(
+            resolve(Some(1), &[]).0,
+            vec![one.clone(), two.clone(), many.clone(), property.clone()]
+        );
+        assert_eq!(
+            resolve(Some(2), &[]),
+            (
+                vec![two.clone(), many.clone(), property.clone()],
+                vec![one.clone()]
+            )
+        );
+        assert_eq!(
+            resolve(Some(2), &["retries"]),
+            (
+                vec![two.clone(), property.clone()],
+                vec![one.clone(), many.clone()]
+            )
+        );
+        assert_eq!(resolve(Some(5), &[]).1, vec![one.clone(), two.clone()]);
+        // Arguments that cannot be counted, or that no overload takes, keep
+        // every candidate
+        assert!(resolve(None, &[]).1.is_empty());
+        assert!(resolve(Some(0), &[]).1.is_empty());
+        assert!(resolve(Some(1), &["missing"]).1.is_empty());
+    }
+}
@@ -1,107 +1,298 @@
 // Parallel graph builder using rayon
 
-use super::{Declaration, DeclarationId, Graph, Location, Reference, ReferenceKind};
+use super::{
+    Declaration, DeclarationId, Graph, Language, Location, Reference, ReferenceKind, SkippedFile,
+};
 use crate::discovery::{FileType, SourceFile};
-use crate::parser::{JavaParser, KotlinParser, Parser as SourceParser};
-use miette::Result;
+use crate::parser::{JavaParser, KotlinParser, ParseResult, Parser as SourceParser};
+use miette::{IntoDiagnostic, Result};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::PathBuf;
 use tracing::{debug, info};
 
 /// Parsed file result
 struct ParsedFile {
     declarations: Vec<Declaration>,
     unresolved_refs: Vec<UnresolvedRef>,
+    /// The parser's result, kept when an observer wants to see it
+    parse_result: Option<ParseResult>,
 }
 
+#[derive(Serialize, Deserialize)]
 struct UnresolvedRef {
     from: DeclarationId,
     name: String,
     qualified_name: Option<String>,
     kind: ReferenceKind,
     imports: Vec<String>,
+    receiver_type: Option<String>,
+    argument_count: Option<usize>,
+    argument_names: Vec<String>,
+    /// Where the reference is, kept for doc links only
+    location: Location,
+}
+
+impl UnresolvedRef {
+    /// Rough heap and inline size, to keep pending references within budget
+    fn approx_bytes(&self) -> usize {
+        let strings = |s: &[String]| s.iter().map(|s| s.len() + 24).sum::<usize>();
+        std::mem::size_of::<Self>()
+            + self.name.len()
+            + self.qualified_name.as_ref().map_or(0, String::len)
+            + self.receiver_type.as_ref().map_or(0, String::len)
+            + strings(&self.imports)
+            + strings(&self.argument_names)
+            + self.from.file.as_os_str().len()
+            + self.location.file.as_os_str().len()
+    }
+}
+
+/// Files parsed per worker thread before their results are merged, so only
+/// a bounded number of parse trees is alive at once
+const FILES_PER_THREAD: usize = 16;
+
+/// References waiting for every declaration to be in the graph. Once they
+/// exceed the memory budget they are written to a temporary directory and
+/// read back one batch at a time during resolution
+struct PendingRefs {
+    budget: Option<usize>,
+    bytes: usize,
+    in_memory: Vec<UnresolvedRef>,
+    spill: Option<SpillDir>,
+}
+
+impl PendingRefs {
+    fn new(budget: Option<usize>) -> Self {
+        Self {
+            budget,
+            bytes: 0,
+            in_memory: Vec::new(),
+            spill: None,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.in_memory.len() + self.spill.as_ref().map_or(0, |s| s.refs)
+    }
+
+    fn extend(&mut self, refs: Vec<UnresolvedRef>) -> Result<()> {
+        self.bytes += refs.iter().map(UnresolvedRef::approx_bytes).sum::<usize>();
+        self.in_memory.extend(refs);
+        if self.budget.is_some_and(|budget| self.bytes > budget) {
+            if self.spill.is_none() {
+                self.spill = Some(SpillDir::create()?);
+            }
+            let spill = self
+                .spill
+                .as_mut()
+                .expect("spill directory was just created");
+            spill.write(std::mem::take(&mut self.in_memory))?;
+            self.bytes = 0;
+        }
+        Ok(())
+    }
+
+    /// Hand every batch to `resolve`, spilled batches first to keep file order
+    fn drain(self, mut resolve: impl FnMut(Vec<UnresolvedRef>)) -> Result<()> {
+        if let Some(spill) = &self.spill {
+            for batch in 0..spill.batches {
+                resolve(spill.read(batch)?);
+            }
+        }
+        resolve(self.in_memory);
+        Ok(())
+    }
+}
+
+/// Temporary directory of spilled reference batches, removed when dropped
+struct SpillDir {
+    path: PathBuf,
+    batches: usize,
+    refs: usize,
+}
+
+impl SpillDir {
+    fn create() -> Result<Self> {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        let path = std::env::temp_dir().join(format!(
+            "searchdeadcode-spill-{}-{}",
+            std::process::id(),
+            nanos
+        ));
+        std::fs::create_dir_all(&path).into_diagnostic()?;
+        info!(
+            "Memory budget exceeded, spilling references to {}",
+            path.display()
+        );
+        Ok(Self {
+            path,
+            batches: 0,
+            refs: 0,
+        })
+    }
+
+    fn batch_path(&self, batch: usize) -> PathBuf {
+        self.path.join(format!("batch-{}.json", batch))
+    }
+
+    fn write(&mut self, refs: Vec<UnresolvedRef>) -> Result<()> {
+        let file = File::create(self.batch_path(self.batches)).into_diagnostic()?;
+        serde_json::to_writer(BufWriter::new(file), &refs).into_diagnostic()?;
+        debug!("Spilled {} references", refs.len());
+        self.batches += 1;
+        self.refs += refs.len();
+        Ok(())
+    }
+
+    fn read(&self, batch: usize) -> Result<Vec<UnresolvedRef>> {
+        let file = File::open(self.batch_path(batch)).into_diagnostic()?;
+        serde_json::from_reader(BufReader::new(file)).into_diagnostic()
+    }
+}
+
+impl Drop for SpillDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
 }
 
 /// Parallel graph builder for faster processing
-pub struct ParallelGraphBuilder;
+#[derive(Default)]
+pub struct ParallelGraphBuilder {
+    /// Worker threads (rayon's default when `None`)
+    jobs: Option<usize>,
+    /// Memory budget for parse results held before resolution, in bytes
+    max_memory: Option<usize>,
+    /// Fail on the first file that cannot be parsed instead of skipping it
+    strict: bool,
+}
 
 impl ParallelGraphBuilder {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Parse with at most this many threads
+    pub fn with_jobs(mut self, jobs: Option<usize>) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// Spill pending parse results to disk above this many megabytes
+    pub fn with_max_memory_mb(mut self, megabytes: Option<usize>) -> Self {
+        self.max_memory = megabytes.map(|mb| mb * 1024 * 1024);
+        self
+    }
+
+    /// Fail on the first file that cannot be parsed instead of skipping it
+    pub fn with_strict_parse(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     /// Build graph from source files using parallel processing
     pub fn build_from_files(&self, files: &[SourceFile]) -> Result<Graph> {
+        self.build_collecting_skipped(files).map(|(graph, _)| graph)
+    }
+
+    /// Build the graph, also returning the files that could not be parsed
+    pub fn build_collecting_skipped(
+        &self,
+        files: &[SourceFile],
+    ) -> Result<(Graph, Vec<SkippedFile>)> {
+        self.build_observing(files, None)
+    }
+
+    /// Build the graph, passing each file's parse result to `on_parsed` on
+    /// the calling thread, in file order, as soon as its chunk is parsed
+    pub fn build_observing(
+        &self,
+        files: &[SourceFile],
+        mut on_parsed: Option<&mut dyn FnMut(&ParseResult)>,
+    ) -> Result<(Graph, Vec<SkippedFile>)> {
         info!("Parsing {} files in parallel...", files.len());
+        let keep = on_parsed.is_some();
 
-        // Parse files in parallel
-        let results: Vec<Result<ParsedFile>> =
-            files.par_iter().map(|file| self.parse_file(file)).collect();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.jobs.unwrap_or(0))
+            .build()
+            .into_diagnostic()?;
+        let chunk_size = pool.current_num_threads() * FILES_PER_THREAD;
 
-        // Collect results
-        let mut all_declarations = Vec::new();
-        let mut all_unresolved = Vec::new();
+        // Parse files in parallel, one chunk at a time. Declarations go
+        // straight into the graph; references wait until all are there
+        let mut graph = Graph::new();
+        let mut declarations = 0;
+        let mut pending = PendingRefs::new(self.max_memory);
+        let mut skipped = Vec::new();
+
+        for chunk in files.chunks(chunk_size.max(1)) {
+            let results: Vec<Result<ParsedFile>> = pool.install(|| {
+                chunk
+                    .par_iter()
+                    .map(|file| self.parse_file(file, keep))
+                    .collect()
+            });
 
-        for result in results {
-            match result {
-                Ok(parsed) => {
-                    all_declarations.extend(parsed.declarations);
-                    all_unresolved.extend(parsed.unresolved_refs);
-                }
-                Err(e) => {
-                    debug!("Parse error (continuing): {}", e);
+            for (file, result) in chunk.iter().zip(results) {
+                match result {
+                    Ok(parsed) => {
+                        if let (Some(observe), Some(parse_result)) =
+                            (on_parsed.as_mut(), &parsed.parse_result)
+                        {
+                            observe(parse_result);
+                        }
+                        declarations += parsed.declarations.len();
+                        for decl in parsed.declarations {
+                            graph.add_declaration(decl);
+                        }
+                        pending.extend(parsed.unresolved_refs)?;
+                    }
+                    Err(e) if self.strict => {
+                        return Err(e.wrap_err(format!("Failed to parse {}", file.path.display())));
+                    }
+                    Err(e) => {
+                        debug!("Parse error (continuing): {}", e);
+                        skipped.push(SkippedFile::new(file.path.clone(), &e));
+                    }
                 }
             }
         }
 
         info!(
             "Parsed {} declarations, {} unresolved references",
-            all_declarations.len(),
-            all_unresolved.len()
+            declarations,
+            pending.len()
         );
 
-        // Build graph
-        let mut graph = Graph::new();
-        for decl in all_declarations {
-            graph.add_declaration(decl);
-        }
-
         // Resolve references
         info!("Resolving references...");
-        self.resolve_references(&mut graph, all_unresolved);
+        pending.drain(|batch| self.resolve_references(&mut graph, batch))?;
 
-        Ok(graph)
+        Ok((graph, skipped))
     }
 
-    /// Parse a single file
-    fn parse_file(&self, file: &SourceFile) -> Result<ParsedFile> {
+    /// Parse a single file, keeping the parser's result if `keep`
+    fn parse_file(&self, file: &SourceFile, keep: bool) -> Result<ParsedFile> {
         let contents = file.read_contents()?;
 
-        match file.file_type {
-            FileType::Kotlin => self.parse_kotlin_file(&file.path, &contents),
-            FileType::Java => self.parse_java_file(&file.path, &contents),
-            _ => Ok(ParsedFile {
-                declarations: Vec::new(),
-                unresolved_refs: Vec::new(),
-            }),
-        }
-    }
-
-    fn parse_kotlin_file(&self, path: &std::path::Path, contents: &str) -> Result<ParsedFile> {
-        let parser = KotlinParser::new();
-        let result = parser.parse(path, contents)?;
-
-        let declarations = result.declarations.clone();
-        let unresolved = self.extract_unresolved(&declarations, result.references);
-
-        Ok(ParsedFile {
-            declarations: result.declarations,
-            unresolved_refs: unresolved,
-        })
-    }
-
-    fn parse_java_file(&self, path: &std::path::Path, contents: &str) -> Result<ParsedFile> {
-        let parser = JavaParser::new();
-        let result = parser.parse(path, contents)?;
+        let result = match file.file_type {
+            FileType::Kotlin => KotlinParser::new().parse(&file.path, &contents)?,
+            FileType::Java => JavaParser::new().parse(&file.path, &contents)?,
+            _ => {
+                return Ok(ParsedFile {
+                    declarations: Vec::new(),
+                    unresolved_refs: Vec::new(),
+                    parse_result: None,
+                })
+            }
+        };
+        let parse_result = keep.then(|| result.clone());
 
         let declarations = result.declarations.clone();
         let unresolved = self.extract_unresolved(&declarations, result.references);
@@ -109,6 +300,7 @@ impl ParallelGraphBuilder {
         Ok(ParsedFile {
             declarations: result.declarations,
             unresolved_refs: unresolved,
+            parse_result,
         })
     }
 
@@ -145,6 +337,10 @@ impl ParallelGraphBuilder {
                     qualified_name: unresolved.qualified_name,
                     kind: unresolved.kind,
                     imports: unresolved.imports,
+                    receiver_type: unresolved.receiver_type,
+                    argument_count: unresolved.argument_count,
+                    argument_names: unresolved.argument_names,
+                    location: unresolved.location,
                 });
             }
         }
@@ -154,29 +350,79 @@ impl ParallelGraphBuilder {
 
     fn resolve_references(&self, graph: &mut Graph, unresolved: Vec<UnresolvedRef>) {
         for unresolved in unresolved {
-            let resolved_ids = self.resolve_reference(graph, &unresolved);
+            let (resolved_ids, excluded) = self.resolve_reference(graph, &unresolved);
+            graph.mark_arity_excluded(excluded);
+            let ambiguous = resolved_ids
+                .iter()
+                .filter_map(|id| graph.get_declaration(id))
+                .filter(|d| d.kind.is_callable())
+                .count()
+                > 1;
             for to_id in resolved_ids {
-                let reference = Reference::new(
-                    unresolved.kind,
+                // Doc links keep their own location so the comment can be
+                // reported, and calls so their arguments can be rewritten
+                let location = if unresolved.kind.counts_as_usage()
+                    && unresolved.kind != ReferenceKind::Call
+                {
                     Location::new(
                         unresolved.from.file.clone(),
                         0,
                         0,
                         unresolved.from.start,
                         unresolved.from.end,
-                    ),
-                    unresolved.name.clone(),
-                );
-                graph.add_reference(&unresolved.from, &to_id, reference);
+                    )
+                } else {
+                    unresolved.location.clone()
+                };
+                let reference = Reference::new(unresolved.kind, location, unresolved.name.clone())
+                    .with_ambiguous(ambiguous);
+                graph.add_reference(&unresolved.from, &to_id, reference.clone());
+
+                // A lambda or method reference implements the interface method
+                if unresolved.kind == ReferenceKind::SamConversion {
+                    let sam = graph.single_abstract_method(&to_id).map(|m| m.id.clone());
+                    if let Some(sam) = sam {
+                        let call = Reference {
+                            kind: ReferenceKind::Call,
+                            ..reference
+                        };
+                        graph.add_reference(&unresolved.from, &sam, call);
+                    }
+                }
             }
         }
     }
 
-    fn resolve_reference(&self, graph: &Graph, unresolved: &UnresolvedRef) -> Vec<DeclarationId> {
+    /// Resolve a reference, returning its targets and the overloads passed
+    /// over because they cannot take the call's arguments
+    fn resolve_reference(
+        &self,
+        graph: &Graph,
+        unresolved: &UnresolvedRef,
+    ) -> (Vec<DeclarationId>, Vec<DeclarationId>) {
+        // Extensions of the same name are told apart by their receiver, and
+        // overloads by the arguments they take
+        let narrow = |candidates: Vec<&Declaration>| {
+            let candidates = graph.prefer_receiver(candidates, unresolved.receiver_type.as_deref());
+            let (kept, excluded) = graph.prefer_arity(
+                candidates,
+                unresolved.argument_count,
+                &unresolved.argument_names,
+            );
+            let ids = |decls: Vec<&Declaration>| -> Vec<DeclarationId> {
+                decls.iter().map(|d| d.id.clone()).collect()
+            };
+            (ids(kept), ids(excluded))
+        };
+        let by_fqn = |fqn: &str| {
+            let found = graph.find_all_by_fqn(fqn);
+            (!found.is_empty()).then(|| narrow(found))
+        };
+
         // Try fully qualified name first
         if let Some(fqn) = &unresolved.qualified_name {
-            if let Some(decl) = graph.find_by_fqn(fqn) {
-                return vec![decl.id.clone()];
+            if let Some(resolved) = by_fqn(fqn) {
+                return resolved;
             }
         }
 
@@ -185,36 +431,83 @@ impl ParallelGraphBuilder {
             if import.ends_with(".*") {
                 let package = &import[..import.len() - 2];
                 let fqn = format!("{}.{}", package, unresolved.name);
-                if let Some(decl) = graph.find_by_fqn(&fqn) {
-                    return vec![decl.id.clone()];
+                if let Some(resolved) = by_fqn(&fqn) {
+                    return resolved;
                 }
             } else if import.ends_with(&format!(".{}", unresolved.name)) {
-                if let Some(decl) = graph.find_by_fqn(import) {
-                    return vec![decl.id.clone()];
+                if let Some(resolved) = by_fqn(import) {
+                    return resolved;
                 }
             } else if let Some(alias_start) = import.find(" as ") {
                 let alias = &import[alias_start + 4..];
                 if alias == unresolved.name {
                     let original = &import[..alias_start];
-                    if let Some(decl) = graph.find_by_fqn(original) {
-                        return vec![decl.id.clone()];
+                    if let Some(resolved) = by_fqn(original) {
+                        return resolved;
                     }
                 }
             }
         }
 
         // Try simple name match
-        let candidates = graph.find_by_name(&unresolved.name);
-        if !candidates.is_empty() {
-            return candidates.iter().map(|c| c.id.clone()).collect();
+        let mut candidates = graph.find_by_name(&unresolved.name);
+
+        // Java code calls Kotlin declarations by their JVM names as well
+        let from_java = graph
+            .get_declaration(&unresolved.from)
+            .is_some_and(|d| d.language == Language::Java);
+        if from_java {
+            candidates.extend(graph.find_by_jvm_name(&unresolved.name));
         }
 
-        Vec::new()
+        narrow(candidates)
     }
 }
 
-impl Default for ParallelGraphBuilder {
-    fn default() -> Self {
-        Self::new()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unresolved(name: &str) -> UnresolvedRef {
+        let file = PathBuf::from("Main.kt");
+        UnresolvedRef {
+            from: DeclarationId::new(file.clone(), 0, 100),
+            name: name.to_string(),
+            qualified_name: None,
+            kind: ReferenceKind::Call,
+            imports: vec!["com.example.*".to_string()],
+            receiver_type: None,
+            argument_count: Some(0),
+            argument_names: Vec::new(),
+            location: Location::new(file, 3, 5, 40, 50),
+        }
+    }
+
+    #[test]
+    fn test_pending_refs_spill_over_budget() {
+        let mut pending = PendingRefs::new(Some(1));
+        pending
+            .extend(vec![unresolved("a"), unresolved("b")])
+            .unwrap();
+        pending.extend(vec![unresolved("c")]).unwrap();
+        assert!(pending.in_memory.is_empty());
+        assert_eq!(pending.len(), 3);
+        let spill_path = pending.spill.as_ref().unwrap().path.clone();
+        assert!(spill_path.exists());
+
+        let mut names = Vec::new();
+        pending
+            .drain(|batch| names.extend(batch.into_iter().map(|r| r.name)))
+            .unwrap();
+        assert_eq!(names, vec!["a", "b", "c"]);
+        assert!(!spill_path.exists());
+    }
+
+    #[test]
+    fn test_pending_refs_within_budget_stay_in_memory() {
+        let mut pending = PendingRefs::new(None);
+        pending.extend(vec![unresolved("a")]).unwrap();
+        assert!(pending.spill.is_none());
+        assert_eq!(pending.in_memory.len(), 1);
     }
 }
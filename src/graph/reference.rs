@@ -60,6 +60,18 @@ pub enum ReferenceKind {
 
     /// Generic type argument (e.g., List<MyClass>)
     GenericArgument,
+
+    /// Lambda or method reference converted to a functional (SAM) interface
+    SamConversion,
+
+    /// Access to every constant of an enum (`values()`, `entries`, `enumValues<T>()`)
+    EnumIteration,
+
+    /// Lookup of an enum constant by name (`valueOf`, `enumValueOf<T>()`)
+    EnumLookup,
+
+    /// Link from a KDoc or Javadoc comment (`[Foo]`, `{@link Foo}`)
+    Documentation,
 }
 
 impl ReferenceKind {
@@ -79,10 +91,39 @@ impl ReferenceKind {
         matches!(self, ReferenceKind::Write)
     }
 
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ReferenceKind::Call => "call",
+            ReferenceKind::Read => "read",
+            ReferenceKind::Write => "write",
+            ReferenceKind::Type => "type reference",
+            ReferenceKind::Inheritance => "inheritance",
+            ReferenceKind::Import => "import",
+            ReferenceKind::Instantiation => "instantiation",
+            ReferenceKind::Annotation => "annotation",
+            ReferenceKind::Cast => "cast",
+            ReferenceKind::TypeArgument => "type argument",
+            ReferenceKind::ReturnType => "return type",
+            ReferenceKind::ParameterType => "parameter type",
+            ReferenceKind::Override => "override",
+            ReferenceKind::Reflection => "reflection",
+            ReferenceKind::ExtensionReceiver => "extension receiver",
+            ReferenceKind::SealedSubtype => "sealed subtype",
+            ReferenceKind::Delegation => "delegation",
+            ReferenceKind::GenericArgument => "generic argument",
+            ReferenceKind::SamConversion => "SAM conversion",
+            ReferenceKind::EnumIteration => "enum iteration",
+            ReferenceKind::EnumLookup => "enum lookup",
+            ReferenceKind::Documentation => "doc link",
+        }
+    }
+
     /// Check if this reference counts as "usage" for dead code detection
+    ///
+    /// Documentation links are soft references: the graph keeps them apart
+    /// from the edges, so they never keep their target alive.
     pub fn counts_as_usage(&self) -> bool {
-        // All references count as usage for now
-        true
+        !matches!(self, ReferenceKind::Documentation)
     }
 }
 
@@ -100,6 +141,10 @@ pub struct Reference {
 
     /// Whether this is a qualified reference (e.g., com.example.Foo)
     pub is_qualified: bool,
+
+    /// Whether the reference could not be narrowed to a single overload, so
+    /// it points at every overload that accepts its arguments
+    pub ambiguous: bool,
 }
 
 impl Reference {
@@ -109,6 +154,7 @@ impl Reference {
             location,
             name,
             is_qualified: false,
+            ambiguous: false,
         }
     }
 
@@ -116,6 +162,11 @@ impl Reference {
         self.is_qualified = qualified;
         self
     }
+
+    pub fn with_ambiguous(mut self, ambiguous: bool) -> Self {
+        self.ambiguous = ambiguous;
+        self
+    }
 }
 
 /// Builder for tracking references during parsing
@@ -142,6 +193,17 @@ pub struct UnresolvedReference {
 
     /// Imports available in scope (for resolution)
     pub imports: Vec<String>,
+
+    /// Static type of the receiver of a member access or call (`user` in
+    /// `user.greet()`), when it can be told from the source
+    pub receiver_type: Option<String>,
+
+    /// Number of arguments passed, when this is a call whose arguments can
+    /// be counted (spread arguments such as `*items` cannot)
+    pub argument_count: Option<usize>,
+
+    /// Names of the arguments passed by name (`retries` in `load(retries = 3)`)
+    pub argument_names: Vec<String>,
 }
 
 impl ReferenceCollector {
@@ -175,6 +237,9 @@ impl ReferenceCollector {
             kind,
             location,
             imports,
+            receiver_type: None,
+            argument_count: None,
+            argument_names: Vec::new(),
         });
     }
 
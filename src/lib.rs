@@ -12,13 +12,21 @@
 //! 4. **Entry Point Detection** - Identify Android entry points
 //! 5. **Reachability Analysis** - Find unreachable code
 //! 6. **Reporting** - Output results in various formats
+//!
+//! These steps are orchestrated by [`pipeline::Pipeline`]. [`AnalysisSession`] runs
+//! them in one call and is what the CLI uses as well.
 
 pub mod analysis;
+pub mod api;
+pub mod baseline;
+pub mod bytecode;
 pub mod config;
 pub mod coverage;
 pub mod discovery;
 pub mod graph;
+pub mod ownership;
 pub mod parser;
+pub mod pipeline;
 pub mod proguard;
 pub mod refactor;
 pub mod report;
@@ -30,6 +38,9 @@ pub use config::Config;
 pub use coverage::{parse_coverage_file, parse_coverage_files, CoverageData, CoverageParser};
 pub use discovery::FileFinder;
 pub use graph::{Declaration, DeclarationKind, Graph, Reference};
+pub use pipeline::{
+    AnalysisReport, AnalysisSession, Pipeline, PipelineContext, PipelineOptions, Stage,
+};
 pub use proguard::{ProguardUsage, UsageEntryKind};
 pub use refactor::SafeDeleter;
 pub use report::{ReportFormat, Reporter};
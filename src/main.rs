@@ -5,43 +5,52 @@ use std::path::PathBuf;
 use tracing::info;
 
 mod analysis;
+mod api;
 mod baseline;
+mod bytecode;
 mod cache;
 mod config;
 mod coverage;
+mod daemon;
 mod discovery;
 mod graph;
+mod ownership;
 mod parser;
+mod pipeline;
 mod proguard;
 mod refactor;
 mod report;
 mod watch;
 
-use proguard::{ProguardUsage, ReportGenerator};
+use proguard::ReportGenerator;
 
-use analysis::detectors::{
-    Detector, RedundantOverrideDetector, UnusedIntentExtraDetector, UnusedParamDetector,
-    UnusedSealedVariantDetector, WriteOnlyDetector,
-};
-use analysis::{
-    Confidence, CycleDetector, DeepAnalyzer, EnhancedAnalyzer, EntryPointDetector, HybridAnalyzer,
-    ReachabilityAnalyzer, ResourceDetector,
-};
+use analysis::detectors::DetectorRegistry;
+use analysis::variants::VariantFindings;
+use analysis::{Confidence, DeadCodeIssue};
 use config::Config;
-use coverage::parse_coverage_files;
-use discovery::FileFinder;
-use graph::{GraphBuilder, ParallelGraphBuilder};
-use report::Reporter;
+use pipeline::{
+    AnalysisManifest, AnalysisSession, CacheUsage, PipelineContext, PipelineOptions, TimingsReport,
+};
+use report::{Reporter, SqliteExporter, TerminalReporter};
 
 /// SearchDeadCode - Fast dead code detection for Android (Kotlin/Java)
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[command(name = "searchdeadcode")]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to the project directory to analyze
     #[arg(default_value = ".")]
     path: PathBuf,
 
+    /// Project roots to analyze together into one graph, instead of PATH
+    /// (can be specified multiple times). The first is the main project.
+    #[arg(long = "path", value_name = "DIR")]
+    paths: Vec<PathBuf>,
+
     /// Path to configuration file
     #[arg(short, long)]
     config: Option<PathBuf>,
@@ -58,14 +67,44 @@ struct Cli {
     #[arg(short, long)]
     retain: Vec<String>,
 
+    /// Additional entry point: a fully qualified name, a pattern such as
+    /// "com.example.api.*", or an annotation such as "@PublicApi" (can be
+    /// specified multiple times)
+    #[arg(long = "entry-point", value_name = "FQN|PATTERN|@ANNOTATION")]
+    entry_points: Vec<String>,
+
     /// Output format
     #[arg(short, long, value_enum, default_value = "terminal")]
     format: OutputFormat,
 
-    /// Output file (for json/sarif formats)
+    /// Output file (for json/sarif/code-quality formats)
     #[arg(short, long)]
     output: Option<PathBuf>,
 
+    /// Append a markdown summary to this file (github-actions format defaults
+    /// to $GITHUB_STEP_SUMMARY)
+    #[arg(long, value_name = "FILE")]
+    summary: Option<PathBuf>,
+
+    /// Validate the JSON report against the bundled schema before writing it
+    #[arg(long)]
+    validate_output: bool,
+
+    /// Also write declarations, references, entry points and findings to a
+    /// SQLite database for ad-hoc queries
+    #[arg(long, value_name = "FILE")]
+    output_db: Option<PathBuf>,
+
+    /// Write an analysis manifest (tool version, configuration hash, inputs,
+    /// entry point counts, stage timings, cache hit rate) to this JSON file
+    #[arg(long, value_name = "FILE")]
+    manifest: Option<PathBuf>,
+
+    /// Print the time spent in each phase (discovery, parsing, resolution,
+    /// entry points, reachability, each detector); JSON reports include it
+    #[arg(long)]
+    timings: bool,
+
     /// Enable safe delete mode
     #[arg(long)]
     delete: bool,
@@ -82,15 +121,97 @@ struct Cli {
     #[arg(long)]
     undo_script: Option<PathBuf>,
 
-    /// Detection types to run (comma-separated)
-    #[arg(long)]
-    detect: Option<String>,
+    /// Record deleted and edited files in a JSON journal for `undo --journal`
+    #[arg(long, value_name = "FILE")]
+    journal: Option<PathBuf>,
+
+    /// Write the deletions to a git-applyable unified diff instead of editing files
+    #[arg(long, value_name = "FILE")]
+    patch: Option<PathBuf>,
+
+    /// Narrow public declarations only used in their file, class or module to
+    /// private or internal (runs the redundant-public detector)
+    #[arg(long, conflicts_with = "delete")]
+    fix_visibility: bool,
+
+    /// Turn private var properties that are never reassigned into val
+    /// (runs the property-accessors detector)
+    #[arg(long, conflicts_with = "delete")]
+    fix_mutability: bool,
+
+    /// Mark dead declarations @Deprecated instead of deleting them; the
+    /// journal records when, for a later --delete --deprecated-for
+    #[arg(
+        long,
+        conflicts_with_all = ["delete", "patch", "fix_visibility", "fix_mutability"],
+        requires = "journal"
+    )]
+    deprecate: bool,
+
+    /// Level of the Kotlin annotations added by --deprecate (Java only warns)
+    #[arg(long, value_enum, default_value = "warning")]
+    deprecation_level: DeprecationLevelArg,
+
+    /// Only delete declarations --deprecate marked at least this long ago,
+    /// as recorded in --journal (e.g. 30d, 4w)
+    #[arg(
+        long,
+        value_name = "AGE",
+        value_parser = coverage::parse_max_age,
+        requires_all = ["delete", "journal"]
+    )]
+    deprecated_for: Option<std::time::Duration>,
+
+    /// Detectors to run (comma-separated, see `list-detectors`)
+    #[arg(
+        long,
+        value_name = "ID",
+        value_delimiter = ',',
+        value_parser = detector_ids()
+    )]
+    detect: Vec<String>,
+
+    /// Detectors to skip, even when enabled by a flag or the config (comma-separated)
+    #[arg(
+        long,
+        value_name = "ID",
+        value_delimiter = ',',
+        value_parser = detector_ids()
+    )]
+    skip_detect: Vec<String>,
+
+    /// Custom rule file (default: .searchdeadcode-rules.toml in the project root)
+    #[arg(long, value_name = "FILE")]
+    rules: Option<PathBuf>,
+
+    /// External detector plugin: a command reading the graph as JSON on stdin
+    /// and writing findings to stdout (can be specified multiple times)
+    #[arg(long = "plugin", value_name = "COMMAND")]
+    plugins: Vec<String>,
 
-    /// Coverage files (JaCoCo XML, Kover XML, or LCOV format)
+    /// Coverage files (JaCoCo XML or .ec, Kover XML, LCOV, method or stack traces)
     /// Can be specified multiple times for merged coverage
     #[arg(long, value_name = "FILE")]
     coverage: Vec<PathBuf>,
 
+    /// Load the JaCoCo and Kover XML reports under build/reports of every
+    /// Gradle module, and list modules without any coverage
+    #[arg(long)]
+    coverage_auto: bool,
+
+    /// Accumulate coverage across runs in a store
+    /// Runtime evidence from earlier runs keeps counting until it is too old
+    #[arg(long)]
+    coverage_store: bool,
+
+    /// Custom coverage store path (default: .searchdeadcode-coverage.json)
+    #[arg(long, value_name = "FILE")]
+    coverage_store_path: Option<PathBuf>,
+
+    /// Ignore runtime evidence older than this (e.g. 90d, 12w, 36h)
+    #[arg(long, value_name = "AGE", value_parser = coverage::parse_max_age)]
+    coverage_max_age: Option<std::time::Duration>,
+
     /// Minimum confidence level to report (low, medium, high, confirmed)
     #[arg(long, default_value = "low")]
     min_confidence: String,
@@ -99,6 +220,23 @@ struct Cli {
     #[arg(long)]
     runtime_only: bool,
 
+    /// Only show findings whose declaration spans at least this many lines
+    #[arg(long, value_name = "LINES")]
+    min_size: Option<usize>,
+
+    /// Hide findings with this false positive risk tag (can be specified multiple times)
+    #[arg(
+        long,
+        value_name = "TAG",
+        value_parser = clap::builder::PossibleValuesParser::new(analysis::risk::RISK_TAGS)
+    )]
+    exclude_risk: Vec<String>,
+
+    /// Tag findings linked from KDoc/Javadoc as doc-linked, lowering their
+    /// confidence (doc links never keep code alive)
+    #[arg(long)]
+    doc_links: bool,
+
     /// Include runtime-dead code (reachable but never executed)
     #[arg(long)]
     include_runtime_dead: bool,
@@ -107,11 +245,56 @@ struct Cli {
     #[arg(long)]
     detect_cycles: bool,
 
+    /// Report files whose declarations are all unused as one finding per file
+    #[arg(long)]
+    dead_files: bool,
+
     /// ProGuard/R8 usage.txt file for enhanced detection
     /// This file lists code that R8 determined is unused
     #[arg(long, value_name = "FILE")]
     proguard_usage: Option<PathBuf>,
 
+    /// ProGuard/R8 keep rules file (can be specified multiple times)
+    /// proguard-rules.pro and consumer-rules.pro are discovered automatically
+    #[arg(long, value_name = "FILE")]
+    proguard_rules: Vec<PathBuf>,
+
+    /// R8/ProGuard seeds.txt file (-printseeds)
+    /// Seeds never referenced in code are reported as kept only by their keep rule
+    #[arg(long, value_name = "FILE")]
+    proguard_seeds: Option<PathBuf>,
+
+    /// Report declarations that are only alive because of a keep rule
+    #[arg(long)]
+    report_kept: bool,
+
+    /// Prebuilt .jar/.aar that uses project code (can be specified multiple times)
+    /// Classes and members it references are treated as entry points
+    #[arg(long, value_name = "FILE")]
+    external_usage: Vec<PathBuf>,
+
+    /// The project's compiled classes: a class output directory, .jar, .aar
+    /// or .class (can be specified multiple times). Findings the bytecode
+    /// does not reach either get high confidence; findings it reaches are
+    /// flagged as possible parser bugs
+    #[arg(long, value_name = "PATH")]
+    classes: Vec<PathBuf>,
+
+    /// Parse annotation processor output (KAPT/KSP) under build/generated
+    /// Code it references stays alive; generated code is never reported
+    #[arg(long)]
+    generated_sources: bool,
+
+    /// Keep composables alive only through calls, with @Preview functions as
+    /// IDE-only entry points, instead of treating every @Composable as used
+    #[arg(long)]
+    compose_previews: bool,
+
+    /// Build variant to analyze (e.g. freeDebug): main + flavor + build type source sets
+    /// Repeat to compare variants and tell code dead everywhere from code dead in some
+    #[arg(long = "variant", value_name = "VARIANT")]
+    variants: Vec<String>,
+
     /// Generate a filtered dead code report from ProGuard usage.txt
     /// Filters out generated code (Dagger, Hilt, _Factory, _Impl, etc.)
     #[arg(long, value_name = "FILE")]
@@ -126,6 +309,20 @@ struct Cli {
     #[arg(long)]
     parallel: bool,
 
+    /// Number of worker threads for parallel work (default: all cores)
+    #[arg(short, long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
+
+    /// Memory budget for parse results in parallel mode; above it they are
+    /// spilled to a temporary directory and read back during resolution
+    #[arg(long, value_name = "MB")]
+    max_memory_mb: Option<usize>,
+
+    /// Fail on the first file that cannot be read or parsed instead of
+    /// skipping it and reporting it with the results
+    #[arg(long)]
+    strict_parse: bool,
+
     /// Enable enhanced detection mode with ProGuard cross-validation
     #[arg(long)]
     enhanced: bool,
@@ -136,13 +333,33 @@ struct Cli {
     #[arg(long)]
     deep: bool,
 
+    /// In deep mode, keep members overriding a used interface or superclass
+    /// member alive instead of reporting them as not deletable independently
+    #[arg(long, requires = "deep")]
+    deep_respect_overrides: bool,
+
+    /// Where the reference graph comes from: tree-sitter alone, or tree-sitter
+    /// plus the references the compiler resolved (from --symbols dumps)
+    #[arg(long, value_enum, default_value = "treesitter")]
+    frontend: FrontendArg,
+
+    /// Entry points reachability starts from: the app's, the test suite's
+    /// (@Test methods and fixtures), or the app's compared with the tests'
+    #[arg(long, value_enum, default_value = "app")]
+    entry_set: EntrySetArg,
+
+    /// Compiler symbol dump written by the companion KSP processor (can be
+    /// specified multiple times; used with --frontend bytecode)
+    #[arg(long = "symbols", value_name = "FILE")]
+    symbols: Vec<PathBuf>,
+
     /// Enable unused parameter detection
     /// Finds function parameters that are declared but never used
     #[arg(long)]
     unused_params: bool,
 
     /// Enable unused resource detection
-    /// Finds Android resources (strings, colors, etc.) that are never referenced
+    /// Finds Android resources (strings, colors, layouts, menus, etc.) that are never referenced
     #[arg(long)]
     unused_resources: bool,
 
@@ -156,6 +373,16 @@ struct Cli {
     #[arg(long)]
     sealed_variants: bool,
 
+    /// Enable unused enum case detection
+    /// Finds enum constants never referenced, iterated (values/entries) or looked up (valueOf)
+    #[arg(long)]
+    unused_enum_cases: bool,
+
+    /// Enable dead branch detection
+    /// Finds if/else branches whose condition is constant (BuildConfig.DEBUG, const val flags)
+    #[arg(long)]
+    dead_branches: bool,
+
     /// Enable redundant override detection
     /// Finds method overrides that only call super (Phase 10)
     #[arg(long)]
@@ -166,16 +393,95 @@ struct Cli {
     #[arg(long)]
     unused_extras: bool,
 
+    /// Enable unused Bundle argument detection
+    /// Finds Fragment argument and SavedStateHandle keys that are set but never read
+    #[arg(long)]
+    unused_bundle_args: bool,
+
+    /// Enable unused broadcast action detection
+    /// Finds actions broadcast but never received, and receivers whose actions are never sent
+    #[arg(long)]
+    unused_broadcasts: bool,
+
     /// Enable write-only SharedPreferences detection
     /// Finds SharedPreferences keys that are written but never read (Phase 9)
     #[arg(long)]
     write_only_prefs: bool,
 
+    /// Enable unused preference key detection
+    /// Finds SharedPreferences and DataStore keys written but never read, or defined but never used
+    #[arg(long)]
+    unused_pref_keys: bool,
+
     /// Enable write-only Room DAO detection
     /// Finds Room DAOs that have @Insert but no @Query methods (Phase 9)
     #[arg(long)]
     write_only_dao: bool,
 
+    /// Enable unused module dependency detection
+    /// Finds project(...) dependencies in build.gradle that the module never imports from
+    #[arg(long)]
+    unused_module_deps: bool,
+
+    /// Write the Gradle module dependency graph to a JSON file
+    #[arg(long, value_name = "FILE")]
+    module_graph: Option<PathBuf>,
+
+    /// Enable unused navigation argument detection
+    /// Finds nav graph arguments never read, actions never navigated and destinations never navigated to
+    #[arg(long)]
+    unused_nav_args: bool,
+
+    /// Enable deep link detection
+    /// Finds deep links never opened, opened without a handler, and activities only reachable through them
+    #[arg(long)]
+    deep_links: bool,
+
+    /// Enable dead doc link detection
+    /// Finds KDoc and Javadoc links to dead declarations
+    #[arg(long)]
+    dead_doc_links: bool,
+
+    /// Enable Compose state detection
+    /// Finds remembered state that is never read and composable defaults no call overrides
+    #[arg(long)]
+    compose_state: bool,
+
+    /// Enable unused import detection
+    /// Finds imports whose name is never used in the file
+    #[arg(long)]
+    unused_imports: bool,
+
+    /// Enable unused typealias detection
+    /// Finds Kotlin typealiases that are declared but never used
+    #[arg(long)]
+    unused_typealiases: bool,
+
+    /// Enable property accessor detection
+    /// Finds var properties never reassigned and custom getters or setters never used
+    #[arg(long)]
+    property_accessors: bool,
+
+    /// Report production declarations that are only referenced from tests
+    /// (src/test, src/androidTest)
+    #[arg(long)]
+    only_used_in_tests: bool,
+
+    /// Report production composables that are only called from @Preview
+    /// functions or tests (implies --compose-previews)
+    #[arg(long)]
+    preview_only: bool,
+
+    /// Report function-typed parameters (`onDone: () -> Unit`) that the
+    /// function never invokes or passes on
+    #[arg(long)]
+    uninvoked_params: bool,
+
+    /// Report objects, companion objects and classes only used as a
+    /// namespace (imported or qualifying names) whose members are all unused
+    #[arg(long)]
+    dead_objects: bool,
+
     /// Enable incremental analysis with caching
     /// Skips re-parsing unchanged files for faster subsequent runs
     #[arg(long)]
@@ -198,6 +504,59 @@ struct Cli {
     #[arg(long, value_name = "FILE")]
     generate_baseline: Option<PathBuf>,
 
+    /// Only report findings in files changed since this git ref (e.g. origin/main)
+    #[arg(long, value_name = "REF")]
+    diff_base: Option<String>,
+
+    /// Only report findings in this package subtree (can be specified multiple times)
+    #[arg(long = "only-package", value_name = "PACKAGE")]
+    only_packages: Vec<String>,
+
+    /// With --only-package, skip parsing sources that neither belong to nor
+    /// import from the package; declarations outside it are assumed used
+    #[arg(long, requires = "only_packages")]
+    prune_by_imports: bool,
+
+    /// Attribute findings to owners using the project's CODEOWNERS file
+    #[arg(long)]
+    owners: bool,
+
+    /// CODEOWNERS file to use (implies --owners)
+    #[arg(long, value_name = "FILE")]
+    codeowners: Option<PathBuf>,
+
+    /// Use git blame of the declaration line when no CODEOWNERS rule matches
+    #[arg(long)]
+    blame: bool,
+
+    /// Group findings into deletion units: a root declaration plus the
+    /// findings that only it keeps alive, shown and deleted together
+    #[arg(long)]
+    cluster: bool,
+
+    /// How to group findings in the terminal report
+    #[arg(long, value_enum, default_value = "file")]
+    group_by: GroupByArg,
+
+    /// How to order findings within a group in the terminal report
+    #[arg(long, value_enum, default_value = "line")]
+    sort_by: SortByArg,
+
+    /// Print counts per detector and package and the estimated deletable
+    /// lines instead of listing each finding
+    #[arg(long)]
+    summary_only: bool,
+
+    /// Print the findings of per-file detectors (unused imports, unused
+    /// parameters, write-only variables) while parsing, before the rest of
+    /// the report (terminal output only)
+    #[arg(long)]
+    stream: bool,
+
+    /// List the evidence behind each finding's confidence in the terminal report
+    #[arg(long)]
+    explain: bool,
+
     /// Watch mode - continuously monitor for changes
     #[arg(long)]
     watch: bool,
@@ -211,12 +570,146 @@ struct Cli {
     quiet: bool,
 }
 
+#[derive(clap::Subcommand, Debug, Clone)]
+enum Command {
+    /// Discover and parse sources into the cache without running analysis
+    PrimeCache(PrimeCacheArgs),
+    /// List the detectors available to --detect and --skip-detect
+    ListDetectors,
+    /// Restore the files changed by a safe delete from its journal
+    Undo(UndoArgs),
+    /// Keep the analysis in memory and answer JSON-RPC requests on a socket
+    Daemon(DaemonArgs),
+    /// Show why a declaration is alive (the path from an entry point) or dead
+    Explain(ExplainArgs),
+    /// Show what else becomes dead code if the given declarations are deleted
+    SimulateDelete(SimulateDeleteArgs),
+    /// Compare public declarations with a recorded API dump (metalava or
+    /// binary compatibility validator)
+    ApiDiff(ApiDiffArgs),
+}
+
+/// Ids accepted by --detect and --skip-detect
+fn detector_ids() -> clap::builder::PossibleValuesParser {
+    clap::builder::PossibleValuesParser::new(DetectorRegistry::builtin().ids())
+}
+
+#[derive(clap::Args, Debug, Clone)]
+struct PrimeCacheArgs {
+    /// Path to the project directory to cache
+    #[arg(default_value = ".")]
+    path: PathBuf,
+
+    /// Project roots to cache, instead of PATH (can be specified multiple
+    /// times). Each root keeps its own cache file.
+    #[arg(long = "path", value_name = "DIR")]
+    paths: Vec<PathBuf>,
+
+    /// Path to configuration file
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Custom cache file path (default: .searchdeadcode-cache.json)
+    #[arg(long, value_name = "FILE")]
+    cache_path: Option<PathBuf>,
+
+    /// Check the existing cache against current sources instead of writing it
+    /// Exits with an error if any entry is stale, missing or uncached
+    #[arg(long)]
+    verify: bool,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+struct DaemonArgs {
+    /// Path to the project directory to analyze
+    #[arg(default_value = ".")]
+    path: PathBuf,
+
+    /// Path to configuration file
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Unix socket to listen on
+    #[arg(long, value_name = "PATH")]
+    socket: PathBuf,
+
+    /// Detectors to run in addition to the defaults (comma-separated)
+    #[arg(
+        long,
+        value_name = "ID",
+        value_delimiter = ',',
+        value_parser = detector_ids()
+    )]
+    detect: Vec<String>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+struct ExplainArgs {
+    /// Declaration to explain: fully qualified name, name, or file:line
+    target: String,
+
+    /// Path to the project directory to analyze
+    #[arg(default_value = ".")]
+    path: PathBuf,
+
+    /// Path to configuration file
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+struct SimulateDeleteArgs {
+    /// Declarations to delete: fully qualified name, name, or file:line
+    #[arg(required = true)]
+    targets: Vec<String>,
+
+    /// Path to the project directory to analyze
+    #[arg(long, default_value = ".")]
+    path: PathBuf,
+
+    /// Path to configuration file
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+struct ApiDiffArgs {
+    /// API dump to compare with (can be specified multiple times, e.g. one
+    /// per library module)
+    #[arg(long = "api", value_name = "FILE", required = true)]
+    dumps: Vec<PathBuf>,
+
+    /// Path to the project directory to analyze
+    #[arg(default_value = ".")]
+    path: PathBuf,
+
+    /// Path to configuration file
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+struct UndoArgs {
+    /// Journal written by --journal
+    #[arg(long, value_name = "FILE")]
+    journal: PathBuf,
+
+    /// Only restore these files (default: every file in the journal)
+    files: Vec<PathBuf>,
+
+    /// Restore files even if they were edited after the safe delete
+    #[arg(long)]
+    force: bool,
+}
+
 #[derive(clap::ValueEnum, Clone, Debug, Default)]
 enum OutputFormat {
     #[default]
     Terminal,
     Json,
     Sarif,
+    GithubActions,
+    CodeQuality,
 }
 
 impl From<OutputFormat> for report::ReportFormat {
@@ -225,18 +718,137 @@ impl From<OutputFormat> for report::ReportFormat {
             OutputFormat::Terminal => report::ReportFormat::Terminal,
             OutputFormat::Json => report::ReportFormat::Json,
             OutputFormat::Sarif => report::ReportFormat::Sarif,
+            OutputFormat::GithubActions => report::ReportFormat::GithubActions,
+            OutputFormat::CodeQuality => report::ReportFormat::CodeQuality,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum GroupByArg {
+    #[default]
+    File,
+    Package,
+    Kind,
+    Severity,
+    Owner,
+}
+
+impl From<GroupByArg> for report::GroupBy {
+    fn from(group_by: GroupByArg) -> Self {
+        match group_by {
+            GroupByArg::File => report::GroupBy::File,
+            GroupByArg::Package => report::GroupBy::Package,
+            GroupByArg::Kind => report::GroupBy::Kind,
+            GroupByArg::Severity => report::GroupBy::Severity,
+            GroupByArg::Owner => report::GroupBy::Owner,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum DeprecationLevelArg {
+    #[default]
+    Warning,
+    Error,
+}
+
+impl From<DeprecationLevelArg> for refactor::DeprecationLevel {
+    fn from(level: DeprecationLevelArg) -> Self {
+        match level {
+            DeprecationLevelArg::Warning => refactor::DeprecationLevel::Warning,
+            DeprecationLevelArg::Error => refactor::DeprecationLevel::Error,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum SortByArg {
+    #[default]
+    Line,
+    Confidence,
+    Size,
+}
+
+impl From<SortByArg> for report::SortBy {
+    fn from(sort_by: SortByArg) -> Self {
+        match sort_by {
+            SortByArg::Line => report::SortBy::Line,
+            SortByArg::Confidence => report::SortBy::Confidence,
+            SortByArg::Size => report::SortBy::Size,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum FrontendArg {
+    #[default]
+    #[value(name = "treesitter")]
+    TreeSitter,
+    Bytecode,
+}
+
+impl From<FrontendArg> for parser::Frontend {
+    fn from(frontend: FrontendArg) -> Self {
+        match frontend {
+            FrontendArg::TreeSitter => parser::Frontend::TreeSitter,
+            FrontendArg::Bytecode => parser::Frontend::Bytecode,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum EntrySetArg {
+    #[default]
+    App,
+    Tests,
+    Diff,
+}
+
+impl From<EntrySetArg> for analysis::EntrySet {
+    fn from(entry_set: EntrySetArg) -> Self {
+        match entry_set {
+            EntrySetArg::App => analysis::EntrySet::App,
+            EntrySetArg::Tests => analysis::EntrySet::Tests,
+            EntrySetArg::Diff => analysis::EntrySet::Diff,
         }
     }
 }
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    // The main project of a multi-root analysis stands in for PATH
+    if let Some(first) = cli.paths.first() {
+        cli.path = first.clone();
+    }
 
     // Initialize logging
     init_logging(cli.verbose, cli.quiet);
 
     info!("SearchDeadCode v{}", env!("CARGO_PKG_VERSION"));
 
+    // Bound every parallel stage, not just parsing
+    if let Some(jobs) = cli.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs.into())
+            .build_global()
+            .map_err(|e| miette::miette!("Failed to start {} worker threads: {}", jobs, e))?;
+    }
+
+    match &cli.command {
+        Some(Command::PrimeCache(args)) => return run_prime_cache(args),
+        Some(Command::ListDetectors) => {
+            list_detectors();
+            return Ok(());
+        }
+        Some(Command::Undo(args)) => return run_undo(args),
+        Some(Command::Daemon(args)) => return run_daemon(args),
+        Some(Command::Explain(args)) => return run_explain(args),
+        Some(Command::SimulateDelete(args)) => return run_simulate_delete(args),
+        Some(Command::ApiDiff(args)) => return run_api_diff(args),
+        None => {}
+    }
+
     // Load configuration
     let config = load_config(&cli)?;
 
@@ -258,43 +870,13 @@ fn run_watch_mode(config: &Config, cli: &Cli) -> Result<()> {
 
     // Clone what we need for the closure
     let config = config.clone();
-    let cli_path = cli.path.clone();
-    let cli_format = cli.format.clone();
-    let cli_output = cli.output.clone();
-    let cli_verbose = cli.verbose;
-    let cli_quiet = cli.quiet;
-    let cli_deep = cli.deep;
-    let cli_parallel = cli.parallel;
-    let cli_enhanced = cli.enhanced;
-    let cli_detect_cycles = cli.detect_cycles;
-    let cli_min_confidence = cli.min_confidence.clone();
-    let cli_baseline = cli.baseline.clone();
-    let cli_coverage = cli.coverage.clone();
-    let cli_proguard_usage = cli.proguard_usage.clone();
+    let cli = cli.clone();
+    let watch_path = cli.path.clone();
 
     watcher
-        .watch(&cli.path, move || {
-            // Suppress output for repeated runs except results
-            if !cli_verbose {
-                // Temporarily change log level
-            }
-
-            // Re-run analysis
-            match run_analysis_internal(
-                &config,
-                &cli_path,
-                cli_format.clone(),
-                cli_output.clone(),
-                cli_deep,
-                cli_parallel,
-                cli_enhanced,
-                cli_detect_cycles,
-                &cli_min_confidence,
-                &cli_baseline,
-                &cli_coverage,
-                &cli_proguard_usage,
-                cli_quiet,
-            ) {
+        .watch(&watch_path, move || {
+            // Re-run analysis through the same pipeline as a single run
+            match run_analysis(&config, &cli) {
                 Ok(_) => {
                     println!();
                     println!("{}", "✓ Analysis complete. Waiting for changes...".green());
@@ -311,162 +893,501 @@ fn run_watch_mode(config: &Config, cli: &Cli) -> Result<()> {
     Ok(())
 }
 
-/// Internal analysis function for watch mode
-#[allow(clippy::too_many_arguments)]
-fn run_analysis_internal(
-    config: &Config,
-    path: &std::path::Path,
-    format: OutputFormat,
-    output: Option<PathBuf>,
-    deep: bool,
-    parallel: bool,
-    enhanced: bool,
-    detect_cycles: bool,
-    min_confidence: &str,
-    baseline_path: &Option<PathBuf>,
-    coverage_files: &[PathBuf],
-    proguard_usage: &Option<PathBuf>,
-    quiet: bool,
-) -> Result<()> {
-    use colored::Colorize;
-    use std::time::Instant;
-
-    let start_time = Instant::now();
-
-    // Discover files
-    let finder = FileFinder::new(config);
-    let files = finder.find_files(path)?;
-
-    if files.is_empty() {
-        if !quiet {
-            println!("{}", "No Kotlin or Java files found.".yellow());
-        }
-        return Ok(());
+fn list_detectors() {
+    let registry = DetectorRegistry::builtin();
+    let width = registry.iter().map(|d| d.id.len()).max().unwrap_or(0);
+    for detector in registry.iter() {
+        let inputs: Vec<&str> = detector.inputs.iter().map(|i| i.as_str()).collect();
+        println!(
+            "{:width$}  {:7}  {}  {}",
+            detector.id.bold(),
+            detector.severity.as_str(),
+            detector.description,
+            format!("[{}]", inputs.join(", ")).dimmed(),
+        );
     }
+}
 
-    // Parse and build graph
-    let graph = if parallel {
-        let parallel_builder = ParallelGraphBuilder::new();
-        parallel_builder.build_from_files(&files)?
-    } else {
-        let mut graph_builder = GraphBuilder::new();
-        for file in &files {
-            graph_builder.process_file(file)?;
-        }
-        graph_builder.build()
+/// Serve JSON-RPC requests from an in-memory analysis until shut down
+fn run_daemon(args: &DaemonArgs) -> Result<()> {
+    let config = match &args.config {
+        Some(config_path) => Config::from_file(config_path)?,
+        None => Config::from_default_locations(&args.path)?,
     };
+    let options = PipelineOptions {
+        detectors: DetectorRegistry::builtin()
+            .default_ids()
+            .into_iter()
+            .map(String::from)
+            .chain(args.detect.iter().cloned())
+            .collect(),
+        ..PipelineOptions::default()
+    }
+    .with_detectors(&config.detectors);
+
+    // Parse everything up front so the first request is already incremental
+    let mut daemon = daemon::Daemon::new(config, args.path.clone(), options);
+    daemon.analyze()?;
+    println!(
+        "{}",
+        format!("Listening on {}", args.socket.display()).green()
+    );
+    daemon::serve(daemon, &args.socket)
+}
 
-    // Detect entry points
-    let entry_detector = EntryPointDetector::new(config);
-    let entry_points = entry_detector.detect(&graph, path)?;
+/// Roll back a safe delete from its journal
+fn run_undo(args: &UndoArgs) -> Result<()> {
+    let journal = refactor::Journal::load(&args.journal)?;
+    let rollback = journal.rollback(&args.files, args.force)?;
 
-    // Load ProGuard data if available
-    let proguard_data = if let Some(ref usage_path) = proguard_usage {
-        ProguardUsage::parse(usage_path).ok()
-    } else {
-        None
-    };
+    for path in &rollback.restored {
+        println!("  {} Restored {}", "✓".green(), path.display());
+    }
+    for path in &rollback.conflicts {
+        println!(
+            "  {} Skipped {}: changed since the safe delete (use --force)",
+            "✗".yellow(),
+            path.display()
+        );
+    }
+    for path in &rollback.unknown {
+        println!("  {} Not in journal: {}", "✗".red(), path.display());
+    }
+    println!(
+        "{}",
+        format!(
+            "Restored {} of {} journaled files",
+            rollback.restored.len(),
+            journal.entries().len()
+        )
+        .dimmed()
+    );
 
-    // Run reachability analysis
-    let (dead_code, reachable) = if deep {
-        let analyzer = DeepAnalyzer::new()
-            .with_parallel(parallel)
-            .with_unused_members(true);
-        analyzer.analyze(&graph, &entry_points)
-    } else if enhanced && proguard_data.is_some() {
-        let mut analyzer = EnhancedAnalyzer::new();
-        if let Some(pg) = proguard_data.clone() {
-            analyzer = analyzer.with_proguard(pg);
-        }
-        analyzer.analyze(&graph, &entry_points)
+    if rollback.conflicts.is_empty() && rollback.unknown.is_empty() {
+        Ok(())
     } else {
-        let analyzer = ReachabilityAnalyzer::new();
-        analyzer.find_unreachable_with_reachable(&graph, &entry_points)
-    };
+        Err(miette::miette!("Some files were not restored"))
+    }
+}
 
-    // Load coverage data
-    let coverage_data = if !coverage_files.is_empty() {
-        parse_coverage_files(coverage_files).ok()
-    } else {
-        None
+/// Print the shortest path from an entry point to each declaration matching
+/// the target, or the findings reported for it when there is none
+fn run_explain(args: &ExplainArgs) -> Result<()> {
+    use graph::PathLink;
+
+    let config = match &args.config {
+        Some(config_path) => Config::from_file(config_path)?,
+        None => Config::from_default_locations(&args.path)?,
     };
+    let ctx = AnalysisSession::new(config).run(&args.path)?.into_context();
 
-    // Enhance findings
-    let mut hybrid = HybridAnalyzer::new();
-    if let Some(coverage) = coverage_data {
-        hybrid = hybrid.with_coverage(coverage);
-    }
-    if let Some(proguard) = proguard_data {
-        hybrid = hybrid.with_proguard(proguard);
+    let matches = ctx.graph.resolve(&args.target);
+    if matches.is_empty() {
+        return Err(miette::miette!("No declaration matches {}", args.target));
     }
 
-    let dead_code = hybrid.enhance_findings(dead_code);
-
-    // Filter by confidence
-    let min_conf = parse_confidence(min_confidence);
-    let dead_code: Vec<_> = dead_code
-        .into_iter()
-        .filter(|dc| dc.confidence >= min_conf)
-        .collect();
-
-    // Apply baseline filter
-    let dead_code = if let Some(ref bp) = baseline_path {
-        match baseline::Baseline::load(bp) {
-            Ok(baseline) => {
-                let stats = baseline.stats(&dead_code, path);
-                if !quiet {
-                    println!("{}", format!("📋 Baseline: {}", stats).cyan());
+    let describe = |decl: &graph::Declaration| {
+        format!(
+            "{} ({}, {}:{})",
+            decl.fully_qualified_name.as_deref().unwrap_or(&decl.name),
+            decl.kind.display_name(),
+            decl.location.file.display(),
+            decl.location.line
+        )
+    };
+    for decl in matches {
+        println!("{}", describe(decl).bold());
+        match ctx.graph.shortest_path(&ctx.entry_points, &decl.id) {
+            Some(path) if path.is_empty() => {
+                let reason = match (
+                    ctx.kept_by_rules.get(&decl.id),
+                    ctx.entry_point_categories.get(&decl.id),
+                ) {
+                    (Some(rule), _) => format!(" (kept by {})", rule),
+                    (None, Some(category)) => format!(" ({})", category.label()),
+                    (None, None) => String::new(),
+                };
+                println!("  {} Alive: it is an entry point{}", "✓".green(), reason);
+            }
+            Some(path) => {
+                let category = ctx
+                    .entry_point_categories
+                    .get(&path[0].from.id)
+                    .map(|category| format!(" ({})", category.label()))
+                    .unwrap_or_default();
+                println!(
+                    "  {} Alive: reached in {} step(s) from entry point{}",
+                    "✓".green(),
+                    path.len(),
+                    category
+                );
+                println!("    {}", describe(path[0].from));
+                for step in &path {
+                    let link = match step.link {
+                        PathLink::Reference(reference) => format!(
+                            "{} at {}:{}{}",
+                            reference.kind.display_name(),
+                            reference.location.file.display(),
+                            reference.location.line,
+                            if reference.ambiguous {
+                                " (ambiguous overload)"
+                            } else {
+                                ""
+                            }
+                        ),
+                        PathLink::Member => "member".to_string(),
+                        PathLink::Parent => "declared in".to_string(),
+                    };
+                    println!("    └─ {} → {}", link.dimmed(), describe(step.to));
                 }
-                baseline
-                    .filter_new(&dead_code, path)
-                    .into_iter()
-                    .cloned()
-                    .collect()
             }
-            Err(_) => dead_code,
+            None if ctx.reachable.contains(&decl.id) => {
+                println!(
+                    "  {} Alive: kept by the analysis without a reference path",
+                    "✓".green()
+                );
+            }
+            None => {
+                println!("  {} Dead: no path from any entry point", "✗".red());
+            }
         }
-    } else {
-        dead_code
-    };
 
-    // Detect cycles if requested
-    if detect_cycles {
-        let cycle_detector = CycleDetector::new();
-        let cycle_stats = cycle_detector.get_cycle_stats(&graph, &reachable);
-        if cycle_stats.has_cycles() && !quiet {
+        if let Some(scheduled_work) = &ctx.scheduled_work {
+            for site in scheduled_work.sites(decl) {
+                println!(
+                    "    {} {} at {}:{}",
+                    "scheduled with".dimmed(),
+                    site.api.label(),
+                    site.file.display(),
+                    site.line
+                );
+            }
+        }
+        for dc in ctx
+            .dead_code
+            .iter()
+            .filter(|dc| dc.declaration.id == decl.id)
+        {
             println!(
-                "{}",
-                format!(
-                    "🧟 {} dead cycles ({} declarations)",
-                    cycle_stats.num_dead_cycles, cycle_stats.total_declarations_in_cycles
-                )
-                .yellow()
+                "    {} [{}] {}",
+                "•".dimmed(),
+                dc.confidence.as_str(),
+                dc.message
             );
         }
     }
 
-    // Report results
-    let reporter = Reporter::new(format.into(), output);
-    reporter.report(&dead_code)?;
+    Ok(())
+}
 
-    // Print timing
-    let elapsed = start_time.elapsed();
-    if !quiet {
+fn run_simulate_delete(args: &SimulateDeleteArgs) -> Result<()> {
+    use analysis::DeletionSimulator;
+
+    let config = match &args.config {
+        Some(config_path) => Config::from_file(config_path)?,
+        None => Config::from_default_locations(&args.path)?,
+    };
+    let ctx = AnalysisSession::new(config)
+        .with_options(PipelineOptions::default())
+        .run(&args.path)?
+        .into_context();
+
+    let mut targets = Vec::new();
+    for target in &args.targets {
+        let matches = ctx.graph.resolve(target);
+        if matches.is_empty() {
+            return Err(miette::miette!("No declaration matches {}", target));
+        }
+        targets.extend(matches.into_iter().map(|decl| decl.id.clone()));
+    }
+
+    let simulation = DeletionSimulator::new()
+        .with_member_retention(ctx.member_retention.clone())
+        .simulate(&ctx.graph, &ctx.entry_points, &targets);
+
+    let describe = |decl: &graph::Declaration| {
+        format!(
+            "{} {} ({}:{})",
+            decl.kind.display_name(),
+            decl.fully_qualified_name.as_deref().unwrap_or(&decl.name),
+            decl.location.file.display(),
+            decl.location.line
+        )
+    };
+
+    println!(
+        "{}",
+        format!("Deleting {} declaration(s):", simulation.removed.len()).bold()
+    );
+    for id in &simulation.removed {
+        if let Some(decl) = ctx.graph.get_declaration(id) {
+            println!("  {} {}", "-".red(), describe(decl));
+        }
+    }
+    println!();
+
+    if simulation.newly_dead.is_empty() {
+        println!("{} Nothing else becomes dead code", "✓".green());
+        return Ok(());
+    }
+    println!(
+        "{}",
+        format!(
+            "{} more declaration(s) become dead code:",
+            simulation.newly_dead.len()
+        )
+        .yellow()
+        .bold()
+    );
+    for dc in &simulation.newly_dead {
+        println!("  {} {}", "✗".red(), describe(&dc.declaration));
+    }
+
+    Ok(())
+}
+
+fn run_api_diff(args: &ApiDiffArgs) -> Result<()> {
+    use api::{ApiDiff, ApiSurface};
+
+    let mut surface = ApiSurface::default();
+    for dump in &args.dumps {
+        let parsed = ApiSurface::parse(dump)
+            .map_err(|e| miette::miette!("Failed to read API dump {}: {}", dump.display(), e))?;
+        surface.merge(parsed);
+    }
+    if surface.is_empty() {
+        return Err(miette::miette!("The API dumps list no classes"));
+    }
+
+    let config = match &args.config {
+        Some(config_path) => Config::from_file(config_path)?,
+        None => Config::from_default_locations(&args.path)?,
+    };
+    let ctx = AnalysisSession::new(config)
+        .with_options(PipelineOptions::default())
+        .run(&args.path)?
+        .into_context();
+    let diff = ApiDiff::compute(&ctx.graph, &ctx.entry_points, &surface);
+
+    if diff.is_empty() {
+        println!("{} Public declarations match the API dump", "✓".green());
+        return Ok(());
+    }
+    let describe = |decl: &graph::Declaration| {
+        format!(
+            "{} {} ({}:{})",
+            decl.kind.display_name(),
+            decl.fully_qualified_name.as_deref().unwrap_or(&decl.name),
+            decl.location.file.display(),
+            decl.location.line
+        )
+    };
+
+    if !diff.accidental.is_empty() {
         println!(
             "{}",
             format!(
-                "⏱  Analyzed {} files in {:.2}s",
-                files.len(),
-                elapsed.as_secs_f64()
+                "{} public declaration(s) missing from the API dump:",
+                diff.accidental.len()
             )
-            .dimmed()
+            .yellow()
+            .bold()
         );
+        for decl in &diff.accidental {
+            println!("  {} {}", "+".yellow(), describe(decl));
+        }
+        println!();
+    }
+    if !diff.unused.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "{} API declaration(s) not used in this workspace:",
+                diff.unused.len()
+            )
+            .bold()
+        );
+        for decl in &diff.unused {
+            println!("  {} {}", "•".dimmed(), describe(decl));
+        }
+        println!();
+    }
+    if !diff.missing.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "{} API dump entry(ies) without a declaration in the sources:",
+                diff.missing.len()
+            )
+            .red()
+            .bold()
+        );
+        for entry in &diff.missing {
+            println!("  {} {}", "-".red(), entry);
+        }
     }
 
     Ok(())
 }
 
+fn run_prime_cache(args: &PrimeCacheArgs) -> Result<()> {
+    use cache::{AnalysisCache, CachePrimer, WorkspaceCache};
+    use std::time::Instant;
+
+    let start_time = Instant::now();
+    let roots = if args.paths.is_empty() {
+        vec![args.path.clone()]
+    } else {
+        args.paths.clone()
+    };
+    if roots.len() > 1 && args.cache_path.is_some() {
+        return Err(miette::miette!(
+            "--cache-path needs a single root; with several --path each root keeps its own cache"
+        ));
+    }
+    let config = match &args.config {
+        Some(config_path) => Config::from_file(config_path)?,
+        None => Config::from_default_locations(&roots[0])?,
+    };
+    let cache_paths: Vec<(PathBuf, PathBuf)> = roots
+        .iter()
+        .map(|root| {
+            let cache_path = args
+                .cache_path
+                .clone()
+                .unwrap_or_else(|| AnalysisCache::default_cache_path(root));
+            (root.clone(), cache_path)
+        })
+        .collect();
+
+    if args.verify {
+        let mut stale_roots = Vec::new();
+        for (root, cache_path) in &cache_paths {
+            let cache = AnalysisCache::load(cache_path).map_err(|e| {
+                miette::miette!("Failed to load cache {}: {}", cache_path.display(), e)
+            })?;
+            let verification = CachePrimer::new(&config, root.clone()).verify(&cache)?;
+
+            println!(
+                "{}",
+                format!("📦 Cache: {} ({})", cache_path.display(), cache.stats()).cyan()
+            );
+            for (label, paths) in [
+                ("stale", &verification.stale),
+                ("missing", &verification.missing),
+                ("uncached", &verification.uncached),
+            ] {
+                for path in paths {
+                    println!("  {} {}", format!("{:>8}", label).yellow(), path.display());
+                }
+            }
+
+            if !verification.is_consistent() {
+                stale_roots.push(format!(
+                    "{}: {} up to date, {} stale, {} missing, {} uncached",
+                    root.display(),
+                    verification.up_to_date,
+                    verification.stale.len(),
+                    verification.missing.len(),
+                    verification.uncached.len()
+                ));
+                continue;
+            }
+
+            println!(
+                "{}",
+                format!("✓ Cache is consistent ({} files)", verification.up_to_date).green()
+            );
+        }
+
+        if !stale_roots.is_empty() {
+            return Err(miette::miette!(
+                "Cache is out of date: {}",
+                stale_roots.join("; ")
+            ));
+        }
+        return Ok(());
+    }
+
+    // Start from the existing caches so unchanged files are not parsed again
+    let mut workspace = WorkspaceCache::load_from(cache_paths.clone());
+    for (root, _, cache) in workspace.iter_mut() {
+        let stats = CachePrimer::new(&config, root.to_path_buf()).prime(cache)?;
+        if roots.len() > 1 {
+            println!("{}", format!("📦 {}: {}", root.display(), stats).cyan());
+        } else {
+            println!("{}", format!("📦 {}", stats).cyan());
+        }
+    }
+    workspace
+        .save()
+        .map_err(|e| miette::miette!("Failed to write cache: {}", e))?;
+
+    let size: u64 = cache_paths
+        .iter()
+        .filter_map(|(_, cache_path)| std::fs::metadata(cache_path).ok())
+        .map(|m| m.len())
+        .sum();
+    let written: Vec<String> = cache_paths
+        .iter()
+        .map(|(_, cache_path)| cache_path.display().to_string())
+        .collect();
+    println!(
+        "{}",
+        format!(
+            "✓ Cache written to {} ({}, {:.1} KiB) in {:.2}s",
+            written.join(", "),
+            workspace.stats(),
+            size as f64 / 1024.0,
+            start_time.elapsed().as_secs_f64()
+        )
+        .green()
+    );
+
+    Ok(())
+}
+
+/// List the files left out of the graph, with why they could not be parsed
+fn print_skipped_files(ctx: &PipelineContext) {
+    if ctx.skipped_files.is_empty() {
+        return;
+    }
+    eprintln!(
+        "{}",
+        format!(
+            "⚠️  Skipped Files ({}): could not be parsed, so what only they reference may be reported",
+            ctx.skipped_files.len()
+        )
+        .yellow()
+    );
+    for skipped in &ctx.skipped_files {
+        let path = skipped
+            .path
+            .strip_prefix(&ctx.root)
+            .unwrap_or(&skipped.path);
+        eprintln!("  {} - {}", path.display(), skipped.reason);
+    }
+}
+
+fn print_uncovered_modules(ctx: &PipelineContext) {
+    if ctx.uncovered_modules.is_empty() {
+        return;
+    }
+    eprintln!(
+        "{}",
+        format!(
+            "📉 Modules Without Coverage ({}): findings in them get no runtime evidence",
+            ctx.uncovered_modules.len()
+        )
+        .yellow()
+    );
+    for module in &ctx.uncovered_modules {
+        eprintln!("  {}", module);
+    }
+}
+
 fn init_logging(verbose: bool, quiet: bool) {
+    use tracing_subscriber::fmt::format::FmtSpan;
     use tracing_subscriber::{fmt, EnvFilter};
 
     let filter = if quiet {
@@ -477,7 +1398,19 @@ fn init_logging(verbose: bool, quiet: bool) {
         EnvFilter::new("info")
     };
 
-    fmt().with_env_filter(filter).with_target(false).init();
+    // Verbose runs log how long each stage and detector span took
+    let span_events = if verbose {
+        FmtSpan::CLOSE
+    } else {
+        FmtSpan::NONE
+    };
+    // Logs go to stderr so stdout holds only the report
+    fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_target(false)
+        .with_span_events(span_events)
+        .init();
 }
 
 fn load_config(cli: &Cli) -> Result<Config> {
@@ -498,179 +1431,299 @@ fn load_config(cli: &Cli) -> Result<Config> {
     if !cli.retain.is_empty() {
         config.retain_patterns.extend(cli.retain.clone());
     }
+    config.entry_points.extend(cli.entry_points.clone());
+    if cli.generated_sources {
+        config.discovery.generated_sources = true;
+    }
+    if cli.compose_previews {
+        config.android.compose_previews = true;
+    }
+    if let Some(variant) = cli.variants.first() {
+        config.android.variant = Some(variant.clone());
+    }
 
     Ok(config)
 }
 
-fn run_analysis(config: &Config, cli: &Cli) -> Result<()> {
-    use colored::Colorize;
-    use indicatif::{ProgressBar, ProgressStyle};
-    use std::time::Instant;
-
-    let start_time = Instant::now();
-
-    // Step 1: Discover files
-    info!("Discovering files...");
-    let finder = FileFinder::new(config);
-    let files = finder.find_files(&cli.path)?;
-
-    info!("Found {} files to analyze", files.len());
+/// Detectors enabled by their individual flags, with their registry ids
+fn detector_flags(cli: &Cli) -> Vec<(bool, &'static str)> {
+    vec![
+        (cli.unused_params, "unused-params"),
+        (cli.write_only, "write-only"),
+        (cli.sealed_variants, "sealed-variants"),
+        (cli.unused_enum_cases, "unused-enum-cases"),
+        (cli.dead_branches, "dead-branches"),
+        (cli.redundant_overrides, "redundant-overrides"),
+        (cli.unused_imports, "unused-imports"),
+        (cli.unused_typealiases, "unused-typealiases"),
+        (cli.property_accessors, "property-accessors"),
+        (cli.only_used_in_tests, "only-used-in-tests"),
+        (cli.preview_only, "preview-only"),
+        (cli.uninvoked_params, "uninvoked-params"),
+        (cli.dead_objects, "dead-objects"),
+        (cli.unused_resources, "unused-resources"),
+        (cli.unused_extras, "unused-extras"),
+        (cli.unused_bundle_args, "unused-bundle-args"),
+        (cli.unused_broadcasts, "unused-broadcasts"),
+        (cli.write_only_prefs, "write-only-prefs"),
+        (cli.unused_pref_keys, "unused-pref-keys"),
+        (cli.write_only_dao, "write-only-dao"),
+        (
+            cli.unused_module_deps || cli.module_graph.is_some(),
+            "unused-module-deps",
+        ),
+        (cli.unused_nav_args, "unused-nav-args"),
+        (cli.deep_links, "deep-links"),
+        (cli.dead_doc_links, "dead-doc-links"),
+        (cli.compose_state, "compose-state"),
+        (cli.detect_cycles, "cycles"),
+        (cli.dead_files, "dead-files"),
+        (cli.include_runtime_dead, "runtime-dead"),
+    ]
+}
 
-    if files.is_empty() {
-        println!("{}", "No Kotlin or Java files found.".yellow());
-        return Ok(());
+/// Project roots analyzed together: the `--path`s, or PATH
+fn project_roots(cli: &Cli) -> Vec<PathBuf> {
+    if cli.paths.is_empty() {
+        vec![cli.path.clone()]
+    } else {
+        cli.paths.clone()
     }
+}
 
-    // Step 2: Parse files and build graph
-    let graph = if cli.parallel {
-        // Parallel parsing mode
-        println!(
-            "{}",
-            format!("⚡ Parallel mode: parsing {} files...", files.len()).cyan()
-        );
-        let parallel_builder = ParallelGraphBuilder::new();
-        parallel_builder.build_from_files(&files)?
-    } else {
-        // Sequential parsing mode
-        let pb = ProgressBar::new(files.len() as u64);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template(
-                    "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})",
-                )
-                .unwrap()
-                .progress_chars("#>-"),
-        );
+/// How many analyzed files the analysis cache holds up to date, when a cache
+/// is in use
+fn cache_usage(ctx: &PipelineContext, cli: &Cli) -> Option<CacheUsage> {
+    use cache::{AnalysisCache, IncrementalAnalyzer};
 
-        info!("Parsing files...");
-        let mut graph_builder = GraphBuilder::new();
+    if !cli.incremental && cli.cache_path.is_none() {
+        return None;
+    }
+    let cache_path = cli
+        .cache_path
+        .clone()
+        .unwrap_or_else(|| AnalysisCache::default_cache_path(&ctx.root));
+    let analyzer = IncrementalAnalyzer::with_cache_path(ctx.root.clone(), cache_path.clone());
+    let paths: Vec<PathBuf> = ctx.files.iter().map(|f| f.path.clone()).collect();
+    let (misses, hits) = analyzer.get_files_to_parse(&paths);
+    Some(CacheUsage::new(cache_path, hits.len(), misses.len()))
+}
 
-        for file in &files {
-            graph_builder.process_file(file)?;
-            pb.inc(1);
-        }
-        pb.finish_with_message("Parsing complete");
+/// Build pipeline options from the command line
+fn pipeline_options(cli: &Cli) -> PipelineOptions {
+    PipelineOptions {
+        parallel: cli.parallel,
+        jobs: cli.jobs.map(usize::from),
+        max_memory_mb: cli.max_memory_mb,
+        strict_parse: cli.strict_parse,
+        deep: cli.deep,
+        deep_respect_overrides: cli.deep_respect_overrides,
+        enhanced: cli.enhanced,
+        show_progress: !cli.quiet,
+        frontend: cli.frontend.into(),
+        entry_set: cli.entry_set.into(),
+        symbol_dumps: cli.symbols.clone(),
+        proguard_usage: cli.proguard_usage.clone(),
+        proguard_rules: cli.proguard_rules.clone(),
+        proguard_seeds: cli.proguard_seeds.clone(),
+        report_kept: cli.report_kept,
+        external_usage: cli.external_usage.clone(),
+        compiled_classes: cli.classes.clone(),
+        coverage_files: cli.coverage.clone(),
+        coverage_auto: cli.coverage_auto,
+        coverage_store: (cli.coverage_store || cli.coverage_store_path.is_some()).then(|| {
+            cli.coverage_store_path
+                .clone()
+                .unwrap_or_else(|| coverage::CoverageStore::default_path(&cli.path))
+        }),
+        coverage_max_age: cli.coverage_max_age,
+        detectors: detector_flags(cli)
+            .into_iter()
+            .filter(|(enabled, _)| *enabled)
+            .map(|(_, id)| id)
+            .chain(cli.fix_visibility.then_some("redundant-public"))
+            .chain(cli.fix_mutability.then_some("property-accessors"))
+            .chain(DetectorRegistry::builtin().default_ids())
+            .map(String::from)
+            .chain(cli.detect.iter().cloned())
+            .collect(),
+        rules_file: cli.rules.clone(),
+        plugins: cli.plugins.clone(),
+        min_confidence: parse_confidence(&cli.min_confidence),
+        runtime_only: cli.runtime_only,
+        exclude_risk: cli.exclude_risk.clone(),
+        min_size: cli.min_size.unwrap_or(0),
+        doc_links: cli.doc_links,
+        baseline: cli.baseline.clone(),
+        generate_baseline: cli.generate_baseline.clone(),
+        diff_base: cli.diff_base.clone(),
+        only_packages: cli.only_packages.clone(),
+        prune_by_imports: cli.prune_by_imports,
+        owners: cli.owners || cli.group_by == GroupByArg::Owner,
+        codeowners: cli.codeowners.clone(),
+        blame: cli.blame,
+        cluster: cli.cluster,
+    }
+}
 
-        graph_builder.build()
+/// Analyze the other `--variant`s and merge their findings into `ctx`, which
+/// holds the analysis of the first one
+fn compare_variants(ctx: &mut PipelineContext, config: &Config, cli: &Cli) -> Result<()> {
+    let findings = |ctx: &mut PipelineContext| {
+        VariantFindings::new(
+            ctx.config.android.variant.clone().unwrap_or_default(),
+            ctx.files.iter().map(|f| f.path.clone()).collect(),
+            std::mem::take(&mut ctx.dead_code),
+        )
     };
 
-    let parse_time = start_time.elapsed();
-    if cli.parallel {
+    let mut results = vec![findings(ctx)];
+    for variant in &cli.variants[1..] {
+        let mut config = config.clone();
+        config.android.variant = Some(variant.clone());
+        let mut options = pipeline_options(cli);
+        // The baseline is written from the merged findings below
+        options.generate_baseline = None;
+        let mut variant_ctx = AnalysisSession::new(config)
+            .with_options(options)
+            .without_detectors(&cli.skip_detect)
+            .with_roots(project_roots(cli))
+            .run(&cli.path)?
+            .into_context();
+        results.push(findings(&mut variant_ctx));
+    }
+
+    let mut comparison = analysis::variants::compare_variants(results);
+    let min_confidence = ctx.options.min_confidence;
+    comparison
+        .dead_code
+        .retain(|dc| dc.confidence >= min_confidence);
+    if !cli.quiet {
         println!(
             "{}",
             format!(
-                "⚡ Parsed {} files in {:.2}s",
-                files.len(),
-                parse_time.as_secs_f64()
+                "🧬 Compared {} variants: {} findings dead in all, {} dead only in some",
+                cli.variants.len(),
+                comparison.dead_in_all,
+                comparison.dead_in_some
             )
-            .green()
+            .cyan()
         );
     }
-
-    // Step 3: Detect entry points
-    info!("Detecting entry points...");
-    let entry_detector = EntryPointDetector::new(config);
-    let entry_points = entry_detector.detect(&graph, &cli.path)?;
-
-    info!("Found {} entry points", entry_points.len());
-
-    // Step 4: Load ProGuard data early if available (needed for enhanced mode)
-    let proguard_data = if let Some(ref usage_path) = cli.proguard_usage {
-        info!("Loading ProGuard usage.txt from {:?}...", usage_path);
-        match ProguardUsage::parse(usage_path) {
-            Ok(data) => {
-                let stats = data.stats();
-                info!("ProGuard usage: {}", stats);
-                println!(
-                    "{}",
-                    format!(
-                        "📋 ProGuard usage.txt: {} unused items ({} classes, {} methods)",
-                        stats.total, stats.classes, stats.methods
-                    )
-                    .cyan()
-                );
-                Some(data)
-            }
-            Err(e) => {
-                eprintln!("{}: Failed to load usage.txt: {}", "Warning".yellow(), e);
-                None
-            }
+    ctx.dead_code = comparison.dead_code;
+
+    if let Some(baseline_path) = &cli.generate_baseline {
+        match baseline::Baseline::from_findings(&ctx.dead_code, &ctx.root).save(baseline_path) {
+            Ok(_) => ctx.baseline_generated = Some(ctx.dead_code.len()),
+            Err(e) => ctx
+                .warnings
+                .push(format!("Failed to generate baseline: {}", e)),
         }
-    } else {
-        None
-    };
+    }
+    Ok(())
+}
+
+fn run_analysis(config: &Config, cli: &Cli) -> Result<()> {
+    use std::time::Instant;
 
-    // Step 5: Run reachability analysis (deep, enhanced, or standard)
-    info!("Running reachability analysis...");
+    let start_time = Instant::now();
 
-    let (dead_code, reachable) = if cli.deep {
-        // Deep analysis mode - most aggressive
+    if cli.deep {
         println!(
             "{}",
             "🔬 Deep mode: aggressive dead code detection...".cyan()
         );
-        let deep = DeepAnalyzer::new()
-            .with_parallel(cli.parallel)
-            .with_unused_members(true);
-        deep.analyze(&graph, &entry_points)
-    } else if cli.enhanced && proguard_data.is_some() {
-        // Enhanced mode with ProGuard cross-validation
+    } else if cli.enhanced && cli.proguard_usage.is_some() {
         println!(
             "{}",
             "🔍 Enhanced mode: cross-validating with ProGuard data...".cyan()
         );
-        let mut enhanced = EnhancedAnalyzer::new();
-        if let Some(pg) = proguard_data.clone() {
-            enhanced = enhanced.with_proguard(pg);
+    }
+    if cli.parallel {
+        println!("{}", "⚡ Parallel mode: parsing files...".cyan());
+    }
+    if cli.entry_set == EntrySetArg::Tests {
+        println!(
+            "{}",
+            "🧪 Test entry points: reporting code no test reaches...".cyan()
+        );
+    }
+
+    // Steps 1-15: Run the analysis pipeline
+    let mut session = AnalysisSession::new(config.clone())
+        .with_options(pipeline_options(cli))
+        .without_detectors(&cli.skip_detect)
+        .with_roots(project_roots(cli));
+    if cli.stream {
+        match stream_blocker(cli) {
+            None => {
+                session =
+                    session.with_finding_sink(|item| TerminalReporter::new().print_streamed(item));
+            }
+            Some(flag) => eprintln!(
+                "{}: --stream has no effect with {}; findings are reported at the end",
+                "Warning".yellow(),
+                flag
+            ),
         }
-        enhanced.analyze(&graph, &entry_points)
-    } else if cli.parallel {
-        // Standard analysis with parallel analyzer
-        let enhanced = EnhancedAnalyzer::new();
-        enhanced.analyze(&graph, &entry_points)
-    } else {
-        // Standard sequential analysis
-        let analyzer = ReachabilityAnalyzer::new();
-        analyzer.find_unreachable_with_reachable(&graph, &entry_points)
-    };
+    }
+    let mut ctx = session.run(&cli.path)?.into_context();
+    if cli.variants.len() > 1 {
+        compare_variants(&mut ctx, config, cli)?;
+    }
 
-    info!(
-        "Reachability: {} reachable, {} total",
-        reachable.len(),
-        graph.declarations().count()
-    );
+    if ctx.files.is_empty() {
+        println!("{}", "No Kotlin or Java files found.".yellow());
+        return Ok(());
+    }
 
-    // Step 6: Load coverage data if provided
-    let coverage_data = if !cli.coverage.is_empty() {
-        info!(
-            "Loading coverage data from {} file(s)...",
-            cli.coverage.len()
-        );
-        match parse_coverage_files(&cli.coverage) {
-            Ok(data) => {
-                let stats = data.stats();
-                info!(
-                    "Coverage: {} files, {} classes ({:.1}% covered), {} methods ({:.1}% covered)",
-                    stats.total_files,
-                    stats.total_classes,
-                    stats.class_coverage_percent(),
-                    stats.total_methods,
-                    stats.method_coverage_percent()
-                );
-                Some(data)
-            }
-            Err(e) => {
-                eprintln!("{}: Failed to load coverage: {}", "Warning".yellow(), e);
-                None
-            }
+    for warning in &ctx.warnings {
+        eprintln!("{}: {}", "Warning".yellow(), warning);
+    }
+    print_skipped_files(&ctx);
+    print_uncovered_modules(&ctx);
+
+    if cli.parallel {
+        if let Some(parse_time) = ctx.timing("parse") {
+            println!(
+                "{}",
+                format!(
+                    "⚡ Parsed {} files in {:.2}s",
+                    ctx.files.len(),
+                    parse_time.as_secs_f64()
+                )
+                .green()
+            );
         }
-    } else {
-        None
-    };
+    }
+
+    if let Some(ref proguard) = ctx.proguard {
+        let stats = proguard.stats();
+        println!(
+            "{}",
+            format!(
+                "📋 ProGuard usage.txt: {} unused items ({} classes, {} methods)",
+                stats.total, stats.classes, stats.methods
+            )
+            .cyan()
+        );
+    }
+
+    if let Some(ref seeds) = ctx.seeds {
+        println!(
+            "{}",
+            format!(
+                "🌱 R8 seeds.txt: {} kept items ({} never referenced in code)",
+                seeds.total_count,
+                ctx.kept_only.len()
+            )
+            .cyan()
+        );
+    }
 
-    // Step 7: Generate filtered report if requested
+    // Generate filtered report if requested
     if let Some(ref report_path) = cli.generate_report {
-        if let Some(ref proguard) = proguard_data {
+        if let Some(ref proguard) = ctx.proguard {
             info!("Generating filtered dead code report...");
             let generator = ReportGenerator::new().with_package_filter(cli.report_package.clone());
 
@@ -699,372 +1752,734 @@ fn run_analysis(config: &Config, cli: &Cli) -> Result<()> {
         }
     }
 
-    // Step 8: Enhance findings with hybrid analysis
-    let mut hybrid = HybridAnalyzer::new();
-    if let Some(coverage) = coverage_data {
-        hybrid = hybrid.with_coverage(coverage);
+    if let (Some(graph_path), Some(module_graph)) = (&cli.module_graph, &ctx.module_deps) {
+        let written = serde_json::to_string_pretty(module_graph)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(graph_path, json).map_err(|e| e.to_string()));
+        match written {
+            Ok(()) => println!(
+                "{}",
+                format!(
+                    "🕸️ Module graph written: {} ({} modules, {} dependencies)",
+                    graph_path.display(),
+                    module_graph.modules.len(),
+                    module_graph.dependencies.len()
+                )
+                .green()
+            ),
+            Err(e) => eprintln!("{}: Failed to write module graph: {}", "Error".red(), e),
+        }
     }
-    if let Some(proguard) = proguard_data.clone() {
-        hybrid = hybrid.with_proguard(proguard);
+
+    if !cli.quiet {
+        print_source_analyses(&ctx, &cli.path);
     }
 
-    let mut dead_code = hybrid.enhance_findings(dead_code);
+    print_cycles(&ctx);
+    print_deletion_units(&ctx);
+    print_kept_only(&ctx, &cli.path);
+    print_entry_set_diff(&ctx, &cli.path);
 
-    // Step 9: Find runtime-dead code (reachable but never executed)
-    if cli.include_runtime_dead {
-        let runtime_dead = hybrid.find_runtime_dead_code(&graph, &reachable);
-        if !runtime_dead.is_empty() {
-            info!(
-                "Found {} additional runtime-dead code items",
-                runtime_dead.len()
-            );
-            dead_code.extend(runtime_dead);
-        }
+    if let (Some(baseline_path), Some(count)) = (&cli.generate_baseline, ctx.baseline_generated) {
+        println!(
+            "{}",
+            format!(
+                "📋 Baseline generated: {} ({} issues)",
+                baseline_path.display(),
+                count
+            )
+            .green()
+        );
     }
 
-    // Step 9b: Detect unused parameters
-    if cli.unused_params {
-        let param_detector = UnusedParamDetector::new();
-        let unused_params = param_detector.detect(&graph);
-        if !unused_params.is_empty() {
-            info!("Found {} unused parameters", unused_params.len());
-            dead_code.extend(unused_params);
+    if let Some(ref stats) = ctx.baseline_stats {
+        println!("{}", format!("📋 Baseline: {}", stats).cyan());
+        if ctx.dead_code.is_empty() && stats.baselined_found > 0 {
+            println!("{}", "✓ No new dead code issues found!".green());
         }
     }
 
-    // Step 9c: Detect write-only variables (Phase 9)
-    if cli.write_only {
-        let write_only_detector = WriteOnlyDetector::new();
-        let write_only_vars = write_only_detector.detect(&graph);
-        if !write_only_vars.is_empty() {
-            info!("Found {} write-only variables", write_only_vars.len());
-            dead_code.extend(write_only_vars);
-        }
+    let downgraded = ctx
+        .dead_code
+        .iter()
+        .filter(|dc| dc.suppressed_by.is_some())
+        .count();
+    if !ctx.suppressed.is_empty() || downgraded > 0 {
+        println!(
+            "{}",
+            format!(
+                "🔇 Suppressed inline: {} excluded, {} downgraded",
+                ctx.suppressed.len(),
+                downgraded
+            )
+            .cyan()
+        );
     }
 
-    // Step 9d: Detect unused sealed variants (Phase 10)
-    if cli.sealed_variants {
-        let sealed_detector = UnusedSealedVariantDetector::new();
-        let sealed_issues = sealed_detector.detect(&graph);
-        if !sealed_issues.is_empty() {
-            info!("Found {} unused sealed variants", sealed_issues.len());
-            dead_code.extend(sealed_issues);
-        }
+    // Step 17: Report results
+    let summary = cli.summary.clone().or_else(|| {
+        matches!(cli.format, OutputFormat::GithubActions)
+            .then(|| std::env::var_os("GITHUB_STEP_SUMMARY"))
+            .flatten()
+            .map(PathBuf::from)
+    });
+    let reporter = Reporter::new(cli.format.clone().into(), cli.output.clone())
+        .with_group_by(cli.group_by.into())
+        .with_sort_by(cli.sort_by.into())
+        .with_summary_only(cli.summary_only)
+        .with_explain(cli.explain)
+        .with_output_validation(cli.validate_output)
+        .with_summary(summary)
+        .with_project_root(ctx.root.clone())
+        .with_baselined(ctx.baseline_stats.is_some().then(|| ctx.baselined.clone()))
+        .with_suppressed(ctx.suppressed.clone())
+        .with_streamed(std::mem::take(&mut ctx.streamed))
+        .with_timings(cli.timings.then(|| TimingsReport::from_context(&ctx)))
+        .with_skipped_files(ctx.skipped_files.clone());
+    reporter.report(&ctx.dead_code)?;
+
+    if cli.timings {
+        eprintln!("\n{}", "⏱️  Timings:".bold());
+        eprint!("{}", TimingsReport::from_context(&ctx).to_table());
+    }
+
+    if let Some(path) = &cli.output_db {
+        SqliteExporter::new(path.clone())
+            .with_project_root(ctx.root.clone())
+            .export(
+                &ctx.graph,
+                &ctx.entry_points,
+                &ctx.entry_point_categories,
+                &ctx.dead_code,
+            )?;
+    }
+
+    if let Some(path) = &cli.manifest {
+        AnalysisManifest::from_context(&ctx)
+            .with_cache(cache_usage(&ctx, cli))
+            .write(path)?;
+        info!("Manifest written to {}", path.display());
     }
 
-    // Step 9e: Detect redundant overrides (Phase 10)
-    if cli.redundant_overrides {
-        let override_detector = RedundantOverrideDetector::new();
-        let override_issues = override_detector.detect(&graph);
-        if !override_issues.is_empty() {
-            info!("Found {} redundant overrides", override_issues.len());
-            dead_code.extend(override_issues);
+    // Print timing
+    let elapsed = start_time.elapsed();
+    info!("Analysis completed in {:.2}s", elapsed.as_secs_f64());
+
+    // Step 18: Safe delete or fix if requested (never while watching)
+    let edits_requested = cli.delete
+        || cli.patch.is_some()
+        || cli.fix_visibility
+        || cli.fix_mutability
+        || cli.deprecate;
+    if edits_requested && !cli.watch && !ctx.dead_code.is_empty() {
+        let fixes = refactor::plan_fixes(&ctx.dead_code, &ctx.graph);
+        let mut deleter =
+            refactor::SafeDeleter::new(cli.interactive, cli.dry_run, cli.undo_script.clone())
+                .with_fixes(fixes);
+        if let Some(patch) = &cli.patch {
+            deleter = deleter.with_patch(patch.clone(), cli.path.clone());
+        }
+        if let Some(journal) = &cli.journal {
+            deleter = deleter.with_journal(journal.clone());
+        }
+        if cli.cluster {
+            deleter = deleter.with_units(ctx.deletion_units.clone());
+        }
+        if let Some(age) = cli.deprecated_for {
+            deleter = deleter.with_deprecation_age(age);
+        }
+
+        if cli.deprecate {
+            deleter.deprecate(&ctx.dead_code, cli.deprecation_level.into())?;
+        } else if cli.fix_visibility || cli.fix_mutability {
+            let suggestions: Vec<_> = ctx
+                .dead_code
+                .iter()
+                .filter(|dc| match dc.issue {
+                    DeadCodeIssue::RedundantPublic => cli.fix_visibility,
+                    DeadCodeIssue::VarCouldBeVal => cli.fix_mutability,
+                    _ => false,
+                })
+                .cloned()
+                .collect();
+            deleter.fix(&suggestions)?;
+        } else {
+            deleter.delete(&ctx.dead_code)?;
         }
     }
 
-    // Step 9f: Detect unused Android resources
-    if cli.unused_resources {
-        let resource_detector = ResourceDetector::new();
-        let resource_analysis = resource_detector.analyze(&cli.path);
+    Ok(())
+}
+
+/// Why per-file findings can't be printed while parsing, if they can't:
+/// they only go to the terminal report, and only when no later stage filters
+/// findings by something a single file cannot tell. Confidence is final only
+/// after the configured overrides and the reflection and suppression
+/// downgrades.
+fn stream_blocker(cli: &Cli) -> Option<&'static str> {
+    let blockers = [
+        (!matches!(cli.format, OutputFormat::Terminal), "--format"),
+        (cli.summary_only, "--summary-only"),
+        (cli.output.is_some(), "--output"),
+        (cli.baseline.is_some(), "--baseline"),
+        (cli.diff_base.is_some(), "--diff-base"),
+        (!cli.only_packages.is_empty(), "--only-package"),
+        (cli.runtime_only, "--runtime-only"),
+        (!cli.exclude_risk.is_empty(), "--exclude-risk"),
+        (
+            parse_confidence(&cli.min_confidence) != Confidence::Low,
+            "--min-confidence",
+        ),
+        (cli.variants.len() > 1, "more than one --variant"),
+    ];
+    blockers
+        .into_iter()
+        .find_map(|(blocks, flag)| blocks.then_some(flag))
+}
+
+/// Print results of the source-based analyses (they're not part of the code graph)
+fn print_source_analyses(ctx: &PipelineContext, root: &std::path::Path) {
+    if let Some(ref resource_analysis) = ctx.resources {
         if !resource_analysis.unused.is_empty() {
-            info!(
-                "Found {} unused resources ({} total defined, {} referenced)",
-                resource_analysis.unused.len(),
-                resource_analysis
-                    .defined
-                    .values()
-                    .map(|m| m.len())
-                    .sum::<usize>(),
-                resource_analysis.referenced.len()
-            );
-            // Print unused resources directly (they're not part of the code graph)
-            if !cli.quiet {
-                use colored::Colorize;
-                println!();
-                println!("{}", "📦 Unused Android Resources:".yellow().bold());
-                for resource in &resource_analysis.unused {
-                    let rel_path = resource
-                        .file
-                        .strip_prefix(&cli.path)
-                        .unwrap_or(&resource.file);
-                    println!(
-                        "  {} {}:{} - {} '{}'",
-                        "○".dimmed(),
-                        rel_path.display(),
-                        resource.line,
-                        resource.resource_type,
-                        resource.name
-                    );
+            println!();
+            println!("{}", "📦 Unused Android Resources:".yellow().bold());
+            for resource in &resource_analysis.unused {
+                let rel_path = resource.file.strip_prefix(root).unwrap_or(&resource.file);
+                let variant = resource
+                    .variant
+                    .label()
+                    .map(|label| format!(" [{}]", label))
+                    .unwrap_or_default();
+                let overlays = match resource.overlays.len() {
+                    0 => String::new(),
+                    1 => " (+1 overlay)".to_string(),
+                    n => format!(" (+{} overlays)", n),
+                };
+                let mut notes = Vec::new();
+                if let Some(size) = resource.total_size() {
+                    notes.push(format!("{:.1} KB", size as f64 / 1024.0));
+                }
+                if resource.is_locale_only() {
+                    notes.push("locale only, no default definition".to_string());
+                }
+                match resource.resource_type.as_str() {
+                    "style" => notes.push("never applied".to_string()),
+                    "attr" => notes.push("never read".to_string()),
+                    _ => {}
                 }
-                println!();
+                let referrers = resource_analysis.unused_referrers(resource);
+                if !referrers.is_empty() {
+                    let names: Vec<String> = referrers
+                        .iter()
+                        .map(|(res_type, name)| format!("{}/{}", res_type, name))
+                        .collect();
+                    notes.push(format!("only used by unused {}", names.join(", ")));
+                }
+                let notes = if notes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", notes.join("; "))
+                };
+                println!(
+                    "  {} {}:{} - {} '{}'{}{}{}",
+                    "○".dimmed(),
+                    rel_path.display(),
+                    resource.line,
+                    resource.resource_type,
+                    resource.name,
+                    variant.dimmed(),
+                    overlays.dimmed(),
+                    notes.dimmed()
+                );
             }
+            let total: u64 = resource_analysis
+                .unused
+                .iter()
+                .filter_map(|resource| resource.total_size())
+                .sum();
+            if total > 0 {
+                println!(
+                    "  Deleting the unused files saves ~{:.1} KB of APK size",
+                    total as f64 / 1024.0
+                );
+            }
+            println!();
         }
     }
 
-    // Step 9g: Detect unused Intent extras (Phase 11)
-    if cli.unused_extras {
-        let intent_detector = UnusedIntentExtraDetector::new();
-        let intent_analysis = intent_detector.analyze(&cli.path);
+    if let Some(ref intent_analysis) = ctx.intent_extras {
         if !intent_analysis.unused_extras.is_empty() {
-            info!(
-                "Found {} unused Intent extras ({} total put, {} retrieved)",
-                intent_analysis.unused_extras.len(),
-                intent_analysis.total_put,
-                intent_analysis.total_get
-            );
-            // Print unused extras directly
-            if !cli.quiet {
-                use colored::Colorize;
-                println!();
-                println!("{}", "🔑 Unused Intent Extras:".yellow().bold());
-                for extra in &intent_analysis.unused_extras {
-                    let rel_path = extra.file.strip_prefix(&cli.path).unwrap_or(&extra.file);
-                    println!(
-                        "  {} {}:{} - putExtra(\"{}\") never retrieved",
-                        "○".dimmed(),
-                        rel_path.display(),
-                        extra.line,
-                        extra.key
-                    );
-                }
-                println!();
+            println!();
+            println!("{}", "🔑 Unused Intent Extras:".yellow().bold());
+            for extra in &intent_analysis.unused_extras {
+                let rel_path = extra.file.strip_prefix(root).unwrap_or(&extra.file);
+                println!(
+                    "  {} {}:{} - putExtra(\"{}\") never retrieved",
+                    "○".dimmed(),
+                    rel_path.display(),
+                    extra.line,
+                    extra.key
+                );
             }
+            println!();
         }
     }
 
-    // Step 9h: Detect write-only SharedPreferences (Phase 9)
-    if cli.write_only_prefs {
-        use analysis::detectors::WriteOnlyPrefsDetector;
-        use discovery::FileType;
-        let prefs_detector = WriteOnlyPrefsDetector::new();
-
-        // Analyze all Kotlin files for SharedPreferences usage
-        let mut prefs_analysis = analysis::detectors::SharedPrefsAnalysis::new();
-        for file in &files {
-            if file.file_type == FileType::Kotlin {
-                if let Ok(content) = std::fs::read_to_string(&file.path) {
-                    let file_analysis = prefs_detector.analyze_source(&content, &file.path);
-                    // Merge results
-                    for (key, locs) in file_analysis.writes {
-                        for loc in locs {
-                            prefs_analysis.add_write(key.clone(), loc.file, loc.line);
-                        }
-                    }
-                    for (key, locs) in file_analysis.reads {
-                        for loc in locs {
-                            prefs_analysis.add_read(key.clone(), loc.file, loc.line);
-                        }
-                    }
-                }
+    if let Some(ref bundle_analysis) = ctx.bundle_args {
+        if !bundle_analysis.unread.is_empty() {
+            println!();
+            println!("{}", "🧳 Unused Bundle Arguments:".yellow().bold());
+            for arg in &bundle_analysis.unread {
+                let rel_path = arg.file.strip_prefix(root).unwrap_or(&arg.file);
+                println!(
+                    "  {} {}:{} - argument \"{}\" set but never read",
+                    "○".dimmed(),
+                    rel_path.display(),
+                    arg.line,
+                    arg.key
+                );
+            }
+            println!();
+        }
+    }
+
+    if let Some(ref broadcast_analysis) = ctx.broadcasts {
+        if !broadcast_analysis.unreceived.is_empty() || !broadcast_analysis.unsent.is_empty() {
+            println!();
+            println!("{}", "📡 Unmatched Broadcast Actions:".yellow().bold());
+            let unmatched = broadcast_analysis
+                .unreceived
+                .iter()
+                .map(|action| (action, "broadcast but never received"))
+                .chain(
+                    broadcast_analysis
+                        .unsent
+                        .iter()
+                        .map(|action| (action, "received but never broadcast")),
+                );
+            for (action, problem) in unmatched {
+                let rel_path = action.file.strip_prefix(root).unwrap_or(&action.file);
+                println!(
+                    "  {} {}:{} - action \"{}\" {}",
+                    "○".dimmed(),
+                    rel_path.display(),
+                    action.line,
+                    action.action,
+                    problem
+                );
             }
+            println!();
         }
+    }
 
+    if let Some(ref prefs_analysis) = ctx.shared_prefs {
         let write_only_keys = prefs_analysis.get_write_only_keys();
         if !write_only_keys.is_empty() {
-            info!(
-                "Found {} write-only SharedPreferences keys",
-                write_only_keys.len()
-            );
-            if !cli.quiet {
-                use colored::Colorize;
-                println!();
-                println!("{}", "🔐 Write-Only SharedPreferences:".yellow().bold());
-                for key in write_only_keys {
-                    if let Some(locs) = prefs_analysis.writes.get(key) {
-                        for loc in locs {
-                            let rel_path = loc.file.strip_prefix(&cli.path).unwrap_or(&loc.file);
-                            println!(
-                                "  {} {}:{} - key \"{}\" written but never read",
-                                "○".dimmed(),
-                                rel_path.display(),
-                                loc.line,
-                                key
-                            );
-                        }
+            println!();
+            println!("{}", "🔐 Write-Only SharedPreferences:".yellow().bold());
+            for key in write_only_keys {
+                if let Some(locs) = prefs_analysis.writes.get(key) {
+                    for loc in locs {
+                        let rel_path = loc.file.strip_prefix(root).unwrap_or(&loc.file);
+                        println!(
+                            "  {} {}:{} - key \"{}\" written but never read",
+                            "○".dimmed(),
+                            rel_path.display(),
+                            loc.line,
+                            key
+                        );
                     }
                 }
-                println!();
             }
+            println!();
         }
     }
 
-    // Step 9i: Detect write-only Room DAOs (Phase 9)
-    if cli.write_only_dao {
-        use analysis::detectors::WriteOnlyDaoDetector;
-        use discovery::FileType;
-        let dao_detector = WriteOnlyDaoDetector::new();
-
-        // Analyze all Kotlin files for DAO definitions
-        let mut dao_analysis = analysis::detectors::DaoCollectionAnalysis::new();
-        for file in &files {
-            if file.file_type == FileType::Kotlin {
-                if let Ok(content) = std::fs::read_to_string(&file.path) {
-                    let file_analysis = dao_detector.analyze_source(&content, &file.path);
-                    dao_analysis.daos.extend(file_analysis.daos);
-                }
+    if let Some(ref pref_key_analysis) = ctx.pref_keys {
+        if !pref_key_analysis.unread.is_empty() || !pref_key_analysis.unused_definitions.is_empty()
+        {
+            println!();
+            println!("{}", "🔑 Unused Preference Keys:".yellow().bold());
+            let unused = pref_key_analysis
+                .unread
+                .iter()
+                .map(|key| (key, "written but never read"))
+                .chain(
+                    pref_key_analysis
+                        .unused_definitions
+                        .iter()
+                        .map(|key| (key, "defined but never used")),
+                );
+            for (key, problem) in unused {
+                let rel_path = key.file.strip_prefix(root).unwrap_or(&key.file);
+                println!(
+                    "  {} {}:{} - key \"{}\" {}",
+                    "○".dimmed(),
+                    rel_path.display(),
+                    key.line,
+                    key.key,
+                    problem
+                );
             }
+            println!();
         }
+    }
 
+    if let Some(ref dao_analysis) = ctx.daos {
         let write_only_daos = dao_analysis.get_write_only_daos();
         if !write_only_daos.is_empty() {
-            info!("Found {} write-only Room DAOs", write_only_daos.len());
-            if !cli.quiet {
-                use colored::Colorize;
-                println!();
-                println!("{}", "🗄️ Write-Only Room DAOs:".yellow().bold());
-                for dao in write_only_daos {
-                    let rel_path = dao.file.strip_prefix(&cli.path).unwrap_or(&dao.file);
+            println!();
+            println!("{}", "🗄️ Write-Only Room DAOs:".yellow().bold());
+            for dao in write_only_daos {
+                let rel_path = dao.file.strip_prefix(root).unwrap_or(&dao.file);
+                println!(
+                    "  {} {}:{} - DAO '{}' has @Insert but no @Query",
+                    "○".dimmed(),
+                    rel_path.display(),
+                    dao.line,
+                    dao.name
+                );
+                for method in dao.write_methods() {
+                    let entity_info = method
+                        .entity_type
+                        .as_ref()
+                        .map(|e| format!(" ({})", e))
+                        .unwrap_or_default();
                     println!(
-                        "  {} {}:{} - DAO '{}' has @Insert but no @Query",
-                        "○".dimmed(),
-                        rel_path.display(),
-                        dao.line,
-                        dao.name
+                        "    {} {}{}",
+                        "└".dimmed(),
+                        method.name,
+                        entity_info.dimmed()
                     );
-                    for method in dao.write_methods() {
-                        let entity_info = method
-                            .entity_type
-                            .as_ref()
-                            .map(|e| format!(" ({})", e))
-                            .unwrap_or_default();
-                        println!(
-                            "    {} {}{}",
-                            "└".dimmed(),
-                            method.name,
-                            entity_info.dimmed()
-                        );
-                    }
                 }
-                println!();
             }
+            println!();
         }
     }
 
-    // Step 10: Filter by confidence level
-    let min_confidence = parse_confidence(&cli.min_confidence);
-    let dead_code: Vec<_> = dead_code
-        .into_iter()
-        .filter(|dc| dc.confidence >= min_confidence)
-        .filter(|dc| !cli.runtime_only || dc.runtime_confirmed)
-        .collect();
-
-    info!("Found {} dead code candidates", dead_code.len());
-
-    // Step 11: Detect zombie code cycles if requested
-    if cli.detect_cycles {
-        let cycle_detector = CycleDetector::new();
-        let cycle_stats = cycle_detector.get_cycle_stats(&graph, &reachable);
+    if let Some(ref module_graph) = ctx.module_deps {
+        let unused = module_graph.unused();
+        if !unused.is_empty() {
+            println!();
+            println!("{}", "📦 Unused Module Dependencies:".yellow().bold());
+            for dep in unused {
+                let rel_path = dep.file.strip_prefix(root).unwrap_or(&dep.file);
+                println!(
+                    "  {} {}:{} - {} '{}' is never imported from '{}'",
+                    "○".dimmed(),
+                    rel_path.display(),
+                    dep.line,
+                    dep.configuration,
+                    dep.to,
+                    dep.from
+                );
+            }
+            println!();
+        }
+    }
 
-        if cycle_stats.has_cycles() {
+    if let Some(ref nav_analysis) = ctx.navigation {
+        if !nav_analysis.unused_arguments.is_empty()
+            || !nav_analysis.unused_actions.is_empty()
+            || !nav_analysis.unreachable_destinations.is_empty()
+        {
             println!();
-            println!("{}", "🧟 Zombie Code Detected:".to_string().yellow().bold());
             println!(
-                "  {} dead cycles found ({} declarations)",
-                cycle_stats.num_dead_cycles, cycle_stats.total_declarations_in_cycles
+                "{}",
+                "🧭 Unused Navigation Arguments, Actions & Destinations:"
+                    .yellow()
+                    .bold()
             );
-            if cycle_stats.largest_cycle_size > 2 {
+            for argument in &nav_analysis.unused_arguments {
+                let rel_path = argument.file.strip_prefix(root).unwrap_or(&argument.file);
                 println!(
-                    "  Largest cycle: {} mutually dependent declarations",
-                    cycle_stats.largest_cycle_size
+                    "  {} {}:{} - argument \"{}\" of '{}' never read",
+                    "○".dimmed(),
+                    rel_path.display(),
+                    argument.line,
+                    argument.name,
+                    argument.destination_id
                 );
             }
-            if cycle_stats.num_zombie_pairs > 0 {
+            for action in &nav_analysis.unused_actions {
+                let rel_path = action.file.strip_prefix(root).unwrap_or(&action.file);
+                let target = action
+                    .destination
+                    .as_ref()
+                    .map(|destination| format!(" to '{}'", destination))
+                    .unwrap_or_default();
                 println!(
-                    "  {} zombie pairs (A↔B mutual references)",
-                    cycle_stats.num_zombie_pairs
+                    "  {} {}:{} - action '{}'{} never navigated",
+                    "○".dimmed(),
+                    rel_path.display(),
+                    action.line,
+                    action.id,
+                    target
                 );
             }
-
-            // Print cycle details
-            let dead_cycles = cycle_detector.find_dead_cycles(&graph, &reachable);
-            for (i, cycle) in dead_cycles.iter().take(5).enumerate() {
-                println!();
+            for destination in &nav_analysis.unreachable_destinations {
+                let rel_path = destination
+                    .file
+                    .strip_prefix(root)
+                    .unwrap_or(&destination.file);
                 println!(
-                    "  {}",
-                    format!("Cycle #{} ({} items):", i + 1, cycle.size).dimmed()
+                    "  {} {}:{} - {} '{}' never navigated to",
+                    "○".dimmed(),
+                    rel_path.display(),
+                    destination.line,
+                    destination.kind,
+                    destination.id
                 );
-                for name in cycle.names.iter().take(5) {
-                    println!("    • {}", name);
-                }
-                if cycle.names.len() > 5 {
-                    println!("    ... and {} more", cycle.names.len() - 5);
-                }
-            }
-            if dead_cycles.len() > 5 {
-                println!();
-                println!("  ... and {} more cycles", dead_cycles.len() - 5);
             }
             println!();
         }
     }
 
-    // Step 12: Generate baseline if requested
-    if let Some(ref baseline_path) = cli.generate_baseline {
-        info!("Generating baseline file...");
-        let baseline = baseline::Baseline::from_findings(&dead_code, &cli.path);
-        match baseline.save(baseline_path) {
-            Ok(_) => {
+    if let Some(ref link_analysis) = ctx.deep_links {
+        if !link_analysis.unopened_routes.is_empty()
+            || !link_analysis.unhandled_links.is_empty()
+            || !link_analysis.deep_link_only.is_empty()
+        {
+            println!();
+            println!("{}", "🔗 Deep Links:".yellow().bold());
+            for route in &link_analysis.deep_link_only {
+                let rel_path = route.file.strip_prefix(root).unwrap_or(&route.file);
+                println!(
+                    "  {} {}:{} - '{}' is only reachable through deep link \"{}\"",
+                    "○".dimmed(),
+                    rel_path.display(),
+                    route.line,
+                    route.handler,
+                    route.uri
+                );
+            }
+            for route in &link_analysis.unopened_routes {
+                let rel_path = route.file.strip_prefix(root).unwrap_or(&route.file);
                 println!(
-                    "{}",
-                    format!(
-                        "📋 Baseline generated: {} ({} issues)",
-                        baseline_path.display(),
-                        dead_code.len()
-                    )
-                    .green()
+                    "  {} {}:{} - {} \"{}\" of '{}' never opened by the app or its tests",
+                    "○".dimmed(),
+                    rel_path.display(),
+                    route.line,
+                    if route.is_app_link() {
+                        "app link"
+                    } else {
+                        "deep link"
+                    },
+                    route.uri,
+                    route.handler
                 );
             }
-            Err(e) => {
-                eprintln!("{}: Failed to generate baseline: {}", "Error".red(), e);
+            for link in &link_analysis.unhandled_links {
+                let rel_path = link.file.strip_prefix(root).unwrap_or(&link.file);
+                println!(
+                    "  {} {}:{} - \"{}\" opened but no deep link handles it",
+                    "○".dimmed(),
+                    rel_path.display(),
+                    link.line,
+                    link.uri
+                );
             }
+            println!();
         }
     }
+}
 
-    // Step 13: Filter by baseline if provided
-    let dead_code = if let Some(ref baseline_path) = cli.baseline {
-        match baseline::Baseline::load(baseline_path) {
-            Ok(baseline) => {
-                let stats = baseline.stats(&dead_code, &cli.path);
-                println!("{}", format!("📋 Baseline: {}", stats).cyan());
+/// Print zombie code cycles found by the cycle stage
+fn print_cycles(ctx: &PipelineContext) {
+    let Some(ref cycle_stats) = ctx.cycle_stats else {
+        return;
+    };
+    if !cycle_stats.has_cycles() {
+        return;
+    }
 
-                // Only report new issues not in baseline
-                let new_issues: Vec<_> = baseline
-                    .filter_new(&dead_code, &cli.path)
-                    .into_iter()
-                    .cloned()
-                    .collect();
+    println!();
+    println!("{}", "🧟 Zombie Code Detected:".to_string().yellow().bold());
+    println!(
+        "  {} dead cycles found ({} declarations)",
+        cycle_stats.num_dead_cycles, cycle_stats.total_declarations_in_cycles
+    );
+    if cycle_stats.largest_cycle_size > 2 {
+        println!(
+            "  Largest cycle: {} mutually dependent declarations",
+            cycle_stats.largest_cycle_size
+        );
+    }
+    if cycle_stats.num_zombie_pairs > 0 {
+        println!(
+            "  {} zombie pairs (A↔B mutual references)",
+            cycle_stats.num_zombie_pairs
+        );
+    }
 
-                if new_issues.is_empty() && stats.baselined_found > 0 {
-                    println!("{}", "✓ No new dead code issues found!".green());
-                }
+    // Print cycle details
+    for (i, cycle) in ctx.dead_cycles.iter().take(5).enumerate() {
+        println!();
+        println!(
+            "  {}",
+            format!("Cycle #{} ({} items):", i + 1, cycle.size).dimmed()
+        );
+        for name in cycle.names.iter().take(5) {
+            println!("    • {}", name);
+        }
+        if cycle.names.len() > 5 {
+            println!("    ... and {} more", cycle.names.len() - 5);
+        }
+    }
+    if ctx.dead_cycles.len() > 5 {
+        println!();
+        println!("  ... and {} more cycles", ctx.dead_cycles.len() - 5);
+    }
+    println!();
+}
 
-                new_issues
-            }
-            Err(e) => {
-                eprintln!("{}: Failed to load baseline: {}", "Warning".yellow(), e);
-                dead_code
-            }
+/// Seeds never referenced in code, grouped by the keep rule retaining them
+fn print_kept_only(ctx: &PipelineContext, root: &std::path::Path) {
+    if ctx.kept_only.is_empty() {
+        return;
+    }
+
+    let mut by_rule: std::collections::BTreeMap<&str, Vec<&analysis::DeadCode>> =
+        std::collections::BTreeMap::new();
+    for dc in &ctx.kept_only {
+        let rule = dc.kept_by.as_deref().unwrap_or("seeds.txt");
+        by_rule.entry(rule).or_default().push(dc);
+    }
+    let mut rules: Vec<_> = by_rule.into_iter().collect();
+    rules.sort_by_key(|(_, findings)| std::cmp::Reverse(findings.len()));
+
+    println!();
+    println!(
+        "{}",
+        "🔒 Kept Only by Keep Rules:".to_string().yellow().bold()
+    );
+    println!(
+        "  {} seeds are never referenced in code; {} keep rules could be narrowed",
+        ctx.kept_only.len(),
+        rules.len()
+    );
+
+    for (rule, kept) in rules.iter().take(10) {
+        println!();
+        println!(
+            "  {} {}",
+            rule.white().bold(),
+            format!("({} declarations)", kept.len()).dimmed()
+        );
+        for dc in kept.iter().take(5) {
+            let decl = &dc.declaration;
+            let rel_path = decl
+                .location
+                .file
+                .strip_prefix(root)
+                .unwrap_or(&decl.location.file);
+            println!(
+                "    • {} {} {}",
+                decl.kind.display_name(),
+                decl.name,
+                format!("{}:{}", rel_path.display(), decl.location.line).dimmed()
+            );
         }
-    } else {
-        dead_code
+        if kept.len() > 5 {
+            println!("    ... and {} more", kept.len() - 5);
+        }
+    }
+}
+
+fn print_entry_set_diff(ctx: &PipelineContext, root: &std::path::Path) {
+    let Some(diff) = &ctx.entry_set_diff else {
+        return;
     };
 
-    // Step 14: Report results
-    let reporter = Reporter::new(cli.format.clone().into(), cli.output.clone());
-    reporter.report(&dead_code)?;
+    println!();
+    println!(
+        "{}",
+        "🧪 App vs Tests Reachability:".to_string().cyan().bold()
+    );
+    println!(
+        "  {} declarations are reachable from the app but no test, {} only from tests",
+        diff.untested.len(),
+        diff.test_only.len()
+    );
 
-    // Print timing
-    let elapsed = start_time.elapsed();
-    info!("Analysis completed in {:.2}s", elapsed.as_secs_f64());
+    for (title, declarations) in [
+        ("Untested", &diff.untested),
+        ("Only reached from tests", &diff.test_only),
+    ] {
+        if declarations.is_empty() {
+            continue;
+        }
+        println!();
+        println!(
+            "  {} {}",
+            title.white().bold(),
+            format!("({} declarations)", declarations.len()).dimmed()
+        );
+        for decl in declarations.iter().take(20) {
+            let rel_path = decl
+                .location
+                .file
+                .strip_prefix(root)
+                .unwrap_or(&decl.location.file);
+            println!(
+                "    • {} {} {}",
+                decl.kind.display_name(),
+                decl.name,
+                format!("{}:{}", rel_path.display(), decl.location.line).dimmed()
+            );
+        }
+        if declarations.len() > 20 {
+            println!("    ... and {} more", declarations.len() - 20);
+        }
+    }
+}
 
-    // Step 15: Safe delete if requested
-    if cli.delete && !dead_code.is_empty() {
-        let deleter =
-            refactor::SafeDeleter::new(cli.interactive, cli.dry_run, cli.undo_script.clone());
-        deleter.delete(&dead_code)?;
+fn print_deletion_units(ctx: &PipelineContext) {
+    let units: Vec<_> = ctx
+        .deletion_units
+        .iter()
+        .filter(|unit| !unit.dependents.is_empty())
+        .collect();
+    if units.is_empty() {
+        return;
     }
 
-    Ok(())
+    println!();
+    println!("{}", "🧩 Deletion Units:".to_string().cyan().bold());
+    println!(
+        "  {} declarations can be deleted together with {} dependents",
+        units.len(),
+        units
+            .iter()
+            .map(|unit| unit.dependents.len())
+            .sum::<usize>()
+    );
+
+    for unit in units.iter().take(10) {
+        let Some(root) = ctx.graph.get_declaration(&unit.root) else {
+            continue;
+        };
+        println!();
+        println!(
+            "  {} {} {}",
+            root.name.white().bold(),
+            format!("{}:{}", root.location.file.display(), root.location.line).dimmed(),
+            format!("(+{} dependents)", unit.dependents.len()).dimmed()
+        );
+        for id in unit.dependents.iter().take(5) {
+            if let Some(decl) = ctx.graph.get_declaration(id) {
+                println!("    • {} {}", decl.kind.display_name(), decl.name);
+            }
+        }
+        if unit.dependents.len() > 5 {
+            println!("    ... and {} more", unit.dependents.len() - 5);
+        }
+    }
+    if units.len() > 10 {
+        println!();
+        println!("  ... and {} more units", units.len() - 10);
+    }
+    println!();
 }
 
 fn parse_confidence(s: &str) -> Confidence {
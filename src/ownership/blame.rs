@@ -0,0 +1,93 @@
+//! `git blame` based attribution
+//!
+//! Used when no CODEOWNERS rule covers a file: the author of the declaration
+//! line is the most likely person to know whether it can go.
+
+use std::path::Path;
+use std::process::Command;
+
+/// Line authors of a file, as reported by `git blame`
+#[derive(Debug, Clone, Default)]
+pub struct GitBlame {
+    /// Author of each line (index 0 is line 1)
+    authors: Vec<String>,
+}
+
+impl GitBlame {
+    /// Blame a file, returning None when git is unavailable or the file is untracked
+    pub fn for_file(repo_root: &Path, file: &Path) -> Option<Self> {
+        let output = Command::new("git")
+            .arg("blame")
+            .arg("--line-porcelain")
+            .arg("--")
+            .arg(file)
+            .current_dir(repo_root)
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        Some(Self::parse_porcelain(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// Parse `git blame --line-porcelain` output
+    pub fn parse_porcelain(output: &str) -> Self {
+        let mut authors = Vec::new();
+        let mut current_author = String::new();
+
+        for line in output.lines() {
+            if let Some(author) = line.strip_prefix("author ") {
+                current_author = author.to_string();
+            } else if line.starts_with('\t') {
+                // The content line closes each entry
+                authors.push(std::mem::take(&mut current_author));
+            }
+        }
+
+        Self { authors }
+    }
+
+    /// Author of a 1-based line
+    pub fn author_of(&self, line: usize) -> Option<&str> {
+        line.checked_sub(1)
+            .and_then(|index| self.authors.get(index))
+            .map(|author| author.as_str())
+            .filter(|author| !author.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line_porcelain() {
+        let output = "\
+4b825dc642cb6eb9a060e54bf8d69288fbee4904 1 1 2
+author Alice
+author-mail <alice@example.com>
+summary Add feature
+filename Feature.kt
+\tclass Feature {
+4b825dc642cb6eb9a060e54bf8d69288fbee4904 2 2
+author Alice
+author-mail <alice@example.com>
+filename Feature.kt
+\t}
+0000000000000000000000000000000000000000 3 3 1
+author Not Committed Yet
+filename Feature.kt
+\tfun draft() {}
+";
+
+        let blame = GitBlame::parse_porcelain(output);
+        assert_eq!(blame.author_of(1), Some("Alice"));
+        assert_eq!(blame.author_of(3), Some("Not Committed Yet"));
+        assert_eq!(blame.author_of(0), None);
+        assert_eq!(blame.author_of(4), None);
+    }
+}
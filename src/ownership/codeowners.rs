@@ -0,0 +1,211 @@
+//! CODEOWNERS file parsing and matching
+//!
+//! Follows the GitHub/GitLab semantics: patterns use gitignore syntax and the
+//! last matching rule wins. A rule without owners explicitly leaves a path
+//! unowned.
+
+use crate::discovery::NormalizedPath;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Locations searched for a CODEOWNERS file, in order
+pub const CODEOWNERS_LOCATIONS: &[&str] = &[
+    ".github/CODEOWNERS",
+    "CODEOWNERS",
+    "docs/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+];
+
+/// A single `pattern @owner ...` line
+#[derive(Debug, Clone)]
+pub struct OwnerRule {
+    /// Pattern as written in the file
+    pub pattern: String,
+    /// Owners (users, teams or emails); empty when the path is unowned
+    pub owners: Vec<String>,
+    regex: Regex,
+}
+
+impl OwnerRule {
+    /// Check if a root-relative path (using `/` separators) matches this rule
+    pub fn matches(&self, relative_path: &str) -> bool {
+        self.regex.is_match(relative_path)
+    }
+}
+
+/// Parsed CODEOWNERS file
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    rules: Vec<OwnerRule>,
+}
+
+impl CodeOwners {
+    /// Parse CODEOWNERS content
+    pub fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .filter_map(|line| {
+                let line = line.split('#').next().unwrap_or("").trim();
+                // GitLab section headers, e.g. "[Android]" or "^[Optional]"
+                if line.is_empty() || line.starts_with('[') || line.starts_with("^[") {
+                    return None;
+                }
+
+                let mut parts = line.split_whitespace();
+                let pattern = parts.next()?.to_string();
+                let owners = parts.map(|o| o.to_string()).collect();
+                let regex = Self::pattern_to_regex(&pattern)?;
+
+                Some(OwnerRule {
+                    pattern,
+                    owners,
+                    regex,
+                })
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Load a CODEOWNERS file
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+    /// Find the CODEOWNERS file of a project, if any
+    pub fn discover(project_root: &Path) -> Option<PathBuf> {
+        CODEOWNERS_LOCATIONS
+            .iter()
+            .map(|location| project_root.join(location))
+            .find(|path| path.is_file())
+    }
+
+    pub fn rules(&self) -> &[OwnerRule] {
+        &self.rules
+    }
+
+    /// Owners of a root-relative path (last matching rule wins)
+    pub fn owners_of(&self, relative_path: &Path) -> Option<&[String]> {
+        let path = NormalizedPath::new(relative_path);
+        let path = path.as_str();
+
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(path))
+            .map(|rule| rule.owners.as_slice())
+            .filter(|owners| !owners.is_empty())
+    }
+
+    /// Convert a gitignore-style pattern into an anchored regex
+    fn pattern_to_regex(pattern: &str) -> Option<Regex> {
+        let trimmed = pattern.trim_end_matches('/');
+        // Patterns with a leading or inner slash are relative to the root
+        let anchored = pattern.starts_with('/') || trimmed.trim_start_matches('/').contains('/');
+        let dir_only = pattern.ends_with('/');
+        let body = trimmed.trim_start_matches('/');
+
+        let mut regex = String::from("^");
+        if !anchored {
+            regex.push_str("(?:.*/)?");
+        }
+
+        let mut chars = body.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        regex.push_str("(?:.*/)?");
+                    } else {
+                        regex.push_str(".*");
+                    }
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+
+        if dir_only {
+            regex.push_str("/.*");
+        } else if !body.is_empty() {
+            // A pattern naming a directory owns everything below it
+            regex.push_str("(?:/.*)?");
+        } else {
+            regex.push_str(".*");
+        }
+        regex.push('$');
+
+        Regex::new(&regex).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODEOWNERS: &str = r#"
+# Default owners
+*                       @org/android
+*.java                  @org/legacy
+/app/src/main/java/com/example/feature/   @org/feature-team @alice
+docs/                   @org/docs
+**/generated/**
+"#;
+
+    fn owners(codeowners: &CodeOwners, path: &str) -> Option<String> {
+        codeowners
+            .owners_of(Path::new(path))
+            .map(|owners| owners.join(" "))
+    }
+
+    #[test]
+    fn test_last_matching_rule_wins() {
+        let codeowners = CodeOwners::parse(CODEOWNERS);
+
+        assert_eq!(
+            owners(&codeowners, "app/src/main/java/com/example/Main.kt").as_deref(),
+            Some("@org/android")
+        );
+        assert_eq!(
+            owners(&codeowners, "app/src/main/java/com/example/Legacy.java").as_deref(),
+            Some("@org/legacy")
+        );
+        assert_eq!(
+            owners(
+                &codeowners,
+                "app/src/main/java/com/example/feature/ui/Screen.kt"
+            )
+            .as_deref(),
+            Some("@org/feature-team @alice")
+        );
+    }
+
+    #[test]
+    fn test_anchoring_and_unowned_paths() {
+        let codeowners = CodeOwners::parse(CODEOWNERS);
+
+        // "docs/" has a trailing slash only, so it matches at any depth
+        assert_eq!(
+            owners(&codeowners, "lib/docs/Guide.kt").as_deref(),
+            Some("@org/docs")
+        );
+        // Anchored pattern doesn't match a nested copy of the path
+        assert_eq!(
+            owners(
+                &codeowners,
+                "lib/app/src/main/java/com/example/feature/X.kt"
+            )
+            .as_deref(),
+            Some("@org/android")
+        );
+        // Rule without owners leaves generated code unowned
+        assert_eq!(
+            owners(&codeowners, "app/build/generated/source/Dagger.kt"),
+            None
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! Ownership attribution for SearchDeadCode findings
+//!
+//! Maps each finding to the people who can act on it, so cleanup work can be
+//! split between teams. Owners come from the project's CODEOWNERS file and,
+//! optionally, from `git blame` of the declaration line when no rule matches.
+
+#![allow(dead_code)] // Accessors reserved for library users
+
+mod blame;
+mod codeowners;
+
+pub use blame::GitBlame;
+pub use codeowners::CodeOwners;
+
+use crate::analysis::DeadCode;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Resolves the owner of findings
+pub struct OwnershipResolver {
+    /// Project root that CODEOWNERS paths are relative to
+    root: PathBuf,
+    codeowners: Option<CodeOwners>,
+    /// Fall back to `git blame` when no CODEOWNERS rule matches
+    use_blame: bool,
+}
+
+impl OwnershipResolver {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            codeowners: None,
+            use_blame: false,
+        }
+    }
+
+    pub fn with_codeowners(mut self, codeowners: CodeOwners) -> Self {
+        self.codeowners = Some(codeowners);
+        self
+    }
+
+    pub fn with_blame(mut self, use_blame: bool) -> Self {
+        self.use_blame = use_blame;
+        self
+    }
+
+    /// Owner from CODEOWNERS, with multiple owners joined by spaces
+    pub fn codeowner_of(&self, file: &Path) -> Option<String> {
+        let codeowners = self.codeowners.as_ref()?;
+        let relative = file.strip_prefix(&self.root).unwrap_or(file);
+        codeowners
+            .owners_of(relative)
+            .map(|owners| owners.join(" "))
+    }
+
+    /// Set the owner of every finding
+    pub fn assign(&self, dead_code: &mut [DeadCode]) {
+        // Blame each file at most once
+        let mut blames: HashMap<PathBuf, Option<GitBlame>> = HashMap::new();
+
+        for dc in dead_code.iter_mut() {
+            let file = &dc.declaration.location.file;
+            let mut owner = self.codeowner_of(file);
+
+            if owner.is_none() && self.use_blame {
+                owner = blames
+                    .entry(file.clone())
+                    .or_insert_with(|| GitBlame::for_file(&self.root, file))
+                    .as_ref()
+                    .and_then(|blame| blame.author_of(dc.declaration.location.line))
+                    .map(|author| author.to_string());
+            }
+
+            dc.owner = owner;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+
+    fn make_dead_code(file: &str) -> DeadCode {
+        let path = PathBuf::from(file);
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), 0, 10),
+            "Unused".to_string(),
+            DeclarationKind::Class,
+            Location::new(path, 1, 1, 0, 10),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, DeadCodeIssue::Unreferenced)
+    }
+
+    #[test]
+    fn test_assign_codeowners() {
+        let resolver = OwnershipResolver::new(PathBuf::from("/project")).with_codeowners(
+            CodeOwners::parse("* @org/android\n/feature/ @org/feature\n"),
+        );
+        let mut findings = vec![
+            make_dead_code("/project/feature/Screen.kt"),
+            make_dead_code("/project/core/Util.kt"),
+        ];
+
+        resolver.assign(&mut findings);
+
+        assert_eq!(findings[0].owner.as_deref(), Some("@org/feature"));
+        assert_eq!(findings[1].owner.as_deref(), Some("@org/android"));
+    }
+}
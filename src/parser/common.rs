@@ -1,12 +1,15 @@
 // Parser utilities - some reserved for future use
 #![allow(dead_code)]
 
-use crate::graph::{Declaration, Location, UnresolvedReference};
+use crate::graph::reference::ReferenceCollector;
+use crate::graph::{Declaration, DeclarationSize, Location, ReferenceKind, UnresolvedReference};
 use miette::Result;
+use regex::Regex;
 use std::path::Path;
+use std::sync::OnceLock;
 
 /// Result of parsing a source file
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ParseResult {
     /// Declarations found in the file
     pub declarations: Vec<Declaration>,
@@ -61,6 +64,169 @@ pub fn point_to_location(
     )
 }
 
+/// Line comment suppressing the findings of the declaration it annotates;
+/// also recorded as a modifier of that declaration
+pub const IGNORE_COMMENT: &str = "searchdeadcode:ignore";
+
+/// Whether a `// searchdeadcode:ignore` comment sits directly above the
+/// declaration starting at `start_byte` (annotation lines in between are
+/// skipped) or at the end of its first line
+pub fn has_ignore_comment(source: &str, start_byte: usize) -> bool {
+    let is_ignore = |text: &str| {
+        text.split_once("//")
+            .is_some_and(|(_, comment)| comment.trim_start().starts_with(IGNORE_COMMENT))
+    };
+
+    let start = start_byte.min(source.len());
+    let line_end = source[start..]
+        .find('\n')
+        .map_or(source.len(), |end| start + end);
+    if is_ignore(&source[start..line_end]) {
+        return true;
+    }
+
+    let line_start = source[..start].rfind('\n').map_or(0, |newline| newline + 1);
+    for line in source[..line_start].lines().rev() {
+        let line = line.trim();
+        if line.starts_with('@') {
+            continue;
+        }
+        return line.starts_with("//") && is_ignore(line);
+    }
+    false
+}
+
+/// Mark the declarations suppressed by an ignore comment with the
+/// [`IGNORE_COMMENT`] modifier
+pub fn mark_ignore_comments(declarations: &mut [Declaration], source: &str) {
+    for decl in declarations {
+        if has_ignore_comment(source, decl.location.start_byte) {
+            decl.modifiers.push(IGNORE_COMMENT.to_string());
+        }
+    }
+}
+
+/// Start bytes of the nodes of the given kinds, in source order; the
+/// branch points of a file when given its language's branching kinds
+pub fn branch_points(root: tree_sitter::Node, kinds: &[&str]) -> Vec<usize> {
+    let mut points: Vec<usize> = descendants(root)
+        .filter(|node| kinds.contains(&node.kind()))
+        .map(|node| node.start_byte())
+        .collect();
+    points.sort_unstable();
+    points
+}
+
+/// Record the size of each declaration; `branch_points` are sorted start
+/// bytes from [`branch_points`]
+pub fn measure_declarations(
+    declarations: &mut [Declaration],
+    source: &str,
+    branch_points: &[usize],
+) {
+    for decl in declarations {
+        let end = decl.location.end_byte.min(source.len());
+        let start = decl.location.start_byte.min(end);
+        let newlines = source.as_bytes()[start..end]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count();
+        let first = branch_points.partition_point(|&point| point < start);
+        let last = branch_points.partition_point(|&point| point < end);
+        decl.size = Some(DeclarationSize {
+            lines: newlines + 1,
+            bytes: end - start,
+            branches: last - first,
+        });
+    }
+}
+
+/// Symbols linked from a KDoc or Javadoc comment: `[Foo]`, `[text][Foo]`,
+/// `[Foo.bar]`, `{@link Foo#bar}`, `{@linkplain Foo label}` and `@see Foo`
+///
+/// Returns the byte offset of each link in the comment with the linked name,
+/// `#` member separators turned into dots. Other comments have no links.
+pub fn doc_links(comment: &str) -> Vec<(usize, String)> {
+    static DOC_LINK: OnceLock<Regex> = OnceLock::new();
+    let re = DOC_LINK.get_or_init(|| {
+        Regex::new(
+            r"\{@link(?:plain)?\s+([\w.$]*)(?:#(\w+))?|@see\s+([\w.$]*)(?:#(\w+))?|\[([A-Za-z_][\w.]*)\]",
+        )
+        .unwrap()
+    });
+    if !comment.starts_with("/**") {
+        return Vec::new();
+    }
+
+    let mut links = Vec::new();
+    for cap in re.captures_iter(comment) {
+        if let Some(name) = cap.get(5) {
+            // `[text](url)` and `[text][Foo]` link elsewhere, `items[i]` is code
+            let whole = cap.get(0).unwrap();
+            let next = comment[whole.end()..].chars().next();
+            let previous = comment[..whole.start()].chars().next_back();
+            if matches!(next, Some('(' | '['))
+                || previous.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == ')')
+            {
+                continue;
+            }
+            links.push((name.start(), name.as_str().to_string()));
+            continue;
+        }
+
+        let (class, member) = match cap.get(1) {
+            Some(class) => (class, cap.get(2)),
+            None => (cap.get(3).unwrap(), cap.get(4)),
+        };
+        let offset = class.start();
+        let class = class.as_str().trim_end_matches('.');
+        match (class.is_empty(), member) {
+            (false, Some(member)) => links.push((offset, format!("{}.{}", class, member.as_str()))),
+            (false, None) => links.push((offset, class.to_string())),
+            (true, Some(member)) => links.push((member.start(), member.as_str().to_string())),
+            (true, None) => {}
+        }
+    }
+    links
+}
+
+/// Documentation references for the links in a doc comment node
+pub fn doc_link_references(
+    path: &Path,
+    comment: tree_sitter::Node,
+    source: &str,
+    imports: &[String],
+) -> Vec<UnresolvedReference> {
+    let text = node_text(comment, source);
+    let start = comment.start_position();
+    let mut collector = ReferenceCollector::new();
+
+    for (offset, name) in doc_links(text) {
+        let prefix = &text[..offset];
+        let newlines = prefix.matches('\n').count();
+        let column = match prefix.rfind('\n') {
+            Some(newline) => offset - newline,
+            None => start.column + offset + 1,
+        };
+        let start_byte = comment.start_byte() + offset;
+        let location = Location::new(
+            path.to_path_buf(),
+            start.row + 1 + newlines,
+            column,
+            start_byte,
+            start_byte + name.len(),
+        );
+        collector.add_reference(
+            name,
+            ReferenceKind::Documentation,
+            location,
+            imports.to_vec(),
+        );
+    }
+
+    collector.drain()
+}
+
 /// Extract text from a node
 pub fn node_text<'a>(node: tree_sitter::Node<'a>, source: &'a str) -> &'a str {
     &source[node.start_byte()..node.end_byte()]
@@ -130,3 +296,34 @@ impl<'a> Iterator for DescendantIterator<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doc_links() {
+        let comment = "/**\n * Use [render] or [Feed.load], see {@link Cache#get} and\n * [the docs](https://example.com), not items[0].\n * @see Store\n */";
+        let names: Vec<String> = doc_links(comment)
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect();
+        assert_eq!(names, vec!["render", "Feed.load", "Cache.get", "Store"]);
+
+        let (offset, _) = doc_links(comment)[2];
+        assert!(comment[offset..].starts_with("Cache#get"));
+
+        // Plain block comments are not documentation
+        assert!(doc_links("/* [render] */").is_empty());
+    }
+
+    #[test]
+    fn test_ignore_comments() {
+        let source = "// searchdeadcode:ignore kept for the plugin\n@Keep\nfun legacy() {}\n\nfun other() {} // searchdeadcode:ignore\n\n// unrelated\nfun dead() {}\n";
+        let start = |name: &str| source.find(name).unwrap();
+
+        assert!(has_ignore_comment(source, start("fun legacy")));
+        assert!(has_ignore_comment(source, start("fun other")));
+        assert!(!has_ignore_comment(source, start("fun dead")));
+    }
+}
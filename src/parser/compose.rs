@@ -0,0 +1,461 @@
+// Compose-aware scanning of Kotlin sources
+//
+// The graph sees a composable as an ordinary function, so two kinds of dead
+// code in Compose UIs go unnoticed:
+//
+// - state held with `remember { mutableStateOf(...) }` that is assigned but
+//   whose value is never read, so nothing recomposes on it
+// - parameters of composables whose default value no call ever overrides
+//
+// This scanner collects the state locals of composable bodies with their
+// reads and writes, the defaulted parameters of composables, and the
+// arguments of every call. The compose detector matches them across files.
+
+use super::common::{descendants, node_text, point_to_location};
+use crate::graph::{DeclarationId, Location};
+use miette::{miette, IntoDiagnostic, Result};
+use std::path::Path;
+use tree_sitter::{Node, Parser as TsParser};
+
+/// Functions creating snapshot state
+const STATE_FACTORIES: &[&str] = &[
+    "mutableStateOf",
+    "mutableIntStateOf",
+    "mutableLongStateOf",
+    "mutableFloatStateOf",
+    "mutableDoubleStateOf",
+];
+
+/// A `remember`ed state local of a composable
+#[derive(Debug, Clone)]
+pub struct RememberedState {
+    pub name: String,
+    /// The local's declaration
+    pub location: Location,
+    /// The composable declaring it
+    pub function: DeclarationId,
+    pub reads: usize,
+    pub writes: usize,
+}
+
+/// A parameter of a composable with a default value
+#[derive(Debug, Clone)]
+pub struct DefaultedParameter {
+    pub name: String,
+    pub location: Location,
+    /// Name of the composable
+    pub function: String,
+    pub function_id: DeclarationId,
+    /// Position in the parameter list
+    pub index: usize,
+    /// Whether it is the last parameter, which a trailing lambda passes
+    pub last: bool,
+    /// Whether the composable is private, so every call is in this file
+    pub private: bool,
+}
+
+/// Arguments passed by a call or function reference
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallArguments {
+    pub callee: String,
+    /// Arguments passed by position inside the parentheses
+    pub positional: usize,
+    /// Arguments passed by name
+    pub named: Vec<String>,
+    pub trailing_lambda: bool,
+    /// A function reference (`::Avatar`), which is called with every argument
+    pub reference: bool,
+}
+
+/// What a Kotlin source tells about its composables
+#[derive(Debug, Clone, Default)]
+pub struct ComposeFacts {
+    pub states: Vec<RememberedState>,
+    pub defaults: Vec<DefaultedParameter>,
+    pub calls: Vec<CallArguments>,
+}
+
+/// How an identifier uses a state local
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    Read,
+    Write,
+}
+
+/// Scan a Kotlin source for composable state, defaults and call arguments
+pub fn scan_compose(path: &Path, source: &str) -> Result<ComposeFacts> {
+    let mut parser = TsParser::new();
+    parser
+        .set_language(&tree_sitter_kotlin::language())
+        .into_diagnostic()?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| miette!("Failed to parse Kotlin file"))?;
+
+    let mut facts = ComposeFacts::default();
+    for node in descendants(tree.root_node()) {
+        match node.kind() {
+            "function_declaration" if is_composable(node, source) => {
+                scan_composable(path, node, source, &mut facts);
+            }
+            "call_expression" => facts.calls.extend(call_arguments(node, source)),
+            "callable_reference" => facts.calls.extend(function_reference(node, source)),
+            _ => {}
+        }
+    }
+    Ok(facts)
+}
+
+fn scan_composable(path: &Path, function: Node, source: &str, facts: &mut ComposeFacts) {
+    let function_id = DeclarationId::new(
+        path.to_path_buf(),
+        function.start_byte(),
+        function.end_byte(),
+    );
+    let mut cursor = function.walk();
+    let name = function
+        .children(&mut cursor)
+        .find(|c| c.kind() == "simple_identifier")
+        .map(|c| node_text(c, source).to_string())
+        .unwrap_or_default();
+
+    let mut cursor = function.walk();
+    let private = function
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "modifiers")
+        .any(|m| {
+            node_text(m, source)
+                .split_whitespace()
+                .any(|w| w == "private")
+        });
+
+    let mut cursor = function.walk();
+    if let Some(params) = function
+        .children(&mut cursor)
+        .find(|c| c.kind() == "function_value_parameters")
+    {
+        let mut index = 0;
+        let mut previous: Option<Node> = None;
+        let mut defaults = Vec::new();
+        let mut cursor = params.walk();
+        for child in params.children(&mut cursor) {
+            match child.kind() {
+                "parameter" => {
+                    previous = Some(child);
+                    index += 1;
+                }
+                // `= default` follows the parameter it belongs to
+                "=" => {
+                    if let Some(parameter) = previous.take() {
+                        defaults.push((parameter, index - 1));
+                    }
+                }
+                _ => {}
+            }
+        }
+        for (parameter, position) in defaults {
+            let mut cursor = parameter.walk();
+            let Some(param_name) = parameter
+                .children(&mut cursor)
+                .find(|c| c.kind() == "simple_identifier")
+            else {
+                continue;
+            };
+            facts.defaults.push(DefaultedParameter {
+                name: node_text(param_name, source).to_string(),
+                location: location(path, parameter),
+                function: name.clone(),
+                function_id: function_id.clone(),
+                index: position,
+                last: position + 1 == index,
+                private,
+            });
+        }
+    }
+
+    // Locals of this composable, not of composables nested in it
+    let own = |node: &Node| {
+        let mut current = node.parent();
+        while let Some(ancestor) = current {
+            if ancestor.kind() == "function_declaration" {
+                return ancestor == function;
+            }
+            current = ancestor.parent();
+        }
+        false
+    };
+    for property in descendants(function).filter(|n| n.kind() == "property_declaration" && own(n)) {
+        let mut cursor = property.walk();
+        let Some(variable) = property
+            .children(&mut cursor)
+            .find(|c| c.kind() == "variable_declaration")
+        else {
+            continue;
+        };
+        let mut cursor = variable.walk();
+        let Some(state_name) = variable
+            .children(&mut cursor)
+            .find(|c| c.kind() == "simple_identifier")
+            .map(|c| node_text(c, source))
+        else {
+            continue;
+        };
+        let text = node_text(property, source);
+        if !text.contains("remember") || !STATE_FACTORIES.iter().any(|f| text.contains(f)) {
+            continue;
+        }
+
+        let mut cursor = property.walk();
+        let delegated = property
+            .children(&mut cursor)
+            .any(|c| c.kind() == "property_delegate");
+        let mut state = RememberedState {
+            name: state_name.to_string(),
+            location: location(path, property),
+            function: function_id.clone(),
+            reads: 0,
+            writes: 0,
+        };
+        // `$count` in a string template is an interpolated_identifier
+        for identifier in descendants(function).filter(|n| {
+            matches!(n.kind(), "simple_identifier" | "interpolated_identifier")
+                && n.start_byte() >= property.end_byte()
+                && node_text(*n, source) == state_name
+        }) {
+            match access(identifier, delegated, source) {
+                Some(Access::Read) => state.reads += 1,
+                Some(Access::Write) => state.writes += 1,
+                None => {}
+            }
+        }
+        facts.states.push(state);
+    }
+}
+
+/// How an identifier naming a state local uses it; None when it names
+/// something else (a member, a named argument)
+fn access(identifier: Node, delegated: bool, source: &str) -> Option<Access> {
+    let parent = identifier.parent()?;
+    let value_suffix = |node: Node| {
+        let mut cursor = node.walk();
+        let found = node
+            .children(&mut cursor)
+            .find(|c| c.kind() == "navigation_suffix");
+        found.is_some_and(|suffix| {
+            node_text(suffix, source).trim_start_matches(['?', '.']) == "value"
+        })
+    };
+    let increments = |node: Node| {
+        let mut cursor = node.walk();
+        let found = node
+            .children(&mut cursor)
+            .any(|c| matches!(c.kind(), "++" | "--"));
+        found
+    };
+
+    match parent.kind() {
+        "navigation_suffix" => None,
+        "value_argument" => {
+            let mut cursor = parent.walk();
+            let named = parent
+                .children(&mut cursor)
+                .any(|c| c.kind() == "=" && identifier.start_byte() < c.start_byte());
+            (!named).then_some(Access::Read)
+        }
+        // `count = 1`, `state.value = 1`, `state.value += 1`
+        "directly_assignable_expression" => {
+            let mut cursor = parent.walk();
+            let has_suffix = parent
+                .children(&mut cursor)
+                .any(|c| c.kind() == "navigation_suffix");
+            if !has_suffix || (!delegated && value_suffix(parent)) {
+                Some(Access::Write)
+            } else {
+                Some(Access::Read)
+            }
+        }
+        // `count++`
+        "prefix_expression" | "postfix_expression" if increments(parent) => Some(Access::Write),
+        // `state.value++`
+        "navigation_expression" if !delegated && value_suffix(parent) => match parent.parent() {
+            Some(outer)
+                if matches!(outer.kind(), "prefix_expression" | "postfix_expression")
+                    && increments(outer) =>
+            {
+                Some(Access::Write)
+            }
+            _ => Some(Access::Read),
+        },
+        _ => Some(Access::Read),
+    }
+}
+
+/// Arguments of a call whose callee is a name (`Avatar(user)`) or a member
+/// (`scope.Avatar(user)`)
+fn call_arguments(call: Node, source: &str) -> Option<CallArguments> {
+    let callee = call.child(0)?;
+    let callee = match callee.kind() {
+        "simple_identifier" => node_text(callee, source),
+        "navigation_expression" => {
+            let suffix = callee.child(callee.child_count().checked_sub(1)?)?;
+            if suffix.kind() != "navigation_suffix" {
+                return None;
+            }
+            node_text(suffix, source).trim_start_matches('.').trim()
+        }
+        _ => return None,
+    };
+
+    let mut arguments = CallArguments {
+        callee: callee.to_string(),
+        ..CallArguments::default()
+    };
+    let mut cursor = call.walk();
+    let suffix = call
+        .children(&mut cursor)
+        .find(|c| c.kind() == "call_suffix")?;
+    let mut cursor = suffix.walk();
+    for child in suffix.children(&mut cursor) {
+        match child.kind() {
+            "value_arguments" => {
+                let mut args = child.walk();
+                for arg in child.children(&mut args) {
+                    if arg.kind() != "value_argument" {
+                        continue;
+                    }
+                    let mut parts = arg.walk();
+                    let parts: Vec<Node> = arg.children(&mut parts).collect();
+                    match parts.as_slice() {
+                        [name, eq, ..]
+                            if name.kind() == "simple_identifier" && eq.kind() == "=" =>
+                        {
+                            arguments.named.push(node_text(*name, source).to_string());
+                        }
+                        _ => arguments.positional += 1,
+                    }
+                }
+            }
+            "annotated_lambda" => arguments.trailing_lambda = true,
+            _ => {}
+        }
+    }
+    // `Button(onClick = …) { … }` parses as a call of the call, with the
+    // trailing lambda in the outer call's suffix
+    if let Some(outer) = call.parent() {
+        if outer.kind() == "call_expression" && outer.child(0) == Some(call) {
+            let mut cursor = outer.walk();
+            let lambda = outer
+                .children(&mut cursor)
+                .filter(|c| c.kind() == "call_suffix")
+                .any(|suffix| {
+                    let mut parts = suffix.walk();
+                    let kinds: Vec<&str> = suffix.children(&mut parts).map(|c| c.kind()).collect();
+                    kinds == ["annotated_lambda"]
+                });
+            arguments.trailing_lambda |= lambda;
+        }
+    }
+    Some(arguments)
+}
+
+/// A function reference (`::Avatar`, `this::Avatar`); class literals are not
+fn function_reference(node: Node, source: &str) -> Option<CallArguments> {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    if children.iter().any(|c| c.kind() == "class") {
+        return None;
+    }
+    let name = children
+        .iter()
+        .rev()
+        .find(|c| c.kind() == "simple_identifier")?;
+    if node_text(*name, source) == "class" {
+        return None;
+    }
+    Some(CallArguments {
+        callee: node_text(*name, source).to_string(),
+        reference: true,
+        ..CallArguments::default()
+    })
+}
+
+/// Whether a function is annotated `@Composable`
+fn is_composable(function: Node, source: &str) -> bool {
+    let composable = |annotation: Node| {
+        let text = node_text(annotation, source).trim_start_matches('@');
+        let name = text.split('(').next().unwrap_or(text).trim();
+        name == "Composable" || name.ends_with(".Composable")
+    };
+
+    let mut cursor = function.walk();
+    let in_modifiers = function
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "modifiers")
+        .any(|m| descendants(m).any(|n| n.kind() == "annotation" && composable(n)));
+    // tree-sitter-kotlin sometimes puts annotations in a preceding
+    // prefix_expression instead of the modifiers
+    let before = function.prev_sibling().is_some_and(|prev| {
+        prev.kind() == "prefix_expression"
+            && descendants(prev).any(|n| n.kind() == "annotation" && composable(n))
+    });
+    in_modifiers || before
+}
+
+fn location(path: &Path, node: Node) -> Location {
+    point_to_location(
+        path,
+        node.start_position(),
+        node.end_position(),
+        node.start_byte(),
+        node.end_byte(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_compose() {
+        let source = r#"
+            package com.example.ui
+
+            @Composable
+            fun Counter(label: String, step: Int = 1, modifier: Modifier = Modifier) {
+                var clicks by remember { mutableStateOf(0) }
+                val expanded = remember { mutableStateOf(false) }
+                var count by remember { mutableIntStateOf(0) }
+                Button(onClick = { clicks++; expanded.value = true; count += step }) {
+                    Text("$label: $count")
+                }
+            }
+
+            @Composable
+            fun Screen() {
+                Counter("Taps", modifier = Modifier.padding(8.dp))
+            }
+        "#;
+
+        let facts = scan_compose(Path::new("Counter.kt"), source).unwrap();
+
+        let state = |name: &str| facts.states.iter().find(|s| s.name == name).unwrap();
+        assert_eq!(facts.states.len(), 3);
+        assert_eq!((state("clicks").reads, state("clicks").writes), (0, 1));
+        assert_eq!((state("expanded").reads, state("expanded").writes), (0, 1));
+        assert_eq!((state("count").reads, state("count").writes), (1, 1));
+
+        let defaults: Vec<_> = facts
+            .defaults
+            .iter()
+            .map(|d| (d.name.as_str(), d.index, d.last))
+            .collect();
+        assert_eq!(defaults, vec![("step", 1, false), ("modifier", 2, true)]);
+
+        let call = facts.calls.iter().find(|c| c.callee == "Counter").unwrap();
+        assert_eq!(call.positional, 1);
+        assert_eq!(call.named, vec!["modifier".to_string()]);
+        assert!(!call.trailing_lambda);
+        let button = facts.calls.iter().find(|c| c.callee == "Button").unwrap();
+        assert!(button.trailing_lambda);
+    }
+}
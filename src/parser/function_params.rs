@@ -0,0 +1,272 @@
+// Uses of function-typed parameters in Kotlin functions
+//
+// A parameter such as `onDone: () -> Unit` only does something when the
+// function invokes it or hands it on to code that may. One that is merely
+// null-checked (`if (onDone != null)`) or not used at all is dead: callers
+// build and pass lambdas nothing ever runs.
+//
+// This scanner collects the function-typed parameters of every function
+// with a body and sorts each use of them into invocations (`onDone()`,
+// `onDone.invoke()`, `onDone?.invoke()`), hand-offs (passing it as an
+// argument, storing, returning or capturing it) and null checks.
+
+use super::common::{descendants, node_text, point_to_location};
+use crate::graph::{DeclarationId, Location};
+use miette::{miette, IntoDiagnostic, Result};
+use std::path::Path;
+use tree_sitter::{Node, Parser as TsParser};
+
+/// A function-typed parameter and how the function's body uses it
+#[derive(Debug, Clone)]
+pub struct FunctionParameter {
+    pub name: String,
+    pub location: Location,
+    /// Name of the function
+    pub function: String,
+    pub function_id: DeclarationId,
+    /// Calls of the parameter, directly or through `invoke`
+    pub invocations: usize,
+    /// Uses handing the function on to code that may call it
+    pub hand_offs: usize,
+    /// Comparisons with `null`
+    pub null_checks: usize,
+}
+
+impl FunctionParameter {
+    /// Whether nothing can ever call the function passed for the parameter
+    pub fn is_never_invoked(&self) -> bool {
+        self.invocations == 0 && self.hand_offs == 0
+    }
+}
+
+/// How an identifier naming a function-typed parameter uses it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Use {
+    Invocation,
+    HandOff,
+    NullCheck,
+}
+
+/// Scan a Kotlin source for function-typed parameters and their uses
+pub fn scan_function_parameters(path: &Path, source: &str) -> Result<Vec<FunctionParameter>> {
+    let mut parser = TsParser::new();
+    parser
+        .set_language(&tree_sitter_kotlin::language())
+        .into_diagnostic()?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| miette!("Failed to parse Kotlin file"))?;
+
+    let mut parameters = Vec::new();
+    for function in descendants(tree.root_node()).filter(|n| n.kind() == "function_declaration") {
+        scan_function(path, function, source, &mut parameters);
+    }
+    Ok(parameters)
+}
+
+fn scan_function(path: &Path, function: Node, source: &str, out: &mut Vec<FunctionParameter>) {
+    let mut cursor = function.walk();
+    let children: Vec<Node> = function.children(&mut cursor).collect();
+    let Some(body) = children.iter().find(|c| c.kind() == "function_body") else {
+        return;
+    };
+    // An override's parameters are fixed by the member it overrides
+    let overrides = children
+        .iter()
+        .filter(|c| c.kind() == "modifiers")
+        .any(|m| {
+            node_text(*m, source)
+                .split_whitespace()
+                .any(|w| w == "override")
+        });
+    if overrides {
+        return;
+    }
+    let Some(params) = children
+        .iter()
+        .find(|c| c.kind() == "function_value_parameters")
+    else {
+        return;
+    };
+
+    let name = children
+        .iter()
+        .find(|c| c.kind() == "simple_identifier")
+        .map(|c| node_text(*c, source).to_string())
+        .unwrap_or_default();
+    let function_id = DeclarationId::new(
+        path.to_path_buf(),
+        function.start_byte(),
+        function.end_byte(),
+    );
+
+    let mut cursor = params.walk();
+    for parameter in params
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "parameter")
+    {
+        let mut cursor = parameter.walk();
+        let parts: Vec<Node> = parameter.named_children(&mut cursor).collect();
+        let Some(param_name) = parts.iter().find(|c| c.kind() == "simple_identifier") else {
+            continue;
+        };
+        let declared_type = parts.iter().find(|c| {
+            !matches!(
+                c.kind(),
+                "simple_identifier" | "parameter_modifiers" | "type_modifiers"
+            )
+        });
+        if !declared_type.is_some_and(|t| is_function_type(*t)) {
+            continue;
+        }
+
+        let param_name = node_text(*param_name, source);
+        let mut found = FunctionParameter {
+            name: param_name.to_string(),
+            location: point_to_location(
+                path,
+                parameter.start_position(),
+                parameter.end_position(),
+                parameter.start_byte(),
+                parameter.end_byte(),
+            ),
+            function: name.clone(),
+            function_id: function_id.clone(),
+            invocations: 0,
+            hand_offs: 0,
+            null_checks: 0,
+        };
+        for identifier in descendants(*body).filter(|n| {
+            matches!(n.kind(), "simple_identifier" | "interpolated_identifier")
+                && node_text(*n, source) == param_name
+        }) {
+            match use_of(identifier, source) {
+                Some(Use::Invocation) => found.invocations += 1,
+                Some(Use::HandOff) => found.hand_offs += 1,
+                Some(Use::NullCheck) => found.null_checks += 1,
+                None => {}
+            }
+        }
+        out.push(found);
+    }
+}
+
+/// Whether a declared type is a function type: `() -> Unit`,
+/// `((String) -> Unit)?`, `suspend () -> Unit`
+fn is_function_type(node: Node) -> bool {
+    match node.kind() {
+        "function_type" => true,
+        "nullable_type" | "parenthesized_type" | "type_reference" => {
+            let mut cursor = node.walk();
+            let inner = node
+                .named_children(&mut cursor)
+                .find(|c| c.kind() != "type_modifiers");
+            inner.is_some_and(is_function_type)
+        }
+        _ => false,
+    }
+}
+
+/// How an identifier naming the parameter uses it; None when it names
+/// something else (a member, a named argument)
+fn use_of(identifier: Node, source: &str) -> Option<Use> {
+    let parent = identifier.parent()?;
+    match parent.kind() {
+        "navigation_suffix" => None,
+        "value_argument" => {
+            let mut cursor = parent.walk();
+            let named = parent
+                .children(&mut cursor)
+                .any(|c| c.kind() == "=" && identifier.start_byte() < c.start_byte());
+            (!named).then_some(Use::HandOff)
+        }
+        // `onDone(result)`
+        "call_expression" if parent.child(0) == Some(identifier) => Some(Use::Invocation),
+        // `onDone.invoke()`, `onDone?.invoke()`; `onDone?.let { ... }` hands
+        // it to a lambda that may call it
+        "navigation_expression" if parent.child(0) == Some(identifier) => {
+            let mut cursor = parent.walk();
+            let invoked = parent
+                .children(&mut cursor)
+                .find(|c| c.kind() == "navigation_suffix")
+                .is_some_and(|suffix| {
+                    node_text(suffix, source).trim_start_matches(['?', '.']) == "invoke"
+                });
+            Some(if invoked {
+                Use::Invocation
+            } else {
+                Use::HandOff
+            })
+        }
+        // `onDone != null`, `null == onDone`
+        "equality_expression" => {
+            let mut cursor = parent.walk();
+            let against_null = parent
+                .children(&mut cursor)
+                .any(|c| c != identifier && node_text(c, source) == "null");
+            Some(if against_null {
+                Use::NullCheck
+            } else {
+                Use::HandOff
+            })
+        }
+        _ => Some(Use::HandOff),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_function_parameters() {
+        let source = r#"
+            fun save(item: Item, onSaved: (Item) -> Unit, onError: ((Throwable) -> Unit)?) {
+                repository.insert(item)
+                onSaved(item)
+                if (onError != null) log("has error handler")
+            }
+
+            fun load(onLoaded: suspend () -> Unit, onProgress: (Int) -> Unit) {
+                scope.launch { onLoaded.invoke() }
+                worker.track(onProgress)
+            }
+
+            fun refresh(onDone: () -> Unit, count: Int) {
+                println(count)
+            }
+
+            interface Source {
+                fun fetch(onResult: () -> Unit)
+            }
+
+            class Screen : Base() {
+                override fun render(onClick: () -> Unit) {}
+            }
+        "#;
+
+        let parameters = scan_function_parameters(Path::new("Save.kt"), source).unwrap();
+        let uses: Vec<(&str, usize, usize, usize)> = parameters
+            .iter()
+            .map(|p| (p.name.as_str(), p.invocations, p.hand_offs, p.null_checks))
+            .collect();
+        assert_eq!(
+            uses,
+            vec![
+                ("onSaved", 1, 0, 0),
+                ("onError", 0, 0, 1),
+                ("onLoaded", 1, 0, 0),
+                ("onProgress", 0, 1, 0),
+                ("onDone", 0, 0, 0),
+            ]
+        );
+
+        let never_invoked: Vec<&str> = parameters
+            .iter()
+            .filter(|p| p.is_never_invoked())
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(never_invoked, vec!["onError", "onDone"]);
+        assert_eq!(parameters[1].function, "save");
+    }
+}
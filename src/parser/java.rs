@@ -1,16 +1,33 @@
 // Java parser - some internal methods reserved for future use
 #![allow(dead_code)]
 
-use super::common::{node_text, point_to_location, ParseResult, Parser};
+use super::common::{
+    branch_points, descendants, doc_link_references, mark_ignore_comments, measure_declarations,
+    node_text, point_to_location, ParseResult, Parser,
+};
 use crate::graph::{
-    Declaration, DeclarationId, DeclarationKind, Language, ReferenceKind, UnresolvedReference,
-    Visibility,
+    simple_type_name, Declaration, DeclarationId, DeclarationKind, Language, ReferenceKind,
+    Signature, UnresolvedReference, Visibility,
 };
 use miette::{IntoDiagnostic, Result};
 use std::path::Path;
 use tracing::debug;
 use tree_sitter::{Node, Parser as TsParser};
 
+/// Node kinds that add a branch to the code they are in
+const BRANCH_KINDS: &[&str] = &[
+    "if_statement",
+    "switch_label",
+    "for_statement",
+    "enhanced_for_statement",
+    "while_statement",
+    "do_statement",
+    "catch_clause",
+    "ternary_expression",
+    "&&",
+    "||",
+];
+
 /// Java source code parser using tree-sitter
 pub struct JavaParser {
     parser: TsParser,
@@ -89,6 +106,9 @@ impl JavaParser {
                 "enum_declaration" => {
                     self.extract_enum(path, child, source, package, None, result)?;
                 }
+                "record_declaration" => {
+                    self.extract_record(path, child, source, package, None, result)?;
+                }
                 "annotation_type_declaration" => {
                     self.extract_annotation_type(path, child, source, package, result)?;
                 }
@@ -246,6 +266,105 @@ impl JavaParser {
         Ok(())
     }
 
+    /// Extract a record: a class whose components are its fields, read
+    /// through implicit accessors of the same name (`point.x()`)
+    fn extract_record(
+        &self,
+        path: &Path,
+        node: Node,
+        source: &str,
+        package: &Option<String>,
+        parent: Option<DeclarationId>,
+        result: &mut ParseResult,
+    ) -> Result<()> {
+        let name = node
+            .child_by_field_name("name")
+            .map(|n| node_text(n, source).to_string())
+            .unwrap_or_else(|| "<anonymous>".to_string());
+
+        let location = point_to_location(
+            path,
+            node.start_position(),
+            node.end_position(),
+            node.start_byte(),
+            node.end_byte(),
+        );
+
+        let id = DeclarationId::new(path.to_path_buf(), node.start_byte(), node.end_byte());
+
+        let mut decl = Declaration::new(
+            id.clone(),
+            name.clone(),
+            DeclarationKind::Class,
+            location,
+            Language::Java,
+        );
+
+        decl.fully_qualified_name = Some(self.build_fqn(package, &name));
+        self.extract_modifiers(node, source, &mut decl);
+        decl.modifiers.push("record".to_string());
+        decl.super_types = self.extract_super_types(node, source);
+        decl.annotations = self.extract_annotations(node, source);
+        decl.parent = parent;
+
+        result.declarations.push(decl);
+
+        if let Some(components) = node.child_by_field_name("parameters") {
+            self.extract_record_components(path, components, source, id.clone(), result);
+        }
+
+        // Compact and explicit constructors, accessors and nested types
+        if let Some(body) = node.child_by_field_name("body") {
+            self.extract_class_members(path, body, source, package, id, result)?;
+        }
+
+        Ok(())
+    }
+
+    fn extract_record_components(
+        &self,
+        path: &Path,
+        node: Node,
+        source: &str,
+        parent: DeclarationId,
+        result: &mut ParseResult,
+    ) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() != "formal_parameter" && child.kind() != "spread_parameter" {
+                continue;
+            }
+            let Some(name_node) = child.child_by_field_name("name") else {
+                continue;
+            };
+
+            let location = point_to_location(
+                path,
+                child.start_position(),
+                child.end_position(),
+                child.start_byte(),
+                child.end_byte(),
+            );
+            let id = DeclarationId::new(path.to_path_buf(), child.start_byte(), child.end_byte());
+
+            let mut decl = Declaration::new(
+                id,
+                node_text(name_node, source).to_string(),
+                DeclarationKind::Field,
+                location,
+                Language::Java,
+            );
+
+            // The field is private final; its accessor is public
+            decl.modifiers = vec!["private".to_string(), "final".to_string()];
+            decl.visibility = Visibility::Public;
+            decl.annotations = self.extract_annotations(child, source);
+            decl.parent = Some(parent.clone());
+
+            result.declarations.push(decl);
+        }
+    }
+
     fn extract_enum_body(
         &self,
         path: &Path,
@@ -389,6 +508,16 @@ impl JavaParser {
                 "enum_declaration" => {
                     self.extract_enum(path, child, source, package, Some(parent.clone()), result)?;
                 }
+                "record_declaration" => {
+                    self.extract_record(
+                        path,
+                        child,
+                        source,
+                        package,
+                        Some(parent.clone()),
+                        result,
+                    )?;
+                }
                 "method_declaration" => {
                     self.extract_method(
                         path,
@@ -399,7 +528,7 @@ impl JavaParser {
                         result,
                     )?;
                 }
-                "constructor_declaration" => {
+                "constructor_declaration" | "compact_constructor_declaration" => {
                     self.extract_constructor(path, child, source, parent.clone(), result)?;
                 }
                 "field_declaration" => {
@@ -448,8 +577,15 @@ impl JavaParser {
         decl.annotations = self.extract_annotations(node, source);
         decl.parent = parent;
 
+        // Interface methods are implicitly abstract; native methods have no body either
+        let is_native = decl.modifiers.iter().any(|m| m == "native");
+        if node.child_by_field_name("body").is_none() && !is_native {
+            decl.is_abstract = true;
+        }
+
         // Extract parameters
         if let Some(params) = node.child_by_field_name("parameters") {
+            decl.signature = Some(signature(params, source));
             self.extract_parameters(path, params, source, id, result)?;
         }
 
@@ -495,6 +631,7 @@ impl JavaParser {
 
         // Extract parameters
         if let Some(params) = node.child_by_field_name("parameters") {
+            decl.signature = Some(signature(params, source));
             self.extract_parameters(path, params, source, id, result)?;
         }
 
@@ -608,6 +745,7 @@ impl JavaParser {
 
         loop {
             let current = cursor.node();
+            let mut descend = true;
 
             match current.kind() {
                 "identifier" => {
@@ -622,12 +760,29 @@ impl JavaParser {
                                 current.end_byte(),
                             );
 
+                            // Only the method name of `obj.foo(a)` is called with
+                            // the arguments, not the `obj` it is called on
+                            let is_callee = kind == ReferenceKind::Call
+                                && parent.child_by_field_name("name") == Some(current);
+                            let argument_count = parent
+                                .child_by_field_name("arguments")
+                                .filter(|_| is_callee)
+                                .map(|args| {
+                                    let mut args_cursor = args.walk();
+                                    args.named_children(&mut args_cursor)
+                                        .filter(|arg| !arg.kind().ends_with("comment"))
+                                        .count()
+                                });
+
                             result.references.push(UnresolvedReference {
                                 name,
                                 qualified_name: None,
                                 kind,
                                 location,
                                 imports: imports.to_vec(),
+                                receiver_type: None,
+                                argument_count,
+                                argument_names: Vec::new(),
                             });
                         }
                     }
@@ -642,12 +797,35 @@ impl JavaParser {
                         current.end_byte(),
                     );
 
+                    // `new Foo()` and `new Foo<>()` create an instance of Foo
+                    let created = current.parent().and_then(|parent| {
+                        if parent.kind() == "generic_type" {
+                            parent.parent()
+                        } else {
+                            Some(parent)
+                        }
+                    });
+                    let kind = match created {
+                        Some(node)
+                            if node.kind() == "object_creation_expression"
+                                && node.child_by_field_name("type").is_some_and(|t| {
+                                    t == current || t.child(0) == Some(current)
+                                }) =>
+                        {
+                            ReferenceKind::Instantiation
+                        }
+                        _ => ReferenceKind::Type,
+                    };
+
                     result.references.push(UnresolvedReference {
                         name,
                         qualified_name: None,
-                        kind: ReferenceKind::Type,
+                        kind,
                         location,
                         imports: imports.to_vec(),
+                        receiver_type: None,
+                        argument_count: None,
+                        argument_names: Vec::new(),
                     });
                 }
                 "scoped_identifier" | "scoped_type_identifier" => {
@@ -666,12 +844,31 @@ impl JavaParser {
                         kind: ReferenceKind::Type,
                         location,
                         imports: imports.to_vec(),
+                        receiver_type: None,
+                        argument_count: None,
+                        argument_names: Vec::new(),
                     });
                 }
+                "method_reference" => {
+                    self.extract_method_reference(path, current, source, imports, result);
+                    self.extract_sam_conversion(path, current, source, imports, result);
+                }
+                "lambda_expression" => {
+                    self.extract_sam_conversion(path, current, source, imports, result);
+                }
+                "method_invocation" => {
+                    self.extract_enum_bulk_access(path, current, source, imports, result);
+                }
+                "permits" => {
+                    // Listing a subtype doesn't use it: the sealed variant
+                    // detector still reports permitted subtypes never created
+                    self.extract_permits(path, current, source, imports, result);
+                    descend = false;
+                }
                 _ => {}
             }
 
-            if cursor.goto_first_child() {
+            if descend && cursor.goto_first_child() {
                 continue;
             }
             while !cursor.goto_next_sibling() {
@@ -682,6 +879,161 @@ impl JavaParser {
         }
     }
 
+    /// Extract `Foo::bar`, `this::bar` and `Foo::new` as a call (or instantiation)
+    /// of the target, plus a reference to a class-name receiver
+    fn extract_method_reference(
+        &self,
+        path: &Path,
+        node: Node,
+        source: &str,
+        imports: &[String],
+        result: &mut ParseResult,
+    ) {
+        let receiver = node.child(0);
+        let target = node.child(node.child_count().saturating_sub(1));
+        let (Some(receiver), Some(target)) = (receiver, target) else {
+            return;
+        };
+        let receiver_name = simple_type_name(node_text(receiver, source)).to_string();
+
+        if target.kind() == "new" {
+            let kind = ReferenceKind::Instantiation;
+            push_reference(path, target, receiver_name, kind, imports, result);
+            return;
+        }
+        if target.kind() == "identifier" {
+            let name = node_text(target, source).to_string();
+            push_reference(path, target, name, ReferenceKind::Call, imports, result);
+        }
+
+        // Type receivers are picked up as type identifiers; a bare identifier is
+        // either a class (`Foo::bar`) or a variable (`service::handle`)
+        if receiver.kind() == "identifier" {
+            let kind = if receiver_name.starts_with(char::is_uppercase) {
+                ReferenceKind::Type
+            } else {
+                ReferenceKind::Read
+            };
+            push_reference(path, receiver, receiver_name, kind, imports, result);
+        }
+    }
+
+    /// Extract access to all constants of an enum: `Color.values()`,
+    /// `Color.valueOf(name)`, `EnumSet.allOf(Color.class)` and
+    /// `Enum.valueOf(Color.class, name)`
+    fn extract_enum_bulk_access(
+        &self,
+        path: &Path,
+        node: Node,
+        source: &str,
+        imports: &[String],
+        result: &mut ParseResult,
+    ) {
+        let (Some(object), Some(name)) = (
+            node.child_by_field_name("object"),
+            node.child_by_field_name("name"),
+        ) else {
+            return;
+        };
+        let receiver = simple_type_name(node_text(object, source));
+        let method = node_text(name, source);
+
+        // `EnumSet.allOf(Color.class)`: the enum is the class literal argument
+        let class_argument = || {
+            let arguments = node.child_by_field_name("arguments")?;
+            let literal = arguments
+                .named_child(0)
+                .filter(|a| a.kind() == "class_literal")?;
+            literal
+                .named_child(0)
+                .map(|t| simple_type_name(node_text(t, source)))
+        };
+        let (enum_name, kind) = match (receiver, method) {
+            ("EnumSet", "allOf") => (class_argument(), ReferenceKind::EnumIteration),
+            ("Enum", "valueOf") => (class_argument(), ReferenceKind::EnumLookup),
+            (_, "values") => (Some(receiver), ReferenceKind::EnumIteration),
+            (_, "valueOf") => (Some(receiver), ReferenceKind::EnumLookup),
+            _ => return,
+        };
+
+        if let Some(enum_name) = enum_name.filter(|n| n.starts_with(char::is_uppercase)) {
+            push_reference(path, node, enum_name.to_string(), kind, imports, result);
+        }
+    }
+
+    /// Extract the subtypes of a `sealed ... permits A, B` clause
+    fn extract_permits(
+        &self,
+        path: &Path,
+        node: Node,
+        source: &str,
+        imports: &[String],
+        result: &mut ParseResult,
+    ) {
+        let mut cursor = node.walk();
+        for type_list in node
+            .children(&mut cursor)
+            .filter(|c| c.kind() == "type_list")
+        {
+            let mut type_cursor = type_list.walk();
+            for type_node in type_list.named_children(&mut type_cursor) {
+                let name = simple_type_name(node_text(type_node, source)).to_string();
+                let kind = ReferenceKind::SealedSubtype;
+                push_reference(path, type_node, name, kind, imports, result);
+            }
+        }
+    }
+
+    /// Extract the functional interface a lambda or method reference is converted to
+    fn extract_sam_conversion(
+        &self,
+        path: &Path,
+        node: Node,
+        source: &str,
+        imports: &[String],
+        result: &mut ParseResult,
+    ) {
+        if let Some(interface) = self.sam_target_type(node, source) {
+            push_reference(
+                path,
+                node,
+                interface,
+                ReferenceKind::SamConversion,
+                imports,
+                result,
+            );
+        }
+    }
+
+    /// Declared target type of a lambda or method reference
+    ///
+    /// Only contexts that name the type syntactically are supported: variable
+    /// and field initializers, casts and return statements. Arguments would
+    /// need overload resolution.
+    fn sam_target_type(&self, node: Node, source: &str) -> Option<String> {
+        let parent = node.parent()?;
+        let type_node = match parent.kind() {
+            "variable_declarator" => parent.parent()?.child_by_field_name("type")?,
+            "cast_expression" => parent.child_by_field_name("type")?,
+            "return_statement" => {
+                let mut current = parent;
+                loop {
+                    current = current.parent()?;
+                    match current.kind() {
+                        "method_declaration" => break current.child_by_field_name("type")?,
+                        "lambda_expression" | "class_body" => return None,
+                        _ => {}
+                    }
+                }
+            }
+            _ => return None,
+        };
+
+        let name = simple_type_name(node_text(type_node, source));
+        (!name.is_empty() && name != "var" && name.starts_with(char::is_uppercase))
+            .then(|| name.to_string())
+    }
+
     // Helper methods
 
     fn extract_modifiers(&self, node: Node, source: &str, decl: &mut Declaration) {
@@ -718,13 +1070,19 @@ impl JavaParser {
     fn extract_super_types(&self, node: Node, source: &str) -> Vec<String> {
         let mut super_types = Vec::new();
 
-        // Check superclass
+        // Check superclass, skipping the `extends` keyword
         if let Some(superclass) = node.child_by_field_name("superclass") {
-            super_types.push(node_text(superclass, source).to_string());
+            if let Some(type_node) = superclass.named_child(0) {
+                super_types.push(node_text(type_node, source).to_string());
+            }
         }
 
-        // Check interfaces
-        if let Some(interfaces) = node.child_by_field_name("interfaces") {
+        // Check implemented interfaces, and interfaces an interface extends
+        let mut cursor = node.walk();
+        let interfaces = node
+            .children(&mut cursor)
+            .filter(|c| c.kind() == "super_interfaces" || c.kind() == "extends_interfaces");
+        for interfaces in interfaces {
             let mut cursor = interfaces.walk();
             for child in interfaces.children(&mut cursor) {
                 if child.kind() == "type_list" {
@@ -769,6 +1127,10 @@ impl JavaParser {
             "object_creation_expression" => Some(ReferenceKind::Instantiation),
             "annotation" | "marker_annotation" => Some(ReferenceKind::Annotation),
             "cast_expression" => Some(ReferenceKind::Cast),
+            // `case Point(int x, int y)` deconstructs a record
+            "record_pattern" => Some(ReferenceKind::Type),
+            // `case RED:` reads the constant
+            "switch_label" => Some(ReferenceKind::Read),
             _ => None,
         }
     }
@@ -781,6 +1143,64 @@ impl JavaParser {
     }
 }
 
+/// Parameter list of a method or constructor from its `formal_parameters`
+fn signature(params: Node, source: &str) -> Signature {
+    let mut signature = Signature::default();
+    let mut cursor = params.walk();
+    for param in params.named_children(&mut cursor) {
+        let name = match param.kind() {
+            "formal_parameter" => param.child_by_field_name("name"),
+            // `String... names`: the name is in a variable declarator
+            "spread_parameter" => {
+                signature.variadic = true;
+                let mut inner = param.walk();
+                let declarator = param
+                    .named_children(&mut inner)
+                    .find(|c| c.kind() == "variable_declarator");
+                declarator.and_then(|d| d.child_by_field_name("name"))
+            }
+            _ => continue,
+        };
+        if param.kind() == "formal_parameter" {
+            signature.required += 1;
+        }
+        signature.parameter_names.push(
+            name.map(|n| node_text(n, source).to_string())
+                .unwrap_or_default(),
+        );
+    }
+    signature
+}
+
+/// Record a reference at `node`
+fn push_reference(
+    path: &Path,
+    node: Node,
+    name: String,
+    kind: ReferenceKind,
+    imports: &[String],
+    result: &mut ParseResult,
+) {
+    let location = point_to_location(
+        path,
+        node.start_position(),
+        node.end_position(),
+        node.start_byte(),
+        node.end_byte(),
+    );
+
+    result.references.push(UnresolvedReference {
+        name,
+        qualified_name: None,
+        kind,
+        location,
+        imports: imports.to_vec(),
+        receiver_type: None,
+        argument_count: None,
+        argument_names: Vec::new(),
+    });
+}
+
 impl Parser for JavaParser {
     fn parse(&self, path: &Path, contents: &str) -> Result<ParseResult> {
         let mut parser = TsParser::new();
@@ -803,8 +1223,18 @@ impl Parser for JavaParser {
         let imports = temp_parser.extract_imports(root, contents);
         result.imports = imports.clone();
         temp_parser.extract_declarations(path, root, contents, &package, &mut result)?;
+        mark_ignore_comments(&mut result.declarations, contents);
+        let branches = branch_points(root, BRANCH_KINDS);
+        measure_declarations(&mut result.declarations, contents, &branches);
         temp_parser.extract_references(path, root, contents, &imports, &mut result)?;
 
+        // Javadoc links only lower the confidence in their targets being dead
+        for comment in descendants(root).filter(|node| node.kind() == "block_comment") {
+            result
+                .references
+                .extend(doc_link_references(path, comment, contents, &imports));
+        }
+
         debug!(
             "Parsed {}: {} declarations, {} references",
             path.display(),
@@ -858,4 +1288,195 @@ mod tests {
 
         assert_eq!(result.imports.len(), 2);
     }
+
+    #[test]
+    fn test_method_references_and_sam_conversions() {
+        let parser = JavaParser::new();
+        let source = r#"
+            class Screen {
+                Listener listener = event -> handle(event);
+
+                Runnable task() {
+                    return this::refresh;
+                }
+
+                void bind(List<String> names) {
+                    names.forEach(Formatter::format);
+                    Supplier<Widget> factory = Widget::new;
+                }
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Screen.java"), source).unwrap();
+        let has = |name: &str, kind: ReferenceKind| {
+            result
+                .references
+                .iter()
+                .any(|r| r.name == name && r.kind == kind)
+        };
+
+        assert!(has("format", ReferenceKind::Call));
+        assert!(has("Formatter", ReferenceKind::Type));
+        assert!(has("refresh", ReferenceKind::Call));
+        assert!(has("Widget", ReferenceKind::Instantiation));
+        assert!(has("Listener", ReferenceKind::SamConversion));
+        assert!(has("Runnable", ReferenceKind::SamConversion));
+        assert!(has("Supplier", ReferenceKind::SamConversion));
+    }
+
+    #[test]
+    fn test_records_sealed_types_and_patterns() {
+        let parser = JavaParser::new();
+        let source = r#"
+            package com.example;
+
+            public sealed interface Shape permits Circle, Square {}
+
+            public record Circle(double radius) implements Shape {
+                public Circle {
+                    if (radius < 0) throw new IllegalArgumentException();
+                }
+            }
+
+            final class Square extends Base implements Shape {}
+
+            class Geometry {
+                double area(Shape shape) {
+                    return switch (shape) {
+                        case Circle c -> c.radius() * c.radius();
+                        case Point(int x, int y) -> 0;
+                        default -> 0;
+                    };
+                }
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Shape.java"), source).unwrap();
+        let decl = |name: &str, kind: DeclarationKind| {
+            result
+                .declarations
+                .iter()
+                .find(|d| d.name == name && d.kind == kind)
+                .unwrap_or_else(|| panic!("missing {} {:?}", name, kind))
+        };
+        let has = |name: &str, kind: ReferenceKind| {
+            result
+                .references
+                .iter()
+                .any(|r| r.name == name && r.kind == kind)
+        };
+
+        let shape = decl("Shape", DeclarationKind::Interface);
+        assert!(shape.modifiers.iter().any(|m| m == "sealed"));
+
+        let circle = decl("Circle", DeclarationKind::Class);
+        assert!(circle.modifiers.iter().any(|m| m == "record"));
+        assert_eq!(circle.super_types, vec!["Shape"]);
+        let radius = decl("radius", DeclarationKind::Field);
+        assert_eq!(radius.parent.as_ref(), Some(&circle.id));
+        let constructor = decl("Circle", DeclarationKind::Constructor);
+        assert_eq!(constructor.parent.as_ref(), Some(&circle.id));
+
+        let square = decl("Square", DeclarationKind::Class);
+        assert_eq!(square.super_types, vec!["Base", "Shape"]);
+
+        // Permitted subtypes are linked to the sealed type without using them
+        assert!(has("Square", ReferenceKind::SealedSubtype));
+        assert!(!has("Square", ReferenceKind::Type));
+
+        assert!(has("Circle", ReferenceKind::Type));
+        assert!(has("Point", ReferenceKind::Type));
+        assert!(has("radius", ReferenceKind::Call));
+    }
+
+    #[test]
+    fn test_enum_bulk_access_and_switch_labels() {
+        let parser = JavaParser::new();
+        let source = r#"
+            class Colors {
+                Color[] all() { return Color.values(); }
+                Set<Shade> shades() { return EnumSet.allOf(Shade.class); }
+                Tone parse(String name) { return Enum.valueOf(Tone.class, name); }
+                String label(Color color) {
+                    switch (color) {
+                        case RED: return "red";
+                        default: return "other";
+                    }
+                }
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Colors.java"), source).unwrap();
+        let has = |name: &str, kind: ReferenceKind| {
+            result
+                .references
+                .iter()
+                .any(|r| r.name == name && r.kind == kind)
+        };
+
+        assert!(has("Color", ReferenceKind::EnumIteration));
+        assert!(has("Shade", ReferenceKind::EnumIteration));
+        assert!(has("Tone", ReferenceKind::EnumLookup));
+        assert!(has("RED", ReferenceKind::Read));
+    }
+
+    #[test]
+    fn test_signatures_and_argument_counts() {
+        let parser = JavaParser::new();
+        let source = r#"
+            class Logger {
+                Logger(String tag) {}
+                void log(String message) {}
+                void log(String format, Object... args) {}
+                void flush() { log("a"); this.log("%s", 1, 2); }
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Logger.java"), source).unwrap();
+
+        let signatures: Vec<(&str, &Signature)> = result
+            .declarations
+            .iter()
+            .filter_map(|d| Some((d.name.as_str(), d.signature.as_ref()?)))
+            .collect();
+        assert_eq!(signatures.len(), 4);
+        assert_eq!(signatures[0].0, "Logger");
+        assert_eq!(signatures[0].1.parameter_names, vec!["tag"]);
+        assert_eq!(signatures[2].1.parameter_names, vec!["format", "args"]);
+        assert_eq!(signatures[2].1.required, 1);
+        assert!(signatures[2].1.variadic);
+        assert_eq!(signatures[3].1.required, 0);
+
+        let calls: Vec<Option<usize>> = result
+            .references
+            .iter()
+            .filter(|r| r.name == "log")
+            .map(|r| r.argument_count)
+            .collect();
+        assert_eq!(calls, vec![Some(1), Some(3)]);
+    }
+
+    #[test]
+    fn test_object_creation_is_instantiation() {
+        let parser = JavaParser::new();
+        let source = r#"
+            class Shapes {
+                Shape circle() { return new Circle(1.0); }
+                List<Square> squares() { return new ArrayList<>(); }
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Shapes.java"), source).unwrap();
+        let kind_of = |name: &str| {
+            result
+                .references
+                .iter()
+                .find(|r| r.name == name)
+                .map(|r| r.kind)
+        };
+
+        assert_eq!(kind_of("Circle"), Some(ReferenceKind::Instantiation));
+        assert_eq!(kind_of("ArrayList"), Some(ReferenceKind::Instantiation));
+        assert_eq!(kind_of("Square"), Some(ReferenceKind::Type));
+    }
 }
@@ -0,0 +1,233 @@
+// JNI native source scanner
+//
+// Kotlin/Java code used only from C/C++ has no references in the code graph.
+// This scanner extracts the JNI string literals and symbol names that refer
+// back into the JVM code:
+//
+// - `env->FindClass("com/example/Bridge")` and `FindClass(env, "...")`
+// - `GetMethodID(cls, "onEvent", "(I)V")` and the static/field variants
+// - `RegisterNatives` tables: `{"nativeInit", "(J)V", (void *) init}`
+// - Exported symbols: `Java_com_example_Bridge_nativeInit`
+
+#![allow(dead_code)] // API methods reserved for future use
+
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File extensions scanned for JNI references
+pub const NATIVE_EXTENSIONS: &[&str] = &["c", "cc", "cpp", "cxx", "h", "hh", "hpp"];
+
+/// JVM declarations referenced from native code
+#[derive(Debug, Clone, Default)]
+pub struct JniReferences {
+    /// Fully qualified class names (nested classes use `.`)
+    pub classes: BTreeSet<String>,
+    /// Member names, keyed by the class they belong to, or by None when the
+    /// class could not be determined (e.g. `GetMethodID` on a passed-in class)
+    pub members: HashMap<Option<String>, BTreeSet<String>>,
+    /// Native files that contained at least one reference
+    pub files: Vec<PathBuf>,
+}
+
+impl JniReferences {
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty() && self.members.is_empty()
+    }
+
+    /// Whether a member of `class_fqn` (if known) named `name` is referenced
+    pub fn references_member(&self, class_fqn: Option<&str>, name: &str) -> bool {
+        let in_class = class_fqn
+            .and_then(|fqn| self.members.get(&Some(fqn.to_string())))
+            .is_some_and(|names| names.contains(name));
+        in_class
+            || self
+                .members
+                .get(&None)
+                .is_some_and(|names| names.contains(name))
+    }
+}
+
+/// Scanner for C/C++ sources
+pub struct JniScanner {
+    find_class: Regex,
+    member_id: Regex,
+    native_method: Regex,
+    exported_symbol: Regex,
+}
+
+impl JniScanner {
+    pub fn new() -> Self {
+        Self {
+            find_class: Regex::new(r#"FindClass\s*\(\s*(?:\w+\s*,\s*)?"([\w/$]+)"\s*\)"#).unwrap(),
+            member_id: Regex::new(
+                r#"Get(?:Static)?(?:Method|Field)ID\s*\([^"]*"(\w+|<init>)"\s*,\s*"[^"]*"\s*\)"#,
+            )
+            .unwrap(),
+            native_method: Regex::new(r#"\{\s*"(\w+)"\s*,\s*"\([^"]*\)[^"]*"\s*,"#).unwrap(),
+            exported_symbol: Regex::new(r"\bJava_(\w+)").unwrap(),
+        }
+    }
+
+    /// Scan every native source under `root`
+    pub fn scan(&self, root: &Path) -> JniReferences {
+        let mut references = JniReferences::default();
+
+        let walker = walkdir::WalkDir::new(root).into_iter().filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            !name.starts_with('.') && name != "build" && name != ".cxx"
+        });
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+            let is_native = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|ext| NATIVE_EXTENSIONS.contains(&ext));
+            if !entry.file_type().is_file() || !is_native {
+                continue;
+            }
+
+            // Native sources are not always UTF-8; literals we need are ASCII
+            let Ok(bytes) = fs::read(path) else {
+                continue;
+            };
+            let content = String::from_utf8_lossy(&bytes);
+            if self.scan_source(&content, &mut references) {
+                references.files.push(path.to_path_buf());
+            }
+        }
+
+        references
+    }
+
+    /// Collect references from one native source; returns whether any were found
+    pub fn scan_source(&self, content: &str, references: &mut JniReferences) -> bool {
+        let mut found = false;
+
+        // Member lookups belong to the class found last in the file, which is
+        // right for the common FindClass-then-GetMethodID sequence
+        let mut file_classes = Vec::new();
+        for cap in self.find_class.captures_iter(content) {
+            let class = jni_class_name(&cap[1]);
+            references.classes.insert(class.clone());
+            file_classes.push(class);
+            found = true;
+        }
+        let owner = match file_classes.as_slice() {
+            [single] => Some(single.clone()),
+            _ => None,
+        };
+
+        for cap in self
+            .member_id
+            .captures_iter(content)
+            .chain(self.native_method.captures_iter(content))
+        {
+            references
+                .members
+                .entry(owner.clone())
+                .or_default()
+                .insert(cap[1].to_string());
+            found = true;
+        }
+
+        for cap in self.exported_symbol.captures_iter(content) {
+            if let Some((class, method)) = parse_exported_symbol(&cap[1]) {
+                references.classes.insert(class.clone());
+                references
+                    .members
+                    .entry(Some(class))
+                    .or_default()
+                    .insert(method);
+                found = true;
+            }
+        }
+
+        found
+    }
+}
+
+impl Default for JniScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `com/example/Bridge$Callback` -> `com.example.Bridge.Callback`
+fn jni_class_name(internal: &str) -> String {
+    internal.replace(['/', '$'], ".")
+}
+
+/// Decode the mangled part of `Java_<class>_<method>[__<signature>]`
+///
+/// `_1` escapes `_`, `_00024` escapes `$`, and `_` separates name parts.
+fn parse_exported_symbol(mangled: &str) -> Option<(String, String)> {
+    let mangled = mangled.split("__").next()?;
+
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut rest = mangled;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("_1") {
+            current.push('_');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("_00024") {
+            current.push('.');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('_') {
+            parts.push(std::mem::take(&mut current));
+            rest = after;
+        } else {
+            let c = rest.chars().next()?;
+            current.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    parts.push(current);
+
+    let method = parts.pop()?;
+    if parts.is_empty() || method.is_empty() {
+        return None;
+    }
+    Some((parts.join("."), method))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_jni_source() {
+        let source = r#"
+#include <jni.h>
+
+static JNINativeMethod methods[] = {
+    {"nativeInit", "(J)V", (void *) native_init},
+    {"nativeRelease", "()V", (void *) native_release},
+};
+
+JNIEXPORT jint JNI_OnLoad(JavaVM *vm, void *reserved) {
+    jclass cls = env->FindClass("com/example/media/Player$Callbacks");
+    jmethodID id = env->GetMethodID(cls, "onFrame", "(I)V");
+    env->RegisterNatives(cls, methods, 2);
+}
+
+JNIEXPORT void JNICALL
+Java_com_example_crypto_Native_1Cipher_encrypt__Ljava_lang_String_2(JNIEnv *env, jobject thiz) {}
+"#;
+        let scanner = JniScanner::new();
+        let mut references = JniReferences::default();
+        assert!(scanner.scan_source(source, &mut references));
+
+        let player = "com.example.media.Player.Callbacks";
+        let cipher = "com.example.crypto.Native_Cipher";
+        assert!(references.classes.contains(player));
+        assert!(references.classes.contains(cipher));
+        assert!(references.references_member(Some(player), "onFrame"));
+        assert!(references.references_member(Some(player), "nativeInit"));
+        assert!(references.references_member(Some(cipher), "encrypt"));
+        assert!(!references.references_member(Some("com.example.Other"), "onFrame"));
+    }
+}
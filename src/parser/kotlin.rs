@@ -1,16 +1,32 @@
 // Kotlin parser - some internal methods reserved for future use
 #![allow(dead_code)]
 
-use super::common::{node_text, point_to_location, ParseResult, Parser};
+use super::common::{
+    branch_points, descendants, doc_link_references, mark_ignore_comments, measure_declarations,
+    node_text, point_to_location, ParseResult, Parser,
+};
 use crate::graph::{
-    Declaration, DeclarationId, DeclarationKind, Language, ReferenceKind, UnresolvedReference,
-    Visibility,
+    simple_type_name, Declaration, DeclarationId, DeclarationKind, Language, Location,
+    ReferenceKind, Signature, UnresolvedReference, Visibility,
 };
 use miette::{IntoDiagnostic, Result};
 use std::path::Path;
 use tracing::debug;
 use tree_sitter::{Node, Parser as TsParser};
 
+/// Node kinds that add a branch to the code they are in
+const BRANCH_KINDS: &[&str] = &[
+    "if_expression",
+    "when_entry",
+    "for_statement",
+    "while_statement",
+    "do_while_statement",
+    "catch_block",
+    "elvis_expression",
+    "&&",
+    "||",
+];
+
 /// Kotlin source code parser using tree-sitter
 pub struct KotlinParser {
     parser: TsParser,
@@ -184,7 +200,8 @@ impl KotlinParser {
         let mut cursor = node.walk();
         let mut found_class_body = false;
         for child in node.children(&mut cursor) {
-            if child.kind() == "class_body" {
+            // Enum entries and members live in an `enum_class_body`
+            if matches!(child.kind(), "class_body" | "enum_class_body") {
                 self.extract_class_members(path, child, source, package, id.clone(), result)?;
                 found_class_body = true;
                 break;
@@ -438,6 +455,7 @@ impl KotlinParser {
 
         // Extract extension receiver type (e.g., fun String.myExtension())
         if let Some(receiver_type) = self.extract_extension_receiver(node, source) {
+            decl.receiver_type = Some(receiver_type.clone());
             // Add a reference to the receiver type so it's not marked as dead code
             result.references.push(UnresolvedReference {
                 name: receiver_type,
@@ -445,11 +463,20 @@ impl KotlinParser {
                 kind: ReferenceKind::ExtensionReceiver,
                 location: location.clone(),
                 imports: result.imports.clone(),
+                receiver_type: None,
+                argument_count: None,
+                argument_names: Vec::new(),
             });
         }
 
-        // Extract parameters
-        if let Some(params) = node.child_by_field_name("function_value_parameters") {
+        decl.signature = self.extract_signature(node, source);
+
+        // Extract parameters; the grammar has no field name for the list
+        let mut cursor = node.walk();
+        let params = node
+            .children(&mut cursor)
+            .find(|c| c.kind() == "function_value_parameters");
+        if let Some(params) = params {
             self.extract_parameters(path, params, source, decl.id.clone(), result)?;
         }
 
@@ -458,39 +485,33 @@ impl KotlinParser {
         Ok(())
     }
 
-    /// Extract the receiver type from an extension function (e.g., "String" from "fun String.myExtension()")
+    /// Extract the receiver type from an extension function or property
+    /// (e.g., "String" from "fun String.myExtension()" or "val String.slug")
     fn extract_extension_receiver(&self, node: Node, source: &str) -> Option<String> {
         let mut cursor = node.walk();
-        let mut found_fun = false;
+        let mut found_keyword = false;
 
         for child in node.children(&mut cursor) {
             let kind = child.kind();
 
-            // Track when we see 'fun' keyword
-            if kind == "fun" {
-                found_fun = true;
+            // Track when we see the 'fun', 'val' or 'var' keyword; properties
+            // wrap theirs in a binding_pattern_kind
+            if matches!(kind, "fun" | "val" | "var" | "binding_pattern_kind") {
+                found_keyword = true;
                 continue;
             }
 
-            // After 'fun', look for receiver_type or user_type before the dot
-            if found_fun {
-                if kind == "receiver_type" || kind == "type_reference" {
-                    let type_text = node_text(child, source);
-                    // Strip generic parameters if present
-                    let name = type_text.split('<').next().unwrap_or(type_text);
-                    // Take the last component of qualified names
-                    let simple_name = name.split('.').next_back().unwrap_or(name);
-                    return Some(simple_name.to_string());
-                }
-                // For simple user types
-                if kind == "user_type" {
-                    let type_text = node_text(child, source);
-                    let name = type_text.split('<').next().unwrap_or(type_text);
-                    let simple_name = name.split('.').next_back().unwrap_or(name);
-                    return Some(simple_name.to_string());
+            // After the keyword, look for receiver_type or user_type before the dot
+            if found_keyword {
+                if matches!(
+                    kind,
+                    "receiver_type" | "type_reference" | "user_type" | "nullable_type"
+                ) {
+                    // Strip generic parameters, nullability and the package
+                    return Some(simple_type_name(node_text(child, source)).to_string());
                 }
-                // Once we hit the function name (simple_identifier after receiver), stop
-                if kind == "simple_identifier" {
+                // Once we hit the name (simple_identifier or variable after receiver), stop
+                if kind == "simple_identifier" || kind == "variable_declaration" {
                     break;
                 }
             }
@@ -498,6 +519,331 @@ impl KotlinParser {
         None
     }
 
+    /// Parameter list of a function: parameter names, how many have no
+    /// default value, and whether one of them is a `vararg`
+    fn extract_signature(&self, node: Node, source: &str) -> Option<Signature> {
+        let mut cursor = node.walk();
+        let params = node
+            .children(&mut cursor)
+            .find(|c| c.kind() == "function_value_parameters")?;
+
+        let mut signature = Signature::default();
+        let mut vararg = false;
+        let mut last_required = false;
+        let mut cursor = params.walk();
+        for child in params.children(&mut cursor) {
+            match child.kind() {
+                "parameter_modifiers" => vararg = node_text(child, source).contains("vararg"),
+                "parameter" => {
+                    let mut inner = child.walk();
+                    let name = child
+                        .children(&mut inner)
+                        .find(|c| c.kind() == "simple_identifier")
+                        .map(|c| node_text(c, source).to_string())
+                        .unwrap_or_default();
+                    signature.parameter_names.push(name);
+                    last_required = !vararg;
+                    if vararg {
+                        signature.variadic = true;
+                    } else {
+                        signature.required += 1;
+                    }
+                    vararg = false;
+                }
+                // `= default` follows the parameter it belongs to
+                "=" if last_required => {
+                    signature.required -= 1;
+                    last_required = false;
+                }
+                _ => {}
+            }
+        }
+        Some(signature)
+    }
+
+    /// Number of arguments of the call whose callee is an identifier under
+    /// `parent` (`foo(a, b)`, `x.foo(a) { ... }`, `a foo b`), and the names of
+    /// those passed by name. `None` when it is not a call or uses a spread
+    /// argument, which passes any number of arguments.
+    fn call_arguments(&self, parent: Node, source: &str) -> Option<(usize, Vec<String>)> {
+        let call = match parent.kind() {
+            "call_expression" => parent,
+            "navigation_suffix" => {
+                let navigation = parent.parent()?;
+                let call = navigation.parent()?;
+                if call.kind() != "call_expression" || call.child(0) != Some(navigation) {
+                    return None;
+                }
+                call
+            }
+            "infix_expression" => return Some((1, Vec::new())),
+            _ => return None,
+        };
+
+        let mut cursor = call.walk();
+        let suffix = call
+            .children(&mut cursor)
+            .find(|c| c.kind() == "call_suffix")?;
+
+        let mut count = 0;
+        let mut named = Vec::new();
+        let mut cursor = suffix.walk();
+        for child in suffix.children(&mut cursor) {
+            match child.kind() {
+                "value_arguments" => {
+                    let mut args = child.walk();
+                    for arg in child.children(&mut args) {
+                        if arg.kind() != "value_argument" {
+                            continue;
+                        }
+                        let mut parts = arg.walk();
+                        let parts: Vec<Node> = arg.children(&mut parts).collect();
+                        // Spread arguments pass an unknown number of values
+                        if parts
+                            .iter()
+                            .any(|p| matches!(p.kind(), "*" | "spread_expression"))
+                        {
+                            return None;
+                        }
+                        if let [name, eq, ..] = parts.as_slice() {
+                            if name.kind() == "simple_identifier" && eq.kind() == "=" {
+                                named.push(node_text(*name, source).to_string());
+                            }
+                        }
+                        count += 1;
+                    }
+                }
+                // Trailing lambda
+                "annotated_lambda" => count += 1,
+                _ => {}
+            }
+        }
+        // The grammar parses `load("a") { … }` as a call of the call, with
+        // the trailing lambda in the outer call's suffix
+        if let Some(outer) = call.parent() {
+            if outer.kind() == "call_expression" && outer.child(0) == Some(call) {
+                let mut cursor = outer.walk();
+                let lambda = outer
+                    .children(&mut cursor)
+                    .filter(|c| c.kind() == "call_suffix")
+                    .any(|suffix| {
+                        let mut parts = suffix.walk();
+                        let kinds: Vec<&str> =
+                            suffix.children(&mut parts).map(|c| c.kind()).collect();
+                        kinds == ["annotated_lambda"]
+                    });
+                if lambda {
+                    count += 1;
+                }
+            }
+        }
+        Some((count, named))
+    }
+
+    /// Static type of the receiver of a member access or call, where it can
+    /// be told from the source: literals, constructor calls, `this`, and
+    /// locals, parameters and properties declared with a type or initialized
+    /// with a literal or constructor call
+    fn receiver_static_type(&self, receiver: Node, source: &str) -> Option<String> {
+        let literal_type = match receiver.kind() {
+            "string_literal" | "line_string_literal" | "multi_line_string_literal" => "String",
+            "integer_literal" | "hex_literal" | "bin_literal" => "Int",
+            "long_literal" => "Long",
+            "real_literal" if node_text(receiver, source).ends_with(['f', 'F']) => "Float",
+            "real_literal" => "Double",
+            "boolean_literal" => "Boolean",
+            "character_literal" => "Char",
+            "parenthesized_expression" => {
+                return self.receiver_static_type(receiver.named_child(0)?, source);
+            }
+            "call_expression" => {
+                // Constructor call: `User("ada").greet()`
+                let callee = receiver.child(0)?;
+                let name = node_text(callee, source);
+                return (callee.kind() == "simple_identifier"
+                    && name.starts_with(char::is_uppercase))
+                .then(|| name.to_string());
+            }
+            "this_expression" if node_text(receiver, source) == "this" => {
+                return self.enclosing_this_type(receiver, source);
+            }
+            "simple_identifier" => return self.declared_type(receiver, source),
+            "navigation_expression" => {
+                // `this.repository`, `session.user`: the declared type of the
+                // property, when the receiver's class is declared in this file
+                let owner = self.receiver_static_type(receiver.named_child(0)?, source)?;
+                let suffix = receiver.named_child(1)?;
+                let mut cursor = suffix.walk();
+                let member = suffix
+                    .named_children(&mut cursor)
+                    .find(|c| c.kind() == "simple_identifier")?;
+                return self.member_type(receiver, &owner, node_text(member, source), source);
+            }
+            _ => return None,
+        };
+        Some(literal_type.to_string())
+    }
+
+    /// Declared type of a property of the class or object named `owner`, if
+    /// the file containing `node` declares it
+    fn member_type(&self, node: Node, owner: &str, member: &str, source: &str) -> Option<String> {
+        let mut root = node;
+        while let Some(parent) = root.parent() {
+            root = parent;
+        }
+
+        let mut stack = vec![root];
+        while let Some(current) = stack.pop() {
+            let mut cursor = current.walk();
+            stack.extend(current.children(&mut cursor));
+            if !matches!(current.kind(), "class_declaration" | "object_declaration")
+                || self.get_type_name(current, source).ok().as_deref() != Some(owner)
+            {
+                continue;
+            }
+
+            let mut cursor = current.walk();
+            let scopes: Vec<Node> = current
+                .children(&mut cursor)
+                .filter(|c| matches!(c.kind(), "primary_constructor" | "class_body"))
+                .collect();
+            for scope in scopes {
+                let mut cursor = scope.walk();
+                for child in scope.children(&mut cursor) {
+                    if let Some(found) = self.variable_type(child, member, source) {
+                        return Some(found);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Static type of the receiver of a function reference: `Formatter` in
+    /// `Formatter::format`, the declared type of `viewModel` in
+    /// `viewModel::onClick`
+    fn callable_reference_receiver(&self, node: Node, source: &str) -> Option<String> {
+        let receiver = node.child(0).filter(|c| c.kind() != "::")?;
+        let text = node_text(receiver, source);
+        if text == "this" {
+            self.enclosing_this_type(receiver, source)
+        } else if text.starts_with(char::is_uppercase) {
+            Some(simple_type_name(text).to_string())
+        } else {
+            self.declared_type(receiver, source)
+        }
+    }
+
+    /// Type of `this`: the receiver of the enclosing extension, or the
+    /// enclosing class or object
+    fn enclosing_this_type(&self, node: Node, source: &str) -> Option<String> {
+        let mut current = node.parent();
+        while let Some(ancestor) = current {
+            match ancestor.kind() {
+                "function_declaration" => {
+                    if let Some(receiver) = self.extract_extension_receiver(ancestor, source) {
+                        return Some(receiver);
+                    }
+                }
+                "class_declaration" | "object_declaration" => {
+                    return self.get_type_name(ancestor, source).ok();
+                }
+                // `this` in a lambda may be a receiver we cannot see
+                "lambda_literal" | "anonymous_function" => return None,
+                _ => {}
+            }
+            current = ancestor.parent();
+        }
+        None
+    }
+
+    /// Declared type of the variable an identifier names, found in the
+    /// enclosing blocks, function parameters and classes
+    fn declared_type(&self, identifier: Node, source: &str) -> Option<String> {
+        let name = node_text(identifier, source);
+        let mut current = identifier.parent();
+        while let Some(ancestor) = current {
+            let mut scopes = vec![ancestor];
+            let mut cursor = ancestor.walk();
+            for child in ancestor.children(&mut cursor) {
+                if matches!(
+                    child.kind(),
+                    "function_value_parameters" | "primary_constructor" | "class_body"
+                ) {
+                    scopes.push(child);
+                }
+            }
+
+            for scope in scopes {
+                let mut cursor = scope.walk();
+                for child in scope.children(&mut cursor) {
+                    // Locals are only visible after their declaration
+                    let is_local =
+                        child.kind() == "property_declaration" && scope.kind() == "statements";
+                    if is_local && child.start_byte() > identifier.start_byte() {
+                        continue;
+                    }
+                    if let Some(found) = self.variable_type(child, name, source) {
+                        return Some(found);
+                    }
+                }
+            }
+            current = ancestor.parent();
+        }
+        None
+    }
+
+    /// Type of a property or parameter declaration if it declares `name`
+    fn variable_type(&self, node: Node, name: &str, source: &str) -> Option<String> {
+        let (declaration, initializer) = match node.kind() {
+            "property_declaration" => {
+                let mut cursor = node.walk();
+                let children: Vec<Node> = node.children(&mut cursor).collect();
+                let variable = children
+                    .iter()
+                    .find(|c| c.kind() == "variable_declaration")
+                    .copied()?;
+                let initializer = children
+                    .iter()
+                    .skip_while(|c| c.kind() != "=")
+                    .nth(1)
+                    .copied();
+                (variable, initializer)
+            }
+            "parameter" | "class_parameter" => (node, None),
+            _ => return None,
+        };
+
+        let mut cursor = declaration.walk();
+        let children: Vec<Node> = declaration.children(&mut cursor).collect();
+        if children
+            .iter()
+            .find(|c| c.kind() == "simple_identifier")
+            .map(|c| node_text(*c, source))
+            != Some(name)
+        {
+            return None;
+        }
+
+        let declared = children
+            .iter()
+            .find(|c| matches!(c.kind(), "user_type" | "nullable_type" | "type_reference"));
+        match (declared, initializer) {
+            (Some(declared), _) => Some(simple_type_name(node_text(*declared, source)).to_string()),
+            // `val user = User("ada")`, but not `val copy = user` or
+            // `val owner = order.customer`
+            (None, Some(initializer))
+                if !matches!(
+                    initializer.kind(),
+                    "simple_identifier" | "navigation_expression"
+                ) =>
+            {
+                self.receiver_static_type(initializer, source)
+            }
+            _ => None,
+        }
+    }
+
     fn extract_property(
         &self,
         path: &Path,
@@ -552,6 +898,33 @@ impl KotlinParser {
                     decl.annotations = self.extract_annotations(node, source);
                     decl.parent = parent.clone();
 
+                    // `var` and custom accessors, for the property accessor detector
+                    let mut keyword_cursor = node.walk();
+                    if node.children(&mut keyword_cursor).any(|c| {
+                        matches!(c.kind(), "var" | "binding_pattern_kind")
+                            && node_text(c, source) == "var"
+                    }) {
+                        decl.modifiers.push("var".to_string());
+                    }
+                    for accessor in self.property_accessors(node) {
+                        decl.modifiers.push(format!("custom-{}", accessor.kind()));
+                    }
+
+                    // Extension property (e.g., val String.slug)
+                    if let Some(receiver_type) = self.extract_extension_receiver(node, source) {
+                        decl.receiver_type = Some(receiver_type.clone());
+                        result.references.push(UnresolvedReference {
+                            name: receiver_type,
+                            qualified_name: None,
+                            kind: ReferenceKind::ExtensionReceiver,
+                            location: location.clone(),
+                            imports: result.imports.clone(),
+                            receiver_type: None,
+                            argument_count: None,
+                            argument_names: Vec::new(),
+                        });
+                    }
+
                     // Check for property delegation (by lazy, by Delegates, etc.)
                     if let Some(delegate_type) = self.extract_property_delegate(node, source) {
                         // Add delegation reference
@@ -561,6 +934,9 @@ impl KotlinParser {
                             kind: ReferenceKind::Delegation,
                             location: location.clone(),
                             imports: result.imports.clone(),
+                            receiver_type: None,
+                            argument_count: None,
+                            argument_names: Vec::new(),
                         });
                         // Mark property as delegated
                         decl.modifiers.push("delegated".to_string());
@@ -682,6 +1058,9 @@ impl KotlinParser {
                             kind: ReferenceKind::GenericArgument,
                             location,
                             imports: imports.to_vec(),
+                            receiver_type: None,
+                            argument_count: None,
+                            argument_names: Vec::new(),
                         });
 
                         // Recursively extract nested generics (e.g., Map<String, List<MyClass>>)
@@ -694,6 +1073,24 @@ impl KotlinParser {
         }
     }
 
+    /// The getter and setter of a property declaration, whether the grammar
+    /// parsed them as children or as following siblings
+    fn property_accessors<'a>(&self, node: Node<'a>) -> Vec<Node<'a>> {
+        let mut cursor = node.walk();
+        let mut accessors: Vec<Node> = node
+            .children(&mut cursor)
+            .filter(|c| matches!(c.kind(), "getter" | "setter"))
+            .collect();
+
+        let mut next = node.next_sibling();
+        while let Some(sibling) = next.filter(|s| matches!(s.kind(), "getter" | "setter")) {
+            accessors.push(sibling);
+            next = sibling.next_sibling();
+        }
+
+        accessors
+    }
+
     /// Find the end byte of a property declaration, including any getter/setter siblings.
     /// In Kotlin's tree-sitter grammar, getter/setter nodes are siblings of property_declaration,
     /// not children. We need to extend the property's byte range to include them.
@@ -766,7 +1163,11 @@ impl KotlinParser {
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
             if child.kind() == "parameter" || child.kind() == "class_parameter" {
-                if let Some(name_node) = child.child_by_field_name("simple_identifier") {
+                let mut name_cursor = child.walk();
+                let name_node = child
+                    .children(&mut name_cursor)
+                    .find(|c| c.kind() == "simple_identifier");
+                if let Some(name_node) = name_node {
                     let name = node_text(name_node, source).to_string();
                     let location = point_to_location(
                         path,
@@ -868,7 +1269,15 @@ impl KotlinParser {
         parent: DeclarationId,
         result: &mut ParseResult,
     ) -> Result<()> {
-        if let Some(name_node) = node.child_by_field_name("simple_identifier") {
+        // tree-sitter-kotlin aliases the name to `type_identifier` without a field name,
+        // so fall back to searching the children by kind
+        let mut cursor = node.walk();
+        let name_node = node.child_by_field_name("simple_identifier").or_else(|| {
+            node.children(&mut cursor)
+                .find(|c| c.kind() == "type_identifier" || c.kind() == "simple_identifier")
+        });
+
+        if let Some(name_node) = name_node {
             let name = node_text(name_node, source).to_string();
             let location = point_to_location(
                 path,
@@ -904,7 +1313,15 @@ impl KotlinParser {
         package: &Option<String>,
         result: &mut ParseResult,
     ) -> Result<()> {
-        if let Some(name_node) = node.child_by_field_name("simple_identifier") {
+        // tree-sitter-kotlin aliases the name to `type_identifier` without a field name,
+        // so fall back to searching the children by kind
+        let mut cursor = node.walk();
+        let name_node = node.child_by_field_name("simple_identifier").or_else(|| {
+            node.children(&mut cursor)
+                .find(|c| c.kind() == "type_identifier" || c.kind() == "simple_identifier")
+        });
+
+        if let Some(name_node) = name_node {
             let name = node_text(name_node, source).to_string();
             let location = point_to_location(
                 path,
@@ -928,11 +1345,50 @@ impl KotlinParser {
             self.extract_modifiers(node, source, &mut decl);
 
             result.declarations.push(decl);
+
+            // Simple aliased types are picked up by extract_references as user_type nodes
+            // contained in the alias, which links the alias to its target. Fully qualified
+            // targets (typealias Foo = com.example.Bar) can't be resolved by simple name,
+            // so record them explicitly with their qualified name.
+            if let Some(aliased) = self.extract_aliased_type(node, source) {
+                if let Some((_, simple)) = aliased.rsplit_once('.') {
+                    let location = point_to_location(
+                        path,
+                        node.start_position(),
+                        node.end_position(),
+                        node.start_byte(),
+                        node.end_byte(),
+                    );
+
+                    result.references.push(UnresolvedReference {
+                        name: simple.to_string(),
+                        qualified_name: Some(aliased.clone()),
+                        kind: ReferenceKind::Type,
+                        location,
+                        imports: result.imports.clone(),
+                        receiver_type: None,
+                        argument_count: None,
+                        argument_names: Vec::new(),
+                    });
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Get the aliased type name of a typealias, without generic arguments
+    /// e.g., "typealias Cache = com.example.LruCache<String, Bitmap>" -> "com.example.LruCache"
+    fn extract_aliased_type(&self, node: Node, source: &str) -> Option<String> {
+        let mut cursor = node.walk();
+        let user_type = node
+            .children(&mut cursor)
+            .find(|c| c.kind() == "user_type")?;
+        let text = node_text(user_type, source);
+        let base = text.split('<').next().unwrap_or(text).trim();
+        Some(base.chars().filter(|c| !c.is_whitespace()).collect())
+    }
+
     fn extract_references(
         &self,
         path: &Path,
@@ -997,12 +1453,33 @@ impl KotlinParser {
                                 current.end_byte(),
                             );
 
+                            // `receiver.name` / `receiver?.name()`: the receiver is
+                            // the first child of the navigation expression
+                            let receiver_type =
+                                if parent.kind() == "navigation_suffix" {
+                                    parent.parent().and_then(|nav| nav.child(0)).and_then(
+                                        |receiver| self.receiver_static_type(receiver, source),
+                                    )
+                                } else {
+                                    None
+                                };
+                            let (argument_count, argument_names) =
+                                match self.call_arguments(parent, source) {
+                                    Some((count, names)) if kind == ReferenceKind::Call => {
+                                        (Some(count), names)
+                                    }
+                                    _ => (None, Vec::new()),
+                                };
+
                             result.references.push(UnresolvedReference {
                                 name,
                                 qualified_name: None,
                                 kind,
                                 location,
                                 imports: imports.to_vec(),
+                                receiver_type,
+                                argument_count,
+                                argument_names,
                             });
                         }
                     }
@@ -1031,11 +1508,17 @@ impl KotlinParser {
                         kind: ReferenceKind::Type,
                         location: location.clone(),
                         imports: imports.to_vec(),
+                        receiver_type: None,
+                        argument_count: None,
+                        argument_names: Vec::new(),
                     });
 
                     // Extract generic type arguments (e.g., FeedState from List<FeedState>)
                     Self::extract_generic_type_arguments(current, source, path, imports, result);
                 }
+                "navigation_expression" | "call_expression" => {
+                    self.extract_enum_bulk_access(path, current, source, imports, result);
+                }
                 // Handle type_arguments directly for better coverage
                 "type_arguments" => {
                     Self::extract_generic_type_arguments(current, source, path, imports, result);
@@ -1069,12 +1552,16 @@ impl KotlinParser {
                             kind: ref_kind,
                             location,
                             imports: imports.to_vec(),
+                            receiver_type: None,
+                            argument_count: None,
+                            argument_names: Vec::new(),
                         });
                     }
 
                     // Also extract the method name from the right side of ::
                     // For patterns like viewModel::gameArchiveProgressChanged
                     if !is_class_literal {
+                        let receiver_type = self.callable_reference_receiver(current, source);
                         let mut ref_cursor = current.walk();
                         for child in current.children(&mut ref_cursor) {
                             if child.kind() == "simple_identifier" {
@@ -1095,6 +1582,9 @@ impl KotlinParser {
                                         kind: ReferenceKind::Call,
                                         location,
                                         imports: imports.to_vec(),
+                                        receiver_type: receiver_type.clone(),
+                                        argument_count: None,
+                                        argument_names: Vec::new(),
                                     });
                                 }
                             }
@@ -1118,6 +1608,80 @@ impl KotlinParser {
 
     // Helper methods
 
+    /// Extract access to all constants of an enum: `Color.values()`,
+    /// `Color.entries`, `Color.valueOf(name)` and `enumValues<Color>()`
+    fn extract_enum_bulk_access(
+        &self,
+        path: &Path,
+        node: Node,
+        source: &str,
+        imports: &[String],
+        result: &mut ParseResult,
+    ) {
+        let (Some(mut first), Some(second)) = (node.named_child(0), node.named_child(1)) else {
+            return;
+        };
+        // The grammar parses `a + enumValues<E>()` as a call of `a + enumValues`,
+        // so the called function is the binary expression's right operand
+        if node.kind() == "call_expression" {
+            while is_binary_expression(first.kind()) {
+                let Some(right) = first.named_child(first.named_child_count().saturating_sub(1))
+                else {
+                    return;
+                };
+                first = right;
+            }
+        }
+        let (enum_name, member) = match (node.kind(), second.kind()) {
+            ("navigation_expression", "navigation_suffix") => {
+                let Some(member) = second.named_child(0) else {
+                    return;
+                };
+                (node_text(first, source), node_text(member, source))
+            }
+            ("call_expression", "call_suffix") if first.kind() == "simple_identifier" => {
+                let mut cursor = second.walk();
+                let Some(type_arguments) = second
+                    .named_children(&mut cursor)
+                    .find(|c| c.kind() == "type_arguments")
+                else {
+                    return;
+                };
+                let type_name = node_text(type_arguments, source).trim_matches(['<', '>']);
+                (type_name, node_text(first, source))
+            }
+            _ => return,
+        };
+
+        let kind = match member {
+            "values" | "entries" | "enumValues" | "enumEntries" => ReferenceKind::EnumIteration,
+            "valueOf" | "enumValueOf" => ReferenceKind::EnumLookup,
+            _ => return,
+        };
+        let enum_name = simple_type_name(enum_name);
+        if !enum_name.starts_with(char::is_uppercase) {
+            return;
+        }
+
+        let location = point_to_location(
+            path,
+            node.start_position(),
+            node.end_position(),
+            node.start_byte(),
+            node.end_byte(),
+        );
+        result.references.push(UnresolvedReference {
+            name: enum_name.to_string(),
+            qualified_name: None,
+            kind,
+            location,
+            imports: imports.to_vec(),
+            receiver_type: None,
+            argument_count: None,
+            argument_names: Vec::new(),
+        });
+    }
+
     /// Extract references to parent classes from enum constant imports
     /// For imports like "import com.example.MyEnum.CONSTANT", this creates
     /// a reference to "MyEnum" so the enum class isn't marked as dead code.
@@ -1167,6 +1731,9 @@ impl KotlinParser {
                         kind: ReferenceKind::Type,
                         location,
                         imports: imports.to_vec(),
+                        receiver_type: None,
+                        argument_count: None,
+                        argument_names: Vec::new(),
                     });
                 }
             }
@@ -1282,6 +1849,12 @@ impl KotlinParser {
     fn determine_class_kind(&self, node: Node, source: &str) -> DeclarationKind {
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
+            // `enum` and `interface` are tokens of the declaration itself
+            match child.kind() {
+                "interface" => return DeclarationKind::Interface,
+                "enum" => return DeclarationKind::Enum,
+                _ => {}
+            }
             if child.kind() == "modifiers" {
                 let modifiers_text = node_text(child, source);
                 if modifiers_text.contains("interface") {
@@ -1435,6 +2008,9 @@ impl KotlinParser {
                                     kind: ReferenceKind::Delegation,
                                     location,
                                     imports: imports.to_vec(),
+                                    receiver_type: None,
+                                    argument_count: None,
+                                    argument_names: Vec::new(),
                                 });
                             }
                         }
@@ -1460,13 +2036,21 @@ impl KotlinParser {
         }
 
         // Also check for annotations in preceding prefix_expression siblings
-        // (tree-sitter-kotlin sometimes places annotations there instead of in modifiers)
+        // (tree-sitter-kotlin sometimes places annotations there instead of in modifiers).
+        // Their arguments then follow as a parenthesized_expression sibling.
         if let Some(prev) = node.prev_sibling() {
             if prev.kind() == "prefix_expression" {
                 let mut prefix_cursor = prev.walk();
                 for child in prev.children(&mut prefix_cursor) {
                     if child.kind() == "annotation" {
-                        annotations.push(node_text(child, source).to_string());
+                        let mut text = node_text(child, source).to_string();
+                        if let Some(arguments) = child.next_sibling().filter(|next| {
+                            next.kind() == "parenthesized_expression"
+                                && next.start_byte() == child.end_byte()
+                        }) {
+                            text.push_str(node_text(arguments, source));
+                        }
+                        annotations.push(text);
                     }
                 }
             }
@@ -1529,8 +2113,9 @@ impl KotlinParser {
             "annotation" => Some(ReferenceKind::Annotation),
             // Value expressions - identifiers used as values (function arguments, return values, etc.)
             "value_argument" | "value_arguments" => Some(ReferenceKind::Read),
-            // Property/variable access
-            "property_declaration" | "variable_declaration" => Some(ReferenceKind::Read),
+            // Property initializers; the identifier in a variable_declaration is
+            // the declared name, not a use
+            "property_declaration" => Some(ReferenceKind::Read),
             // Default parameter values: fun test(x: Int = MY_CONST)
             // The default value is a sibling of parameter node, parented by function_value_parameters
             "parameter" | "class_parameter" | "function_value_parameters" => {
@@ -1546,14 +2131,24 @@ impl KotlinParser {
             | "multiplicative_expression"
             | "conjunction_expression"
             | "disjunction_expression"
-            | "prefix_expression"
-            | "postfix_expression"
             | "infix_expression"
             | "check_expression"
             | "elvis_expression"
             | "as_expression"
             | "spread_expression"
             | "parenthesized_expression" => Some(ReferenceKind::Read),
+            // `count++` and `--count` assign to the property; `value!!` and `-value` read it
+            "prefix_expression" | "postfix_expression" => {
+                let mut cursor = parent.walk();
+                let increments = parent
+                    .children(&mut cursor)
+                    .any(|c| matches!(c.kind(), "++" | "--"));
+                Some(if increments {
+                    ReferenceKind::Write
+                } else {
+                    ReferenceKind::Read
+                })
+            }
             // Indexing and range expressions
             "indexing_expression" | "range_expression" => Some(ReferenceKind::Read),
             // If/when conditions and bodies
@@ -1765,12 +2360,30 @@ impl Parser for KotlinParser {
         let imports = temp_parser.extract_imports(root, contents);
         result.imports = imports.clone();
 
+        // Scripts run their top-level statements: the script itself holds
+        // the references made outside any declaration
+        if path.extension().is_some_and(|ext| ext == "kts") {
+            result
+                .declarations
+                .push(script_declaration(path, contents, &package));
+        }
+
         // Extract declarations
         temp_parser.extract_declarations(path, root, contents, &package, &mut result)?;
+        mark_ignore_comments(&mut result.declarations, contents);
+        let branches = branch_points(root, BRANCH_KINDS);
+        measure_declarations(&mut result.declarations, contents, &branches);
 
         // Extract references
         temp_parser.extract_references(path, root, contents, &imports, &mut result)?;
 
+        // KDoc links only lower the confidence in their targets being dead
+        for comment in descendants(root).filter(|node| node.kind() == "multiline_comment") {
+            result
+                .references
+                .extend(doc_link_references(path, comment, contents, &imports));
+        }
+
         debug!(
             "Parsed {}: {} declarations, {} references",
             path.display(),
@@ -1782,6 +2395,42 @@ impl Parser for KotlinParser {
     }
 }
 
+/// Declaration spanning a whole Kotlin script
+fn script_declaration(path: &Path, contents: &str, package: &Option<String>) -> Declaration {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut decl = Declaration::new(
+        DeclarationId::new(path.to_path_buf(), 0, contents.len()),
+        name.clone(),
+        DeclarationKind::File,
+        Location::new(path.to_path_buf(), 1, 1, 0, contents.len()),
+        Language::Kotlin,
+    );
+    decl.fully_qualified_name = Some(match package {
+        Some(package) => format!("{}.{}", package, name),
+        None => name,
+    });
+    decl
+}
+
+/// Binary operator expressions, whose last child is the right operand
+fn is_binary_expression(kind: &str) -> bool {
+    matches!(
+        kind,
+        "additive_expression"
+            | "multiplicative_expression"
+            | "comparison_expression"
+            | "equality_expression"
+            | "conjunction_expression"
+            | "disjunction_expression"
+            | "elvis_expression"
+            | "range_expression"
+            | "infix_expression"
+    )
+}
+
 impl Default for KotlinParser {
     fn default() -> Self {
         Self::new()
@@ -1824,4 +2473,366 @@ mod tests {
 
         assert_eq!(result.imports.len(), 2);
     }
+
+    #[test]
+    fn test_parse_type_alias() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            package com.example
+
+            typealias UserId = String
+            typealias Cache = com.example.cache.LruCache<String, Int>
+        "#;
+
+        let result = parser.parse(Path::new("test.kt"), source).unwrap();
+
+        let aliases: Vec<_> = result
+            .declarations
+            .iter()
+            .filter(|d| d.kind == DeclarationKind::TypeAlias)
+            .collect();
+        assert_eq!(aliases.len(), 2);
+        assert_eq!(aliases[0].name, "UserId");
+        assert_eq!(
+            aliases[0].fully_qualified_name.as_deref(),
+            Some("com.example.UserId")
+        );
+
+        // Qualified alias targets are recorded with their FQN
+        assert!(result.references.iter().any(|r| r.name == "LruCache"
+            && r.qualified_name.as_deref() == Some("com.example.cache.LruCache")));
+    }
+
+    #[test]
+    fn test_parse_gradle_script() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            plugins {
+                id("com.android.application")
+            }
+
+            tasks.register<GenerateDocs>("docs") {
+                output = Versions.docsDir
+            }
+        "#;
+
+        let result = parser
+            .parse(Path::new("app/build.gradle.kts"), source)
+            .unwrap();
+
+        let script = &result.declarations[0];
+        assert!(script.is_script());
+        assert_eq!(script.name, "build.gradle.kts");
+        assert_eq!(script.id.end, source.len());
+        assert!(result.references.iter().any(|r| r.name == "GenerateDocs"));
+        assert!(result.references.iter().any(|r| r.name == "Versions"));
+    }
+
+    #[test]
+    fn test_enum_bulk_access() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            enum class Color { RED, BLUE }
+            fun all() = Color.entries + enumValues<Shade>()
+            fun parse(name: String) = enumValueOf<Tone>(name)
+            fun label(color: Color) = when (color) {
+                Color.RED -> "red"
+                else -> "other"
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Colors.kt"), source).unwrap();
+        let has = |name: &str, kind: ReferenceKind| {
+            result
+                .references
+                .iter()
+                .any(|r| r.name == name && r.kind == kind)
+        };
+
+        assert!(has("Color", ReferenceKind::EnumIteration));
+        assert!(has("Shade", ReferenceKind::EnumIteration));
+        assert!(has("Tone", ReferenceKind::EnumLookup));
+        assert!(has("RED", ReferenceKind::Read));
+
+        let kinds: Vec<_> = result
+            .declarations
+            .iter()
+            .filter(|d| d.name == "Color" || d.name == "RED")
+            .map(|d| d.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![DeclarationKind::Enum, DeclarationKind::EnumCase]
+        );
+    }
+
+    #[test]
+    fn test_extension_receivers() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            fun String.slugify(): String = lowercase()
+            fun Money.slugify(): String = amount.toString()
+            val String.initial: Char get() = first()
+
+            fun render(price: Money) {
+                val title = "Hello"
+                title.slugify()
+                price.slugify()
+                Money(1).slugify()
+                unknown().slugify()
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Slugs.kt"), source).unwrap();
+
+        let receivers: Vec<(&str, Option<&str>)> = result
+            .declarations
+            .iter()
+            .filter(|d| d.kind != DeclarationKind::Parameter)
+            .map(|d| (d.name.as_str(), d.receiver_type.as_deref()))
+            .collect();
+        assert!(receivers.contains(&("slugify", Some("String"))));
+        assert!(receivers.contains(&("slugify", Some("Money"))));
+        assert!(receivers.contains(&("initial", Some("String"))));
+        assert!(receivers.contains(&("render", None)));
+
+        let calls: Vec<Option<&str>> = result
+            .references
+            .iter()
+            .filter(|r| r.name == "slugify")
+            .map(|r| r.receiver_type.as_deref())
+            .collect();
+        assert_eq!(
+            calls,
+            vec![Some("String"), Some("Money"), Some("Money"), None]
+        );
+    }
+
+    #[test]
+    fn test_member_receivers() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            class UserRepository { fun findById(id: String) {} }
+            class OrderRepository { fun findById(id: String) {} }
+            class Session { val repository: UserRepository = UserRepository() }
+
+            class Orders(private val orders: OrderRepository) {
+                val users = UserRepository()
+
+                fun load(id: String, session: Session) {
+                    orders.findById(id)
+                    this.users.findById(id)
+                    session.repository.findById(id)
+                    session.current().findById(id)
+                }
+
+                val lookups = listOf(orders::findById, UserRepository::findById)
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Orders.kt"), source).unwrap();
+        let calls: Vec<Option<&str>> = result
+            .references
+            .iter()
+            .filter(|r| r.name == "findById")
+            .map(|r| r.receiver_type.as_deref())
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                Some("OrderRepository"),
+                Some("UserRepository"),
+                Some("UserRepository"),
+                None,
+                // Function references
+                Some("OrderRepository"),
+                Some("UserRepository"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_signatures_and_argument_counts() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            fun load(id: String, retries: Int = 3, vararg tags: String) {}
+            fun load(id: String, onDone: () -> Unit) {}
+
+            fun main() {
+                load("a")
+                repo.load("a", retries = 1)
+                load("a") { println() }
+                load(*ids)
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Loader.kt"), source).unwrap();
+
+        let signatures: Vec<&Signature> = result
+            .declarations
+            .iter()
+            .filter(|d| d.name == "load")
+            .filter_map(|d| d.signature.as_ref())
+            .collect();
+        assert_eq!(signatures.len(), 2);
+        assert_eq!(signatures[0].parameter_names, vec!["id", "retries", "tags"]);
+        assert_eq!(signatures[0].required, 1);
+        assert!(signatures[0].variadic);
+        assert_eq!(signatures[1].required, 2);
+        assert!(!signatures[1].variadic);
+
+        let calls: Vec<(Option<usize>, &[String])> = result
+            .references
+            .iter()
+            .filter(|r| r.name == "load")
+            .map(|r| (r.argument_count, r.argument_names.as_slice()))
+            .collect();
+        assert_eq!(calls.len(), 4);
+        assert_eq!(calls[0], (Some(1), &[][..]));
+        assert_eq!(calls[1], (Some(2), &["retries".to_string()][..]));
+        assert_eq!(calls[2], (Some(2), &[][..]));
+        assert_eq!(calls[3], (None, &[][..]));
+    }
+
+    #[test]
+    fn test_top_level_annotation_arguments() {
+        let parser = KotlinParser::new();
+        let source = "fun main() {}\n\n@Suppress(\"DeadCode\")\nfun legacyExport() {}\n";
+
+        let result = parser.parse(Path::new("Main.kt"), source).unwrap();
+        let legacy = result
+            .declarations
+            .iter()
+            .find(|d| d.name == "legacyExport")
+            .unwrap();
+        assert_eq!(legacy.annotations, vec!["@Suppress(\"DeadCode\")"]);
+    }
+
+    #[test]
+    fn test_property_mutability_and_accessors() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            class Counter {
+                private var count = 0
+                val limit = 10
+                var label: String = ""
+                    get() = field.uppercase()
+                    set(value) { field = value.trim() }
+
+                fun tick() {
+                    count++
+                    --count
+                    println(-limit)
+                }
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Counter.kt"), source).unwrap();
+        let modifiers = |name: &str| {
+            result
+                .declarations
+                .iter()
+                .find(|d| d.name == name)
+                .map(|d| d.modifiers.clone())
+                .unwrap_or_default()
+        };
+
+        assert!(modifiers("count").contains(&"var".to_string()));
+        assert!(!modifiers("limit").contains(&"var".to_string()));
+        let label = modifiers("label");
+        assert!(label.contains(&"var".to_string()));
+        assert!(label.contains(&"custom-getter".to_string()));
+        assert!(label.contains(&"custom-setter".to_string()));
+
+        let kinds = |name: &str| -> Vec<ReferenceKind> {
+            result
+                .references
+                .iter()
+                .filter(|r| r.name == name)
+                .map(|r| r.kind)
+                .collect()
+        };
+        assert_eq!(
+            kinds("count"),
+            vec![ReferenceKind::Write, ReferenceKind::Write]
+        );
+        assert_eq!(kinds("limit"), vec![ReferenceKind::Read]);
+    }
+
+    #[test]
+    fn test_kdoc_links() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            /**
+             * Replaces [legacyLoad], see [Feed.render].
+             */
+            fun load() {}
+
+            /* Not KDoc: [ignored] */
+            fun legacyLoad() {}
+        "#;
+
+        let result = parser.parse(Path::new("Feed.kt"), source).unwrap();
+        let links: Vec<_> = result
+            .references
+            .iter()
+            .filter(|r| r.kind == ReferenceKind::Documentation)
+            .collect();
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].name, "legacyLoad");
+        assert_eq!(links[0].location.line, 3);
+        assert_eq!(links[1].name, "render");
+        assert_eq!(links[1].qualified_name.as_deref(), Some("Feed.render"));
+    }
+
+    #[test]
+    fn test_ignore_comment_modifier() {
+        use crate::parser::IGNORE_COMMENT;
+
+        let parser = KotlinParser::new();
+        let source = r#"
+            class Feed {
+                // searchdeadcode:ignore called from the debug menu
+                @JvmStatic
+                fun dump() {}
+
+                fun render() {}
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Feed.kt"), source).unwrap();
+        let ignored = |name: &str| {
+            result
+                .declarations
+                .iter()
+                .find(|d| d.name == name)
+                .is_some_and(|d| d.modifiers.iter().any(|m| m == IGNORE_COMMENT))
+        };
+
+        assert!(ignored("dump"));
+        assert!(!ignored("render"));
+        assert!(!ignored("Feed"));
+    }
+
+    #[test]
+    fn test_declaration_size() {
+        let parser = KotlinParser::new();
+        let source = "class Cache {\n    fun get(key: String?): String {\n        if (key == null || key.isEmpty()) return \"\"\n        return key ?: \"\"\n    }\n}\n";
+
+        let result = parser.parse(Path::new("Cache.kt"), source).unwrap();
+        let size = |name: &str| {
+            result
+                .declarations
+                .iter()
+                .find(|d| d.name == name)
+                .and_then(|d| d.size)
+                .unwrap()
+        };
+
+        assert_eq!(size("get").lines, 4);
+        assert_eq!(size("get").branches, 3);
+        assert_eq!(size("Cache").lines, 6);
+        assert_eq!(size("Cache").bytes, source.trim_end().len());
+    }
 }
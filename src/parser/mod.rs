@@ -1,8 +1,16 @@
 mod common;
+mod compose;
+mod function_params;
 mod java;
+mod jni;
 mod kotlin;
+mod symbol_dump;
 pub mod xml;
 
-pub use common::Parser;
+pub use common::{ParseResult, Parser, IGNORE_COMMENT};
+pub use compose::{scan_compose, CallArguments, ComposeFacts, DefaultedParameter};
+pub use function_params::{scan_function_parameters, FunctionParameter};
 pub use java::JavaParser;
+pub use jni::{JniReferences, JniScanner};
 pub use kotlin::KotlinParser;
+pub use symbol_dump::{Frontend, SymbolDump};
@@ -0,0 +1,257 @@
+//! Symbol dumps from the Kotlin compiler, for `--frontend bytecode`
+//!
+//! tree-sitter only sees syntax, so calls through inferred types, extension
+//! receivers and overloads are resolved by name, arity and receiver guesses.
+//! A companion KSP processor (or compiler plugin) can write what the compiler
+//! resolved to a JSON file:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "symbols": [
+//!     { "fqn": "com.example.FeedRepository.load", "line": 12 }
+//!   ],
+//!   "references": [
+//!     {
+//!       "from": "com.example.FeedViewModel.refresh",
+//!       "to": "com.example.FeedRepository.load",
+//!       "to_line": 12,
+//!       "kind": "call",
+//!       "file": "app/src/main/kotlin/com/example/FeedViewModel.kt",
+//!       "line": 30
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Symbols and reference ends are matched to the parsed declarations by
+//! fully qualified name, and by line among overloads. The compiler's
+//! references are added next to the ones resolved from source; symbols the
+//! parser did not find are counted, so parser gaps show up in the logs.
+
+use crate::graph::{DeclarationId, Graph, Location, Reference, ReferenceKind};
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Version of the dump format this build reads
+pub const SYMBOL_DUMP_VERSION: u32 = 1;
+
+/// Where the reference graph comes from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Frontend {
+    /// Parse the sources with tree-sitter
+    #[default]
+    TreeSitter,
+    /// Parse the sources, then add the references the compiler resolved
+    /// from symbol dumps
+    Bytecode,
+}
+
+/// A symbol dump written by the companion KSP processor
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SymbolDump {
+    pub version: u32,
+    /// Declarations the compiler saw
+    #[serde(default)]
+    pub symbols: Vec<DumpedSymbol>,
+    /// References the compiler resolved
+    #[serde(default)]
+    pub references: Vec<DumpedReference>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DumpedSymbol {
+    pub fqn: String,
+    /// Line of the declaration, to tell overloads apart
+    #[serde(default)]
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DumpedReference {
+    /// Fully qualified name of the referencing declaration
+    pub from: String,
+    #[serde(default)]
+    pub from_line: Option<usize>,
+    /// Fully qualified name of the referenced declaration
+    pub to: String,
+    #[serde(default)]
+    pub to_line: Option<usize>,
+    /// call, read, write, instantiation, inheritance, override, annotation,
+    /// reflection; anything else counts as a type reference
+    #[serde(default)]
+    pub kind: String,
+    /// File of the reference, relative to the project root or absolute
+    #[serde(default)]
+    pub file: PathBuf,
+    #[serde(default)]
+    pub line: usize,
+}
+
+/// What applying a dump changed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SymbolDumpStats {
+    /// Compiler references added to the graph
+    pub references_added: usize,
+    /// References with an end the parser did not find
+    pub unresolved_references: usize,
+    /// Symbols the parser did not find
+    pub missing_symbols: usize,
+}
+
+impl SymbolDump {
+    /// Read a dump, rejecting versions this build does not understand
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to read symbol dump {}", path.display()))?;
+        let dump: Self = serde_json::from_str(&content)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Invalid symbol dump {}", path.display()))?;
+        if dump.version != SYMBOL_DUMP_VERSION {
+            return Err(miette!(
+                "Symbol dump {} has version {}, expected {}",
+                path.display(),
+                dump.version,
+                SYMBOL_DUMP_VERSION
+            ));
+        }
+        Ok(dump)
+    }
+
+    /// Add the compiler's references to a graph parsed from the sources
+    pub fn apply(&self, graph: &mut Graph, root: &Path) -> SymbolDumpStats {
+        let mut stats = SymbolDumpStats {
+            missing_symbols: self
+                .symbols
+                .iter()
+                .filter(|symbol| resolve(graph, &symbol.fqn, symbol.line).is_empty())
+                .count(),
+            ..SymbolDumpStats::default()
+        };
+
+        for reference in &self.references {
+            let from = resolve(graph, &reference.from, reference.from_line);
+            // Ambiguous targets keep every candidate alive
+            let targets = resolve(graph, &reference.to, reference.to_line);
+            let Some(from) = from.first() else {
+                stats.unresolved_references += 1;
+                continue;
+            };
+            if targets.is_empty() {
+                stats.unresolved_references += 1;
+                continue;
+            }
+
+            let file = if reference.file.is_absolute() {
+                reference.file.clone()
+            } else {
+                root.join(&reference.file)
+            };
+            let name = reference
+                .to
+                .rsplit('.')
+                .next()
+                .unwrap_or(&reference.to)
+                .to_string();
+            for to in &targets {
+                let mut edge = Reference::new(
+                    reference_kind(&reference.kind),
+                    Location::new(file.clone(), reference.line, 1, 0, 0),
+                    name.clone(),
+                );
+                edge.is_qualified = true;
+                graph.add_reference(from, to, edge);
+                stats.references_added += 1;
+            }
+        }
+
+        stats
+    }
+}
+
+/// Parsed declarations with this fully qualified name, narrowed to the one
+/// on `line` when there are several
+fn resolve(graph: &Graph, fqn: &str, line: Option<usize>) -> Vec<DeclarationId> {
+    let candidates = graph.find_all_by_fqn(fqn);
+    if candidates.len() > 1 {
+        if let Some(decl) =
+            line.and_then(|line| candidates.iter().find(|decl| decl.location.line == line))
+        {
+            return vec![decl.id.clone()];
+        }
+    }
+    candidates.into_iter().map(|decl| decl.id.clone()).collect()
+}
+
+fn reference_kind(kind: &str) -> ReferenceKind {
+    match kind {
+        "call" => ReferenceKind::Call,
+        "read" => ReferenceKind::Read,
+        "write" => ReferenceKind::Write,
+        "instantiation" => ReferenceKind::Instantiation,
+        "inheritance" => ReferenceKind::Inheritance,
+        "override" => ReferenceKind::Override,
+        "annotation" => ReferenceKind::Annotation,
+        "reflection" => ReferenceKind::Reflection,
+        _ => ReferenceKind::Type,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Declaration, DeclarationKind, Language};
+
+    fn add(graph: &mut Graph, name: &str, fqn: &str, line: usize) -> DeclarationId {
+        let file = PathBuf::from("/project/Feed.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            DeclarationKind::Function,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        );
+        decl.fully_qualified_name = Some(fqn.to_string());
+        graph.add_declaration(decl)
+    }
+
+    #[test]
+    fn test_apply_symbol_dump() {
+        let mut graph = Graph::new();
+        let refresh = add(&mut graph, "refresh", "com.example.refresh", 1);
+        let format_int = add(&mut graph, "format", "com.example.format", 5);
+        let format_text = add(&mut graph, "format", "com.example.format", 9);
+
+        let dump: SymbolDump = serde_json::from_str(
+            r#"{
+                "version": 1,
+                "symbols": [
+                    { "fqn": "com.example.refresh" },
+                    { "fqn": "com.example.generatedHelper", "line": 3 }
+                ],
+                "references": [
+                    { "from": "com.example.refresh", "to": "com.example.format",
+                      "to_line": 9, "kind": "call", "file": "Feed.kt", "line": 2 },
+                    { "from": "com.example.refresh", "to": "kotlin.io.println",
+                      "kind": "call", "file": "Feed.kt", "line": 3 }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let stats = dump.apply(&mut graph, Path::new("/project"));
+        assert_eq!(
+            stats,
+            SymbolDumpStats {
+                references_added: 1,
+                unresolved_references: 1,
+                missing_symbols: 1,
+            }
+        );
+        assert!(graph.is_referenced(&format_text));
+        assert!(!graph.is_referenced(&format_int));
+        assert!(!graph.is_referenced(&refresh));
+    }
+}
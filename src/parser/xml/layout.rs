@@ -157,6 +157,57 @@ impl LayoutParser {
     }
 }
 
+impl LayoutParser {
+    /// Generated ViewBinding/DataBinding class for a layout
+    /// e.g., "activity_main" -> "ActivityMainBinding"
+    pub fn binding_class_name(layout_name: &str) -> String {
+        let mut name: String = layout_name
+            .split('_')
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let mut chars = part.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect();
+        name.push_str("Binding");
+        name
+    }
+
+    /// Layout a generated binding class was created from
+    /// e.g., "ActivityMainBinding" -> "activity_main"
+    pub fn layout_name_for_binding(class_name: &str) -> Option<String> {
+        let prefix = class_name.strip_suffix("Binding")?;
+        if !prefix.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return None;
+        }
+        Some(Self::camel_to_snake(prefix))
+    }
+
+    /// View id exposed as a binding field, e.g. "toolbarTitle" -> "toolbar_title"
+    pub fn id_for_binding_field(field: &str) -> String {
+        Self::camel_to_snake(field)
+    }
+
+    /// Convert a camelCase/PascalCase name to snake_case
+    fn camel_to_snake(name: &str) -> String {
+        let mut result = String::with_capacity(name.len() + 4);
+        for (i, c) in name.chars().enumerate() {
+            if c.is_uppercase() {
+                if i > 0 {
+                    result.push('_');
+                }
+                result.extend(c.to_lowercase());
+            } else {
+                result.push(c);
+            }
+        }
+        result
+    }
+}
+
 impl Default for LayoutParser {
     fn default() -> Self {
         Self::new()
@@ -217,4 +268,25 @@ mod tests {
 
         assert!(result.class_references.contains(".MainActivity"));
     }
+
+    #[test]
+    fn test_binding_name_mapping() {
+        assert_eq!(
+            LayoutParser::binding_class_name("activity_main"),
+            "ActivityMainBinding"
+        );
+        assert_eq!(
+            LayoutParser::layout_name_for_binding("ActivityMainBinding").as_deref(),
+            Some("activity_main")
+        );
+        assert_eq!(
+            LayoutParser::layout_name_for_binding("ItemUser2Binding").as_deref(),
+            Some("item_user2")
+        );
+        assert_eq!(LayoutParser::layout_name_for_binding("dataBinding"), None);
+        assert_eq!(
+            LayoutParser::id_for_binding_field("toolbarTitle"),
+            "toolbar_title"
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use super::XmlParseResult;
+use super::{ManifestComponent, XmlParseResult};
 use miette::Result;
 use quick_xml::events::Event;
 use quick_xml::Reader;
@@ -20,9 +20,19 @@ impl ManifestParser {
         reader.trim_text(true);
 
         let mut buf = Vec::new();
+        // Component whose intent filters are being read
+        let mut current: Option<String> = None;
 
         loop {
-            match reader.read_event_into(&mut buf) {
+            let event = reader.read_event_into(&mut buf);
+            let opens_element = matches!(event, Ok(Event::Start(_)));
+            match event {
+                Ok(Event::End(ref e)) => {
+                    let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    if is_component_tag(&tag_name) {
+                        current = None;
+                    }
+                }
                 Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                     let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
 
@@ -37,16 +47,43 @@ impl ManifestParser {
                     }
 
                     // Extract android:name attributes from component declarations
-                    if matches!(
-                        tag_name.as_str(),
-                        "activity" | "service" | "receiver" | "provider" | "application"
-                    ) {
+                    if is_component_tag(&tag_name) {
+                        let mut component = ManifestComponent {
+                            tag: tag_name.clone(),
+                            ..Default::default()
+                        };
+                        let mut class_name = None;
                         for attr in e.attributes().filter_map(|a| a.ok()) {
                             let key = String::from_utf8_lossy(attr.key.as_ref());
+                            let value = String::from_utf8_lossy(&attr.value).to_string();
                             if key == "android:name" || key.ends_with(":name") {
-                                let value = String::from_utf8_lossy(&attr.value).to_string();
-                                let class_name = self.resolve_class_name(&value, &result.package);
-                                result.class_references.insert(class_name);
+                                class_name = Some(self.resolve_class_name(&value, &result.package));
+                            } else if key == "android:permission" {
+                                component.permission = Some(value);
+                            }
+                        }
+                        if let Some(class_name) = class_name {
+                            result.class_references.insert(class_name.clone());
+                            result.components.insert(class_name.clone(), component);
+                            if opens_element {
+                                current = Some(class_name);
+                            }
+                        }
+                    }
+
+                    // Collect intent filter actions of the enclosing component
+                    if tag_name == "action" {
+                        let component = current
+                            .as_ref()
+                            .and_then(|name| result.components.get_mut(name));
+                        if let Some(component) = component {
+                            for attr in e.attributes().filter_map(|a| a.ok()) {
+                                let key = String::from_utf8_lossy(attr.key.as_ref());
+                                if key == "android:name" || key.ends_with(":name") {
+                                    component
+                                        .actions
+                                        .insert(String::from_utf8_lossy(&attr.value).to_string());
+                                }
                             }
                         }
                     }
@@ -113,6 +150,13 @@ impl ManifestParser {
     }
 }
 
+fn is_component_tag(tag_name: &str) -> bool {
+    matches!(
+        tag_name,
+        "activity" | "service" | "receiver" | "provider" | "application"
+    )
+}
+
 impl Default for ManifestParser {
     fn default() -> Self {
         Self::new()
@@ -153,6 +197,43 @@ mod tests {
             .contains("com.example.app.MyApplication"));
     }
 
+    #[test]
+    fn test_parse_component_intent_filters() {
+        let parser = ManifestParser::new();
+        let manifest = r#"
+            <manifest xmlns:android="http://schemas.android.com/apk/res/android"
+                package="com.example.app">
+                <application>
+                    <receiver android:name=".StocksWidget" android:exported="false">
+                        <intent-filter>
+                            <action android:name="android.appwidget.action.APPWIDGET_UPDATE" />
+                        </intent-filter>
+                        <meta-data android:name="android.appwidget.provider"
+                            android:resource="@xml/stocks_widget_info" />
+                    </receiver>
+                    <service android:name=".QuickToggle"
+                        android:permission="android.permission.BIND_QUICK_SETTINGS_TILE" />
+                </application>
+            </manifest>
+        "#;
+
+        let result = parser
+            .parse(Path::new("AndroidManifest.xml"), manifest)
+            .unwrap();
+
+        let widget = &result.components["com.example.app.StocksWidget"];
+        assert_eq!(widget.tag, "receiver");
+        assert!(widget
+            .actions
+            .contains("android.appwidget.action.APPWIDGET_UPDATE"));
+        let tile = &result.components["com.example.app.QuickToggle"];
+        assert!(tile.actions.is_empty());
+        assert_eq!(
+            tile.permission.as_deref(),
+            Some("android.permission.BIND_QUICK_SETTINGS_TILE")
+        );
+    }
+
     #[test]
     fn test_resolve_class_name() {
         let parser = ManifestParser::new();
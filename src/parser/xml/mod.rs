@@ -5,13 +5,15 @@ mod layout;
 mod manifest;
 mod menu;
 mod navigation;
+mod resource;
 
 pub use layout::LayoutParser;
 pub use manifest::ManifestParser;
 pub use menu::MenuParser;
 pub use navigation::NavigationParser;
+pub use resource::XmlResourceParser;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// Result of parsing Android XML files
 #[derive(Debug, Default)]
@@ -21,6 +23,23 @@ pub struct XmlParseResult {
 
     /// Package name from manifest
     pub package: Option<String>,
+
+    /// Components declared in the manifest, by class name
+    pub components: HashMap<String, ManifestComponent>,
+
+    /// Root element of the file (`shortcuts`, `appwidget-provider`, ...)
+    pub root_tag: Option<String>,
+}
+
+/// A component declared in AndroidManifest.xml
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ManifestComponent {
+    /// Element name: activity, service, receiver, provider or application
+    pub tag: String,
+    /// Actions of its intent filters
+    pub actions: HashSet<String>,
+    /// Permission a client needs to bind or start it (`android:permission`)
+    pub permission: Option<String>,
 }
 
 impl XmlParseResult {
@@ -30,8 +49,12 @@ impl XmlParseResult {
 
     pub fn merge(&mut self, other: XmlParseResult) {
         self.class_references.extend(other.class_references);
+        self.components.extend(other.components);
         if self.package.is_none() {
             self.package = other.package;
         }
+        if self.root_tag.is_none() {
+            self.root_tag = other.root_tag;
+        }
     }
 }
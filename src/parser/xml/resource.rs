@@ -0,0 +1,127 @@
+// XML resource parser
+//
+// Parses files under res/xml that name classes the system starts on the
+// app's behalf: static shortcuts (shortcuts.xml), app widget configuration
+// activities (appwidget-provider) and settings activities of services
+// (accessibility-service, input-method, ...).
+
+use super::XmlParseResult;
+use miette::Result;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::path::Path;
+use tracing::debug;
+
+/// Attributes holding a class name
+const CLASS_ATTRIBUTES: &[&str] = &["targetClass", "configure", "settingsActivity"];
+
+/// Parser for res/xml resource files
+pub struct XmlResourceParser;
+
+impl XmlResourceParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse an XML resource and extract the classes it names
+    pub fn parse(&self, path: &Path, contents: &str) -> Result<XmlParseResult> {
+        let mut result = XmlParseResult::new();
+        let mut reader = Reader::from_str(contents);
+        reader.trim_text(true);
+
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                    let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    if result.root_tag.is_none() {
+                        result.root_tag = Some(tag_name);
+                    }
+
+                    // <intent android:targetClass="com.example.ComposeActivity" />
+                    // <appwidget-provider android:configure="com.example.ConfigActivity" />
+                    for attr in e.attributes().filter_map(|a| a.ok()) {
+                        let key = String::from_utf8_lossy(attr.key.as_ref());
+                        let local_name = key.rsplit(':').next().unwrap_or(&key);
+                        if CLASS_ATTRIBUTES.contains(&local_name) {
+                            let value = String::from_utf8_lossy(&attr.value).to_string();
+                            if value.contains('.') {
+                                result.class_references.insert(value);
+                            }
+                        }
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => {
+                    debug!("Error parsing XML resource {}: {:?}", path.display(), e);
+                    break;
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        debug!(
+            "Parsed XML resource {}: {} class references",
+            path.display(),
+            result.class_references.len()
+        );
+
+        Ok(result)
+    }
+}
+
+impl Default for XmlResourceParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shortcuts() {
+        let parser = XmlResourceParser::new();
+        let shortcuts = r#"
+            <shortcuts xmlns:android="http://schemas.android.com/apk/res/android">
+                <shortcut android:shortcutId="compose"
+                    android:shortcutShortLabel="@string/compose_short">
+                    <intent android:action="android.intent.action.VIEW"
+                        android:targetPackage="com.example.app"
+                        android:targetClass="com.example.app.ComposeActivity" />
+                </shortcut>
+            </shortcuts>
+        "#;
+
+        let result = parser.parse(Path::new("shortcuts.xml"), shortcuts).unwrap();
+
+        assert_eq!(result.root_tag.as_deref(), Some("shortcuts"));
+        assert!(result
+            .class_references
+            .contains("com.example.app.ComposeActivity"));
+        assert_eq!(result.class_references.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_widget_and_accessibility_settings() {
+        let parser = XmlResourceParser::new();
+        let widget = r#"<appwidget-provider xmlns:android="http://schemas.android.com/apk/res/android"
+            android:configure="com.example.app.WidgetConfigActivity"
+            android:initialLayout="@layout/widget" />"#;
+        let result = parser.parse(Path::new("widget_info.xml"), widget).unwrap();
+        assert_eq!(result.root_tag.as_deref(), Some("appwidget-provider"));
+        assert!(result
+            .class_references
+            .contains("com.example.app.WidgetConfigActivity"));
+
+        let service = r#"<accessibility-service xmlns:android="http://schemas.android.com/apk/res/android"
+            android:settingsActivity="com.example.app.A11ySettingsActivity" />"#;
+        let result = parser.parse(Path::new("a11y.xml"), service).unwrap();
+        assert!(result
+            .class_references
+            .contains("com.example.app.A11ySettingsActivity"));
+    }
+}
@@ -0,0 +1,381 @@
+use crate::analysis::detectors::{
+    BroadcastActionAnalysis, BundleArgumentAnalysis, DaoCollectionAnalysis, DeepLinkAnalysis,
+    DetectorRegistry, IntentExtraAnalysis, NavigationAnalysis, PrefKeyAnalysis,
+    SharedPrefsAnalysis,
+};
+use crate::analysis::event_bus::EventBusUsage;
+use crate::analysis::module_deps::ModuleDependencyGraph;
+use crate::analysis::resources::ResourceAnalysis;
+use crate::analysis::scheduled_work::ScheduledWork;
+use crate::analysis::{
+    Confidence, CycleInfo, CycleStats, DeadCode, DeletionUnit, EntryPointCategory, EntrySet,
+    EntrySetDiff, MemberRetention,
+};
+use crate::baseline::BaselineStats;
+use crate::config::{Config, DetectorsConfig};
+use crate::coverage::CoverageData;
+use crate::discovery::SourceFile;
+use crate::graph::{DeclarationId, Graph, SkippedFile};
+use crate::parser::Frontend;
+use crate::proguard::{ProguardSeeds, ProguardUsage};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Options controlling which stages do work and how
+#[derive(Debug, Clone)]
+pub struct PipelineOptions {
+    /// Parse files and run reachability in parallel
+    pub parallel: bool,
+    /// Worker threads in parallel mode (all cores when `None`)
+    pub jobs: Option<usize>,
+    /// Megabytes of parse results held in memory before spilling to disk
+    pub max_memory_mb: Option<usize>,
+    /// Fail on the first file that cannot be parsed instead of skipping it
+    pub strict_parse: bool,
+    /// Use the aggressive DeepAnalyzer
+    pub deep: bool,
+    /// In deep mode, keep overrides of used super type members alive
+    /// instead of reporting them as not deletable independently
+    pub deep_respect_overrides: bool,
+    /// Cross-validate with ProGuard data (requires `proguard_usage`)
+    pub enhanced: bool,
+    /// Show a progress bar while parsing sequentially
+    pub show_progress: bool,
+    /// Where the reference graph comes from
+    pub frontend: Frontend,
+    /// Compiler symbol dumps read by the bytecode frontend
+    pub symbol_dumps: Vec<PathBuf>,
+
+    /// ProGuard/R8 usage.txt file
+    pub proguard_usage: Option<PathBuf>,
+    /// Keep rule files in addition to the discovered proguard-rules.pro files
+    pub proguard_rules: Vec<PathBuf>,
+    /// R8 seeds.txt file, listing the declarations matched by keep rules
+    pub proguard_seeds: Option<PathBuf>,
+    /// Report declarations that are only alive because of a keep rule
+    pub report_kept: bool,
+    /// Prebuilt .jar/.aar/.class files whose references retain declarations
+    pub external_usage: Vec<PathBuf>,
+    /// The project's compiled classes, to cross-check findings against
+    pub compiled_classes: Vec<PathBuf>,
+    /// Coverage files (JaCoCo, Kover, LCOV, traces)
+    pub coverage_files: Vec<PathBuf>,
+    /// Also load the JaCoCo and Kover reports of every Gradle module
+    pub coverage_auto: bool,
+    /// Accumulate coverage across runs in this store
+    pub coverage_store: Option<PathBuf>,
+    /// Ignore runtime evidence older than this
+    pub coverage_max_age: Option<Duration>,
+    /// Entry points reachability starts from
+    pub entry_set: EntrySet,
+
+    /// Ids of the enabled optional detectors (see [`DetectorRegistry`])
+    pub detectors: BTreeSet<String>,
+    /// Custom rule file (discovered in the project root if not set)
+    pub rules_file: Option<PathBuf>,
+    /// Plugin commands given on the command line, run after those of the
+    /// configuration
+    pub plugins: Vec<String>,
+
+    /// Minimum confidence level to keep
+    pub min_confidence: Confidence,
+    /// Only keep findings confirmed by runtime coverage
+    pub runtime_only: bool,
+    /// Drop findings carrying any of these risk tags
+    pub exclude_risk: Vec<String>,
+    /// Drop findings whose declaration spans fewer lines
+    pub min_size: usize,
+    /// Tag findings linked from KDoc/Javadoc, lowering their confidence
+    pub doc_links: bool,
+
+    /// Baseline file used to filter existing issues
+    pub baseline: Option<PathBuf>,
+    /// Write a baseline of the current findings to this path
+    pub generate_baseline: Option<PathBuf>,
+    /// Only report findings in files changed since this git ref
+    pub diff_base: Option<String>,
+    /// Only report findings in these package subtrees
+    pub only_packages: Vec<String>,
+    /// With `only_packages`, parse only the sources that can reference the
+    /// subtrees and assume the declarations outside them are used
+    pub prune_by_imports: bool,
+
+    /// Attribute findings to owners
+    pub owners: bool,
+    /// CODEOWNERS file (discovered in the project root if not set)
+    pub codeowners: Option<PathBuf>,
+    /// Fall back to `git blame` for findings without a CODEOWNERS rule
+    pub blame: bool,
+
+    /// Group findings into deletion units
+    pub cluster: bool,
+}
+
+impl Default for PipelineOptions {
+    fn default() -> Self {
+        Self {
+            parallel: false,
+            jobs: None,
+            max_memory_mb: None,
+            strict_parse: false,
+            deep: false,
+            deep_respect_overrides: false,
+            enhanced: false,
+            show_progress: false,
+            frontend: Frontend::default(),
+            symbol_dumps: Vec::new(),
+            proguard_usage: None,
+            proguard_rules: Vec::new(),
+            proguard_seeds: None,
+            report_kept: false,
+            external_usage: Vec::new(),
+            compiled_classes: Vec::new(),
+            coverage_files: Vec::new(),
+            coverage_auto: false,
+            coverage_store: None,
+            coverage_max_age: None,
+            entry_set: EntrySet::default(),
+            detectors: BTreeSet::new(),
+            rules_file: None,
+            plugins: Vec::new(),
+            min_confidence: Confidence::Low,
+            runtime_only: false,
+            exclude_risk: Vec::new(),
+            min_size: 0,
+            doc_links: false,
+            baseline: None,
+            generate_baseline: None,
+            diff_base: None,
+            only_packages: Vec::new(),
+            prune_by_imports: false,
+            owners: false,
+            codeowners: None,
+            blame: false,
+            cluster: false,
+        }
+    }
+}
+
+impl PipelineOptions {
+    /// Enable detectors turned on in the config's `detectors` section
+    pub fn with_detectors(mut self, detectors: &DetectorsConfig) -> Self {
+        for registration in DetectorRegistry::builtin().iter() {
+            let enabled = self.detector_enabled(registration.id);
+            if detectors
+                .settings(registration.id)
+                .is_some_and(|settings| settings.is_enabled(enabled))
+            {
+                self.detectors.insert(registration.id.to_string());
+            }
+        }
+        self
+    }
+
+    /// Enable a detector by id
+    pub fn with_detector(mut self, id: &str) -> Self {
+        self.detectors.insert(id.to_string());
+        self
+    }
+
+    /// Disable detectors, even when enabled by a flag or the config
+    pub fn without_detectors(mut self, ids: &[String]) -> Self {
+        for id in ids {
+            self.detectors.remove(id);
+        }
+        self
+    }
+
+    /// Whether the optional detector with this id runs
+    pub fn detector_enabled(&self, id: &str) -> bool {
+        self.detectors.contains(id)
+    }
+}
+
+/// Receives each per-file finding while files are being parsed
+pub type FindingSink = Box<dyn Fn(&DeadCode)>;
+
+/// State shared between pipeline stages
+pub struct PipelineContext {
+    /// Loaded configuration
+    pub config: Config,
+    /// Project root being analyzed; the first of `roots`
+    pub root: PathBuf,
+    /// Project roots analyzed together into one graph
+    pub roots: Vec<PathBuf>,
+    /// Pipeline options
+    pub options: PipelineOptions,
+
+    /// Discovered source files
+    pub files: Vec<SourceFile>,
+    /// Files left out of the graph because they could not be parsed
+    pub skipped_files: Vec<SkippedFile>,
+    /// Reference graph
+    pub graph: Graph,
+    /// Detected entry points
+    pub entry_points: HashSet<DeclarationId>,
+    /// Why each detected entry point is one
+    pub entry_point_categories: HashMap<DeclarationId, EntryPointCategory>,
+    /// Call sites scheduling workers and jobs
+    pub scheduled_work: Option<ScheduledWork>,
+    /// Event bus subscribers and post sites, if linked
+    pub event_bus: Option<EventBusUsage>,
+    /// Entry points retained by a ProGuard keep rule, with the rule
+    pub kept_by_rules: HashMap<DeclarationId, String>,
+    /// Which members of reachable classes are kept without a reference
+    pub member_retention: MemberRetention,
+    /// Declarations reachable from entry points
+    pub reachable: HashSet<DeclarationId>,
+    /// Reachability from the app compared with reachability from tests,
+    /// with `--entry-set diff`
+    pub entry_set_diff: Option<EntrySetDiff>,
+
+    /// ProGuard usage data, if loaded
+    pub proguard: Option<ProguardUsage>,
+    /// R8 seeds data, if loaded
+    pub seeds: Option<ProguardSeeds>,
+    /// Seeds never referenced in code, only alive because of their keep rule
+    pub kept_only: Vec<DeadCode>,
+    /// Runtime coverage data, if loaded
+    pub coverage: Option<CoverageData>,
+    /// Gradle modules with sources but no line coverage, with --coverage-auto
+    pub uncovered_modules: Vec<String>,
+
+    /// Dead code findings
+    pub dead_code: Vec<DeadCode>,
+    /// Called with each per-file finding as soon as its file is parsed;
+    /// setting it enables streaming in the parse stage
+    pub finding_sink: Option<FindingSink>,
+    /// Findings of the per-file detectors, by detector id, collected while
+    /// parsing; the detector stage uses them instead of running the detector
+    pub early_findings: HashMap<&'static str, Vec<DeadCode>>,
+    /// Findings already passed to the finding sink
+    pub streamed: Vec<DeadCode>,
+
+    /// Results of source-based analyses
+    pub resources: Option<ResourceAnalysis>,
+    pub intent_extras: Option<IntentExtraAnalysis>,
+    pub bundle_args: Option<BundleArgumentAnalysis>,
+    pub broadcasts: Option<BroadcastActionAnalysis>,
+    pub shared_prefs: Option<SharedPrefsAnalysis>,
+    pub pref_keys: Option<PrefKeyAnalysis>,
+    pub daos: Option<DaoCollectionAnalysis>,
+    pub module_deps: Option<ModuleDependencyGraph>,
+    pub navigation: Option<NavigationAnalysis>,
+    pub deep_links: Option<DeepLinkAnalysis>,
+
+    /// Zombie cycle results
+    pub cycle_stats: Option<CycleStats>,
+    pub dead_cycles: Vec<CycleInfo>,
+
+    /// Reported findings grouped into deletion units
+    pub deletion_units: Vec<DeletionUnit>,
+
+    /// Number of findings written to a generated baseline
+    pub baseline_generated: Option<usize>,
+    /// Baseline statistics, if a baseline was applied
+    pub baseline_stats: Option<BaselineStats>,
+    /// Findings matched by the applied baseline, removed from `dead_code`
+    pub baselined: Vec<DeadCode>,
+    /// Findings excluded by an inline suppression, removed from `dead_code`
+    pub suppressed: Vec<DeadCode>,
+
+    /// Non-fatal problems encountered while running
+    pub warnings: Vec<String>,
+    /// Time spent in each stage
+    pub timings: Vec<(&'static str, Duration)>,
+    /// Time spent in the phases of a stage, named `stage/phase`
+    pub phase_timings: Vec<(String, Duration)>,
+    /// Set by a stage to stop the pipeline (e.g., no files found)
+    pub halted: bool,
+}
+
+impl PipelineContext {
+    pub fn new(config: Config, root: PathBuf, options: PipelineOptions) -> Self {
+        Self {
+            config,
+            roots: vec![root.clone()],
+            root,
+            options,
+            files: Vec::new(),
+            skipped_files: Vec::new(),
+            graph: Graph::new(),
+            entry_points: HashSet::new(),
+            entry_point_categories: HashMap::new(),
+            scheduled_work: None,
+            event_bus: None,
+            kept_by_rules: HashMap::new(),
+            member_retention: MemberRetention::default(),
+            reachable: HashSet::new(),
+            entry_set_diff: None,
+            proguard: None,
+            seeds: None,
+            kept_only: Vec::new(),
+            coverage: None,
+            uncovered_modules: Vec::new(),
+            dead_code: Vec::new(),
+            finding_sink: None,
+            early_findings: HashMap::new(),
+            streamed: Vec::new(),
+            resources: None,
+            intent_extras: None,
+            bundle_args: None,
+            broadcasts: None,
+            shared_prefs: None,
+            pref_keys: None,
+            daos: None,
+            module_deps: None,
+            navigation: None,
+            deep_links: None,
+            cycle_stats: None,
+            dead_cycles: Vec::new(),
+            deletion_units: Vec::new(),
+            baseline_generated: None,
+            baseline_stats: None,
+            baselined: Vec::new(),
+            suppressed: Vec::new(),
+            warnings: Vec::new(),
+            timings: Vec::new(),
+            phase_timings: Vec::new(),
+            halted: false,
+        }
+    }
+
+    /// Analyze several project roots together, the first one being the main
+    /// project (config, baseline and report paths are relative to it)
+    pub fn with_roots(mut self, roots: Vec<PathBuf>) -> Self {
+        if let Some(first) = roots.first() {
+            self.root = first.clone();
+            self.roots = roots;
+        }
+        self
+    }
+
+    /// Report the findings of per-file detectors while parsing
+    pub fn with_finding_sink(mut self, sink: impl Fn(&DeadCode) + 'static) -> Self {
+        self.finding_sink = Some(Box::new(sink));
+        self
+    }
+
+    /// Innermost project root containing a file
+    pub fn root_of(&self, file: &Path) -> Option<&PathBuf> {
+        self.roots
+            .iter()
+            .filter(|root| file.starts_with(root))
+            .max_by_key(|root| root.components().count())
+    }
+
+    /// Time spent in a stage, if it ran
+    pub fn timing(&self, stage: &str) -> Option<Duration> {
+        self.timings
+            .iter()
+            .find(|(name, _)| *name == stage)
+            .map(|(_, duration)| *duration)
+    }
+
+    /// Record the time spent in a phase of a stage
+    pub fn record_phase(&mut self, stage: &str, phase: &str, duration: Duration) {
+        self.phase_timings
+            .push((format!("{}/{}", stage, phase), duration));
+    }
+}
@@ -0,0 +1,221 @@
+//! Analysis manifest
+//!
+//! A machine-readable record of what a run analyzed and how: the tool
+//! version, a hash of the configuration and options, the external inputs it
+//! read (coverage, ProGuard, compiled classes), entry point counts, the time
+//! spent in each stage and the state of the analysis cache. CI compares the
+//! manifests of two runs to check they are comparable, and bug reports carry
+//! the context needed to reproduce a result.
+
+use super::PipelineContext;
+use miette::{IntoDiagnostic, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Current manifest format version
+const MANIFEST_VERSION: u32 = 1;
+
+/// Everything a run depended on, written with `--manifest`
+#[derive(Debug, Serialize)]
+pub struct AnalysisManifest {
+    manifest_version: u32,
+    tool_version: &'static str,
+    /// Digest of the configuration and pipeline options; two runs with the
+    /// same hash over the same sources give the same findings
+    config_hash: String,
+    roots: Vec<PathBuf>,
+    files: usize,
+    declarations: usize,
+    findings: usize,
+    entry_points: EntryPointCounts,
+    inputs: ManifestInputs,
+    /// Seconds spent in each stage, in execution order
+    timings: Vec<StageTiming>,
+    total_seconds: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache: Option<CacheUsage>,
+}
+
+#[derive(Debug, Serialize)]
+struct EntryPointCounts {
+    total: usize,
+    /// Entry points whose category was detected, by category
+    by_category: BTreeMap<&'static str, usize>,
+}
+
+/// External inputs given to the run, and whether they were loaded
+#[derive(Debug, Serialize)]
+struct ManifestInputs {
+    coverage_files: Vec<PathBuf>,
+    coverage_loaded: bool,
+    proguard_usage: Option<PathBuf>,
+    proguard_loaded: bool,
+    proguard_rules: Vec<PathBuf>,
+    external_usage: Vec<PathBuf>,
+    compiled_classes: Vec<PathBuf>,
+    symbol_dumps: Vec<PathBuf>,
+    baseline: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+struct StageTiming {
+    stage: &'static str,
+    seconds: f64,
+}
+
+/// How many of the analyzed files the analysis cache holds up to date
+#[derive(Debug, Serialize)]
+pub struct CacheUsage {
+    pub path: PathBuf,
+    pub hits: usize,
+    pub misses: usize,
+    pub hit_rate: f64,
+}
+
+impl CacheUsage {
+    pub fn new(path: PathBuf, hits: usize, misses: usize) -> Self {
+        let total = hits + misses;
+        let hit_rate = if total == 0 {
+            0.0
+        } else {
+            hits as f64 / total as f64
+        };
+        Self {
+            path,
+            hits,
+            misses,
+            hit_rate,
+        }
+    }
+}
+
+impl AnalysisManifest {
+    /// Record a finished pipeline run
+    pub fn from_context(ctx: &PipelineContext) -> Self {
+        let mut by_category = BTreeMap::new();
+        for category in ctx.entry_point_categories.values() {
+            *by_category.entry(category.label()).or_default() += 1;
+        }
+        let options = &ctx.options;
+        let timings: Vec<StageTiming> = ctx
+            .timings
+            .iter()
+            .map(|&(stage, duration)| StageTiming {
+                stage,
+                seconds: duration.as_secs_f64(),
+            })
+            .collect();
+
+        Self {
+            manifest_version: MANIFEST_VERSION,
+            tool_version: env!("CARGO_PKG_VERSION"),
+            config_hash: config_hash(ctx),
+            roots: ctx.roots.clone(),
+            files: ctx.files.len(),
+            declarations: ctx.graph.declaration_count(),
+            findings: ctx.dead_code.len(),
+            entry_points: EntryPointCounts {
+                total: ctx.entry_points.len(),
+                by_category,
+            },
+            inputs: ManifestInputs {
+                coverage_files: options.coverage_files.clone(),
+                coverage_loaded: ctx.coverage.is_some(),
+                proguard_usage: options.proguard_usage.clone(),
+                proguard_loaded: ctx.proguard.is_some(),
+                proguard_rules: options.proguard_rules.clone(),
+                external_usage: options.external_usage.clone(),
+                compiled_classes: options.compiled_classes.clone(),
+                symbol_dumps: options.symbol_dumps.clone(),
+                baseline: options.baseline.clone(),
+            },
+            total_seconds: timings.iter().map(|t| t.seconds).sum(),
+            timings,
+            cache: None,
+        }
+    }
+
+    /// Include the state of the analysis cache
+    pub fn with_cache(mut self, cache: Option<CacheUsage>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Write the manifest as pretty-printed JSON
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).into_diagnostic()?;
+        std::fs::write(path, json).into_diagnostic()
+    }
+}
+
+/// FNV-1a digest of the configuration and the options that change findings.
+/// Progress display is left out
+fn config_hash(ctx: &PipelineContext) -> String {
+    let config = serde_json::to_string(&ctx.config).unwrap_or_default();
+    let mut options = ctx.options.clone();
+    options.show_progress = false;
+    let options = format!("{:?}", options);
+
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in config.bytes().chain([0]).chain(options.bytes()) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::pipeline::PipelineOptions;
+    use std::time::Duration;
+
+    fn context(options: PipelineOptions) -> PipelineContext {
+        PipelineContext::new(Config::default(), PathBuf::from("/project"), options)
+    }
+
+    #[test]
+    fn test_config_hash() {
+        let hash = config_hash(&context(PipelineOptions::default()));
+        assert_eq!(hash.len(), 16);
+
+        let progress = PipelineOptions {
+            show_progress: true,
+            ..PipelineOptions::default()
+        };
+        assert_eq!(config_hash(&context(progress)), hash);
+
+        let deep = PipelineOptions {
+            deep: true,
+            ..PipelineOptions::default()
+        };
+        assert_ne!(config_hash(&context(deep)), hash);
+    }
+
+    #[test]
+    fn test_manifest_from_context() {
+        let mut ctx = context(PipelineOptions {
+            coverage_files: vec![PathBuf::from("jacoco.xml")],
+            ..PipelineOptions::default()
+        });
+        ctx.timings = vec![
+            ("discover", Duration::from_millis(250)),
+            ("parse", Duration::from_millis(750)),
+        ];
+
+        let manifest = AnalysisManifest::from_context(&ctx).with_cache(Some(CacheUsage::new(
+            PathBuf::from("cache.json"),
+            3,
+            1,
+        )));
+        let value = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(value["tool_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(value["inputs"]["coverage_files"][0], "jacoco.xml");
+        assert_eq!(value["inputs"]["coverage_loaded"], false);
+        assert_eq!(value["timings"][1]["stage"], "parse");
+        assert_eq!(value["total_seconds"], 1.0);
+        assert_eq!(value["cache"]["hit_rate"], 0.75);
+    }
+}
@@ -0,0 +1,212 @@
+//! Analysis pipeline for SearchDeadCode
+//!
+//! The pipeline runs the analysis as a sequence of [`Stage`]s sharing a single
+//! [`PipelineContext`]. The CLI, watch mode and library users all run the same
+//! stages, so a new analysis step only has to be added once.
+//!
+//! ```ignore
+//! let mut ctx = PipelineContext::new(config, project_root, PipelineOptions::default());
+//! Pipeline::standard().run(&mut ctx)?;
+//! println!("{} dead code candidates", ctx.dead_code.len());
+//! ```
+//!
+//! [`AnalysisSession`] does the same in one call and returns an
+//! [`AnalysisReport`].
+
+#![allow(dead_code)] // Stage accessors reserved for library users
+
+mod context;
+mod manifest;
+mod session;
+mod stages;
+mod timings;
+
+pub use context::{FindingSink, PipelineContext, PipelineOptions};
+pub use manifest::{AnalysisManifest, CacheUsage};
+#[allow(unused_imports)] // Returned by the session; named by library users
+pub use session::{AnalysisReport, AnalysisSession};
+pub use stages::{
+    BaselineStage, BuildGraphStage, ChangedFilesStage, ClusterStage, ConfidenceFilterStage,
+    CoverageStage, CycleStage, DeadFileStage, DetectorStage, DiscoveryStage, EntryPointStage,
+    HybridStage, OwnershipStage, PackageScopeStage, ProguardStage, ReachabilityStage,
+    SourceAnalysisStage,
+};
+pub use timings::{PhaseTiming, TimingsReport};
+
+use miette::Result;
+use std::time::Instant;
+use tracing::{debug, info_span};
+
+/// A single step of the analysis pipeline
+pub trait Stage {
+    /// Short name used for logging and timings
+    fn name(&self) -> &'static str;
+
+    /// Run the stage, reading and updating the shared context
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()>;
+}
+
+/// Ordered list of stages run against a shared context
+pub struct Pipeline {
+    stages: Vec<Box<dyn Stage>>,
+}
+
+impl Pipeline {
+    /// Create an empty pipeline
+    pub fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    /// The standard analysis pipeline used by the CLI
+    pub fn standard() -> Self {
+        Self::new()
+            .with_stage(DiscoveryStage)
+            .with_stage(BuildGraphStage)
+            .with_stage(EntryPointStage)
+            .with_stage(ProguardStage)
+            .with_stage(ReachabilityStage)
+            .with_stage(CoverageStage)
+            .with_stage(HybridStage)
+            .with_stage(DetectorStage)
+            .with_stage(SourceAnalysisStage)
+            .with_stage(ConfidenceFilterStage)
+            .with_stage(CycleStage)
+            .with_stage(BaselineStage)
+            .with_stage(DeadFileStage)
+            .with_stage(ChangedFilesStage)
+            .with_stage(PackageScopeStage)
+            .with_stage(OwnershipStage)
+            .with_stage(ClusterStage)
+    }
+
+    /// Append a stage to the pipeline
+    pub fn with_stage(mut self, stage: impl Stage + 'static) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// Remove the stage with this name, e.g. to replace it with work done
+    /// outside the pipeline
+    pub fn without_stage(mut self, name: &str) -> Self {
+        self.stages.retain(|stage| stage.name() != name);
+        self
+    }
+
+    /// Names of the stages in execution order
+    pub fn stage_names(&self) -> Vec<&'static str> {
+        self.stages.iter().map(|s| s.name()).collect()
+    }
+
+    /// Run all stages in order, stopping early if a stage halts the pipeline
+    pub fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        for stage in &self.stages {
+            if ctx.halted {
+                debug!("Pipeline halted before stage '{}'", stage.name());
+                break;
+            }
+
+            let _span = info_span!("stage", name = stage.name()).entered();
+            let start = Instant::now();
+            stage.run(ctx)?;
+            let elapsed = start.elapsed();
+            debug!(
+                "Stage '{}' took {:.3}s",
+                stage.name(),
+                elapsed.as_secs_f64()
+            );
+            ctx.timings.push((stage.name(), elapsed));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use std::path::PathBuf;
+
+    struct MarkerStage(&'static str);
+
+    impl Stage for MarkerStage {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+            ctx.warnings.push(self.0.to_string());
+            Ok(())
+        }
+    }
+
+    struct HaltStage;
+
+    impl Stage for HaltStage {
+        fn name(&self) -> &'static str {
+            "halt"
+        }
+
+        fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+            ctx.halted = true;
+            Ok(())
+        }
+    }
+
+    fn make_context() -> PipelineContext {
+        PipelineContext::new(
+            Config::default(),
+            PathBuf::from("."),
+            PipelineOptions::default(),
+        )
+    }
+
+    #[test]
+    fn test_stages_run_in_order() {
+        let pipeline = Pipeline::new()
+            .with_stage(MarkerStage("first"))
+            .with_stage(MarkerStage("second"));
+        let mut ctx = make_context();
+
+        pipeline.run(&mut ctx).unwrap();
+
+        assert_eq!(ctx.warnings, vec!["first", "second"]);
+        assert_eq!(ctx.timings.len(), 2);
+    }
+
+    #[test]
+    fn test_halt_stops_pipeline() {
+        let pipeline = Pipeline::new()
+            .with_stage(HaltStage)
+            .with_stage(MarkerStage("skipped"));
+        let mut ctx = make_context();
+
+        pipeline.run(&mut ctx).unwrap();
+
+        assert!(ctx.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_standard_stage_order() {
+        let names = Pipeline::standard().stage_names();
+        assert_eq!(names.first(), Some(&"discovery"));
+        assert_eq!(names.last(), Some(&"clusters"));
+        let ownership = names.iter().position(|n| *n == "ownership").unwrap();
+        assert_eq!(names[ownership + 1], "clusters");
+    }
+
+    #[test]
+    fn test_without_stage() {
+        let names = Pipeline::standard()
+            .without_stage("discovery")
+            .stage_names();
+        assert_eq!(names.first(), Some(&"parse"));
+        assert!(!names.contains(&"discovery"));
+    }
+}
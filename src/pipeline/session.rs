@@ -0,0 +1,210 @@
+//! One-call analysis API
+//!
+//! [`AnalysisSession`] wraps configuring [`PipelineOptions`], creating the
+//! [`PipelineContext`] and running [`Pipeline::standard`], so Gradle plugins,
+//! tests and the CLI all analyze a project through the same code path:
+//!
+//! ```ignore
+//! let report = AnalysisSession::new(config)
+//!     .with_coverage(vec!["build/reports/kover/report.xml".into()])
+//!     .with_proguard("build/outputs/mapping/release/usage.txt")
+//!     .run("path/to/project")?;
+//! for finding in report.findings() {
+//!     println!("{}", finding.declaration.name);
+//! }
+//! ```
+
+use super::{FindingSink, Pipeline, PipelineContext, PipelineOptions};
+use crate::analysis::detectors::DetectorRegistry;
+use crate::analysis::{Confidence, DeadCode};
+use crate::config::Config;
+use crate::graph::SkippedFile;
+use miette::Result;
+use std::path::PathBuf;
+
+/// Builder for a single analysis run
+pub struct AnalysisSession {
+    config: Config,
+    options: PipelineOptions,
+    skip_detectors: Vec<String>,
+    roots: Vec<PathBuf>,
+    pipeline: Option<Pipeline>,
+    finding_sink: Option<FindingSink>,
+}
+
+impl AnalysisSession {
+    /// Create a session with the default detectors and those the config
+    /// enables
+    pub fn new(config: Config) -> Self {
+        let options = PipelineOptions {
+            detectors: DetectorRegistry::builtin()
+                .default_ids()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            ..PipelineOptions::default()
+        };
+        Self {
+            config,
+            options,
+            skip_detectors: Vec::new(),
+            roots: Vec::new(),
+            pipeline: None,
+            finding_sink: None,
+        }
+    }
+
+    /// Replace all options, e.g. with the ones built from command line flags
+    pub fn with_options(mut self, options: PipelineOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Load line coverage from these JaCoCo, Kover or LCOV reports
+    pub fn with_coverage(mut self, files: Vec<PathBuf>) -> Self {
+        self.options.coverage_files = files;
+        self
+    }
+
+    /// Cross-check findings with a ProGuard/R8 `usage.txt`
+    pub fn with_proguard(mut self, usage: impl Into<PathBuf>) -> Self {
+        self.options.proguard_usage = Some(usage.into());
+        self
+    }
+
+    /// Suppress findings recorded in this baseline
+    pub fn with_baseline(mut self, baseline: impl Into<PathBuf>) -> Self {
+        self.options.baseline = Some(baseline.into());
+        self
+    }
+
+    /// Only report findings with at least this confidence
+    pub fn with_min_confidence(mut self, confidence: Confidence) -> Self {
+        self.options.min_confidence = confidence;
+        self
+    }
+
+    /// Enable a detector by id
+    pub fn with_detector(mut self, id: &str) -> Self {
+        self.options = self.options.with_detector(id);
+        self
+    }
+
+    /// Disable detectors, even when enabled by the options or the config
+    pub fn without_detectors(mut self, ids: &[String]) -> Self {
+        self.skip_detectors.extend(ids.iter().cloned());
+        self
+    }
+
+    /// Analyze these source roots together instead of the path given to
+    /// [`run`](Self::run) alone
+    pub fn with_roots(mut self, roots: Vec<PathBuf>) -> Self {
+        self.roots = roots;
+        self
+    }
+
+    /// Run these stages instead of the standard pipeline
+    pub fn with_pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.pipeline = Some(pipeline);
+        self
+    }
+
+    /// Receive findings as soon as the detector producing them finishes
+    pub fn with_finding_sink(mut self, sink: impl Fn(&DeadCode) + 'static) -> Self {
+        self.finding_sink = Some(Box::new(sink));
+        self
+    }
+
+    /// Analyze the project at `path`
+    pub fn run(self, path: impl Into<PathBuf>) -> Result<AnalysisReport> {
+        let options = self
+            .options
+            .with_detectors(&self.config.detectors)
+            .without_detectors(&self.skip_detectors);
+        let mut ctx =
+            PipelineContext::new(self.config, path.into(), options).with_roots(self.roots);
+        ctx.finding_sink = self.finding_sink;
+
+        self.pipeline
+            .unwrap_or_else(Pipeline::standard)
+            .run(&mut ctx)?;
+        Ok(AnalysisReport { ctx })
+    }
+}
+
+/// Results of an [`AnalysisSession`]
+///
+/// The common results have typed accessors; everything else the stages
+/// computed stays available through [`context`](Self::context).
+pub struct AnalysisReport {
+    ctx: PipelineContext,
+}
+
+impl AnalysisReport {
+    /// Findings left after filtering, baselines and suppressions
+    pub fn findings(&self) -> &[DeadCode] {
+        &self.ctx.dead_code
+    }
+
+    /// Number of source files analyzed
+    pub fn files_analyzed(&self) -> usize {
+        self.ctx.files.len()
+    }
+
+    /// Files that could not be read or parsed
+    pub fn skipped_files(&self) -> &[SkippedFile] {
+        &self.ctx.skipped_files
+    }
+
+    /// Problems that did not stop the analysis, e.g. an unreadable report
+    pub fn warnings(&self) -> &[String] {
+        &self.ctx.warnings
+    }
+
+    /// Whether the analysis found nothing to report
+    pub fn is_clean(&self) -> bool {
+        self.ctx.dead_code.is_empty()
+    }
+
+    /// Everything the pipeline computed
+    pub fn context(&self) -> &PipelineContext {
+        &self.ctx
+    }
+
+    /// Take the pipeline context, e.g. to post-process or report findings
+    pub fn into_context(self) -> PipelineContext {
+        self.ctx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_options() {
+        let session = AnalysisSession::new(Config::default())
+            .with_coverage(vec![PathBuf::from("kover.xml")])
+            .with_proguard("usage.txt")
+            .with_detector("unused-params")
+            .without_detectors(&["unused-params".to_string()]);
+        assert_eq!(
+            session.options.coverage_files,
+            vec![PathBuf::from("kover.xml")]
+        );
+        assert_eq!(
+            session.options.proguard_usage,
+            Some(PathBuf::from("usage.txt"))
+        );
+        for id in DetectorRegistry::builtin().default_ids() {
+            assert!(session.options.detector_enabled(id));
+        }
+
+        let report = session
+            .with_pipeline(Pipeline::new())
+            .run("/project")
+            .unwrap();
+        assert!(report.is_clean());
+        assert!(!report.context().options.detector_enabled("unused-params"));
+    }
+}
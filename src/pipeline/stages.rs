@@ -0,0 +1,1652 @@
+//! Standard pipeline stages
+//!
+//! Each stage mirrors one step of the CLI analysis and only reads the options
+//! it needs, so disabled stages are cheap no-ops.
+
+use super::{PipelineContext, PipelineOptions, Stage};
+use crate::analysis::detectors::{
+    ComposeStateDetector, ConstValue, CustomRuleDetector, DaoCollectionAnalysis, DeadBranch,
+    DeadBranchDetector, DeepLinkDetector, Detector, DetectorRegistry, PluginDetector,
+    SharedPrefsAnalysis, UninvokedParamDetector, UnusedBroadcastActionDetector,
+    UnusedBundleArgumentDetector, UnusedIntentExtraDetector, UnusedNavArgumentDetector,
+    UnusedPrefKeyDetector, WriteOnlyDaoDetector, WriteOnlyPrefsDetector,
+};
+use crate::analysis::event_bus::EventBusUsage;
+use crate::analysis::module_deps::ModuleDependencyGraph;
+use crate::analysis::reflection::ReflectionUsage;
+use crate::analysis::risk;
+use crate::analysis::scheduled_work::{ScheduledWork, SchedulingApi};
+use crate::analysis::size::is_deletable;
+use crate::analysis::suppression;
+use crate::analysis::{
+    aggregate_dead_containers, aggregate_dead_files, classify_by_annotations, cluster_findings,
+    sort_findings, test_entry_points, Confidence, CycleDetector, DeadCode, DeadCodeIssue,
+    DeepAnalyzer, EnhancedAnalyzer, EntryPointCategory, EntryPointDetector, EntrySet, EntrySetDiff,
+    HybridAnalyzer, ReachabilityAnalyzer, ResourceDetector, Severity,
+};
+use crate::baseline::Baseline;
+use crate::bytecode::{BytecodeGraph, ExternalUsage};
+use crate::config::{PluginConfig, RetainMembers, RuleSet};
+use crate::coverage::{
+    discover_module_reports, modules_without_coverage, parse_coverage_file, report_timestamp,
+    unix_now, CoverageData, CoverageStore, SourceIndex,
+};
+use crate::discovery::{
+    declared_package, is_generated_source, ChangedFiles, FileFinder, FileType, PackageScope,
+    Variant,
+};
+use crate::graph::{
+    DeclarationId, DeclarationKind, Graph, GraphBuilder, ParallelGraphBuilder, SkippedFile,
+};
+use crate::ownership::{CodeOwners, OwnershipResolver};
+use crate::parser::{Frontend, ParseResult, SymbolDump};
+use crate::proguard::{KeepRules, ProguardUsage, SeedsParser};
+use indicatif::{ProgressBar, ProgressStyle};
+use miette::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::{debug, info, info_span};
+
+/// Step 1: Discover source files
+pub struct DiscoveryStage;
+
+impl Stage for DiscoveryStage {
+    fn name(&self) -> &'static str {
+        "discovery"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        info!("Discovering files...");
+        let finder = FileFinder::new(&ctx.config);
+        ctx.files.clear();
+        for root in &ctx.roots {
+            let mut files = finder.find_files(root)?;
+            if ctx.config.discovery.generated_sources {
+                let generated = finder.find_generated_files(root, &files)?;
+                info!("Found {} generated source files", generated.len());
+                files.extend(generated);
+            }
+            if ctx.roots.len() > 1 {
+                info!("Found {} files in {}", files.len(), root.display());
+            }
+            ctx.files.extend(files);
+        }
+        // Nested roots would otherwise contribute their files twice
+        let mut seen = HashSet::new();
+        ctx.files.retain(|f| seen.insert(f.path.clone()));
+        if let Some(name) = ctx.config.android.variant.clone() {
+            let variant = Variant::parse(&name);
+            ctx.files.retain(|f| variant.includes(&f.path));
+            info!(
+                "Analyzing variant {} (source sets: {})",
+                name,
+                variant.source_sets().join(", ")
+            );
+
+            let build_config = &mut ctx.config.android.build_config;
+            build_config.insert("DEBUG".to_string(), ConstValue::Bool(variant.is_debug()));
+            build_config.insert(
+                "BUILD_TYPE".to_string(),
+                ConstValue::Str(variant.build_type.clone()),
+            );
+            build_config.insert("FLAVOR".to_string(), ConstValue::Str(variant.flavor_name()));
+        }
+
+        let scope = PackageScope::new(&ctx.options.only_packages);
+        if ctx.options.prune_by_imports && !scope.is_empty() {
+            let before = ctx.files.len();
+            ctx.files.retain(|f| {
+                !f.file_type.is_source()
+                    || f.read_contents()
+                        .map_or(true, |contents| scope.is_relevant_source(&contents))
+            });
+            info!(
+                "Skipping {} sources that cannot reference {}",
+                before - ctx.files.len(),
+                scope.packages().join(", ")
+            );
+        }
+
+        info!("Found {} files to analyze", ctx.files.len());
+
+        if ctx.files.is_empty() {
+            ctx.halted = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Step 2: Parse files and build the reference graph
+pub struct BuildGraphStage;
+
+impl Stage for BuildGraphStage {
+    fn name(&self) -> &'static str {
+        "parse"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        let pb = if ctx.options.show_progress && !ctx.options.parallel {
+            ProgressBar::new(ctx.files.len() as u64)
+        } else {
+            ProgressBar::hidden()
+        };
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template(
+                    "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})",
+                )
+                .unwrap()
+                .progress_chars("#>-"),
+        );
+
+        // With a finding sink, per-file detectors run on each file as soon as
+        // it is parsed, against a graph of that file alone
+        let registry = DetectorRegistry::builtin();
+        let streamed: Vec<_> = match ctx.finding_sink {
+            Some(_) => registry
+                .iter()
+                .filter(|r| r.per_file && ctx.options.detector_enabled(r.id))
+                .collect(),
+            None => Vec::new(),
+        };
+        let mut stream = |parse_result: &ParseResult| {
+            let mut file_builder = GraphBuilder::new();
+            file_builder.add_parse_result(parse_result.clone());
+            let file_graph = file_builder.build();
+            for registration in &streamed {
+                let Some(create) = registration.create else {
+                    continue;
+                };
+                let issues = create().detect(&file_graph);
+
+                // Only show what the confidence filter stage would keep
+                let mut shown = issues.clone();
+                suppression::apply(&mut shown, &file_graph, &ctx.config.suppression);
+                shown.retain(|dc| dc.suppressed_by.is_none() && is_reported(dc, &ctx.options));
+                if let Some(sink) = &ctx.finding_sink {
+                    pb.suspend(|| shown.iter().for_each(sink));
+                }
+                ctx.streamed.extend(shown);
+                ctx.early_findings
+                    .entry(registration.id)
+                    .or_default()
+                    .extend(issues);
+            }
+        };
+
+        if ctx.options.parallel {
+            let parallel_builder = ParallelGraphBuilder::new()
+                .with_jobs(ctx.options.jobs)
+                .with_max_memory_mb(ctx.options.max_memory_mb)
+                .with_strict_parse(ctx.options.strict_parse);
+            let observer: Option<&mut dyn FnMut(&ParseResult)> = if streamed.is_empty() {
+                None
+            } else {
+                Some(&mut stream)
+            };
+            let (graph, skipped) = parallel_builder.build_observing(&ctx.files, observer)?;
+            ctx.graph = graph;
+            ctx.skipped_files = skipped;
+            return apply_symbol_dumps(ctx);
+        }
+
+        info!("Parsing files...");
+        let mut graph_builder = GraphBuilder::new();
+        let mut skipped = Vec::new();
+        for file in &ctx.files {
+            // A file that cannot be parsed is skipped, unless parsing is strict
+            let parse_result = match graph_builder.parse_file(file) {
+                Ok(Some(parse_result)) => parse_result,
+                Ok(None) => {
+                    pb.inc(1);
+                    continue;
+                }
+                Err(e) if ctx.options.strict_parse => {
+                    return Err(e.wrap_err(format!("Failed to parse {}", file.path.display())));
+                }
+                Err(e) => {
+                    debug!("Skipping {}: {}", file.path.display(), e);
+                    skipped.push(SkippedFile::new(file.path.clone(), &e));
+                    pb.inc(1);
+                    continue;
+                }
+            };
+            if !streamed.is_empty() {
+                stream(&parse_result);
+            }
+            graph_builder.add_parse_result(parse_result);
+            pb.inc(1);
+        }
+        pb.finish_with_message("Parsing complete");
+        ctx.skipped_files = skipped;
+
+        let start = Instant::now();
+        ctx.graph = info_span!("resolve").in_scope(|| graph_builder.build());
+        ctx.record_phase(self.name(), "resolve", start.elapsed());
+        info!(
+            "Built graph with {} declarations and {} references",
+            ctx.graph.declaration_count(),
+            ctx.graph.reference_count()
+        );
+        apply_symbol_dumps(ctx)
+    }
+}
+
+/// Add the references the compiler resolved, for the bytecode frontend
+fn apply_symbol_dumps(ctx: &mut PipelineContext) -> Result<()> {
+    if ctx.options.frontend != Frontend::Bytecode {
+        return Ok(());
+    }
+    if ctx.options.symbol_dumps.is_empty() {
+        ctx.warnings
+            .push("No symbol dump given (--symbols); using the tree-sitter graph only".to_string());
+        return Ok(());
+    }
+
+    for path in ctx.options.symbol_dumps.clone() {
+        let dump = SymbolDump::load(&path)?;
+        let stats = dump.apply(&mut ctx.graph, &ctx.root);
+        info!(
+            "{}: added {} compiler references, {} unresolved, {} symbols not found by the parser",
+            path.display(),
+            stats.references_added,
+            stats.unresolved_references,
+            stats.missing_symbols
+        );
+    }
+    Ok(())
+}
+
+/// Step 3: Detect entry points
+pub struct EntryPointStage;
+
+impl Stage for EntryPointStage {
+    fn name(&self) -> &'static str {
+        "entry_points"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        info!("Detecting entry points...");
+        // Composables have to be reached through calls for previews to tell
+        if ctx.options.detector_enabled("preview-only") {
+            ctx.config.android.compose_previews = true;
+        }
+        let scheduled_work = ScheduledWork::scan(&ctx.files);
+        let event_bus = ctx
+            .config
+            .event_bus
+            .enabled
+            .then(|| EventBusUsage::scan(&ctx.files, &ctx.config.event_bus));
+        let mut entry_detector = EntryPointDetector::new(&ctx.config)
+            .with_scheduled_work(&scheduled_work)
+            .with_files(&ctx.files);
+        if let Some(event_bus) = &event_bus {
+            entry_detector = entry_detector.with_event_bus(event_bus);
+        }
+        ctx.entry_point_categories.clear();
+        for root in &ctx.roots {
+            let categories = entry_detector.detect_categorized(&ctx.graph, root)?;
+            for (id, category) in categories {
+                ctx.entry_point_categories.entry(id).or_insert(category);
+            }
+        }
+        ctx.member_retention = entry_detector.member_retention(&ctx.graph)?;
+        for entry_point in entry_detector.unmatched_entry_points(&ctx.graph) {
+            ctx.warnings.push(format!(
+                "Entry point '{}' matches no declaration (typo?)",
+                entry_point
+            ));
+        }
+        ctx.scheduled_work = Some(scheduled_work);
+        ctx.event_bus = event_bus;
+        ctx.entry_points = ctx.entry_point_categories.keys().cloned().collect();
+
+        // Generated code is wired up by the framework that generated it, so
+        // whatever it references is alive
+        let generated: Vec<DeclarationId> = ctx
+            .graph
+            .declarations()
+            .filter(|decl| is_generated_source(&decl.location.file))
+            .map(|decl| decl.id.clone())
+            .collect();
+        for id in &generated {
+            ctx.entry_point_categories
+                .entry(id.clone())
+                .or_insert(EntryPointCategory::Generated);
+        }
+        ctx.entry_points.extend(generated);
+
+        // Sources that could have used the declarations outside the package
+        // scope were not parsed, so those declarations are assumed used
+        let scope = PackageScope::new(&ctx.options.only_packages);
+        if ctx.options.prune_by_imports && !scope.is_empty() {
+            let outside: HashSet<&PathBuf> = ctx
+                .files
+                .iter()
+                .filter(|f| f.file_type.is_source())
+                .filter(|f| {
+                    f.read_contents()
+                        .ok()
+                        .as_deref()
+                        .and_then(declared_package)
+                        .is_none_or(|package| !scope.contains(package))
+                })
+                .map(|f| &f.path)
+                .collect();
+            let assumed: Vec<DeclarationId> = ctx
+                .graph
+                .declarations()
+                .filter(|decl| outside.contains(&decl.location.file))
+                .map(|decl| decl.id.clone())
+                .collect();
+            for id in &assumed {
+                ctx.entry_point_categories
+                    .entry(id.clone())
+                    .or_insert(EntryPointCategory::OutsidePackageScope);
+            }
+            ctx.entry_points.extend(assumed);
+        }
+
+        // Declarations kept by ProGuard/R8 keep rules are used from outside the
+        // code graph (reflection, JNI, serialization)
+        let mut keep_rules = KeepRules::default();
+        if ctx.config.android.parse_keep_rules {
+            for root in &ctx.roots {
+                keep_rules.extend(KeepRules::discover(root));
+            }
+        }
+        for path in &ctx.options.proguard_rules {
+            match KeepRules::parse(path) {
+                Ok(rules) => keep_rules.extend(rules),
+                Err(e) => ctx.warnings.push(format!(
+                    "Failed to load keep rules {}: {}",
+                    path.display(),
+                    e
+                )),
+            }
+        }
+        if !keep_rules.is_empty() {
+            for decl in ctx.graph.declarations() {
+                if let Some(rule) = keep_rules.keeping(decl, &ctx.graph) {
+                    ctx.kept_by_rules.insert(decl.id.clone(), rule.describe());
+                }
+            }
+            info!(
+                "{} keep rules retain {} declarations",
+                keep_rules.rules().len(),
+                ctx.kept_by_rules.len()
+            );
+            ctx.entry_points.extend(ctx.kept_by_rules.keys().cloned());
+        }
+
+        // Declarations referenced from prebuilt dependencies are used even
+        // though nothing in the analyzed sources calls them
+        let mut external_usage = ExternalUsage::default();
+        for path in &ctx.options.external_usage {
+            if let Err(e) = external_usage.add_artifact(path) {
+                ctx.warnings.push(format!(
+                    "Failed to read external usage {}: {}",
+                    path.display(),
+                    e
+                ));
+            }
+        }
+        if !external_usage.is_empty() {
+            let used: Vec<DeclarationId> = ctx
+                .graph
+                .declarations()
+                .filter(|decl| external_usage.uses(decl, &ctx.graph))
+                .map(|decl| decl.id.clone())
+                .collect();
+            info!(
+                "{} classes in prebuilt artifacts reference {} declarations",
+                external_usage.class_count,
+                used.len()
+            );
+            ctx.entry_points.extend(used);
+        }
+
+        // Reachability from the test suite instead of the app
+        if ctx.options.entry_set == EntrySet::Tests {
+            ctx.entry_points = test_entry_points(&ctx.graph, &ctx.entry_points);
+            ctx.entry_point_categories
+                .retain(|id, _| ctx.entry_points.contains(id));
+            for id in &ctx.entry_points {
+                ctx.entry_point_categories
+                    .entry(id.clone())
+                    .or_insert(EntryPointCategory::Test);
+            }
+        }
+
+        info!("Found {} entry points", ctx.entry_points.len());
+        Ok(())
+    }
+}
+
+/// Step 4: Load ProGuard usage data (needed for enhanced mode)
+pub struct ProguardStage;
+
+impl Stage for ProguardStage {
+    fn name(&self) -> &'static str {
+        "proguard"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        if let Some(usage_path) = ctx.options.proguard_usage.clone() {
+            info!("Loading ProGuard usage.txt from {:?}...", usage_path);
+            match ProguardUsage::parse(&usage_path) {
+                Ok(data) => {
+                    info!("ProGuard usage: {}", data.stats());
+                    ctx.proguard = Some(data);
+                }
+                Err(e) => {
+                    ctx.warnings
+                        .push(format!("Failed to load usage.txt: {}", e));
+                }
+            }
+        }
+
+        if let Some(seeds_path) = ctx.options.proguard_seeds.clone() {
+            info!("Loading R8 seeds.txt from {:?}...", seeds_path);
+            match SeedsParser::parse(&seeds_path) {
+                Ok(seeds) => {
+                    // R8 keeps seeds whether or not their rule is in the analyzed
+                    // keep rule files, so they are entry points like kept code
+                    let seeded: HashMap<DeclarationId, String> = ctx
+                        .graph
+                        .declarations()
+                        .filter(|decl| seeds.keeps(decl, &ctx.graph))
+                        .map(|decl| {
+                            let reason = ctx
+                                .kept_by_rules
+                                .get(&decl.id)
+                                .cloned()
+                                .unwrap_or_else(|| "seeds.txt".to_string());
+                            (decl.id.clone(), reason)
+                        })
+                        .collect();
+                    ctx.kept_only = kept_only_findings(&ctx.graph, &seeded);
+                    info!(
+                        "R8 seeds: {} entries match {} declarations, {} never referenced in code",
+                        seeds.total_count,
+                        seeded.len(),
+                        ctx.kept_only.len()
+                    );
+                    for (id, reason) in seeded {
+                        ctx.entry_points.insert(id.clone());
+                        ctx.kept_by_rules.entry(id).or_insert(reason);
+                    }
+                    ctx.seeds = Some(seeds);
+                }
+                Err(e) => {
+                    ctx.warnings
+                        .push(format!("Failed to load seeds.txt: {}", e));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Step 5: Run reachability analysis (deep, enhanced, or standard)
+pub struct ReachabilityStage;
+
+impl Stage for ReachabilityStage {
+    fn name(&self) -> &'static str {
+        "reachability"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        info!("Running reachability analysis...");
+
+        let retention = ctx.member_retention.clone();
+        let (dead_code, reachable) = if ctx.options.deep {
+            let deep = DeepAnalyzer::new()
+                .with_parallel(ctx.options.parallel)
+                .with_unused_members(true)
+                .with_respect_overrides(ctx.options.deep_respect_overrides)
+                .with_member_retention(retention.clone().with_default(RetainMembers::None));
+            deep.analyze(&ctx.graph, &ctx.entry_points)
+        } else if ctx.options.enhanced && ctx.proguard.is_some() {
+            let mut enhanced = EnhancedAnalyzer::new().with_member_retention(retention.clone());
+            if let Some(pg) = ctx.proguard.clone() {
+                enhanced = enhanced.with_proguard(pg);
+            }
+            enhanced.analyze(&ctx.graph, &ctx.entry_points)
+        } else if ctx.options.parallel {
+            let enhanced = EnhancedAnalyzer::new().with_member_retention(retention.clone());
+            enhanced.analyze(&ctx.graph, &ctx.entry_points)
+        } else {
+            let analyzer = ReachabilityAnalyzer::new().with_member_retention(retention.clone());
+            analyzer.find_unreachable_with_reachable(&ctx.graph, &ctx.entry_points)
+        };
+
+        info!(
+            "Reachability: {} reachable, {} total",
+            reachable.len(),
+            ctx.graph.declarations().count()
+        );
+
+        ctx.dead_code = dead_code.into_iter().map(classify_by_annotations).collect();
+        ctx.reachable = reachable;
+
+        // Both tell production entry points from test ones, which a run from
+        // the test suite alone does not have
+        let from_tests = ctx.options.entry_set == EntrySet::Tests;
+        if ctx.options.detector_enabled("only-used-in-tests") && !from_tests {
+            let test_only = ReachabilityAnalyzer::new()
+                .with_member_retention(retention.clone())
+                .find_test_only(&ctx.graph, &ctx.entry_points, &ctx.reachable);
+            info!("{} declarations are only used from tests", test_only.len());
+            ctx.dead_code
+                .extend(test_only.into_iter().map(classify_by_annotations));
+        }
+
+        if ctx.options.entry_set == EntrySet::Diff {
+            let diff = EntrySetDiff::compute(
+                &ctx.graph,
+                &ctx.entry_points,
+                &ReachabilityAnalyzer::new().with_member_retention(retention.clone()),
+            );
+            info!(
+                "{} declarations untested, {} only reached from tests",
+                diff.untested.len(),
+                diff.test_only.len()
+            );
+            ctx.entry_set_diff = Some(diff);
+        }
+
+        if ctx.options.detector_enabled("preview-only") && !from_tests {
+            let reported: HashSet<DeclarationId> = ctx
+                .dead_code
+                .iter()
+                .map(|dc| dc.declaration.id.clone())
+                .collect();
+            let previews: HashSet<DeclarationId> = ctx
+                .entry_point_categories
+                .iter()
+                .filter(|(_, category)| **category == EntryPointCategory::Preview)
+                .map(|(id, _)| id.clone())
+                .collect();
+            let preview_only: Vec<DeadCode> = ReachabilityAnalyzer::new()
+                .with_member_retention(retention)
+                .find_preview_only(&ctx.graph, &ctx.entry_points, &previews, &ctx.reachable)
+                .into_iter()
+                .filter(|dc| !reported.contains(&dc.declaration.id))
+                .collect();
+            info!(
+                "{} composables are only used from previews or tests",
+                preview_only.len()
+            );
+            ctx.dead_code.extend(preview_only);
+        }
+
+        if ctx.options.report_kept {
+            ctx.dead_code
+                .extend(kept_only_findings(&ctx.graph, &ctx.kept_by_rules));
+        }
+
+        // Findings of the scheduling and event bus linkage replace the generic
+        // ones for the same declaration
+        let never_scheduled = never_scheduled_findings(ctx);
+        info!(
+            "{} workers and jobs are never scheduled",
+            never_scheduled.len()
+        );
+        let event_bus = event_bus_findings(ctx);
+        info!(
+            "{} event subscribers or events are unmatched",
+            event_bus.len()
+        );
+        let linkage: Vec<DeadCode> = never_scheduled.into_iter().chain(event_bus).collect();
+        if !linkage.is_empty() {
+            let ids: HashSet<_> = linkage.iter().map(|dc| dc.declaration.id.clone()).collect();
+            ctx.dead_code.retain(|dc| !ids.contains(&dc.declaration.id));
+            ctx.dead_code.extend(linkage);
+        }
+
+        // Calls told apart from these overloads only by their argument count,
+        // and declarations documentation still links to
+        for dc in &mut ctx.dead_code {
+            if ctx.graph.is_arity_excluded(&dc.declaration.id) {
+                dc.risk_tags.push(risk::OVERLOAD_RESOLUTION.to_string());
+            }
+            if ctx.options.doc_links
+                && !ctx
+                    .graph
+                    .get_soft_references_to(&dc.declaration.id)
+                    .is_empty()
+            {
+                dc.risk_tags.push(risk::DOC_LINKED.to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Subscribers of events that are never posted, and events posted without a
+/// subscriber. Post sites whose event type is unknown (`bus.post(event)`) may
+/// post anything, so subscribers are then only reported with low confidence.
+fn event_bus_findings(ctx: &PipelineContext) -> Vec<DeadCode> {
+    let Some(event_bus) = &ctx.event_bus else {
+        return Vec::new();
+    };
+    // Without any subscriber the project has no bus, whatever `post(` means
+    if !event_bus.has_subscribers() {
+        return Vec::new();
+    }
+
+    let mut findings: Vec<DeadCode> = ctx
+        .graph
+        .declarations()
+        .filter(|decl| !ctx.entry_points.contains(&decl.id))
+        .filter_map(|decl| {
+            let event = event_bus.subscribed_event(decl)?;
+            if event_bus.is_posted(event, &ctx.graph) {
+                return None;
+            }
+            let message = format!(
+                "Subscriber '{}' handles '{}', which is never posted",
+                decl.name, event
+            );
+            let confidence = if event_bus.unresolved_posts > 0 {
+                Confidence::Low
+            } else {
+                Confidence::Medium
+            };
+            Some(
+                DeadCode::new(decl.clone(), DeadCodeIssue::Unreferenced)
+                    .with_message(message)
+                    .with_confidence(confidence),
+            )
+        })
+        .collect();
+
+    for (event, sites) in event_bus.posts() {
+        if event_bus.is_subscribed(event, &ctx.graph) {
+            continue;
+        }
+        let site = &sites[0];
+        for decl in ctx.graph.find_by_name(event) {
+            if !decl.kind.is_type() {
+                continue;
+            }
+            let message = format!(
+                "Event '{}' is posted ({}:{}) but has no subscriber",
+                event,
+                site.file.display(),
+                site.line
+            );
+            findings.push(
+                DeadCode::new(decl.clone(), DeadCodeIssue::Unreferenced).with_message(message),
+            );
+        }
+    }
+
+    findings
+}
+
+/// Workers and jobs without a call site scheduling them. The system never
+/// starts them, even when code such as a WorkerFactory references them.
+fn never_scheduled_findings(ctx: &PipelineContext) -> Vec<DeadCode> {
+    let Some(scheduled_work) = &ctx.scheduled_work else {
+        return Vec::new();
+    };
+
+    ctx.graph
+        .declarations()
+        .filter(|decl| !ctx.entry_points.contains(&decl.id))
+        .filter(|decl| scheduled_work.sites(decl).is_empty())
+        .filter_map(|decl| {
+            let message = match SchedulingApi::required_by(decl)? {
+                SchedulingApi::WorkManager => {
+                    format!("Worker '{}' is never enqueued with WorkManager", decl.name)
+                }
+                _ => format!("Job '{}' is never scheduled with JobScheduler", decl.name),
+            };
+            Some(DeadCode::new(decl.clone(), DeadCodeIssue::Unreferenced).with_message(message))
+        })
+        .collect()
+}
+
+/// Declarations never referenced in code that are only alive because of a
+/// keep rule; members are folded into their class when both are kept
+fn kept_only_findings(graph: &Graph, kept: &HashMap<DeclarationId, String>) -> Vec<DeadCode> {
+    let is_unreferenced = |id: &DeclarationId| kept.contains_key(id) && !graph.is_referenced(id);
+
+    let mut findings: Vec<DeadCode> = kept
+        .iter()
+        .filter(|(id, _)| is_unreferenced(id))
+        .filter_map(|(id, rule)| {
+            let decl = graph.get_declaration(id)?;
+            if decl.parent.as_ref().is_some_and(is_unreferenced) {
+                return None;
+            }
+            let message = format!(
+                "{} '{}' is never referenced in code; only kept alive by {}",
+                decl.kind.display_name(),
+                decl.name,
+                rule
+            );
+            Some(
+                DeadCode::new(decl.clone(), DeadCodeIssue::Unreferenced)
+                    .with_message(message)
+                    .with_severity(Severity::Info)
+                    .with_confidence(Confidence::Low)
+                    .with_kept_by(Some(rule.clone())),
+            )
+        })
+        .collect();
+
+    findings.sort_by(|a, b| {
+        a.declaration
+            .location
+            .file
+            .cmp(&b.declaration.location.file)
+            .then(
+                a.declaration
+                    .location
+                    .line
+                    .cmp(&b.declaration.location.line),
+            )
+    });
+    findings
+}
+
+/// Step 6: Load runtime coverage data, through the coverage store when one
+/// is used or a maximum evidence age is set, and with --coverage-auto from the
+/// reports of every Gradle module
+pub struct CoverageStage;
+
+impl Stage for CoverageStage {
+    fn name(&self) -> &'static str {
+        "coverage"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        let store_path = ctx.options.coverage_store.clone();
+        let mut coverage_files = ctx.options.coverage_files.clone();
+        let modules = if ctx.options.coverage_auto {
+            let modules = discover_module_reports(&ctx.root);
+            for report in modules.iter().flat_map(|m| &m.reports) {
+                if !coverage_files.contains(report) {
+                    coverage_files.push(report.clone());
+                }
+            }
+            info!(
+                "Found {} coverage report(s) in {} Gradle module(s)",
+                coverage_files.len() - ctx.options.coverage_files.len(),
+                modules.len()
+            );
+            if modules.iter().all(|m| m.reports.is_empty()) {
+                ctx.warnings.push(
+                    "--coverage-auto found no JaCoCo or Kover reports under build/reports; run the coverage tasks first"
+                        .to_string(),
+                );
+            }
+            modules
+        } else {
+            Vec::new()
+        };
+        if coverage_files.is_empty() && store_path.is_none() {
+            return Ok(());
+        }
+
+        let mut store = match &store_path {
+            Some(path) => CoverageStore::load(path).unwrap_or_else(|e| {
+                ctx.warnings
+                    .push(format!("Failed to load coverage store: {}", e));
+                CoverageStore::new()
+            }),
+            None => CoverageStore::new(),
+        };
+
+        info!(
+            "Loading coverage data from {} file(s)...",
+            coverage_files.len()
+        );
+        let now = unix_now();
+        let max_age = ctx.options.coverage_max_age;
+        let mut current = CoverageData::new();
+        for path in &coverage_files {
+            match parse_coverage_file(path) {
+                Ok(data) => {
+                    let produced = report_timestamp(path).unwrap_or(now);
+                    store.record(&data, produced);
+                    if max_age.is_none_or(|age| now.saturating_sub(produced) <= age.as_secs()) {
+                        current.merge(data);
+                    }
+                }
+                Err(e) => {
+                    ctx.warnings
+                        .push(format!("Failed to load coverage {}: {}", path.display(), e));
+                }
+            }
+        }
+
+        // Class and method evidence comes from the store, line evidence from
+        // the reports given to this run
+        let mut data = store.coverage(now, max_age);
+        data.files = current.files;
+        data.source_roots = current.source_roots;
+
+        // Key line coverage by the analyzed sources, by package and path
+        let roots = ctx
+            .config
+            .coverage
+            .source_roots
+            .iter()
+            .map(|root| ctx.root.join(root))
+            .collect();
+        let mut index = SourceIndex::new().with_source_roots(roots);
+        for file in ctx.files.iter().filter(|f| f.file_type.is_source()) {
+            let contents = file.read_contents().ok();
+            index.add_source(
+                file.path.clone(),
+                contents.as_deref().and_then(declared_package),
+            );
+        }
+        for ambiguous in data.resolve_sources(&index) {
+            let candidates: Vec<String> = ambiguous
+                .candidates
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            ctx.warnings.push(format!(
+                "Coverage for {} matches several source files ({}); its line coverage is ignored, set coverage.source_roots to tell them apart",
+                ambiguous.entry.display(),
+                candidates.join(", ")
+            ));
+        }
+        if ctx.options.coverage_auto {
+            ctx.uncovered_modules = modules_without_coverage(&modules, data.files.keys());
+        }
+
+        if let Some(path) = &store_path {
+            if let Err(e) = store.save(path) {
+                ctx.warnings
+                    .push(format!("Failed to save coverage store: {}", e));
+            }
+        }
+
+        let stats = data.stats();
+        info!(
+            "Coverage: {} files, {} classes ({:.1}% covered), {} methods ({:.1}% covered)",
+            stats.total_files,
+            stats.total_classes,
+            stats.class_coverage_percent(),
+            stats.total_methods,
+            stats.method_coverage_percent()
+        );
+        ctx.coverage = Some(data);
+
+        Ok(())
+    }
+}
+
+/// Step 7: Score findings with coverage/ProGuard/keep rule/bytecode evidence and add
+/// runtime-dead code
+pub struct HybridStage;
+
+impl Stage for HybridStage {
+    fn name(&self) -> &'static str {
+        "hybrid"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        let mut hybrid = HybridAnalyzer::new();
+        if let Some(coverage) = ctx.coverage.clone() {
+            hybrid = hybrid.with_coverage(coverage);
+        }
+        if let Some(proguard) = ctx.proguard.clone() {
+            hybrid = hybrid.with_proguard(proguard);
+        }
+        if !ctx.kept_by_rules.is_empty() {
+            hybrid = hybrid.with_keep_rules(ctx.kept_by_rules.clone());
+        }
+        if let Some(reachable) = bytecode_cross_check(ctx) {
+            hybrid = hybrid.with_bytecode(reachable);
+        }
+
+        ctx.dead_code = hybrid.enhance_findings(std::mem::take(&mut ctx.dead_code));
+
+        if ctx.options.detector_enabled("runtime-dead") {
+            let runtime_dead = hybrid.find_runtime_dead_code(&ctx.graph, &ctx.reachable);
+            if !runtime_dead.is_empty() {
+                info!(
+                    "Found {} additional runtime-dead code items",
+                    runtime_dead.len()
+                );
+                ctx.dead_code.extend(runtime_dead);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Walk the project's compiled classes from the entry points and record
+/// whether they reach each finding
+fn bytecode_cross_check(ctx: &mut PipelineContext) -> Option<HashMap<DeclarationId, bool>> {
+    if ctx.options.compiled_classes.is_empty() {
+        return None;
+    }
+
+    let mut bytecode = BytecodeGraph::default();
+    for path in &ctx.options.compiled_classes {
+        if let Err(e) = bytecode.add_path(path) {
+            ctx.warnings.push(format!(
+                "Failed to read compiled classes {}: {}",
+                path.display(),
+                e
+            ));
+        }
+    }
+    if bytecode.is_empty() {
+        return None;
+    }
+
+    let reachability = bytecode.reachability(&ctx.graph, &ctx.entry_points);
+    let reachable: HashMap<DeclarationId, bool> = ctx
+        .dead_code
+        .iter()
+        .filter_map(|dc| {
+            let reachable = reachability.is_reachable(&dc.declaration, &ctx.graph)?;
+            Some((dc.declaration.id.clone(), reachable))
+        })
+        .collect();
+
+    let disagreements = reachable.values().filter(|&&r| r).count();
+    info!(
+        "{} compiled classes confirm {} findings",
+        bytecode.class_count(),
+        reachable.len() - disagreements
+    );
+    if disagreements > 0 {
+        ctx.warnings.push(format!(
+            "{} findings are reachable in the compiled classes; they may be parser bugs",
+            disagreements
+        ));
+    }
+    Some(reachable)
+}
+
+/// Step 8: Run the enabled graph-based detectors from the registry
+pub struct DetectorStage;
+
+impl Stage for DetectorStage {
+    fn name(&self) -> &'static str {
+        "detectors"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        for registration in DetectorRegistry::builtin().iter() {
+            let Some(create) = registration.create else {
+                continue;
+            };
+            if !ctx.options.detector_enabled(registration.id) {
+                continue;
+            }
+
+            // Per-file detectors may already have run while parsing
+            let issues = match ctx.early_findings.remove(registration.id) {
+                Some(issues) => issues,
+                None => {
+                    let _span = info_span!("detector", id = registration.id).entered();
+                    let start = Instant::now();
+                    let issues = create().detect(&ctx.graph);
+                    ctx.record_phase(self.name(), registration.id, start.elapsed());
+                    issues
+                }
+            };
+            if issues.is_empty() {
+                continue;
+            }
+            info!("{}: found {} issues", registration.id, issues.len());
+
+            if registration.supersedes_unreferenced {
+                let ids: HashSet<_> = issues.iter().map(|dc| dc.declaration.id.clone()).collect();
+                ctx.dead_code.retain(|dc| !ids.contains(&dc.declaration.id));
+            }
+            ctx.dead_code.extend(issues);
+        }
+
+        if ctx.options.detector_enabled("custom-rules") {
+            let start = Instant::now();
+            run_custom_rules(ctx)?;
+            ctx.record_phase(self.name(), "custom-rules", start.elapsed());
+        }
+
+        run_plugins(ctx);
+
+        if ctx.options.detector_enabled("dead-objects") {
+            let start = Instant::now();
+            let dead_code = std::mem::take(&mut ctx.dead_code);
+            ctx.dead_code =
+                aggregate_dead_containers(&ctx.graph, &ctx.entry_points, &ctx.reachable, dead_code);
+            ctx.record_phase(self.name(), "dead-objects", start.elapsed());
+            let objects = ctx
+                .dead_code
+                .iter()
+                .filter(|dc| dc.issue == DeadCodeIssue::EffectivelyDeadObject)
+                .count();
+            info!("dead-objects: found {} issues", objects);
+        }
+
+        if ctx.options.detector_enabled("dead-doc-links") {
+            let dead_links = dead_doc_link_findings(ctx);
+            info!("dead-doc-links: found {} issues", dead_links.len());
+            ctx.dead_code.extend(dead_links);
+        }
+
+        Ok(())
+    }
+}
+
+/// Run the rules of the custom rule file, skipping declarations already reported
+fn run_custom_rules(ctx: &mut PipelineContext) -> Result<()> {
+    let Some(path) = ctx
+        .options
+        .rules_file
+        .clone()
+        .or_else(|| RuleSet::discover(&ctx.root))
+    else {
+        return Ok(());
+    };
+
+    let rule_set = RuleSet::from_file(&path)?;
+    info!(
+        "Loaded {} custom rules from {}",
+        rule_set.rules.len(),
+        path.display()
+    );
+
+    let reported: HashSet<_> = ctx
+        .dead_code
+        .iter()
+        .map(|dc| dc.declaration.id.clone())
+        .collect();
+    let issues: Vec<DeadCode> = CustomRuleDetector::new(rule_set)
+        .detect(&ctx.graph)
+        .into_iter()
+        .filter(|dc| !reported.contains(&dc.declaration.id))
+        .collect();
+    info!("custom-rules: found {} issues", issues.len());
+    ctx.dead_code.extend(issues);
+    Ok(())
+}
+
+/// Run the external detector plugins, skipping declarations already reported.
+/// A failing plugin is reported as a warning and does not stop the analysis
+fn run_plugins(ctx: &mut PipelineContext) {
+    let from_cli = ctx.options.plugins.iter().map(|command| PluginConfig {
+        name: Path::new(command)
+            .file_stem()
+            .map_or_else(|| command.clone(), |s| s.to_string_lossy().into_owned()),
+        command: command.clone(),
+        args: Vec::new(),
+    });
+    let plugins: Vec<PluginConfig> = ctx.config.plugins.iter().cloned().chain(from_cli).collect();
+
+    for plugin in plugins {
+        let detector = PluginDetector::new(plugin);
+        let start = Instant::now();
+        let result = detector.run(&ctx.graph, &ctx.entry_points, &ctx.root);
+        ctx.record_phase(
+            "detectors",
+            &format!("plugin {}", detector.name()),
+            start.elapsed(),
+        );
+        let issues = match result {
+            Ok(issues) => issues,
+            Err(e) => {
+                ctx.warnings
+                    .push(format!("Plugin {} failed: {}", detector.name(), e));
+                continue;
+            }
+        };
+
+        let reported: HashSet<_> = ctx
+            .dead_code
+            .iter()
+            .map(|dc| dc.declaration.id.clone())
+            .collect();
+        let issues: Vec<DeadCode> = issues
+            .into_iter()
+            .filter(|dc| !reported.contains(&dc.declaration.id))
+            .collect();
+        info!("plugin {}: found {} issues", detector.name(), issues.len());
+        ctx.dead_code.extend(issues);
+    }
+}
+
+/// Doc comment links to declarations reported as dead, each reported at the
+/// link. Parameters are skipped: KDoc names them with the same brackets.
+fn dead_doc_link_findings(ctx: &PipelineContext) -> Vec<DeadCode> {
+    let dead: HashSet<&DeclarationId> = ctx
+        .dead_code
+        .iter()
+        .filter(|dc| is_deletable(dc.issue))
+        .filter(|dc| dc.declaration.kind != DeclarationKind::Parameter)
+        .map(|dc| &dc.declaration.id)
+        .collect();
+
+    let mut findings: Vec<DeadCode> = ctx
+        .graph
+        .soft_references()
+        .filter(|(to, _, _)| dead.contains(to))
+        .filter_map(|(to, _, reference)| {
+            let mut decl = ctx.graph.get_declaration(to)?.clone();
+            decl.location = reference.location.clone();
+            Some(DeadCode::new(decl, DeadCodeIssue::DeadDocLink))
+        })
+        .collect();
+
+    findings.sort_by(|a, b| {
+        let a = &a.declaration.location;
+        let b = &b.declaration.location;
+        a.file.cmp(&b.file).then(a.start_byte.cmp(&b.start_byte))
+    });
+    findings
+}
+
+/// Step 9: Run source-based analyses. Dead branches, Compose state and
+/// uninvoked function parameters join the dead code list; the other analyses
+/// are reported on their own
+pub struct SourceAnalysisStage;
+
+impl Stage for SourceAnalysisStage {
+    fn name(&self) -> &'static str {
+        "source_analysis"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        if ctx.options.detector_enabled("unused-resources") {
+            let resource_analysis = ResourceDetector::new().analyze(&ctx.root);
+            info!(
+                "Found {} unused resources ({} total defined, {} referenced)",
+                resource_analysis.unused.len(),
+                resource_analysis
+                    .defined
+                    .values()
+                    .map(|m| m.len())
+                    .sum::<usize>(),
+                resource_analysis.referenced.len()
+            );
+            ctx.resources = Some(resource_analysis);
+        }
+
+        if ctx.options.detector_enabled("unused-extras") {
+            let intent_analysis = UnusedIntentExtraDetector::new().analyze(&ctx.root);
+            info!(
+                "Found {} unused Intent extras ({} total put, {} retrieved)",
+                intent_analysis.unused_extras.len(),
+                intent_analysis.total_put,
+                intent_analysis.total_get
+            );
+            ctx.intent_extras = Some(intent_analysis);
+        }
+
+        if ctx.options.detector_enabled("unused-bundle-args") {
+            let bundle_analysis = UnusedBundleArgumentDetector::new().analyze(&ctx.root);
+            info!(
+                "Found {} unused Bundle arguments ({} set, {} read)",
+                bundle_analysis.unread.len(),
+                bundle_analysis.total_set,
+                bundle_analysis.total_read
+            );
+            ctx.bundle_args = Some(bundle_analysis);
+        }
+
+        if ctx.options.detector_enabled("unused-broadcasts") {
+            let broadcast_analysis = UnusedBroadcastActionDetector::new().analyze(&ctx.root);
+            info!(
+                "Found {} unmatched broadcast actions ({} sent, {} received)",
+                broadcast_analysis.unreceived.len() + broadcast_analysis.unsent.len(),
+                broadcast_analysis.total_sent,
+                broadcast_analysis.total_received
+            );
+            ctx.broadcasts = Some(broadcast_analysis);
+        }
+
+        if ctx.options.detector_enabled("unused-pref-keys") {
+            let pref_key_analysis = UnusedPrefKeyDetector::new().analyze(&ctx.root);
+            info!(
+                "Found {} unused preference keys ({} written, {} read)",
+                pref_key_analysis.unread.len() + pref_key_analysis.unused_definitions.len(),
+                pref_key_analysis.total_written,
+                pref_key_analysis.total_read
+            );
+            ctx.pref_keys = Some(pref_key_analysis);
+        }
+
+        if ctx.options.detector_enabled("write-only-prefs") {
+            let prefs_detector = WriteOnlyPrefsDetector::new();
+            let mut prefs_analysis = SharedPrefsAnalysis::new();
+            for file in ctx.files.iter().filter(|f| f.file_type == FileType::Kotlin) {
+                if let Ok(content) = std::fs::read_to_string(&file.path) {
+                    let file_analysis = prefs_detector.analyze_source(&content, &file.path);
+                    for (key, locs) in file_analysis.writes {
+                        for loc in locs {
+                            prefs_analysis.add_write(key.clone(), loc.file, loc.line);
+                        }
+                    }
+                    for (key, locs) in file_analysis.reads {
+                        for loc in locs {
+                            prefs_analysis.add_read(key.clone(), loc.file, loc.line);
+                        }
+                    }
+                }
+            }
+            info!(
+                "Found {} write-only SharedPreferences keys",
+                prefs_analysis.get_write_only_keys().len()
+            );
+            ctx.shared_prefs = Some(prefs_analysis);
+        }
+
+        if ctx.options.detector_enabled("write-only-dao") {
+            let dao_detector = WriteOnlyDaoDetector::new();
+            let mut dao_analysis = DaoCollectionAnalysis::new();
+            for file in ctx.files.iter().filter(|f| f.file_type == FileType::Kotlin) {
+                if let Ok(content) = std::fs::read_to_string(&file.path) {
+                    let file_analysis = dao_detector.analyze_source(&content, &file.path);
+                    dao_analysis.daos.extend(file_analysis.daos);
+                }
+            }
+            info!(
+                "Found {} write-only Room DAOs",
+                dao_analysis.get_write_only_daos().len()
+            );
+            ctx.daos = Some(dao_analysis);
+        }
+
+        if ctx.options.detector_enabled("unused-module-deps") {
+            let module_graph = ModuleDependencyGraph::analyze(&ctx.root);
+            info!(
+                "Found {} unused module dependencies ({} modules, {} declared)",
+                module_graph.unused().len(),
+                module_graph.modules.len(),
+                module_graph.dependencies.len()
+            );
+            ctx.module_deps = Some(module_graph);
+        }
+
+        if ctx.options.detector_enabled("unused-nav-args") {
+            let nav_analysis = UnusedNavArgumentDetector::new().analyze(&ctx.root);
+            info!(
+                "Found {} unused navigation arguments, {} unused actions and {} unreachable destinations",
+                nav_analysis.unused_arguments.len(),
+                nav_analysis.unused_actions.len(),
+                nav_analysis.unreachable_destinations.len()
+            );
+            ctx.navigation = Some(nav_analysis);
+        }
+
+        if ctx.options.detector_enabled("deep-links") {
+            let link_analysis = DeepLinkDetector::new().analyze(&ctx.root);
+            info!(
+                "Found {} deep link routes ({} never opened, {} links without a handler)",
+                link_analysis.routes.len(),
+                link_analysis.unopened_routes.len(),
+                link_analysis.unhandled_links.len()
+            );
+            ctx.deep_links = Some(link_analysis);
+        }
+
+        if ctx.options.detector_enabled("dead-branches") {
+            let sources: Vec<_> = ctx
+                .files
+                .iter()
+                .filter(|f| f.file_type.is_source())
+                .filter_map(|f| Some((f.path.clone(), f.read_contents().ok()?)))
+                .collect();
+            let branches = DeadBranchDetector::new()
+                .with_build_config(ctx.config.android.build_config.clone())
+                .analyze(&sources);
+            info!("Found {} dead branches", branches.len());
+            ctx.dead_code
+                .extend(branches.iter().map(DeadBranch::to_dead_code));
+        }
+
+        if ctx.options.detector_enabled("compose-state") {
+            let sources: Vec<_> = ctx
+                .files
+                .iter()
+                .filter(|f| f.file_type == FileType::Kotlin)
+                .filter_map(|f| Some((f.path.clone(), f.read_contents().ok()?)))
+                .collect();
+            let findings = ComposeStateDetector::new().analyze(&sources);
+            info!("compose-state: found {} issues", findings.len());
+            ctx.dead_code.extend(findings);
+        }
+
+        if ctx.options.detector_enabled("uninvoked-params") {
+            let sources: Vec<_> = ctx
+                .files
+                .iter()
+                .filter(|f| f.file_type == FileType::Kotlin)
+                .filter_map(|f| Some((f.path.clone(), f.read_contents().ok()?)))
+                .collect();
+            // Parameters never used at all are already reported as unused
+            let unused: HashSet<DeclarationId> = ctx
+                .dead_code
+                .iter()
+                .filter(|dc| dc.issue == DeadCodeIssue::UnusedParameter)
+                .map(|dc| dc.declaration.id.clone())
+                .collect();
+            let findings: Vec<DeadCode> = UninvokedParamDetector::new()
+                .analyze(&sources)
+                .into_iter()
+                .filter(|dc| !unused.contains(&dc.declaration.id))
+                .collect();
+            info!("uninvoked-params: found {} issues", findings.len());
+            ctx.dead_code.extend(findings);
+        }
+
+        Ok(())
+    }
+}
+
+/// Step 10: Apply configured detector and package severity overrides, the
+/// reflection downgrade and inline suppressions, then filter findings by
+/// confidence, runtime confirmation and risk tags and sort them into report
+/// order
+pub struct ConfidenceFilterStage;
+
+impl Stage for ConfidenceFilterStage {
+    fn name(&self) -> &'static str {
+        "filter"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        ctx.config.detectors.apply(&mut ctx.dead_code);
+        ctx.config.severity.apply(&mut ctx.dead_code);
+
+        if ctx.config.reflection.enabled && !ctx.dead_code.is_empty() {
+            let reflection = ReflectionUsage::scan(&ctx.files);
+            if !reflection.is_empty() {
+                reflection.apply(&mut ctx.dead_code, &ctx.config.reflection);
+            }
+        }
+
+        ctx.suppressed =
+            suppression::apply(&mut ctx.dead_code, &ctx.graph, &ctx.config.suppression);
+        if !ctx.suppressed.is_empty() {
+            info!("{} findings are suppressed inline", ctx.suppressed.len());
+        }
+
+        let options = &ctx.options;
+        ctx.dead_code.retain(|dc| is_reported(dc, options));
+        sort_findings(&mut ctx.dead_code);
+
+        info!("Found {} dead code candidates", ctx.dead_code.len());
+        Ok(())
+    }
+}
+
+/// Whether a finding passes the confidence, runtime confirmation, risk tag
+/// and size filters
+fn is_reported(dc: &DeadCode, options: &PipelineOptions) -> bool {
+    // Generated declarations only keep handwritten code alive
+    !is_generated_source(&dc.declaration.location.file)
+        && dc.confidence >= options.min_confidence
+        && (!options.runtime_only || dc.runtime_confirmed)
+        && !options.exclude_risk.iter().any(|tag| dc.has_risk_tag(tag))
+        && dc.declaration.size.map_or(1, |size| size.lines) >= options.min_size
+}
+
+/// Step 11: Detect zombie code cycles
+pub struct CycleStage;
+
+impl Stage for CycleStage {
+    fn name(&self) -> &'static str {
+        "cycles"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        if !ctx.options.detector_enabled("cycles") {
+            return Ok(());
+        }
+
+        let cycle_detector = CycleDetector::new();
+        let cycle_stats = cycle_detector.get_cycle_stats(&ctx.graph, &ctx.reachable);
+        if cycle_stats.has_cycles() {
+            ctx.dead_cycles = cycle_detector.find_dead_cycles(&ctx.graph, &ctx.reachable);
+        }
+        ctx.cycle_stats = Some(cycle_stats);
+
+        Ok(())
+    }
+}
+
+/// Step 12: Generate and/or apply a baseline
+pub struct BaselineStage;
+
+impl Stage for BaselineStage {
+    fn name(&self) -> &'static str {
+        "baseline"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        if let Some(baseline_path) = ctx.options.generate_baseline.clone() {
+            info!("Generating baseline file...");
+            let baseline = Baseline::from_findings(&ctx.dead_code, &ctx.root);
+            match baseline.save(&baseline_path) {
+                Ok(_) => ctx.baseline_generated = Some(ctx.dead_code.len()),
+                Err(e) => {
+                    ctx.warnings
+                        .push(format!("Failed to generate baseline: {}", e));
+                }
+            }
+        }
+
+        if let Some(baseline_path) = ctx.options.baseline.clone() {
+            match Baseline::load(&baseline_path) {
+                Ok(baseline) => {
+                    ctx.baseline_stats = Some(baseline.stats(&ctx.dead_code, &ctx.root));
+                    let (baselined, new): (Vec<DeadCode>, Vec<DeadCode>) =
+                        std::mem::take(&mut ctx.dead_code)
+                            .into_iter()
+                            .partition(|dc| baseline.is_baselined(dc, &ctx.root));
+                    ctx.baselined = baselined;
+                    ctx.dead_code = new;
+                }
+                Err(e) => {
+                    ctx.warnings.push(format!("Failed to load baseline: {}", e));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Step 13: Collapse the findings of entirely unused files into one per file
+pub struct DeadFileStage;
+
+impl Stage for DeadFileStage {
+    fn name(&self) -> &'static str {
+        "dead_files"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        if !ctx.options.detector_enabled("dead-files") {
+            return Ok(());
+        }
+
+        let dead_code = std::mem::take(&mut ctx.dead_code);
+        ctx.dead_code = aggregate_dead_files(&ctx.graph, dead_code);
+
+        let files = ctx
+            .dead_code
+            .iter()
+            .filter(|dc| dc.issue == DeadCodeIssue::DeadFile)
+            .count();
+        if files > 0 {
+            info!("Found {} entirely unused files", files);
+        }
+        Ok(())
+    }
+}
+
+/// Step 14: Keep only findings in files changed since the diff base. The
+/// graph still covers the whole project, so reachability stays correct.
+pub struct ChangedFilesStage;
+
+impl Stage for ChangedFilesStage {
+    fn name(&self) -> &'static str {
+        "changed_files"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        let Some(base) = ctx.options.diff_base.as_deref() else {
+            return Ok(());
+        };
+
+        let changed = ChangedFiles::since(&ctx.root, base)?;
+        ctx.dead_code
+            .retain(|dc| changed.contains(&dc.declaration.location.file));
+
+        info!(
+            "Found {} dead code candidates in {} files changed since {}",
+            ctx.dead_code.len(),
+            changed.file_count(),
+            base
+        );
+        Ok(())
+    }
+}
+
+/// Step 15: Keep only findings in the package subtrees selected with
+/// `--only-package`
+pub struct PackageScopeStage;
+
+impl Stage for PackageScopeStage {
+    fn name(&self) -> &'static str {
+        "package_scope"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        let scope = PackageScope::new(&ctx.options.only_packages);
+        if scope.is_empty() {
+            return Ok(());
+        }
+
+        let mut packages: HashMap<PathBuf, Option<String>> = HashMap::new();
+        ctx.dead_code.retain(|dc| {
+            let file = &dc.declaration.location.file;
+            packages
+                .entry(file.clone())
+                .or_insert_with(|| {
+                    std::fs::read_to_string(file)
+                        .ok()
+                        .as_deref()
+                        .and_then(declared_package)
+                        .map(str::to_string)
+                })
+                .as_deref()
+                .is_some_and(|package| scope.contains(package))
+        });
+
+        info!(
+            "Found {} dead code candidates in {}",
+            ctx.dead_code.len(),
+            scope.packages().join(", ")
+        );
+        Ok(())
+    }
+}
+
+/// Step 16: Attribute findings to owners, and to the project root they come
+/// from when several roots are analyzed together
+pub struct OwnershipStage;
+
+impl Stage for OwnershipStage {
+    fn name(&self) -> &'static str {
+        "ownership"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        if ctx.roots.len() > 1 {
+            assign_roots(ctx);
+        }
+
+        let options = &ctx.options;
+        if !options.owners && options.codeowners.is_none() && !options.blame {
+            return Ok(());
+        }
+
+        let mut resolver = OwnershipResolver::new(ctx.root.clone()).with_blame(options.blame);
+
+        let codeowners_path = options
+            .codeowners
+            .clone()
+            .or_else(|| CodeOwners::discover(&ctx.root));
+        if let Some(path) = codeowners_path {
+            match CodeOwners::load(&path) {
+                Ok(codeowners) => {
+                    info!(
+                        "Loaded {} CODEOWNERS rules from {}",
+                        codeowners.rules().len(),
+                        path.display()
+                    );
+                    resolver = resolver.with_codeowners(codeowners);
+                }
+                Err(e) => {
+                    ctx.warnings.push(format!(
+                        "Failed to load CODEOWNERS {}: {}",
+                        path.display(),
+                        e
+                    ));
+                }
+            }
+        } else if !ctx.options.blame {
+            ctx.warnings
+                .push("No CODEOWNERS file found; use --codeowners or --blame".to_string());
+        }
+
+        resolver.assign(&mut ctx.dead_code);
+        Ok(())
+    }
+}
+
+/// Step 17: Group the reported findings into deletion units
+pub struct ClusterStage;
+
+impl Stage for ClusterStage {
+    fn name(&self) -> &'static str {
+        "clusters"
+    }
+
+    fn run(&self, ctx: &mut PipelineContext) -> Result<()> {
+        if ctx.options.cluster {
+            ctx.deletion_units = cluster_findings(&ctx.graph, &ctx.dead_code);
+        }
+        Ok(())
+    }
+}
+
+/// Tag each finding, reported or baselined, with the root containing its file
+fn assign_roots(ctx: &mut PipelineContext) {
+    let roots: Vec<Option<String>> = ctx
+        .dead_code
+        .iter()
+        .chain(&ctx.baselined)
+        .map(|dc| {
+            ctx.root_of(&dc.declaration.location.file)
+                .map(|root| root.display().to_string())
+        })
+        .collect();
+    for (dc, root) in ctx
+        .dead_code
+        .iter_mut()
+        .chain(ctx.baselined.iter_mut())
+        .zip(roots)
+    {
+        dc.root = root;
+    }
+}
@@ -0,0 +1,173 @@
+//! Phase timings
+//!
+//! Breaks a run down by stage, and within a stage by the phases it records
+//! (graph resolution, each detector), next to the counts that explain the
+//! time: files, declarations, references, entry points. Printed with
+//! `--timings` and included in the JSON report.
+
+use super::PipelineContext;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Time spent in the phases of a run
+#[derive(Debug, Clone, Serialize)]
+pub struct TimingsReport {
+    /// Stages in execution order, each followed by its own phases
+    pub phases: Vec<PhaseTiming>,
+    pub total_seconds: f64,
+    /// Sizes of the analyzed project and of the results
+    pub counters: BTreeMap<&'static str, usize>,
+}
+
+/// Time spent in a stage or in one phase of a stage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    /// Stage name, or `stage/phase` for a phase within a stage
+    pub phase: String,
+    pub seconds: f64,
+    /// Share of the total run time
+    pub percent: f64,
+}
+
+impl TimingsReport {
+    /// Collect the timings of a finished pipeline run
+    pub fn from_context(ctx: &PipelineContext) -> Self {
+        let total_seconds: f64 = ctx.timings.iter().map(|(_, d)| d.as_secs_f64()).sum();
+        let percent = |seconds: f64| {
+            if total_seconds > 0.0 {
+                seconds * 100.0 / total_seconds
+            } else {
+                0.0
+            }
+        };
+
+        let mut phases = Vec::new();
+        for &(stage, duration) in &ctx.timings {
+            let seconds = duration.as_secs_f64();
+            phases.push(PhaseTiming {
+                phase: stage.to_string(),
+                seconds,
+                percent: percent(seconds),
+            });
+            let prefix = format!("{}/", stage);
+            for (phase, duration) in &ctx.phase_timings {
+                if phase.starts_with(&prefix) {
+                    let seconds = duration.as_secs_f64();
+                    phases.push(PhaseTiming {
+                        phase: phase.clone(),
+                        seconds,
+                        percent: percent(seconds),
+                    });
+                }
+            }
+        }
+
+        let counters = BTreeMap::from([
+            ("files", ctx.files.len()),
+            ("declarations", ctx.graph.declaration_count()),
+            ("references", ctx.graph.reference_count()),
+            ("entry_points", ctx.entry_points.len()),
+            ("reachable", ctx.reachable.len()),
+            ("findings", ctx.dead_code.len()),
+        ]);
+
+        Self {
+            phases,
+            total_seconds,
+            counters,
+        }
+    }
+
+    /// Render the phase breakdown as a table
+    pub fn to_table(&self) -> String {
+        // Phases within a stage are indented under it
+        let names: Vec<String> = self
+            .phases
+            .iter()
+            .map(|p| match p.phase.split_once('/') {
+                Some((_, name)) => format!("  {}", name),
+                None => p.phase.clone(),
+            })
+            .collect();
+        let width = names
+            .iter()
+            .map(String::len)
+            .chain(["Phase".len(), "total".len()])
+            .max()
+            .unwrap_or(0);
+
+        let mut table = String::new();
+        let _ = writeln!(table, "{:<width$}  {:>10}  {:>6}", "Phase", "Time", "%");
+        for (name, phase) in names.iter().zip(&self.phases) {
+            let _ = writeln!(
+                table,
+                "{:<width$}  {:>9.3}s  {:>5.1}%",
+                name, phase.seconds, phase.percent
+            );
+        }
+        let _ = writeln!(
+            table,
+            "{:<width$}  {:>9.3}s  {:>5.1}%",
+            "total", self.total_seconds, 100.0
+        );
+
+        let counters: Vec<String> = self
+            .counters
+            .iter()
+            .map(|(name, count)| format!("{} {}", count, name.replace('_', " ")))
+            .collect();
+        let _ = writeln!(table, "{}", counters.join(", "));
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::pipeline::PipelineOptions;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn test_timings_report() {
+        let mut ctx = PipelineContext::new(
+            Config::default(),
+            PathBuf::from("."),
+            PipelineOptions::default(),
+        );
+        ctx.timings = vec![
+            ("parse", Duration::from_millis(750)),
+            ("detectors", Duration::from_millis(250)),
+        ];
+        ctx.phase_timings = vec![
+            ("parse/resolve".to_string(), Duration::from_millis(300)),
+            (
+                "detectors/unused-class".to_string(),
+                Duration::from_millis(200),
+            ),
+        ];
+
+        let report = TimingsReport::from_context(&ctx);
+        let phases: Vec<(&str, f64)> = report
+            .phases
+            .iter()
+            .map(|p| (p.phase.as_str(), p.percent.round()))
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                ("parse", 75.0),
+                ("parse/resolve", 30.0),
+                ("detectors", 25.0),
+                ("detectors/unused-class", 20.0)
+            ]
+        );
+        assert_eq!(report.counters["files"], 0);
+
+        let table = report.to_table();
+        assert!(table.contains("  unused-class      0.200s   20.0%"));
+        assert!(table.contains("total"));
+    }
+}
@@ -4,9 +4,14 @@
 // - usage.txt: Lists code that ProGuard determined is unused
 // - seeds.txt: Lists code that matched -keep rules
 // - mapping.txt: Obfuscation mapping (for reverse lookups)
+// - proguard-rules.pro: -keep rules that retain code outside the code graph
 
 mod report_generator;
+mod rules;
+mod seeds;
 mod usage;
 
 pub use report_generator::ReportGenerator;
+pub use rules::KeepRules;
+pub use seeds::{ProguardSeeds, SeedsParser};
 pub use usage::{ProguardUsage, UsageEntryKind};
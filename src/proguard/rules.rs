@@ -0,0 +1,629 @@
+// ProGuard/R8 keep rules parser
+//
+// Parses the project's proguard-rules.pro / consumer-rules.pro files and
+// interprets the keep options that keep code alive outside of the code graph
+// (reflection, JNI callbacks, serialization):
+//
+// ```
+// -keep class com.example.model.** { *; }
+// -keepclassmembers class * { @com.example.Api <methods>; }
+// -keepclasseswithmembernames class * { native <methods>; }
+// -keepnames class * extends android.os.Parcelable
+// ```
+//
+// The `*names` variants only prevent obfuscation, but in source they signal
+// that a name is looked up at runtime, so they are treated as retention too.
+// Rules with the `allowshrinking` modifier are ignored.
+
+#![allow(dead_code)] // API methods reserved for future use
+
+use crate::graph::{simple_type_name, Declaration, DeclarationKind, Graph};
+use miette::{IntoDiagnostic, Result};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File names of keep rule files discovered in project modules
+pub const KEEP_RULE_FILES: &[&str] = &["proguard-rules.pro", "consumer-rules.pro"];
+
+/// Keep option of a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeepKind {
+    /// `-keep`: the class and the listed members
+    Keep,
+    /// `-keepclassmembers`: only the listed members
+    KeepClassMembers,
+    /// `-keepclasseswithmembers`: classes having the listed members, and those members
+    KeepClassesWithMembers,
+    /// `-keepnames`
+    KeepNames,
+    /// `-keepclassmembernames`
+    KeepClassMemberNames,
+    /// `-keepclasseswithmembernames`
+    KeepClassesWithMemberNames,
+}
+
+impl KeepKind {
+    fn from_option(option: &str) -> Option<Self> {
+        match option {
+            "-keep" => Some(KeepKind::Keep),
+            "-keepclassmembers" => Some(KeepKind::KeepClassMembers),
+            "-keepclasseswithmembers" => Some(KeepKind::KeepClassesWithMembers),
+            "-keepnames" => Some(KeepKind::KeepNames),
+            "-keepclassmembernames" => Some(KeepKind::KeepClassMemberNames),
+            "-keepclasseswithmembernames" => Some(KeepKind::KeepClassesWithMemberNames),
+            _ => None,
+        }
+    }
+
+    /// Whether matching classes themselves are kept (not only their members)
+    fn keeps_class(&self) -> bool {
+        !matches!(
+            self,
+            KeepKind::KeepClassMembers | KeepKind::KeepClassMemberNames
+        )
+    }
+
+    /// Whether a class is only kept when it has a matching member
+    fn requires_members(&self) -> bool {
+        matches!(
+            self,
+            KeepKind::KeepClassesWithMembers | KeepKind::KeepClassesWithMemberNames
+        )
+    }
+}
+
+/// A single keep rule
+#[derive(Debug, Clone)]
+pub struct KeepRule {
+    pub kind: KeepKind,
+    /// Required class annotation (simple name)
+    pub annotation: Option<String>,
+    /// `class`, `interface`, `enum` or `@interface`
+    pub class_type: String,
+    /// Comma separated class name patterns, optionally negated with `!`
+    pub class_names: Vec<String>,
+    /// Compiled `class_names`, with whether the pattern is negated
+    class_regexes: Vec<(bool, Regex)>,
+    /// Required supertype from `extends`/`implements`
+    pub super_type: Option<String>,
+    /// Member specifications from the rule body
+    pub members: Vec<MemberSpec>,
+    /// Rule text as written (whitespace collapsed)
+    pub text: String,
+    /// File and line the rule was read from
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// A member specification inside a keep rule body
+#[derive(Debug, Clone)]
+pub struct MemberSpec {
+    /// Required member annotation (simple name)
+    pub annotation: Option<String>,
+    /// Required modifiers such as `native` or `public`
+    pub modifiers: Vec<String>,
+    pub kind: MemberKind,
+    /// Name pattern, `*` for any
+    pub name: String,
+    /// Compiled `name`, None when it matches any name
+    name_regex: Option<Regex>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberKind {
+    /// `*` or a name without a type
+    Any,
+    /// `<methods>` or `type name(args)`
+    Method,
+    /// `<fields>` or `type name`
+    Field,
+    /// `<init>(...)`
+    Constructor,
+}
+
+/// All keep rules of a project
+#[derive(Debug, Clone, Default)]
+pub struct KeepRules {
+    rules: Vec<KeepRule>,
+}
+
+impl KeepRules {
+    /// Parse a keep rules file
+    pub fn parse(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).into_diagnostic()?;
+        Ok(Self::parse_content(&content, path))
+    }
+
+    /// Parse keep rules content read from `file`
+    pub fn parse_content(content: &str, file: &Path) -> Self {
+        let mut rules = Vec::new();
+
+        for (line, text) in split_options(content) {
+            if let Some(rule) = parse_rule(&text, file, line) {
+                rules.push(rule);
+            }
+        }
+
+        Self { rules }
+    }
+
+    /// Find and parse every keep rules file of the project's modules
+    pub fn discover(root: &Path) -> Self {
+        let mut all = Self::default();
+
+        let walker = walkdir::WalkDir::new(root).into_iter().filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            !name.starts_with('.') && name != "build" && name != "generated"
+        });
+
+        for entry in walker.flatten() {
+            let name = entry.file_name().to_string_lossy();
+            if entry.file_type().is_file() && KEEP_RULE_FILES.contains(&name.as_ref()) {
+                if let Ok(rules) = Self::parse(entry.path()) {
+                    all.extend(rules);
+                }
+            }
+        }
+
+        all
+    }
+
+    /// Add the rules of another file
+    pub fn extend(&mut self, other: KeepRules) {
+        self.rules.extend(other.rules);
+    }
+
+    pub fn rules(&self) -> &[KeepRule] {
+        &self.rules
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The first rule keeping `decl` alive, if any
+    pub fn keeping(&self, decl: &Declaration, graph: &Graph) -> Option<&KeepRule> {
+        self.rules.iter().find(|rule| rule.keeps(decl, graph))
+    }
+}
+
+impl KeepRule {
+    /// Whether this rule keeps the declaration
+    pub fn keeps(&self, decl: &Declaration, graph: &Graph) -> bool {
+        if is_class_like(decl.kind) {
+            return self.kind.keeps_class()
+                && self.matches_class(decl, graph)
+                && (!self.kind.requires_members() || self.has_matching_member(decl, graph));
+        }
+
+        let Some(class) = decl.parent.as_ref().and_then(|p| graph.get_declaration(p)) else {
+            return false;
+        };
+        is_class_like(class.kind)
+            && self.matches_class(class, graph)
+            && self.members.iter().any(|member| member.matches(decl))
+    }
+
+    /// Location and text for messages, e.g. `proguard-rules.pro:12: -keep class ...`
+    pub fn describe(&self) -> String {
+        let file = self
+            .file
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        format!("{}:{}: {}", file, self.line, self.text)
+    }
+
+    fn matches_class(&self, decl: &Declaration, graph: &Graph) -> bool {
+        let type_matches = match self.class_type.as_str() {
+            "interface" => decl.kind == DeclarationKind::Interface,
+            "enum" => decl.kind == DeclarationKind::Enum,
+            "@interface" => decl.kind == DeclarationKind::Annotation,
+            _ => true,
+        };
+        if !type_matches {
+            return false;
+        }
+
+        if let Some(annotation) = &self.annotation {
+            if !has_annotation(decl, annotation) {
+                return false;
+            }
+        }
+
+        if let Some(super_type) = &self.super_type {
+            if !graph.inherits_from(decl, super_type) {
+                return false;
+            }
+        }
+
+        let name = decl.fully_qualified_name.as_deref().unwrap_or(&decl.name);
+        let mut matched = false;
+        for (negated, regex) in &self.class_regexes {
+            if regex.is_match(name) {
+                if *negated {
+                    return false;
+                }
+                matched = true;
+            }
+        }
+        matched
+    }
+
+    fn has_matching_member(&self, class: &Declaration, graph: &Graph) -> bool {
+        graph
+            .get_children(&class.id)
+            .into_iter()
+            .filter_map(|id| graph.get_declaration(id))
+            .any(|child| self.members.iter().any(|member| member.matches(child)))
+    }
+}
+
+impl MemberSpec {
+    /// Parse a member specification such as `public void set*(***)` or
+    /// `native <methods>`
+    fn parse(spec: &str) -> Option<Self> {
+        let mut annotation = None;
+        let mut modifiers = Vec::new();
+        let mut words = Vec::new();
+
+        for token in spec.split_whitespace() {
+            if let Some(name) = token.strip_prefix('@') {
+                annotation = Some(simple_type_name(name).to_string());
+            } else if is_modifier(token) {
+                // Negated modifiers (`!private`) only narrow the match; ignore them
+                if !token.starts_with('!') {
+                    modifiers.push(token.to_string());
+                }
+            } else {
+                words.push(token);
+            }
+        }
+        let rest = words.join(" ");
+        if rest.is_empty() {
+            return None;
+        }
+
+        let (kind, name) = match rest.as_str() {
+            "*" => (MemberKind::Any, "*".to_string()),
+            "<methods>" => (MemberKind::Method, "*".to_string()),
+            "<fields>" => (MemberKind::Field, "*".to_string()),
+            _ if rest.starts_with("<init>") => (MemberKind::Constructor, "<init>".to_string()),
+            _ => match rest.find('(') {
+                Some(paren) => {
+                    let before = rest[..paren].trim();
+                    let name = before.rsplit(' ').next().unwrap_or(before);
+                    (MemberKind::Method, name.to_string())
+                }
+                None => {
+                    let name = rest.rsplit(' ').next().unwrap_or(&rest);
+                    (MemberKind::Field, name.to_string())
+                }
+            },
+        };
+
+        Some(Self {
+            annotation,
+            modifiers,
+            kind,
+            name_regex: member_name_regex(&name),
+            name,
+        })
+    }
+
+    fn matches(&self, decl: &Declaration) -> bool {
+        let kind_matches = match self.kind {
+            MemberKind::Any => true,
+            MemberKind::Method => {
+                matches!(
+                    decl.kind,
+                    DeclarationKind::Method | DeclarationKind::Function
+                )
+            }
+            MemberKind::Field => {
+                matches!(
+                    decl.kind,
+                    DeclarationKind::Field | DeclarationKind::Property
+                )
+            }
+            MemberKind::Constructor => decl.kind == DeclarationKind::Constructor,
+        };
+        if !kind_matches {
+            return false;
+        }
+
+        if let Some(annotation) = &self.annotation {
+            if !has_annotation(decl, annotation) {
+                return false;
+            }
+        }
+
+        let visibility = format!("{:?}", decl.visibility).to_lowercase();
+        let has_modifiers = self.modifiers.iter().all(|modifier| {
+            // Kotlin declares JNI methods as `external`
+            *modifier == visibility
+                || decl
+                    .modifiers
+                    .iter()
+                    .any(|m| m == modifier || (modifier == "native" && m == "external"))
+        });
+
+        has_modifiers
+            && self
+                .name_regex
+                .as_ref()
+                .is_none_or(|regex| regex.is_match(&decl.name))
+    }
+}
+
+fn is_class_like(kind: DeclarationKind) -> bool {
+    kind.is_type() && kind != DeclarationKind::TypeAlias
+}
+
+fn is_modifier(token: &str) -> bool {
+    matches!(
+        token.trim_start_matches('!'),
+        "public"
+            | "private"
+            | "protected"
+            | "static"
+            | "final"
+            | "abstract"
+            | "native"
+            | "synchronized"
+            | "transient"
+            | "volatile"
+            | "synthetic"
+            | "bridge"
+            | "varargs"
+            | "strictfp"
+    )
+}
+
+fn has_annotation(decl: &Declaration, annotation: &str) -> bool {
+    let expected = simple_type_name(annotation);
+    decl.annotations
+        .iter()
+        .any(|a| simple_type_name(a) == expected)
+}
+
+/// Split rules content into options (`-keep ...`), with their line numbers
+fn split_options(content: &str) -> Vec<(usize, String)> {
+    let mut options: Vec<(usize, String)> = Vec::new();
+    let mut depth = 0usize;
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line = raw_line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if depth == 0 && line.starts_with('-') {
+            options.push((index + 1, String::new()));
+        }
+        if let Some((_, text)) = options.last_mut() {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(line);
+        }
+
+        for c in line.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+    }
+
+    options
+}
+
+/// Parse a single keep option; other options (e.g. `-dontwarn`) yield None
+fn parse_rule(text: &str, file: &Path, line: usize) -> Option<KeepRule> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let (option, rest) = text.split_once(' ')?;
+
+    let mut option_parts = option.split(',');
+    let kind = KeepKind::from_option(option_parts.next()?)?;
+    if option_parts.any(|modifier| modifier == "allowshrinking") {
+        return None;
+    }
+
+    let (class_spec, body) = match rest.split_once('{') {
+        Some((spec, body)) => (spec, body.trim_end().trim_end_matches('}')),
+        None => (rest, ""),
+    };
+
+    let mut annotation = None;
+    let mut class_type = None;
+    let mut class_names = Vec::new();
+    let mut super_type = None;
+    let mut tokens = class_spec.split_whitespace().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            "class" | "interface" | "enum" | "@interface" | "!interface" | "!enum" => {
+                class_type = Some(token.trim_start_matches('!').to_string());
+                // Names may be written as "A, B" or "A,B"
+                while let Some(next) = tokens.peek() {
+                    if matches!(*next, "extends" | "implements") {
+                        break;
+                    }
+                    class_names.extend(
+                        next.split(',')
+                            .filter(|name| !name.is_empty())
+                            .map(str::to_string),
+                    );
+                    tokens.next();
+                }
+            }
+            "extends" | "implements" => {
+                let mut next = tokens.next()?;
+                if next.starts_with('@') {
+                    next = tokens.next()?;
+                }
+                super_type = Some(next.to_string());
+            }
+            _ if token.starts_with('@') && class_type.is_none() => {
+                annotation = Some(simple_type_name(token).to_string());
+            }
+            _ => {}
+        }
+    }
+
+    let class_regexes = class_names
+        .iter()
+        .filter_map(|pattern| match pattern.strip_prefix('!') {
+            Some(negated) => class_name_regex(negated).map(|regex| (true, regex)),
+            None => class_name_regex(pattern).map(|regex| (false, regex)),
+        })
+        .collect();
+
+    Some(KeepRule {
+        kind,
+        annotation,
+        class_type: class_type?,
+        class_names,
+        class_regexes,
+        super_type,
+        members: body.split(';').filter_map(MemberSpec::parse).collect(),
+        text,
+        file: file.to_path_buf(),
+        line,
+    })
+}
+
+/// Compile a ProGuard class name pattern matched against fully qualified names
+///
+/// `?` matches one character except `.`, `*` any run without `.`, and `**`
+/// any run including `.`. A lone `*` matches any class in any package.
+fn class_name_regex(pattern: &str) -> Option<Regex> {
+    if pattern == "*" {
+        return Regex::new(".*").ok();
+    }
+
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^.]*"),
+            '?' => regex.push_str("[^.]"),
+            // Nested classes are written with `$` in rules but `.` in source
+            '$' => regex.push_str("[.$]"),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+
+    Regex::new(&regex).ok()
+}
+
+/// Compile a member name pattern (`*` and `?` wildcards); None matches any name
+fn member_name_regex(pattern: &str) -> Option<Regex> {
+    if pattern == "*" || pattern == "<init>" {
+        return None;
+    }
+    let regex = format!(
+        "^{}$",
+        regex::escape(pattern)
+            .replace(r"\*", ".*")
+            .replace(r"\?", ".")
+    );
+    Regex::new(&regex).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, Language, Location};
+
+    const RULES: &str = r#"
+# Models are created through reflection
+-keep class com.example.model.** { *; }
+-keepclassmembers class * {
+    @com.example.Api <methods>;
+}
+-keepclasseswithmembernames,includedescriptorclasses class * {
+    native <methods>;
+}
+-keep,allowshrinking class com.example.Optional
+-dontwarn okhttp3.**
+"#;
+
+    fn make_decl(path: &str, kind: DeclarationKind, parent: Option<&DeclarationId>) -> Declaration {
+        let file = PathBuf::from("src/Test.kt");
+        let start = path.len() * 100;
+        let mut decl = Declaration::new(
+            DeclarationId::new(file.clone(), start, start + 5),
+            path.rsplit('.').next().unwrap().to_string(),
+            kind,
+            Location::new(file, 1, 1, start, start + 5),
+            Language::Kotlin,
+        );
+        decl.fully_qualified_name = Some(format!("com.example.{}", path));
+        decl.parent = parent.cloned();
+        decl
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let rules = KeepRules::parse_content(RULES, Path::new("app/proguard-rules.pro"));
+        assert_eq!(rules.rules().len(), 3);
+
+        let keep = &rules.rules()[0];
+        assert_eq!(keep.kind, KeepKind::Keep);
+        assert_eq!(keep.class_names, vec!["com.example.model.**"]);
+        assert_eq!(keep.line, 3);
+        assert_eq!(
+            keep.describe(),
+            "proguard-rules.pro:3: -keep class com.example.model.** { *; }"
+        );
+
+        let members = &rules.rules()[1];
+        assert_eq!(members.kind, KeepKind::KeepClassMembers);
+        assert_eq!(members.members[0].annotation.as_deref(), Some("Api"));
+        assert_eq!(members.members[0].kind, MemberKind::Method);
+
+        let native = &rules.rules()[2];
+        assert_eq!(native.members[0].modifiers, vec!["native"]);
+    }
+
+    #[test]
+    fn test_rules_keep_declarations() {
+        let rules = KeepRules::parse_content(RULES, Path::new("proguard-rules.pro"));
+        let mut graph = Graph::new();
+
+        let model = graph.add_declaration(make_decl("model.User", DeclarationKind::Class, None));
+        let field = make_decl("model.User.email", DeclarationKind::Property, Some(&model));
+        let field = graph.add_declaration(field);
+
+        let service = graph.add_declaration(make_decl("Service", DeclarationKind::Class, None));
+        let mut api = make_decl("Service.fetch", DeclarationKind::Method, Some(&service));
+        api.annotations.push("@Api".to_string());
+        let api = graph.add_declaration(api);
+
+        let bridge = graph.add_declaration(make_decl("Bridge", DeclarationKind::Class, None));
+        let mut callback = make_decl("Bridge.onEvent", DeclarationKind::Method, Some(&bridge));
+        callback.modifiers.push("external".to_string());
+        let callback = graph.add_declaration(callback);
+
+        let kept = |id: &DeclarationId| {
+            rules
+                .keeping(graph.get_declaration(id).unwrap(), &graph)
+                .map(|rule| rule.line)
+        };
+        assert_eq!(kept(&model), Some(3));
+        assert_eq!(kept(&field), Some(3));
+        assert_eq!(kept(&service), None);
+        assert_eq!(kept(&api), Some(4));
+        assert_eq!(kept(&bridge), Some(7));
+        assert_eq!(kept(&callback), Some(7));
+    }
+}
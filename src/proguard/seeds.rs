@@ -0,0 +1,224 @@
+// ProGuard/R8 seeds.txt parser
+//
+// The seeds.txt file (`-printseeds`) lists the classes and members that
+// matched a keep rule. R8 keeps them whether or not anything uses them, so a
+// seed that nothing in the code references is only alive because of its rule.
+//
+// Format:
+// ```
+// com.example.Api
+// com.example.Api: void fetch(java.lang.String)
+// com.example.Api: int retries
+// com.example.Model: Model(java.lang.String)
+// com.example.Outer$Inner
+// ```
+
+#![allow(dead_code)] // API methods reserved for future use
+
+use crate::graph::{Declaration, DeclarationKind, Graph};
+use miette::{IntoDiagnostic, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Member name used for constructors
+const CONSTRUCTOR: &str = "<init>";
+
+/// Parsed seeds.txt data
+#[derive(Debug, Clone, Default)]
+pub struct ProguardSeeds {
+    /// Kept classes, by fully qualified name with `.` for nested classes
+    classes: HashSet<String>,
+    /// Names of the kept members, by class
+    members: HashMap<String, HashSet<String>>,
+    /// Total count of seed lines
+    pub total_count: usize,
+}
+
+/// Parser for seeds.txt files
+pub struct SeedsParser;
+
+impl SeedsParser {
+    /// Parse a seeds.txt file
+    pub fn parse(path: &Path) -> Result<ProguardSeeds> {
+        let content = fs::read_to_string(path).into_diagnostic()?;
+        Ok(Self::parse_content(&content))
+    }
+
+    /// Parse seeds.txt content
+    pub fn parse_content(content: &str) -> ProguardSeeds {
+        let mut seeds = ProguardSeeds::default();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            seeds.total_count += 1;
+
+            let Some((class, member)) = line.split_once(": ") else {
+                seeds.classes.insert(normalize_class(line));
+                continue;
+            };
+            let class = normalize_class(class);
+            let names = seeds.members.entry(class.clone()).or_default();
+            for name in member_names(&class, member.trim()) {
+                names.insert(name);
+            }
+        }
+
+        seeds
+    }
+}
+
+impl ProguardSeeds {
+    /// Whether a class is kept
+    pub fn is_class_seeded(&self, class_name: &str) -> bool {
+        self.classes.contains(class_name)
+    }
+
+    /// Whether a member of a class is kept
+    pub fn is_member_seeded(&self, class_name: &str, member_name: &str) -> bool {
+        self.members
+            .get(class_name)
+            .is_some_and(|names| names.contains(member_name))
+    }
+
+    /// Whether a declaration of the analyzed sources is a seed
+    pub fn keeps(&self, decl: &Declaration, graph: &Graph) -> bool {
+        if decl.kind.is_type() {
+            return self.is_class_seeded(qualified_name(decl));
+        }
+
+        let Some(class) = decl.parent.as_ref().and_then(|p| graph.get_declaration(p)) else {
+            return false;
+        };
+        if !class.kind.is_type() {
+            return false;
+        }
+        let member = if decl.kind == DeclarationKind::Constructor {
+            CONSTRUCTOR
+        } else {
+            decl.name.as_str()
+        };
+        self.is_member_seeded(qualified_name(class), member)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total_count == 0
+    }
+}
+
+fn qualified_name(decl: &Declaration) -> &str {
+    decl.fully_qualified_name.as_deref().unwrap_or(&decl.name)
+}
+
+/// `com.example.Outer$Inner` -> `com.example.Outer.Inner`
+fn normalize_class(name: &str) -> String {
+    name.trim().replace('$', ".")
+}
+
+/// Names a member line stands for: `void fetch(int)` -> `fetch`,
+/// `int retries` -> `retries`, `Model(int)` -> `<init>`. `getX`/`setX`
+/// accessors also stand for the Kotlin property `x` they were generated for.
+fn member_names(class: &str, member: &str) -> Vec<String> {
+    let Some((before_paren, _)) = member.split_once('(') else {
+        return member
+            .split_whitespace()
+            .last()
+            .map(|name| vec![name.to_string()])
+            .unwrap_or_default();
+    };
+
+    let mut tokens = before_paren.split_whitespace();
+    let first = tokens.next().unwrap_or_default();
+    let name = tokens.last().unwrap_or(first);
+    let simple_class = class.rsplit('.').next().unwrap_or(class);
+    if name == CONSTRUCTOR || name == simple_class {
+        return vec![CONSTRUCTOR.to_string()];
+    }
+
+    let mut names = vec![name.to_string()];
+    let property = name
+        .strip_prefix("get")
+        .or_else(|| name.strip_prefix("set"));
+    if let Some(property) = property.filter(|p| p.starts_with(|c: char| c.is_ascii_uppercase())) {
+        names.push(decapitalize(property));
+    }
+    names
+}
+
+fn decapitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, Language, Location};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_seeds_txt() {
+        let content = r#"
+com.example.Api
+com.example.Api: void fetch(java.lang.String)
+com.example.Api: int retries
+com.example.Model: Model(java.lang.String)
+com.example.Model: java.lang.String getDisplayName()
+com.example.Outer$Inner
+"#;
+        let seeds = SeedsParser::parse_content(content);
+
+        assert_eq!(seeds.total_count, 6);
+        assert!(seeds.is_class_seeded("com.example.Api"));
+        assert!(seeds.is_class_seeded("com.example.Outer.Inner"));
+        assert!(!seeds.is_class_seeded("com.example.Model"));
+        assert!(seeds.is_member_seeded("com.example.Api", "fetch"));
+        assert!(seeds.is_member_seeded("com.example.Api", "retries"));
+        assert!(seeds.is_member_seeded("com.example.Model", "<init>"));
+        assert!(seeds.is_member_seeded("com.example.Model", "displayName"));
+        assert!(!seeds.is_member_seeded("com.example.Api", "close"));
+    }
+
+    #[test]
+    fn test_seeds_keep_declarations() {
+        let seeds = SeedsParser::parse_content(
+            "com.example.Api\ncom.example.Api: void fetch(int)\ncom.example.Api: Api()\n",
+        );
+        let mut graph = Graph::new();
+        let declare = |graph: &mut Graph, name: &str, kind, parent: Option<&DeclarationId>| {
+            let file = PathBuf::from("Api.kt");
+            let start = graph.declarations().count() * 100;
+            let mut decl = Declaration::new(
+                DeclarationId::new(file.clone(), start, start + 50),
+                name.to_string(),
+                kind,
+                Location::new(file, 1, 1, start, start + 50),
+                Language::Kotlin,
+            );
+            if parent.is_none() {
+                decl.fully_qualified_name = Some(format!("com.example.{}", name));
+            }
+            decl.parent = parent.cloned();
+            graph.add_declaration(decl)
+        };
+
+        let api = declare(&mut graph, "Api", DeclarationKind::Class, None);
+        let fetch = declare(&mut graph, "fetch", DeclarationKind::Method, Some(&api));
+        let init = declare(&mut graph, "Api", DeclarationKind::Constructor, Some(&api));
+        let close = declare(&mut graph, "close", DeclarationKind::Method, Some(&api));
+        let other = declare(&mut graph, "Other", DeclarationKind::Class, None);
+
+        let kept = |id: &DeclarationId| seeds.keeps(graph.get_declaration(id).unwrap(), &graph);
+        assert!(kept(&api));
+        assert!(kept(&fetch));
+        assert!(kept(&init));
+        assert!(!kept(&close));
+        assert!(!kept(&other));
+    }
+}
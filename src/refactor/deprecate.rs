@@ -0,0 +1,211 @@
+use crate::analysis::{DeadCode, DeadCodeIssue};
+use crate::graph::{simple_type_name, Declaration, DeclarationKind, Language};
+use crate::refactor::editor::TextEdit;
+
+/// Message of the inserted annotations
+pub const DEPRECATION_MESSAGE: &str = "Scheduled for removal by searchdeadcode";
+
+/// How hard a Kotlin `@Deprecated` annotation stops callers; Java only has
+/// warnings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeprecationLevel {
+    /// Uses still compile, with a warning
+    #[default]
+    Warning,
+    /// Uses no longer compile
+    Error,
+}
+
+impl DeprecationLevel {
+    fn kotlin_name(&self) -> &'static str {
+        match self {
+            DeprecationLevel::Warning => "WARNING",
+            DeprecationLevel::Error => "ERROR",
+        }
+    }
+}
+
+/// Whether a finding is a whole declaration that can be deprecated before it
+/// is deleted, and is not deprecated yet
+pub fn is_deprecatable(dc: &DeadCode) -> bool {
+    let decl = &dc.declaration;
+    let removable = matches!(
+        dc.issue,
+        DeadCodeIssue::Unreferenced
+            | DeadCodeIssue::UnusedEnumCase
+            | DeadCodeIssue::UnusedSealedVariant
+            | DeadCodeIssue::UnusedTypeAlias
+            | DeadCodeIssue::OnlyUsedInTests
+            | DeadCodeIssue::OnlyUsedInPreviews
+            | DeadCodeIssue::EffectivelyDeadObject
+    );
+    let annotatable = !matches!(
+        decl.kind,
+        DeclarationKind::Parameter
+            | DeclarationKind::Import
+            | DeclarationKind::Package
+            | DeclarationKind::File
+    );
+    removable && annotatable && !is_deprecated(decl)
+}
+
+/// Whether a declaration carries `@Deprecated`
+pub fn is_deprecated(decl: &Declaration) -> bool {
+    decl.annotations
+        .iter()
+        .any(|a| simple_type_name(a) == "Deprecated")
+}
+
+/// The edit deprecating a declaration: an annotation on a line of its own
+/// above it, and for Java a `@deprecated` Javadoc tag, added to the Javadoc
+/// right above the declaration if there is one. None if the declaration does
+/// not start its line (`class Point(val x: Int)`).
+pub fn deprecation_edit(
+    contents: &str,
+    dc: &DeadCode,
+    level: DeprecationLevel,
+) -> Option<TextEdit> {
+    let start = dc.declaration.location.start_byte;
+    let before = contents.get(..start)?;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let indent = &before[line_start..];
+    if !indent.chars().all(char::is_whitespace) {
+        return None;
+    }
+
+    if dc.declaration.language == Language::Kotlin {
+        let annotation = format!(
+            "{}@Deprecated(\"{}\", level = DeprecationLevel.{})\n",
+            indent,
+            DEPRECATION_MESSAGE,
+            level.kotlin_name()
+        );
+        return Some(TextEdit::replace(line_start, line_start, annotation));
+    }
+
+    let annotation = format!("{}@Deprecated\n", indent);
+    match javadoc_end(contents, line_start) {
+        Some(end) => {
+            let prefix_start = contents[..end].rfind('\n').map_or(0, |i| i + 1);
+            let prefix = &contents[prefix_start..end];
+            if prefix.trim().is_empty() {
+                // `   */` closing a Javadoc: the tag goes on a line above it
+                Some(TextEdit::replace(
+                    prefix_start,
+                    line_start,
+                    format!(
+                        "{}* @deprecated {}\n{}*/\n{}",
+                        prefix, DEPRECATION_MESSAGE, prefix, annotation
+                    ),
+                ))
+            } else {
+                // `/** Loads the cache. */`: block tags start their own line
+                Some(TextEdit::replace(
+                    contents[..end].trim_end().len(),
+                    line_start,
+                    format!(
+                        "\n{} * @deprecated {}\n{} */\n{}",
+                        indent, DEPRECATION_MESSAGE, indent, annotation
+                    ),
+                ))
+            }
+        }
+        None => Some(TextEdit::replace(
+            line_start,
+            line_start,
+            format!(
+                "{}/** @deprecated {} */\n{}",
+                indent, DEPRECATION_MESSAGE, annotation
+            ),
+        )),
+    }
+}
+
+/// Where the `*/` of a Javadoc ending on the line before `line_start` is
+fn javadoc_end(contents: &str, line_start: usize) -> Option<usize> {
+    let previous = contents[..line_start].strip_suffix('\n')?;
+    let end = previous.trim_end().strip_suffix("*/")?.len();
+    let comment_start = previous[..end].rfind("/*")?;
+    previous[comment_start..].starts_with("/**").then_some(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Declaration, DeclarationId, Location};
+    use std::path::PathBuf;
+
+    fn finding(contents: &str, name: &str, language: Language) -> DeadCode {
+        let start = contents.find(name).unwrap();
+        let line = contents[..start].matches('\n').count() + 1;
+        let path = PathBuf::from("Cache.kt");
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), start, start + name.len()),
+            name.to_string(),
+            DeclarationKind::Method,
+            Location::new(path, line, 1, start, start + name.len()),
+            language,
+        );
+        DeadCode::new(decl, DeadCodeIssue::Unreferenced)
+    }
+
+    fn deprecate(contents: &str, name: &str, language: Language) -> Option<String> {
+        let dc = finding(contents, name, language);
+        let edit = deprecation_edit(contents, &dc, DeprecationLevel::Warning)?;
+        crate::refactor::editor::apply_edits(contents, &[edit]).ok()
+    }
+
+    #[test]
+    fn test_deprecation_edit_kotlin() {
+        let contents = "class Cache {\n    fun evictAll() {}\n}\n";
+        assert_eq!(
+            deprecate(contents, "fun evictAll", Language::Kotlin).unwrap(),
+            "class Cache {\n    @Deprecated(\"Scheduled for removal by searchdeadcode\", \
+             level = DeprecationLevel.WARNING)\n    fun evictAll() {}\n}\n"
+        );
+
+        let dc = finding(contents, "fun evictAll", Language::Kotlin);
+        let edit = deprecation_edit(contents, &dc, DeprecationLevel::Error).unwrap();
+        assert!(edit.replacement.contains("DeprecationLevel.ERROR"));
+
+        // A property of the primary constructor shares the class's line
+        assert_eq!(
+            deprecate("class Point(val x: Int)\n", "val x", Language::Kotlin),
+            None
+        );
+    }
+
+    #[test]
+    fn test_deprecation_edit_java() {
+        assert_eq!(
+            deprecate(
+                "class Cache {\n  void evictAll() {}\n}\n",
+                "void",
+                Language::Java
+            )
+            .unwrap(),
+            "class Cache {\n  /** @deprecated Scheduled for removal by searchdeadcode */\n  \
+             @Deprecated\n  void evictAll() {}\n}\n"
+        );
+        assert_eq!(
+            deprecate(
+                "class Cache {\n  /**\n   * Drops everything.\n   */\n  void evictAll() {}\n}\n",
+                "void",
+                Language::Java
+            )
+            .unwrap(),
+            "class Cache {\n  /**\n   * Drops everything.\n   * @deprecated Scheduled for removal by \
+             searchdeadcode\n   */\n  @Deprecated\n  void evictAll() {}\n}\n"
+        );
+        assert_eq!(
+            deprecate(
+                "class Cache {\n  /** Drops everything. */\n  void evictAll() {}\n}\n",
+                "void",
+                Language::Java
+            )
+            .unwrap(),
+            "class Cache {\n  /** Drops everything.\n   * @deprecated Scheduled for removal by \
+             searchdeadcode\n   */\n  @Deprecated\n  void evictAll() {}\n}\n"
+        );
+    }
+}
@@ -1,6 +1,68 @@
 use miette::{IntoDiagnostic, Result};
 use std::path::Path;
 
+/// Replacement of a byte range, so several changes to a file can be applied
+/// in one pass against its original contents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    pub fn delete(start: usize, end: usize) -> Self {
+        Self::replace(start, end, "")
+    }
+
+    pub fn replace(start: usize, end: usize, replacement: impl Into<String>) -> Self {
+        Self {
+            start,
+            end,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Whether the edits touch the same bytes (insertions at a shared
+    /// position count as overlapping)
+    pub fn overlaps(&self, other: &TextEdit) -> bool {
+        self.start < other.end && other.start < self.end || self.start == other.start
+    }
+}
+
+/// Apply non-overlapping edits made against `contents`. Two edits at the
+/// same offset, including two insertions, have no defined order and are
+/// rejected as overlapping.
+pub fn apply_edits(contents: &str, edits: &[TextEdit]) -> Result<String> {
+    let mut edits: Vec<&TextEdit> = edits.iter().collect();
+    edits.sort_by_key(|e| (e.start, e.end));
+
+    let mut result = String::with_capacity(contents.len());
+    let mut position = 0;
+    let mut previous: Option<&TextEdit> = None;
+    for edit in edits {
+        if edit.start < position
+            || previous.is_some_and(|p| p.overlaps(edit))
+            || edit.end < edit.start
+            || edit.end > contents.len()
+            || !contents.is_char_boundary(edit.start)
+            || !contents.is_char_boundary(edit.end)
+        {
+            return Err(miette::miette!(
+                "Invalid or overlapping edit at bytes {}..{}",
+                edit.start,
+                edit.end
+            ));
+        }
+        result.push_str(&contents[position..edit.start]);
+        result.push_str(&edit.replacement);
+        position = edit.end;
+        previous = Some(edit);
+    }
+    result.push_str(&contents[position..]);
+    Ok(result)
+}
+
 /// File editor for modifying source files
 pub struct FileEditor;
 
@@ -46,6 +108,19 @@ impl FileEditor {
         Ok(())
     }
 
+    /// Apply non-overlapping edits made against the file's current contents
+    pub fn apply_edits(&self, path: &Path, edits: &[TextEdit]) -> Result<()> {
+        let contents = std::fs::read_to_string(path).into_diagnostic()?;
+        let new_contents = apply_edits(&contents, edits)?;
+        std::fs::write(path, new_contents).into_diagnostic()?;
+        Ok(())
+    }
+
+    /// Delete a whole file
+    pub fn delete_file(&self, path: &Path) -> Result<()> {
+        std::fs::remove_file(path).into_diagnostic()
+    }
+
     /// Replace a range of text in a file
     pub fn replace_range(
         &self,
@@ -112,4 +187,43 @@ mod tests {
         assert!(!contents.contains("Line 2"));
         assert!(contents.contains("Line 3"));
     }
+
+    #[test]
+    fn test_apply_edits() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "fun greet(name: String, unused: Int) = greet(\"a\", 1)"
+        )
+        .unwrap();
+
+        let editor = FileEditor::new();
+        let edits = vec![
+            TextEdit::delete(48, 51),
+            TextEdit::delete(22, 35),
+            TextEdit::replace(4, 9, "hello"),
+        ];
+        editor.apply_edits(file.path(), &edits).unwrap();
+
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        assert_eq!(contents, "fun hello(name: String) = greet(\"a\")");
+
+        let overlapping = vec![TextEdit::delete(0, 5), TextEdit::delete(3, 8)];
+        assert!(apply_edits("0123456789", &overlapping).is_err());
+
+        let insertions = vec![TextEdit::replace(3, 3, "a"), TextEdit::replace(3, 3, "b")];
+        assert!(apply_edits("0123456789", &insertions).is_err());
+    }
+
+    #[test]
+    fn test_delete_file() {
+        let file = NamedTempFile::new().unwrap();
+        let path = file.path().to_path_buf();
+
+        let editor = FileEditor::new();
+        editor.delete_file(&path).unwrap();
+
+        assert!(!path.exists());
+        assert!(editor.delete_file(&path).is_err());
+    }
 }
@@ -0,0 +1,742 @@
+//! Fixers
+//!
+//! Some findings are better fixed by rewriting code than by deleting whole
+//! lines: an unused import is removed without touching its neighbours, and an
+//! unused parameter is renamed to `_` in a Kotlin lambda, or removed together
+//! with its argument at every call site when all of them are in the same file,
+//! a redundant `public` is narrowed to `private` or `internal`, and a `var`
+//! that is never reassigned becomes a `val`.
+//!
+//! A [`Fixer`] plans the rewrite as [`TextEdit`]s against the current source
+//! and declines whenever it cannot show the rewrite is safe, in which case the
+//! finding is handled like any other deletion.
+
+use super::editor::TextEdit;
+use crate::analysis::detectors::RedundantPublicDetector;
+use crate::analysis::{DeadCode, DeadCodeIssue};
+use crate::graph::{
+    Declaration, DeclarationId, DeclarationKind, Graph, Language, ReferenceKind, Visibility,
+};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+/// A planned rewrite for one finding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// Edits to the finding's file
+    pub edits: Vec<TextEdit>,
+    /// What the fix does, shown when it is applied
+    pub description: String,
+}
+
+/// A targeted rewrite for one kind of finding
+pub trait Fixer {
+    /// Whether the fixer applies to findings of this kind
+    fn handles(&self, issue: DeadCodeIssue) -> bool;
+
+    /// Plan the fix against the source of the finding's file, or None when
+    /// it cannot be done safely
+    fn fix(&self, dead_code: &DeadCode, source: &str, graph: &Graph) -> Option<Fix>;
+}
+
+/// Fixers applied by the safe deleter
+pub fn default_fixers() -> Vec<Box<dyn Fixer>> {
+    vec![
+        Box::new(UnusedImportFixer),
+        Box::new(UnusedParamFixer),
+        Box::new(VisibilityFixer),
+        Box::new(MutabilityFixer),
+    ]
+}
+
+/// Plan fixes for every finding a default fixer can handle
+pub fn plan_fixes(dead_code: &[DeadCode], graph: &Graph) -> HashMap<DeclarationId, Fix> {
+    let fixers = default_fixers();
+    let mut sources: HashMap<&Path, Option<String>> = HashMap::new();
+    let mut fixes = HashMap::new();
+
+    for dc in dead_code {
+        let Some(fixer) = fixers.iter().find(|f| f.handles(dc.issue)) else {
+            continue;
+        };
+        let file = dc.declaration.location.file.as_path();
+        let source = sources
+            .entry(file)
+            .or_insert_with(|| std::fs::read_to_string(file).ok());
+        let Some(source) = source else {
+            continue;
+        };
+        if let Some(fix) = fixer.fix(dc, source, graph) {
+            fixes.insert(dc.declaration.id.clone(), fix);
+        }
+    }
+
+    fixes
+}
+
+/// Removes an unused import, with its line when nothing else is on it
+pub struct UnusedImportFixer;
+
+impl Fixer for UnusedImportFixer {
+    fn handles(&self, issue: DeadCodeIssue) -> bool {
+        issue == DeadCodeIssue::UnusedImport
+    }
+
+    fn fix(&self, dead_code: &DeadCode, source: &str, _graph: &Graph) -> Option<Fix> {
+        let decl = &dead_code.declaration;
+        let text = source.get(decl.id.start..decl.id.end)?;
+        if !text.trim_start().starts_with("import") {
+            return None;
+        }
+
+        let (start, end) = whole_line(source, decl.id.start, decl.id.end);
+        Some(Fix {
+            edits: vec![TextEdit::delete(start, end)],
+            description: format!("removed import '{}'", decl.name),
+        })
+    }
+}
+
+/// Renames unused Kotlin lambda parameters to `_` and removes unused
+/// parameters of private functions along with their arguments
+pub struct UnusedParamFixer;
+
+impl Fixer for UnusedParamFixer {
+    fn handles(&self, issue: DeadCodeIssue) -> bool {
+        issue == DeadCodeIssue::UnusedParameter
+    }
+
+    fn fix(&self, dead_code: &DeadCode, source: &str, graph: &Graph) -> Option<Fix> {
+        let param = &dead_code.declaration;
+        if param.kind != DeclarationKind::Parameter || param.name == "_" {
+            return None;
+        }
+        let text = source.get(param.id.start..param.id.end)?;
+
+        if param.language == Language::Kotlin && is_lambda_parameter(source, param) {
+            let name_start = param.id.start + find_word(text, &param.name)?;
+            return Some(Fix {
+                edits: vec![TextEdit::replace(
+                    name_start,
+                    name_start + param.name.len(),
+                    "_",
+                )],
+                description: format!("renamed lambda parameter '{}' to '_'", param.name),
+            });
+        }
+
+        let function = graph.get_declaration(param.parent.as_ref()?)?;
+        remove_parameter(param, function, source, graph)
+    }
+}
+
+/// Narrows public declarations to the visibility the redundant-public
+/// detector suggests, rechecked against the graph
+pub struct VisibilityFixer;
+
+impl Fixer for VisibilityFixer {
+    fn handles(&self, issue: DeadCodeIssue) -> bool {
+        issue == DeadCodeIssue::RedundantPublic
+    }
+
+    fn fix(&self, dead_code: &DeadCode, source: &str, graph: &Graph) -> Option<Fix> {
+        let decl = graph.get_declaration(&dead_code.declaration.id)?;
+        let keyword = match RedundantPublicDetector::new().narrowest_visibility(decl, graph)? {
+            Visibility::Private => "private",
+            Visibility::Internal => "internal",
+            _ => return None,
+        };
+
+        let text = source.get(decl.id.start..decl.id.end)?;
+        let modifiers = &text[..find_word(text, &decl.name)?];
+        let edit = match find_word(modifiers, "public") {
+            Some(pos) => {
+                let start = decl.id.start + pos;
+                TextEdit::replace(start, start + "public".len(), keyword)
+            }
+            // Kotlin declarations are public by default
+            None if decl.language == Language::Kotlin => {
+                let start = decl.id.start + skip_annotations(modifiers);
+                TextEdit::replace(start, start, format!("{} ", keyword))
+            }
+            None => return None,
+        };
+
+        Some(Fix {
+            edits: vec![edit],
+            description: format!(
+                "made {} '{}' {}",
+                decl.kind.display_name(),
+                decl.name,
+                keyword
+            ),
+        })
+    }
+}
+
+/// Turns `var` properties that are never reassigned into `val`, rechecked
+/// against the graph
+pub struct MutabilityFixer;
+
+impl Fixer for MutabilityFixer {
+    fn handles(&self, issue: DeadCodeIssue) -> bool {
+        issue == DeadCodeIssue::VarCouldBeVal
+    }
+
+    fn fix(&self, dead_code: &DeadCode, source: &str, graph: &Graph) -> Option<Fix> {
+        let decl = graph.get_declaration(&dead_code.declaration.id)?;
+        if decl.kind != DeclarationKind::Property
+            || decl.language != Language::Kotlin
+            || graph.count_writes(&decl.id) > 0
+        {
+            return None;
+        }
+
+        let text = source.get(decl.id.start..decl.id.end)?;
+        let keyword = decl.id.start + find_word(&text[..find_word(text, &decl.name)?], "var")?;
+        Some(Fix {
+            edits: vec![TextEdit::replace(keyword, keyword + "var".len(), "val")],
+            description: format!("made property '{}' a val", decl.name),
+        })
+    }
+}
+
+/// Remove a parameter from a private function and from all of its calls
+fn remove_parameter(
+    param: &Declaration,
+    function: &Declaration,
+    source: &str,
+    graph: &Graph,
+) -> Option<Fix> {
+    // Only private functions are guaranteed to have all their callers in the
+    // graph; annotated ones may be called through reflection or generated code
+    if !matches!(
+        function.kind,
+        DeclarationKind::Function | DeclarationKind::Method
+    ) || function.visibility != Visibility::Private
+        || !function.annotations.is_empty()
+        || function.is_abstract
+    {
+        return None;
+    }
+
+    let mut params: Vec<&Declaration> = graph
+        .get_children(&function.id)
+        .into_iter()
+        .filter_map(|id| graph.get_declaration(id))
+        .filter(|decl| decl.kind == DeclarationKind::Parameter)
+        .collect();
+    params.sort_by_key(|decl| decl.id.start);
+    let index = params.iter().position(|decl| decl.id == param.id)?;
+
+    // The declaration's parameter list, found from the function name
+    let header = source.get(function.id.start..function.id.end)?;
+    let mut lists = find_words(header, &function.name)
+        .filter_map(|pos| open_paren(header, pos, &function.name));
+    let open = function.id.start + lists.next()?;
+    // Recursive calls are not references in the graph
+    if lists.next().is_some() {
+        return None;
+    }
+    let list = argument_list(source, open, true)?;
+    if list.slots.len() != params.len()
+        || !list
+            .slots
+            .iter()
+            .zip(&params)
+            .all(|(&(start, end), decl)| start <= decl.id.start && decl.id.end <= end)
+    {
+        return None;
+    }
+    let (slot_start, slot_end) = list.slots[index];
+    let slot = &source[slot_start..slot_end];
+    if slot.contains("vararg") || slot.contains("...") {
+        return None;
+    }
+
+    let mut edits = vec![remove_slot(&list, index)];
+
+    let mut calls = BTreeSet::new();
+    for (caller, reference) in graph.get_references_to(&function.id) {
+        if reference.kind != ReferenceKind::Call || caller.location.file != function.location.file {
+            return None;
+        }
+        calls.insert(reference.location.start_byte);
+    }
+
+    for &position in &calls {
+        if source.get(position..position + function.name.len())? != function.name {
+            return None;
+        }
+        let open = position + open_paren(&source[position..], 0, &function.name)?;
+        let arguments = argument_list(source, open, false)?;
+        if arguments.slots.len() != params.len() {
+            return None;
+        }
+
+        if arguments
+            .slots
+            .iter()
+            .any(|&(start, end)| is_named_argument(&source[start..end]))
+        {
+            return None;
+        }
+        // Dropping the argument must not drop a side effect
+        let (start, end) = arguments.slots[index];
+        if ["(", "++", "--", "="]
+            .iter()
+            .any(|token| source[start..end].contains(token))
+        {
+            return None;
+        }
+        if function.language == Language::Kotlin
+            && source[arguments.close + 1..].trim_start().starts_with('{')
+        {
+            return None;
+        }
+
+        edits.push(remove_slot(&arguments, index));
+    }
+
+    Some(Fix {
+        edits,
+        description: format!(
+            "removed parameter '{}' from '{}' and {} call sites",
+            param.name,
+            function.name,
+            calls.len()
+        ),
+    })
+}
+
+/// The full line of `start..end`, with its line break, when nothing but
+/// whitespace or `;` surrounds the range; otherwise the range itself
+fn whole_line(source: &str, start: usize, end: usize) -> (usize, usize) {
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[end..]
+        .find('\n')
+        .map_or(source.len(), |i| end + i + 1);
+
+    let before = &source[line_start..start];
+    let after = source[end..line_end].trim().trim_start_matches(';');
+    if before.trim().is_empty() && after.trim().is_empty() {
+        (line_start, line_end)
+    } else {
+        (start, end)
+    }
+}
+
+/// Whether the parameter sits between the `{` and `->` of a lambda
+fn is_lambda_parameter(source: &str, param: &Declaration) -> bool {
+    let in_header = |c: char| c.is_alphanumeric() || c.is_whitespace() || "_,:<>?.()`".contains(c);
+
+    let before = &source[..param.id.start];
+    let opens_lambda = before
+        .rfind(|c: char| !in_header(c))
+        .is_some_and(|i| before[i..].starts_with('{'));
+
+    let after = &source[param.id.end..];
+    let closes_header = after
+        .find(|c: char| !in_header(c))
+        .is_some_and(|i| after[i..].starts_with("->"));
+
+    opens_lambda && closes_header
+}
+
+/// Offset of the first modifier or keyword, after annotations and comments
+fn skip_annotations(text: &str) -> usize {
+    let mut pos = 0;
+    loop {
+        let rest = &text[pos..];
+        let trimmed = rest.trim_start();
+        pos += rest.len() - trimmed.len();
+
+        if let Some(annotation) = trimmed.strip_prefix('@') {
+            let name_len = annotation
+                .find(|c: char| !(is_identifier_char(c) || c == '.' || c == ':'))
+                .unwrap_or(annotation.len());
+            pos += 1 + name_len;
+            if text[pos..].starts_with('(') {
+                match argument_list(text, pos, false) {
+                    Some(arguments) => pos = arguments.close + 1,
+                    None => return pos,
+                }
+            }
+        } else if trimmed.starts_with("//") {
+            pos += trimmed.find('\n').map_or(trimmed.len(), |i| i + 1);
+        } else if trimmed.starts_with("/*") {
+            pos += trimmed.find("*/").map_or(trimmed.len(), |i| i + 2);
+        } else {
+            return pos;
+        }
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Positions of `word` in `text` not inside a longer identifier
+fn find_words<'a>(text: &'a str, word: &'a str) -> impl Iterator<Item = usize> + 'a {
+    text.match_indices(word).map(|(i, _)| i).filter(move |&i| {
+        !text[..i]
+            .chars()
+            .next_back()
+            .is_some_and(is_identifier_char)
+            && !text[i + word.len()..]
+                .chars()
+                .next()
+                .is_some_and(is_identifier_char)
+    })
+}
+
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    find_words(text, word).next()
+}
+
+/// Position of the `(` following `name` at `position`
+fn open_paren(text: &str, position: usize, name: &str) -> Option<usize> {
+    let rest = &text[position + name.len()..];
+    let skipped = rest.len() - rest.trim_start().len();
+    rest[skipped..]
+        .starts_with('(')
+        .then_some(position + name.len() + skipped)
+}
+
+fn is_named_argument(text: &str) -> bool {
+    let rest = text.trim_start_matches(is_identifier_char);
+    rest.len() < text.len()
+        && rest
+            .trim_start()
+            .strip_prefix('=')
+            .is_some_and(|value| !value.starts_with('='))
+}
+
+/// Parenthesized, comma-separated list of parameters or arguments
+struct ArgumentList {
+    /// Byte range of each entry, without surrounding whitespace
+    slots: Vec<(usize, usize)>,
+    /// Position of the closing `)`
+    close: usize,
+}
+
+/// Split the list opened by the `(` at `open`, skipping nested brackets,
+/// strings and comments; `angles` also nests `<...>` for generic types
+fn argument_list(source: &str, open: usize, angles: bool) -> Option<ArgumentList> {
+    let bytes = source.as_bytes();
+    if bytes.get(open) != Some(&b'(') {
+        return None;
+    }
+
+    let mut slots = Vec::new();
+    let mut slot_start = open + 1;
+    let mut depth = 0usize;
+    let mut i = open + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' | b'\'' => {
+                i = skip_string(source, i)?;
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i += source[i..].find('\n')?;
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += source[i..].find("*/")? + 2;
+                continue;
+            }
+            b'(' | b'[' | b'{' => depth += 1,
+            b'<' if angles => depth += 1,
+            b'>' if angles && bytes[i - 1] != b'-' => depth = depth.checked_sub(1)?,
+            b')' | b']' | b'}' if depth > 0 => depth -= 1,
+            b')' => {
+                let slot = trimmed(source, slot_start, i);
+                if slot.0 < slot.1 {
+                    slots.push(slot);
+                }
+                return Some(ArgumentList { slots, close: i });
+            }
+            b']' | b'}' => return None,
+            b',' if depth == 0 => {
+                slots.push(trimmed(source, slot_start, i));
+                slot_start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Position after the string or character literal starting at `start`
+fn skip_string(source: &str, start: usize) -> Option<usize> {
+    let rest = &source[start..];
+    if let Some(body) = rest.strip_prefix("\"\"\"") {
+        return body.find("\"\"\"").map(|i| start + i + 6);
+    }
+
+    let quote = rest.as_bytes()[0];
+    let mut escaped = false;
+    for (i, byte) in rest.bytes().enumerate().skip(1) {
+        match byte {
+            _ if escaped => escaped = false,
+            b'\\' => escaped = true,
+            b'\n' => return None,
+            _ if byte == quote => return Some(start + i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `start..end` without leading and trailing whitespace
+fn trimmed(source: &str, start: usize, end: usize) -> (usize, usize) {
+    let text = &source[start..end];
+    let trimmed_start = start + (text.len() - text.trim_start().len());
+    let trimmed_end = start + text.trim_end().len();
+    (trimmed_start, trimmed_end.max(trimmed_start))
+}
+
+/// Edit removing one entry of a list along with one adjoining comma
+fn remove_slot(list: &ArgumentList, index: usize) -> TextEdit {
+    let slots = &list.slots;
+    if index + 1 < slots.len() {
+        TextEdit::delete(slots[index].0, slots[index + 1].0)
+    } else if index > 0 {
+        TextEdit::delete(slots[index - 1].1, slots[index].1)
+    } else {
+        TextEdit::delete(slots[index].0, list.close)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Location, Reference};
+    use crate::refactor::editor::apply_edits;
+    use std::path::PathBuf;
+
+    fn declaration(
+        source: &str,
+        text: &str,
+        name: &str,
+        kind: DeclarationKind,
+        parent: Option<&Declaration>,
+    ) -> Declaration {
+        let start = source.find(text).unwrap();
+        let path = PathBuf::from("Greeter.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), start, start + text.len()),
+            name.to_string(),
+            kind,
+            Location::new(path, 1, 1, start, start + text.len()),
+            Language::Kotlin,
+        );
+        decl.visibility = Visibility::Private;
+        decl.parent = parent.map(|p| p.id.clone());
+        decl
+    }
+
+    fn call(graph: &mut Graph, source: &str, from: &Declaration, to: &Declaration, at: &str) {
+        let start = source.find(at).unwrap();
+        let location = Location::new(
+            from.location.file.clone(),
+            1,
+            1,
+            start,
+            start + to.name.len(),
+        );
+        graph.add_reference(
+            &from.id,
+            &to.id,
+            Reference::new(ReferenceKind::Call, location, to.name.clone()),
+        );
+    }
+
+    #[test]
+    fn test_import_fixer_removes_line() {
+        let source = "import a.B\nimport a.C\n\nclass D\n";
+        let decl = declaration(source, "import a.C", "a.C", DeclarationKind::Import, None);
+        let dead_code = DeadCode::new(decl, DeadCodeIssue::UnusedImport);
+
+        let fix = UnusedImportFixer
+            .fix(&dead_code, source, &Graph::new())
+            .unwrap();
+        assert_eq!(
+            apply_edits(source, &fix.edits).unwrap(),
+            "import a.B\n\nclass D\n"
+        );
+    }
+
+    #[test]
+    fn test_param_fixer_rewrites_local_calls() {
+        let source = "private fun greet(name: String, unused: Int) {\n    println(name)\n}\n\n\
+                      fun main() {\n    greet(\"a, b\", 1)\n    \
+                      greet(name = \"c\", unused = 2)\n}\n";
+        let function_kind = DeclarationKind::Function;
+        let greet_text = &source[..source.find("}\n").unwrap() + 1];
+        let function = declaration(source, greet_text, "greet", function_kind, None);
+        let main_text = &source[source.find("fun main").unwrap()..];
+        let main = declaration(source, main_text, "main", function_kind, None);
+        let param_kind = DeclarationKind::Parameter;
+        let name = declaration(source, "name: String", "name", param_kind, Some(&function));
+        let unused = declaration(source, "unused: Int", "unused", param_kind, Some(&function));
+
+        let mut graph = Graph::new();
+        for decl in [&function, &main, &name, &unused] {
+            graph.add_declaration(decl.clone());
+        }
+        call(&mut graph, source, &main, &function, "greet(\"a");
+
+        let dead_code = DeadCode::new(unused, DeadCodeIssue::UnusedParameter);
+        let fix = UnusedParamFixer.fix(&dead_code, source, &graph).unwrap();
+        assert_eq!(
+            apply_edits(source, &fix.edits).unwrap(),
+            source
+                .replace("name: String, unused: Int", "name: String")
+                .replace("\"a, b\", 1", "\"a, b\"")
+        );
+
+        // A named argument can't be rewritten by position
+        call(&mut graph, source, &main, &function, "greet(name");
+        assert!(UnusedParamFixer.fix(&dead_code, source, &graph).is_none());
+    }
+
+    #[test]
+    fn test_param_fixer_on_built_graph() {
+        use crate::graph::GraphBuilder;
+        use crate::parser::{KotlinParser, Parser};
+
+        let fix = |source: &str| {
+            let parsed = KotlinParser::new()
+                .parse(&PathBuf::from("Greeter.kt"), source)
+                .unwrap();
+            let mut builder = GraphBuilder::new();
+            builder.add_parse_result(parsed);
+            let graph = builder.build();
+            let unused = graph
+                .declarations()
+                .find(|d| d.kind == DeclarationKind::Parameter && d.name == "unused")
+                .unwrap()
+                .clone();
+            let dead_code = DeadCode::new(unused, DeadCodeIssue::UnusedParameter);
+            UnusedParamFixer.fix(&dead_code, source, &graph)
+        };
+
+        let source = r#"class Greeter {
+    private fun greet(name: String, unused: Int) {
+        println(name)
+    }
+
+    fun run() {
+        greet("a", 1)
+        greet("b", 2)
+    }
+}
+"#;
+        let edits = fix(source).unwrap().edits;
+        assert_eq!(
+            apply_edits(source, &edits).unwrap(),
+            source
+                .replace("name: String, unused: Int", "name: String")
+                .replace("\"a\", 1", "\"a\"")
+                .replace("\"b\", 2", "\"b\"")
+        );
+
+        // A recursive call passes the parameter too
+        assert!(fix(&source.replace("println(name)", "greet(name, 0)")).is_none());
+    }
+
+    #[test]
+    fn test_param_fixer_renames_lambda_parameter() {
+        let source = "val printer = { item: String, index: Int -> println(item) }\n";
+        let property_kind = DeclarationKind::Property;
+        let printer = declaration(source, "val printer", "printer", property_kind, None);
+        let param_kind = DeclarationKind::Parameter;
+        let index = declaration(source, "index: Int", "index", param_kind, Some(&printer));
+        let mut graph = Graph::new();
+        graph.add_declaration(printer);
+        graph.add_declaration(index.clone());
+        let dead_code = DeadCode::new(index, DeadCodeIssue::UnusedParameter);
+
+        let fix = UnusedParamFixer.fix(&dead_code, source, &graph).unwrap();
+        assert_eq!(
+            apply_edits(source, &fix.edits).unwrap(),
+            "val printer = { item: String, _: Int -> println(item) }\n"
+        );
+    }
+
+    #[test]
+    fn test_visibility_fixer() {
+        let source = "class Cart {\n    fun total() = price()\n    fun price() = 2\n}\n";
+        let mut cart = declaration(source, source, "Cart", DeclarationKind::Class, None);
+        cart.visibility = Visibility::Public;
+        let mut members = Vec::new();
+        for (text, name) in [
+            ("fun total() = price()", "total"),
+            ("fun price() = 2", "price"),
+        ] {
+            let mut member = declaration(source, text, name, DeclarationKind::Method, Some(&cart));
+            member.visibility = Visibility::Public;
+            members.push(member);
+        }
+
+        let mut graph = Graph::new();
+        graph.add_declaration(cart);
+        for member in &members {
+            graph.add_declaration(member.clone());
+        }
+        call(&mut graph, source, &members[0], &members[1], "price()");
+
+        let dead_code = DeadCode::new(members[1].clone(), DeadCodeIssue::RedundantPublic);
+        let fix = VisibilityFixer.fix(&dead_code, source, &graph).unwrap();
+        assert_eq!(
+            apply_edits(source, &fix.edits).unwrap(),
+            source.replace("fun price() = 2", "private fun price() = 2")
+        );
+        assert_eq!(skip_annotations("@Suppress(\"x\") public fun"), 15);
+    }
+
+    #[test]
+    fn test_mutability_fixer() {
+        let source = "class Greeter {\n    private var name = \"ada\"\n    fun greet() = name\n}\n";
+        let greeter = declaration(source, source, "Greeter", DeclarationKind::Class, None);
+        let name = declaration(
+            source,
+            "private var name = \"ada\"",
+            "name",
+            DeclarationKind::Property,
+            Some(&greeter),
+        );
+        let greet = declaration(
+            source,
+            "fun greet() = name",
+            "greet",
+            DeclarationKind::Method,
+            Some(&greeter),
+        );
+
+        let mut graph = Graph::new();
+        graph.add_declaration(greeter);
+        graph.add_declaration(name.clone());
+        graph.add_declaration(greet.clone());
+        call(&mut graph, source, &greet, &name, "name\n}");
+
+        let dead_code = DeadCode::new(name.clone(), DeadCodeIssue::VarCouldBeVal);
+        let fix = MutabilityFixer.fix(&dead_code, source, &graph).unwrap();
+        assert_eq!(
+            apply_edits(source, &fix.edits).unwrap(),
+            source.replace("private var", "private val")
+        );
+
+        // A write found since the analysis keeps the var
+        let location = Location::new(name.location.file.clone(), 1, 1, 0, 4);
+        graph.add_reference(
+            &greet.id,
+            &name.id,
+            Reference::new(ReferenceKind::Write, location, "name".to_string()),
+        );
+        assert!(MutabilityFixer.fix(&dead_code, source, &graph).is_none());
+    }
+}
@@ -0,0 +1,256 @@
+use miette::{miette, Context, IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const JOURNAL_VERSION: u32 = 1;
+
+/// Journal of the files changed by a safe delete, restored natively by
+/// `searchdeadcode undo --journal <file>`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Journal {
+    version: u32,
+    files: Vec<JournalEntry>,
+    /// Declarations deprecated by `--deprecate` and not deleted yet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    deprecations: Vec<Deprecation>,
+}
+
+/// A changed file, before and after the change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub path: PathBuf,
+    /// Contents before the change
+    pub original: String,
+    /// Contents after the change, or None if the file was deleted
+    pub modified: Option<String>,
+}
+
+/// A declaration marked `@Deprecated` by `--deprecate`, deleted by a later
+/// `--delete --deprecated-for <AGE>` once deprecated long enough
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deprecation {
+    pub path: PathBuf,
+    /// Kind as displayed, e.g. `function`
+    pub kind: String,
+    pub name: String,
+    /// Seconds since the UNIX epoch
+    pub deprecated_at: u64,
+}
+
+/// Result of a rollback
+#[derive(Debug, Default)]
+pub struct Rollback {
+    pub restored: Vec<PathBuf>,
+    /// Files edited again since the change, left alone unless forced
+    pub conflicts: Vec<PathBuf>,
+    /// Requested files the journal has no entry for
+    pub unknown: Vec<PathBuf>,
+}
+
+impl Journal {
+    pub fn new() -> Self {
+        Self {
+            version: JOURNAL_VERSION,
+            files: Vec::new(),
+            deprecations: Vec::new(),
+        }
+    }
+
+    /// Record a change; a file changed twice keeps its first original
+    pub fn record(&mut self, path: &Path, original: &str, modified: Option<String>) {
+        match self.files.iter_mut().find(|entry| entry.path == path) {
+            Some(entry) => entry.modified = modified,
+            None => self.files.push(JournalEntry {
+                path: path.to_path_buf(),
+                original: original.to_string(),
+                modified,
+            }),
+        }
+    }
+
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.files
+    }
+
+    /// Record a deprecation; a declaration deprecated earlier keeps its date
+    pub fn record_deprecation(&mut self, deprecation: Deprecation) {
+        let known = self.deprecations.iter().any(|d| {
+            d.path == deprecation.path && d.kind == deprecation.kind && d.name == deprecation.name
+        });
+        if !known {
+            self.deprecations.push(deprecation);
+        }
+    }
+
+    pub fn deprecations(&self) -> &[Deprecation] {
+        &self.deprecations
+    }
+
+    /// When a declaration was deprecated, if the journal recorded it
+    pub fn deprecated_at(&self, path: &Path, kind: &str, name: &str) -> Option<u64> {
+        self.deprecations
+            .iter()
+            .find(|d| d.path == path && d.kind == kind && d.name == name)
+            .map(|d| d.deprecated_at)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Write the journal as JSON
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).into_diagnostic()?;
+        std::fs::write(path, json)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to write journal {}", path.display()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to read journal {}", path.display()))?;
+        let journal: Self = serde_json::from_str(&json)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Invalid journal {}", path.display()))?;
+        if journal.version != JOURNAL_VERSION {
+            return Err(miette!(
+                "Unsupported journal version {} in {}",
+                journal.version,
+                path.display()
+            ));
+        }
+        Ok(journal)
+    }
+
+    /// Restore the original contents of the selected files (all if `only` is
+    /// empty). Files changed since the journal was written are reported as
+    /// conflicts and skipped unless `force` is set.
+    pub fn rollback(&self, only: &[PathBuf], force: bool) -> Result<Rollback> {
+        let mut rollback = Rollback {
+            unknown: only
+                .iter()
+                .filter(|path| !self.files.iter().any(|entry| entry.path.ends_with(path)))
+                .cloned()
+                .collect(),
+            ..Default::default()
+        };
+
+        let selected = self
+            .files
+            .iter()
+            .filter(|entry| only.is_empty() || only.iter().any(|path| entry.path.ends_with(path)));
+        for entry in selected {
+            let current = std::fs::read_to_string(&entry.path).ok();
+            if current.as_deref() == Some(entry.original.as_str()) {
+                continue;
+            }
+            if current != entry.modified && !force {
+                rollback.conflicts.push(entry.path.clone());
+                continue;
+            }
+
+            if let Some(parent) = entry.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent).into_diagnostic()?;
+            }
+            std::fs::write(&entry.path, &entry.original)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Failed to restore {}", entry.path.display()))?;
+            rollback.restored.push(entry.path.clone());
+        }
+
+        Ok(rollback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_journal_rollback() {
+        let temp_dir = TempDir::new().unwrap();
+        let edited = temp_dir.path().join("Edited.kt");
+        let deleted = temp_dir.path().join("Deleted.kt");
+        let touched = temp_dir.path().join("Touched.kt");
+        std::fs::write(&edited, "class A\n").unwrap();
+        std::fs::write(&touched, "class C\n// edited by hand\n").unwrap();
+
+        let mut journal = Journal::new();
+        journal.record(
+            &edited,
+            "class A\nclass Dead\n",
+            Some("class A\n".to_string()),
+        );
+        journal.record(&deleted, "class Dead\n", None);
+        journal.record(
+            &touched,
+            "class C\nclass Dead\n",
+            Some("class C\n".to_string()),
+        );
+
+        let journal_path = temp_dir.path().join("journal.json");
+        journal.write(&journal_path).unwrap();
+        let journal = Journal::load(&journal_path).unwrap();
+
+        // Partial rollback of one file
+        let rollback = journal
+            .rollback(
+                &[PathBuf::from("Deleted.kt"), PathBuf::from("Other.kt")],
+                false,
+            )
+            .unwrap();
+        assert_eq!(rollback.restored, vec![deleted.clone()]);
+        assert_eq!(rollback.unknown, vec![PathBuf::from("Other.kt")]);
+        assert_eq!(std::fs::read_to_string(&deleted).unwrap(), "class Dead\n");
+        assert_eq!(std::fs::read_to_string(&edited).unwrap(), "class A\n");
+
+        let rollback = journal.rollback(&[], false).unwrap();
+        assert_eq!(rollback.restored, vec![edited.clone()]);
+        assert_eq!(rollback.conflicts, vec![touched.clone()]);
+
+        let rollback = journal.rollback(&[], true).unwrap();
+        assert_eq!(rollback.restored, vec![touched.clone()]);
+        assert_eq!(
+            std::fs::read_to_string(&touched).unwrap(),
+            "class C\nclass Dead\n"
+        );
+    }
+
+    #[test]
+    fn test_journal_deprecations() {
+        let temp_dir = TempDir::new().unwrap();
+        let deprecation = |name: &str, deprecated_at| Deprecation {
+            path: PathBuf::from("Cache.kt"),
+            kind: "function".to_string(),
+            name: name.to_string(),
+            deprecated_at,
+        };
+
+        let mut journal = Journal::new();
+        journal.record_deprecation(deprecation("evictAll", 100));
+        journal.record_deprecation(deprecation("warmUp", 200));
+        journal.record_deprecation(deprecation("evictAll", 300));
+
+        let journal_path = temp_dir.path().join("journal.json");
+        journal.write(&journal_path).unwrap();
+        let journal = Journal::load(&journal_path).unwrap();
+        assert_eq!(journal.deprecations().len(), 2);
+        assert_eq!(
+            journal.deprecated_at(Path::new("Cache.kt"), "function", "evictAll"),
+            Some(100)
+        );
+        assert_eq!(
+            journal.deprecated_at(Path::new("Cache.kt"), "property", "evictAll"),
+            None
+        );
+
+        // Journals written before deprecations were recorded still load
+        std::fs::write(&journal_path, r#"{"version": 1, "files": []}"#).unwrap();
+        assert!(Journal::load(&journal_path)
+            .unwrap()
+            .deprecations()
+            .is_empty());
+    }
+}
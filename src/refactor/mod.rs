@@ -2,10 +2,20 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+mod deprecate;
 mod editor;
+mod fixer;
+mod journal;
+mod patch;
 mod safe_delete;
 mod undo;
 
-pub use editor::FileEditor;
+pub use deprecate::{
+    deprecation_edit, is_deprecatable, is_deprecated, DeprecationLevel, DEPRECATION_MESSAGE,
+};
+pub use editor::{FileEditor, TextEdit};
+pub use fixer::{default_fixers, plan_fixes, Fix, Fixer, UnusedImportFixer, UnusedParamFixer};
+pub use journal::{Deprecation, Journal, JournalEntry, Rollback};
+pub use patch::Patch;
 pub use safe_delete::SafeDeleter;
 pub use undo::UndoScript;
@@ -0,0 +1,354 @@
+use super::editor::{apply_edits, TextEdit};
+use crate::discovery::NormalizedPath;
+use miette::{IntoDiagnostic, Result};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Lines of context around each hunk
+const CONTEXT: usize = 3;
+
+/// A git-applyable unified diff of file edits and removals
+pub struct Patch {
+    /// Project root; paths in the patch are relative to it
+    root: PathBuf,
+    files: Vec<FilePatch>,
+}
+
+struct FilePatch {
+    path: PathBuf,
+    contents: String,
+    /// Edits against `contents`, or None to delete the file
+    edits: Option<Vec<TextEdit>>,
+}
+
+/// Lines `start..end` (0-based) of the old file replaced by `text`
+struct Change {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl Patch {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            files: Vec::new(),
+        }
+    }
+
+    /// Remove line ranges (0-based, inclusive) from a file with the given contents
+    pub fn remove_lines(&mut self, path: &Path, contents: &str, ranges: Vec<(usize, usize)>) {
+        let offsets = line_offsets(contents);
+        let line_count = offsets.len() - 1;
+        let edits = merge_ranges(line_count, &ranges)
+            .into_iter()
+            .map(|(start, end)| TextEdit::delete(offsets[start], offsets[end + 1]))
+            .collect();
+        self.files.push(FilePatch {
+            path: path.to_path_buf(),
+            contents: contents.to_string(),
+            edits: Some(edits),
+        });
+    }
+
+    /// Apply non-overlapping edits to a file with the given contents
+    pub fn edit_file(&mut self, path: &Path, contents: &str, edits: Vec<TextEdit>) -> Result<()> {
+        apply_edits(contents, &edits)?;
+        self.files.push(FilePatch {
+            path: path.to_path_buf(),
+            contents: contents.to_string(),
+            edits: Some(edits),
+        });
+        Ok(())
+    }
+
+    /// Delete a whole file with the given contents
+    pub fn delete_file(&mut self, path: &Path, contents: &str) {
+        self.files.push(FilePatch {
+            path: path.to_path_buf(),
+            contents: contents.to_string(),
+            edits: None,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Render the patch in `git diff` format
+    pub fn render(&self) -> String {
+        let mut files: Vec<&FilePatch> = self.files.iter().collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut out = String::new();
+        for file in files {
+            let path = self.relative(&file.path);
+            let lines: Vec<&str> = file.contents.split_inclusive('\n').collect();
+            let _ = writeln!(out, "diff --git a/{} b/{}", path, path);
+
+            match &file.edits {
+                None => {
+                    out.push_str("deleted file mode 100644\n");
+                    let _ = writeln!(out, "--- a/{}\n+++ /dev/null", path);
+                    if !lines.is_empty() {
+                        let _ = writeln!(out, "@@ -1,{} +0,0 @@", lines.len());
+                        for line in &lines {
+                            push_line(&mut out, '-', line);
+                        }
+                    }
+                }
+                Some(edits) => {
+                    let _ = writeln!(out, "--- a/{}\n+++ b/{}", path, path);
+                    let changes = changes(&file.contents, edits);
+                    render_hunks(&mut out, &lines, &changes);
+                }
+            }
+        }
+        out
+    }
+
+    /// Write the patch to a file
+    pub fn write(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.render()).into_diagnostic()
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        NormalizedPath::relative_to(path, &self.root).to_string()
+    }
+}
+
+/// Byte offset of every line start, followed by the length of `contents`
+fn line_offsets(contents: &str) -> Vec<usize> {
+    let mut offsets = vec![0];
+    offsets.extend(contents.split_inclusive('\n').scan(0, |offset, line| {
+        *offset += line.len();
+        Some(*offset)
+    }));
+    offsets
+}
+
+/// Append a diff line (with or without its `\n`), marking a missing newline
+fn push_line(out: &mut String, prefix: char, line: &str) {
+    out.push(prefix);
+    match line.strip_suffix('\n') {
+        Some(line) => {
+            out.push_str(line);
+            out.push('\n');
+        }
+        None => {
+            out.push_str(line);
+            out.push_str("\n\\ No newline at end of file\n");
+        }
+    }
+}
+
+/// Turn byte edits into whole-line changes, merging edits on shared lines
+fn changes(contents: &str, edits: &[TextEdit]) -> Vec<Change> {
+    let offsets = line_offsets(contents);
+    let line_count = offsets.len() - 1;
+    let line_of = |pos: usize| {
+        let line = offsets
+            .partition_point(|&offset| offset <= pos)
+            .saturating_sub(1);
+        line.min(line_count.saturating_sub(1))
+    };
+    let span = |edit: &TextEdit| {
+        let start = line_of(edit.start);
+        let last = if edit.end > edit.start {
+            line_of(edit.end - 1)
+        } else {
+            start
+        };
+        (start, (last + 1).min(line_count))
+    };
+
+    let mut edits: Vec<&TextEdit> = edits.iter().collect();
+    edits.sort_by_key(|e| (e.start, e.end));
+
+    let mut changes = Vec::new();
+    let mut i = 0;
+    while i < edits.len() {
+        let (start, mut end) = span(edits[i]);
+        let mut j = i + 1;
+        loop {
+            while j < edits.len() && span(edits[j]).0 <= end {
+                end = end.max(span(edits[j]).1);
+                j += 1;
+            }
+
+            let base = offsets[start];
+            let shifted: Vec<TextEdit> = edits[i..j]
+                .iter()
+                .map(|e| TextEdit::replace(e.start - base, e.end - base, e.replacement.clone()))
+                .collect();
+            let text = apply_edits(&contents[base..offsets[end]], &shifted).unwrap_or_default();
+
+            // A change must end on a line break unless it runs to the end of the file
+            if end < line_count && !text.is_empty() && !text.ends_with('\n') {
+                end += 1;
+                continue;
+            }
+            changes.push(Change { start, end, text });
+            break;
+        }
+        i = j;
+    }
+    changes
+}
+
+/// Render `changes` to `lines` as hunks with context
+fn render_hunks(out: &mut String, lines: &[&str], changes: &[Change]) {
+    // Group changes whose context overlaps into one hunk
+    let mut hunks: Vec<&[Change]> = Vec::new();
+    let mut first = 0;
+    for i in 1..=changes.len() {
+        if i == changes.len() || changes[i].start > changes[i - 1].end + 2 * CONTEXT {
+            hunks.push(&changes[first..i]);
+            first = i;
+        }
+    }
+
+    let mut offset: isize = 0;
+    for hunk in hunks.into_iter().filter(|hunk| !hunk.is_empty()) {
+        let start = hunk[0].start.saturating_sub(CONTEXT);
+        let end = (hunk[hunk.len() - 1].end + CONTEXT).min(lines.len());
+        let old_count = end - start;
+        let mut new_count = old_count;
+        for change in hunk {
+            new_count =
+                new_count - (change.end - change.start) + change.text.split_inclusive('\n').count();
+        }
+        let new_start = (start as isize + offset) as usize;
+
+        let _ = writeln!(
+            out,
+            "@@ -{} +{} @@",
+            hunk_range(start, old_count),
+            hunk_range(new_start, new_count)
+        );
+        let mut line = start;
+        for change in hunk {
+            for context in &lines[line..change.start] {
+                push_line(out, ' ', context);
+            }
+            for removed in &lines[change.start..change.end] {
+                push_line(out, '-', removed);
+            }
+            for added in change.text.split_inclusive('\n') {
+                push_line(out, '+', added);
+            }
+            line = change.end;
+        }
+        for context in &lines[line..end] {
+            push_line(out, ' ', context);
+        }
+        offset += new_count as isize - old_count as isize;
+    }
+}
+
+/// `start,count` of a hunk side; an empty side names the line before it
+fn hunk_range(start: usize, count: usize) -> String {
+    if count == 0 {
+        format!("{},0", start)
+    } else {
+        format!("{},{}", start + 1, count)
+    }
+}
+
+/// Sort, clamp and merge overlapping or adjacent ranges
+fn merge_ranges(line_count: usize, ranges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = ranges
+        .iter()
+        .filter(|(start, end)| start <= end && *start < line_count)
+        .map(|&(start, end)| (start, end.min(line_count - 1)))
+        .collect();
+    ranges.sort();
+
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patch_removes_lines() {
+        let contents = "package a\n\nclass Used\n\nclass Dead {\n}\n\nfun used() {}\n";
+        let mut patch = Patch::new(PathBuf::from("/project"));
+        patch.remove_lines(Path::new("/project/src/A.kt"), contents, vec![(4, 5)]);
+
+        assert_eq!(
+            patch.render(),
+            "diff --git a/src/A.kt b/src/A.kt\n\
+             --- a/src/A.kt\n\
+             +++ b/src/A.kt\n\
+             @@ -2,7 +2,5 @@\n \n class Used\n \n-class Dead {\n-}\n \n fun used() {}\n"
+        );
+    }
+
+    #[test]
+    fn test_patch_deletes_file() {
+        let mut patch = Patch::new(PathBuf::from("/project"));
+        patch.delete_file(Path::new("/project/Dead.kt"), "class Dead\nfun dead() {}");
+
+        assert_eq!(
+            patch.render(),
+            "diff --git a/Dead.kt b/Dead.kt\n\
+             deleted file mode 100644\n\
+             --- a/Dead.kt\n\
+             +++ /dev/null\n\
+             @@ -1,2 +0,0 @@\n\
+             -class Dead\n\
+             -fun dead() {}\n\
+             \\ No newline at end of file\n"
+        );
+    }
+
+    #[test]
+    fn test_patch_edits_within_lines() {
+        let contents = "import a.B\nimport a.C\n\n\
+                        private fun greet(name: String, unused: Int) {\n    \
+                        println(name)\n}\n\nfun main() = greet(\"a\", 1)";
+        let mut patch = Patch::new(PathBuf::from("/project"));
+        let edits = vec![
+            TextEdit::delete(11, 22),
+            TextEdit::delete(53, 66),
+            TextEdit::delete(113, 116),
+        ];
+        patch
+            .edit_file(Path::new("/project/A.kt"), contents, edits)
+            .unwrap();
+
+        assert_eq!(
+            patch.render(),
+            "diff --git a/A.kt b/A.kt\n\
+             --- a/A.kt\n\
+             +++ b/A.kt\n\
+             @@ -1,8 +1,7 @@\n \
+             import a.B\n\
+             -import a.C\n \n\
+             -private fun greet(name: String, unused: Int) {\n\
+             +private fun greet(name: String) {\n     \
+             println(name)\n }\n \n\
+             -fun main() = greet(\"a\", 1)\n\
+             \\ No newline at end of file\n\
+             +fun main() = greet(\"a\")\n\
+             \\ No newline at end of file\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_ranges() {
+        assert_eq!(
+            merge_ranges(10, &[(5, 6), (0, 1), (2, 3), (8, 20), (12, 13)]),
+            vec![(0, 3), (5, 6), (8, 9)]
+        );
+    }
+}
@@ -1,16 +1,59 @@
-use crate::analysis::DeadCode;
+use crate::analysis::{DeadCode, DeadCodeIssue, DeletionUnit};
+use crate::coverage::unix_now;
+use crate::graph::DeclarationId;
+use crate::refactor::deprecate::{
+    deprecation_edit, is_deprecatable, is_deprecated, DeprecationLevel,
+};
+use crate::refactor::editor::{FileEditor, TextEdit};
+use crate::refactor::fixer::Fix;
+use crate::refactor::journal::{Deprecation, Journal};
+use crate::refactor::patch::Patch;
 use crate::refactor::undo::UndoScript;
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Confirm, MultiSelect};
-use miette::{IntoDiagnostic, Result};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use miette::{Context, IntoDiagnostic, Result};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Safe delete functionality with user confirmation
 pub struct SafeDeleter {
     interactive: bool,
     dry_run: bool,
     undo_script_path: Option<PathBuf>,
+    /// Record the changes here for `searchdeadcode undo`
+    journal_path: Option<PathBuf>,
+    /// Write a patch here instead of editing files
+    patch_path: Option<PathBuf>,
+    /// Project root, for the paths in the patch
+    root: PathBuf,
+    /// Rewrites used instead of deleting the declaration's lines
+    fixes: HashMap<DeclarationId, Fix>,
+    /// Findings selected and deleted together with their unit's root
+    units: Vec<DeletionUnit>,
+    /// Only delete declarations the journal recorded as deprecated at least
+    /// this long ago
+    deprecation_age: Option<Duration>,
+}
+
+/// What happens to one finding when its file is edited
+enum Outcome {
+    Deleted,
+    Fixed(String),
+    Deprecated,
+    /// Its edits overlap those of another finding
+    Skipped,
+    /// It does not start its line, so an annotation above would mark
+    /// something else
+    SharesLine,
+}
+
+/// The edits for the selected findings of one file
+struct FilePlan<'a> {
+    /// None deletes the file
+    edits: Option<Vec<TextEdit>>,
+    outcomes: Vec<(&'a DeadCode, Outcome)>,
 }
 
 impl SafeDeleter {
@@ -19,37 +62,90 @@ impl SafeDeleter {
             interactive,
             dry_run,
             undo_script_path,
+            journal_path: None,
+            patch_path: None,
+            root: PathBuf::new(),
+            fixes: HashMap::new(),
+            units: Vec::new(),
+            deprecation_age: None,
         }
     }
 
+    /// Record every changed file in a journal that `searchdeadcode undo` can
+    /// roll back
+    pub fn with_journal(mut self, journal_path: PathBuf) -> Self {
+        self.journal_path = Some(journal_path);
+        self
+    }
+
+    /// Rewrite the findings that have a fix instead of deleting their lines
+    pub fn with_fixes(mut self, fixes: HashMap<DeclarationId, Fix>) -> Self {
+        self.fixes = fixes;
+        self
+    }
+
+    /// Write the deletions as a git-applyable unified diff instead of editing
+    /// files; paths in the patch are relative to `root`
+    pub fn with_patch(mut self, patch_path: PathBuf, root: PathBuf) -> Self {
+        self.patch_path = Some(patch_path);
+        self.root = root;
+        self
+    }
+
+    /// Offer each deletion unit as one choice, so a root is never deleted
+    /// without its dependents or the other way around
+    pub fn with_units(mut self, units: Vec<DeletionUnit>) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Delete only the findings that `deprecate` marked at least `age` ago,
+    /// as recorded in the journal
+    pub fn with_deprecation_age(mut self, age: Duration) -> Self {
+        self.deprecation_age = Some(age);
+        self
+    }
+
     /// Delete dead code with user confirmation
     pub fn delete(&self, dead_code: &[DeadCode]) -> Result<()> {
+        let due: Vec<DeadCode>;
+        let dead_code = match self.deprecation_age {
+            Some(age) => {
+                due = self.deprecated_for(dead_code, age)?;
+                &due[..]
+            }
+            None => dead_code,
+        };
         if dead_code.is_empty() {
             println!("{}", "No dead code to delete.".green());
             return Ok(());
         }
 
-        // Group by file for batch operations
-        let mut by_file: HashMap<PathBuf, Vec<&DeadCode>> = HashMap::new();
-        for item in dead_code {
-            by_file
-                .entry(item.declaration.location.file.clone())
-                .or_default()
-                .push(item);
+        if let Some(patch_path) = &self.patch_path {
+            return self.write_patch(dead_code, patch_path);
         }
 
+        let choices = self.choices(dead_code);
+
         // In dry-run mode, skip selection and show all candidates
         if self.dry_run {
             println!();
             println!("{}", "Dry run - would delete:".yellow().bold());
-            for item in dead_code {
-                println!(
-                    "  {} {} at {}:{}",
-                    item.declaration.kind.display_name(),
-                    item.declaration.name.white(),
-                    item.declaration.location.file.display(),
-                    item.declaration.location.line
-                );
+            for choice in &choices {
+                for (i, item) in choice.iter().enumerate() {
+                    let indent = if i == 0 { "  " } else { "    └ " };
+                    println!(
+                        "{}{} {} at {}:{}",
+                        indent,
+                        item.declaration.kind.display_name(),
+                        item.declaration.name.white(),
+                        item.declaration.location.file.display(),
+                        item.declaration.location.line
+                    );
+                    if let Some(fix) = self.fixes.get(&item.declaration.id) {
+                        println!("    {}", format!("fix: {}", fix.description).dimmed());
+                    }
+                }
             }
             println!();
             println!(
@@ -61,9 +157,9 @@ impl SafeDeleter {
 
         // Get user selection (only in non-dry-run mode)
         let selected = if self.interactive {
-            self.interactive_select(dead_code)?
+            self.interactive_select(&choices)?
         } else {
-            self.batch_confirm(dead_code)?
+            self.batch_confirm(&choices)?
         };
 
         if selected.is_empty() {
@@ -71,42 +167,189 @@ impl SafeDeleter {
             return Ok(());
         }
 
+        // Perform deletions
+        println!();
+        println!("{}", "Deleting dead code...".cyan().bold());
+        self.apply(&selected, None)
+    }
+
+    /// Mark dead declarations `@Deprecated` instead of deleting them, and
+    /// record when in the journal for a later `with_deprecation_age` run
+    pub fn deprecate(&self, dead_code: &[DeadCode], level: DeprecationLevel) -> Result<()> {
+        let candidates: Vec<&DeadCode> =
+            dead_code.iter().filter(|dc| is_deprecatable(dc)).collect();
+        if candidates.is_empty() {
+            println!("{}", "Nothing to deprecate.".green());
+            return Ok(());
+        }
+
+        if self.dry_run {
+            println!();
+            println!("{}", "Dry run - would deprecate:".yellow().bold());
+            for item in &candidates {
+                println!(
+                    "  {} {} at {}:{}",
+                    item.declaration.kind.display_name(),
+                    item.declaration.name.white(),
+                    item.declaration.location.file.display(),
+                    item.declaration.location.line
+                );
+            }
+            println!();
+            println!(
+                "{}",
+                format!("Total: {} items would be deprecated", candidates.len()).dimmed()
+            );
+            return Ok(());
+        }
+
+        println!();
+        println!("{}", "Deprecating dead code...".cyan().bold());
+        self.apply(&candidates, Some(level))
+    }
+
+    /// Apply the planned fixes only, leaving findings without a fix alone
+    pub fn fix(&self, dead_code: &[DeadCode]) -> Result<()> {
+        let fixable: Vec<DeadCode> = dead_code
+            .iter()
+            .filter(|dc| self.fixes.contains_key(&dc.declaration.id))
+            .cloned()
+            .collect();
+        if fixable.is_empty() {
+            println!("{}", "Nothing to fix.".green());
+            return Ok(());
+        }
+
+        if let Some(patch_path) = &self.patch_path {
+            return self.write_patch(&fixable, patch_path);
+        }
+
+        if self.dry_run {
+            println!();
+            println!("{}", "Dry run - would fix:".yellow().bold());
+            for item in &fixable {
+                println!(
+                    "  {} {} at {}:{}: {}",
+                    item.declaration.kind.display_name(),
+                    item.declaration.name.white(),
+                    item.declaration.location.file.display(),
+                    item.declaration.location.line,
+                    self.fixes[&item.declaration.id].description
+                );
+            }
+            println!();
+            println!(
+                "{}",
+                format!("Total: {} items would be fixed", fixable.len()).dimmed()
+            );
+            return Ok(());
+        }
+
+        println!();
+        println!("{}", "Applying fixes...".cyan().bold());
+        let selected: Vec<&DeadCode> = fixable.iter().collect();
+        self.apply(&selected, None)
+    }
+
+    /// Findings still marked `@Deprecated` that the journal recorded as
+    /// deprecated at least `age` ago
+    fn deprecated_for(&self, dead_code: &[DeadCode], age: Duration) -> Result<Vec<DeadCode>> {
+        let journal = match &self.journal_path {
+            Some(path) if path.exists() => Journal::load(path)?,
+            _ => Journal::new(),
+        };
+        let cutoff = unix_now().saturating_sub(age.as_secs());
+        let due: Vec<DeadCode> = dead_code
+            .iter()
+            .filter(|dc| {
+                let decl = &dc.declaration;
+                is_deprecated(decl)
+                    && journal
+                        .deprecated_at(&decl.location.file, decl.kind.display_name(), &decl.name)
+                        .is_some_and(|at| at <= cutoff)
+            })
+            .cloned()
+            .collect();
+        if due.len() < dead_code.len() {
+            println!(
+                "{}",
+                format!(
+                    "Keeping {} findings not deprecated long enough",
+                    dead_code.len() - due.len()
+                )
+                .dimmed()
+            );
+        }
+        Ok(due)
+    }
+
+    /// Deprecations recorded in the journal by earlier runs
+    fn earlier_deprecations(&self) -> Result<Vec<Deprecation>> {
+        match &self.journal_path {
+            Some(path) if path.exists() => Ok(Journal::load(path)?.deprecations().to_vec()),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Edit the files of the selected findings, deprecating them if a level
+    /// is given and deleting or fixing them otherwise, and record an undo
+    /// script and journal if requested
+    fn apply(&self, selected: &[&DeadCode], level: Option<DeprecationLevel>) -> Result<()> {
         // Generate undo script if requested
         let mut undo_script = if self.undo_script_path.is_some() {
             Some(UndoScript::new())
         } else {
             None
         };
+        let mut journal = Journal::new();
+        let earlier = self.earlier_deprecations()?;
+        let now = unix_now();
+        let mut deprecated: Vec<Deprecation> = Vec::new();
+        let mut deleted: Vec<&DeadCode> = Vec::new();
+
+        // Edit each file once, so earlier edits don't shift later ones
+        for (file, items) in group_by_file(selected) {
+            let contents = match std::fs::read_to_string(file) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    for item in items {
+                        print_failure(item, &e);
+                    }
+                    continue;
+                }
+            };
 
-        // Perform deletions
-        println!();
-        println!("{}", "Deleting dead code...".cyan().bold());
-
-        for item in &selected {
             if let Some(ref mut script) = undo_script {
                 // Record for undo
-                if let Ok(contents) = std::fs::read_to_string(&item.declaration.location.file) {
-                    script.record_file_state(&item.declaration.location.file, &contents);
-                }
+                script.record_file_state(file, &contents);
             }
 
-            // Perform deletion
-            match self.delete_declaration(item) {
-                Ok(_) => {
-                    println!(
-                        "  {} Deleted {} '{}'",
-                        "✓".green(),
-                        item.declaration.kind.display_name(),
-                        item.declaration.name
-                    );
+            let plan = match level {
+                Some(level) => plan_deprecations(&contents, &items, level),
+                None => self.plan_file(&contents, &items),
+            };
+            let editor = FileEditor::new();
+            let result = match &plan.edits {
+                Some(edits) => editor.apply_edits(file, edits),
+                None => editor.delete_file(file),
+            };
+
+            match result {
+                Ok(()) => {
+                    journal.record(file, &contents, std::fs::read_to_string(file).ok());
+                    for (item, outcome) in &plan.outcomes {
+                        match outcome {
+                            Outcome::Deleted => deleted.push(*item),
+                            Outcome::Deprecated => deprecated.push(deprecation(item, now)),
+                            _ => {}
+                        }
+                        print_outcome(item, outcome);
+                    }
                 }
                 Err(e) => {
-                    println!(
-                        "  {} Failed to delete '{}': {}",
-                        "✗".red(),
-                        item.declaration.name,
-                        e
-                    );
+                    for item in items {
+                        print_failure(item, &e);
+                    }
                 }
             }
         }
@@ -118,11 +361,75 @@ impl SafeDeleter {
             println!("{} Undo script saved to: {}", "→".dimmed(), path.display());
         }
 
+        // Earlier deprecations wait for a later run unless deleted by this one
+        for earlier in earlier {
+            let gone = deleted.iter().any(|dc| {
+                let decl = &dc.declaration;
+                earlier.path == decl.location.file
+                    && earlier.kind == decl.kind.display_name()
+                    && earlier.name == decl.name
+            });
+            if !gone {
+                journal.record_deprecation(earlier);
+            }
+        }
+        for deprecation in deprecated {
+            journal.record_deprecation(deprecation);
+        }
+
+        if let Some(path) = &self.journal_path {
+            journal.write(path)?;
+            println!(
+                "{} Journal saved to: {} (roll back with: searchdeadcode undo --journal {})",
+                "→".dimmed(),
+                path.display(),
+                path.display()
+            );
+        }
+
         Ok(())
     }
 
+    /// What can be selected: each deletion unit's findings, root first, and
+    /// every finding outside a unit on its own
+    fn choices<'a>(&self, dead_code: &'a [DeadCode]) -> Vec<Vec<&'a DeadCode>> {
+        let mut unit_of: HashMap<&DeclarationId, usize> = HashMap::new();
+        for (i, unit) in self.units.iter().enumerate() {
+            for id in unit.ids() {
+                unit_of.insert(id, i);
+            }
+        }
+
+        let mut choices: Vec<Vec<&DeadCode>> = Vec::new();
+        let mut unit_choice: HashMap<usize, usize> = HashMap::new();
+        for item in dead_code {
+            match unit_of.get(&item.declaration.id) {
+                Some(&unit) => {
+                    let index = *unit_choice.entry(unit).or_insert_with(|| {
+                        choices.push(Vec::new());
+                        choices.len() - 1
+                    });
+                    choices[index].push(item);
+                }
+                None => choices.push(vec![item]),
+            }
+        }
+        for choice in &mut choices {
+            let root = unit_of
+                .get(&choice[0].declaration.id)
+                .map(|&unit| &self.units[unit].root);
+            if let Some(at) = choice
+                .iter()
+                .position(|dc| Some(&dc.declaration.id) == root)
+            {
+                choice[..=at].rotate_right(1);
+            }
+        }
+        choices
+    }
+
     /// Interactive selection mode - confirm each item
-    fn interactive_select<'a>(&self, dead_code: &'a [DeadCode]) -> Result<Vec<&'a DeadCode>> {
+    fn interactive_select<'a>(&self, choices: &[Vec<&'a DeadCode>]) -> Result<Vec<&'a DeadCode>> {
         let mut selected = Vec::new();
 
         println!();
@@ -132,14 +439,8 @@ impl SafeDeleter {
         );
         println!();
 
-        for item in dead_code {
-            let prompt = format!(
-                "Delete {} '{}' at {}:{}?",
-                item.declaration.kind.display_name(),
-                item.declaration.name,
-                item.declaration.location.file.display(),
-                item.declaration.location.line
-            );
+        for choice in choices {
+            let prompt = format!("Delete {}?", choice_label(choice));
 
             if Confirm::with_theme(&ColorfulTheme::default())
                 .with_prompt(&prompt)
@@ -147,7 +448,7 @@ impl SafeDeleter {
                 .interact()
                 .into_diagnostic()?
             {
-                selected.push(item);
+                selected.extend(choice);
             }
         }
 
@@ -155,19 +456,8 @@ impl SafeDeleter {
     }
 
     /// Batch confirmation - select multiple at once
-    fn batch_confirm<'a>(&self, dead_code: &'a [DeadCode]) -> Result<Vec<&'a DeadCode>> {
-        let items: Vec<String> = dead_code
-            .iter()
-            .map(|dc| {
-                format!(
-                    "{} '{}' at {}:{}",
-                    dc.declaration.kind.display_name(),
-                    dc.declaration.name,
-                    dc.declaration.location.file.display(),
-                    dc.declaration.location.line
-                )
-            })
-            .collect();
+    fn batch_confirm<'a>(&self, choices: &[Vec<&'a DeadCode>]) -> Result<Vec<&'a DeadCode>> {
+        let items: Vec<String> = choices.iter().map(|choice| choice_label(choice)).collect();
 
         println!();
         println!("{}", "Select items to delete:".cyan().bold());
@@ -179,7 +469,10 @@ impl SafeDeleter {
             .interact()
             .into_diagnostic()?;
 
-        let selected: Vec<&DeadCode> = selections.into_iter().map(|i| &dead_code[i]).collect();
+        let selected: Vec<&DeadCode> = selections
+            .into_iter()
+            .flat_map(|i| choices[i].iter().copied())
+            .collect();
 
         // Confirm final selection
         if !selected.is_empty() {
@@ -198,30 +491,151 @@ impl SafeDeleter {
         Ok(selected)
     }
 
-    /// Delete a single declaration from its file
-    fn delete_declaration(&self, dead_code: &DeadCode) -> Result<()> {
-        let file_path = &dead_code.declaration.location.file;
-        let contents = std::fs::read_to_string(file_path).into_diagnostic()?;
+    /// Write all deletions to a patch without touching the working tree
+    fn write_patch(&self, dead_code: &[DeadCode], patch_path: &Path) -> Result<()> {
+        let items: Vec<&DeadCode> = dead_code.iter().collect();
+        let by_file = group_by_file(&items);
+
+        let mut patch = Patch::new(self.root.clone());
+        let (mut deletions, mut fixes) = (0, 0);
+        for (file, items) in &by_file {
+            let contents = std::fs::read_to_string(file)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Failed to read {}", file.display()))?;
+
+            let plan = self.plan_file(&contents, items);
+            for (item, outcome) in &plan.outcomes {
+                match outcome {
+                    Outcome::Deleted => deletions += 1,
+                    Outcome::Fixed(_) => fixes += 1,
+                    _ => print_outcome(item, outcome),
+                }
+            }
+            match plan.edits {
+                Some(edits) => patch.edit_file(file, &contents, edits)?,
+                None => patch.delete_file(file, &contents),
+            }
+        }
+        patch.write(patch_path)?;
 
-        let lines: Vec<&str> = contents.lines().collect();
+        println!();
+        println!(
+            "{} Patch with {} deletions and {} fixes in {} files saved to: {}",
+            "→".dimmed(),
+            deletions,
+            fixes,
+            by_file.len(),
+            patch_path.display()
+        );
+        println!(
+            "{}",
+            format!(
+                "Review it, then apply with: git apply {}",
+                patch_path.display()
+            )
+            .dimmed()
+        );
+        Ok(())
+    }
+
+    /// First and last line (0-based) of a declaration
+    fn declaration_lines(&self, lines: &[&str], dead_code: &DeadCode) -> (usize, usize) {
         let start_line = dead_code.declaration.location.line.saturating_sub(1);
+        (start_line, self.find_declaration_end(lines, start_line))
+    }
+
+    /// Plan the edits of one file: dead files are deleted, findings with a
+    /// fix are rewritten and the others lose their lines. Deletions nested in
+    /// another deletion are covered by it; other overlapping findings are
+    /// skipped.
+    fn plan_file<'a>(&self, contents: &str, items: &[&'a DeadCode]) -> FilePlan<'a> {
+        if items.iter().any(|dc| dc.issue == DeadCodeIssue::DeadFile) {
+            return FilePlan {
+                edits: None,
+                outcomes: items.iter().map(|&dc| (dc, Outcome::Deleted)).collect(),
+            };
+        }
 
-        // Find the end of the declaration (simple heuristic)
-        let end_line = self.find_declaration_end(&lines, start_line);
+        let lines: Vec<&str> = contents.lines().collect();
+        let mut line_starts = vec![0];
+        line_starts.extend(contents.split_inclusive('\n').scan(0, |offset, line| {
+            *offset += line.len();
+            Some(*offset)
+        }));
+
+        let mut candidates: Vec<(&DeadCode, Vec<TextEdit>, Outcome)> = Vec::new();
+        let mut outcomes = Vec::new();
+        for &item in items {
+            if let Some(fix) = self.fixes.get(&item.declaration.id) {
+                let outcome = Outcome::Fixed(fix.description.clone());
+                candidates.push((item, fix.edits.clone(), outcome));
+                continue;
+            }
+            // Dropping a branch means rewriting the `if` around it, accessor
+            // findings are about a property that is still used, a dead doc
+            // link is fixed in the comment, Compose state and defaults are
+            // still written or passed where they are used, callers pass
+            // arguments for uninvoked function parameters, and effectively
+            // dead objects are still imported or named as qualifiers
+            if matches!(
+                item.issue,
+                DeadCodeIssue::DeadBranch
+                    | DeadCodeIssue::VarCouldBeVal
+                    | DeadCodeIssue::UnusedGetter
+                    | DeadCodeIssue::UnusedSetter
+                    | DeadCodeIssue::DeadDocLink
+                    | DeadCodeIssue::UnusedComposeState
+                    | DeadCodeIssue::UnusedComposableDefault
+                    | DeadCodeIssue::UninvokedFunctionParameter
+                    | DeadCodeIssue::EffectivelyDeadObject
+            ) {
+                outcomes.push((item, Outcome::Skipped));
+                continue;
+            }
 
-        // Remove the lines
-        let mut new_lines: Vec<&str> = Vec::new();
-        for (i, line) in lines.iter().enumerate() {
-            if i < start_line || i > end_line {
-                new_lines.push(line);
+            let (start, end) = self.declaration_lines(&lines, item);
+            if start >= lines.len() {
+                outcomes.push((item, Outcome::Skipped));
+                continue;
             }
+            let edit = TextEdit::delete(line_starts[start], line_starts[end + 1]);
+            candidates.push((item, vec![edit], Outcome::Deleted));
         }
 
-        // Write back
-        let new_contents = new_lines.join("\n");
-        std::fs::write(file_path, new_contents).into_diagnostic()?;
+        // Outer deletions first, then the fixes
+        candidates.sort_by_key(|(_, edits, outcome)| {
+            let first = edits.first().map_or((0, 0), |e| (e.start, e.end));
+            (
+                matches!(outcome, Outcome::Fixed(_)),
+                first.0,
+                Reverse(first.1),
+            )
+        });
+
+        let mut accepted: Vec<TextEdit> = Vec::new();
+        for (item, edits, outcome) in candidates {
+            let covered = edits.iter().all(|edit| {
+                accepted
+                    .iter()
+                    .any(|a| a.replacement.is_empty() && a.start <= edit.start && edit.end <= a.end)
+            });
+            if covered {
+                outcomes.push((item, Outcome::Deleted));
+            } else if edits
+                .iter()
+                .any(|edit| accepted.iter().any(|a| a.overlaps(edit)))
+            {
+                outcomes.push((item, Outcome::Skipped));
+            } else {
+                accepted.extend(edits);
+                outcomes.push((item, outcome));
+            }
+        }
 
-        Ok(())
+        FilePlan {
+            edits: Some(accepted),
+            outcomes,
+        }
     }
 
     /// Find the end line of a declaration (simple brace matching)
@@ -256,3 +670,113 @@ impl SafeDeleter {
         start_line
     }
 }
+
+/// One line describing a choice: its root finding and how many dependents
+/// come with it
+fn choice_label(choice: &[&DeadCode]) -> String {
+    let root = choice[0];
+    let mut label = format!(
+        "{} '{}' at {}:{}",
+        root.declaration.kind.display_name(),
+        root.declaration.name,
+        root.declaration.location.file.display(),
+        root.declaration.location.line
+    );
+    if choice.len() > 1 {
+        label.push_str(&format!(" (+{} dependents)", choice.len() - 1));
+    }
+    label
+}
+
+/// Plan the annotations deprecating the selected findings of one file
+fn plan_deprecations<'a>(
+    contents: &str,
+    items: &[&'a DeadCode],
+    level: DeprecationLevel,
+) -> FilePlan<'a> {
+    let mut edits: Vec<TextEdit> = Vec::new();
+    let mut outcomes = Vec::new();
+    for &item in items {
+        match deprecation_edit(contents, item, level) {
+            Some(edit) if edits.iter().any(|e| e.overlaps(&edit)) => {
+                outcomes.push((item, Outcome::Skipped))
+            }
+            Some(edit) => {
+                edits.push(edit);
+                outcomes.push((item, Outcome::Deprecated));
+            }
+            None => outcomes.push((item, Outcome::SharesLine)),
+        }
+    }
+    FilePlan {
+        edits: Some(edits),
+        outcomes,
+    }
+}
+
+/// The journal record of a declaration deprecated now
+fn deprecation(item: &DeadCode, now: u64) -> Deprecation {
+    let decl = &item.declaration;
+    Deprecation {
+        path: decl.location.file.clone(),
+        kind: decl.kind.display_name().to_string(),
+        name: decl.name.clone(),
+        deprecated_at: now,
+    }
+}
+
+/// Group findings by file, in path order
+fn group_by_file<'a>(items: &[&'a DeadCode]) -> BTreeMap<&'a Path, Vec<&'a DeadCode>> {
+    let mut by_file: BTreeMap<&Path, Vec<&DeadCode>> = BTreeMap::new();
+    for &item in items {
+        by_file
+            .entry(&item.declaration.location.file)
+            .or_default()
+            .push(item);
+    }
+    by_file
+}
+
+fn print_outcome(item: &DeadCode, outcome: &Outcome) {
+    let decl = &item.declaration;
+    match outcome {
+        Outcome::Deleted => println!(
+            "  {} Deleted {} '{}'",
+            "✓".green(),
+            decl.kind.display_name(),
+            decl.name
+        ),
+        Outcome::Fixed(description) => println!(
+            "  {} Fixed {} '{}': {}",
+            "✓".green(),
+            decl.kind.display_name(),
+            decl.name,
+            description
+        ),
+        Outcome::Deprecated => println!(
+            "  {} Deprecated {} '{}'",
+            "✓".green(),
+            decl.kind.display_name(),
+            decl.name
+        ),
+        Outcome::Skipped => println!(
+            "  {} Skipped '{}': overlaps another change",
+            "✗".yellow(),
+            decl.name
+        ),
+        Outcome::SharesLine => println!(
+            "  {} Skipped '{}': does not start its own line",
+            "✗".yellow(),
+            decl.name
+        ),
+    }
+}
+
+fn print_failure(item: &DeadCode, error: &dyn std::fmt::Display) {
+    println!(
+        "  {} Failed to delete '{}': {}",
+        "✗".red(),
+        item.declaration.name,
+        error
+    );
+}
@@ -0,0 +1,126 @@
+use crate::analysis::{DeadCode, Severity};
+use crate::baseline::{FindingId, IssueFingerprint};
+use miette::{IntoDiagnostic, Result};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// GitLab Code Quality reporter, for the `codequality` report artifact that
+/// shows findings on merge requests
+pub struct CodeQualityReporter {
+    output_path: Option<PathBuf>,
+    /// Project root; paths in the report are relative to it
+    root: PathBuf,
+}
+
+impl CodeQualityReporter {
+    pub fn new(output_path: Option<PathBuf>, root: PathBuf) -> Self {
+        Self { output_path, root }
+    }
+
+    pub fn report(&self, dead_code: &[DeadCode]) -> Result<()> {
+        let issues = self.issues(dead_code);
+        let json = serde_json::to_string_pretty(&issues).into_diagnostic()?;
+
+        if let Some(path) = &self.output_path {
+            std::fs::write(path, &json).into_diagnostic()?;
+            println!("Code Quality report written to: {}", path.display());
+        } else {
+            println!("{}", json);
+        }
+
+        Ok(())
+    }
+
+    fn issues(&self, dead_code: &[DeadCode]) -> Vec<CodeQualityIssue> {
+        // GitLab drops issues with duplicate fingerprints, e.g. overloads,
+        // which the assigned ids number apart
+        let ids = FindingId::assign(dead_code, &self.root);
+        dead_code
+            .iter()
+            .zip(ids)
+            .map(|(dc, id)| {
+                let fingerprint = IssueFingerprint::from_dead_code(dc, &self.root);
+
+                CodeQualityIssue {
+                    description: dc.message.clone(),
+                    check_name: dc.issue.code(),
+                    fingerprint: id.to_string(),
+                    severity: match dc.severity {
+                        Severity::Error => "major",
+                        Severity::Warning => "minor",
+                        Severity::Info => "info",
+                    },
+                    location: CodeQualityLocation {
+                        path: fingerprint.file.clone(),
+                        lines: CodeQualityLines {
+                            begin: dc.declaration.location.line,
+                        },
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+/// An issue in the Code Climate format GitLab reads
+#[derive(Serialize)]
+struct CodeQualityIssue {
+    description: String,
+    check_name: &'static str,
+    fingerprint: String,
+    severity: &'static str,
+    location: CodeQualityLocation,
+}
+
+#[derive(Serialize)]
+struct CodeQualityLocation {
+    path: String,
+    lines: CodeQualityLines,
+}
+
+#[derive(Serialize)]
+struct CodeQualityLines {
+    begin: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+
+    fn make_dead_code(name: &str, line: usize) -> DeadCode {
+        let path = PathBuf::from("/project/app/src/Test.kt");
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), line * 10, line * 10 + 5),
+            name.to_string(),
+            DeclarationKind::Function,
+            Location::new(path, line, 5, line * 10, line * 10 + 5),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, DeadCodeIssue::Unreferenced)
+    }
+
+    #[test]
+    fn test_code_quality_issues() {
+        let reporter = CodeQualityReporter::new(None, PathBuf::from("/project"));
+        let dead_code = vec![
+            make_dead_code("helper", 3),
+            make_dead_code("helper", 8),
+            make_dead_code("other", 12),
+        ];
+
+        let issues = serde_json::to_value(reporter.issues(&dead_code)).unwrap();
+        assert_eq!(issues[0]["check_name"], "DC001");
+        assert_eq!(issues[0]["severity"], "minor");
+        assert_eq!(issues[0]["location"]["path"], "app/src/Test.kt");
+        assert_eq!(issues[0]["location"]["lines"]["begin"], 3);
+
+        // Overloads stay distinct, and fingerprints don't depend on the line
+        let fingerprint = issues[0]["fingerprint"].as_str().unwrap();
+        assert_eq!(issues[1]["fingerprint"], format!("{}-2", fingerprint));
+        let moved = reporter.issues(&[make_dead_code("helper", 30)]);
+        assert_eq!(moved[0].fingerprint, fingerprint);
+        assert_ne!(issues[2]["fingerprint"], fingerprint);
+    }
+}
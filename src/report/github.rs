@@ -0,0 +1,206 @@
+use crate::analysis::metrics::{package_of, Metrics};
+use crate::analysis::{DeadCode, Severity};
+use crate::discovery::NormalizedPath;
+use miette::{IntoDiagnostic, Result};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Packages listed in the summary
+const TOP_PACKAGES: usize = 10;
+
+/// GitHub Actions reporter: one workflow command per finding, shown as an
+/// annotation on the pull request diff
+pub struct GithubActionsReporter;
+
+impl GithubActionsReporter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn report(&self, dead_code: &[DeadCode]) -> Result<()> {
+        let root = std::env::current_dir().unwrap_or_default();
+        for item in dead_code {
+            println!("{}", annotation(item, &root));
+        }
+        Ok(())
+    }
+}
+
+impl Default for GithubActionsReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Append a markdown summary of the findings to `path`, e.g. $GITHUB_STEP_SUMMARY
+pub fn write_summary(dead_code: &[DeadCode], path: &Path) -> Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .into_diagnostic()?;
+    let metrics = Metrics::compute(dead_code);
+    file.write_all(markdown_summary(dead_code, &metrics).as_bytes())
+        .into_diagnostic()
+}
+
+/// `::warning file=...,line=...::message` command for a finding
+fn annotation(item: &DeadCode, root: &Path) -> String {
+    let command = match item.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "notice",
+    };
+    let location = &item.declaration.location;
+    let file = NormalizedPath::relative_to(&location.file, root);
+
+    format!(
+        "::{} file={},line={},col={},title={}::{}",
+        command,
+        escape_property(file.as_str()),
+        location.line,
+        location.column,
+        escape_property(&format!("Dead code ({})", item.issue.code())),
+        escape_data(&item.message)
+    )
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Markdown summary: counts by severity and detector, the packages with the
+/// most findings and the estimated savings
+fn markdown_summary(dead_code: &[DeadCode], metrics: &Metrics) -> String {
+    let mut out = String::from("## SearchDeadCode\n\n");
+    if dead_code.is_empty() {
+        out.push_str("No dead code found.\n\n");
+        return out;
+    }
+
+    let files: HashSet<&PathBuf> = dead_code
+        .iter()
+        .map(|item| &item.declaration.location.file)
+        .collect();
+    let _ = writeln!(
+        out,
+        "**{} findings** in {} files\n",
+        dead_code.len(),
+        files.len()
+    );
+
+    let by_severity = counts(dead_code, |item| item.severity.as_str().to_string());
+    table(&mut out, "Severity", &by_severity);
+
+    let by_detector = counts(dead_code, |item| item.issue.code().to_string());
+    table(&mut out, "Detector", &by_detector);
+
+    let by_package = counts(dead_code, |item| {
+        package_of(item).unwrap_or_else(|| "(default package)".to_string())
+    });
+    let top = &by_package[..by_package.len().min(TOP_PACKAGES)];
+    out.push_str("### Top packages\n\n");
+    table(&mut out, "Package", top);
+
+    out.push_str("### Estimated savings\n\n| Metric | Estimate |\n| --- | ---: |\n");
+    let _ = writeln!(out, "| Deletable lines | {} |", metrics.deletable_lines);
+    let _ = writeln!(out, "| Dead methods | {} |", metrics.dead_methods);
+    let _ = writeln!(
+        out,
+        "| APK size | {:.1} KB |\n",
+        metrics.estimated_apk_bytes as f64 / 1024.0
+    );
+
+    out
+}
+
+/// Count findings per key, most frequent first
+pub(super) fn counts(
+    dead_code: &[DeadCode],
+    key: impl Fn(&DeadCode) -> String,
+) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for item in dead_code {
+        *counts.entry(key(item)).or_default() += 1;
+    }
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+fn table(out: &mut String, header: &str, rows: &[(String, usize)]) {
+    let _ = writeln!(out, "| {} | Findings |\n| --- | ---: |", header);
+    for (key, count) in rows {
+        let _ = writeln!(out, "| {} | {} |", key.replace('|', "\\|"), count);
+    }
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+
+    fn make_dead_code(name: &str, package: &str, issue: DeadCodeIssue) -> DeadCode {
+        let path = PathBuf::from("/project/app/src/Test.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), 10, 20),
+            name.to_string(),
+            DeclarationKind::Function,
+            Location::new(path, 3, 5, 10, 20),
+            Language::Kotlin,
+        );
+        decl.fully_qualified_name = Some(format!("{}.{}", package, name));
+        DeadCode::new(decl, issue)
+    }
+
+    #[test]
+    fn test_annotation() {
+        let item = make_dead_code("helper", "com.app", DeadCodeIssue::Unreferenced)
+            .with_message("Function 'helper' is never used: 100%\nsure".to_string());
+
+        assert_eq!(
+            annotation(&item, Path::new("/project")),
+            "::warning file=app/src/Test.kt,line=3,col=5,title=Dead code (DC001)::\
+             Function 'helper' is never used: 100%25%0Asure"
+        );
+    }
+
+    #[test]
+    fn test_markdown_summary() {
+        let dead_code = vec![
+            make_dead_code("a", "com.app.ui", DeadCodeIssue::Unreferenced),
+            make_dead_code("b", "com.app.ui", DeadCodeIssue::UnusedImport),
+            make_dead_code("c", "com.app.data", DeadCodeIssue::Unreferenced),
+        ];
+
+        let metrics = Metrics {
+            deletable_lines: 12,
+            dead_methods: 2,
+            estimated_apk_bytes: 2048,
+            ..Metrics::default()
+        };
+
+        assert_eq!(
+            markdown_summary(&dead_code, &metrics),
+            "## SearchDeadCode\n\n\
+             **3 findings** in 1 files\n\n\
+             | Severity | Findings |\n| --- | ---: |\n| warning | 2 |\n| info | 1 |\n\n\
+             | Detector | Findings |\n| --- | ---: |\n| DC001 | 2 |\n| DC004 | 1 |\n\n\
+             ### Top packages\n\n\
+             | Package | Findings |\n| --- | ---: |\n| com.app.ui | 2 |\n| com.app.data | 1 |\n\n\
+             ### Estimated savings\n\n| Metric | Estimate |\n| --- | ---: |\n\
+             | Deletable lines | 12 |\n| Dead methods | 2 |\n| APK size | 2.0 KB |\n\n"
+        );
+    }
+}
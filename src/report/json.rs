@@ -1,21 +1,93 @@
-use crate::analysis::{Confidence, DeadCode, Severity};
+use super::model::{self, Report};
+use super::schema::{validate_report, JSON_REPORT_VERSION};
+use crate::analysis::DeadCode;
+use crate::discovery::NormalizedPath;
+use crate::graph::SkippedFile;
+use crate::pipeline::TimingsReport;
 use miette::{IntoDiagnostic, Result};
-use serde::Serialize;
 use std::path::PathBuf;
 
 /// JSON reporter for programmatic output
 pub struct JsonReporter {
     output_path: Option<PathBuf>,
+    /// Validate the report against the bundled schema before writing it
+    validate: bool,
+    /// Findings excluded by inline suppressions
+    suppressed: usize,
+    /// Project root, which finding ids are relative to
+    root: PathBuf,
+    /// Phase timings of the run, when requested
+    timings: Option<TimingsReport>,
+    /// Files left out of the analysis because they could not be parsed
+    skipped_files: Vec<SkippedFile>,
 }
 
 impl JsonReporter {
     pub fn new(output_path: Option<PathBuf>) -> Self {
-        Self { output_path }
+        Self {
+            output_path,
+            validate: false,
+            suppressed: 0,
+            root: PathBuf::new(),
+            timings: None,
+            skipped_files: Vec::new(),
+        }
+    }
+
+    pub fn with_validation(mut self, validate: bool) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// Count findings excluded by inline suppressions in the summary
+    pub fn with_suppressed(mut self, suppressed: usize) -> Self {
+        self.suppressed = suppressed;
+        self
+    }
+
+    pub fn with_project_root(mut self, root: PathBuf) -> Self {
+        self.root = root;
+        self
+    }
+
+    /// Include the phase timings of the run
+    pub fn with_timings(mut self, timings: Option<TimingsReport>) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    /// List the files that could not be parsed
+    pub fn with_skipped_files(mut self, skipped_files: Vec<SkippedFile>) -> Self {
+        self.skipped_files = skipped_files;
+        self
     }
 
     pub fn report(&self, dead_code: &[DeadCode]) -> Result<()> {
-        let report = JsonReport::from_dead_code(dead_code);
-        let json = serde_json::to_string_pretty(&report).into_diagnostic()?;
+        let mut report = Report::from_dead_code(dead_code, &self.root);
+        report.summary.suppressed += self.suppressed;
+        report.timings = self.timings.clone().map(Into::into);
+        report.skipped_files = self
+            .skipped_files
+            .iter()
+            .map(|skipped| model::SkippedFile {
+                file: NormalizedPath::new(&skipped.path).to_string(),
+                reason: skipped.reason.clone(),
+            })
+            .collect();
+        let value = serde_json::to_value(&report).into_diagnostic()?;
+
+        if self.validate {
+            let errors = validate_report(&value);
+            if !errors.is_empty() {
+                return Err(miette::miette!(
+                    "JSON report does not match schema v{}:\n  {}",
+                    JSON_REPORT_VERSION,
+                    errors.join("\n  ")
+                ));
+            }
+        }
+
+        let json = serde_json::to_string_pretty(&value).into_diagnostic()?;
 
         if let Some(path) = &self.output_path {
             std::fs::write(path, &json).into_diagnostic()?;
@@ -28,116 +100,70 @@ impl JsonReporter {
     }
 }
 
-#[derive(Serialize)]
-struct JsonReport {
-    version: &'static str,
-    total_issues: usize,
-    issues: Vec<JsonIssue>,
-    summary: JsonSummary,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+    use std::path::Path;
 
-#[derive(Serialize)]
-struct JsonIssue {
-    code: &'static str,
-    severity: &'static str,
-    confidence: &'static str,
-    confidence_score: f64,
-    runtime_confirmed: bool,
-    message: String,
-    file: String,
-    line: usize,
-    column: usize,
-    declaration: JsonDeclaration,
-}
+    fn make_dead_code(name: &str, line: usize) -> DeadCode {
+        let path = PathBuf::from("src/Test.kt");
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), line * 10, line * 10 + 5),
+            name.to_string(),
+            DeclarationKind::Function,
+            Location::new(path, line, 5, line * 10, line * 10 + 5),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, DeadCodeIssue::Unreferenced)
+    }
 
-#[derive(Serialize)]
-struct JsonDeclaration {
-    name: String,
-    kind: &'static str,
-    fully_qualified_name: Option<String>,
-}
+    #[test]
+    fn test_report_matches_schema() {
+        let dead_code = vec![
+            make_dead_code("unusedHelper", 3),
+            make_dead_code("staleCallback", 8)
+                .with_runtime_confirmed(true)
+                .with_owner(Some("@org/android".to_string())),
+        ];
 
-#[derive(Serialize)]
-struct JsonSummary {
-    errors: usize,
-    warnings: usize,
-    infos: usize,
-    by_confidence: JsonConfidenceSummary,
-    runtime_confirmed_count: usize,
-}
+        let report =
+            serde_json::to_value(Report::from_dead_code(&dead_code, Path::new(""))).unwrap();
+        assert_eq!(validate_report(&report), Vec::<String>::new());
+    }
 
-#[derive(Serialize)]
-struct JsonConfidenceSummary {
-    confirmed: usize,
-    high: usize,
-    medium: usize,
-    low: usize,
-}
+    #[test]
+    fn test_report_with_timings_and_skipped_files_matches_schema() {
+        let mut ctx = crate::pipeline::PipelineContext::new(
+            crate::config::Config::default(),
+            PathBuf::from("."),
+            crate::pipeline::PipelineOptions::default(),
+        );
+        ctx.timings = vec![("parse", std::time::Duration::from_millis(20))];
+        ctx.phase_timings = vec![(
+            "parse/resolve".to_string(),
+            std::time::Duration::from_millis(5),
+        )];
 
-impl JsonReport {
-    fn from_dead_code(dead_code: &[DeadCode]) -> Self {
-        let mut errors = 0;
-        let mut warnings = 0;
-        let mut infos = 0;
-        let mut confirmed = 0;
-        let mut high = 0;
-        let mut medium = 0;
-        let mut low = 0;
-        let mut runtime_confirmed_count = 0;
-
-        let issues: Vec<JsonIssue> = dead_code
-            .iter()
-            .map(|dc| {
-                match dc.severity {
-                    Severity::Error => errors += 1,
-                    Severity::Warning => warnings += 1,
-                    Severity::Info => infos += 1,
-                }
-                match dc.confidence {
-                    Confidence::Confirmed => confirmed += 1,
-                    Confidence::High => high += 1,
-                    Confidence::Medium => medium += 1,
-                    Confidence::Low => low += 1,
-                }
-                if dc.runtime_confirmed {
-                    runtime_confirmed_count += 1;
-                }
-
-                JsonIssue {
-                    code: dc.issue.code(),
-                    severity: dc.severity.as_str(),
-                    confidence: dc.confidence.as_str(),
-                    confidence_score: dc.confidence.score(),
-                    runtime_confirmed: dc.runtime_confirmed,
-                    message: dc.message.clone(),
-                    file: dc.declaration.location.file.to_string_lossy().to_string(),
-                    line: dc.declaration.location.line,
-                    column: dc.declaration.location.column,
-                    declaration: JsonDeclaration {
-                        name: dc.declaration.name.clone(),
-                        kind: dc.declaration.kind.display_name(),
-                        fully_qualified_name: dc.declaration.fully_qualified_name.clone(),
-                    },
-                }
-            })
-            .collect();
+        let mut report = Report::from_dead_code(&[], Path::new(""));
+        report.timings = Some(TimingsReport::from_context(&ctx).into());
+        report.skipped_files = vec![model::SkippedFile {
+            file: "src/Broken.kt".to_string(),
+            reason: "stream did not contain valid UTF-8".to_string(),
+        }];
+        let report = serde_json::to_value(report).unwrap();
+        assert_eq!(validate_report(&report), Vec::<String>::new());
+        assert_eq!(report["timings"]["phases"][1]["phase"], "parse/resolve");
+    }
 
-        Self {
-            version: "1.1",
-            total_issues: dead_code.len(),
-            issues,
-            summary: JsonSummary {
-                errors,
-                warnings,
-                infos,
-                by_confidence: JsonConfidenceSummary {
-                    confirmed,
-                    high,
-                    medium,
-                    low,
-                },
-                runtime_confirmed_count,
-            },
-        }
+    #[test]
+    fn test_schema_rejects_unversioned_changes() {
+        let mut report = serde_json::to_value(Report::from_dead_code(&[], Path::new(""))).unwrap();
+        report["unexpected"] = serde_json::json!(true);
+
+        let errors = validate_report(&report);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("unexpected property 'unexpected'"));
     }
 }
@@ -1,12 +1,22 @@
+mod code_quality;
+mod github;
 mod json;
+pub mod model;
 mod sarif;
+pub mod schema;
+mod sqlite;
 mod terminal;
 
+pub use code_quality::CodeQualityReporter;
+pub use github::{write_summary, GithubActionsReporter};
 pub use json::JsonReporter;
 pub use sarif::SarifReporter;
+pub use sqlite::SqliteExporter;
 pub use terminal::TerminalReporter;
 
-use crate::analysis::DeadCode;
+use crate::analysis::{sort_findings, DeadCode};
+use crate::graph::SkippedFile;
+use crate::pipeline::TimingsReport;
 use miette::Result;
 use std::path::PathBuf;
 
@@ -17,12 +27,56 @@ pub enum ReportFormat {
     Terminal,
     Json,
     Sarif,
+    GithubActions,
+    CodeQuality,
+}
+
+/// How the terminal report groups findings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GroupBy {
+    #[default]
+    File,
+    Package,
+    Kind,
+    Severity,
+    Owner,
+}
+
+/// How the terminal report orders findings within a group
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortBy {
+    /// By file and line
+    #[default]
+    Line,
+    /// Most confident first
+    Confidence,
+    /// Largest declarations first
+    Size,
 }
 
 /// Reporter for outputting dead code analysis results
 pub struct Reporter {
     format: ReportFormat,
     output_path: Option<PathBuf>,
+    group_by: GroupBy,
+    sort_by: SortBy,
+    summary_only: bool,
+    explain: bool,
+    validate_output: bool,
+    /// Append a markdown summary here, e.g. $GITHUB_STEP_SUMMARY
+    summary_path: Option<PathBuf>,
+    /// Project root, for the relative paths some formats need
+    root: PathBuf,
+    /// Findings matched by the baseline, when one was applied
+    baselined: Option<Vec<DeadCode>>,
+    /// Findings excluded by inline suppressions
+    suppressed: Vec<DeadCode>,
+    /// Findings the terminal already printed while parsing
+    streamed: Vec<DeadCode>,
+    /// Phase timings, for formats that include them (JSON)
+    timings: Option<TimingsReport>,
+    /// Files that could not be parsed, for formats that list them (JSON)
+    skipped_files: Vec<SkippedFile>,
 }
 
 impl Reporter {
@@ -30,22 +84,137 @@ impl Reporter {
         Self {
             format,
             output_path,
+            group_by: GroupBy::default(),
+            sort_by: SortBy::default(),
+            summary_only: false,
+            explain: false,
+            validate_output: false,
+            summary_path: None,
+            root: PathBuf::new(),
+            baselined: None,
+            suppressed: Vec::new(),
+            streamed: Vec::new(),
+            timings: None,
+            skipped_files: Vec::new(),
+        }
+    }
+
+    pub fn with_group_by(mut self, group_by: GroupBy) -> Self {
+        self.group_by = group_by;
+        self
+    }
+
+    pub fn with_sort_by(mut self, sort_by: SortBy) -> Self {
+        self.sort_by = sort_by;
+        self
+    }
+
+    /// Only print the aggregate tables in the terminal report
+    pub fn with_summary_only(mut self, summary_only: bool) -> Self {
+        self.summary_only = summary_only;
+        self
+    }
+
+    /// List the evidence behind each finding in the terminal report
+    pub fn with_explain(mut self, explain: bool) -> Self {
+        self.explain = explain;
+        self
+    }
+
+    /// Validate JSON reports against the bundled schema before writing them
+    pub fn with_output_validation(mut self, validate: bool) -> Self {
+        self.validate_output = validate;
+        self
+    }
+
+    /// Append a markdown summary of the findings to a file
+    pub fn with_summary(mut self, summary_path: Option<PathBuf>) -> Self {
+        self.summary_path = summary_path;
+        self
+    }
+
+    pub fn with_project_root(mut self, root: PathBuf) -> Self {
+        self.root = root;
+        self
+    }
+
+    /// Findings hidden by the baseline, for formats that report them as
+    /// suppressed (SARIF)
+    pub fn with_baselined(mut self, mut baselined: Option<Vec<DeadCode>>) -> Self {
+        if let Some(baselined) = &mut baselined {
+            sort_findings(baselined);
         }
+        self.baselined = baselined;
+        self
+    }
+
+    /// Findings excluded by inline suppressions, for formats that count or
+    /// report them as suppressed (JSON, SARIF)
+    pub fn with_suppressed(mut self, mut suppressed: Vec<DeadCode>) -> Self {
+        sort_findings(&mut suppressed);
+        self.suppressed = suppressed;
+        self
+    }
+
+    /// Findings printed while parsing, left out of the terminal listing
+    pub fn with_streamed(mut self, streamed: Vec<DeadCode>) -> Self {
+        self.streamed = streamed;
+        self
     }
 
-    /// Report the dead code findings
+    /// Phase timings of the run, included in JSON reports
+    pub fn with_timings(mut self, timings: Option<TimingsReport>) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    /// Files left out of the analysis because they could not be parsed
+    pub fn with_skipped_files(mut self, skipped_files: Vec<SkippedFile>) -> Self {
+        self.skipped_files = skipped_files;
+        self
+    }
+
+    /// Report the dead code findings, in report order whatever order they
+    /// were found in (see [`DeadCode::report_order`])
     pub fn report(&self, dead_code: &[DeadCode]) -> Result<()> {
+        let mut dead_code = dead_code.to_vec();
+        sort_findings(&mut dead_code);
+        let dead_code = dead_code.as_slice();
+
+        if let Some(path) = &self.summary_path {
+            write_summary(dead_code, path)?;
+        }
+
         match &self.format {
             ReportFormat::Terminal => {
-                let reporter = TerminalReporter::new();
+                let reporter = TerminalReporter::new()
+                    .with_group_by(self.group_by)
+                    .with_sort_by(self.sort_by)
+                    .with_summary_only(self.summary_only)
+                    .with_explain(self.explain)
+                    .with_streamed(&self.streamed);
                 reporter.report(dead_code)
             }
             ReportFormat::Json => {
-                let reporter = JsonReporter::new(self.output_path.clone());
+                let reporter = JsonReporter::new(self.output_path.clone())
+                    .with_project_root(self.root.clone())
+                    .with_validation(self.validate_output)
+                    .with_suppressed(self.suppressed.len())
+                    .with_timings(self.timings.clone())
+                    .with_skipped_files(self.skipped_files.clone());
                 reporter.report(dead_code)
             }
             ReportFormat::Sarif => {
-                let reporter = SarifReporter::new(self.output_path.clone());
+                let reporter = SarifReporter::new(self.output_path.clone())
+                    .with_project_root(self.root.clone())
+                    .with_baselined(self.baselined.clone())
+                    .with_suppressed(self.suppressed.clone());
+                reporter.report(dead_code)
+            }
+            ReportFormat::GithubActions => GithubActionsReporter::new().report(dead_code),
+            ReportFormat::CodeQuality => {
+                let reporter =
+                    CodeQualityReporter::new(self.output_path.clone(), self.root.clone());
                 reporter.report(dead_code)
             }
         }
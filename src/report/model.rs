@@ -0,0 +1,307 @@
+//! Serde models of the JSON report
+//!
+//! These types are the published shape of `--format json`, described by the
+//! schema in `schemas/`. They are kept apart from the analysis types
+//! ([`DeadCode`], [`crate::graph::Declaration`]) so those can change without
+//! breaking tools that read reports; the report's `version` field
+//! ([`Report::schema_version`]) only changes along with the schema.
+//!
+//! ```ignore
+//! let report: Report = serde_json::from_str(&std::fs::read_to_string("report.json")?)?;
+//! for finding in &report.issues {
+//!     println!("{}:{} {}", finding.location.file, finding.location.line, finding.code);
+//! }
+//! ```
+
+use super::schema::JSON_REPORT_VERSION;
+use crate::analysis::metrics::Metrics as FindingMetrics;
+use crate::analysis::DeadCode;
+use crate::baseline::FindingId;
+use crate::discovery::NormalizedPath;
+use crate::graph::DeclarationSize;
+use crate::pipeline::{PhaseTiming, TimingsReport};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub use crate::analysis::{Confidence, Severity};
+
+/// A JSON report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    /// Version of the report schema, e.g. `1.1`
+    #[serde(rename = "version")]
+    pub schema_version: String,
+    pub total_issues: usize,
+    pub issues: Vec<Finding>,
+    pub summary: Summary,
+    #[serde(default)]
+    pub metrics: Metrics,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<Timings>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_files: Vec<SkippedFile>,
+}
+
+/// A finding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    /// Stable across runs (see [`FindingId`])
+    pub id: FindingId,
+    /// Rule code, e.g. `DC001`
+    pub code: String,
+    pub severity: Severity,
+    pub confidence: Confidence,
+    pub confidence_score: f64,
+    pub runtime_confirmed: bool,
+    pub message: String,
+    #[serde(flatten)]
+    pub location: Location,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppressed_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub risk_tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<Evidence>,
+    pub declaration: Declaration,
+}
+
+/// Where a finding is, with `/` separated paths
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Location {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The declaration a finding is about
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Declaration {
+    pub name: String,
+    /// Display name of the kind, e.g. `function`
+    pub kind: String,
+    pub fully_qualified_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<DeclarationSize>,
+}
+
+/// A piece of evidence behind a finding's confidence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Evidence {
+    pub kind: String,
+    pub description: String,
+    pub weight: f64,
+}
+
+/// Finding counts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Summary {
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+    pub by_confidence: ConfidenceSummary,
+    pub runtime_confirmed_count: usize,
+    /// Findings suppressed inline: excluded ones, plus downgraded ones
+    /// still among the issues
+    pub suppressed: usize,
+}
+
+/// Finding counts by confidence
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfidenceSummary {
+    pub confirmed: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+}
+
+/// What deleting the findings would save
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metrics {
+    pub deletable_lines: usize,
+    pub deletable_bytes: usize,
+    pub dead_methods: usize,
+    pub dead_fields: usize,
+    pub dead_classes: usize,
+    pub dead_methods_by_package: Vec<PackageMethods>,
+    pub estimated_apk_bytes: usize,
+}
+
+/// Dead methods in a package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageMethods {
+    pub package: String,
+    pub methods: usize,
+}
+
+/// Phase timings of the run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    pub phases: Vec<PhaseTiming>,
+    pub total_seconds: f64,
+    pub counters: BTreeMap<String, usize>,
+}
+
+/// A file left out of the analysis
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedFile {
+    pub file: String,
+    pub reason: String,
+}
+
+impl Report {
+    /// Build the report of these findings, with ids relative to `root`
+    pub fn from_dead_code(dead_code: &[DeadCode], root: &Path) -> Self {
+        let issues: Vec<Finding> = dead_code
+            .iter()
+            .zip(FindingId::assign(dead_code, root))
+            .map(|(dc, id)| Finding::from_dead_code(dc, id))
+            .collect();
+
+        let mut summary = Summary::default();
+        for finding in &issues {
+            match finding.severity {
+                Severity::Error => summary.errors += 1,
+                Severity::Warning => summary.warnings += 1,
+                Severity::Info => summary.infos += 1,
+            }
+            let by_confidence = &mut summary.by_confidence;
+            match finding.confidence {
+                Confidence::Confirmed => by_confidence.confirmed += 1,
+                Confidence::High => by_confidence.high += 1,
+                Confidence::Medium => by_confidence.medium += 1,
+                Confidence::Low => by_confidence.low += 1,
+            }
+            if finding.runtime_confirmed {
+                summary.runtime_confirmed_count += 1;
+            }
+            if finding.suppressed_by.is_some() {
+                summary.suppressed += 1;
+            }
+        }
+
+        Self {
+            schema_version: JSON_REPORT_VERSION.to_string(),
+            total_issues: issues.len(),
+            issues,
+            summary,
+            metrics: FindingMetrics::compute(dead_code).into(),
+            timings: None,
+            skipped_files: Vec::new(),
+        }
+    }
+}
+
+impl Finding {
+    /// Model of a finding, with its id among the report's findings
+    pub fn from_dead_code(dc: &DeadCode, id: FindingId) -> Self {
+        let location = &dc.declaration.location;
+        Self {
+            id,
+            code: dc.issue.code().to_string(),
+            severity: dc.severity,
+            confidence: dc.confidence,
+            confidence_score: dc.confidence_score(),
+            runtime_confirmed: dc.runtime_confirmed,
+            message: dc.message.clone(),
+            location: Location {
+                file: NormalizedPath::new(&location.file).to_string(),
+                line: location.line,
+                column: location.column,
+            },
+            owner: dc.owner.clone(),
+            root: dc.root.clone(),
+            suppressed_by: dc.suppressed_by.clone(),
+            risk_tags: dc.risk_tags.clone(),
+            evidence: dc
+                .evidence
+                .iter()
+                .map(|e| Evidence {
+                    kind: e.kind.as_str().to_string(),
+                    description: e.description.clone(),
+                    weight: e.weight(),
+                })
+                .collect(),
+            declaration: Declaration {
+                name: dc.declaration.name.clone(),
+                kind: dc.declaration.kind.display_name().to_string(),
+                fully_qualified_name: dc.declaration.fully_qualified_name.clone(),
+                size: dc.declaration.size,
+            },
+        }
+    }
+}
+
+impl From<FindingMetrics> for Metrics {
+    fn from(metrics: FindingMetrics) -> Self {
+        Self {
+            deletable_lines: metrics.deletable_lines,
+            deletable_bytes: metrics.deletable_bytes,
+            dead_methods: metrics.dead_methods,
+            dead_fields: metrics.dead_fields,
+            dead_classes: metrics.dead_classes,
+            dead_methods_by_package: metrics
+                .methods_by_package
+                .into_iter()
+                .map(|(package, methods)| PackageMethods { package, methods })
+                .collect(),
+            estimated_apk_bytes: metrics.estimated_apk_bytes,
+        }
+    }
+}
+
+impl From<TimingsReport> for Timings {
+    fn from(timings: TimingsReport) -> Self {
+        Self {
+            phases: timings.phases,
+            total_seconds: timings.total_seconds,
+            counters: timings
+                .counters
+                .into_iter()
+                .map(|(name, count)| (name.to_string(), count))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{DeclarationId, DeclarationKind, Language};
+    use std::path::PathBuf;
+
+    fn dead_code(name: &str, line: usize) -> DeadCode {
+        let path = PathBuf::from("src/Test.kt");
+        let decl = crate::graph::Declaration::new(
+            DeclarationId::new(path.clone(), line * 10, line * 10 + 5),
+            name.to_string(),
+            DeclarationKind::Function,
+            crate::graph::Location::new(path, line, 5, line * 10, line * 10 + 5),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, DeadCodeIssue::Unreferenced)
+    }
+
+    #[test]
+    fn test_report_round_trips() {
+        let findings = vec![
+            dead_code("unusedHelper", 3),
+            dead_code("staleCallback", 8).with_runtime_confirmed(true),
+        ];
+        let report = Report::from_dead_code(&findings, Path::new(""));
+        assert_eq!(report.schema_version, JSON_REPORT_VERSION);
+        assert_eq!(report.summary.runtime_confirmed_count, 1);
+        assert_eq!(report.issues[0].location.file, "src/Test.kt");
+
+        let json = serde_json::to_string(&report).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["version"], JSON_REPORT_VERSION);
+        assert_eq!(value["issues"][0]["line"], 3);
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
+    }
+}
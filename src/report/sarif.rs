@@ -1,20 +1,64 @@
-use crate::analysis::{DeadCode, Severity};
+use crate::analysis::metrics::Metrics;
+use crate::analysis::{DeadCode, DeadCodeIssue, Severity};
+use crate::baseline::FindingId;
+use crate::discovery::NormalizedPath;
 use miette::{IntoDiagnostic, Result};
 use serde::Serialize;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Where the rule documentation lives; each rule links to its section
+const RULES_DOC: &str = concat!(env!("CARGO_PKG_REPOSITORY"), "/blob/main/docs/rules.md");
+
+/// Key of the finding id among a result's fingerprints
+const FINGERPRINT_KEY: &str = "searchdeadcode/v1";
 
 /// SARIF reporter for CI/CD integration (GitHub, Azure DevOps, etc.)
 pub struct SarifReporter {
     output_path: Option<PathBuf>,
+    /// Findings matched by the baseline, when one was applied
+    baselined: Option<Vec<DeadCode>>,
+    /// Findings excluded by inline suppressions
+    suppressed: Vec<DeadCode>,
+    /// Project root, which finding ids are relative to
+    root: PathBuf,
 }
 
 impl SarifReporter {
     pub fn new(output_path: Option<PathBuf>) -> Self {
-        Self { output_path }
+        Self {
+            output_path,
+            baselined: None,
+            suppressed: Vec::new(),
+            root: PathBuf::new(),
+        }
+    }
+
+    /// Mark findings as new or unchanged against a baseline, and report the
+    /// baselined ones as suppressed
+    pub fn with_baselined(mut self, baselined: Option<Vec<DeadCode>>) -> Self {
+        self.baselined = baselined;
+        self
+    }
+
+    /// Report findings excluded by inline suppressions as suppressed in source
+    pub fn with_suppressed(mut self, suppressed: Vec<DeadCode>) -> Self {
+        self.suppressed = suppressed;
+        self
+    }
+
+    pub fn with_project_root(mut self, root: PathBuf) -> Self {
+        self.root = root;
+        self
     }
 
     pub fn report(&self, dead_code: &[DeadCode]) -> Result<()> {
-        let sarif = SarifReport::from_dead_code(dead_code);
+        let sarif = SarifReport::from_dead_code(
+            dead_code,
+            self.baselined.as_deref(),
+            &self.suppressed,
+            &self.root,
+        );
         let json = serde_json::to_string_pretty(&sarif).into_diagnostic()?;
 
         if let Some(path) = &self.output_path {
@@ -41,6 +85,17 @@ struct SarifReport {
 struct SarifRun {
     tool: SarifTool,
     results: Vec<SarifResult>,
+    properties: SarifRunProperties,
+}
+
+/// Estimated savings of the run's findings, excluding baselined ones
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRunProperties {
+    deletable_lines: usize,
+    deletable_bytes: usize,
+    dead_methods: usize,
+    estimated_apk_bytes: usize,
 }
 
 #[derive(Serialize)]
@@ -63,6 +118,10 @@ struct SarifRule {
     name: &'static str,
     #[serde(rename = "shortDescription")]
     short_description: SarifMessage,
+    #[serde(rename = "fullDescription")]
+    full_description: SarifMessage,
+    #[serde(rename = "helpUri")]
+    help_uri: String,
     #[serde(rename = "defaultConfiguration")]
     default_configuration: SarifConfiguration,
 }
@@ -79,6 +138,38 @@ struct SarifResult {
     level: &'static str,
     message: SarifMessage,
     locations: Vec<SarifLocation>,
+    /// Stable identity of the result across runs
+    fingerprints: BTreeMap<&'static str, FindingId>,
+    /// `new` or `unchanged`, when a baseline was applied
+    #[serde(rename = "baselineState", skip_serializing_if = "Option::is_none")]
+    baseline_state: Option<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suppressions: Vec<SarifSuppression>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<SarifResultProperties>,
+}
+
+/// Why a result is not shown as an open alert
+#[derive(Serialize)]
+struct SarifSuppression {
+    /// `external`: recorded outside the source (baseline, keep rules);
+    /// `inSource`: a comment or annotation in the code
+    kind: &'static str,
+    justification: String,
+}
+
+#[derive(Serialize)]
+struct SarifResultProperties {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    root: Option<String>,
+    /// SARIF property bag convention for classifying results
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    /// Branch points of the declaration, for prioritizing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branches: Option<usize>,
 }
 
 #[derive(Serialize)]
@@ -110,98 +201,52 @@ struct SarifRegion {
     start_line: usize,
     #[serde(rename = "startColumn")]
     start_column: usize,
+    #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
+    end_line: Option<usize>,
 }
 
 impl SarifReport {
-    fn from_dead_code(dead_code: &[DeadCode]) -> Self {
-        let rules = vec![
-            SarifRule {
-                id: "DC001",
-                name: "unreferenced-declaration",
-                short_description: SarifMessage {
-                    text: "Declaration is never referenced".to_string(),
-                },
-                default_configuration: SarifConfiguration { level: "warning" },
-            },
-            SarifRule {
-                id: "DC002",
-                name: "assign-only-property",
-                short_description: SarifMessage {
-                    text: "Property is assigned but never read".to_string(),
-                },
-                default_configuration: SarifConfiguration { level: "warning" },
-            },
-            SarifRule {
-                id: "DC003",
-                name: "unused-parameter",
-                short_description: SarifMessage {
-                    text: "Parameter is never used".to_string(),
-                },
-                default_configuration: SarifConfiguration { level: "note" },
-            },
-            SarifRule {
-                id: "DC004",
-                name: "unused-import",
-                short_description: SarifMessage {
-                    text: "Import is never used".to_string(),
-                },
-                default_configuration: SarifConfiguration { level: "note" },
-            },
-            SarifRule {
-                id: "DC005",
-                name: "unused-enum-case",
-                short_description: SarifMessage {
-                    text: "Enum case is never used".to_string(),
-                },
-                default_configuration: SarifConfiguration { level: "warning" },
-            },
-            SarifRule {
-                id: "DC006",
-                name: "redundant-public",
-                short_description: SarifMessage {
-                    text: "Public visibility is unnecessary".to_string(),
-                },
-                default_configuration: SarifConfiguration { level: "note" },
-            },
-            SarifRule {
-                id: "DC007",
-                name: "dead-branch",
-                short_description: SarifMessage {
-                    text: "Code branch can never be executed".to_string(),
-                },
-                default_configuration: SarifConfiguration { level: "warning" },
-            },
-        ];
+    fn from_dead_code(
+        dead_code: &[DeadCode],
+        baselined: Option<&[DeadCode]>,
+        suppressed: &[DeadCode],
+        root: &Path,
+    ) -> Self {
+        let rules = DeadCodeIssue::ALL
+            .iter()
+            .map(|&issue| rule(issue))
+            .collect();
 
-        let results: Vec<SarifResult> = dead_code
+        let baseline_state = |state| baselined.is_some().then_some(state);
+        let ids = |findings: &[DeadCode]| FindingId::assign(findings, root);
+        let mut results: Vec<SarifResult> = dead_code
             .iter()
-            .map(|dc| {
-                let level = match dc.severity {
-                    Severity::Error => "error",
-                    Severity::Warning => "warning",
-                    Severity::Info => "note",
-                };
-
-                SarifResult {
-                    rule_id: dc.issue.code(),
-                    level,
-                    message: SarifMessage {
-                        text: dc.message.clone(),
-                    },
-                    locations: vec![SarifLocation {
-                        physical_location: SarifPhysicalLocation {
-                            artifact_location: SarifArtifactLocation {
-                                uri: dc.declaration.location.file.to_string_lossy().to_string(),
-                            },
-                            region: SarifRegion {
-                                start_line: dc.declaration.location.line,
-                                start_column: dc.declaration.location.column,
-                            },
-                        },
-                    }],
-                }
-            })
+            .zip(ids(dead_code))
+            .map(|(dc, id)| result(dc, id, baseline_state("new")))
             .collect();
+        let baselined = baselined.unwrap_or_default();
+        for (dc, id) in baselined.iter().zip(ids(baselined)) {
+            let mut unchanged = result(dc, id, baseline_state("unchanged"));
+            unchanged.suppressions.push(SarifSuppression {
+                kind: "external",
+                justification: "Recorded in the baseline".to_string(),
+            });
+            results.push(unchanged);
+        }
+        for (dc, id) in suppressed.iter().zip(ids(suppressed)) {
+            let mut in_source = result(dc, id, None);
+            in_source.suppressions.push(SarifSuppression {
+                kind: "inSource",
+                justification: format!(
+                    "Suppressed by {}",
+                    dc.suppressed_by
+                        .as_deref()
+                        .unwrap_or("an inline suppression")
+                ),
+            });
+            results.push(in_source);
+        }
+        let metrics = Metrics::compute(dead_code);
 
         SarifReport {
             schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
@@ -211,12 +256,312 @@ impl SarifReport {
                     driver: SarifDriver {
                         name: "searchdeadcode",
                         version: env!("CARGO_PKG_VERSION"),
-                        information_uri: "https://github.com/user/searchdeadcode",
+                        information_uri: env!("CARGO_PKG_REPOSITORY"),
                         rules,
                     },
                 },
                 results,
+                properties: SarifRunProperties {
+                    deletable_lines: metrics.deletable_lines,
+                    deletable_bytes: metrics.deletable_bytes,
+                    dead_methods: metrics.dead_methods,
+                    estimated_apk_bytes: metrics.estimated_apk_bytes,
+                },
             }],
         }
     }
 }
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+/// The rule reporting an issue type, documented in `docs/rules.md`
+fn rule(issue: DeadCodeIssue) -> SarifRule {
+    let (name, short, full) = match issue {
+        DeadCodeIssue::Unreferenced => (
+            "unreferenced-declaration",
+            "Declaration is never referenced",
+            "The declaration cannot be reached from any entry point (Android components, \
+             main functions, tests, retained patterns), so removing it does not change \
+             the behavior of the app.",
+        ),
+        DeadCodeIssue::AssignOnly => (
+            "assign-only-property",
+            "Property is assigned but never read",
+            "The property is written but its value is never read, so the assignments and \
+             the property can be removed.",
+        ),
+        DeadCodeIssue::UnusedParameter => (
+            "unused-parameter",
+            "Parameter is never used",
+            "The parameter is declared but never used in the body of its function. It \
+             can be removed together with the arguments passed for it, or renamed to `_` \
+             in a lambda.",
+        ),
+        DeadCodeIssue::UnusedImport => (
+            "unused-import",
+            "Import is never used",
+            "No name brought in by the import is used in the file.",
+        ),
+        DeadCodeIssue::UnusedEnumCase => (
+            "unused-enum-case",
+            "Enum case is never used",
+            "The enum constant is never referenced, and the enum is never iterated \
+             (`values()`, `entries`) or looked up by name (`valueOf`).",
+        ),
+        DeadCodeIssue::RedundantPublic => (
+            "redundant-public",
+            "Public visibility is unnecessary",
+            "The declaration is public but only used in its own file, class or module, \
+             so it can be made private or internal.",
+        ),
+        DeadCodeIssue::DeadBranch => (
+            "dead-branch",
+            "Code branch can never be executed",
+            "The condition guarding the branch is constant (BuildConfig fields, `const val` \
+             flags), so the branch never runs.",
+        ),
+        DeadCodeIssue::UnusedSealedVariant => (
+            "unused-sealed-variant",
+            "Sealed class variant is never instantiated",
+            "No instance of the sealed class variant is ever created, so the branches \
+             handling it are dead as well.",
+        ),
+        DeadCodeIssue::RedundantOverride => (
+            "redundant-override",
+            "Override only calls super",
+            "The override adds no behavior to the member it overrides and can be removed.",
+        ),
+        DeadCodeIssue::WriteOnlyPreference => (
+            "write-only-preference",
+            "SharedPreferences key is written but never read",
+            "The app stores a value under the SharedPreferences key but never reads it back.",
+        ),
+        DeadCodeIssue::WriteOnlyDao => (
+            "write-only-dao",
+            "Room DAO writes data that is never queried",
+            "The DAO inserts or updates data but has no query reading it back.",
+        ),
+        DeadCodeIssue::UnusedTypeAlias => (
+            "unused-type-alias",
+            "Type alias is never used",
+            "The Kotlin typealias is declared but never used.",
+        ),
+        DeadCodeIssue::OnlyUsedInTests => (
+            "only-used-in-tests",
+            "Declaration is only used from tests",
+            "The production declaration is only referenced from test source sets, so it \
+             is dead in the shipped app.",
+        ),
+        DeadCodeIssue::CustomRule => (
+            "custom-rule",
+            "Declaration matches a user-defined rule",
+            "The declaration matches a rule from .searchdeadcode-rules.toml or --rules.",
+        ),
+        DeadCodeIssue::DeadFile => (
+            "dead-file",
+            "File is entirely unused",
+            "No declaration in the file is used, so the whole file can be deleted.",
+        ),
+        DeadCodeIssue::VarCouldBeVal => (
+            "var-could-be-val",
+            "Property is never reassigned",
+            "The property is declared with `var` but never written after its \
+             initialization, so it can be a `val`.",
+        ),
+        DeadCodeIssue::UnusedGetter => (
+            "unused-getter",
+            "Custom getter is never used",
+            "The property has a custom getter but is only ever written, so the getter \
+             never runs.",
+        ),
+        DeadCodeIssue::UnusedSetter => (
+            "unused-setter",
+            "Custom setter is never used",
+            "The property has a custom setter but is only ever read, so the setter never \
+             runs.",
+        ),
+        DeadCodeIssue::DeadDocLink => (
+            "dead-doc-link",
+            "Documentation links to dead code",
+            "A KDoc or Javadoc comment links to a declaration that is dead, so the link \
+             breaks once the declaration is deleted.",
+        ),
+        DeadCodeIssue::UnusedComposeState => (
+            "unused-compose-state",
+            "Compose state is never read",
+            "The composable remembers state that is assigned but never read, so its \
+             writes schedule recompositions nothing depends on.",
+        ),
+        DeadCodeIssue::UnusedComposableDefault => (
+            "unused-composable-default",
+            "Composable default is never overridden",
+            "Every call of the composable leaves the parameter at its default value, so \
+             the parameter can become a local.",
+        ),
+        DeadCodeIssue::OnlyUsedInPreviews => (
+            "only-used-in-previews",
+            "Composable is only used from previews or tests",
+            "The composable is only called from @Preview functions or test source sets, \
+             which the shipped app never runs.",
+        ),
+        DeadCodeIssue::UninvokedFunctionParameter => (
+            "uninvoked-function-parameter",
+            "Function parameter is never invoked",
+            "The function neither calls the function-typed parameter nor passes it on, so \
+             the lambdas its callers pass never run.",
+        ),
+        DeadCodeIssue::EffectivelyDeadObject => (
+            "effectively-dead-object",
+            "Object is only used as a namespace",
+            "The object or class is only imported or used to qualify names, and none of its \
+             members is used, so it can be removed as a whole.",
+        ),
+    };
+
+    SarifRule {
+        id: issue.code(),
+        name,
+        short_description: SarifMessage {
+            text: short.to_string(),
+        },
+        full_description: SarifMessage {
+            text: full.to_string(),
+        },
+        help_uri: format!("{}#{}", RULES_DOC, issue.code().to_lowercase()),
+        default_configuration: SarifConfiguration {
+            level: level(issue.default_severity()),
+        },
+    }
+}
+
+fn result(dc: &DeadCode, id: FindingId, baseline_state: Option<&'static str>) -> SarifResult {
+    // Findings of declarations only kept by a keep rule are not alerts
+    let suppressions = dc
+        .kept_by
+        .iter()
+        .map(|rule| SarifSuppression {
+            kind: "external",
+            justification: format!("Kept by {}", rule),
+        })
+        .collect();
+
+    SarifResult {
+        rule_id: dc.issue.code(),
+        level: level(dc.severity),
+        message: SarifMessage {
+            text: dc.message.clone(),
+        },
+        locations: vec![SarifLocation {
+            physical_location: SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation {
+                    uri: NormalizedPath::new(&dc.declaration.location.file).to_string(),
+                },
+                region: SarifRegion {
+                    start_line: dc.declaration.location.line,
+                    start_column: dc.declaration.location.column,
+                    end_line: dc
+                        .declaration
+                        .size
+                        .map(|size| dc.declaration.location.line + size.lines - 1),
+                },
+            },
+        }],
+        fingerprints: BTreeMap::from([(FINGERPRINT_KEY, id)]),
+        baseline_state,
+        suppressions,
+        properties: (dc.owner.is_some()
+            || dc.root.is_some()
+            || !dc.risk_tags.is_empty()
+            || dc.declaration.size.is_some())
+        .then(|| SarifResultProperties {
+            owner: dc.owner.clone(),
+            root: dc.root.clone(),
+            tags: dc.risk_tags.clone(),
+            branches: dc.declaration.size.map(|size| size.branches),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+
+    fn make_dead_code(name: &str, line: usize) -> DeadCode {
+        let path = PathBuf::from("app/src/Test.kt");
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), line * 10, line * 10 + 5),
+            name.to_string(),
+            DeclarationKind::Function,
+            Location::new(path, line, 5, line * 10, line * 10 + 5),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, DeadCodeIssue::Unreferenced)
+    }
+
+    #[test]
+    fn test_rules() {
+        let sarif =
+            serde_json::to_value(SarifReport::from_dead_code(&[], None, &[], Path::new("")))
+                .unwrap();
+        let rules = sarif["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
+        assert_eq!(rules.len(), DeadCodeIssue::ALL.len());
+        assert_eq!(rules[2]["id"], "DC003");
+        assert_eq!(rules[2]["defaultConfiguration"]["level"], "note");
+        assert!(rules[2]["fullDescription"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("renamed to `_`"));
+        assert!(rules[17]["helpUri"]
+            .as_str()
+            .unwrap()
+            .ends_with("/blob/main/docs/rules.md#dc018"));
+    }
+
+    #[test]
+    fn test_baseline_state_and_suppressions() {
+        let kept = make_dead_code("onReceive", 9).with_kept_by(Some("-keep class *".to_string()));
+        let dead_code = vec![make_dead_code("helper", 3), kept];
+        let baselined = vec![make_dead_code("legacy", 20)];
+        let mut ignored = make_dead_code("debugOnly", 30);
+        ignored.suppressed_by = Some("// searchdeadcode:ignore".to_string());
+
+        let sarif =
+            SarifReport::from_dead_code(&dead_code, Some(&baselined), &[ignored], Path::new("app"));
+        let results = serde_json::to_value(&sarif.runs[0].results).unwrap();
+        assert_eq!(results[0]["baselineState"], "new");
+        let id = results[0]["fingerprints"][FINGERPRINT_KEY]
+            .as_str()
+            .unwrap();
+        assert!(id.starts_with("DC001-"));
+        assert_ne!(results[1]["fingerprints"][FINGERPRINT_KEY], id);
+        assert!(results[0].get("suppressions").is_none());
+        assert_eq!(results[1]["suppressions"][0]["kind"], "external");
+        assert_eq!(
+            results[1]["suppressions"][0]["justification"],
+            "Kept by -keep class *"
+        );
+        assert_eq!(results[2]["baselineState"], "unchanged");
+        assert_eq!(
+            results[2]["suppressions"][0]["justification"],
+            "Recorded in the baseline"
+        );
+        assert_eq!(results[3]["suppressions"][0]["kind"], "inSource");
+        assert_eq!(
+            results[3]["suppressions"][0]["justification"],
+            "Suppressed by // searchdeadcode:ignore"
+        );
+
+        // Without a baseline, results have no baseline state
+        let sarif = SarifReport::from_dead_code(&dead_code, None, &[], Path::new("app"));
+        assert!(sarif.runs[0].results[0].baseline_state.is_none());
+    }
+}
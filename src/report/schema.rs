@@ -0,0 +1,211 @@
+//! JSON Schema for the JSON report
+//!
+//! The schema is published in `schemas/` and bundled into the binary so
+//! `--validate-output` can check a report before it is written. Only the
+//! subset of JSON Schema (draft-07) used by our own schemas is supported:
+//! `type`, `const`, `enum`, `pattern`, `minimum`/`maximum`, `required`,
+//! `properties`, `additionalProperties: false`, `items` and local `$ref`s.
+
+use regex::Regex;
+use serde_json::Value;
+
+/// Version of the JSON report format, bumped on breaking changes
+pub const JSON_REPORT_VERSION: &str = "1.1";
+
+/// Schema of the current JSON report version
+pub const JSON_REPORT_SCHEMA: &str = include_str!("../../schemas/report-1.1.schema.json");
+
+/// Validate a JSON report against the bundled schema
+///
+/// Returns one message per violation, prefixed with the JSON pointer of the
+/// offending value.
+pub fn validate_report(report: &Value) -> Vec<String> {
+    let schema: Value =
+        serde_json::from_str(JSON_REPORT_SCHEMA).expect("bundled report schema is valid JSON");
+    validate(report, &schema)
+}
+
+/// Validate an instance against a schema
+pub fn validate(instance: &Value, schema: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_node(instance, schema, schema, "", &mut errors);
+    errors
+}
+
+fn validate_node(
+    instance: &Value,
+    schema: &Value,
+    root: &Value,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    let location = if path.is_empty() { "/" } else { path };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        match resolve_ref(root, reference) {
+            Some(target) => validate_node(instance, target, root, path, errors),
+            None => errors.push(format!("{}: unresolved $ref '{}'", location, reference)),
+        }
+        return;
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let matches = match expected {
+            Value::String(t) => has_type(instance, t),
+            Value::Array(types) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|t| has_type(instance, t)),
+            _ => true,
+        };
+        if !matches {
+            errors.push(format!("{}: expected type {}", location, expected));
+            return;
+        }
+    }
+
+    if let Some(expected) = schema.get("const") {
+        if instance != expected {
+            errors.push(format!(
+                "{}: expected {}, found {}",
+                location, expected, instance
+            ));
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(instance) {
+            errors.push(format!(
+                "{}: {} is not one of {:?}",
+                location, instance, allowed
+            ));
+        }
+    }
+
+    if let (Some(pattern), Some(value)) = (
+        schema.get("pattern").and_then(Value::as_str),
+        instance.as_str(),
+    ) {
+        if !Regex::new(pattern).is_ok_and(|re| re.is_match(value)) {
+            errors.push(format!(
+                "{}: '{}' does not match {}",
+                location, value, pattern
+            ));
+        }
+    }
+
+    if let Some(number) = instance.as_f64() {
+        if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+            if number < minimum {
+                errors.push(format!("{}: {} is less than {}", location, number, minimum));
+            }
+        }
+        if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+            if number > maximum {
+                errors.push(format!(
+                    "{}: {} is greater than {}",
+                    location, number, maximum
+                ));
+            }
+        }
+    }
+
+    if let Some(object) = instance.as_object() {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    errors.push(format!("{}: missing required property '{}'", location, key));
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+
+        for (key, value) in object {
+            let child_path = format!("{}/{}", path, key);
+            match properties.and_then(|p| p.get(key)) {
+                Some(property_schema) => {
+                    validate_node(value, property_schema, root, &child_path, errors)
+                }
+                None if closed => {
+                    errors.push(format!("{}: unexpected property '{}'", location, key))
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), instance.as_array()) {
+        for (index, item) in array.iter().enumerate() {
+            validate_node(item, items, root, &format!("{}/{}", path, index), errors);
+        }
+    }
+}
+
+fn has_type(instance: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => instance.is_object(),
+        "array" => instance.is_array(),
+        "string" => instance.is_string(),
+        "boolean" => instance.is_boolean(),
+        "null" => instance.is_null(),
+        "number" => instance.is_number(),
+        "integer" => instance.is_i64() || instance.is_u64(),
+        _ => true,
+    }
+}
+
+/// Resolve a local reference such as "#/definitions/issue"
+fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    let pointer = reference.strip_prefix('#')?;
+    root.pointer(pointer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bundled_schema_matches_version() {
+        let schema: Value = serde_json::from_str(JSON_REPORT_SCHEMA).unwrap();
+        assert_eq!(
+            schema.pointer("/properties/version/const"),
+            Some(&json!(JSON_REPORT_VERSION))
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_violations() {
+        let schema = json!({
+            "type": "object",
+            "required": ["name", "count"],
+            "additionalProperties": false,
+            "properties": {
+                "name": { "type": "string", "pattern": "^DC[0-9]{3}$" },
+                "count": { "type": "integer", "minimum": 0 },
+                "items": { "type": "array", "items": { "$ref": "#/definitions/item" } }
+            },
+            "definitions": {
+                "item": { "enum": ["a", "b"] }
+            }
+        });
+
+        let valid = json!({ "name": "DC001", "count": 2, "items": ["a"] });
+        assert!(validate(&valid, &schema).is_empty());
+
+        let errors = validate(
+            &json!({ "name": "X1", "count": -1, "items": ["c"], "extra": true }),
+            &schema,
+        );
+        assert_eq!(errors.len(), 4);
+        assert!(errors.iter().any(|e| e.starts_with("/items/0:")));
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("unexpected property 'extra'")));
+    }
+}
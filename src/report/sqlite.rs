@@ -0,0 +1,410 @@
+//! SQLite export of the analysis, for ad-hoc SQL over the findings
+//!
+//! `--output-db findings.sqlite` writes the declarations, the references
+//! between them, the entry points and the findings into the tables of
+//! [`SCHEMA`]. For example, dead public methods of the `:feature` modules
+//! longer than 50 lines:
+//!
+//! ```sql
+//! SELECT d.fully_qualified_name, d.module, d.lines
+//! FROM findings f JOIN declarations d ON d.id = f.declaration_id
+//! WHERE f.code = 'DC001' AND d.kind = 'method' AND d.visibility = 'public'
+//!   AND d.module LIKE ':feature%' AND d.lines > 50;
+//! ```
+//!
+//! An existing file is replaced. [`SCHEMA_VERSION`] is stored in the
+//! `metadata` table and bumped on incompatible changes.
+
+use crate::analysis::size::FindingSize;
+use crate::analysis::{DeadCode, EntryPointCategory};
+use crate::discovery::NormalizedPath;
+use crate::graph::{Declaration, DeclarationId, Graph, Visibility};
+use miette::{IntoDiagnostic, Result};
+use rusqlite::{params, Connection};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Version of the database layout, in the `metadata` table
+pub const SCHEMA_VERSION: &str = "1";
+
+/// Tables written by the export
+pub const SCHEMA: &str = "
+-- key/value pairs: schema_version, tool_version, root
+CREATE TABLE metadata (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+-- every declaration in the graph, including live ones
+CREATE TABLE declarations (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    -- class, method, property, ... as in the reports
+    kind TEXT NOT NULL,
+    fully_qualified_name TEXT,
+    -- package of the enclosing top-level declaration
+    package TEXT,
+    -- Gradle path of the module, e.g. :feature:home (NULL outside modules)
+    module TEXT,
+    -- public, private, protected, internal or package-private
+    visibility TEXT NOT NULL,
+    language TEXT NOT NULL,
+    -- relative to the project root when inside it
+    file TEXT NOT NULL,
+    line INTEGER NOT NULL,
+    column INTEGER NOT NULL,
+    -- source lines the declaration spans
+    lines INTEGER NOT NULL,
+    parent_id INTEGER REFERENCES declarations(id)
+);
+
+-- resolved references; a declaration referencing another many times has
+-- one row per site
+CREATE TABLE refs (
+    from_id INTEGER NOT NULL REFERENCES declarations(id),
+    to_id INTEGER NOT NULL REFERENCES declarations(id),
+    -- call, read, write, type reference, ...
+    kind TEXT NOT NULL,
+    file TEXT NOT NULL,
+    line INTEGER NOT NULL
+);
+
+-- declarations the analysis starts from, with the reason when known
+CREATE TABLE entry_points (
+    declaration_id INTEGER PRIMARY KEY REFERENCES declarations(id),
+    reason TEXT
+);
+
+-- the reported findings, after filtering and baselining
+CREATE TABLE findings (
+    id INTEGER PRIMARY KEY,
+    declaration_id INTEGER NOT NULL REFERENCES declarations(id),
+    code TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    confidence TEXT NOT NULL,
+    confidence_score REAL NOT NULL,
+    runtime_confirmed INTEGER NOT NULL,
+    message TEXT NOT NULL,
+    owner TEXT,
+    root TEXT,
+    suppressed_by TEXT
+);
+
+CREATE INDEX refs_to ON refs(to_id);
+CREATE INDEX findings_declaration ON findings(declaration_id);
+";
+
+/// Writes the analysis into a SQLite database
+pub struct SqliteExporter {
+    path: PathBuf,
+    /// Project root, to store file paths relative to it
+    root: PathBuf,
+}
+
+impl SqliteExporter {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            root: PathBuf::new(),
+        }
+    }
+
+    pub fn with_project_root(mut self, root: PathBuf) -> Self {
+        self.root = root;
+        self
+    }
+
+    /// Write the graph, entry points and findings, replacing the file
+    pub fn export(
+        &self,
+        graph: &Graph,
+        entry_points: &HashSet<DeclarationId>,
+        categories: &HashMap<DeclarationId, EntryPointCategory>,
+        dead_code: &[DeadCode],
+    ) -> Result<()> {
+        if self.path.exists() {
+            std::fs::remove_file(&self.path).into_diagnostic()?;
+        }
+        let mut conn = Connection::open(&self.path).into_diagnostic()?;
+        self.write(&mut conn, graph, entry_points, categories, dead_code)
+            .into_diagnostic()?;
+        println!("Database written to: {}", self.path.display());
+        Ok(())
+    }
+
+    fn write(
+        &self,
+        conn: &mut Connection,
+        graph: &Graph,
+        entry_points: &HashSet<DeclarationId>,
+        categories: &HashMap<DeclarationId, EntryPointCategory>,
+        dead_code: &[DeadCode],
+    ) -> rusqlite::Result<()> {
+        let tx = conn.transaction()?;
+        tx.execute_batch(SCHEMA)?;
+
+        {
+            let mut metadata = tx.prepare("INSERT INTO metadata (key, value) VALUES (?1, ?2)")?;
+            metadata.execute(params!["schema_version", SCHEMA_VERSION])?;
+            metadata.execute(params!["tool_version", env!("CARGO_PKG_VERSION")])?;
+            metadata.execute(params!["root", self.root.display().to_string()])?;
+        }
+
+        // Rows are numbered in a stable order so parents can be linked
+        let mut declarations: Vec<&Declaration> = graph.declarations().collect();
+        declarations.sort_by(|a, b| {
+            a.location
+                .file
+                .cmp(&b.location.file)
+                .then(a.location.start_byte.cmp(&b.location.start_byte))
+                .then(b.location.end_byte.cmp(&a.location.end_byte))
+        });
+        let rows: HashMap<&DeclarationId, i64> = declarations
+            .iter()
+            .enumerate()
+            .map(|(i, decl)| (&decl.id, i as i64 + 1))
+            .collect();
+
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO declarations (id, name, kind, fully_qualified_name, package, module,
+                     visibility, language, file, line, column, lines, parent_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            )?;
+            let mut sources: HashMap<&PathBuf, Option<String>> = HashMap::new();
+            for decl in &declarations {
+                let location = &decl.location;
+                let lines = match decl.size {
+                    Some(size) => size.lines,
+                    None => sources
+                        .entry(&location.file)
+                        .or_insert_with(|| std::fs::read_to_string(&location.file).ok())
+                        .as_deref()
+                        .map_or(1, |source| FindingSize::of(location, source).lines),
+                };
+                insert.execute(params![
+                    rows[&decl.id],
+                    decl.name,
+                    decl.kind.display_name(),
+                    decl.fully_qualified_name,
+                    package_of(decl, graph),
+                    gradle_module(&location.file, &self.root),
+                    visibility_name(decl),
+                    format!("{:?}", decl.language).to_lowercase(),
+                    self.relative(&location.file),
+                    location.line as i64,
+                    location.column as i64,
+                    lines as i64,
+                    decl.parent.as_ref().and_then(|parent| rows.get(parent)),
+                ])?;
+            }
+        }
+
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO refs (from_id, to_id, kind, file, line) VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            let inner = graph.inner();
+            for edge in inner.edge_indices() {
+                let (Some((from, to)), Some(reference)) =
+                    (inner.edge_endpoints(edge), inner.edge_weight(edge))
+                else {
+                    continue;
+                };
+                let (Some(from), Some(to)) = (
+                    inner.node_weight(from).and_then(|id| rows.get(id)),
+                    inner.node_weight(to).and_then(|id| rows.get(id)),
+                ) else {
+                    continue;
+                };
+                insert.execute(params![
+                    from,
+                    to,
+                    reference.kind.display_name(),
+                    self.relative(&reference.location.file),
+                    reference.location.line as i64,
+                ])?;
+            }
+        }
+
+        {
+            let mut insert =
+                tx.prepare("INSERT INTO entry_points (declaration_id, reason) VALUES (?1, ?2)")?;
+            for id in entry_points {
+                let Some(row) = rows.get(id) else {
+                    continue;
+                };
+                insert.execute(params![row, categories.get(id).map(|c| c.label())])?;
+            }
+        }
+
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO findings (declaration_id, code, severity, confidence,
+                     confidence_score, runtime_confirmed, message, owner, root, suppressed_by)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?;
+            for dc in dead_code {
+                // Findings on declarations outside the graph (resources,
+                // files) have no row to point at
+                let Some(row) = rows.get(&dc.declaration.id) else {
+                    continue;
+                };
+                insert.execute(params![
+                    row,
+                    dc.issue.code(),
+                    dc.severity.as_str(),
+                    dc.confidence.as_str(),
+                    dc.confidence.score(),
+                    dc.runtime_confirmed,
+                    dc.message,
+                    dc.owner,
+                    dc.root,
+                    dc.suppressed_by,
+                ])?;
+            }
+        }
+
+        tx.commit()
+    }
+
+    fn relative(&self, file: &Path) -> String {
+        NormalizedPath::relative_to(file, &self.root).to_string()
+    }
+}
+
+/// Package of the top-level declaration enclosing `decl`
+fn package_of(decl: &Declaration, graph: &Graph) -> Option<String> {
+    let mut top = decl;
+    while let Some(parent) = top.parent.as_ref().and_then(|p| graph.get_declaration(p)) {
+        top = parent;
+    }
+    let fqn = top.fully_qualified_name.as_deref()?;
+    let (package, _) = fqn.rsplit_once('.')?;
+    Some(package.to_string())
+}
+
+/// Gradle path of the module holding a source file: the directory above its
+/// `src/`, relative to the project root (`feature/home` is `:feature:home`)
+fn gradle_module(file: &Path, root: &Path) -> Option<String> {
+    let relative = file.strip_prefix(root).unwrap_or(file);
+    let components: Vec<_> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect();
+    let src = components.iter().rposition(|c| c == "src")?;
+    Some(format!(":{}", components[..src].join(":")))
+}
+
+fn visibility_name(decl: &Declaration) -> &'static str {
+    match decl.visibility {
+        Visibility::Public => "public",
+        Visibility::Private => "private",
+        Visibility::Protected => "protected",
+        Visibility::Internal => "internal",
+        Visibility::PackagePrivate => "package-private",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{DeclarationKind, Language, Location, Reference, ReferenceKind};
+
+    fn declaration(name: &str, kind: DeclarationKind, start: usize) -> Declaration {
+        let file = PathBuf::from("/project/feature/home/src/main/kotlin/Home.kt");
+        Declaration::new(
+            DeclarationId::new(file.clone(), start, start + 10),
+            name.to_string(),
+            kind,
+            Location::new(file, start / 10 + 1, 1, start, start + 10),
+            Language::Kotlin,
+        )
+    }
+
+    #[test]
+    fn test_gradle_module() {
+        let root = Path::new("/project");
+        assert_eq!(
+            gradle_module(Path::new("/project/feature/home/src/main/Home.kt"), root).as_deref(),
+            Some(":feature:home")
+        );
+        assert_eq!(
+            gradle_module(Path::new("/project/src/main/App.kt"), root).as_deref(),
+            Some(":")
+        );
+        assert_eq!(gradle_module(Path::new("/project/App.kt"), root), None);
+    }
+
+    #[test]
+    fn test_export() {
+        let mut home = declaration("HomeScreen", DeclarationKind::Class, 0);
+        home.fully_qualified_name = Some("com.example.home.HomeScreen".to_string());
+        let mut render = declaration("render", DeclarationKind::Method, 10);
+        render.parent = Some(home.id.clone());
+        let mut legacy = declaration("legacy", DeclarationKind::Method, 20);
+        legacy.parent = Some(home.id.clone());
+        let mut graph = Graph::new();
+        for decl in [&home, &render, &legacy] {
+            graph.add_declaration(decl.clone());
+        }
+        graph.add_reference(
+            &home.id,
+            &render.id,
+            Reference::new(
+                ReferenceKind::Call,
+                render.location.clone(),
+                "render".to_string(),
+            ),
+        );
+
+        let entry_points: HashSet<_> = [home.id.clone()].into_iter().collect();
+        let categories: HashMap<_, _> = [(home.id.clone(), EntryPointCategory::Activity)]
+            .into_iter()
+            .collect();
+        let dead_code = vec![DeadCode::new(legacy.clone(), DeadCodeIssue::Unreferenced)];
+
+        let exporter = SqliteExporter::new(PathBuf::from("findings.sqlite"))
+            .with_project_root(PathBuf::from("/project"));
+        let mut conn = Connection::open_in_memory().unwrap();
+        exporter
+            .write(&mut conn, &graph, &entry_points, &categories, &dead_code)
+            .unwrap();
+
+        let row: (String, String, String, Option<String>) = conn
+            .query_row(
+                "SELECT d.name, d.module, d.package, p.name
+                 FROM findings f
+                 JOIN declarations d ON d.id = f.declaration_id
+                 LEFT JOIN declarations p ON p.id = d.parent_id
+                 WHERE f.code = 'DC001'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            row,
+            (
+                "legacy".to_string(),
+                ":feature:home".to_string(),
+                "com.example.home".to_string(),
+                Some("HomeScreen".to_string())
+            )
+        );
+
+        let reason: String = conn
+            .query_row("SELECT reason FROM entry_points", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(reason, "activity");
+        let calls: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM refs r JOIN declarations d ON d.id = r.to_id
+                 WHERE d.name = 'render' AND r.kind = 'call'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(calls, 1);
+    }
+}
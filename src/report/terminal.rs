@@ -1,22 +1,80 @@
-use crate::analysis::{Confidence, DeadCode, Severity};
+use super::github::counts;
+use super::{GroupBy, SortBy};
+use crate::analysis::metrics::{package_of, Metrics};
+use crate::analysis::size::{finding_sizes, FindingSize};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue, Severity};
+use crate::graph::DeclarationId;
 use colored::Colorize;
 use miette::Result;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+
+/// Packages listed by `--summary-only`
+const TOP_PACKAGES: usize = 10;
 
 /// Terminal reporter with colored output
 pub struct TerminalReporter {
     /// Show confidence levels in output
     show_confidence: bool,
+    /// How findings are grouped
+    group_by: GroupBy,
+    /// How findings are ordered within a group
+    sort_by: SortBy,
+    /// Print aggregate tables instead of the findings
+    summary_only: bool,
+    /// List the evidence behind each finding
+    explain: bool,
+    /// Findings already printed while parsing, by declaration and code
+    streamed: HashSet<(DeclarationId, &'static str)>,
 }
 
 impl TerminalReporter {
     pub fn new() -> Self {
         Self {
             show_confidence: true,
+            group_by: GroupBy::File,
+            sort_by: SortBy::Line,
+            summary_only: false,
+            explain: false,
+            streamed: HashSet::new(),
         }
     }
 
+    pub fn with_group_by(mut self, group_by: GroupBy) -> Self {
+        self.group_by = group_by;
+        self
+    }
+
+    pub fn with_sort_by(mut self, sort_by: SortBy) -> Self {
+        self.sort_by = sort_by;
+        self
+    }
+
+    pub fn with_summary_only(mut self, summary_only: bool) -> Self {
+        self.summary_only = summary_only;
+        self
+    }
+
+    pub fn with_explain(mut self, explain: bool) -> Self {
+        self.explain = explain;
+        self
+    }
+
+    /// Leave out of the listing the findings printed with [`Self::print_streamed`];
+    /// the summary still counts them
+    pub fn with_streamed(mut self, streamed: &[DeadCode]) -> Self {
+        self.streamed = streamed
+            .iter()
+            .map(|item| (item.declaration.id.clone(), item.issue.code()))
+            .collect();
+        self
+    }
+
+    /// Print a finding reported while the project is still being parsed
+    pub fn print_streamed(&self, item: &DeadCode) {
+        let size = finding_sizes(std::slice::from_ref(item))[0];
+        self.print_item(item, size, true);
+    }
+
     #[allow(dead_code)] // Builder pattern method for future use
     pub fn with_confidence(mut self, show: bool) -> Self {
         self.show_confidence = show;
@@ -29,53 +87,184 @@ impl TerminalReporter {
             return Ok(());
         }
 
-        // Group by file
-        let mut by_file: HashMap<PathBuf, Vec<&DeadCode>> = HashMap::new();
-        for item in dead_code {
-            by_file
-                .entry(item.declaration.location.file.clone())
-                .or_default()
-                .push(item);
-        }
+        let sizes = finding_sizes(dead_code);
+        let metrics = Metrics::from_sizes(dead_code, &sizes);
 
         // Print header
         println!();
-        println!(
-            "{}",
+        let header = if self.group_by == GroupBy::Owner {
+            let owners: HashSet<&str> = dead_code
+                .iter()
+                .filter_map(|item| item.owner.as_deref())
+                .collect();
+            format!(
+                "Found {} dead code issues across {} owners:",
+                dead_code.len(),
+                owners.len()
+            )
+        } else {
             format!("Found {} dead code issues:", dead_code.len())
-                .yellow()
-                .bold()
-        );
+        };
+        println!("{}", header.yellow().bold());
+        let shown = dead_code
+            .iter()
+            .filter(|item| self.is_streamed(item))
+            .count();
+        if shown > 0 {
+            println!(
+                "{}",
+                format!("({} of them reported while parsing, above)", shown).dimmed()
+            );
+        }
         println!();
 
+        if self.summary_only {
+            self.print_tables(dead_code, &metrics);
+            self.print_summary(dead_code, &metrics);
+            return Ok(());
+        }
+
         // Print legend if showing confidence
         if self.show_confidence {
             self.print_legend();
         }
 
-        // Print by file
-        let mut files: Vec<_> = by_file.keys().collect();
-        files.sort();
-
-        for file in files {
-            let items = &by_file[file];
+        for (label, mut items) in self.groups(dead_code, &sizes) {
+            items.retain(|(item, _)| !self.is_streamed(item));
+            if items.is_empty() {
+                continue;
+            }
+            self.sort_items(&mut items);
 
-            // File header
-            println!("{}", file.display().to_string().cyan().bold());
+            // File headers stay bare, other groups show how many they hold
+            if self.group_by == GroupBy::File {
+                println!("{}", label.cyan().bold());
+            } else {
+                println!(
+                    "{} {}",
+                    label.cyan().bold(),
+                    format!("({} issues)", items.len()).dimmed()
+                );
+            }
 
-            for item in items {
-                self.print_item(item);
+            // Grouping by anything but file loses the file from the header
+            let show_file = self.group_by != GroupBy::File;
+            for (item, size) in items {
+                self.print_item(item, size, show_file);
             }
 
             println!();
         }
 
         // Print summary
-        self.print_summary(dead_code);
+        self.print_summary(dead_code, &metrics);
 
         Ok(())
     }
 
+    /// Findings with their sizes, grouped and in the order groups are printed
+    fn groups<'a>(
+        &self,
+        dead_code: &'a [DeadCode],
+        sizes: &[FindingSize],
+    ) -> Vec<(String, Vec<(&'a DeadCode, FindingSize)>)> {
+        let mut groups: HashMap<(u8, String), Vec<(&DeadCode, FindingSize)>> = HashMap::new();
+        for (item, size) in dead_code.iter().zip(sizes) {
+            groups
+                .entry(self.group_key(item))
+                .or_default()
+                .push((item, *size));
+        }
+
+        let mut groups: Vec<_> = groups.into_iter().collect();
+        groups.sort_by(|a, b| a.0.cmp(&b.0));
+        groups
+            .into_iter()
+            .map(|((_, label), items)| (label, items))
+            .collect()
+    }
+
+    /// Rank and header of a finding's group; groups sort by rank, then header
+    fn group_key(&self, item: &DeadCode) -> (u8, String) {
+        match self.group_by {
+            GroupBy::File => (0, item.declaration.location.file.display().to_string()),
+            GroupBy::Package => match package_of(item) {
+                Some(package) => (0, package),
+                None => (1, "(default package)".to_string()),
+            },
+            GroupBy::Kind => (0, item.declaration.kind.display_name().to_string()),
+            // Most severe first
+            GroupBy::Severity => {
+                let rank = match item.severity {
+                    Severity::Error => 0,
+                    Severity::Warning => 1,
+                    Severity::Info => 2,
+                };
+                (rank, item.severity.as_str().to_string())
+            }
+            // Unowned findings last
+            GroupBy::Owner => match &item.owner {
+                Some(owner) => (0, owner.clone()),
+                None => (1, "(unowned)".to_string()),
+            },
+        }
+    }
+
+    fn sort_items(&self, items: &mut [(&DeadCode, FindingSize)]) {
+        let by_line = DeadCode::report_order;
+        match self.sort_by {
+            SortBy::Line => items.sort_by(|a, b| by_line(a.0, b.0)),
+            SortBy::Confidence => items.sort_by(|a, b| {
+                b.0.runtime_confirmed
+                    .cmp(&a.0.runtime_confirmed)
+                    .then(b.0.confidence_score().total_cmp(&a.0.confidence_score()))
+                    .then(by_line(a.0, b.0))
+            }),
+            SortBy::Size => items.sort_by(|a, b| {
+                b.1.lines
+                    .cmp(&a.1.lines)
+                    .then(b.1.bytes.cmp(&a.1.bytes))
+                    .then(by_line(a.0, b.0))
+            }),
+        }
+    }
+
+    /// Aggregate tables for `--summary-only`: findings per detector and
+    /// package, and dead methods per package
+    fn print_tables(&self, dead_code: &[DeadCode], metrics: &Metrics) {
+        println!("{}", "By Detector:".dimmed());
+        for issue in DeadCodeIssue::ALL {
+            let count = dead_code.iter().filter(|item| item.issue == issue).count();
+            if count > 0 {
+                println!("  {:<34} {:>6}", issue.code(), count);
+            }
+        }
+        println!();
+
+        println!("{}", "By Package:".dimmed());
+        let by_package = counts(dead_code, |item| {
+            package_of(item).unwrap_or_else(|| "(default package)".to_string())
+        });
+        for (package, count) in by_package.iter().take(TOP_PACKAGES) {
+            println!("  {:<34} {:>6}", package, count);
+        }
+        if by_package.len() > TOP_PACKAGES {
+            println!(
+                "  {}",
+                format!("... and {} more packages", by_package.len() - TOP_PACKAGES).dimmed()
+            );
+        }
+        println!();
+
+        if !metrics.methods_by_package.is_empty() {
+            println!("{}", "Dead Methods by Package:".dimmed());
+            for (package, methods) in metrics.methods_by_package.iter().take(TOP_PACKAGES) {
+                println!("  {:<34} {:>6}", package, methods);
+            }
+            println!();
+        }
+    }
+
     fn print_legend(&self) {
         println!("{}", "Confidence Legend:".dimmed());
         println!(
@@ -108,17 +297,31 @@ impl TerminalReporter {
         }
     }
 
-    fn print_item(&self, item: &DeadCode) {
+    fn is_streamed(&self, item: &DeadCode) -> bool {
+        self.streamed
+            .contains(&(item.declaration.id.clone(), item.issue.code()))
+    }
+
+    fn print_item(&self, item: &DeadCode, size: FindingSize, show_file: bool) {
         let severity_str = match item.severity {
             Severity::Error => "error".red().bold(),
             Severity::Warning => "warning".yellow().bold(),
             Severity::Info => "info".blue().bold(),
         };
 
-        let location = format!(
-            "{}:{}",
-            item.declaration.location.line, item.declaration.location.column
-        );
+        let location = if show_file {
+            format!(
+                "{}:{}:{}",
+                item.declaration.location.file.display(),
+                item.declaration.location.line,
+                item.declaration.location.column
+            )
+        } else {
+            format!(
+                "{}:{}",
+                item.declaration.location.line, item.declaration.location.column
+            )
+        };
 
         // Build confidence badge
         let confidence_badge = if self.show_confidence {
@@ -144,16 +347,71 @@ impl TerminalReporter {
             runtime_badge
         );
 
+        // Owner is already the group header when grouping by owner
+        let owner_info = match (&item.owner, self.group_by) {
+            (Some(_), GroupBy::Owner) | (None, _) => String::new(),
+            (Some(owner), _) => format!(" (owner: {})", owner).dimmed().to_string(),
+        };
+
+        let root_info = match &item.root {
+            Some(root) => format!(" (root: {})", root).dimmed().to_string(),
+            None => String::new(),
+        };
+
+        let size_info = if self.sort_by == SortBy::Size {
+            let lines = counted(size.lines, "line");
+            match item.declaration.size {
+                Some(measured) if measured.branches > 0 => {
+                    format!(" ({}, {})", lines, counted(measured.branches, "branch"))
+                }
+                _ => format!(" ({})", lines),
+            }
+            .dimmed()
+            .to_string()
+        } else {
+            String::new()
+        };
+
+        let risk_info = if item.risk_tags.is_empty() {
+            String::new()
+        } else {
+            format!(" [risk: {}]", item.risk_tags.join(", "))
+                .yellow()
+                .dimmed()
+                .to_string()
+        };
+
         // Print declaration info
         println!(
-            "    {} {} '{}'",
+            "    {} {} '{}'{}{}{}{}",
             "→".dimmed(),
             item.declaration.kind.display_name().dimmed(),
-            item.declaration.name.white()
+            item.declaration.name.white(),
+            size_info,
+            owner_info,
+            root_info,
+            risk_info
         );
+
+        if self.explain && !item.evidence.is_empty() {
+            println!(
+                "    {} {}",
+                "why:".dimmed(),
+                format!("score {:.2}", item.confidence_score()).dimmed()
+            );
+            for evidence in &item.evidence {
+                let weight = format!("{:+.2}", evidence.weight());
+                let weight = if evidence.weight() >= 0.0 {
+                    weight.green()
+                } else {
+                    weight.red()
+                };
+                println!("      {} {} {}", "•".dimmed(), evidence.description, weight);
+            }
+        }
     }
 
-    fn print_summary(&self, dead_code: &[DeadCode]) {
+    fn print_summary(&self, dead_code: &[DeadCode], metrics: &Metrics) {
         // Severity counts
         let mut errors = 0;
         let mut warnings = 0;
@@ -197,6 +455,12 @@ impl TerminalReporter {
             severity_parts.push(format!("{} info", infos).blue().to_string());
         }
         println!("Summary: {}", severity_parts.join(", "));
+        println!(
+            "Estimated deletable: {} lines, {} methods (~{:.1} KB of APK size)",
+            metrics.deletable_lines,
+            metrics.dead_methods,
+            metrics.estimated_apk_bytes as f64 / 1024.0
+        );
 
         // Confidence summary (if showing confidence)
         if self.show_confidence {
@@ -264,6 +528,15 @@ impl TerminalReporter {
     }
 }
 
+/// `count` followed by `noun`, pluralized
+fn counted(count: usize, noun: &str) -> String {
+    match (count, noun.ends_with('h')) {
+        (1, _) => format!("1 {}", noun),
+        (_, true) => format!("{} {}es", count, noun),
+        (_, false) => format!("{} {}s", count, noun),
+    }
+}
+
 impl Default for TerminalReporter {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,24 @@
+// Test fixture: Typealias and import usage patterns
+package com.example.fixtures.aliases
+
+import android.os.Bundle
+import android.util.Log  // DEAD: never used
+import com.example.fixtures.aliases.model.UserRecord as Record
+
+class User(val id: String)
+
+// Case 1: Alias used in a parameter type keeps the aliased type alive
+typealias UserAlias = User
+
+fun loadUser(user: UserAlias, state: Bundle?): String = user.id
+
+// Case 2: Alias used as a generic argument
+typealias UserList = List<User>
+
+fun countUsers(users: UserList): Int = users.size
+
+// Case 3: Alias declared but never used
+typealias UnusedCallback = (Int) -> Unit  // DEAD: never used
+
+// Case 4: Aliased import used through its alias
+fun describe(record: Record): String = record.toString()
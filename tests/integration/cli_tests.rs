@@ -239,6 +239,469 @@ fn test_cli_json_output() {
     }
 }
 
+#[test]
+fn test_cli_validate_output() {
+    let fixtures = fixtures_path().join("kotlin");
+    if !fixtures.exists() {
+        return;
+    }
+
+    let (stdout, stderr, success) = run_cli(&[
+        fixtures.to_str().unwrap(),
+        "--format",
+        "json",
+        "--validate-output",
+        "--quiet",
+    ]);
+
+    assert!(
+        success,
+        "Report should match the bundled schema, stderr: {}",
+        stderr
+    );
+    assert!(stdout.trim().starts_with('{'));
+}
+
+#[test]
+fn test_cli_github_actions_output() {
+    let fixtures = fixtures_path().join("kotlin");
+    if !fixtures.exists() {
+        return;
+    }
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let summary = temp_dir.path().join("summary.md");
+    let (stdout, stderr, success) = run_cli(&[
+        fixtures.to_str().unwrap(),
+        "--format",
+        "github-actions",
+        "--summary",
+        summary.to_str().unwrap(),
+        "--quiet",
+    ]);
+
+    assert!(success, "stderr: {}", stderr);
+    let annotations: Vec<&str> = stdout.lines().filter(|l| l.starts_with("::")).collect();
+    for annotation in &annotations {
+        assert!(annotation.contains(" file=") && annotation.contains(",line="));
+    }
+    let summary = std::fs::read_to_string(&summary).unwrap();
+    assert!(summary.starts_with("## SearchDeadCode"));
+    if !annotations.is_empty() {
+        assert!(summary.contains("| Severity | Findings |"));
+    }
+}
+
+#[test]
+fn test_cli_code_quality_output() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let root = temp_dir.path();
+    std::fs::write(
+        root.join("Main.kt"),
+        "fun main() {\n    helper()\n}\n\nfun helper() {}\n\nfun unused() {}\n",
+    )
+    .unwrap();
+    let baseline_path = root.join("baseline.json");
+
+    let (stdout, stderr, success) = run_cli(&[
+        root.to_str().unwrap(),
+        "--format",
+        "code-quality",
+        "--quiet",
+        "--min-confidence",
+        "low",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    let issues: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    let issues = issues.as_array().unwrap();
+    assert!(!issues.is_empty(), "stdout: {}", stdout);
+    assert!(
+        !stdout.contains(root.to_str().unwrap()),
+        "Paths should be relative"
+    );
+
+    let (_, stderr, success) = run_cli(&[
+        root.to_str().unwrap(),
+        "--quiet",
+        "--min-confidence",
+        "low",
+        "--generate-baseline",
+        baseline_path.to_str().unwrap(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    let baseline = searchdeadcode::baseline::Baseline::load(&baseline_path).unwrap();
+    let fingerprint = baseline
+        .issues
+        .iter()
+        .find(|issue| issue.name == "unused")
+        .unwrap();
+
+    let issue = issues
+        .iter()
+        .find(|issue| {
+            issue["location"]["path"] == fingerprint.file.as_str()
+                && issue["location"]["lines"]["begin"] == fingerprint.line
+        })
+        .unwrap();
+    assert_eq!(
+        issue["fingerprint"],
+        format!("DC001-{}", fingerprint.digest())
+    );
+}
+
+#[test]
+fn test_cli_sarif_baseline_state() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let main = temp_dir.path().join("Main.kt");
+    std::fs::write(&main, "fun main() {}\n\nfun legacy() {}\n").unwrap();
+    let baseline = temp_dir.path().join("baseline.json");
+
+    let (_, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--generate-baseline",
+        baseline.to_str().unwrap(),
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+
+    std::fs::write(
+        &main,
+        "fun main() {}\n\nfun legacy() {}\n\nfun fresh() {}\n",
+    )
+    .unwrap();
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--baseline",
+        baseline.to_str().unwrap(),
+        "--format",
+        "sarif",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+
+    let start = stdout.find('{').expect("SARIF output");
+    let sarif: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let run = &sarif["runs"][0];
+    assert!(run["tool"]["driver"]["rules"].as_array().unwrap().len() >= 18);
+
+    let result = |name: &str| {
+        run["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|r| r["message"]["text"].as_str().unwrap().contains(name))
+            .cloned()
+            .unwrap_or_else(|| panic!("no result for {}: {}", name, stdout))
+    };
+    assert_eq!(result("'fresh'")["baselineState"], "new");
+    let legacy = result("'legacy'");
+    assert_eq!(legacy["baselineState"], "unchanged");
+    assert_eq!(legacy["suppressions"][0]["kind"], "external");
+}
+
+#[test]
+fn test_cli_terminal_grouping_and_summary_only() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "package com.example.app\n\n\
+         fun main() {}\n\n\
+         fun small() {}\n\n\
+         fun large() {\n    println(1)\n    println(2)\n    println(3)\n}\n",
+    )
+    .unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[path, "--group-by", "package", "--sort-by", "size"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("com.example.app"), "stdout: {}", stdout);
+    assert!(stdout.contains("(5 lines)"), "stdout: {}", stdout);
+    assert!(stdout.contains("(1 line)"), "stdout: {}", stdout);
+    let large = stdout.find("'large'").expect("large is reported");
+    let small = stdout.find("'small'").expect("small is reported");
+    assert!(large < small, "Largest findings should come first");
+
+    let (stdout, stderr, success) = run_cli(&[path, "--summary-only"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("By Detector:"), "stdout: {}", stdout);
+    assert!(stdout.contains("DC001"));
+    assert!(stdout.contains("Estimated deletable: "));
+    assert!(!stdout.contains("'small'"), "Findings should not be listed");
+}
+
+#[test]
+fn test_cli_json_metrics() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "package com.example.app\n\nfun main() {}\n\nfun unused() {\n    println()\n}\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let metrics = &report["metrics"];
+    assert_eq!(metrics["deletable_lines"], 3, "metrics: {}", metrics);
+    assert_eq!(metrics["dead_methods"], 1);
+    assert_eq!(
+        metrics["dead_methods_by_package"][0]["package"],
+        "com.example.app"
+    );
+    assert!(metrics["estimated_apk_bytes"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn test_cli_output_db() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let src = temp_dir.path().join("feature/home/src/main/kotlin");
+    std::fs::create_dir_all(&src).unwrap();
+    std::fs::write(
+        src.join("Main.kt"),
+        "package com.example.home\n\nfun main() {\n    render()\n}\n\nfun render() {}\n\nfun legacy() {\n    println()\n}\n",
+    )
+    .unwrap();
+    let db = temp_dir.path().join("findings.sqlite");
+
+    let (_, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--output-db",
+        db.to_str().unwrap(),
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    let (module, package, lines): (String, String, i64) = conn
+        .query_row(
+            "SELECT d.module, d.package, d.lines FROM findings f
+             JOIN declarations d ON d.id = f.declaration_id
+             WHERE f.code = 'DC001' AND d.name = 'legacy'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(
+        (module.as_str(), package.as_str(), lines),
+        (":feature:home", "com.example.home", 3)
+    );
+
+    let main_kept: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM entry_points e JOIN declarations d ON d.id = e.declaration_id
+             WHERE d.name = 'main'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(main_kept, 1);
+    let calls: i64 = conn
+        .query_row("SELECT COUNT(*) FROM refs WHERE kind = 'call'", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert!(calls >= 1);
+}
+
+#[test]
+fn test_cli_golden_ordering_and_finding_ids() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Beta.kt"),
+        "package com.example\n\nclass Beta\n\nclass Gamma\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("Alpha.kt"),
+        "package com.example\n\nimport android.util.Log\n\nclass Alpha\n",
+    )
+    .unwrap();
+
+    let path = temp_dir.path().to_str().unwrap();
+    let run = || {
+        let (stdout, stderr, success) =
+            run_cli(&[path, "--unused-imports", "--format", "json", "--quiet"]);
+        assert!(success, "stderr: {}", stderr);
+        stdout
+    };
+
+    // Two runs over the same sources give byte-identical reports
+    let stdout = run();
+    assert_eq!(stdout, run());
+
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    let golden: Vec<(String, u64, String)> = issues
+        .iter()
+        .map(|issue| {
+            let file = issue["file"].as_str().unwrap();
+            let file = file.rsplit(['/', '\\']).next().unwrap().to_string();
+            let line = issue["line"].as_u64().unwrap();
+            (file, line, issue["code"].as_str().unwrap().to_string())
+        })
+        .collect();
+    let expected = [
+        ("Alpha.kt", 3, "DC004"),
+        ("Alpha.kt", 5, "DC001"),
+        ("Beta.kt", 3, "DC001"),
+        ("Beta.kt", 5, "DC001"),
+    ];
+    assert_eq!(
+        golden,
+        expected
+            .iter()
+            .map(|(file, line, code)| (file.to_string(), *line, code.to_string()))
+            .collect::<Vec<_>>()
+    );
+
+    // Ids are unique within the report
+    let ids: std::collections::HashSet<&str> = issues
+        .iter()
+        .map(|issue| issue["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids.len(), issues.len());
+    assert!(ids.iter().all(|id| id.starts_with("DC00")));
+}
+
+#[test]
+fn test_cli_manifest() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("Orphan.kt"), "class Orphan\n").unwrap();
+    let manifest_path = temp_dir.path().join("analysis-manifest.json");
+
+    let path = temp_dir.path().to_str().unwrap();
+    let run = || {
+        let (_, stderr, success) = run_cli(&[
+            path,
+            "--quiet",
+            "--manifest",
+            manifest_path.to_str().unwrap(),
+        ]);
+        assert!(success, "stderr: {}", stderr);
+        let contents = std::fs::read_to_string(&manifest_path).unwrap();
+        serde_json::from_str::<serde_json::Value>(&contents).unwrap()
+    };
+
+    let manifest = run();
+    assert_eq!(manifest["manifest_version"], 1);
+    assert!(manifest["tool_version"].is_string());
+    assert_eq!(manifest["files"], 1);
+    assert_eq!(manifest["findings"], 1);
+    assert_eq!(manifest["inputs"]["proguard_loaded"], false);
+    assert!(manifest["timings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|timing| timing["stage"] == "parse"));
+    assert!(manifest.get("cache").is_none());
+
+    // Runs with the same configuration have the same hash
+    assert_eq!(run()["config_hash"], manifest["config_hash"]);
+}
+
+#[test]
+fn test_cli_timings() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("Orphan.kt"), "class Orphan\n").unwrap();
+    let report_path = temp_dir.path().join("report.json");
+
+    let (_, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--timings",
+        "--detect",
+        "unused-params",
+        "--format",
+        "json",
+        "--output",
+        report_path.to_str().unwrap(),
+        "--validate-output",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stderr.contains("Timings:"), "stderr: {}", stderr);
+    assert!(stderr.contains("reachability"));
+    assert!(stderr.contains("  unused-params"));
+
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report_path).unwrap()).unwrap();
+    let phases = report["timings"]["phases"].as_array().unwrap();
+    assert!(phases.iter().any(|p| p["phase"] == "parse/resolve"));
+    assert_eq!(report["timings"]["counters"]["files"], 1);
+}
+
+#[test]
+fn test_cli_stream_per_file_findings() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Orphan.kt"),
+        "import android.util.Log\n\nclass Orphan\n",
+    )
+    .unwrap();
+
+    for parallel in [false, true] {
+        let mut args = vec![
+            temp_dir.path().to_str().unwrap(),
+            "--unused-imports",
+            "--stream",
+        ];
+        if parallel {
+            args.push("--parallel");
+        }
+        let (stdout, stderr, success) = run_cli(&args);
+        assert!(success, "stderr: {}", stderr);
+
+        // The unused import is printed while parsing, the rest after it
+        let header = stdout.find("dead code issues").expect("report header");
+        let import = stdout.find("android.util.Log").expect("streamed import");
+        assert!(import < header, "stdout: {}", stdout);
+        assert!(!stdout[header..].contains("android.util.Log"));
+        assert!(stdout[header..].contains("reported while parsing"));
+        assert!(stdout[header..].contains("Orphan"));
+    }
+}
+
+#[test]
+fn test_cli_stream_waits_for_confidence_filter() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Orphan.kt"),
+        "import android.util.Log\n\nclass Orphan\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--unused-imports",
+        "--stream",
+        "--min-confidence",
+        "high",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+
+    // Confidence is only final after detection, so nothing is streamed
+    assert!(
+        stderr.contains("--stream has no effect with --min-confidence"),
+        "stderr: {}",
+        stderr
+    );
+    let header = stdout.find("dead code issues").unwrap_or(stdout.len());
+    assert!(
+        stdout
+            .find("android.util.Log")
+            .is_none_or(|import| import > header),
+        "stdout: {}",
+        stdout
+    );
+    assert!(!stdout.contains("reported while parsing"));
+}
+
 #[test]
 fn test_cli_quiet_mode() {
     let fixtures = fixtures_path().join("kotlin");
@@ -246,14 +709,1587 @@ fn test_cli_quiet_mode() {
         return;
     }
 
-    let (stdout, stderr, _) = run_cli(&[fixtures.to_str().unwrap(), "--quiet"]);
+    let (stdout, stderr, _) = run_cli(&[fixtures.to_str().unwrap(), "--quiet"]);
+
+    // Quiet mode should have minimal output
+    let combined = format!("{}{}", stdout, stderr);
+    assert!(
+        !combined.contains("INFO") || combined.len() < 1000,
+        "Quiet mode should reduce output"
+    );
+}
+
+// ============================================================================
+// Detector Tests
+// ============================================================================
+
+#[test]
+fn test_cli_list_detectors() {
+    let (stdout, _, success) = run_cli(&["list-detectors"]);
+
+    assert!(success, "list-detectors should succeed");
+    assert!(stdout.contains("sealed-variants"));
+    assert!(stdout.contains("unused-resources"));
+    assert!(stdout.contains("cycles"));
+}
+
+#[test]
+fn test_cli_detect_by_id() {
+    let fixtures = fixtures_path().join("kotlin");
+    if !fixtures.exists() {
+        return;
+    }
+
+    let (stdout, stderr, _) = run_cli(&[
+        fixtures.to_str().unwrap(),
+        "--detect",
+        "sealed-variants,write-only",
+        "--skip-detect",
+        "write-only",
+    ]);
+    let combined = format!("{}{}", stdout, stderr);
+    assert!(!combined.is_empty(), "Should produce output");
+
+    let (_, stderr, success) =
+        run_cli(&[fixtures.to_str().unwrap(), "--detect", "no-such-detector"]);
+    assert!(!success, "Unknown detector ids should be rejected");
+    assert!(stderr.contains("no-such-detector"));
+}
+
+#[test]
+fn test_cli_custom_rules() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Payments.kt"),
+        "class PaymentLegacy\n\nfun main() {\n    println(\"hi\")\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join(".searchdeadcode-rules.toml"),
+        "[[rule]]\nid = \"legacy\"\nname_regex = \".*Legacy\"\nkind = [\"class\"]\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("PaymentLegacy"));
+
+    let bad_rules = temp_dir.path().join("bad-rules.toml");
+    std::fs::write(&bad_rules, "[[rule]]\nid = \"bad\"\nkind = [\"widget\"]\n").unwrap();
+    let (_, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--rules",
+        bad_rules.to_str().unwrap(),
+    ]);
+    assert!(!success, "Invalid rule files should be rejected");
+    assert!(stderr.contains("widget"));
+}
+
+#[test]
+fn test_cli_unused_enum_cases_honor_bulk_access() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "enum class Status { ACTIVE, LEGACY }\n\n\
+         enum class Color { RED, BLUE }\n\n\
+         fun main() {\n\
+         println(Status.ACTIVE)\n\
+         println(Color.entries)\n\
+         }\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--unused-enum-cases",
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("LEGACY"), "stdout: {}", stdout);
+    assert!(!stdout.contains("ACTIVE"));
+    assert!(!stdout.contains("BLUE"));
+}
+
+#[test]
+fn test_cli_workers_need_enqueue_site() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Workers.kt"),
+        "class SyncWorker(ctx: Context, params: WorkerParameters) : CoroutineWorker(ctx, params) {\n\
+         override suspend fun doWork(): Result = Result.success()\n\
+         }\n\n\
+         class LegacyWorker(ctx: Context, params: WorkerParameters) : Worker(ctx, params) {\n\
+         override fun doWork(): Result = Result.success()\n\
+         }\n\n\
+         fun createWorker(name: String, ctx: Context, params: WorkerParameters) = when (name) {\n\
+         \"legacy\" -> LegacyWorker(ctx, params)\n\
+         else -> SyncWorker(ctx, params)\n\
+         }\n\n\
+         fun main() {\n\
+         createWorker(\"sync\", context, params)\n\
+         WorkManager.getInstance(context).enqueue(OneTimeWorkRequestBuilder<SyncWorker>().build())\n\
+         }\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("Worker 'LegacyWorker' is never enqueued"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(!stdout.contains("'SyncWorker'"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_cli_event_bus_linkage() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Session.kt"),
+        "class UserLoggedIn\nclass UserLoggedOut\nclass Orphan\n\n\
+         class Session(private val bus: EventBus) {\n\
+         fun login() {\n\
+         bus.post(UserLoggedIn())\n\
+         bus.post(Orphan())\n\
+         }\n\n\
+         @Subscribe\n\
+         fun onLogin(event: UserLoggedIn) {}\n\n\
+         @Subscribe\n\
+         fun onLogout(event: UserLoggedOut) {}\n\
+         }\n\n\
+         fun main() {\n\
+         Session(EventBus.getDefault()).login()\n\
+         }\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("Subscriber 'onLogout' handles 'UserLoggedOut', which is never posted"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("Event 'Orphan' is posted"));
+    assert!(!stdout.contains("'onLogin'"));
+}
+
+#[test]
+fn test_cli_overrides_of_never_instantiated_classes() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Shapes.kt"),
+        "interface Shape {\n\
+         fun area(): Double\n\
+         }\n\n\
+         class Circle(val radius: Double) : Shape {\n\
+         override fun area(): Double = 3.14 * radius * radius\n\
+         }\n\n\
+         class Square(val side: Double) : Shape {\n\
+         override fun area(): Double = side * side\n\
+         }\n\n\
+         fun total(shapes: List<Shape>, square: Square?) = shapes.sumOf { it.area() }\n\n\
+         fun main() {\n\
+         println(total(listOf(Circle(1.0)), null))\n\
+         }\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("'area' overrides a super type member, but 'Square' is never instantiated"),
+        "stdout: {}",
+        stdout
+    );
+    assert_eq!(stdout.matches("'area'").count(), 1, "stdout: {}", stdout);
+}
+
+#[test]
+fn test_cli_unused_broadcasts() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Sync.kt"),
+        "const val SYNC_DONE = \"com.example.SYNC_DONE\"\n\n\
+         fun finish(context: Context) {\n\
+         context.sendBroadcast(Intent(SYNC_DONE))\n\
+         context.sendBroadcast(Intent(\"com.example.LEGACY_REFRESH\"))\n\
+         }\n\n\
+         fun listen(context: Context) {\n\
+         context.registerReceiver(receiver, IntentFilter(SYNC_DONE))\n\
+         context.registerReceiver(receiver, IntentFilter(\"com.example.LOGOUT\"))\n\
+         }\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) =
+        run_cli(&[temp_dir.path().to_str().unwrap(), "--unused-broadcasts"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains(
+            "Sync.kt:5 - action \"com.example.LEGACY_REFRESH\" broadcast but never received"
+        ),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("action \"com.example.LOGOUT\" received but never broadcast"));
+    assert!(!stdout.contains("com.example.SYNC_DONE\""));
+}
+
+#[test]
+fn test_cli_unused_bundle_args() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("DetailFragment.kt"),
+        "class DetailFragment : Fragment() {\n\
+         companion object {\n\
+         const val ARG_ITEM_ID = \"item_id\"\n\
+         fun newInstance(id: String) = DetailFragment().apply {\n\
+         arguments = bundleOf(ARG_ITEM_ID to id, \"legacy_source\" to \"list\")\n\
+         }\n\
+         }\n\
+         val itemId by lazy { requireArguments().getString(ARG_ITEM_ID) }\n\
+         }\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) =
+        run_cli(&[temp_dir.path().to_str().unwrap(), "--unused-bundle-args"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("DetailFragment.kt:5 - argument \"legacy_source\" set but never read"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(!stdout.contains("\"item_id\""));
+}
+
+#[test]
+fn test_cli_unused_pref_keys() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Keys.kt"),
+        "object Keys {\n\
+         const val TOKEN = \"auth_token\"\n\
+         val DARK_MODE = booleanPreferencesKey(\"dark_mode\")\n\
+         val LEGACY_THEME = intPreferencesKey(\"legacy_theme\")\n\
+         }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("Session.kt"),
+        "class Session(private val prefs: SharedPreferences) {\n\
+         fun save(token: String, now: Long) {\n\
+         prefs.edit().putString(Keys.TOKEN, token).apply()\n\
+         prefs.edit().putLong(\"last_sync\", now).apply()\n\
+         }\n\
+         fun token() = prefs.getString(Keys.TOKEN, null)\n\
+         fun dark(p: Preferences) = p[Keys.DARK_MODE] ?: false\n\
+         }\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) =
+        run_cli(&[temp_dir.path().to_str().unwrap(), "--unused-pref-keys"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("Session.kt:4 - key \"last_sync\" written but never read"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("Keys.kt:4 - key \"legacy_theme\" defined but never used"));
+    assert!(!stdout.contains("\"auth_token\""));
+    assert!(!stdout.contains("\"dark_mode\""));
+}
+
+#[test]
+fn test_cli_deep_links() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("AndroidManifest.xml"),
+        r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <application>
+        <activity android:name=".ItemActivity">
+            <intent-filter>
+                <action android:name="android.intent.action.VIEW" />
+                <data android:scheme="myapp" android:host="item" />
+            </intent-filter>
+        </activity>
+        <activity android:name=".PromoActivity">
+            <intent-filter>
+                <action android:name="android.intent.action.VIEW" />
+                <data android:scheme="myapp" android:host="promo" />
+            </intent-filter>
+        </activity>
+    </application>
+</manifest>
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("Links.kt"),
+        "class ItemActivity\nclass PromoActivity\n\n\
+         fun openItem(id: Int) = Uri.parse(\"myapp://item/$id\")\n\
+         fun openCart() = Uri.parse(\"myapp://cart\")\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[temp_dir.path().to_str().unwrap(), "--deep-links"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("deep link \"myapp://promo\" of 'com.example.PromoActivity' never opened"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("Links.kt:5 - \"myapp://cart\" opened but no deep link handles it"));
+    assert!(!stdout.contains("\"myapp://item\" of"));
+}
+
+#[test]
+fn test_cli_dead_branches_follow_build_config() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "const val NEW_CHECKOUT = false\n\n\
+         fun main() {\n\
+         if (NEW_CHECKOUT) println(\"new\")\n\
+         if (BuildConfig.DEBUG) println(\"debug\")\n\
+         }\n",
+    )
+    .unwrap();
+    let args = [
+        temp_dir.path().to_str().unwrap(),
+        "--dead-branches",
+        "--format",
+        "json",
+        "--quiet",
+    ];
+
+    let (stdout, stderr, success) = run_cli(&args);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("Condition `NEW_CHECKOUT` is always false"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("Condition `BuildConfig.DEBUG` is always false"));
+
+    std::fs::write(
+        temp_dir.path().join(".deadcode.yml"),
+        "android:\n  build_config:\n    DEBUG: true\n",
+    )
+    .unwrap();
+    let (stdout, stderr, success) = run_cli(&args);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("NEW_CHECKOUT"));
+    assert!(!stdout.contains("BuildConfig.DEBUG"));
+}
+
+#[test]
+fn test_cli_doc_links() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "package com.example.app\n\n/**\n * Replaces [legacyLoad].\n */\nfun main() {}\n\nfun legacyLoad() {}\n",
+    )
+    .unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    // A doc link does not keep its target alive
+    let (stdout, stderr, success) = run_cli(&[path, "--format", "json", "--quiet"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("legacyLoad"), "stdout: {}", stdout);
+    assert!(!stdout.contains("doc-linked"));
+
+    let (stdout, stderr, success) = run_cli(&[
+        path,
+        "--doc-links",
+        "--dead-doc-links",
+        "--min-confidence",
+        "low",
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    let dead = issues
+        .iter()
+        .find(|issue| issue["code"] == "DC001")
+        .expect("legacyLoad is still dead");
+    assert!(dead["risk_tags"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("doc-linked")));
+    let link = issues
+        .iter()
+        .find(|issue| issue["code"] == "DC019")
+        .expect("dead doc link reported");
+    assert_eq!(link["line"], 4);
+}
+
+#[test]
+fn test_cli_compose_state() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Counter.kt"),
+        r#"package com.example.ui
+
+@Composable
+fun Counter(label: String, step: Int = 1) {
+    var clicks by remember { mutableStateOf(0) }
+    var total by remember { mutableStateOf(0) }
+    Button(onClick = { clicks++; total += step }) {
+        Text("$label: $total")
+    }
+}
+
+fun main() {
+    Counter("Taps")
+}
+"#,
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--compose-state",
+        "--min-confidence",
+        "low",
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    let names = |code: &str| -> Vec<String> {
+        issues
+            .iter()
+            .filter(|issue| issue["code"] == code)
+            .map(|issue| issue["declaration"]["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(names("DC020"), vec!["clicks".to_string()]);
+    assert_eq!(names("DC021"), vec!["step".to_string()]);
+}
+
+#[test]
+fn test_cli_preview_only() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Home.kt"),
+        r#"package com.example.ui
+
+fun main() {
+    HomeScreen()
+}
+
+@Composable
+fun HomeScreen() {
+    Header()
+}
+
+@Composable
+fun Header() {}
+
+@Composable
+fun Badge() {}
+
+@Preview
+@Composable
+fun BadgePreview() {
+    Badge()
+}
+"#,
+    )
+    .unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    // Every composable is an entry point by default
+    let (stdout, stderr, success) = run_cli(&[path, "--format", "json", "--quiet"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(!stdout.contains("Badge"), "stdout: {}", stdout);
+
+    let (stdout, stderr, success) = run_cli(&[
+        path,
+        "--preview-only",
+        "--min-confidence",
+        "low",
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let names: Vec<&str> = report["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["declaration"]["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Badge"]);
+}
+
+#[test]
+fn test_cli_dead_objects() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Screen.kt"),
+        r#"package com.example
+
+class Screen {
+    fun show() {
+        println("screen")
+    }
+
+    companion object {
+        fun create(): Screen = Screen()
+        fun preview(): Screen = create()
+    }
+}
+
+fun main() {
+    Screen().show()
+}
+"#,
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--dead-objects",
+        "--min-confidence",
+        "low",
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    let names: Vec<&str> = issues
+        .iter()
+        .filter(|issue| issue["code"] == "DC024")
+        .map(|issue| issue["declaration"]["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Companion"]);
+    assert!(!issues
+        .iter()
+        .any(|issue| issue["declaration"]["name"] == "Screen"));
+}
+
+#[test]
+fn test_cli_uninvoked_params() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Saver.kt"),
+        r#"package com.example
+
+fun main() {
+    save("a", onSaved = { println("saved") }, onError = null)
+    handlers["click"]?.invoke()
+}
+
+val handlers = mapOf("click" to ::handleClick)
+
+fun handleClick() {}
+
+fun save(item: String, onSaved: () -> Unit, onError: ((Throwable) -> Unit)?) {
+    println(item)
+    if (onError != null) println("with error handler")
+}
+"#,
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--uninvoked-params",
+        "--min-confidence",
+        "low",
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    let names: Vec<&str> = issues
+        .iter()
+        .filter(|issue| issue["code"] == "DC023")
+        .map(|issue| issue["declaration"]["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["onSaved", "onError"]);
+    // A function only stored as a handler is still used
+    assert!(!stdout.contains("handleClick"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_cli_deep_respect_overrides() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Shapes.java"),
+        r#"package com.example;
+
+interface Shape {
+    double area();
+}
+
+class Circle implements Shape {
+    public double area() {
+        return square(2.0);
+    }
+
+    private double square(double r) {
+        return r * r;
+    }
+}
+
+public class Main {
+    public static void main(String[] args) {
+        Shape shape = new Circle();
+        System.out.println(shape.area());
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    let circle_area = |report: &serde_json::Value| {
+        report["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|issue| {
+                issue["declaration"]["name"] == "area" && issue["line"].as_u64() == Some(8)
+            })
+            .cloned()
+    };
+    let run = |extra: &[&str]| {
+        let mut args = vec![
+            temp_dir.path().to_str().unwrap(),
+            "--deep",
+            "--format",
+            "json",
+            "--quiet",
+        ];
+        args.extend_from_slice(extra);
+        let (stdout, stderr, success) = run_cli(&args);
+        assert!(success, "stderr: {}", stderr);
+        let start = stdout.find('{').expect("JSON output");
+        serde_json::from_str::<serde_json::Value>(&stdout[start..]).unwrap()
+    };
+
+    if let Some(issue) = circle_area(&run(&[])) {
+        assert!(
+            issue["message"]
+                .as_str()
+                .unwrap()
+                .contains("cannot delete independently"),
+            "issue: {}",
+            issue
+        );
+    }
+    assert!(circle_area(&run(&["--deep-respect-overrides"])).is_none());
+}
+
+#[test]
+fn test_cli_deep_keeps_serialization_hooks_of_live_classes() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Session.java"),
+        r#"package com.example;
+
+import java.io.Serializable;
+
+public class Session implements Serializable {
+    private static final long serialVersionUID = 1L;
+
+    private void unusedHelper() {}
+}
+
+class DeadSession implements Serializable {
+    private static final long serialVersionUID = 2L;
+}
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "package com.example\n\nfun main() {\n    println(Session())\n}\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--deep",
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let findings: Vec<(&str, u64)> = report["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| {
+            (
+                issue["declaration"]["name"].as_str().unwrap(),
+                issue["line"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert!(
+        !findings.iter().any(|(name, _)| *name == "Session"),
+        "findings: {:?}",
+        findings
+    );
+    assert!(
+        !findings.contains(&("serialVersionUID", 6)),
+        "findings: {:?}",
+        findings
+    );
+    assert!(findings.iter().any(|(name, _)| *name == "DeadSession"));
+}
+
+#[test]
+fn test_cli_unused_module_deps() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let root = temp_dir.path();
+    for dir in [
+        "app/src/main/java",
+        "core/network/src/main/java",
+        "core/legacy/src/main/java",
+    ] {
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    std::fs::write(
+        root.join("app/build.gradle.kts"),
+        "dependencies {\n\
+         implementation(project(\":core:network\"))\n\
+         implementation(project(\":core:legacy\"))\n\
+         }\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("core/network/build.gradle.kts"), "").unwrap();
+    std::fs::write(root.join("core/legacy/build.gradle.kts"), "").unwrap();
+    std::fs::write(
+        root.join("app/src/main/java/Main.kt"),
+        "package com.example.app\n\nimport com.example.network.Api\n\nfun main() = Api().fetch()\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("core/network/src/main/java/Api.kt"),
+        "package com.example.network\n\nclass Api { fun fetch() {} }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("core/legacy/src/main/java/Old.kt"),
+        "package com.example.legacy\n\nclass Old\n",
+    )
+    .unwrap();
+    let graph_path = root.join("modules.json");
+
+    let (stdout, stderr, success) = run_cli(&[
+        root.to_str().unwrap(),
+        "--unused-module-deps",
+        "--module-graph",
+        graph_path.to_str().unwrap(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains(
+            "app/build.gradle.kts:3 - implementation ':core:legacy' is never imported from ':app'"
+        ),
+        "stdout: {}",
+        stdout
+    );
+    assert!(!stdout.contains("':core:network' is never imported"));
+
+    let graph: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&graph_path).unwrap()).unwrap();
+    assert_eq!(graph["modules"].as_array().unwrap().len(), 3);
+    assert_eq!(graph["dependencies"].as_array().unwrap().len(), 2);
+}
+
+// ============================================================================
+// Entry Point and Source Set Tests
+// ============================================================================
+
+#[test]
+fn test_cli_gradle_script_keeps_build_logic_alive() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let build_logic = temp_dir.path().join("buildSrc/src/main/kotlin");
+    std::fs::create_dir_all(&build_logic).unwrap();
+    std::fs::write(
+        build_logic.join("GenerateDocs.kt"),
+        "abstract class GenerateDocs : DefaultTask()\n",
+    )
+    .unwrap();
+    std::fs::write(build_logic.join("Orphan.kt"), "class OrphanTask\n").unwrap();
+    std::fs::write(
+        temp_dir.path().join("build.gradle.kts"),
+        "tasks.register<GenerateDocs>(\"docs\")\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        !stdout.contains("GenerateDocs"),
+        "Used from build.gradle.kts"
+    );
+    assert!(
+        !stdout.contains("build.gradle.kts"),
+        "Scripts are never dead"
+    );
+    assert!(stdout.contains("Orphan"));
+}
+
+#[test]
+fn test_cli_generated_sources_keep_handwritten_code_alive() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let sources = temp_dir.path().join("app/src/main/kotlin");
+    let generated = temp_dir.path().join("app/build/generated/ksp/debug/kotlin");
+    std::fs::create_dir_all(&sources).unwrap();
+    std::fs::create_dir_all(&generated).unwrap();
+    std::fs::write(sources.join("Repository.kt"), "class Repository\n").unwrap();
+    std::fs::write(
+        generated.join("Repository_Factory.kt"),
+        "class Repository_Factory {\n    fun get() = Repository()\n}\n",
+    )
+    .unwrap();
+
+    let path = temp_dir.path().to_str().unwrap();
+    let (stdout, stderr, success) = run_cli(&[path, "--format", "json", "--quiet"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("Repository"), "Generated code is opt-in");
+
+    let (stdout, stderr, success) =
+        run_cli(&[path, "--generated-sources", "--format", "json", "--quiet"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(!stdout.contains("Repository"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_cli_reflection_downgrades_confidence() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("PluginLoader.kt"),
+        "package com.example.plugins\n\n\
+         private class LegacyPlugin\n\n\
+         fun load(name: String) = Class.forName(name)\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("(file uses reflection: Class.forName)"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("Its file uses reflection: Class.forName"));
+    assert!(!stdout.contains("No reflection or framework markers"));
+
+    let config = temp_dir.path().join(".deadcode.yml");
+    std::fs::write(&config, "reflection:\n  enabled: false\n").unwrap();
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("LegacyPlugin"));
+    assert!(!stdout.contains("uses reflection"));
+}
+
+#[test]
+fn test_cli_entry_set_tests() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let main_dir = temp_dir.path().join("app/src/main/kotlin");
+    let test_dir = temp_dir.path().join("app/src/test/kotlin");
+    std::fs::create_dir_all(&main_dir).unwrap();
+    std::fs::create_dir_all(&test_dir).unwrap();
+    std::fs::write(
+        main_dir.join("Prices.kt"),
+        "package com.example\n\n\
+         fun main() { checkout() }\n\n\
+         fun checkout() { format() }\n\n\
+         fun format() {}\n\n\
+         fun legacyFormat() {}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        test_dir.join("PricesTest.kt"),
+        "package com.example\n\n\
+         import org.junit.Test\n\n\
+         class PricesTest {\n\
+         \x20   @Test\n\
+         \x20   fun formatsPrices() {\n\
+         \x20       format()\n\
+         \x20       legacyFormat()\n\
+         \x20   }\n\
+         }\n",
+    )
+    .unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    let (stdout, stderr, success) =
+        run_cli(&[path, "--entry-set", "tests", "--format", "json", "--quiet"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("checkout"), "stdout: {}", stdout);
+    assert!(!stdout.contains("legacyFormat"), "stdout: {}", stdout);
+    assert!(!stdout.contains("formatsPrices"));
+
+    let (stdout, stderr, success) = run_cli(&[path, "--entry-set", "diff", "--quiet"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("App vs Tests Reachability"),
+        "stdout: {}",
+        stdout
+    );
+    let untested = stdout.find("Untested").unwrap();
+    let test_only = stdout.find("Only reached from tests").unwrap();
+    assert!(stdout[untested..test_only].contains("checkout"));
+    assert!(stdout[test_only..].contains("legacyFormat"));
+}
+
+#[test]
+fn test_cli_visibility_annotations() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let main_dir = temp_dir.path().join("app/src/main/kotlin");
+    let test_dir = temp_dir.path().join("app/src/test/kotlin");
+    std::fs::create_dir_all(&main_dir).unwrap();
+    std::fs::create_dir_all(&test_dir).unwrap();
+    std::fs::write(
+        main_dir.join("Cache.kt"),
+        "package com.example\n\n\
+         import androidx.annotation.RestrictTo\n\
+         import androidx.annotation.VisibleForTesting\n\n\
+         fun main() { load() }\n\n\
+         fun load() {}\n\n\
+         @VisibleForTesting\n\
+         fun resetCache() {}\n\n\
+         fun legacyLoad() {}\n\n\
+         @RestrictTo(RestrictTo.Scope.LIBRARY_GROUP)\n\
+         fun warmUp() {}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        test_dir.join("CacheTest.kt"),
+        "package com.example\n\n\
+         import org.junit.Test\n\n\
+         class CacheTest {\n\
+         \x20   @Test\n\
+         \x20   fun loads() {\n\
+         \x20       resetCache()\n\
+         \x20       legacyLoad()\n\
+         \x20   }\n\
+         }\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--only-used-in-tests",
+        "--min-confidence",
+        "low",
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let issue = |name: &str| {
+        report["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|issue| issue["declaration"]["name"] == name)
+            .cloned()
+            .unwrap_or_else(|| panic!("no finding for {}: {}", name, stdout))
+    };
+    assert_eq!(issue("resetCache")["code"], "DC006");
+    assert_eq!(issue("legacyLoad")["code"], "DC013");
+    let warm_up = issue("warmUp");
+    assert_eq!(warm_up["code"], "DC001");
+    assert!(warm_up["message"].as_str().unwrap().contains("@RestrictTo"));
+}
+
+#[test]
+fn test_cli_entry_point_overrides() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("SdkFacade.kt"),
+        "package com.example.api\n\nclass SdkFacade\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("Exported.kt"),
+        "package com.example\n\n@PublicApi\nclass Exported\n\nclass Orphan\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--entry-point",
+        "com.example.api.*",
+        "--entry-point",
+        "@PublicApi",
+        "--entry-point",
+        "com.example.Missing",
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let names: Vec<&str> = report["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["declaration"]["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["Orphan"]);
+    assert!(
+        stderr.contains("Entry point 'com.example.Missing' matches no declaration"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_cli_proguard_seeds_kept_only() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Api.kt"),
+        "package com.example\n\n\
+         class Api {\n\
+         fun fetch() {}\n\
+         }\n",
+    )
+    .unwrap();
+    let seeds = temp_dir.path().join("seeds.txt");
+    std::fs::write(&seeds, "com.example.Api\ncom.example.Api: void fetch()\n").unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--proguard-seeds",
+        seeds.to_str().unwrap(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("R8 seeds.txt: 2 kept items"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Kept Only by Keep Rules"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("• class Api"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_cli_variant_source_sets() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let src = temp_dir.path().join("app/src");
+    for dir in ["main/java", "free/java", "paid/java"] {
+        std::fs::create_dir_all(src.join(dir)).unwrap();
+    }
+    std::fs::write(
+        src.join("main/java/Main.kt"),
+        "fun main() {\n    showAds()\n}\n\nfun formatPrice() {}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        src.join("free/java/Ads.kt"),
+        "fun showAds() {\n    formatPrice()\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        src.join("paid/java/Ads.kt"),
+        "fun showAds() {}\n\nfun unusedBilling() {}\n",
+    )
+    .unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        path,
+        "--variant",
+        "freeDebug",
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(!stdout.contains("unusedBilling"), "stdout: {}", stdout);
+    assert!(!stdout.contains("formatPrice"));
+
+    let (stdout, stderr, success) = run_cli(&[
+        path,
+        "--variant",
+        "freeDebug",
+        "--variant",
+        "paidDebug",
+        "--min-confidence",
+        "low",
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("unusedBilling"), "stdout: {}", stdout);
+    assert!(stdout.contains("dead in paidDebug; used in freeDebug"));
+}
+
+#[test]
+fn test_cli_multiple_roots() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let app = temp_dir.path().join("app");
+    let library = temp_dir.path().join("library-repo");
+    std::fs::create_dir_all(&app).unwrap();
+    std::fs::create_dir_all(&library).unwrap();
+    std::fs::write(
+        app.join("Main.kt"),
+        "package com.example.app\n\nimport com.example.lib.formatPrice\n\nfun main() {\n    formatPrice()\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        library.join("Prices.kt"),
+        "package com.example.lib\n\nfun formatPrice() {}\n\nfun legacyPrice() {}\n",
+    )
+    .unwrap();
+    let app_arg = app.to_str().unwrap();
+    let library_arg = library.to_str().unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        "--path",
+        app_arg,
+        "--path",
+        library_arg,
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    // The app's call keeps the library function alive
+    assert!(!stdout.contains("'formatPrice'"), "stdout: {}", stdout);
+    let legacy = issues
+        .iter()
+        .find(|issue| issue["message"].as_str().unwrap().contains("legacyPrice"))
+        .expect("legacyPrice is dead");
+    assert_eq!(legacy["root"], library_arg);
+
+    // Each root gets its own cache
+    let (_, stderr, success) = run_cli(&["prime-cache", "--path", app_arg, "--path", library_arg]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(app.join(".searchdeadcode-cache.json").exists());
+    assert!(library.join(".searchdeadcode-cache.json").exists());
+}
+
+#[test]
+fn test_cli_only_package() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("App.kt"),
+        "package com.example.app\n\nimport com.example.feature.home.showHome\n\nfun main() {\n    showHome()\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("Home.kt"),
+        "package com.example.feature.home\n\nfun showHome() {}\n\nfun oldHome() {}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("Other.kt"),
+        "package com.example.other\n\nfun unusedOther() {}\n",
+    )
+    .unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    for extra in [&[][..], &["--prune-by-imports"][..]] {
+        let mut args = vec![
+            path,
+            "--only-package",
+            "com.example.feature",
+            "--format",
+            "json",
+            "--quiet",
+        ];
+        args.extend_from_slice(extra);
+        let (stdout, stderr, success) = run_cli(&args);
+        assert!(success, "stderr: {}", stderr);
+        let start = stdout.find('{').expect("JSON output");
+        let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+        let names: Vec<&str> = report["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|issue| issue["declaration"]["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["oldHome"], "args: {:?}", extra);
+    }
+}
+
+#[test]
+fn test_cli_bytecode_frontend() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "package com.example.app\n\nfun main() {}\n\nfun calledByInference() {}\n",
+    )
+    .unwrap();
+    // The compiler resolved a call tree-sitter could not see
+    let dump = temp_dir.path().join("symbols.json");
+    std::fs::write(
+        &dump,
+        r#"{"version": 1, "references": [{"from": "com.example.app.main", "to": "com.example.app.calledByInference", "kind": "call", "file": "Main.kt", "line": 3}]}"#,
+    )
+    .unwrap();
+    let path = temp_dir.path().to_str().unwrap();
 
-    // Quiet mode should have minimal output
-    let combined = format!("{}{}", stdout, stderr);
+    let (stdout, _, success) = run_cli(&[path, "--quiet"]);
+    assert!(success);
+    assert!(stdout.contains("calledByInference"));
+
+    let (stdout, stderr, success) = run_cli(&[
+        path,
+        "--frontend",
+        "bytecode",
+        "--symbols",
+        dump.to_str().unwrap(),
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(!stdout.contains("calledByInference"), "stdout: {}", stdout);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cli_plugin() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("Main.kt"), "fun main() {\n}\n").unwrap();
+
+    // Flags the first declaration of the graph it is sent
+    let plugin = temp_dir.path().join("flag-first.sh");
+    std::fs::write(
+        &plugin,
+        r#"#!/bin/sh
+id=$(grep -o '{"file":"[^"]*","start":[0-9]*,"end":[0-9]*}' | head -n 1)
+echo "{\"findings\": [{\"id\": $id, \"message\": \"flagged by plugin\"}]}"
+"#,
+    )
+    .unwrap();
+    let failing = temp_dir.path().join("failing.sh");
+    std::fs::write(&failing, "#!/bin/sh\nexit 3\n").unwrap();
+    for script in [&plugin, &failing] {
+        std::fs::set_permissions(script, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--plugin",
+        plugin.to_str().unwrap(),
+        "--plugin",
+        failing.to_str().unwrap(),
+        "--format",
+        "json",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("flagged by plugin"), "stdout: {}", stdout);
     assert!(
-        !combined.contains("INFO") || combined.len() < 1000,
-        "Quiet mode should reduce output"
+        stderr.contains("Plugin failing failed"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+// ============================================================================
+// Coverage Tests
+// ============================================================================
+
+#[test]
+fn test_cli_stack_traces_as_coverage() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "package com.example\n\n\
+         fun main() {}\n\n\
+         fun exportLegacyReport() {}\n",
+    )
+    .unwrap();
+    let crashes = temp_dir.path().join("crashes.txt");
+    std::fs::write(
+        &crashes,
+        "java.lang.IllegalStateException: no report\n\
+         \tat com.example.MainKt.exportLegacyReport(Main.kt:5)\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--coverage",
+        crashes.to_str().unwrap(),
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("but was executed at runtime"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(!stdout.contains("confirmed by runtime coverage"));
+}
+
+#[test]
+fn test_cli_coverage_store_keeps_evidence() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "package com.example\n\n\
+         fun main() {}\n\n\
+         fun exportLegacyReport() {}\n",
+    )
+    .unwrap();
+    let crashes = temp_dir.path().join("crashes.txt");
+    std::fs::write(
+        &crashes,
+        "\tat com.example.MainKt.exportLegacyReport(Main.kt:5)\n",
+    )
+    .unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    let (_, stderr, success) = run_cli(&[
+        path,
+        "--coverage",
+        crashes.to_str().unwrap(),
+        "--coverage-store",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    let store =
+        std::fs::read_to_string(temp_dir.path().join(".searchdeadcode-coverage.json")).unwrap();
+    assert!(store.contains("com.example.MainKt.exportLegacyReport"));
+
+    // Later runs without the crash export still see it
+    let (stdout, stderr, success) =
+        run_cli(&[path, "--coverage-store", "--format", "json", "--quiet"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("but was executed at runtime"),
+        "stdout: {}",
+        stdout
+    );
+
+    let (_, stderr, success) = run_cli(&[path, "--coverage-store", "--coverage-max-age", "3m"]);
+    assert!(!success);
+    assert!(stderr.contains("unknown unit"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_cli_coverage_auto() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let write = |path: &str, contents: &str| {
+        let path = temp_dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    };
+    write("settings.gradle.kts", "include(\":app\", \":lib\")\n");
+    write("app/build.gradle.kts", "");
+    write(
+        "app/src/main/kotlin/com/example/app/Main.kt",
+        "package com.example.app\n\nfun main() {}\n",
+    );
+    write(
+        "app/build/reports/kover/report.xml",
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<report name="Kover Report">
+    <package name="com/example/app">
+        <sourcefile name="Main.kt">
+            <line nr="3" mi="0" ci="2"/>
+        </sourcefile>
+    </package>
+</report>"#,
+    );
+    write("lib/build.gradle.kts", "");
+    write(
+        "lib/src/main/kotlin/com/example/lib/Util.kt",
+        "package com.example.lib\n\nfun util() {}\n",
+    );
+
+    let (_, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--coverage-auto",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stderr.contains("Modules Without Coverage (1)"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(stderr.contains("  :lib"));
+    assert!(!stderr.contains("  :app"));
+}
+
+// ============================================================================
+// Explain Tests
+// ============================================================================
+
+#[test]
+fn test_cli_explain_command() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "fun main() {\n    helper()\n}\n\nfun helper() {}\n\nfun unused() {}\n",
+    )
+    .unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    let (stdout, stderr, success) = run_cli(&["explain", "helper", path]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("Alive"), "stdout: {}", stdout);
+    assert!(stdout.contains("call at"));
+
+    let (stdout, _, success) = run_cli(&["explain", "Main.kt:7", path]);
+    assert!(success);
+    assert!(stdout.contains("unused"), "stdout: {}", stdout);
+    assert!(stdout.contains("Dead: no path from any entry point"));
+
+    let (_, stderr, success) = run_cli(&["explain", "missing", path]);
+    assert!(!success);
+    assert!(stderr.contains("No declaration matches missing"));
+}
+
+#[test]
+fn test_cli_explain_entry_point_category() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let xml_dir = temp_dir.path().join("res").join("xml");
+    std::fs::create_dir_all(&xml_dir).unwrap();
+    std::fs::write(
+        xml_dir.join("shortcuts.xml"),
+        r#"<shortcuts xmlns:android="http://schemas.android.com/apk/res/android">
+    <shortcut android:shortcutId="compose">
+        <intent android:targetClass="com.example.ComposeActivity" />
+    </shortcut>
+</shortcuts>
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("Entries.kt"),
+        "package com.example\n\n\
+         class ComposeActivity\n\n\
+         class ClockWidget : AppWidgetProvider()\n",
+    )
+    .unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    let (stdout, stderr, success) = run_cli(&["explain", "ComposeActivity", path]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("entry point (app shortcut)"),
+        "stdout: {}",
+        stdout
+    );
+
+    let (stdout, _, success) = run_cli(&["explain", "ClockWidget", path]);
+    assert!(success);
+    assert!(
+        stdout.contains("entry point (app widget)"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_cli_explain() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "fun main() {\n    helper()\n}\n\nfun helper() {}\n\nfun unused() {}\n",
+    )
+    .unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[path, "--explain", "--min-confidence", "low"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("unused"), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("why:"),
+        "Findings should list their evidence: {}",
+        stdout
     );
+    assert!(stdout.contains("Not reachable from any entry point"));
+
+    let (stdout, _, _) = run_cli(&[
+        path,
+        "--format",
+        "json",
+        "--quiet",
+        "--min-confidence",
+        "low",
+    ]);
+    assert!(stdout.contains("\"DC001\""), "stdout: {}", stdout);
+    assert!(stdout.contains("\"static-unreferenced\""));
 }
 
 // ============================================================================
@@ -288,6 +2324,485 @@ fn test_cli_min_confidence_low() {
     println!("Low confidence output: {}", combined);
 }
 
+#[test]
+fn test_cli_exclude_risk() {
+    let fixtures = fixtures_path().join("kotlin");
+    if !fixtures.exists() {
+        return;
+    }
+
+    let (stdout, stderr, success) = run_cli(&[
+        fixtures.to_str().unwrap(),
+        "--format",
+        "json",
+        "--exclude-risk",
+        "public-api",
+        "--quiet",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(!stdout.contains("\"public-api\""));
+
+    let (_, _, success) = run_cli(&[fixtures.to_str().unwrap(), "--exclude-risk", "unknown"]);
+    assert!(!success, "Unknown risk tags should be rejected");
+}
+
+#[test]
+fn test_cli_inline_suppressions() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "package com.example.app\n\nfun main() {}\n\n// searchdeadcode:ignore called from the debug menu\nfun dumpState() {}\n\n@Suppress(\"DeadCode\")\nfun legacyExport() {}\n\nfun unused() {}\n",
+    )
+    .unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[path, "--format", "json", "--quiet"]);
+    assert!(success, "stderr: {}", stderr);
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 1, "issues: {:?}", issues);
+    assert!(issues[0]["message"].as_str().unwrap().contains("unused"));
+    assert_eq!(report["summary"]["suppressed"], 2);
+
+    // Downgraded findings stay in the report
+    std::fs::write(
+        temp_dir.path().join(".deadcode.yml"),
+        "suppression:\n  action: downgrade\n",
+    )
+    .unwrap();
+    let (stdout, stderr, success) = run_cli(&[path, "--format", "json", "--quiet"]);
+    assert!(success, "stderr: {}", stderr);
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let dump = report["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|issue| issue["message"].as_str().unwrap().contains("dumpState"))
+        .expect("dumpState is downgraded, not excluded");
+    assert_eq!(dump["suppressed_by"], "// searchdeadcode:ignore");
+    assert_eq!(dump["confidence"], "low");
+}
+
+#[test]
+fn test_cli_min_size() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("Small.kt"), "class SmallHelper\n").unwrap();
+    std::fs::write(
+        temp_dir.path().join("Big.kt"),
+        "class BigHelper {\n    fun clamp(value: Int): Int {\n        if (value < 0) return 0\n        return value\n    }\n}\n",
+    )
+    .unwrap();
+
+    let path = temp_dir.path().to_str().unwrap();
+    let (stdout, stderr, success) =
+        run_cli(&[path, "--min-size", "3", "--format", "json", "--quiet"]);
+    assert!(success, "stderr: {}", stderr);
+
+    let start = stdout.find('{').expect("JSON output");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    assert!(!issues
+        .iter()
+        .any(|issue| issue["declaration"]["name"] == "SmallHelper"));
+    let big = issues
+        .iter()
+        .find(|issue| issue["declaration"]["name"] == "BigHelper")
+        .expect("BigHelper is reported");
+    assert_eq!(big["declaration"]["size"]["lines"], 6);
+    assert_eq!(big["declaration"]["size"]["branches"], 1);
+}
+
+// ============================================================================
+// Refactoring Tests
+// ============================================================================
+
+#[test]
+fn test_cli_patch_leaves_tree_untouched() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dead_file = temp_dir.path().join("Dead.kt");
+    std::fs::write(temp_dir.path().join("Main.kt"), "fun main() {\n}\n").unwrap();
+    std::fs::write(&dead_file, "class Dead {\n}\n").unwrap();
+    let patch = temp_dir.path().join("dead.diff");
+
+    let (_, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--dead-files",
+        "--patch",
+        patch.to_str().unwrap(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(dead_file.exists(), "--patch must not edit the working tree");
+
+    let diff = std::fs::read_to_string(&patch).unwrap();
+    assert!(diff.contains("diff --git a/Dead.kt b/Dead.kt"));
+    assert!(diff.contains("deleted file mode"));
+    assert!(!diff.contains("Main.kt"));
+}
+
+#[test]
+fn test_cli_patch_applies_with_git() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let root = temp_dir.path();
+    // The dead function ends the file without a trailing newline
+    std::fs::write(
+        root.join("Main.kt"),
+        "fun main() {\n    keep()\n}\n\nfun keep() {}\n\nfun unused() {\n}",
+    )
+    .unwrap();
+    std::fs::write(root.join("Dead.kt"), "class Dead {\n}\n").unwrap();
+    let patch = root.join("dead.diff");
+
+    let (_, stderr, success) = run_cli(&[
+        root.to_str().unwrap(),
+        "--dead-files",
+        "--min-confidence",
+        "low",
+        "--patch",
+        patch.to_str().unwrap(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    let diff = std::fs::read_to_string(&patch).unwrap();
+    assert!(diff.contains("+++ /dev/null"), "diff: {}", diff);
+    assert!(diff.contains("-fun unused() {\n-}\n\\ No newline at end of file\n"));
+
+    let git_apply = |check: bool| {
+        let mut command = Command::new("git");
+        command.arg("apply");
+        if check {
+            command.arg("--check");
+        }
+        command
+            .arg(&patch)
+            .current_dir(root)
+            .output()
+            .map(|output| (output.status.success(), output.stderr))
+    };
+    let Ok((applies, stderr)) = git_apply(true) else {
+        return; // git unavailable
+    };
+    assert!(
+        applies,
+        "git apply --check: {}\n{}",
+        String::from_utf8_lossy(&stderr),
+        diff
+    );
+
+    assert!(git_apply(false).unwrap().0);
+    assert!(!root.join("Dead.kt").exists());
+    assert_eq!(
+        std::fs::read_to_string(root.join("Main.kt")).unwrap(),
+        "fun main() {\n    keep()\n}\n\nfun keep() {}\n\n"
+    );
+}
+
+#[test]
+fn test_cli_undo_from_journal() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let file = temp_dir.path().join("Legacy.kt");
+    // Recreated with new content after the journaled delete
+    let edited = "class Legacy {\n    fun added() {}\n}\n";
+    std::fs::write(&file, edited).unwrap();
+    let journal = temp_dir.path().join("journal.json");
+    let entry = format!(
+        r#"{{"path": {:?}, "original": "class Legacy\n", "modified": null}}"#,
+        file.to_str().unwrap()
+    );
+    std::fs::write(
+        &journal,
+        format!(r#"{{"version": 1, "files": [{}]}}"#, entry),
+    )
+    .unwrap();
+
+    // The file was edited after the delete: refuse without --force
+    let (_, _, success) = run_cli(&["undo", "--journal", journal.to_str().unwrap()]);
+    assert!(!success);
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), edited);
+
+    std::fs::remove_file(&file).unwrap();
+    let (stdout, stderr, success) =
+        run_cli(&["undo", "--journal", journal.to_str().unwrap(), "Legacy.kt"]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("Restored"));
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "class Legacy\n");
+}
+
+#[test]
+fn test_cli_fix_visibility_patch() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let cart = temp_dir.path().join("Cart.kt");
+    let cart_source = "class Cart {\n    fun total() = price()\n    fun price() = 2\n}\n";
+    std::fs::write(&cart, cart_source).unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "fun main() {\n    println(Cart().total())\n}\n",
+    )
+    .unwrap();
+    let patch = temp_dir.path().join("visibility.diff");
+
+    let (_, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--fix-visibility",
+        "--patch",
+        patch.to_str().unwrap(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert_eq!(std::fs::read_to_string(&cart).unwrap(), cart_source);
+
+    let diff = std::fs::read_to_string(&patch).unwrap();
+    assert!(diff.contains("+    private fun price() = 2"), "{}", diff);
+}
+
+#[test]
+fn test_cli_fix_mutability_patch() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let session = temp_dir.path().join("Session.kt");
+    let session_source = "class Session {\n    private var token = \"abc\"\n    private var count = 0\n\n    fun header(): String {\n        count++\n        return token + count\n    }\n}\n";
+    std::fs::write(&session, session_source).unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "fun main() {\n    println(Session().header())\n}\n",
+    )
+    .unwrap();
+    let patch = temp_dir.path().join("mutability.diff");
+
+    let (_, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--fix-mutability",
+        "--patch",
+        patch.to_str().unwrap(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert_eq!(std::fs::read_to_string(&session).unwrap(), session_source);
+
+    let diff = std::fs::read_to_string(&patch).unwrap();
+    assert!(
+        diff.contains("+    private val token = \"abc\""),
+        "{}",
+        diff
+    );
+    assert!(!diff.contains("private val count"), "{}", diff);
+}
+
+#[test]
+fn test_cli_deprecate_then_delete() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let main = temp_dir.path().join("Main.kt");
+    std::fs::write(&main, "fun main() {}\n\nfun legacy() {}\n").unwrap();
+    let journal = temp_dir.path().join("journal.json");
+
+    let (_, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--deprecate",
+        "--journal",
+        journal.to_str().unwrap(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert_eq!(
+        std::fs::read_to_string(&main).unwrap(),
+        "fun main() {}\n\n@Deprecated(\"Scheduled for removal by searchdeadcode\", \
+         level = DeprecationLevel.WARNING)\nfun legacy() {}\n"
+    );
+    let recorded = std::fs::read_to_string(&journal).unwrap();
+    assert!(recorded.contains("\"deprecations\""), "{}", recorded);
+
+    // Deprecated just now: not old enough to delete yet
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--delete",
+        "--dry-run",
+        "--deprecated-for",
+        "30d",
+        "--journal",
+        journal.to_str().unwrap(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("No dead code to delete"), "{}", stdout);
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--delete",
+        "--dry-run",
+        "--deprecated-for",
+        "0d",
+        "--journal",
+        journal.to_str().unwrap(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("would delete"), "{}", stdout);
+    assert!(stdout.contains("legacy"), "{}", stdout);
+
+    // Without --delete the age means nothing
+    let (_, _, success) = run_cli(&[temp_dir.path().to_str().unwrap(), "--deprecated-for", "30d"]);
+    assert!(!success);
+}
+
+#[test]
+fn test_cli_cluster_deletion_units() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("LegacyScreen.kt"),
+        r#"package com.example
+
+class LegacyScreen {
+    fun render() {
+        formatTitle()
+    }
+}
+
+fun formatTitle() {
+    titleCase()
+}
+
+fun titleCase() {}
+"#,
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        temp_dir.path().to_str().unwrap(),
+        "--cluster",
+        "--delete",
+        "--dry-run",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("Deletion Units"), "stdout: {}", stdout);
+    assert!(stdout.contains("(+2 dependents)"), "stdout: {}", stdout);
+    assert!(
+        stdout
+            .lines()
+            .any(|line| line.contains('└') && line.contains("titleCase")),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_cli_simulate_delete() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        r#"package com.example
+
+fun main() {
+    LegacyScreen().render()
+    sharedUtil()
+}
+
+class LegacyScreen {
+    fun render() {
+        formatTitle()
+        sharedUtil()
+    }
+}
+
+fun formatTitle() {}
+
+fun sharedUtil() {}
+"#,
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        "simulate-delete",
+        "com.example.LegacyScreen",
+        "--path",
+        temp_dir.path().to_str().unwrap(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("become dead code"), "stdout: {}", stdout);
+    assert!(stdout.contains("formatTitle"), "stdout: {}", stdout);
+    assert!(
+        !stdout
+            .lines()
+            .any(|line| line.contains('✗') && line.contains("sharedUtil")),
+        "stdout: {}",
+        stdout
+    );
+
+    let (_, stderr, success) = run_cli(&[
+        "simulate-delete",
+        "com.example.Missing",
+        "--path",
+        temp_dir.path().to_str().unwrap(),
+    ]);
+    assert!(!success);
+    assert!(
+        stderr.contains("No declaration matches"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_cli_api_diff() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Client.kt"),
+        r#"package com.example
+
+class Client {
+    val retries: Int = 3
+
+    fun fetch() {}
+
+    fun debugDump() {}
+}
+
+fun main() {
+    Client().fetch()
+}
+"#,
+    )
+    .unwrap();
+    let dump = temp_dir.path().join("client.api");
+    std::fs::write(
+        &dump,
+        "public final class com/example/Client {\n\
+         \tpublic fun <init> ()V\n\
+         \tpublic final fun close ()V\n\
+         \tpublic final fun fetch ()V\n\
+         \tpublic final fun getRetries ()I\n\
+         }\n\n\
+         public final class com/example/ClientKt {\n\
+         \tpublic static final fun main ()V\n\
+         }\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        "api-diff",
+        "--api",
+        dump.to_str().unwrap(),
+        temp_dir.path().to_str().unwrap(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    // Progress logs go to stderr, leaving only the report on stdout
+    assert!(stderr.contains("Discovering files"), "stderr: {}", stderr);
+    assert!(!stdout.contains("Discovering files"), "stdout: {}", stdout);
+    let section = |title: &str| -> Vec<String> {
+        stdout
+            .split("\n\n")
+            .find(|block| block.contains(title))
+            .map(|block| block.lines().skip(1).map(String::from).collect())
+            .unwrap_or_default()
+    };
+    let accidental = section("missing from the API dump");
+    assert_eq!(accidental.len(), 1, "stdout: {}", stdout);
+    assert!(accidental[0].contains("debugDump"), "stdout: {}", stdout);
+    let unused = section("not used in this workspace");
+    assert_eq!(unused.len(), 1, "stdout: {}", stdout);
+    assert!(unused[0].contains("retries"), "stdout: {}", stdout);
+    let missing = section("without a declaration in the sources");
+    assert_eq!(missing.len(), 1, "stdout: {}", stdout);
+    assert!(
+        missing[0].contains("com.example.Client#close"),
+        "stdout: {}",
+        stdout
+    );
+}
+
 // ============================================================================
 // Combined Options Tests
 // ============================================================================
@@ -329,10 +2844,198 @@ fn test_cli_detect_cycles() {
     println!("Cycle detection output: {}", combined);
 }
 
+#[test]
+fn test_cli_diff_base_reports_changed_files_only() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let repo = temp_dir.path();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .is_ok_and(|output| output.status.success())
+    };
+    std::fs::write(repo.join("Legacy.kt"), "class LegacyHelper\n").unwrap();
+    if !git(&["init", "-q"]) || !git(&["add", "."]) || !git(&["commit", "-q", "-m", "init"]) {
+        return; // git unavailable
+    }
+    std::fs::write(repo.join("Fresh.kt"), "class FreshHelper\n").unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[
+        repo.to_str().unwrap(),
+        "--diff-base",
+        "HEAD",
+        "--format",
+        "json",
+        "--quiet",
+    ]);
+
+    assert!(success, "stderr: {}", stderr);
+    assert!(!stdout.contains("LegacyHelper") && !stdout.contains("Legacy.kt"));
+    if stdout.contains("\"DC0") {
+        assert!(stdout.contains("Fresh"));
+    }
+
+    let (_, _, success) = run_cli(&[repo.to_str().unwrap(), "--diff-base", "no-such-ref"]);
+    assert!(!success, "Unknown refs should be an error");
+}
+
+#[test]
+fn test_cli_prime_cache_and_verify() {
+    let fixtures = fixtures_path().join("kotlin");
+    if !fixtures.exists() {
+        return;
+    }
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let cache_path = temp_dir.path().join("cache.json");
+    let cache_arg = cache_path.to_str().unwrap();
+    let fixtures_arg = fixtures.to_str().unwrap();
+
+    let (_, stderr, success) = run_cli(&["prime-cache", fixtures_arg, "--cache-path", cache_arg]);
+    assert!(success, "prime-cache should succeed, stderr: {}", stderr);
+    assert!(cache_path.exists());
+
+    let (stdout, stderr, success) = run_cli(&[
+        "prime-cache",
+        fixtures_arg,
+        "--cache-path",
+        cache_arg,
+        "--verify",
+    ]);
+    assert!(success, "Fresh cache should verify, stderr: {}", stderr);
+    assert!(stdout.contains("Cache is consistent"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cli_daemon_answers_json_rpc() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("Legacy.kt"), "class Legacy\n").unwrap();
+    let socket = temp_dir.path().join("daemon.sock");
+
+    let mut daemon = Command::new(binary_path())
+        .args(["daemon", "--socket", socket.to_str().unwrap()])
+        .arg(temp_dir.path())
+        .spawn()
+        .expect("Failed to start daemon");
+    let stream = (0..100).find_map(|_| {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        UnixStream::connect(&socket).ok()
+    });
+    let Some(mut stream) = stream else {
+        let _ = daemon.kill();
+        panic!("Daemon did not open its socket");
+    };
+
+    let mut responses = BufReader::new(stream.try_clone().unwrap()).lines();
+    writeln!(
+        stream,
+        r#"{{"jsonrpc": "2.0", "id": 1, "method": "analyze"}}"#
+    )
+    .unwrap();
+    let analyze = responses.next().unwrap().unwrap();
+    assert!(analyze.contains(r#""id":1"#) && analyze.contains(r#""parsed":0"#));
+
+    writeln!(
+        stream,
+        r#"{{"jsonrpc": "2.0", "id": 2, "method": "shutdown"}}"#
+    )
+    .unwrap();
+    assert!(responses.next().unwrap().unwrap().contains(r#""id":2"#));
+    assert!(daemon.wait().unwrap().success());
+    assert!(!socket.exists());
+}
+
+#[test]
+fn test_cli_parallel_jobs_and_memory_budget() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Main.kt"),
+        "package com.example\n\nfun main() {\n    Helper().run()\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("Helper.kt"),
+        "package com.example\n\nclass Helper {\n    fun run() {}\n}\n\nclass Orphan\n",
+    )
+    .unwrap();
+
+    let path = temp_dir.path().to_str().unwrap();
+    let names = |args: &[&str]| {
+        let mut all = vec![path, "--format", "json", "--quiet"];
+        all.extend_from_slice(args);
+        let (stdout, stderr, success) = run_cli(&all);
+        assert!(success, "stderr: {}", stderr);
+        let start = stdout.find('{').expect("JSON output");
+        let report: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+        let mut names: Vec<String> = report["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|issue| issue["declaration"]["name"].as_str().unwrap().to_string())
+            .collect();
+        names.sort();
+        names
+    };
+
+    // A zero budget spills every parsed file's references to disk
+    let sequential = names(&[]);
+    let bounded = names(&["--parallel", "--jobs", "2", "--max-memory-mb", "0"]);
+    assert!(sequential.contains(&"Orphan".to_string()));
+    assert!(!sequential.contains(&"Helper".to_string()));
+    assert_eq!(sequential, bounded);
+}
+
 // ============================================================================
 // Error Handling Tests
 // ============================================================================
 
+#[test]
+fn test_cli_skips_unparsable_files() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("Orphan.kt"), "class Orphan\n").unwrap();
+    std::fs::write(
+        temp_dir.path().join("Broken.kt"),
+        b"class Broken \xff\xfe\n",
+    )
+    .unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[path]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stderr.contains("Skipped Files (1)"), "stderr: {}", stderr);
+    assert!(stderr.contains("Broken.kt - "));
+    assert!(stdout.contains("Orphan"));
+
+    let report_path = temp_dir.path().join("report.json");
+    let (_, stderr, success) = run_cli(&[
+        path,
+        "--format",
+        "json",
+        "--output",
+        report_path.to_str().unwrap(),
+        "--validate-output",
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report_path).unwrap()).unwrap();
+    let skipped = report["skipped_files"].as_array().unwrap();
+    assert_eq!(skipped.len(), 1);
+    assert!(skipped[0]["file"].as_str().unwrap().ends_with("Broken.kt"));
+
+    let (_, stderr, success) = run_cli(&[path, "--strict-parse"]);
+    assert!(
+        !success,
+        "Strict parsing should fail on the unreadable file"
+    );
+    assert!(stderr.contains("Broken.kt"), "stderr: {}", stderr);
+}
+
 #[test]
 fn test_cli_nonexistent_path() {
     let (stdout, stderr, success) = run_cli(&["/nonexistent/path/to/analyze"]);
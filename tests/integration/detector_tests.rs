@@ -3,8 +3,8 @@
 //! These tests verify that each detector correctly identifies dead code patterns.
 
 use searchdeadcode::analysis::detectors::{
-    Detector, RedundantOverrideDetector, UnusedParamDetector, UnusedSealedVariantDetector,
-    WriteOnlyDetector,
+    Detector, RedundantOverrideDetector, UnusedImportDetector, UnusedParamDetector,
+    UnusedSealedVariantDetector, UnusedTypeAliasDetector, WriteOnlyDetector,
 };
 use searchdeadcode::analysis::ReachabilityAnalyzer;
 use searchdeadcode::discovery::{FileType, SourceFile};
@@ -282,6 +282,61 @@ mod redundant_override_tests {
     }
 }
 
+// ============================================================================
+// Unused Typealias and Import Detection Tests
+// ============================================================================
+
+mod type_alias_tests {
+    use super::*;
+
+    #[test]
+    fn test_type_aliases_fixture_parses() {
+        let graph = build_kotlin_graph("type_aliases.kt");
+        let names = get_declaration_names(&graph);
+
+        assert!(names.contains(&"UserAlias".to_string()));
+        assert!(names.contains(&"UserList".to_string()));
+        assert!(names.contains(&"UnusedCallback".to_string()));
+    }
+
+    #[test]
+    fn test_unused_typealias_detected() {
+        let graph = build_kotlin_graph("type_aliases.kt");
+        let detector = UnusedTypeAliasDetector::new();
+        let issues = detector.detect(&graph);
+        let names: HashSet<_> = issues.iter().map(|i| i.declaration.name.as_str()).collect();
+
+        assert!(names.contains("UnusedCallback"));
+        assert!(!names.contains("UserAlias"));
+        assert!(!names.contains("UserList"));
+    }
+
+    #[test]
+    fn test_alias_references_count_toward_aliased_type() {
+        let graph = build_kotlin_graph("type_aliases.kt");
+        let user = graph
+            .declarations()
+            .find(|d| d.name == "User")
+            .expect("User class should be parsed");
+
+        let referenced_from_alias = graph
+            .get_references_to(&user.id)
+            .iter()
+            .any(|(from, _)| from.name == "UserAlias");
+        assert!(referenced_from_alias);
+    }
+
+    #[test]
+    fn test_unused_import_detected() {
+        let graph = build_kotlin_graph("type_aliases.kt");
+        let detector = UnusedImportDetector::new();
+        let issues = detector.detect(&graph);
+        let names: Vec<_> = issues.iter().map(|i| i.declaration.name.as_str()).collect();
+
+        assert_eq!(names, vec!["android.util.Log"]);
+    }
+}
+
 // ============================================================================
 // Unreferenced Code Detection Tests
 // ============================================================================
@@ -1560,3 +1560,70 @@ class Derived : Base() {
         // car elles ajoutent du comportement
     }
 }
+
+// ============================================================================
+// 9. INTEROPÉRABILITÉ JAVA (@JvmName, accesseurs, @JvmStatic)
+// ============================================================================
+
+mod java_interop_tests {
+    use super::*;
+
+    /// Les appels Java utilisent les noms JVM générés par Kotlin
+    #[test]
+    fn test_java_calls_resolve_to_jvm_names() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let kotlin_path = temp_dir.path().join("DateUtils.kt");
+        let java_path = temp_dir.path().join("Caller.java");
+        std::fs::write(
+            &kotlin_path,
+            r#"
+package com.example
+
+object DateUtils {
+    @JvmStatic
+    @JvmName("formatDate")
+    fun format(millis: Long): String = millis.toString()
+
+    var userName: String = ""
+
+    @get:JvmName("isEnabled")
+    var enabled: Boolean = false
+}
+"#,
+        )
+        .expect("Failed to write file");
+        std::fs::write(
+            &java_path,
+            r#"
+package com.example;
+
+public class Caller {
+    public static void main(String[] args) {
+        String date = DateUtils.formatDate(0L);
+        String name = DateUtils.INSTANCE.getUserName();
+        boolean enabled = DateUtils.INSTANCE.isEnabled();
+    }
+}
+"#,
+        )
+        .expect("Failed to write file");
+
+        let mut builder = GraphBuilder::new();
+        for (path, file_type) in [(kotlin_path, FileType::Kotlin), (java_path, FileType::Java)] {
+            builder
+                .process_file(&SourceFile::new(path, file_type))
+                .expect("Failed to process file");
+        }
+        let graph = builder.build();
+        let dead = get_dead_code_names(&graph, "main");
+
+        for name in ["format", "userName", "enabled"] {
+            assert!(
+                !dead.contains(name),
+                "{} est appelé depuis Java via son nom JVM: {:?}",
+                name,
+                dead
+            );
+        }
+    }
+}